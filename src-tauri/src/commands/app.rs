@@ -1,3 +1,5 @@
+use crate::models::settings::AppState;
+use crate::services::window_modes::{self, MonitorInfo};
 use log::debug;
 use lru::LruCache;
 use once_cell::sync::Lazy;
@@ -76,6 +78,75 @@ pub async fn get_window_size(window: Window) -> Result<(u32, u32), String> {
     Ok((size.width, size.height))
 }
 
+/// Pin the main window above other windows, or unpin it, and save the choice.
+/// Shared with the `toggle_always_on_top` hotkey.
+pub(crate) fn pin_main_window(app: &tauri::AppHandle, on_top: bool) -> Result<(), String> {
+    window_modes::set_always_on_top(app, on_top).map_err(|e| e.to_string())?;
+    let state = app.state::<AppState>();
+    let mut settings = state.settings.lock().unwrap().clone();
+    settings.always_on_top = on_top;
+    crate::commands::settings::store_settings(&state, &settings)
+}
+
+/// Pin the main window above other windows, or unpin it. Saved, and applied
+/// again at startup.
+#[command]
+pub fn set_always_on_top(on_top: bool, app_handle: tauri::AppHandle) -> Result<(), String> {
+    pin_main_window(&app_handle, on_top)
+}
+
+/// Shrink the main window into a small always-on-top player in the corner of
+/// its display, with chat hidden. The layout it had is restored by
+/// `exit_mini_mode`.
+#[command]
+pub fn enter_mini_mode(app_handle: tauri::AppHandle) -> Result<(), String> {
+    window_modes::enter_mini(&app_handle).map_err(|e| e.to_string())
+}
+
+#[command]
+pub fn exit_mini_mode(app_handle: tauri::AppHandle) -> Result<(), String> {
+    window_modes::exit_mini(&app_handle).map_err(|e| e.to_string())
+}
+
+/// The connected displays, numbered left to right (see `set_fullscreen`).
+#[command]
+pub fn get_monitors(app_handle: tauri::AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    window_modes::monitors(&app_handle).map_err(|e| e.to_string())
+}
+
+/// Borderless fullscreen on display `monitor_index` from `get_monitors`, or
+/// on the current display when it's omitted.
+#[command]
+pub fn set_fullscreen(
+    monitor_index: Option<usize>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    window_modes::set_fullscreen(&app_handle, monitor_index).map_err(|e| e.to_string())
+}
+
+/// Choose what the window title / taskbar badge shows: `"viewers"` (watched
+/// channel's viewer count), `"mentions"` (unread mentions) or `"off"`, and/or
+/// report the unread mention count. `None` leaves that part unchanged; the
+/// backend keeps the badge current from there.
+#[command]
+pub async fn set_window_badge(
+    app_handle: tauri::AppHandle,
+    source: Option<String>,
+    unread_mentions: Option<u32>,
+) -> Result<(), String> {
+    use crate::services::window_badge::{self, BadgeSource};
+    let source = source.as_deref().map(BadgeSource::parse).transpose()?;
+    window_badge::set(&app_handle, source, unread_mentions).await;
+    Ok(())
+}
+
+/// The main window has painted its first frame: release the deferred startup
+/// work (badge databases, drop campaigns). Safe to call more than once.
+#[command]
+pub fn notify_first_paint() {
+    crate::services::startup::mark_first_paint();
+}
+
 #[command]
 pub async fn calculate_aspect_ratio_size(
     current_width: u32,
@@ -235,6 +306,36 @@ pub fn get_system_info() -> String {
     format!("{} {} ({})", os, arch, family)
 }
 
+/// Features switched off after their Twitch endpoint kept failing, and when
+/// each is retried.
+#[command]
+pub fn get_feature_flags() -> Vec<crate::services::feature_flags::FeatureStatus> {
+    crate::services::feature_flags::status()
+}
+
+/// Switch a feature back on now instead of waiting for its retry.
+#[command]
+pub fn reset_feature_flag(feature: crate::services::feature_flags::Feature) {
+    crate::services::feature_flags::reset(feature);
+}
+
+/// Check what playback, capture and storage depend on outside the app, with
+/// a suggestion for anything missing and a `fix_id` where the app can fix it.
+#[command]
+pub async fn run_dependency_check() -> Vec<crate::services::dependency_check::DependencyCheck> {
+    crate::services::dependency_check::run().await
+}
+
+/// Apply a fix `run_dependency_check` offered; returns the item rechecked.
+#[command]
+pub async fn fix_dependency(
+    fix_id: String,
+) -> Result<crate::services::dependency_check::DependencyCheck, String> {
+    crate::services::dependency_check::fix(&fix_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 /// Fetch an emoji image from CDN and return as base64 data URL
 /// This bypasses the browser's tracking prevention by using Tauri's HTTP client
 #[command]
@@ -0,0 +1,83 @@
+//! App data backup and restore; see services::backup.
+
+use crate::commands::settings::{apply_settings, store_settings};
+use crate::models::settings::{AppState, Settings, SettingsSync};
+use crate::services::backup::{self, BackupManifest, RestoreSummary};
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+
+/// Write a backup zip of settings, favorites and history to `path`. With
+/// `include_tokens`, sign-in tokens go in too, sealed with `password`.
+#[tauri::command]
+pub async fn create_backup(
+    path: String,
+    include_tokens: bool,
+    password: Option<String>,
+) -> Result<BackupManifest, String> {
+    let password = match (include_tokens, password.filter(|p| !p.is_empty())) {
+        (true, None) => return Err("A password is required to back up sign-in tokens".into()),
+        (true, password) => password,
+        (false, _) => None,
+    };
+    tokio::task::spawn_blocking(move || backup::create(&PathBuf::from(path), password.as_deref()))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a backup zip over the current app data. Tokens are restored only
+/// when `password` opens them. Backups never hold the OBS password or the
+/// settings sync Gist token; this machine's are kept, and on a new machine
+/// they have to be entered again. Settings take effect right away; restart
+/// for sign-ins and everything else read at startup.
+#[tauri::command]
+pub async fn restore_backup(
+    path: String,
+    password: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<RestoreSummary, String> {
+    let password = password.filter(|p| !p.is_empty());
+    let summary = tokio::task::spawn_blocking(move || {
+        backup::restore(&PathBuf::from(path), password.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())?;
+
+    if summary
+        .restored
+        .iter()
+        .any(|name| name == "local/settings.json")
+    {
+        let path = crate::services::cache_service::get_app_data_dir()
+            .map_err(|e| e.to_string())?
+            .join("settings.json");
+        let mut settings: Settings = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))?;
+        // Backups leave the OBS password and Gist token out; keep this
+        // machine's.
+        {
+            let current = state.settings.lock().unwrap();
+            if settings.obs.password.is_none() && current.obs.password.is_some() {
+                settings.obs.password = current.obs.password.clone();
+            }
+            if let (
+                SettingsSync::Gist { token, gist_id },
+                SettingsSync::Gist {
+                    token: current_token,
+                    gist_id: current_gist_id,
+                },
+            ) = (&mut settings.settings_sync, &current.settings_sync)
+            {
+                if token.is_empty() && (gist_id.is_none() || gist_id == current_gist_id) {
+                    *token = current_token.clone();
+                }
+            }
+        }
+        store_settings(&state, &settings)?;
+        apply_settings(&app_handle, &state, &settings).await?;
+    }
+    Ok(summary)
+}
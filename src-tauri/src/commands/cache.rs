@@ -1,9 +1,11 @@
+use crate::models::settings::AppState;
 use crate::services::cache_service::{
     add_favorite_emote, clear_all_cache, get_cache_stats, load_badge_cache, load_emote_cache,
     load_emote_from_cache, load_favorite_emotes, remove_favorite_emote, save_badge_cache,
     save_emote_cache, save_emote_to_cache, save_favorite_emotes, CacheStats,
 };
-use tauri::command;
+use crate::services::memory_caches::CacheStat;
+use tauri::{command, State};
 
 #[command]
 pub async fn save_emote_by_id(
@@ -62,6 +64,15 @@ pub async fn get_cache_statistics() -> Result<CacheStats, String> {
     get_cache_stats().map_err(|e| e.to_string())
 }
 
+/// Entry counts and caps of the backend's in-memory caches, plus how many
+/// entries each has evicted since startup.
+#[command]
+pub async fn get_memory_cache_stats(state: State<'_, AppState>) -> Result<Vec<CacheStat>, String> {
+    let mut stats = state.drops_service.lock().await.cache_stats().await;
+    stats.extend(crate::services::chatter_tracker::cache_stats());
+    Ok(stats)
+}
+
 #[command]
 pub async fn save_cosmetics_cache(user_id: String, data: String) -> Result<(), String> {
     save_emote_to_cache(&format!("cosmetics_{}", user_id), &data, 1).map_err(|e| e.to_string())
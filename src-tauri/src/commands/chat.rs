@@ -2,6 +2,7 @@ use crate::models::chat_layout::ChatMessage;
 use crate::models::settings::AppState;
 use crate::services::chat_logger_service::ChatLoggerService;
 use crate::services::chat_service::{ChatService, SendResult};
+use crate::services::chat_tabs::{self, ChatTab};
 use crate::services::irc_service::IrcService;
 use crate::services::providers::{registry, SendCapability, SendOutcome};
 use anyhow::Result;
@@ -356,6 +357,73 @@ pub async fn leave_chat_channel(channel: String, window: tauri::Window) -> Resul
         .map_err(|e| e.to_string())
 }
 
+/// Open `channel`'s chat without a player: joins it for this window and polls
+/// its title and uptime (`stream-info-update`) like a watched channel, but
+/// without crediting watch time. Independent of playback, so starting or
+/// stopping a stream leaves it open. Returns the chat bridge port.
+#[tauri::command]
+pub async fn start_chat_only(
+    channel: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+    app_handle: tauri::AppHandle,
+) -> Result<u16, String> {
+    let port = ChatService::start(&channel, &state, true, false, window.label())
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::services::stream_info_poller::subscribe_chat_only(app_handle, &channel);
+    Ok(port)
+}
+
+/// Close a chat-only session: leaves the chat for this window and stops the
+/// poll unless the channel is also being watched.
+#[tauri::command]
+pub async fn stop_chat_only(channel: String, window: tauri::Window) -> Result<(), String> {
+    crate::services::stream_info_poller::unsubscribe_chat_only(&channel);
+    ChatService::leave_channel(&channel.to_lowercase(), window.label())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_chat_only_sessions() -> Vec<String> {
+    crate::services::stream_info_poller::chat_only_channels()
+}
+
+/// Open `channel` as a chat tab in this window. Tabs share the one chat
+/// connection; their messages arrive batched as `chat-tab-messages`.
+#[tauri::command]
+pub async fn open_chat_tab(
+    channel: String,
+    window: tauri::Window,
+    state: State<'_, AppState>,
+) -> Result<ChatTab, String> {
+    chat_tabs::open(&channel, window.label(), &state)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn close_chat_tab(id: String) -> Result<(), String> {
+    chat_tabs::close(&id).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_chat_tabs() -> Vec<ChatTab> {
+    chat_tabs::list()
+}
+
+/// Ranked completions for `prefix` from `channel`'s emotes and recent
+/// chatters (every joined channel when None); see services::autocomplete.
+#[tauri::command]
+pub fn autocomplete(
+    prefix: String,
+    limit: Option<usize>,
+    channel: Option<String>,
+) -> Vec<crate::services::autocomplete::Completion> {
+    crate::services::autocomplete::autocomplete(channel.as_deref(), &prefix, limit.unwrap_or(10))
+}
+
 #[tauri::command]
 pub async fn start_multi_chat(
     channels: Vec<String>,
@@ -0,0 +1,106 @@
+//! Raw Helix / GQL requests for diagnosing API problems from inside the app.
+//! Gated on the `developer_mode` setting: the command signs requests with the
+//! stored credentials, so it stays off unless the user opted in.
+
+use crate::models::settings::AppState;
+use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::twitch_service::TwitchService;
+use log::info;
+use reqwest::header::AUTHORIZATION;
+use reqwest::Method;
+use serde::Serialize;
+use std::time::Instant;
+use tauri::State;
+
+const HELIX_BASE: &str = "https://api.twitch.tv/helix/";
+const GQL_URL: &str = "https://gql.twitch.tv/gql";
+
+#[derive(Debug, Serialize)]
+pub struct DebugApiResponse {
+    pub status: u16,
+    pub elapsed_ms: u64,
+    /// Parsed JSON when the body is JSON, otherwise the raw text as a string.
+    pub body: serde_json::Value,
+}
+
+/// Send a request as the signed-in user and return the raw response.
+///
+/// `api` is `"helix"` (main account token, `endpoint` is the path and query
+/// after `/helix/`, e.g. `streams?user_login=foo`) or `"gql"` (drops account
+/// token, `body` is the GQL operation or batch; `method` and `endpoint` are
+/// ignored).
+#[tauri::command]
+pub async fn debug_api_request(
+    api: String,
+    method: String,
+    endpoint: String,
+    body: Option<serde_json::Value>,
+    state: State<'_, AppState>,
+) -> Result<DebugApiResponse, String> {
+    if !state.settings.lock().unwrap().developer_mode {
+        return Err("Developer mode is off. Enable it in settings to use the API console.".into());
+    }
+
+    let client = crate::services::http::client().clone();
+    let request = match api.to_lowercase().as_str() {
+        "helix" => {
+            let method = Method::from_bytes(method.to_uppercase().as_bytes())
+                .map_err(|_| format!("Invalid HTTP method: {}", method))?;
+            let token = TwitchService::get_token()
+                .await
+                .map_err(|e| format!("Failed to get token: {}", e))?;
+            let url = format!("{}{}", HELIX_BASE, endpoint.trim_start_matches('/'));
+            info!("[DebugApi] Helix {} {}", method, url);
+            let mut request = client
+                .request(method, url)
+                .header("Client-Id", env!("TWITCH_APP_CLIENT_ID"))
+                .header(AUTHORIZATION, format!("Bearer {}", token));
+            if let Some(body) = &body {
+                request = request.json(body);
+            }
+            request
+        }
+        "gql" => {
+            let body = body.ok_or("GQL requests need a body")?;
+            let token = DropsAuthService::get_token()
+                .await
+                .map_err(|e| format!("Failed to get drops token: {}", e))?;
+            info!("[DebugApi] GQL {}", gql_operation_names(&body).join(", "));
+            client
+                .post(GQL_URL)
+                .header("Client-Id", env!("TWITCH_ANDROID_CLIENT_ID"))
+                .header(AUTHORIZATION, format!("OAuth {}", token))
+                .json(&body)
+        }
+        other => return Err(format!("Unknown API '{}' (expected helix or gql)", other)),
+    };
+
+    let started = Instant::now();
+    let response = request.send().await.map_err(|e| e.to_string())?;
+    let status = response.status().as_u16();
+    let text = response.text().await.map_err(|e| e.to_string())?;
+    let body = serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+
+    Ok(DebugApiResponse {
+        status,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        body,
+    })
+}
+
+/// Operation names in a GQL request or batch, for the log line (the body
+/// itself may carry user data, so it isn't logged).
+fn gql_operation_names(body: &serde_json::Value) -> Vec<String> {
+    let ops = match body.as_array() {
+        Some(batch) => batch.iter().collect(),
+        None => vec![body],
+    };
+    ops.iter()
+        .map(|op| {
+            op["operationName"]
+                .as_str()
+                .unwrap_or("(unnamed)")
+                .to_string()
+        })
+        .collect()
+}
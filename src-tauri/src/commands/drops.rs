@@ -5,6 +5,7 @@ lazy_static::lazy_static! { static ref HTTP_CLIENT: reqwest::Client = crate::ser
 
 use crate::models::drops::*;
 use crate::models::settings::AppState;
+use crate::services::campaign_runs::{self, CampaignSummary};
 use crate::services::drops_auth_service::{DropsAuthService, DropsDeviceCodeInfo};
 use log::debug;
 use tauri::{AppHandle, Emitter, State};
@@ -60,6 +61,39 @@ pub async fn update_drops_settings(
     Ok(())
 }
 
+/// Bring priority games, excluded games and campaign order over from a
+/// TwitchDropsMiner folder, and its drops sign-in when `import_token`. With
+/// `dry_run` nothing changes; the reply lists what would.
+#[tauri::command]
+pub async fn import_twitch_drops_miner(
+    state: State<'_, AppState>,
+    folder: String,
+    import_token: bool,
+    dry_run: bool,
+) -> Result<crate::services::tdm_import::TdmImport, String> {
+    use crate::services::tdm_import;
+
+    let current = state.drops_service.lock().await.get_settings().await;
+    let (mut import, token) = tdm_import::read(std::path::Path::new(folder.trim()), &current)
+        .map_err(|e| e.to_string())?;
+    if dry_run {
+        return Ok(import);
+    }
+    if import_token {
+        if let Some(token) = token {
+            match DropsAuthService::import_token(&token).await {
+                Ok(login) => import.signed_in_as = Some(login),
+                Err(e) => import.warnings.push(format!(
+                    "The TwitchDropsMiner sign-in wasn't imported: {}",
+                    e
+                )),
+            }
+        }
+    }
+    update_drops_settings(tdm_import::merge(&current, &import), state).await?;
+    Ok(import)
+}
+
 #[tauri::command]
 pub async fn get_active_drop_campaigns(
     state: State<'_, AppState>,
@@ -72,6 +106,272 @@ pub async fn get_active_drop_campaigns(
         .map_err(|e| e.to_string())
 }
 
+/// Active campaigns that need a game account linked, with the link URL, so the
+/// UI can offer a one-click fix.
+#[tauri::command]
+pub async fn get_unlinked_campaigns(
+    state: State<'_, AppState>,
+) -> Result<Vec<UnlinkedCampaign>, String> {
+    let drops_service = state.drops_service.lock().await.clone();
+    drops_service
+        .get_unlinked_campaigns()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Live streams with drops enabled, in `game_id` or across every category,
+/// each with the active campaigns it earns toward. `cursor` comes from the
+/// previous page.
+#[tauri::command]
+pub async fn get_drops_enabled_streams(
+    state: State<'_, AppState>,
+    game_id: Option<String>,
+    cursor: Option<String>,
+    limit: Option<u32>,
+) -> Result<DropsStreamsPage, String> {
+    let drops_service = state.drops_service.lock().await.clone();
+    drops_service
+        .get_drops_enabled_streams(
+            game_id.as_deref(),
+            cursor.as_deref(),
+            limit.unwrap_or(30).min(100),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// How the live channels `campaign_id` can be mined on rank under the
+/// current scoring weights, best first, each with its score broken down.
+#[tauri::command]
+pub async fn preview_channel_scores(
+    state: State<'_, AppState>,
+    campaign_id: String,
+) -> Result<Vec<crate::services::channel_scoring::ChannelScore>, String> {
+    let drops_service = state.drops_service.lock().await.clone();
+    let campaign = drops_service
+        .get_all_active_campaigns_cached()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|c| c.id == campaign_id)
+        .ok_or_else(|| format!("No active campaign {}", campaign_id))?;
+    let settings = drops_service.get_settings().await;
+    crate::services::channel_scoring::preview(&settings, &campaign)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// For each active campaign, whether a channel it counts on is live in its
+/// game right now and the biggest one's viewer count, live campaigns first.
+#[tauri::command]
+pub async fn get_campaign_availability(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::services::campaign_availability::CampaignAvailability>, String> {
+    let campaigns = {
+        let drops_service = state.drops_service.lock().await;
+        drops_service
+            .get_all_active_campaigns_cached()
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    Ok(crate::services::campaign_availability::check(campaigns).await)
+}
+
+/// Whether the drops account can earn `campaign_id`, on `channel_login` when
+/// given, and what stands in the way if not.
+#[tauri::command]
+pub async fn check_campaign_eligibility(
+    state: State<'_, AppState>,
+    campaign_id: String,
+    channel_login: Option<String>,
+) -> Result<crate::services::campaign_eligibility::CampaignEligibility, String> {
+    let channel_login = channel_login
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty());
+    crate::services::campaign_eligibility::check(&state, &campaign_id, channel_login.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Host-side args for a `drops.*` hook action. Every action gets `trace_id`,
+/// the mining session's correlation id: a `drops.mine*` action starts a new
+/// session and `drops.stop` ends it. Every action but `drops.stop` gets
+/// `skip_channel_ids`, the blacklisted channels, and with
+/// `prefer_followed_channels` on, `preferred_channel_ids`: followed channels
+/// live with a campaign (the given one, if any), and `score_weights` for
+/// ranking channels. `preferred_languages` and `viewer_range` come along
+/// when the user has set them. An auto-selecting run (no
+/// `campaign_id`) also gets `priority_campaign_ids`, the campaigns holding
+/// wishlisted rewards, and `skip_campaign_ids` listing unlinked campaigns
+/// when `skip_unlinked_campaigns` is on; explicit picks and other args pass
+/// through.
+pub async fn drops_action_args(
+    state: &AppState,
+    action: &str,
+    mut args: serde_json::Value,
+) -> serde_json::Value {
+    use crate::services::trace_ids;
+
+    if !action.starts_with("drops.") {
+        return args;
+    }
+    if args.is_null() {
+        args = serde_json::json!({});
+    }
+    if !args.is_object() {
+        return args;
+    }
+    if action == "drops.stop" {
+        if let Some(trace_id) = trace_ids::end(trace_ids::MINING) {
+            args["trace_id"] = serde_json::json!(trace_id);
+        }
+        return args;
+    }
+    let starts_session = action.starts_with("drops.mine") || action == "drops.run-auto";
+    let trace_id = match trace_ids::active(trace_ids::MINING) {
+        Some(trace_id) if !starts_session => trace_id,
+        _ => trace_ids::begin(trace_ids::MINING),
+    };
+    args["trace_id"] = serde_json::json!(trace_id);
+    if starts_session {
+        if let Some(mode) = crate::services::game_mode::mining_playback(state) {
+            args["playback"] = serde_json::json!(mode);
+        }
+    }
+    args["skip_channel_ids"] = serde_json::json!(crate::services::drops_blacklist::channel_ids());
+    let settings = {
+        let drops_service = state.drops_service.lock().await;
+        drops_service.get_settings().await
+    };
+    args["score_weights"] = serde_json::json!(settings.score_weights);
+    let languages = settings.language_codes();
+    if !languages.is_empty() {
+        args["preferred_languages"] = serde_json::json!(languages);
+    }
+    if settings.min_viewers.is_some() || settings.max_viewers.is_some() {
+        args["viewer_range"] =
+            serde_json::json!({ "min": settings.min_viewers, "max": settings.max_viewers });
+    }
+    let preferred = followed_campaign_channels(state, args["campaign_id"].as_str()).await;
+    if !preferred.is_empty() {
+        args["preferred_channel_ids"] = serde_json::json!(preferred);
+    }
+    if !args["campaign_id"].is_null() {
+        return args;
+    }
+    let wished = crate::services::drops_wishlist::priority_campaign_ids();
+    if !wished.is_empty() {
+        args["priority_campaign_ids"] = serde_json::json!(wished);
+    }
+    let drops_service = state.drops_service.lock().await.clone();
+    if !drops_service.get_settings().await.skip_unlinked_campaigns {
+        return args;
+    }
+    let skip: Vec<String> = drops_service
+        .get_unlinked_campaigns()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| c.campaign_id)
+        .collect();
+    args["skip_campaign_ids"] = serde_json::json!(skip);
+    args
+}
+
+/// Followed channels live right now in a running campaign's game and counting
+/// toward it (`campaign_id` only, when given) and within the viewer bounds,
+/// those in the preferred languages first, then biggest first. Empty unless
+/// `prefer_followed_channels` is on.
+async fn followed_campaign_channels(state: &AppState, campaign_id: Option<&str>) -> Vec<String> {
+    let drops_service = state.drops_service.lock().await.clone();
+    let settings = drops_service.get_settings().await;
+    if !settings.prefer_followed_channels {
+        return Vec::new();
+    }
+    let campaigns = drops_service
+        .get_all_active_campaigns_cached()
+        .await
+        .unwrap_or_default();
+    let now = chrono::Utc::now();
+    let campaigns: Vec<&DropCampaign> = campaigns
+        .iter()
+        .filter(|c| campaign_id.is_none_or(|id| c.id == id))
+        .filter(|c| c.start_at <= now && c.end_at > now)
+        .collect();
+    if campaigns.is_empty() {
+        return Vec::new();
+    }
+    let mut streams =
+        match crate::services::twitch_service::TwitchService::get_followed_streams(state).await {
+            Ok(streams) => streams,
+            Err(e) => {
+                debug!("[Drops] Followed channels unavailable: {}", e);
+                return Vec::new();
+            }
+        };
+    streams.sort_by_key(|s| {
+        (
+            settings.language_rank(s.language.as_deref()),
+            std::cmp::Reverse(s.viewer_count),
+        )
+    });
+    let blacklisted = crate::services::drops_blacklist::channel_ids();
+    streams
+        .into_iter()
+        .filter(|s| !blacklisted.contains(&s.user_id))
+        .filter(|s| settings.viewers_in_range(s.viewer_count))
+        .filter(|s| {
+            campaigns
+                .iter()
+                .any(|c| c.game_id == s.game_id && c.allows_channel(&s.user_id, &s.user_login))
+        })
+        .map(|s| s.user_id)
+        .collect()
+}
+
+/// Channels drops mining is skipping right now, with why and until when.
+#[tauri::command]
+pub fn get_blacklisted_streamers() -> Vec<BlacklistedStreamer> {
+    crate::services::drops_blacklist::list()
+}
+
+/// Let mining use `channel_id` again. False when it wasn't blacklisted.
+#[tauri::command]
+pub fn remove_from_blacklist(channel_id: String) -> bool {
+    crate::services::drops_blacklist::remove(&channel_id)
+}
+
+/// Make mining skip `channel_id` for `duration_secs` (the recovery settings'
+/// blacklist duration when omitted, at most a week).
+#[tauri::command]
+pub async fn blacklist_streamer(
+    state: State<'_, AppState>,
+    channel_id: String,
+    channel_name: Option<String>,
+    duration_secs: Option<u64>,
+) -> Result<BlacklistedStreamer, String> {
+    if channel_id.trim().is_empty() {
+        return Err("channel_id is required".to_string());
+    }
+    let duration_secs = match duration_secs {
+        Some(secs) => secs,
+        None => {
+            let drops_service = state.drops_service.lock().await;
+            drops_service
+                .get_settings()
+                .await
+                .recovery_settings
+                .streamer_blacklist_duration_seconds
+        }
+    };
+    Ok(crate::services::drops_blacklist::add(
+        channel_id.trim(),
+        channel_name.as_deref().unwrap_or(""),
+        BlacklistReason::Manual,
+        duration_secs,
+    ))
+}
+
 /// Re-fetch active campaigns to pick up a fresh `is_account_connected` after the user connects
 /// their account, bypassing the 5-minute cache. Deliberately does NOT run the progress-map sync
 /// (unlike `get_active_drop_campaigns`), so a connection refresh can't snap live automation
@@ -92,8 +392,19 @@ pub async fn refresh_drops_connection_status(
 #[tauri::command]
 pub async fn get_drops_inventory(state: State<'_, AppState>) -> Result<InventoryResponse, String> {
     let drops_service = state.drops_service.lock().await;
-    drops_service
+    let inventory = drops_service
         .fetch_inventory()
+        .await
+        .map_err(|e| e.to_string())?;
+    crate::services::drops_service::DropsService::remember_inventory(&inventory);
+    Ok(inventory)
+}
+
+/// The last-synced inventory with local image paths, so the drops page can
+/// render instantly (and offline) before the live fetch returns.
+#[tauri::command]
+pub async fn get_inventory_offline() -> Result<Option<OfflineInventory>, String> {
+    crate::services::drops_service::DropsService::load_inventory_offline()
         .await
         .map_err(|e| e.to_string())
 }
@@ -183,6 +494,12 @@ pub async fn get_claimed_drops(state: State<'_, AppState>) -> Result<Vec<Claimed
     Ok(drops_service.get_claimed_drops().await)
 }
 
+/// Completion summaries of past campaigns, oldest first.
+#[tauri::command]
+pub async fn get_campaign_history() -> Result<Vec<CampaignSummary>, String> {
+    Ok(campaign_runs::load_history())
+}
+
 #[tauri::command]
 pub async fn get_channel_points_history(
     state: State<'_, AppState>,
@@ -191,6 +508,16 @@ pub async fn get_channel_points_history(
     Ok(drops_service.get_channel_points_history().await)
 }
 
+/// Earned vs spent totals and the balance series for one channel this session.
+#[tauri::command]
+pub async fn get_points_trend(
+    channel_id: String,
+    state: State<'_, AppState>,
+) -> Result<PointsTrend, String> {
+    let drops_service = state.drops_service.lock().await;
+    Ok(drops_service.get_points_trend(&channel_id).await)
+}
+
 #[tauri::command]
 pub async fn get_channel_points_balance(
     channel_id: String,
@@ -373,6 +700,31 @@ pub async fn refresh_followed_channel_points(
     Ok(drops_service.get_all_channel_points_balances().await)
 }
 
+/// The mined channel promoted to real playback, while the drops plugin is
+/// leaving the watch payloads to the player's heartbeat.
+static PROMOTED_CHANNEL: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+/// Hand the mined channel back to the drops plugin's own payloads, once the
+/// player has moved off it or stopped.
+async fn detach_promoted_viewer(state: &State<'_, AppState>, unless_channel: Option<&str>) {
+    let channel_id = {
+        let mut promoted = PROMOTED_CHANNEL.lock().unwrap();
+        if promoted.is_none() || promoted.as_deref() == unless_channel {
+            return;
+        }
+        promoted.take().unwrap()
+    };
+    let args = serde_json::json!({ "channel_id": channel_id });
+    let args = drops_action_args(state, "drops.detach-viewer", args).await;
+    if let Err(e) = state
+        .plugin_host
+        .invoke_action("drops.detach-viewer", args)
+        .await
+    {
+        debug!("[Drops] drops.detach-viewer: {}", e);
+    }
+}
+
 /// Shared active-channel chokepoint: points the parity heartbeat at the
 /// channel now on screen and forwards the matching stream event to plugins.
 /// Every caller of start/update monitoring funnels through here, including
@@ -387,6 +739,7 @@ async fn report_active_channel(state: &State<'_, AppState>, channel_id: &str, lo
         Some(prev) if prev != channel_id => "change",
         _ => return, // same channel re-registered; nothing changed
     };
+    detach_promoted_viewer(state, Some(channel_id)).await;
     let _ = state
         .plugin_host
         .report_stream_event(
@@ -429,6 +782,7 @@ pub async fn stop_drops_monitoring(state: State<'_, AppState>) -> Result<(), Str
         let drops_service = state.drops_service.lock().await;
         drops_service.stop_monitoring().await;
     }
+    detach_promoted_viewer(&state, None).await;
     if let Some(prev) = state.watch_heartbeat.clear_target().await {
         let _ = state
             .plugin_host
@@ -471,6 +825,230 @@ pub async fn report_player_playing(
     Ok(())
 }
 
+/// Preview of the channel the drops plugin reports in `drops.status`, as a
+/// Twitch live thumbnail rather than a second player: nothing is streamed, so
+/// collecting isn't affected. Call again every `refresh_secs` for a new frame.
+/// `None` when nothing is being collected.
+#[tauri::command]
+pub async fn preview_mined_channel(
+    state: State<'_, AppState>,
+) -> Result<Option<MinedChannelPreview>, String> {
+    const REFRESH_SECS: u64 = 30;
+
+    let Some(status) = state.plugin_host.status("drops.status") else {
+        return Ok(None);
+    };
+    if !status["is_mining"].as_bool().unwrap_or(false) {
+        return Ok(None);
+    }
+    let Some(login) = status["channel_login"].as_str().filter(|l| !l.is_empty()) else {
+        return Ok(None);
+    };
+
+    // Twitch's CDN caches previews for a few minutes; the bucketed query
+    // parameter forces a fresh frame once per refresh window.
+    let bucket = chrono::Utc::now().timestamp() as u64 / REFRESH_SECS;
+    Ok(Some(MinedChannelPreview {
+        channel_login: login.to_string(),
+        thumbnail_url: format!(
+            "https://static-cdn.jtvnw.net/previews-ttv/live_user_{}-640x360.jpg?t={}",
+            login.to_lowercase(),
+            bucket
+        ),
+        refresh_secs: REFRESH_SECS,
+    }))
+}
+
+/// Watch the channel being mined for real: the frontend starts playback and
+/// chat on the returned channel, and the drops plugin is sent
+/// `drops.attach-viewer` so it stops its own watch payloads and lets the
+/// player's heartbeat keep the minutes coming. Leaving the channel or
+/// stopping playback sends `drops.detach-viewer` and the plugin takes over
+/// again. Mining carries on either way.
+#[tauri::command]
+pub async fn promote_mining_to_viewing(
+    state: State<'_, AppState>,
+) -> Result<PromotedChannel, String> {
+    let status = state
+        .plugin_host
+        .status("drops.status")
+        .filter(|s| s["is_mining"].as_bool().unwrap_or(false))
+        .ok_or_else(|| "Nothing is being mined".to_string())?;
+    let login = status["channel_login"]
+        .as_str()
+        .filter(|l| !l.is_empty())
+        .ok_or_else(|| "Mining hasn't picked a channel yet".to_string())?;
+    let user = crate::services::twitch_service::TwitchService::get_user_by_login(login)
+        .await
+        .map_err(|e| e.to_string())?;
+    let promoted = PromotedChannel {
+        channel_id: user.id,
+        channel_login: user.login,
+    };
+
+    let args = serde_json::json!({
+        "channel_id": promoted.channel_id,
+        "channel_login": promoted.channel_login,
+    });
+    let args = drops_action_args(&state, "drops.attach-viewer", args).await;
+    match state
+        .plugin_host
+        .invoke_action("drops.attach-viewer", args)
+        .await
+    {
+        Ok(_) => {
+            *PROMOTED_CHANNEL.lock().unwrap() = Some(promoted.channel_id.clone());
+        }
+        // A plugin without the hook keeps its own payloads going; the
+        // player only adds to them.
+        Err(e) => debug!("[Drops] drops.attach-viewer: {}", e),
+    }
+    Ok(promoted)
+}
+
+/// Compact drop progress (drop, percent, ETA, channel) for an overlay; the
+/// local API serves the same at `/v1/overlay`.
+#[tauri::command]
+pub fn get_overlay_state(state: State<'_, AppState>) -> serde_json::Value {
+    crate::services::local_api::overlay_state(state.plugin_host.status("drops.status").as_ref())
+}
+
+/// Move the running mining session onto `channel_id`, or the next-best
+/// eligible channel when None, right away instead of after repeated payload
+/// failures. The drops plugin does the switch (`drops.switch-channel`).
+#[tauri::command]
+pub async fn switch_mining_channel(
+    state: State<'_, AppState>,
+    channel_id: Option<String>,
+) -> Result<(), String> {
+    let mining = state
+        .plugin_host
+        .status("drops.status")
+        .is_some_and(|s| s["is_mining"].as_bool().unwrap_or(false));
+    if !mining {
+        return Err("Nothing is being mined".to_string());
+    }
+    let channel_id = channel_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if let Some(id) = &channel_id {
+        if crate::services::drops_blacklist::channel_ids().contains(id) {
+            return Err("That channel is blacklisted; remove it from the blacklist first".into());
+        }
+    }
+    let args = serde_json::json!({ "channel_id": channel_id });
+    let args = drops_action_args(&state, "drops.switch-channel", args).await;
+    state
+        .plugin_host
+        .invoke_action("drops.switch-channel", args)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Stop sending watch payloads and polling but keep the mining session
+/// (campaign, channel, queue), so `resume_mining` picks up where it left off
+/// without rediscovering. `drops.status` reports `paused` meanwhile.
+#[tauri::command]
+pub async fn pause_mining(state: State<'_, AppState>) -> Result<(), String> {
+    let status = state.plugin_host.status("drops.status");
+    if !status
+        .as_ref()
+        .is_some_and(|s| s["is_mining"].as_bool().unwrap_or(false))
+    {
+        return Err("Nothing is being mined".to_string());
+    }
+    if status.is_some_and(|s| s["paused"].as_bool().unwrap_or(false)) {
+        return Ok(());
+    }
+    let args = drops_action_args(&state, "drops.pause", serde_json::json!({})).await;
+    state
+        .plugin_host
+        .invoke_action("drops.pause", args)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Continue a session paused with `pause_mining`.
+#[tauri::command]
+pub async fn resume_mining(state: State<'_, AppState>) -> Result<(), String> {
+    let paused = state
+        .plugin_host
+        .status("drops.status")
+        .is_some_and(|s| s["paused"].as_bool().unwrap_or(false));
+    if !paused {
+        return Ok(());
+    }
+    let args = drops_action_args(&state, "drops.resume", serde_json::json!({})).await;
+    state
+        .plugin_host
+        .invoke_action("drops.resume", args)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Star the reward `benefit_id` (from an active campaign) so campaigns that
+/// grant it are mined first and announced when they appear.
+#[tauri::command]
+pub async fn add_to_wishlist(
+    state: State<'_, AppState>,
+    benefit_id: String,
+) -> Result<crate::services::drops_wishlist::WishedReward, String> {
+    let campaigns = {
+        let drops_service = state.drops_service.lock().await;
+        drops_service
+            .get_all_active_campaigns_cached()
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    crate::services::drops_wishlist::add(&benefit_id, &campaigns).map_err(|e| e.to_string())
+}
+
+/// Unstar `benefit_id`. False when it wasn't on the wishlist.
+#[tauri::command]
+pub fn remove_from_wishlist(benefit_id: String) -> bool {
+    crate::services::drops_wishlist::remove(&benefit_id)
+}
+
+/// Every wished reward with the active campaigns that grant it and the
+/// progress toward it in each.
+#[tauri::command]
+pub async fn get_wishlist_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::services::drops_wishlist::WishlistStatus>, String> {
+    let campaigns = {
+        let drops_service = state.drops_service.lock().await;
+        drops_service
+            .get_all_active_campaigns_cached()
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    Ok(crate::services::drops_wishlist::status(&campaigns))
+}
+
+/// Watch payloads the drops plugin sent against the minutes Twitch credited
+/// over the last `hours` (default 6, at most 48), per channel and campaign.
+#[tauri::command]
+pub fn get_mining_efficiency(
+    hours: Option<u32>,
+) -> crate::services::mining_audit::MiningEfficiency {
+    crate::services::mining_audit::efficiency(hours.unwrap_or(6))
+}
+
+/// Connection state of the realtime PubSub socket(s): topics, connected flag,
+/// last ping/pong and reconnect count per socket. Empty when nothing is watched.
+#[tauri::command]
+pub async fn get_websocket_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::services::channel_points_websocket_service::PubSubConnectionStatus>, String>
+{
+    let background = state.background_service.lock().await;
+    let ws = background.websocket_service.lock().await;
+    Ok(ws.get_status().await)
+}
+
 // Drops Authentication commands
 #[tauri::command]
 pub async fn start_drops_device_flow() -> Result<DropsDeviceCodeInfo, String> {
@@ -1189,6 +1767,7 @@ pub async fn redeem_channel_reward(
     cost: i32,
     title: String,
     prompt: Option<String>,
+    text_input: Option<String>,
 ) -> Result<crate::models::drops::RedemptionResult, String> {
     use crate::services::drops_auth_service::DropsAuthService;
     use reqwest::Client;
@@ -1212,6 +1791,21 @@ pub async fn redeem_channel_reward(
     let device_id = uuid::Uuid::new_v4().to_string().replace("-", "");
     let session_id = uuid::Uuid::new_v4().to_string().replace("-", "")[..16].to_string();
 
+    let mut input = json!({
+        "channelID": channel_id,
+        "cost": cost,
+        "pricingType": "POINTS",
+        "prompt": prompt.unwrap_or_default(),
+        "rewardID": reward_id,
+        "title": title,
+        "transactionID": transaction_id,
+    });
+    // Rewards with `is_user_input_required` carry the viewer's text here,
+    // the same field the web client fills from its redeem textbox.
+    if let Some(text) = text_input.filter(|t| !t.trim().is_empty()) {
+        input["textInput"] = json!(text);
+    }
+
     let response = client
         .post("https://gql.twitch.tv/gql")
         .header("Client-Id", CLIENT_ID)
@@ -1221,15 +1815,7 @@ pub async fn redeem_channel_reward(
         .json(&json!({
             "operationName": "RedeemCustomReward",
             "variables": {
-                "input": {
-                    "channelID": channel_id,
-                    "cost": cost,
-                    "pricingType": "POINTS",
-                    "prompt": prompt.unwrap_or_default(),
-                    "rewardID": reward_id,
-                    "title": title,
-                    "transactionID": transaction_id,
-                }
+                "input": input
             },
             "extensions": {
                 "persistedQuery": {
@@ -1354,6 +1940,57 @@ pub async fn redeem_channel_reward(
     })
 }
 
+/// Whether `reward_id` is the built-in "Highlight My Message" reward. An
+/// automatic reward's id ends in its type (`<channel id>:SEND_HIGHLIGHTED_MESSAGE`,
+/// or just the type); a custom reward could share the title but never the id.
+fn is_highlight_reward(reward_id: &str) -> bool {
+    reward_id.rsplit(':').next() == Some("SEND_HIGHLIGHTED_MESSAGE")
+}
+
+/// Redeem a reward by id alone, for chat-side redemptions that only know what
+/// the user typed. Re-reads the channel's reward list so the mutation carries
+/// the live cost/title (a stale price is rejected as PROPERTIES_MISMATCH), then
+/// routes "Highlight My Message" to `send_highlighted_message` and everything
+/// else to `redeem_channel_reward` with `input` as the viewer's text.
+#[tauri::command]
+pub async fn redeem_reward(
+    channel_login: String,
+    channel_id: String,
+    reward_id: String,
+    input: Option<String>,
+) -> Result<crate::models::drops::RedemptionResult, String> {
+    let reward = get_channel_rewards(channel_login.clone())
+        .await?
+        .into_iter()
+        .find(|r| r.id == reward_id)
+        .ok_or_else(|| format!("Reward {} not found on {}", reward_id, channel_login))?;
+
+    let input = input.filter(|t| !t.trim().is_empty());
+    if reward.is_user_input_required && input.is_none() {
+        return Ok(crate::models::drops::RedemptionResult {
+            success: false,
+            error_code: Some("INPUT_REQUIRED".to_string()),
+            error_message: Some(format!("\"{}\" needs a message", reward.title)),
+            new_balance: None,
+            unlocked_emote: None,
+        });
+    }
+
+    if is_highlight_reward(&reward.id) {
+        return send_highlighted_message(channel_id, input.unwrap_or_default(), reward.cost).await;
+    }
+
+    redeem_channel_reward(
+        channel_id,
+        reward.id,
+        reward.cost,
+        reward.title,
+        reward.prompt,
+        input,
+    )
+    .await
+}
+
 #[tauri::command]
 pub async fn send_highlighted_message(
     channel_id: String,
@@ -1,6 +1,6 @@
 use crate::services::emote_prefetch_service::{EmotePrefetchService, PrefetchProgress};
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::State;
 
 /// Managed state wrapper for the AFK emote prefetch service.
 pub struct EmotePrefetchServiceState(pub Arc<EmotePrefetchService>);
@@ -14,9 +14,8 @@ pub struct EmotePrefetchServiceState(pub Arc<EmotePrefetchService>);
 pub async fn emote_prefetch_plan(
     tier: String,
     state: State<'_, EmotePrefetchServiceState>,
-    app_handle: AppHandle,
 ) -> Result<PrefetchProgress, String> {
-    state.0.plan(app_handle, tier).await;
+    state.0.plan(tier).await;
     Ok(state.0.get_progress().await)
 }
 
@@ -26,9 +25,8 @@ pub async fn emote_prefetch_plan(
 #[tauri::command]
 pub async fn emote_prefetch_start(
     state: State<'_, EmotePrefetchServiceState>,
-    app_handle: AppHandle,
 ) -> Result<(), String> {
-    state.0.start(app_handle).await;
+    state.0.start().await;
     Ok(())
 }
 
@@ -1,4 +1,8 @@
+use crate::models::settings::AppState;
+use crate::services::emote_frames::{self, PreparedEmote};
 use crate::services::emote_service::{Emote, EmoteService, EmoteSet};
+use futures_util::StreamExt;
+use serde::Deserialize;
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::RwLock;
@@ -35,3 +39,49 @@ pub async fn clear_emote_cache(state: State<'_, EmoteServiceState>) -> Result<()
     service.clear_cache().await;
     Ok(())
 }
+
+#[derive(Debug, Deserialize)]
+pub struct EmoteSource {
+    pub id: String,
+    pub url: String,
+}
+
+/// Animation metadata for `emotes`, plus an optimized WebP at `size` px tall
+/// (default 28) for each oversized one. Requires
+/// `chat_design.optimize_animated_emotes`. Emotes that fail to load come back
+/// without info.
+#[tauri::command]
+pub async fn prepare_animated_emotes(
+    emotes: Vec<EmoteSource>,
+    size: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PreparedEmote>, String> {
+    let enabled = state
+        .settings
+        .lock()
+        .unwrap()
+        .chat_design
+        .optimize_animated_emotes;
+    if !enabled {
+        return Err("Animated emote optimization is off".to_string());
+    }
+    let size = size.unwrap_or(emote_frames::SIZES[0]);
+    let prepared = futures_util::stream::iter(emotes)
+        .map(|emote| async move {
+            match emote_frames::prepare(&emote.id, &emote.url, size).await {
+                Ok(prepared) => prepared,
+                Err(e) => {
+                    log::debug!("[EmoteFrames] {} failed: {}", emote.id, e);
+                    PreparedEmote {
+                        id: emote.id,
+                        info: None,
+                        path: None,
+                    }
+                }
+            }
+        })
+        .buffer_unordered(4)
+        .collect()
+        .await;
+    Ok(prepared)
+}
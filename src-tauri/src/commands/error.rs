@@ -0,0 +1,124 @@
+//! Structured command errors: what went wrong as a `kind` the frontend can act
+//! on (sign in again, retry, explain a permission), whether retrying can help,
+//! and a message fit to show.
+//!
+//! The kind comes from the error's type, never its wording: an `ApiError` the
+//! service raised (see services::api_error) or a reqwest transport failure.
+//! Anything else is `Other`, and its message is shown as is.
+//!
+//! Only commands with a recovery the frontend offers return
+//! `Result<T, CommandError>`: the chat moderation actions, where the UI asks
+//! for a sign-in, explains a missing moderator role, or offers a retry. The
+//! rest return plain strings, which the frontend reads as `other`
+//! (`parseCommandError`); a command moves over when its UI gains a recovery
+//! step, along with typed errors from the service it calls.
+
+pub use crate::services::api_error::ErrorKind;
+use crate::services::api_error::{kind_of, ApiError};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandError {
+    pub kind: ErrorKind,
+    pub retryable: bool,
+    pub user_message: String,
+    /// The underlying error, for logs.
+    pub detail: String,
+}
+
+impl CommandError {
+    fn new(kind: ErrorKind, detail: String) -> Self {
+        let user_message = match kind {
+            ErrorKind::AuthExpired => "Your Twitch sign-in has expired. Sign in again.",
+            ErrorKind::NotAModerator => "You need to be a moderator in this channel to do that.",
+            ErrorKind::Forbidden => "Twitch didn't allow that.",
+            ErrorKind::NotFound => "Twitch couldn't find that.",
+            ErrorKind::Offline => "The channel isn't live.",
+            ErrorKind::Disabled => "The channel has turned that off.",
+            ErrorKind::RateLimited => "Too many requests. Try again in a moment.",
+            ErrorKind::Network => "Couldn't reach Twitch. Check your connection and try again.",
+            ErrorKind::Other => detail.as_str(),
+        }
+        .to_string();
+        CommandError {
+            kind,
+            retryable: matches!(kind, ErrorKind::RateLimited | ErrorKind::Network),
+            user_message,
+            detail,
+        }
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(error: anyhow::Error) -> Self {
+        Self::new(kind_of(&error), format!("{:#}", error))
+    }
+}
+
+impl From<ApiError> for CommandError {
+    fn from(error: ApiError) -> Self {
+        Self::new(error.kind, error.message)
+    }
+}
+
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorKind::Other, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorKind::Other, message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn kinds_come_from_the_error_type_not_its_text() {
+        let rejected = |status, forbidden| -> anyhow::Error {
+            ApiError::helix(status, forbidden, "Twitch rejected delete").into()
+        };
+        let kind = |error: anyhow::Error| CommandError::from(error).kind;
+
+        assert_eq!(
+            kind(rejected(StatusCode::FORBIDDEN, ErrorKind::NotAModerator)),
+            ErrorKind::NotAModerator
+        );
+        assert_eq!(
+            kind(rejected(StatusCode::FORBIDDEN, ErrorKind::Forbidden)),
+            ErrorKind::Forbidden
+        );
+        assert_eq!(
+            kind(rejected(StatusCode::UNAUTHORIZED, ErrorKind::Forbidden)),
+            ErrorKind::AuthExpired
+        );
+        // Context added on the way up doesn't hide the kind.
+        let wrapped = Err::<(), _>(rejected(
+            StatusCode::TOO_MANY_REQUESTS,
+            ErrorKind::Forbidden,
+        ))
+        .context("Couldn't pin")
+        .unwrap_err();
+        let error = CommandError::from(wrapped);
+        assert_eq!(error.kind, ErrorKind::RateLimited);
+        assert!(error.retryable);
+        assert_eq!(error.detail, "Couldn't pin: Twitch rejected delete");
+
+        // Wording alone means nothing.
+        assert_eq!(
+            kind(anyhow::anyhow!(
+                "REAUTH (HTTP 403 Forbidden): not a moderator"
+            )),
+            ErrorKind::Other
+        );
+        let signed_out = CommandError::from(ApiError::auth_expired("Please log in again."));
+        assert_eq!(signed_out.kind, ErrorKind::AuthExpired);
+        assert_eq!(signed_out.detail, "Please log in again.");
+    }
+}
@@ -1,7 +1,7 @@
 use crate::services::eventsub_service::EventSubService;
 use log::debug;
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::State;
 use tokio::sync::RwLock;
 
 // Global EventSub service state
@@ -12,7 +12,6 @@ pub struct EventSubServiceState(pub Arc<RwLock<EventSubService>>);
 #[tauri::command]
 pub async fn connect_eventsub(
     broadcaster_id: String,
-    app_handle: AppHandle,
     state: State<'_, EventSubServiceState>,
 ) -> Result<(), String> {
     let service = state.0.read().await;
@@ -23,7 +22,7 @@ pub async fn connect_eventsub(
     );
 
     service
-        .connect_and_listen(broadcaster_id, app_handle)
+        .connect_and_listen(broadcaster_id)
         .await
         .map_err(|e| format!("Failed to connect to EventSub: {}", e))?;
 
@@ -0,0 +1,170 @@
+//! Quick-launch favorites (`Settings::pinned_channels`): channels pinned in
+//! the user's order, each with the quality it should start at.
+
+use crate::commands::settings::store_settings;
+use crate::models::settings::{AppState, PinnedChannel};
+use crate::models::stream::TwitchStream;
+use crate::services::twitch_service::TwitchService;
+use serde::Serialize;
+use tauri::State;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FavoriteWithStatus {
+    #[serde(flatten)]
+    pub channel: PinnedChannel,
+    /// The pinned quality, or the global default; pass it to `start_stream`.
+    pub start_quality: String,
+    pub live: bool,
+    /// The live stream, while live.
+    pub stream: Option<TwitchStream>,
+}
+
+fn normalize(login: &str) -> String {
+    login.trim().trim_start_matches('@').to_lowercase()
+}
+
+/// Order `pinned` as `logins`; favorites missing from `logins` keep their
+/// relative order after the listed ones, and unknown logins are ignored.
+fn reorder(pinned: &mut Vec<PinnedChannel>, logins: &[String]) {
+    let rank = |c: &PinnedChannel| {
+        logins
+            .iter()
+            .position(|l| normalize(l) == c.login)
+            .unwrap_or(usize::MAX)
+    };
+    pinned.sort_by_key(rank);
+}
+
+/// Pin `login` (appended at the end) or unpin it. Pinning a channel that's
+/// already pinned just updates its quality. Returns the favorites in order.
+#[tauri::command]
+pub async fn pin_channel(
+    login: String,
+    pinned: bool,
+    quality: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PinnedChannel>, String> {
+    let login = normalize(&login);
+    if login.is_empty() {
+        return Err("Login is empty".into());
+    }
+    let quality = quality.filter(|q| !q.trim().is_empty());
+
+    let already = state
+        .settings
+        .lock()
+        .unwrap()
+        .pinned_channels
+        .iter()
+        .any(|c| c.login == login);
+    let user = if pinned && !already {
+        Some(
+            TwitchService::get_user_by_login(&login)
+                .await
+                .map_err(|e| format!("Couldn't find {}: {}", login, e))?,
+        )
+    } else {
+        None
+    };
+
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        if !pinned {
+            settings.pinned_channels.retain(|c| c.login != login);
+        } else if let Some(existing) = settings
+            .pinned_channels
+            .iter_mut()
+            .find(|c| c.login == login)
+        {
+            existing.quality = quality;
+        } else if let Some(user) = user {
+            settings.pinned_channels.push(PinnedChannel {
+                user_id: user.id,
+                login: user.login.to_lowercase(),
+                display_name: user.display_name,
+                profile_image_url: user.profile_image_url,
+                quality,
+                pinned_at: chrono::Utc::now().timestamp(),
+            });
+        }
+        settings.clone()
+    };
+    store_settings(&state, &settings)?;
+    Ok(settings.pinned_channels)
+}
+
+/// Set the pinned order: `logins` first, in that order.
+#[tauri::command]
+pub async fn reorder_favorites(
+    logins: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PinnedChannel>, String> {
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        reorder(&mut settings.pinned_channels, &logins);
+        settings.clone()
+    };
+    store_settings(&state, &settings)?;
+    Ok(settings.pinned_channels)
+}
+
+/// The favorites in pinned order with their live status, ready to launch.
+#[tauri::command]
+pub async fn get_favorites_with_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<FavoriteWithStatus>, String> {
+    let (pinned, default_quality) = {
+        let settings = state.settings.lock().unwrap();
+        (settings.pinned_channels.clone(), settings.quality.clone())
+    };
+    if pinned.is_empty() {
+        return Ok(Vec::new());
+    }
+    let logins: Vec<String> = pinned.iter().map(|c| c.login.clone()).collect();
+    let live = TwitchService::check_streams_online(&logins)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(pinned
+        .into_iter()
+        .map(|channel| {
+            let stream = live
+                .iter()
+                .find(|s| s.user_id == channel.user_id || s.user_login == channel.login)
+                .cloned();
+            FavoriteWithStatus {
+                start_quality: channel
+                    .quality
+                    .clone()
+                    .unwrap_or_else(|| default_quality.clone()),
+                live: stream.is_some(),
+                stream,
+                channel,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pinned(login: &str) -> PinnedChannel {
+        PinnedChannel {
+            user_id: login.to_string(),
+            login: login.to_string(),
+            display_name: login.to_string(),
+            profile_image_url: None,
+            quality: None,
+            pinned_at: 0,
+        }
+    }
+
+    #[test]
+    fn reorder_puts_listed_first_and_keeps_the_rest() {
+        let mut list = vec![pinned("a"), pinned("b"), pinned("c"), pinned("d")];
+        reorder(&mut list, &["C".into(), "@a".into(), "zzz".into()]);
+        let order: Vec<&str> = list.iter().map(|c| c.login.as_str()).collect();
+        assert_eq!(order, ["c", "a", "b", "d"]);
+    }
+}
@@ -1,5 +1,5 @@
-use crate::services::log_service::{ActivityEntry, LogEntry, LogLevel, LogService};
-use tauri::command;
+use crate::services::log_service::{ActivityEntry, ErrorRecord, LogEntry, LogLevel, LogService};
+use tauri::{command, AppHandle};
 
 #[command]
 pub async fn log_message(
@@ -61,3 +61,28 @@ pub async fn get_recent_activity() -> Result<Vec<ActivityEntry>, String> {
 pub async fn clear_logs() -> Result<(), String> {
     LogService::clear_logs().await.map_err(|e| e.to_string())
 }
+
+/// Captured errors (panics and error logs) from errors.json, oldest first.
+#[command]
+pub fn get_error_records() -> Vec<ErrorRecord> {
+    LogService::get_error_records()
+}
+
+/// Open a pre-filled GitHub new-issue page (version, OS, recent errors, with
+/// credentials scrubbed) in the browser. Returns the URL so the UI can offer
+/// to copy it if the browser didn't open.
+#[command]
+pub async fn create_issue_draft(
+    app_handle: AppHandle,
+    title: Option<String>,
+    description: Option<String>,
+) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let url = LogService::issue_draft_url(title, description);
+    app_handle
+        .opener()
+        .open_url(url.clone(), None::<String>)
+        .map_err(|e| format!("Failed to open issue page: {}", e))?;
+    Ok(url)
+}
@@ -2,6 +2,7 @@ pub mod accounts;
 pub mod announcements;
 pub mod app;
 pub mod automation;
+pub mod backup;
 pub mod badge_metadata;
 pub mod badge_service;
 pub mod badges;
@@ -12,13 +13,16 @@ pub mod chat;
 pub mod chat_identity;
 pub mod components;
 pub mod cosmetics_cache;
+pub mod debug_api;
 pub mod diagnostic_logging;
 pub mod discord;
 pub mod drops;
 pub mod emoji;
 pub mod emote_prefetch;
 pub mod emotes;
+pub mod error;
 pub mod eventsub;
+pub mod favorites;
 pub mod hype_train;
 pub mod identity;
 pub mod justlog;
@@ -28,12 +32,14 @@ pub mod logs;
 pub mod mod_log_storage;
 pub mod modroom;
 pub mod multi_nook;
+pub mod obs;
 pub mod plugins;
 pub mod profile_cache;
 pub mod resub;
 pub mod screen_capture;
 pub mod session;
 pub mod settings;
+pub mod setup;
 pub mod seventv;
 pub mod song_id;
 pub mod seventv_cosmetics;
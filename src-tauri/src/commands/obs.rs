@@ -0,0 +1,30 @@
+//! OBS Studio control (obs-websocket v5); see services::obs_service.
+
+use crate::models::settings::{AppState, ObsSettings};
+use crate::services::obs_service::{self, ObsStreamStatus};
+use tauri::State;
+
+fn obs_settings(state: &AppState) -> ObsSettings {
+    state.settings.lock().unwrap().obs.clone()
+}
+
+#[tauri::command]
+pub async fn obs_switch_scene(scene: String, state: State<'_, AppState>) -> Result<(), String> {
+    obs_service::switch_scene(&obs_settings(&state), &scene)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn obs_get_scenes(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    obs_service::get_scenes(&obs_settings(&state))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn obs_get_stream_status(state: State<'_, AppState>) -> Result<ObsStreamStatus, String> {
+    obs_service::get_stream_status(&obs_settings(&state))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -218,6 +218,7 @@ pub async fn plugins_invoke_action(
     args: Value,
     state: State<'_, AppState>,
 ) -> Result<Value, String> {
+    let args = crate::commands::drops::drops_action_args(&state, &action, args).await;
     state
         .plugin_host
         .invoke_action(&action, args)
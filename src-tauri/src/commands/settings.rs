@@ -1,6 +1,8 @@
-use crate::models::settings::{AppState, Settings};
-use crate::services::cache_service;
+use crate::models::settings::{AppState, PinnedChannel, Settings};
+use crate::services::audio_cues::{self, WavInfo};
 use crate::services::live_notification_service::LiveNotification;
+use crate::services::twitch_service::TwitchService;
+use crate::services::{cache_service, client_import, settings_sync, whats_new};
 use log::debug;
 use regex::Regex;
 use std::fs;
@@ -23,9 +25,10 @@ pub async fn load_settings(state: State<'_, AppState>) -> Result<Settings, Strin
 pub async fn save_settings(
     mut settings: Settings,
     state: State<'_, AppState>,
+    app_handle: AppHandle,
 ) -> Result<(), String> {
     {
-        let mut state_settings = state.settings.lock().unwrap();
+        let state_settings = state.settings.lock().unwrap();
         // `drops` is owned by the drops service: it's written only through
         // update_drops_settings (the plugin's Autopilot panel writes through that
         // path too), which keeps state.settings.drops authoritative. A frontend
@@ -35,29 +38,103 @@ pub async fn save_settings(
         // priority targets the moment any unrelated setting is saved (e.g. a
         // notifications toggle). Keep the backend's copy instead.
         settings.drops = state_settings.drops.clone();
-        *state_settings = settings.clone();
+        // Same for global hotkeys: set_hotkey registers them with the OS and is
+        // their only writer, so a stale frontend copy must not unbind them.
+        settings.global_hotkeys = state_settings.global_hotkeys.clone();
+        // And the friend list, written by set_chat_friend.
+        settings.chat_friends = state_settings.chat_friends.clone();
+        // And the pinned favorites, written by commands::favorites.
+        settings.pinned_channels = state_settings.pinned_channels.clone();
+        // And the wizard's step results, written by commands::setup.
+        settings.first_run_state = state_settings.first_run_state.clone();
+        // And the window pin, written by set_always_on_top.
+        settings.always_on_top = state_settings.always_on_top;
+        audio_cues::sync_mute(&state_settings, &mut settings);
     }
 
-    // Save to our custom location in the same directory as cache
-    let settings_path = get_settings_path()?;
-    let json = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    store_settings(&state, &settings)?;
+    apply_settings(&app_handle, &state, &settings).await
+}
+
+/// Bind a system-wide hotkey action (toggle_mute, toggle_stream,
+/// toggle_window, toggle_mining, toggle_mini_mode,
+/// toggle_always_on_top) to an accelerator like `CmdOrCtrl+Shift+M`,
+/// or clear it with `null`. Errors name the conflict: an action already using
+/// the keys, or another application holding them. Saved on success.
+#[tauri::command]
+pub async fn set_hotkey(
+    action: String,
+    accelerator: Option<String>,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let accelerator = accelerator.filter(|a| !a.trim().is_empty());
+    crate::services::global_hotkeys::set(&app_handle, &action, accelerator.as_deref())?;
+
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        match accelerator {
+            Some(a) => settings.global_hotkeys.insert(action, a),
+            None => settings.global_hotkeys.remove(&action),
+        };
+        settings.clone()
+    };
+    store_settings(&state, &settings)
+}
 
-    fs::write(&settings_path, json).map_err(|e| format!("Failed to write settings file: {}", e))?;
+/// Check a sound picked for an audio cue: `None` for a built-in sound, the
+/// WAV's format for a file. Errors say why the file won't play.
+#[tauri::command]
+pub async fn check_audio_cue_sound(sound: String) -> Result<Option<WavInfo>, String> {
+    audio_cues::check_sound(sound.trim()).map_err(|e| e.to_string())
+}
 
-    Ok(())
+#[tauri::command]
+pub async fn get_chat_friends(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    Ok(state.settings.lock().unwrap().chat_friends.clone())
+}
+
+/// Mark (or unmark) `login` as a friend. Friends raise `friend-active-in-chat`
+/// on their first message in a chat. Saved immediately.
+#[tauri::command]
+pub async fn set_chat_friend(
+    login: String,
+    friend: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let login = login.trim().trim_start_matches('@').to_lowercase();
+    if login.is_empty() {
+        return Err("Login is empty".into());
+    }
+
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        settings.chat_friends.retain(|f| *f != login);
+        if friend {
+            settings.chat_friends.push(login);
+            settings.chat_friends.sort();
+        }
+        settings.clone()
+    };
+    crate::services::chatter_tracker::set_friends(&settings.chat_friends);
+
+    store_settings(&state, &settings)?;
+    Ok(settings.chat_friends)
 }
 
 /// Top-level keys tied to *this machine's* session, never written into a backup
 /// and never pulled out of one on import: which Twitch accounts are signed in,
-/// the active account, the onboarding flag, and the last-seen version. Everything
-/// else (theme, chat design, keybindings, highlights, custom commands, custom
-/// themes, ...) is a portable preference and is included.
+/// the active account, the onboarding flag, the last-seen version, and where
+/// (and with which token) settings sync. Everything else (theme, chat design,
+/// keybindings, highlights, custom commands, custom themes, ...) is a portable
+/// preference and is included.
 const NON_PORTABLE_KEYS: &[&str] = &[
     "accounts",
     "current_account",
     "setup_complete",
+    "first_run_state",
     "last_seen_version",
+    "settings_sync",
 ];
 
 /// Absolute path of the folder that holds settings.json (alongside caches/logs).
@@ -81,46 +158,77 @@ pub async fn open_settings_folder(app: AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to open settings folder: {}", e))
 }
 
-/// Write the user's portable preferences to `path` (chosen via a save dialog on
-/// the frontend). Session/login keys are stripped so a backup carries pure
-/// app/UI customization and no account info.
+/// Open the script-hooks folder (created if missing). Hook programs must live
+/// here; see services::script_hooks.
 #[tauri::command]
-pub async fn export_settings(path: String, state: State<'_, AppState>) -> Result<(), String> {
-    let settings = { state.settings.lock().unwrap().clone() };
-    let mut value = serde_json::to_value(&settings)
+pub async fn open_script_hooks_folder(app: AppHandle) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+    let dir = crate::services::script_hooks::hooks_dir()
+        .map_err(|e| format!("Failed to create hooks folder: {}", e))?;
+    app.opener()
+        .open_path(dir.to_string_lossy().to_string(), None::<String>)
+        .map_err(|e| format!("Failed to open hooks folder: {}", e))
+}
+
+/// Recent outbound webhook deliveries, newest first, optionally for one
+/// endpoint. Kept in memory for the session.
+#[tauri::command]
+pub async fn get_webhook_deliveries(
+    webhook_id: Option<String>,
+) -> Result<Vec<crate::services::webhooks::WebhookDelivery>, String> {
+    Ok(crate::services::webhooks::deliveries(webhook_id.as_deref()))
+}
+
+/// Connection details for the local control API: whether it's listening,
+/// its base URL and the token clients must send.
+#[tauri::command]
+pub async fn get_local_api_info(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
+    let settings = state.settings.lock().unwrap().local_api.clone();
+    let token = crate::services::local_api::token()
+        .map_err(|e| format!("Failed to read local API token: {}", e))?;
+    let port = crate::services::local_api::running_port().await;
+    Ok(serde_json::json!({
+        "enabled": settings.enabled,
+        "running": port.is_some(),
+        "base_url": format!("http://127.0.0.1:{}/v1", port.unwrap_or(settings.port)),
+        "token": token,
+        "token_header": "X-StreamNook-Token",
+    }))
+}
+
+/// The user's portable preferences as JSON: `settings` minus session/login
+/// keys and the OBS password. What `export_settings` writes and settings sync
+/// pushes.
+pub(crate) fn portable_settings(settings: &Settings) -> Result<serde_json::Value, String> {
+    let mut value = serde_json::to_value(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
     if let Some(obj) = value.as_object_mut() {
         for key in NON_PORTABLE_KEYS {
             obj.remove(*key);
         }
+        // The OBS connection travels, its password doesn't.
+        if let Some(obs) = obj.get_mut("obs").and_then(|o| o.as_object_mut()) {
+            obs.remove("password");
+        }
     }
-    let json = serde_json::to_string_pretty(&value)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    fs::write(&path, json).map_err(|e| format!("Failed to write backup file: {}", e))?;
-    Ok(())
+    Ok(value)
 }
 
-/// Apply a previously exported backup at `path`. Portable preferences from the
-/// file overwrite the current ones; this machine's session/login keys are kept
-/// as-is (a backup carries none anyway). The merged result is validated by
-/// round-tripping through the typed Settings struct *before* anything is written,
-/// so an unrelated or malformed file fails cleanly without disturbing live
-/// settings. On success the in-memory state and settings.json are both updated;
-/// the frontend reloads to re-apply everything.
-#[tauri::command]
-pub async fn import_settings(path: String, state: State<'_, AppState>) -> Result<(), String> {
-    let contents =
-        fs::read_to_string(&path).map_err(|e| format!("Couldn't read that file: {}", e))?;
-    let incoming: serde_json::Value = serde_json::from_str(&contents)
-        .map_err(|_| "That file isn't a valid StreamNook settings backup.".to_string())?;
+/// Overlay the portable keys of `incoming` (an export) onto `current`. This
+/// machine's session/login keys and OBS password are kept. The result is
+/// validated by round-tripping through the typed Settings struct, so an
+/// unrelated or malformed file fails cleanly.
+pub(crate) fn merge_portable(
+    current: &Settings,
+    incoming: &serde_json::Value,
+) -> Result<Settings, String> {
     let incoming_obj = incoming
         .as_object()
         .ok_or_else(|| "That file isn't a valid StreamNook settings backup.".to_string())?;
 
     // Start from the live settings so session/login keys survive, then overlay
     // every portable key the backup provides.
-    let current = { state.settings.lock().unwrap().clone() };
-    let mut merged = serde_json::to_value(&current)
+    let mut merged = serde_json::to_value(current)
         .map_err(|e| format!("Failed to read current settings: {}", e))?;
     {
         let merged_obj = merged
@@ -134,21 +242,210 @@ pub async fn import_settings(path: String, state: State<'_, AppState>) -> Result
         }
     }
 
-    let imported: Settings = serde_json::from_value(merged)
+    let mut imported: Settings = serde_json::from_value(merged)
         .map_err(|e| format!("That backup isn't compatible with this version: {}", e))?;
+    // Exports strip the OBS password; keep this machine's.
+    if imported.obs.password.is_none() {
+        imported.obs.password = current.obs.password.clone();
+    }
+    Ok(imported)
+}
 
+/// Replace the live settings with `settings` and write settings.json.
+pub(crate) fn store_settings(state: &AppState, settings: &Settings) -> Result<(), String> {
     {
         let mut state_settings = state.settings.lock().unwrap();
-        *state_settings = imported.clone();
+        *state_settings = settings.clone();
     }
     let settings_path = get_settings_path()?;
-    let json = serde_json::to_string_pretty(&imported)
+    let json = serde_json::to_string_pretty(settings)
         .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    fs::write(&settings_path, json).map_err(|e| format!("Failed to write settings file: {}", e))?;
+    fs::write(&settings_path, json).map_err(|e| format!("Failed to write settings file: {}", e))
+}
+
+/// Bring the running services in line with `settings`: everything that holds
+/// its own copy of a setting rather than reading `AppState` on each use. Run
+/// after `store_settings` wherever the settings are replaced wholesale (save,
+/// backup restore, sync pull).
+pub(crate) async fn apply_settings(
+    app: &AppHandle,
+    state: &AppState,
+    settings: &Settings,
+) -> Result<(), String> {
+    crate::services::power_saver::set_enabled(settings.power_saver);
+    crate::services::download_pool::set_concurrency(settings.prefetch_concurrency);
+    crate::services::ad_detect::set_mute_during_ads(settings.video_player.mute_during_ads);
+    crate::services::chatter_tracker::set_friends(&settings.chat_friends);
+    crate::services::global_hotkeys::register_saved(app, &settings.global_hotkeys);
+    state
+        .drops_service
+        .lock()
+        .await
+        .update_settings(settings.drops.clone())
+        .await;
+    crate::services::local_api::apply(app, &settings.local_api)
+        .await
+        .map_err(|e| format!("Settings saved, but the local API didn't start: {}", e))
+}
 
+/// Write the user's portable preferences to `path` (chosen via a save dialog on
+/// the frontend). Session/login keys are stripped so a backup carries pure
+/// app/UI customization and no account info.
+#[tauri::command]
+pub async fn export_settings(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let settings = { state.settings.lock().unwrap().clone() };
+    let value = portable_settings(&settings)?;
+    let json = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write backup file: {}", e))?;
     Ok(())
 }
 
+/// Apply a previously exported backup at `path`. Portable preferences from the
+/// file overwrite the current ones; this machine's session/login keys are kept
+/// as-is (a backup carries none anyway). The merge is validated before
+/// anything is written (see `merge_portable`). On success the in-memory state
+/// and settings.json are both updated; the frontend reloads to re-apply
+/// everything.
+#[tauri::command]
+pub async fn import_settings(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let contents =
+        fs::read_to_string(&path).map_err(|e| format!("Couldn't read that file: {}", e))?;
+    let incoming: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|_| "That file isn't a valid StreamNook settings backup.".to_string())?;
+
+    let current = { state.settings.lock().unwrap().clone() };
+    let imported = merge_portable(&current, &incoming)?;
+    store_settings(&state, &imported)
+}
+
+/// Bring highlights and channels over from Chatterino or Streamlink Twitch
+/// GUI (`folder` is the client's settings or data folder). Channels are
+/// pinned, at the client's quality when it had one. With `dry_run` nothing
+/// changes and the reply lists what would be added; otherwise it lists what
+/// was, and the frontend reloads settings.
+#[tauri::command]
+pub async fn import_from_client(
+    source: client_import::ImportSource,
+    folder: String,
+    dry_run: bool,
+    state: State<'_, AppState>,
+) -> Result<client_import::ClientImport, String> {
+    let current = { state.settings.lock().unwrap().clone() };
+    let mut import = client_import::read(source, std::path::Path::new(folder.trim()), &current)
+        .map_err(|e| e.to_string())?;
+    if dry_run {
+        return Ok(import);
+    }
+
+    let mut pins = Vec::new();
+    let mut channels = Vec::new();
+    for channel in std::mem::take(&mut import.channels) {
+        let user = match (&channel.login, &channel.user_id) {
+            (Some(login), _) => TwitchService::get_user_by_login(login).await,
+            (None, Some(id)) => TwitchService::get_user_by_id(id).await,
+            (None, None) => continue,
+        };
+        match user {
+            Ok(user) => {
+                pins.push((user, channel.quality.clone()));
+                channels.push(channel);
+            }
+            Err(e) => import.skipped.push(format!(
+                "Channel {}: {}",
+                channel
+                    .login
+                    .as_deref()
+                    .or(channel.user_id.as_deref())
+                    .unwrap_or_default(),
+                e
+            )),
+        }
+    }
+    import.channels = channels;
+
+    let mut settings = { state.settings.lock().unwrap().clone() };
+    client_import::apply_highlights(&mut settings, &import);
+    for (user, quality) in pins {
+        let login = user.login.to_lowercase();
+        match settings
+            .pinned_channels
+            .iter_mut()
+            .find(|c| c.login == login)
+        {
+            Some(pinned) => pinned.quality = quality.or(pinned.quality.take()),
+            None => settings.pinned_channels.push(PinnedChannel {
+                user_id: user.id,
+                login,
+                display_name: user.display_name,
+                profile_image_url: user.profile_image_url,
+                quality,
+                pinned_at: chrono::Utc::now().timestamp(),
+            }),
+        }
+    }
+    store_settings(&state, &settings)?;
+    Ok(import)
+}
+
+/// Compare the local portable settings with the synced copy (see
+/// services::settings_sync).
+#[tauri::command]
+pub async fn get_settings_sync_status(
+    state: State<'_, AppState>,
+) -> Result<settings_sync::SyncStatus, String> {
+    let settings = { state.settings.lock().unwrap().clone() };
+    let local = portable_settings(&settings)?;
+    settings_sync::status(&settings.settings_sync, &local)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Upload this machine's portable settings. Refuses on a conflict unless
+/// `force`.
+#[tauri::command]
+pub async fn push_settings_sync(force: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let settings = { state.settings.lock().unwrap().clone() };
+    let local = portable_settings(&settings)?;
+    let updated = settings_sync::push(&settings.settings_sync, &local, force)
+        .await
+        .map_err(|e| e.to_string())?;
+    // The first push to a Gist creates it; remember its id.
+    if let Some(config) = updated {
+        let mut settings = { state.settings.lock().unwrap().clone() };
+        settings.settings_sync = config;
+        store_settings(&state, &settings)?;
+    }
+    Ok(())
+}
+
+/// Replace this machine's portable settings with the synced ones, like
+/// `import_settings`. Refuses on a conflict unless `force`. The frontend
+/// reloads to re-apply everything.
+#[tauri::command]
+pub async fn pull_settings_sync(
+    force: bool,
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    let current = { state.settings.lock().unwrap().clone() };
+    let local = portable_settings(&current)?;
+    let mut pulled = None;
+    settings_sync::pull(&current.settings_sync, &local, force, |remote| {
+        let merged = merge_portable(&current, remote).map_err(|e| anyhow::anyhow!(e))?;
+        store_settings(&state, &merged).map_err(|e| anyhow::anyhow!(e))?;
+        let portable = portable_settings(&merged).map_err(|e| anyhow::anyhow!(e))?;
+        pulled = Some(merged);
+        Ok(portable)
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    match pulled {
+        Some(settings) => apply_settings(&app_handle, &state, &settings).await,
+        None => Ok(()),
+    }
+}
+
 #[tauri::command]
 pub async fn send_test_notification(
     app_handle: AppHandle,
@@ -317,98 +614,42 @@ pub struct ReleaseNotes {
     pub name: String,
     pub body: String,
     pub published_at: String,
+    /// The bullets of the release's Features and Fixes sections.
+    pub features: Vec<String>,
+    pub fixes: Vec<String>,
 }
 
 #[tauri::command]
 pub async fn get_release_notes(version: Option<String>) -> Result<ReleaseNotes, String> {
-    let client = crate::services::http::client().clone();
-
-    // Fetch the raw CHANGELOG.md from the GitHub repo
-    let url = "https://raw.githubusercontent.com/winters27/StreamNook/main/CHANGELOG.md";
-
-    let response = client
-        .get(url)
-        .header("User-Agent", "StreamNook")
-        .send()
+    let changelog = whats_new::fetch_changelog()
         .await
         .map_err(|e| format!("Failed to fetch changelog: {}", e))?;
 
-    if !response.status().is_success() {
-        return Err(format!(
-            "Failed to fetch changelog: HTTP {}",
-            response.status()
-        ));
-    }
-
-    let changelog_content = response
-        .text()
-        .await
-        .map_err(|e| format!("Failed to read changelog: {}", e))?;
-
-    // Determine which version to look for
-    let target_version = match version {
-        Some(v) => v,
-        None => {
-            // If no version specified, use the current app version
-            env!("CARGO_PKG_VERSION").to_string()
-        }
-    };
-
-    // Parse the changelog to find the specific version section
-    // Version headers look like: ## [2.9.0] - 2025-11-26
-    let version_header_regex =
-        Regex::new(r"##\s*\[?v?(\d+\.\d+\.\d+)\]?\s*-?\s*(\d{4}-\d{2}-\d{2})?")
-            .map_err(|e| format!("Failed to create regex: {}", e))?;
-
-    let lines: Vec<&str> = changelog_content.lines().collect();
-    let mut found_version = false;
-    let mut body_lines: Vec<&str> = Vec::new();
-    let mut published_at = String::new();
-
-    for line in &lines {
-        if let Some(caps) = version_header_regex.captures(line) {
-            let line_version = caps.get(1).map(|m| m.as_str()).unwrap_or("");
-
-            if found_version {
-                // We hit the next version header, stop collecting
-                break;
-            }
-
-            if line_version == target_version {
-                found_version = true;
-                // Extract the date if present
-                if let Some(date_match) = caps.get(2) {
-                    published_at = date_match.as_str().to_string();
-                }
-                continue;
-            }
-        } else if found_version {
-            body_lines.push(*line);
-        }
-    }
-
-    if !found_version {
-        return Err(format!("Version {} not found in changelog", target_version));
-    }
-
-    // Trim leading/trailing empty lines from body
-    while body_lines.first().is_some_and(|l| l.trim().is_empty()) {
-        body_lines.remove(0);
-    }
-    while body_lines.last().is_some_and(|l| l.trim().is_empty()) {
-        body_lines.pop();
-    }
-
-    let body = body_lines.join("\n");
+    // If no version specified, use the current app version
+    let target_version = version.unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string());
+    let release = whats_new::parse(&changelog)
+        .into_iter()
+        .find(|r| r.version == target_version)
+        .ok_or_else(|| format!("Version {} not found in changelog", target_version))?;
 
     Ok(ReleaseNotes {
-        version: target_version.clone(),
-        name: format!("Version {}", target_version),
-        body,
-        published_at,
+        name: format!("Version {}", release.version),
+        version: release.version,
+        body: release.body,
+        published_at: release.published_at,
+        features: release.features,
+        fixes: release.fixes,
     })
 }
 
+/// The releases since the version last run, when this run follows an
+/// update and the popup hasn't been closed yet (see services::whats_new).
+#[tauri::command]
+pub fn get_whats_new(state: State<'_, AppState>) -> Option<whats_new::WhatsNew> {
+    let last_seen = state.settings.lock().unwrap().last_seen_version.clone();
+    whats_new::pending().filter(|w| last_seen.as_deref() != Some(w.to.as_str()))
+}
+
 #[tauri::command]
 pub async fn download_and_install_app_update(
     app_handle: tauri::AppHandle,
@@ -0,0 +1,220 @@
+//! Backend for the first-run wizard. Each step is run (or skipped) on its
+//! own and leaves a `SetupStepStatus` in `settings.first_run_state`, so the
+//! wizard can show what's done, retry what failed and resume after a restart
+//! instead of leaving a half-configured install.
+//!
+//! There is no Streamlink to detect: live playback resolves natively, so the
+//! playback step proves the whole path (resolve, then fetch the media
+//! playlist) against a real live channel.
+
+use crate::commands::settings::store_settings;
+use crate::models::settings::{AppState, FirstRunState, SetupStep, SetupStepState, SetupStepStatus};
+use crate::services::twitch_service::TwitchService;
+use log::debug;
+use serde::Deserialize;
+use tauri::State;
+
+/// What drops mining does without being asked, as chosen in the wizard.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MiningDefaults {
+    pub automation_enabled: bool,
+    pub auto_claim_drops: bool,
+    pub auto_claim_channel_points: bool,
+    #[serde(default)]
+    pub priority_games: Vec<String>,
+}
+
+/// Every step in wizard order, pending where nothing was recorded yet.
+fn with_all_steps(mut first_run: FirstRunState) -> FirstRunState {
+    first_run.steps = SetupStep::ALL
+        .iter()
+        .map(|&step| {
+            first_run
+                .steps
+                .iter()
+                .find(|s| s.step == step)
+                .cloned()
+                .unwrap_or(SetupStepStatus {
+                    step,
+                    state: SetupStepState::Pending,
+                    detail: None,
+                    updated_at: None,
+                })
+        })
+        .collect();
+    first_run
+}
+
+fn record(
+    state: &AppState,
+    step: SetupStep,
+    step_state: SetupStepState,
+    detail: String,
+) -> Result<SetupStepStatus, String> {
+    let status = SetupStepStatus {
+        step,
+        state: step_state,
+        detail: Some(detail),
+        updated_at: Some(chrono::Utc::now().to_rfc3339()),
+    };
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        settings.first_run_state.steps.retain(|s| s.step != step);
+        settings.first_run_state.steps.push(status.clone());
+        settings.clone()
+    };
+    store_settings(state, &settings)?;
+    Ok(status)
+}
+
+/// Resolve the biggest drops-enabled live channel and fetch its media
+/// playlist, as the player would.
+async fn test_playback(state: &AppState) -> Result<String, String> {
+    let (streams, _) = TwitchService::get_drops_enabled_streams(None, None, 1)
+        .await
+        .map_err(|e| format!("Couldn't reach Twitch: {}", e))?;
+    let login = streams
+        .first()
+        .map(|s| s.user_login.clone())
+        .ok_or("Couldn't find a live channel to test with")?;
+    let oauth = state.twitch_auth.get_token().await.ok();
+    let resolved = crate::services::twitch_resolver::resolve_live(&login, oauth.as_deref(), "best")
+        .await
+        .map_err(|e| format!("Couldn't resolve {}'s stream: {}", login, e))?;
+    let playlist = crate::services::http::client()
+        .get(&resolved.url)
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Couldn't load {}'s playlist: {}", login, e))?
+        .text()
+        .await
+        .map_err(|e| format!("Couldn't load {}'s playlist: {}", login, e))?;
+    if !playlist.starts_with("#EXTM3U") {
+        return Err(format!("{}'s playlist wasn't a stream playlist", login));
+    }
+    Ok(format!(
+        "Played {} at {} ({} qualities available)",
+        login,
+        resolved.quality,
+        resolved.available.len()
+    ))
+}
+
+async fn apply_mining_defaults(
+    state: &State<'_, AppState>,
+    defaults: MiningDefaults,
+) -> Result<String, String> {
+    let mut drops = state.drops_service.lock().await.get_settings().await;
+    drops.automation_enabled = defaults.automation_enabled;
+    drops.auto_claim_drops = defaults.auto_claim_drops;
+    drops.auto_claim_channel_points = defaults.auto_claim_channel_points;
+    drops.priority_games = defaults.priority_games;
+    crate::commands::drops::update_drops_settings(drops, state.clone()).await?;
+    let detail = match state.plugin_host.provides("drops.mining").await {
+        Some(_) => "Saved",
+        None => "Saved; mining starts once a drops plugin is installed",
+    };
+    Ok(detail.to_string())
+}
+
+/// The wizard's progress: every step in order with its latest result.
+#[tauri::command]
+pub async fn get_first_run_state(state: State<'_, AppState>) -> Result<FirstRunState, String> {
+    let first_run = state.settings.lock().unwrap().first_run_state.clone();
+    Ok(with_all_steps(first_run))
+}
+
+/// Run one wizard step and record the outcome. `mining_defaults` is required
+/// for the mining step. A failed step is a normal result (state `failed`,
+/// with the reason in `detail`), not an error.
+#[tauri::command]
+pub async fn run_setup_step(
+    state: State<'_, AppState>,
+    step: SetupStep,
+    mining_defaults: Option<MiningDefaults>,
+) -> Result<SetupStepStatus, String> {
+    let outcome = match step {
+        SetupStep::Playback => test_playback(&state).await,
+        SetupStep::Login => match TwitchService::get_user_info().await {
+            Ok(user) => Ok(format!("Signed in as {}", user.display_name)),
+            Err(e) => {
+                debug!("[Setup] Login check failed: {}", e);
+                Err("Not signed in to Twitch yet".to_string())
+            }
+        },
+        SetupStep::MiningDefaults => {
+            let defaults = mining_defaults.ok_or("mining_defaults is required for this step")?;
+            apply_mining_defaults(&state, defaults).await
+        }
+    };
+    match outcome {
+        Ok(detail) => record(&state, step, SetupStepState::Done, detail),
+        Err(detail) => record(&state, step, SetupStepState::Failed, detail),
+    }
+}
+
+/// Leave a step for later; the wizard can still be finished.
+#[tauri::command]
+pub async fn skip_setup_step(
+    state: State<'_, AppState>,
+    step: SetupStep,
+) -> Result<SetupStepStatus, String> {
+    record(&state, step, SetupStepState::Skipped, "Skipped".to_string())
+}
+
+/// Finish the wizard once every step is done or skipped.
+#[tauri::command]
+pub async fn complete_first_run(state: State<'_, AppState>) -> Result<FirstRunState, String> {
+    let settings = {
+        let mut settings = state.settings.lock().unwrap();
+        let first_run = with_all_steps(settings.first_run_state.clone());
+        let open: Vec<String> = first_run
+            .steps
+            .iter()
+            .filter(|s| matches!(s.state, SetupStepState::Pending | SetupStepState::Failed))
+            .map(|s| format!("{:?}", s.step))
+            .collect();
+        if !open.is_empty() {
+            return Err(format!("Steps still to do: {}", open.join(", ")));
+        }
+        settings.first_run_state = FirstRunState {
+            completed_at: Some(chrono::Utc::now().to_rfc3339()),
+            ..first_run
+        };
+        settings.setup_complete = true;
+        settings.clone()
+    };
+    store_settings(&state, &settings)?;
+    Ok(settings.first_run_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrecorded_steps_show_as_pending_in_order() {
+        let first_run = FirstRunState {
+            steps: vec![SetupStepStatus {
+                step: SetupStep::MiningDefaults,
+                state: SetupStepState::Skipped,
+                detail: None,
+                updated_at: None,
+            }],
+            completed_at: None,
+        };
+        let steps = with_all_steps(first_run).steps;
+        let states: Vec<(SetupStep, SetupStepState)> =
+            steps.iter().map(|s| (s.step, s.state)).collect();
+        assert_eq!(
+            states,
+            [
+                (SetupStep::Playback, SetupStepState::Pending),
+                (SetupStep::Login, SetupStepState::Pending),
+                (SetupStep::MiningDefaults, SetupStepState::Skipped),
+            ]
+        );
+    }
+}
@@ -1,11 +1,21 @@
-use crate::models::settings::AppState;
+use crate::models::settings::{AdFreeOverride, AppState, PostProcessOptions};
 use crate::services::auth_proxy;
+use crate::services::raid_events::{self, RaidChannelStats};
+use crate::services::recording_guard::ActiveRecording;
+use crate::services::recording_postprocess::PostProcessJob;
+use crate::services::recordings_archive::RecordingEntry;
+use crate::services::script_hooks;
+use crate::services::squad_streams::{self, Squad};
 use crate::services::stream_server::StreamServer;
+use crate::services::trace_ids;
 use crate::services::twitch_resolver as tr;
+use crate::services::twitch_service::TwitchService;
+use crate::services::watch_history;
 use log::debug;
 use serde::Serialize;
 use serde_json::json;
-use tauri::State;
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
 
 /// The hook a resolution-owning plugin fills (see docs/plugins/HOOKS.md): the
 /// host invokes this action with the channel and quality, and the plugin
@@ -16,7 +26,9 @@ pub(crate) const PLAYBACK_RESOLVE_HOOK: &str = "playback.resolve";
 /// one provides the `playback.resolve` hook.
 ///
 /// `core` is the core resolver's own result: entitled resolutions are never
-/// delegated (Turbo or a channel sub is already ad-free), and a successful
+/// delegated (Turbo or a channel sub is already ad-free) unless the channel's
+/// `ad_free_overrides` entry says `workaround`, a `direct` entry keeps the
+/// channel off the plugin altogether, and a successful
 /// core master rides along in the action args so the plugin can graft the
 /// above-1080p tiers the viewer's login unlocks onto whatever master it
 /// resolves. Returns `None` whenever the plugin path does not produce a
@@ -28,8 +40,21 @@ pub(crate) async fn resolve_via_plugin(
     quality: &str,
     core: &Result<tr::ResolvedLive, anyhow::Error>,
 ) -> Option<tr::ResolvedLive> {
-    if core.as_ref().map(|r| r.status.entitled).unwrap_or(false) {
-        return None;
+    let ad_free = {
+        let settings = state.settings.lock().unwrap();
+        settings
+            .ad_free_overrides
+            .get(&channel.to_lowercase())
+            .copied()
+    };
+    match ad_free {
+        Some(AdFreeOverride::Direct) => return None,
+        Some(AdFreeOverride::Workaround) => {}
+        None => {
+            if core.as_ref().map(|r| r.status.entitled).unwrap_or(false) {
+                return None;
+            }
+        }
     }
     state.plugin_host.provides(PLAYBACK_RESOLVE_HOOK).await?;
     let auth_master = core.as_ref().ok().map(|r| r.master.clone());
@@ -159,11 +184,24 @@ pub async fn resolve_clip_media(
     })
 }
 
+/// Start playing `url`. Each start is a new `stream` trace (`trace_ids`), so
+/// the resolve and relay startup can be followed in the logs.
 #[tauri::command]
 pub async fn start_stream(
     url: String,
     quality: String,
     state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<StreamStartResult, String> {
+    let trace_id = trace_ids::begin(trace_ids::STREAM);
+    trace_ids::scope(trace_id, resolve_and_start(url, quality, state, app)).await
+}
+
+async fn resolve_and_start(
+    url: String,
+    quality: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<StreamStartResult, String> {
     debug!("[Streaming] start_stream called for URL: {}", url);
 
@@ -254,6 +292,12 @@ pub async fn start_stream(
     // plugin protocol's "solo" stream id (set_upstream, on_ad_window) always
     // addresses a live relay.
     crate::services::stream_server::set_solo_session(Some(channel.clone()));
+    squad_streams::check_in_background(channel.clone());
+    let priority = state.settings.lock().unwrap().playback_resources.priority;
+    crate::services::playback_priority::apply(priority).await;
+    let started = json!({ "channel": channel, "quality": r.quality });
+    script_hooks::fire(&app, script_hooks::STREAM_STARTED, started.clone());
+    crate::services::webhooks::fire(&app, script_hooks::STREAM_STARTED, started);
     Ok(StreamStartResult {
         url: local_player_url(port),
         quality: r.quality,
@@ -266,9 +310,285 @@ pub async fn start_stream(
 
 #[tauri::command]
 pub async fn stop_stream() -> Result<(), String> {
+    trace_ids::end(trace_ids::STREAM);
+    crate::services::playback_priority::reset().await;
+    crate::services::media_session::set_stopped();
+    crate::services::stream_info_poller::unsubscribe();
     StreamServer::stop().await.map_err(|e| e.to_string())
 }
 
+#[derive(Serialize)]
+pub struct AdFreeStatus {
+    /// The signed-in account has Turbo (ad-free everywhere).
+    pub turbo: bool,
+    /// The signed-in account is subscribed to the channel.
+    pub subscribed: bool,
+    /// The user's override for the channel, if any.
+    #[serde(rename = "override")]
+    pub override_mode: Option<AdFreeOverride>,
+    /// Whether the channel plays direct from Twitch, skipping any playback
+    /// plugin: entitled without a `workaround` override, or set `direct`.
+    pub direct: bool,
+}
+
+/// Whether `channel` plays ad-free through the viewer's own Turbo or sub,
+/// and what the user's override for it says. Signed out, both read false.
+#[tauri::command]
+pub async fn get_ad_free_status(
+    channel: String,
+    state: State<'_, AppState>,
+) -> Result<AdFreeStatus, String> {
+    let channel = channel.to_lowercase();
+    let override_mode = {
+        let settings = state.settings.lock().unwrap();
+        settings.ad_free_overrides.get(&channel).copied()
+    };
+    let (turbo, subscribed) = match state.twitch_auth.get_token().await {
+        Ok(token) => {
+            let turbo = auth_proxy::account_has_turbo(&token).await;
+            let subscribed = auth_proxy::is_subscribed(&channel, &token).await;
+            (turbo, subscribed)
+        }
+        Err(_) => (false, false),
+    };
+    let direct = match override_mode {
+        Some(AdFreeOverride::Direct) => true,
+        Some(AdFreeOverride::Workaround) => false,
+        None => turbo || subscribed,
+    };
+    Ok(AdFreeStatus {
+        turbo,
+        subscribed,
+        override_mode,
+        direct,
+    })
+}
+
+/// Poll `channel`'s title, category, viewer count, uptime and tags from the
+/// backend, emitting `stream-info-update` every 30s until the stream stops or
+/// another channel is subscribed.
+#[tauri::command]
+pub fn subscribe_stream_info(channel: String, app_handle: tauri::AppHandle) {
+    crate::services::stream_info_poller::subscribe(app_handle, &channel);
+}
+
+#[tauri::command]
+pub fn unsubscribe_stream_info() {
+    crate::services::stream_info_poller::unsubscribe();
+}
+
+/// Bookmark the current moment of the live stream being watched, with an
+/// optional note, for `export_session_highlights`.
+#[tauri::command]
+pub fn add_stream_bookmark(
+    note: Option<String>,
+) -> Result<crate::services::watch_sessions::Bookmark, String> {
+    crate::services::watch_sessions::add_bookmark(note.as_deref().unwrap_or(""))
+        .map_err(|e| e.to_string())
+}
+
+/// Recently watched broadcasts with their bookmarks, most recent first.
+#[tauri::command]
+pub fn get_watch_sessions(
+    limit: Option<usize>,
+) -> Vec<crate::services::watch_sessions::WatchSession> {
+    crate::services::watch_sessions::recent(limit.unwrap_or(50))
+}
+
+/// A session's bookmarks as `markdown` or `csv` text (stream time, note and a
+/// VOD link once the archive exists), or its chapters as `ffmetadata` or
+/// `xml`, for the frontend to copy or save.
+#[tauri::command]
+pub async fn export_session_highlights(
+    session_id: String,
+    format: String,
+) -> Result<String, String> {
+    crate::services::watch_sessions::export(&session_id, &format)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Save a session's chapters (category and title changes) beside a recording
+/// of it, as `.ffmetadata` and `.chapters.xml`, timed from
+/// `recording_started_at` (RFC 3339; the session's start when omitted).
+/// Returns the paths written.
+#[tauri::command]
+pub fn write_recording_chapters(
+    session_id: String,
+    recording_path: String,
+    recording_started_at: Option<String>,
+) -> Result<Vec<String>, String> {
+    let started_at = recording_started_at
+        .map(|t| {
+            chrono::DateTime::parse_from_rfc3339(&t)
+                .map(|t| t.with_timezone(&chrono::Utc))
+                .map_err(|e| format!("Invalid recording_started_at: {}", e))
+        })
+        .transpose()?;
+    crate::services::watch_sessions::write_recording_chapters(
+        &session_id,
+        std::path::Path::new(&recording_path),
+        started_at,
+    )
+    .map(|paths| paths.iter().map(|p| p.display().to_string()).collect())
+    .map_err(|e| e.to_string())
+}
+
+/// Start recording a channel through the recording plugin, within the
+/// recording limit and free-space floor in settings.
+#[tauri::command]
+pub async fn start_recording(channel_login: String, app: AppHandle) -> Result<(), String> {
+    crate::services::recording_guard::start(&app, &channel_login)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// What the recording plugin reports it's recording, oldest first.
+#[tauri::command]
+pub fn get_active_recordings(state: State<'_, AppState>) -> Vec<ActiveRecording> {
+    crate::services::recording_guard::active(&state)
+}
+
+/// Recordings in the recordings folder, newest first.
+#[tauri::command]
+pub fn list_recordings(state: State<'_, AppState>) -> Result<Vec<RecordingEntry>, String> {
+    let settings = state.settings.lock().unwrap().recording.clone();
+    crate::services::recordings_archive::list(&settings).map_err(|e| e.to_string())
+}
+
+/// One recording's file details and indexed channel, game and duration.
+#[tauri::command]
+pub fn get_recording_metadata(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<RecordingEntry, String> {
+    let settings = state.settings.lock().unwrap().recording.clone();
+    crate::services::recordings_archive::metadata(&settings, &path).map_err(|e| e.to_string())
+}
+
+/// Delete a recording from the recordings folder, with what belongs to it.
+#[tauri::command]
+pub fn delete_recording(path: String, state: State<'_, AppState>) -> Result<(), String> {
+    let settings = state.settings.lock().unwrap().recording.clone();
+    crate::services::recordings_archive::delete(&settings, &path).map_err(|e| e.to_string())
+}
+
+/// Open a recording from the recordings folder in the system's video player.
+#[tauri::command]
+pub fn play_recording(
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let settings = state.settings.lock().unwrap().recording.clone();
+    let path = crate::services::recordings_archive::playable(&settings, &path)
+        .map_err(|e| e.to_string())?;
+    app.opener()
+        .open_path(path.display().to_string(), None::<String>)
+        .map_err(|e| format!("Failed to open the recording: {}", e))
+}
+
+/// Queue a finished recording for post-processing, with `options` or the
+/// ones in settings. Progress comes as `recording-postprocess-progress`.
+#[tauri::command]
+pub async fn postprocess_recording(
+    path: String,
+    options: Option<PostProcessOptions>,
+    state: State<'_, AppState>,
+) -> Result<PostProcessJob, String> {
+    let options = match options {
+        Some(options) => options,
+        None => state
+            .settings
+            .lock()
+            .unwrap()
+            .recording
+            .postprocess_options
+            .clone(),
+    };
+    crate::services::recording_postprocess::enqueue(std::path::Path::new(&path), options)
+        .map_err(|e| e.to_string())
+}
+
+/// This session's post-processing jobs, oldest first.
+#[tauri::command]
+pub fn get_postprocess_jobs() -> Vec<PostProcessJob> {
+    crate::services::recording_postprocess::jobs()
+}
+
+/// Queue a failed post-processing job again.
+#[tauri::command]
+pub async fn retry_postprocess_job(id: u64) -> Result<PostProcessJob, String> {
+    crate::services::recording_postprocess::retry(id).map_err(|e| e.to_string())
+}
+
+/// Save the current frame of the live stream as a PNG at `path` (a timestamped
+/// file under Pictures/StreamNook when omitted). Needs ffmpeg on PATH. Returns
+/// the saved path and emits `stream-frame-captured` for the notification.
+#[tauri::command]
+pub async fn capture_stream_frame(path: Option<String>) -> Result<String, String> {
+    crate::services::frame_capture::capture(path.as_deref())
+        .await
+        .map(|captured| captured.path)
+        .map_err(|e| e.to_string())
+}
+
+/// Publish the watched live stream to the OS media session (media keys,
+/// Windows volume flyout, MPRIS). Cleared back to stopped by `stop_stream`.
+#[tauri::command]
+pub fn set_media_session(channel: String, game_name: String, thumbnail_url: Option<String>) {
+    crate::services::media_session::set_playing(&channel, &game_name, thumbnail_url.as_deref());
+}
+
+/// Channels to check for the continue-watching rail; more than the rail shows
+/// since most won't be live.
+const CONTINUE_WATCHING_CANDIDATES: usize = 30;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContinueWatching {
+    /// Recently watched channels that are live now, most recent first.
+    pub live: Vec<crate::models::stream::TwitchStream>,
+    /// Partly watched VODs, most recent first.
+    pub vods: Vec<watch_history::VodPosition>,
+}
+
+/// Everything the home screen's continue-watching rail needs in one call.
+/// `limit` caps each list (default 10).
+#[tauri::command]
+pub async fn get_continue_watching(limit: Option<usize>) -> Result<ContinueWatching, String> {
+    let limit = limit.unwrap_or(10);
+    let recent = watch_history::recent_channels(CONTINUE_WATCHING_CANDIDATES);
+    let logins: Vec<String> = recent.iter().map(|c| c.login.clone()).collect();
+    let mut live = if logins.is_empty() {
+        Vec::new()
+    } else {
+        TwitchService::check_streams_online(&logins)
+            .await
+            .map_err(|e| e.to_string())?
+    };
+    // Helix returns them by viewer count; put them back in watch order.
+    live.sort_by_key(|s| {
+        logins
+            .iter()
+            .position(|l| l.eq_ignore_ascii_case(&s.user_login))
+            .unwrap_or(usize::MAX)
+    });
+    live.truncate(limit);
+    Ok(ContinueWatching {
+        live,
+        vods: watch_history::vod_positions(limit),
+    })
+}
+
+/// Remember where the user is in a VOD. The player reports this every 15s
+/// while a VOD plays; near-start and finished positions aren't kept.
+#[tauri::command]
+pub fn set_vod_position(position: watch_history::VodPosition) {
+    watch_history::set_vod_position(position);
+}
+
 /// Whether the relay's LL-HLS origin is actively serving parts for this stream.
 /// True ⇒ hls.js should run in `lowLatencyMode` (a real `#EXT-X-PART` playlist with
 /// blocking reload is being served). The player reads this once at construction to
@@ -370,10 +690,13 @@ pub async fn change_stream_quality(
     url: String,
     quality: String,
     state: State<'_, AppState>,
+    app: AppHandle,
 ) -> Result<StreamStartResult, String> {
     // Don't stop the server - just update the stream URL.
-    // The server keeps running on the same port.
-    start_stream(url, quality, state).await
+    // The server keeps running on the same port. Same stream, same trace.
+    let trace_id =
+        trace_ids::active(trace_ids::STREAM).unwrap_or_else(|| trace_ids::begin(trace_ids::STREAM));
+    trace_ids::scope(trace_id, resolve_and_start(url, quality, state, app)).await
 }
 
 #[tauri::command]
@@ -399,3 +722,36 @@ pub async fn unregister_active_channel(
     ws_service.unregister_active_channel(&channel_id).await;
     Ok(())
 }
+
+/// Where to go now that `channel_login` went offline, when it's part of an
+/// open raid-train event that follows with playback (see
+/// services::raid_events). The player asks before its own auto-switch.
+#[tauri::command]
+pub fn get_raid_event_next(channel_login: String, state: State<'_, AppState>) -> Option<String> {
+    let events = state.settings.lock().unwrap().raid_events.clone();
+    raid_events::next_channel(&events, &channel_login)
+}
+
+/// Time tracked for a raid-train event, per channel login.
+#[tauri::command]
+pub fn get_raid_event_stats(event_id: String) -> HashMap<String, RaidChannelStats> {
+    raid_events::stats(&event_id)
+}
+
+/// Who `channel_login` is live together with (Shared Chat or partners named in
+/// the title), None when it streams alone.
+#[tauri::command]
+pub async fn get_squad(channel_login: String) -> Result<Option<Squad>, String> {
+    squad_streams::detect(&channel_login)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Open `channel_login` and its co-streamers in MultiNook. Returns the channels
+/// the grid gets.
+#[tauri::command]
+pub async fn launch_squad_multiview(channel_login: String) -> Result<Vec<String>, String> {
+    squad_streams::launch(&channel_login)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,7 +1,10 @@
+use crate::commands::error::CommandError;
 use crate::models::settings::AppState;
-use crate::models::stream::{TwitchClip, TwitchStream, TwitchVideo};
+use crate::models::stream::{ShareInfo, StreamFilter, TwitchClip, TwitchStream, TwitchVideo};
 use crate::models::user::{ChannelInfo, UserInfo};
 use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::follow_batch;
+use crate::services::inactive_follows::{self, InactiveFollow};
 use crate::services::twitch_service::{DeviceCodeInfo, TokenHealthStatus, TwitchService};
 use crate::services::whisper_history_service::{
     WhisperHistoryService, WhisperMessage, WhisperThread,
@@ -30,6 +33,23 @@ pub async fn twitch_login(
     Ok((verification_uri, user_code))
 }
 
+/// Sign in for the site token and then the drops token in one sequence,
+/// skipping whichever is already signed in. Progress arrives as
+/// `combined-login-progress` events.
+#[tauri::command]
+pub async fn start_combined_login(app: AppHandle) -> Result<(), String> {
+    crate::services::combined_login::start(app);
+    Ok(())
+}
+
+/// Abort a `twitch_login` or `start_combined_login` still waiting for a code
+/// to be authorized. Returns false when there was nothing to cancel.
+#[tauri::command]
+pub fn cancel_login(app: AppHandle) -> bool {
+    let combined = crate::services::combined_login::cancel(&app);
+    TwitchService::cancel_login(&app) || combined
+}
+
 #[derive(serde::Serialize)]
 pub struct CreateClipResult {
     pub id: String,
@@ -958,6 +978,24 @@ pub async fn get_followed_streams(state: State<'_, AppState>) -> Result<Vec<Twit
         .map_err(|e| e.to_string())
 }
 
+/// Recent viewer counts for a live followed channel with average, stability
+/// and trend. `None` until a status check has seen it live.
+#[tauri::command]
+pub fn get_viewer_count_history(
+    channel_id: String,
+) -> Result<Option<crate::services::viewer_stats_service::ViewerCountSummary>, String> {
+    Ok(crate::services::viewer_stats_service::summary(&channel_id))
+}
+
+/// Typical broadcast length and current-uptime penalty for a channel (by id
+/// or login), for the channel picker. `None` if it has never been seen live.
+#[tauri::command]
+pub fn get_channel_uptime_stats(
+    channel: String,
+) -> Result<Option<crate::services::stream_uptime_service::ChannelUptimeStats>, String> {
+    Ok(crate::services::stream_uptime_service::stats(&channel))
+}
+
 #[tauri::command]
 pub async fn get_channel_info(
     channel_name: String,
@@ -968,6 +1006,89 @@ pub async fn get_channel_info(
         .map_err(|e| e.to_string())
 }
 
+/// Link, title, and category for sharing a channel, with a pre-formatted
+/// message. Offline channels get their last-set title and category from the
+/// channel info instead of the stream.
+#[tauri::command]
+pub async fn get_share_info(
+    channel: String,
+    state: State<'_, AppState>,
+) -> Result<ShareInfo, String> {
+    let login = channel.trim().trim_start_matches('#').to_lowercase();
+    let url = format!("https://www.twitch.tv/{}", login);
+
+    if let Some(stream) = TwitchService::check_stream_online(&login)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        let message = if stream.game_name.is_empty() {
+            format!(
+                "Watching {} live on Twitch: \"{}\" {}",
+                stream.user_name, stream.title, url
+            )
+        } else {
+            format!(
+                "Watching {} play {} on Twitch: \"{}\" {}",
+                stream.user_name, stream.game_name, stream.title, url
+            )
+        };
+        return Ok(ShareInfo {
+            channel_login: login,
+            display_name: stream.user_name,
+            url,
+            is_live: true,
+            title: Some(stream.title),
+            game_name: Some(stream.game_name).filter(|g| !g.is_empty()),
+            message,
+        });
+    }
+
+    let info = TwitchService::get_channel_info(&login, &state).await.ok();
+    let display_name = info
+        .as_ref()
+        .map(|i| i.broadcaster_name.clone())
+        .unwrap_or_else(|| login.clone());
+    Ok(ShareInfo {
+        message: format!("Check out {} on Twitch: {}", display_name, url),
+        channel_login: login,
+        display_name,
+        url,
+        is_live: false,
+        title: info
+            .as_ref()
+            .map(|i| i.title.clone())
+            .filter(|t| !t.is_empty()),
+        game_name: info.map(|i| i.game_name).filter(|g| !g.is_empty()),
+    })
+}
+
+/// Open a VOD on twitch.tv at a playback position (seconds), e.g. to hand the
+/// current spot in the player off to the browser.
+#[tauri::command]
+pub async fn open_in_browser_at_timestamp(
+    app_handle: AppHandle,
+    vod_id: String,
+    seconds: u64,
+) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let vod_id = vod_id.trim_start_matches('v');
+    if vod_id.is_empty() || !vod_id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("Invalid VOD id: {}", vod_id));
+    }
+    let url = format!(
+        "https://www.twitch.tv/videos/{}?t={}h{}m{}s",
+        vod_id,
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    );
+    app_handle
+        .opener()
+        .open_url(url, None::<String>)
+        .map_err(|e| format!("Failed to open VOD: {}", e))
+}
+
 #[tauri::command]
 pub async fn get_user_info(_state: State<'_, AppState>) -> Result<UserInfo, String> {
     TwitchService::get_user_info()
@@ -979,20 +1100,32 @@ pub async fn get_user_info(_state: State<'_, AppState>) -> Result<UserInfo, Stri
 pub async fn get_recommended_streams(
     state: State<'_, AppState>,
 ) -> Result<Vec<TwitchStream>, String> {
-    TwitchService::get_recommended_streams(&state)
+    let streams = TwitchService::get_recommended_streams(&state)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    Ok(crate::services::recommendations::rank_for(&state, streams).await)
 }
 
+/// `filter` narrows by language (server-side) and tags; a filtered page can
+/// come back shorter than `limit` while still having a next cursor.
 #[tauri::command]
 pub async fn get_recommended_streams_paginated(
     state: State<'_, AppState>,
     cursor: Option<String>,
     limit: u32,
+    filter: Option<StreamFilter>,
 ) -> Result<(Vec<TwitchStream>, Option<String>), String> {
-    TwitchService::get_recommended_streams_paginated(&state, cursor, limit)
-        .await
-        .map_err(|e| e.to_string())
+    let (streams, cursor) = TwitchService::get_recommended_streams_paginated(
+        &state,
+        cursor,
+        limit,
+        &filter.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    // Ranked within the page, per `recommendation_strategy`.
+    let streams = crate::services::recommendations::rank_for(&state, streams).await;
+    Ok((streams, cursor))
 }
 
 #[tauri::command]
@@ -1053,8 +1186,9 @@ pub async fn get_streams_by_game(
     game_id: String,
     cursor: Option<String>,
     limit: u32,
+    filter: Option<StreamFilter>,
 ) -> Result<(Vec<TwitchStream>, Option<String>), String> {
-    TwitchService::get_streams_by_game(&state, &game_id, cursor, limit)
+    TwitchService::get_streams_by_game(&state, &game_id, cursor, limit, &filter.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -1110,6 +1244,29 @@ pub async fn unfollow_channel(target_user_id: String) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+/// Unfollow many channels in the background, paced for Twitch's rate limit.
+/// Returns the batch id carried by `unfollow-batch-progress` and
+/// `unfollow-batch-complete` (see services::follow_batch).
+#[tauri::command]
+pub fn unfollow_channels(ids: Vec<String>) -> Result<u64, String> {
+    follow_batch::unfollow(ids).map_err(|e| e.to_string())
+}
+
+/// Follow back a batch's channels, within 30 seconds of it finishing.
+#[tauri::command]
+pub fn undo_unfollow_channels(batch_id: u64) -> Result<usize, String> {
+    follow_batch::undo(batch_id).map_err(|e| e.to_string())
+}
+
+/// Followed channels that haven't streamed in `days` days, to pick from for
+/// `unfollow_channels`.
+#[tauri::command]
+pub async fn get_inactive_follows(days: u32) -> Result<Vec<InactiveFollow>, String> {
+    inactive_follows::list(days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_all_followed_channels(
     limit: u32,
@@ -1243,7 +1400,6 @@ pub async fn send_whisper(to_user_id: String, message: String) -> Result<(), Str
 /// This should be called after the user is authenticated
 #[tauri::command]
 pub async fn start_whisper_listener(
-    app: AppHandle,
     whisper_service: State<'_, Arc<TokioMutex<WhisperService>>>,
 ) -> Result<(), String> {
     // Get the current user's ID and token
@@ -1258,7 +1414,7 @@ pub async fn start_whisper_listener(
     // Start the whisper listener
     let service = whisper_service.lock().await;
     service
-        .start_listening(user_info.id, token, app)
+        .start_listening(user_info.id, token)
         .await
         .map_err(|e| format!("Failed to start whisper listener: {}", e))?;
 
@@ -1378,6 +1534,24 @@ pub async fn search_categories(
         .map_err(|e| e.to_string())
 }
 
+/// Resolve a category name or shorthand ("LoL") to the category, from the
+/// local cache when it's been resolved before. None unless a category has
+/// exactly that name (ignoring case).
+#[tauri::command]
+pub async fn resolve_game(
+    query: String,
+) -> Result<Option<crate::services::game_resolver::ResolvedGame>, String> {
+    crate::services::game_resolver::resolve(&query)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Add a category alias for `resolve_game`, or remove it when `name` is None.
+#[tauri::command]
+pub fn set_game_alias(alias: String, name: Option<String>) -> Result<(), String> {
+    crate::services::game_resolver::set_alias(&alias, name.as_deref()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_clips_by_game(
     _state: State<'_, AppState>,
@@ -1754,24 +1928,27 @@ pub async fn get_vod_comments(
 pub async fn update_chat_settings(
     broadcaster_id: String,
     settings: serde_json::Value,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::update_chat_settings(&broadcaster_id, settings)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn clear_chat(broadcaster_id: String) -> Result<(), String> {
+pub async fn clear_chat(broadcaster_id: String) -> Result<(), CommandError> {
     TwitchService::clear_chat(&broadcaster_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn delete_chat_message(broadcaster_id: String, message_id: String) -> Result<(), String> {
+pub async fn delete_chat_message(
+    broadcaster_id: String,
+    message_id: String,
+) -> Result<(), CommandError> {
     TwitchService::delete_chat_message(&broadcaster_id, &message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1779,17 +1956,20 @@ pub async fn pin_chat_message(
     broadcaster_id: String,
     message_id: String,
     duration_seconds: Option<u32>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::pin_chat_message(&broadcaster_id, &message_id, duration_seconds)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn unpin_chat_message(broadcaster_id: String, message_id: String) -> Result<(), String> {
+pub async fn unpin_chat_message(
+    broadcaster_id: String,
+    message_id: String,
+) -> Result<(), CommandError> {
     TwitchService::unpin_chat_message(&broadcaster_id, &message_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1798,7 +1978,7 @@ pub async fn ban_user(
     target_user_id: String,
     duration: Option<u32>,
     reason: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::ban_user(
         &broadcaster_id,
         &target_user_id,
@@ -1806,51 +1986,57 @@ pub async fn ban_user(
         reason.as_deref(),
     )
     .await
-    .map_err(|e| e.to_string())
+    .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn unban_user(broadcaster_id: String, target_user_id: String) -> Result<(), String> {
+pub async fn unban_user(
+    broadcaster_id: String,
+    target_user_id: String,
+) -> Result<(), CommandError> {
     TwitchService::unban_user(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub async fn add_channel_moderator(
     broadcaster_id: String,
     target_user_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::add_channel_moderator(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub async fn remove_channel_moderator(
     broadcaster_id: String,
     target_user_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::remove_channel_moderator(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
-pub async fn add_channel_vip(broadcaster_id: String, target_user_id: String) -> Result<(), String> {
+pub async fn add_channel_vip(
+    broadcaster_id: String,
+    target_user_id: String,
+) -> Result<(), CommandError> {
     TwitchService::add_channel_vip(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub async fn remove_channel_vip(
     broadcaster_id: String,
     target_user_id: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::remove_channel_vip(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1858,10 +2044,10 @@ pub async fn update_suspicious_user_status(
     broadcaster_id: String,
     target_user_id: String,
     status: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     TwitchService::update_suspicious_user_status(&broadcaster_id, &target_user_id, &status)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1928,6 +2114,18 @@ pub async fn get_channel_chatters(
         .map_err(|e| e.to_string())
 }
 
+/// Who's in `channel_id`'s chat, with joins and leaves since the last call.
+/// Full Helix roster when the user moderates the channel, otherwise what IRC
+/// membership and recent messages show. Cached for 30s.
+#[tauri::command]
+pub async fn get_chatters(
+    channel_id: String,
+) -> Result<crate::services::chatter_tracker::ChattersSnapshot, String> {
+    crate::services::chatter_tracker::get_chatters(&channel_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn send_chat_announcement(
     broadcaster_id: String,
@@ -1954,10 +2152,17 @@ pub async fn start_commercial(broadcaster_id: String, length: u32) -> Result<(),
 }
 
 #[tauri::command]
-pub async fn start_raid(broadcaster_id: String, target_user_id: String) -> Result<(), String> {
+pub async fn start_raid(
+    broadcaster_id: String,
+    target_user_id: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     TwitchService::start_raid(&broadcaster_id, &target_user_id)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    let obs = state.settings.lock().unwrap().obs.clone();
+    crate::services::obs_service::on_raid_started(obs);
+    Ok(())
 }
 
 #[tauri::command]
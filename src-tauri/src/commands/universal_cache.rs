@@ -1,14 +1,28 @@
+use crate::models::settings::AppState;
+use crate::services::cache_bundle::{self, CacheBundleSummary};
+use crate::services::channel_caches::{self, ChannelCache};
+use crate::services::download_pool;
 use crate::services::universal_cache_service::{
     assign_badge_metadata_positions, auto_sync_if_stale, cache_file, cache_item,
     cleanup_expired_entries, clear_universal_cache, export_manifest_for_github,
     get_all_cached_items_by_type, get_cached_file_path, get_cached_files_list, get_cached_item,
     get_cached_items_batch, get_universal_cache_dir, get_universal_cache_stats,
-    sync_universal_cache, CacheType, UniversalCacheEntry, UniversalCacheStats,
+    sync_universal_cache, verify_cache_integrity, CacheIntegrityReport, CacheType,
+    UniversalCacheEntry, UniversalCacheStats,
 };
-
+use serde::Serialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
-use tauri::command;
+use tauri::{command, State};
+
+/// What `cleanup_universal_cache` removed.
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheCleanup {
+    /// Manifest entries past their expiry.
+    pub expired_entries: usize,
+    /// Channels whose caches went for not being opened in time.
+    pub channel_caches: usize,
+}
 
 /// Open the universal cache folder in the OS file manager. Local paths cannot go
 /// through the shell plugin (its `open` scope only allows http/mailto/tel URLs),
@@ -40,6 +54,7 @@ pub async fn get_universal_cached_item(
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
@@ -61,6 +76,7 @@ pub async fn save_universal_cached_item(
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
@@ -87,9 +103,27 @@ pub async fn sync_universal_cache_data(cache_types: Vec<String>) -> Result<usize
     sync_universal_cache(types).await.map_err(|e| e.to_string())
 }
 
+/// Drop expired entries, and the caches of channels not opened within
+/// `CacheSettings.channel_expiry_days`. Returns how many of each went.
 #[command]
-pub async fn cleanup_universal_cache() -> Result<usize, String> {
-    cleanup_expired_entries().map_err(|e| e.to_string())
+pub async fn cleanup_universal_cache(state: State<'_, AppState>) -> Result<CacheCleanup, String> {
+    let settings = state.settings.lock().unwrap().cache.clone();
+    let expired = cleanup_expired_entries().map_err(|e| e.to_string())?;
+    let channels = tokio::task::spawn_blocking(move || channel_caches::cleanup(&settings))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+    Ok(CacheCleanup {
+        expired_entries: expired,
+        channel_caches: channels,
+    })
+}
+
+/// The channels with caches on disk, least recently opened first.
+#[command]
+pub async fn get_channel_caches(state: State<'_, AppState>) -> Result<Vec<ChannelCache>, String> {
+    let settings = state.settings.lock().unwrap().cache.clone();
+    channel_caches::list(&settings).map_err(|e| e.to_string())
 }
 
 #[command]
@@ -102,6 +136,17 @@ pub async fn get_universal_cache_statistics() -> Result<UniversalCacheStats, Str
     get_universal_cache_stats().map_err(|e| e.to_string())
 }
 
+/// Check the cache manifest against the files on disk; with `repair`, drop
+/// broken entries and delete stray and half-written files.
+#[command]
+pub async fn verify_universal_cache_integrity(
+    repair: bool,
+) -> Result<CacheIntegrityReport, String> {
+    verify_cache_integrity(repair)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[command]
 pub async fn assign_badge_positions() -> Result<usize, String> {
     assign_badge_metadata_positions()
@@ -115,24 +160,73 @@ pub async fn export_manifest(output_path: String) -> Result<(), String> {
     export_manifest_for_github(path).map_err(|e| e.to_string())
 }
 
+/// Write the whole cache, image files included, to a zip at `path` that
+/// `import_manifest_bundle` can load on another machine.
+#[command]
+pub async fn export_manifest_bundle(path: String) -> Result<CacheBundleSummary, String> {
+    tokio::task::spawn_blocking(move || cache_bundle::export(&PathBuf::from(path)))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// Prime the cache from a bundle made by `export_manifest_bundle`, without
+/// touching the network. Items already cached here are kept as they are.
+#[command]
+pub async fn import_manifest_bundle(path: String) -> Result<CacheBundleSummary, String> {
+    cache_bundle::import(&PathBuf::from(path))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Download a file into the universal cache through the download pool.
+/// `priority` is `"visible"` for images on screen now, `"background"` for
+/// warm-up, anything else (or none) for normal.
 #[command]
 pub async fn download_and_cache_file(
     cache_type: String,
     id: String,
     url: String,
     expiry_days: u32,
+    priority: Option<String>,
 ) -> Result<String, String> {
     let cache_type_enum = match cache_type.as_str() {
         "badge" => CacheType::Badge,
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
-    cache_file(cache_type_enum, id, url, expiry_days)
-        .await
-        .map_err(|e| e.to_string())
+    let priority = download_pool::Priority::parse(priority.as_deref());
+    download_pool::run(
+        priority,
+        "frontend",
+        cache_file(cache_type_enum, id, url, expiry_days),
+    )
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+/// Queue depth per priority and group, in-flight count, and whether the pool
+/// is paused or holding back for playback.
+#[command]
+pub fn get_prefetch_queue_status() -> download_pool::QueueStatus {
+    download_pool::status()
+}
+
+/// Stop starting queued background downloads (in-flight ones finish; images
+/// on screen still load).
+#[command]
+pub fn pause_prefetch() {
+    download_pool::pause();
+}
+
+#[command]
+pub fn resume_prefetch() {
+    download_pool::resume();
 }
 
 #[command]
@@ -142,6 +236,7 @@ pub async fn get_cached_file(cache_type: String, id: String) -> Result<Option<St
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
@@ -157,6 +252,7 @@ pub async fn get_cached_files(cache_type: String) -> Result<HashMap<String, Stri
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
@@ -175,6 +271,7 @@ pub fn get_all_universal_cached_items(
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
@@ -192,6 +289,7 @@ pub fn get_universal_cached_items_batch(
         "emote" => CacheType::Emote,
         "third-party-badge" => CacheType::ThirdPartyBadge,
         "cosmetic" => CacheType::Cosmetic,
+        "drop-reward" => CacheType::DropReward,
         _ => return Err(format!("Invalid cache type: {}", cache_type)),
     };
 
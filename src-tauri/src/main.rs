@@ -27,18 +27,17 @@
 #![allow(clippy::collapsible_match)]
 
 use commands::{
-    accounts::*, announcements::*, app::*, automation::*, badge_metadata::*, badge_service::*,
-    badges::*, cache::*, channel_panels::*, chat::*, chat_identity::*, components::*,
-    cosmetics_cache::*, diagnostic_logging::*, discord::*, drops::*, emoji::*, emote_prefetch::*,
-    emotes::*, eventsub::*, hype_train::*, identity::*, justlog::*, layout::*,
-    link_preview::*, logs::*, mod_log_storage::*, modroom::*, multi_nook::*, plugins::*,
-    profile_cache::*,
-    resub::*, screen_capture::*, session::*, settings::*, seventv::*, seventv_cosmetics::*,
-    seventv_cosmetics_fetch::*, song_id::*, streaming::*, subscriptions::*, twitch::*,
-    universal_cache::*,
-    user_profile::*, watch_streak::*, whisper_storage::*,
+    accounts::*, announcements::*, app::*, automation::*, backup::*, badge_metadata::*,
+    badge_service::*, badges::*, cache::*, channel_panels::*, chat::*, chat_identity::*,
+    components::*, cosmetics_cache::*, debug_api::*, diagnostic_logging::*, discord::*, drops::*,
+    emoji::*, emote_prefetch::*, emotes::*, eventsub::*, favorites::*, hype_train::*, identity::*,
+    justlog::*, layout::*, link_preview::*, logs::*, mod_log_storage::*, modroom::*, multi_nook::*,
+    obs::*, plugins::*, profile_cache::*, resub::*, screen_capture::*, session::*, settings::*,
+    setup::*, seventv::*, seventv_cosmetics::*, seventv_cosmetics_fetch::*, song_id::*,
+    streaming::*, subscriptions::*, twitch::*, universal_cache::*, user_profile::*,
+    watch_streak::*, whisper_storage::*,
 };
-use log::{debug, error};
+use log::{debug, error, info, warn};
 use models::settings::{AppState, Settings};
 use services::background_service::BackgroundService;
 use services::cache_service;
@@ -119,7 +118,33 @@ fn show_main_window(app: &tauri::AppHandle) {
     // config defines so the app shell, overlays, and player come back identically.
     // The WebView2 browser args are applied process-wide via env var, so no
     // per-window arg work is needed here.
-    //
+    match build_main_window(app) {
+        Ok(win) => {
+            debug!("[Main] Recreated main window on demand");
+            // Re-point the UI-hang watchdog at the new HWND. The old watchdog
+            // thread self-exits once its HWND is destroyed (see ui_hang_watchdog).
+            #[cfg(windows)]
+            if let Ok(hwnd) = win.hwnd() {
+                services::ui_hang_watchdog::start_for_hwnd(hwnd.0 as isize);
+            }
+        }
+        Err(e) => error!("[Main] Failed to recreate main window: {e}"),
+    }
+}
+
+/// `--headless` (or `STREAMNOOK_HEADLESS=1`): start without the main window so
+/// only the backend runs (background service, plugins and drops mining, the
+/// local API) on a dedicated mining machine. The tray's "Show StreamNook", or
+/// launching the app again, opens the UI on demand; closing it goes back to
+/// headless instead of quitting.
+fn headless_requested() -> bool {
+    std::env::args().any(|arg| arg == "--headless")
+        || std::env::var("STREAMNOOK_HEADLESS").is_ok_and(|v| v == "1")
+}
+
+/// Build the main window. The config entry has `create: false` (so headless
+/// mode can skip it); startup and `show_main_window` both build it here.
+fn build_main_window(app: &tauri::AppHandle) -> tauri::Result<tauri::WebviewWindow> {
     // URL: in a DEBUG build (`tauri dev`) runtime-created windows are NOT
     // auto-pointed at the Vite dev server the way config-defined windows are, so an
     // App URL loads the (unbuilt) bundled dist and renders blank white. Use the dev
@@ -134,26 +159,17 @@ fn show_main_window(app: &tauri::AppHandle) {
     } else {
         tauri::WebviewUrl::App("index.html".into())
     };
-    match tauri::WebviewWindowBuilder::new(app, "main", app_url)
+    let mut builder = tauri::WebviewWindowBuilder::new(app, "main", app_url)
         .title("StreamNook")
-    .inner_size(1600.0, 1000.0)
-    .min_inner_size(800.0, 600.0)
-    .center()
-    .resizable(true)
-    .decorations(false)
-    .build()
-    {
-        Ok(win) => {
-            debug!("[Main] Recreated main window on demand");
-            // Re-point the UI-hang watchdog at the new HWND. The old watchdog
-            // thread self-exits once its HWND is destroyed (see ui_hang_watchdog).
-            #[cfg(windows)]
-            if let Ok(hwnd) = win.hwnd() {
-                services::ui_hang_watchdog::start_for_hwnd(hwnd.0 as isize);
-            }
-        }
-        Err(e) => error!("[Main] Failed to recreate main window: {e}"),
+        .inner_size(1600.0, 1000.0)
+        .center()
+        .resizable(true)
+        .decorations(false)
+        .devtools(true);
+    if let Some(min) = services::window_modes::main_min_size(app) {
+        builder = builder.min_inner_size(min.width, min.height);
     }
+    builder.build()
 }
 
 /// Get-or-create the main window. Invoked from a MultiChat popout when an action
@@ -277,6 +293,7 @@ fn main() {
 
     // Initialize the logging system FIRST so all debug!/error! macros work
     services::diagnostic_logger::init_logging();
+    services::log_service::LogService::install_panic_hook();
 
     // Clean up any leftover files from previous update attempts
     cleanup_update_artifacts();
@@ -345,6 +362,12 @@ fn main() {
 
     // Apply persisted diagnostic logging setting immediately after loading settings
     services::diagnostic_logger::set_diagnostics_enabled(settings.error_reporting_enabled);
+    services::power_saver::set_enabled(settings.power_saver);
+    services::chatter_tracker::set_friends(&settings.chat_friends);
+    services::download_pool::set_concurrency(settings.prefetch_concurrency);
+    services::ad_detect::set_mute_during_ads(settings.video_player.mute_during_ads);
+    // Before the builder creates the first webview, which reads the args once.
+    services::playback_priority::apply_gpu_preference(settings.playback_resources.gpu);
 
     // Initialize drops service with persisted settings (including priority_games for favorites)
     let drops_service = Arc::new(TokioMutex::new(DropsService::new_with_settings(
@@ -379,6 +402,8 @@ fn main() {
     ));
     let eventsub_service_state = commands::eventsub::EventSubServiceState(eventsub_service.clone());
 
+    let headless = headless_requested();
+
     Builder::default()
         // Single-instance MUST be first: a streamnook:// deep link opened while the
         // app is already running launches a second process with the URL as its only
@@ -416,6 +441,7 @@ fn main() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(services::global_hotkeys::plugin())
         .manage(live_notification_service.clone())
         .manage(whisper_service.clone())
         .manage(layout_service.clone())
@@ -424,6 +450,20 @@ fn main() {
         .manage(eventsub_service_state)
         .setup(move |app| {
             let app_handle = app.handle().clone();
+            // Services emit through the event bus; route it to the webviews
+            // and backend listeners.
+            services::event_bus::install(Arc::new(services::event_bus::TauriBus(
+                app_handle.clone(),
+            )));
+            // The main window is built here rather than from the config so
+            // headless mode can skip it entirely.
+            if headless {
+                info!("[Main] Headless mode: not creating the main window");
+                // Nothing will paint; release the deferred startup work now.
+                services::startup::mark_first_paint();
+            } else {
+                build_main_window(&app_handle)?;
+            }
             // Runtime stall detector: measures backend freezes (tokio-blocked vs
             // whole-process) and records them to the capture file. Started here,
             // inside the tokio runtime Tauri set up.
@@ -440,22 +480,19 @@ fn main() {
                     }
                 }
             }
-            // Hand the stream server an app handle so the ad auto-pivot can emit
-            // its `ad-pivot` reload event to the player.
-            services::stream_server::set_app_handle(app_handle.clone());
             services::providers::set_app_handle(app_handle.clone());
             let live_notif_service = live_notification_service.clone();
 
             // Start the shared 7TV EventAPI WebSocket client (live emote set
             // updates, and later cosmetics). It idle-connects and subscribes
             // per channel as the IRC service JOINs/PARTs them.
-            services::seventv_eventapi::init(app_handle.clone(), emote_service.clone());
+            services::seventv_eventapi::init(emote_service.clone());
 
             // Dedicated EventSub socket for the moderator view (channel.moderate).
             // Tied to chat, not the watched stream: it subscribes per channel the
             // IRC service JOINs and the user moderates, so the mod log enriches
             // with the acting moderator in single / offline / MultiNook / popout.
-            services::eventsub_moderation::init(app_handle.clone());
+            services::eventsub_moderation::init();
 
             // Register deep link scheme on Windows
             #[cfg(windows)]
@@ -507,6 +544,11 @@ fn main() {
                 drops_service.clone(),
             )));
 
+            // Lifetime drops stats: saved periodically, and on exit below.
+            tauri::async_runtime::spawn(DropsService::run_statistics_saver(
+                drops_service.clone(),
+            ));
+
             let twitch_auth =
                 services::twitch_auth_service::TwitchAuthService::new(app_handle.clone());
 
@@ -564,6 +606,50 @@ fn main() {
                     .await;
             });
 
+            // Refresh tokens and sockets right away when the system wakes
+            // from sleep instead of waiting for each service's next poll.
+            services::resume_watch::start(app_handle.clone());
+            // Away detection from OS idle time.
+            services::idle_monitor::start(app_handle.clone());
+            services::game_mode::start(app_handle.clone());
+            services::channel_caches::start(app_handle.clone());
+            services::whats_new::start(app_handle.clone());
+            services::raid_events::start(app_handle.clone());
+
+            // System-wide hotkeys saved from a previous session.
+            let saved_hotkeys = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .unwrap()
+                .global_hotkeys
+                .clone();
+            services::global_hotkeys::register_saved(&app_handle, &saved_hotkeys);
+
+            // The window pin, likewise.
+            if app.state::<AppState>().settings.lock().unwrap().always_on_top {
+                if let Err(e) = services::window_modes::set_always_on_top(&app_handle, true) {
+                    warn!("Couldn't pin the window on top: {}", e);
+                }
+            }
+
+            // Local control API (Stream Deck), if enabled. Always on when
+            // headless: it's the only way to drive the app without the UI.
+            if headless {
+                services::local_api::keep_running();
+            }
+            let local_api = app.state::<AppState>().settings.lock().unwrap().local_api.clone();
+            let local_api_app = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = services::local_api::apply(&local_api_app, &local_api).await {
+                    warn!("[LocalApi] {}", e);
+                }
+            });
+
+            // Media keys / system media flyout for the watched stream.
+            services::media_session::init(&app_handle);
+            services::window_badge::start(app_handle.clone());
+
             // Start live notification service
             let live_app_handle = app_handle.clone();
             let app_state_for_live_notif_clone = app_state_for_live_notif.clone();
@@ -586,8 +672,8 @@ fn main() {
 
                 debug!("[Main] Starting token health verification...");
 
-                // Wait a moment to let the app fully initialize
-                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                // Wait until the window has painted
+                services::startup::wait_ready().await;
 
                 match TwitchService::verify_token_health().await {
                     Ok(status) => {
@@ -624,17 +710,9 @@ fn main() {
             // badge-drop detection is handled centrally by the bot and delivered
             // over the badge feed, so no client hits Helix for badges at startup.
 
-            // Initialize unified badge service
-            tauri::async_runtime::spawn(async move {
-                use commands::badge_service::initialize_badge_service;
-
-                debug!("[Main] Initializing unified badge service...");
-
-                // Wait a moment for token to be available
-                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-
-                initialize_badge_service().await;
-            });
+            // Badge databases and the drops campaign list load after the
+            // frontend's first paint, not during it (services::startup).
+            services::startup::start(app_handle.clone());
 
             // System tray. Keeps the app running when the user closes the main
             // window while StreamNook MultiChat popouts are still open. Left
@@ -700,16 +778,33 @@ fn main() {
             get_app_authors,
             fetch_exchange_rates,
             get_window_size,
+            set_window_badge,
+            set_always_on_top,
+            enter_mini_mode,
+            exit_mini_mode,
+            get_monitors,
+            set_fullscreen,
+            notify_first_paint,
             take_pending_watch_link,
             ensure_main_window,
             close_main_window,
             calculate_aspect_ratio_size,
             calculate_aspect_ratio_size_preserve_video,
             get_system_info,
+            get_feature_flags,
+            get_first_run_state,
+            run_setup_step,
+            skip_setup_step,
+            complete_first_run,
+            reset_feature_flag,
+            run_dependency_check,
+            fix_dependency,
             get_emoji_image,
             read_clipboard_text_native,
             // Twitch commands
             twitch_login,
+            cancel_login,
+            start_combined_login,
             create_clip,
             get_live_broadcast,
             create_vod_clip,
@@ -742,6 +837,10 @@ fn main() {
             modroom_list_moderated,
             modroom_get_room_token,
             get_followed_streams,
+            get_viewer_count_history,
+            get_channel_uptime_stats,
+            get_share_info,
+            open_in_browser_at_timestamp,
             get_channel_info,
             get_user_info,
             get_recommended_streams,
@@ -753,6 +852,8 @@ fn main() {
             get_streams_by_game,
             search_channels,
             search_categories,
+            resolve_game,
+            set_game_alias,
             get_category_info,
             get_user_by_id,
             get_user_by_login,
@@ -760,6 +861,9 @@ fn main() {
             get_channel_vips,
             follow_channel,
             unfollow_channel,
+            unfollow_channels,
+            undo_unfollow_channels,
+            get_inactive_follows,
             check_following_status,
             get_all_followed_channels,
             get_offline_last_broadcasts,
@@ -788,6 +892,26 @@ fn main() {
             start_stream,
             resolve_clip_media,
             stop_stream,
+            get_ad_free_status,
+            set_media_session,
+            subscribe_stream_info,
+            unsubscribe_stream_info,
+            capture_stream_frame,
+            add_stream_bookmark,
+            get_watch_sessions,
+            export_session_highlights,
+            write_recording_chapters,
+            start_recording,
+            get_active_recordings,
+            list_recordings,
+            get_recording_metadata,
+            delete_recording,
+            play_recording,
+            postprocess_recording,
+            get_postprocess_jobs,
+            retry_postprocess_job,
+            get_continue_watching,
+            set_vod_position,
             get_ad_detection,
             get_stream_low_latency,
             set_experimental_low_latency,
@@ -806,12 +930,23 @@ fn main() {
             get_active_multi_nooks,
             register_active_channel,
             unregister_active_channel,
+            get_raid_event_next,
+            get_raid_event_stats,
+            get_squad,
+            launch_squad_multiview,
             // Chat commands
             start_chat,
             stop_chat,
             send_chat_message,
             join_chat_channel,
             leave_chat_channel,
+            start_chat_only,
+            stop_chat_only,
+            get_chat_only_sessions,
+            open_chat_tab,
+            close_chat_tab,
+            list_chat_tabs,
+            autocomplete,
             start_multi_chat,
             provider_chat_connect,
             provider_chat_disconnect,
@@ -863,10 +998,14 @@ fn main() {
             get_channel_vips,
             get_chatters_by_role,
             get_channel_chatters,
+            get_chatters,
             send_chat_announcement,
             send_shoutout,
             start_commercial,
             start_raid,
+            obs_switch_scene,
+            obs_get_scenes,
+            obs_get_stream_status,
             cancel_raid,
             create_stream_marker,
             warn_chat_user,
@@ -882,12 +1021,25 @@ fn main() {
             save_settings,
             get_settings_dir,
             open_settings_folder,
+            open_script_hooks_folder,
+            get_local_api_info,
+            get_webhook_deliveries,
+            create_backup,
+            restore_backup,
             export_settings,
             import_settings,
+            import_from_client,
+            get_settings_sync_status,
+            push_settings_sync,
+            pull_settings_sync,
+            pin_channel,
+            reorder_favorites,
+            get_favorites_with_status,
             get_current_app_version,
             get_latest_app_version,
             download_and_install_app_update,
             get_release_notes,
+            get_whats_new,
             send_test_notification,
             // Badge commands
             fetch_global_badges,
@@ -929,6 +1081,7 @@ fn main() {
             load_badges_from_cache,
             clear_cache,
             get_cache_statistics,
+            get_memory_cache_stats,
             save_favorite_emotes_cache,
             load_favorite_emotes_cache,
             add_favorite_emote_cache,
@@ -938,12 +1091,19 @@ fn main() {
             save_universal_cached_item,
             sync_universal_cache_data,
             cleanup_universal_cache,
+            get_channel_caches,
             clear_all_universal_cache,
             get_universal_cache_statistics,
+            verify_universal_cache_integrity,
             open_universal_cache_folder,
             assign_badge_positions,
             export_manifest,
+            export_manifest_bundle,
+            import_manifest_bundle,
             download_and_cache_file,
+            get_prefetch_queue_status,
+            pause_prefetch,
+            resume_prefetch,
             get_cached_file,
             get_cached_files,
             get_all_universal_cached_items,
@@ -967,17 +1127,36 @@ fn main() {
             clear_user_profile_cache_for_user,
             // Drops commands
             get_drops_settings,
+            import_twitch_drops_miner,
             update_drops_settings,
             get_active_drop_campaigns,
+            get_unlinked_campaigns,
+            get_drops_enabled_streams,
+            get_campaign_availability,
+            preview_channel_scores,
+            check_campaign_eligibility,
+            get_blacklisted_streamers,
+            remove_from_blacklist,
+            blacklist_streamer,
+            switch_mining_channel,
+            pause_mining,
+            resume_mining,
+            get_mining_efficiency,
+            add_to_wishlist,
+            remove_from_wishlist,
+            get_wishlist_status,
             refresh_drops_connection_status,
             get_drops_inventory,
+            get_inventory_offline,
             get_drop_progress,
             claim_drop,
             check_channel_points,
             claim_channel_points,
             get_drops_statistics,
             get_claimed_drops,
+            get_campaign_history,
             get_channel_points_history,
+            get_points_trend,
             get_channel_points_balance,
             get_all_channel_points_balances,
             record_channel_points_balance,
@@ -986,6 +1165,10 @@ fn main() {
             stop_drops_monitoring,
             update_monitoring_channel,
             report_player_playing,
+            get_websocket_status,
+            preview_mined_channel,
+            get_overlay_state,
+            promote_mining_to_viewing,
             // Automation commands
             // Drops Authentication commands
             start_drops_device_flow,
@@ -1004,6 +1187,7 @@ fn main() {
             // Channel Points Rewards commands
             get_channel_rewards,
             redeem_channel_reward,
+            redeem_reward,
             send_highlighted_message,
             unlock_random_emote,
             get_modifiable_emotes,
@@ -1035,6 +1219,7 @@ fn main() {
             fetch_channel_emotes,
             get_emote_by_name,
             clear_emote_cache,
+            prepare_animated_emotes,
             // Emote prefetch (AFK bulk cache) commands
             emote_prefetch_plan,
             emote_prefetch_start,
@@ -1084,6 +1269,12 @@ fn main() {
             get_logs_by_level,
             get_recent_activity,
             clear_logs,
+            get_error_records,
+            create_issue_draft,
+            set_hotkey,
+            check_audio_cue_sound,
+            get_chat_friends,
+            set_chat_friend,
             // EventSub commands
             connect_eventsub,
             disconnect_eventsub,
@@ -1117,6 +1308,8 @@ fn main() {
             // Diagnostic Logging commands
             set_diagnostics_enabled,
             is_diagnostics_enabled,
+            // Developer API console
+            debug_api_request,
             // Watch Streak commands
             get_watch_streak,
             get_watch_streaks_batch,
@@ -1170,6 +1363,7 @@ fn main() {
                 // JOINed (and their IRC traffic flowing) forever. Sweep them;
                 // channels with no remaining consumers PART.
                 let gone = label.clone();
+                services::chat_tabs::forget_window(&gone);
                 tauri::async_runtime::spawn(async move {
                     services::irc_service::IrcService::release_window_claims(&gone, None).await;
                 });
@@ -1202,10 +1396,21 @@ fn main() {
                             // (which would tear down the IRC connection too).
                             let _ = main_win.emit("main-hiding-to-tray", ());
                             let _ = main_win.hide();
+                            services::power_saver::set_window_background(true);
                         }
                     }
                 }
 
+                // Minimize/restore arrives as a resize; showing from the tray
+                // focuses the window. Either way, re-check whether it's in view.
+                if matches!(event, WindowEvent::Resized(_) | WindowEvent::Focused(_)) {
+                    if let Some(main_win) = app_handle.get_webview_window("main") {
+                        let background = main_win.is_minimized().unwrap_or(false)
+                            || !main_win.is_visible().unwrap_or(true);
+                        services::power_saver::set_window_background(background);
+                    }
+                }
+
             } else if label.starts_with("multichat-") {
                 if let WindowEvent::Destroyed = event {
                     // Tell the main window this popout is gone so it can
@@ -1241,14 +1446,40 @@ fn main() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|app_handle, event| {
+        .run(move |app_handle, event| {
+            // Closing a window opened from the tray returns a headless instance
+            // to headless; an explicit exit (tray Quit) still goes through.
+            if let tauri::RunEvent::ExitRequested { code: None, api, .. } = &event {
+                if headless {
+                    api.prevent_exit();
+                    return;
+                }
+            }
             if let tauri::RunEvent::Exit = event {
-                // Ask running plugin processes to shut down before the app
-                // process dies, waiting briefly so well-behaved plugins exit
-                // gracefully (stragglers are killed with the supervisor).
+                // Save lifetime drops stats and the mining audit, then ask
+                // running plugin processes to shut down before the app process
+                // dies, waiting briefly so well-behaved plugins exit gracefully
+                // (stragglers are killed with the supervisor).
                 let state = app_handle.state::<AppState>();
                 let host = state.plugin_host.clone();
+                let drops_service = state.drops_service.clone();
                 tauri::async_runtime::block_on(async move {
+                    // A task stuck holding the service lock mustn't stall the
+                    // exit; the periodic save has at most a minute to lose.
+                    match tokio::time::timeout(
+                        std::time::Duration::from_secs(1),
+                        drops_service.lock(),
+                    )
+                    .await
+                    {
+                        Ok(guard) => {
+                            let service = guard.clone();
+                            drop(guard);
+                            service.save_statistics().await;
+                        }
+                        Err(_) => warn!("[Main] Drops service busy at exit, stats not saved"),
+                    }
+                    services::mining_audit::flush();
                     host.shutdown_all().await;
                     for _ in 0..20 {
                         if !host.has_running().await {
@@ -82,6 +82,38 @@ pub struct MessageMetadata {
     /// System message for subscriptions/donations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_message: Option<String>,
+    /// Hints for the chat virtualizer, see services::render_hints
+    #[serde(default)]
+    pub render_hints: RenderHints,
+}
+
+/// Precomputed so the frontend's virtualizer can size and schedule rows
+/// without walking segments or tags itself
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct RenderHints {
+    /// Names the logged-in user, or replies to them
+    #[serde(default)]
+    pub mention: bool,
+    /// Animated emotes and cheermotes in the message
+    #[serde(default)]
+    pub animated_emotes: u32,
+    /// Sent by the broadcaster or a moderator, announcements included. The
+    /// sender's role only; moderation events themselves aren't chat messages
+    #[serde(default)]
+    pub from_moderator: bool,
+    #[serde(default)]
+    pub length: LengthClass,
+}
+
+/// Rough rendered length, for picking a row height estimate
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LengthClass {
+    /// Fits on a line in a typical chat width
+    #[default]
+    Short,
+    Medium,
+    Long,
 }
 
 /// Represents a parsed segment of a chat message
@@ -99,6 +131,9 @@ pub enum MessageSegment {
         emote_url: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         is_zero_width: Option<bool>,
+        /// Known only for providers that report it (see `Emote::animated`).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        animated: Option<bool>,
     },
     Emoji {
         content: String,
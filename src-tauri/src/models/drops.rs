@@ -23,6 +23,23 @@ pub struct DropCampaign {
     pub details_url: Option<String>, // "About this drop" link
     #[serde(default)]
     pub account_link: Option<String>, // publisher "connect account" URL (Twitch accountLinkURL)
+    /// Set while drop minutes are accruing on this campaign but the game account
+    /// isn't connected, so the watch time can't turn into rewards.
+    #[serde(default)]
+    pub unlinked_warning: bool,
+}
+
+impl DropCampaign {
+    /// Whether watching this broadcaster earns toward the campaign: any
+    /// channel in the campaign's game, or one on its list when it has one.
+    /// Doesn't check the game itself.
+    pub fn allows_channel(&self, user_id: &str, login: &str) -> bool {
+        !self.is_acl_based
+            || self
+                .allowed_channels
+                .iter()
+                .any(|c| c.id == user_id || c.name.eq_ignore_ascii_case(login))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -123,6 +140,10 @@ pub struct ChannelPointsClaim {
     pub points_earned: i32,
     pub claimed_at: DateTime<Utc>,
     pub claim_type: ChannelPointsClaimType,
+    /// Channel balance right after this entry, when the source reported it.
+    /// Feeds `get_points_trend`.
+    #[serde(default)]
+    pub balance_after: Option<i32>,
 }
 
 /// Result of claiming a bonus chest. `points_earned` is the exact amount the
@@ -141,9 +162,95 @@ pub enum ChannelPointsClaimType {
     Raid,       // Participated in raid
     Prediction, // Prediction reward
     Bonus,      // Bonus chest claim
+    Redemption, // Points spent (reward redemption / prediction); points_earned is negative
     Other,
 }
 
+/// One sample of a channel's balance over time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsTrendSample {
+    pub at: DateTime<Utc>,
+    pub delta: i32,
+    pub balance: Option<i32>,
+}
+
+/// Net earned/spent totals and the balance series for one channel, built from
+/// the session's points history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PointsTrend {
+    pub channel_id: String,
+    pub current_balance: Option<i32>,
+    pub total_earned: i32,
+    pub total_spent: i32,
+    pub net: i32,
+    pub samples: Vec<PointsTrendSample>,
+}
+
+/// A glance at the channel the drops plugin is collecting on: its live preview
+/// image, cache-busted so re-fetching every `refresh_secs` shows a new frame.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinedChannelPreview {
+    pub channel_login: String,
+    pub thumbnail_url: String,
+    pub refresh_secs: u64,
+}
+
+/// The mined channel `promote_mining_to_viewing` handed to the player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromotedChannel {
+    pub channel_id: String,
+    pub channel_login: String,
+}
+
+/// An active campaign that needs its game account linked before its drops can
+/// be earned, with the publisher's link page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlinkedCampaign {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub game_name: String,
+    pub image_url: String,
+    pub account_link: Option<String>,
+    pub end_at: DateTime<Utc>,
+}
+
+/// A campaign a live stream earns toward, as listed on the drops browse page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamCampaign {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub image_url: String,
+    pub end_at: DateTime<Utc>,
+    pub is_account_connected: bool,
+}
+
+/// A live drops-enabled stream with the active campaigns it counts for. Empty
+/// `campaigns` means Twitch flags the stream but no campaign the user can see
+/// covers it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropsEnabledStream {
+    pub stream: crate::models::stream::TwitchStream,
+    pub campaigns: Vec<StreamCampaign>,
+}
+
+/// One page of `get_drops_enabled_streams`; `cursor` fetches the next.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropsStreamsPage {
+    pub streams: Vec<DropsEnabledStream>,
+    pub cursor: Option<String>,
+}
+
+/// A user-set balance goal for a channel (e.g. the cost of a reward they want).
+/// `channel-points-target-reached` fires when the balance rises across it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PointsTarget {
+    pub channel_id: String,
+    pub target: i32,
+    /// Free-form note shown in the alert, e.g. the reward's title.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
 /// A custom channel reward that users can redeem with channel points
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChannelReward {
@@ -187,6 +294,9 @@ pub struct UnlockedEmote {
 pub struct DropsStatistics {
     pub total_drops_claimed: i32,
     pub total_channel_points_earned: i32,
+    /// Drop-progress minutes earned over the app's lifetime.
+    #[serde(default)]
+    pub total_minutes_watched: i64,
     pub active_campaigns: i32,
     pub drops_in_progress: i32,
     pub recent_claims: Vec<ClaimedDrop>,
@@ -209,6 +319,36 @@ pub struct PriorityChannel {
     pub display_name: String,
 }
 
+/// Weights for ranking the channels a campaign can be mined on; the highest
+/// total wins. See services::channel_scoring for how each term is worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChannelScoreWeights {
+    /// Added for a channel on `priority_channels`.
+    pub priority_bonus: f64,
+    /// Added for a channel named on the campaign's own list.
+    pub acl_bonus: f64,
+    /// Times log10(viewers + 1). Negative favors small channels.
+    pub viewer_weight: f64,
+    /// Times the broadcast's 0..1 uptime penalty, since a long stream is
+    /// likelier to end soon (see stream_uptime_service).
+    pub uptime_penalty: f64,
+    /// Added for the first preferred language, less for each one after.
+    pub language_bonus: f64,
+}
+
+impl Default for ChannelScoreWeights {
+    fn default() -> Self {
+        Self {
+            priority_bonus: 100.0,
+            acl_bonus: 50.0,
+            viewer_weight: 10.0,
+            uptime_penalty: 20.0,
+            language_bonus: 25.0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DropsSettings {
     pub auto_claim_drops: bool,
@@ -253,12 +393,126 @@ pub struct DropsSettings {
     // Recovery settings
     #[serde(default)]
     pub recovery_settings: RecoverySettings,
+    /// Per-channel balance goals; see `PointsTarget`.
+    #[serde(default)]
+    pub points_targets: Vec<PointsTarget>,
+    /// When true, automatic campaign selection skips campaigns whose game
+    /// account isn't connected (their minutes can't pay out). A campaign the
+    /// user starts by hand is still honored.
+    #[serde(default)]
+    pub skip_unlinked_campaigns: bool,
+    /// When true, mining is handed the followed channels live with an active
+    /// campaign (`preferred_channel_ids`) to try first, so points and watch
+    /// streaks build where the user watches anyway. Without one live, the
+    /// usual channel selection applies.
+    #[serde(default)]
+    pub prefer_followed_channels: bool,
+    /// Broadcast languages (ISO 639-1, "en", "de") mining should favor, most
+    /// wanted first. Handed to the plugin as `preferred_languages` and used to
+    /// order `preferred_channel_ids`; empty means no preference.
+    #[serde(default)]
+    pub preferred_languages: Vec<String>,
+    /// Only mine on channels with at least this many viewers.
+    #[serde(default)]
+    pub min_viewers: Option<u32>,
+    /// Only mine on channels with at most this many viewers, for users who'd
+    /// rather their watch time help small streamers.
+    #[serde(default)]
+    pub max_viewers: Option<u32>,
+    /// How channels are ranked for mining; see `ChannelScoreWeights`.
+    #[serde(default)]
+    pub score_weights: ChannelScoreWeights,
+    /// Raise `drop-progress-milestone` as the drop being earned passes the
+    /// milestones below, so the user can switch to watching for the final
+    /// stretch.
+    #[serde(default)]
+    pub notify_on_progress_milestones: bool,
+    /// Percent-complete milestones.
+    #[serde(default = "default_milestone_percents")]
+    pub progress_milestone_percents: Vec<u8>,
+    /// Also notify this many minutes before the drop completes (`None` to skip).
+    #[serde(default = "default_milestone_minutes_remaining")]
+    pub progress_milestone_minutes_remaining: Option<i32>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_milestone_percents() -> Vec<u8> {
+    vec![50, 90]
+}
+
+fn default_milestone_minutes_remaining() -> Option<i32> {
+    Some(15)
+}
+
+/// A point along a drop's progress; see `DropsSettings::crossed_milestones`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProgressMilestone {
+    Percent { percent: u8 },
+    MinutesRemaining { minutes: i32 },
+}
+
+impl DropsSettings {
+    /// `preferred_languages`, trimmed and lowercased, without blanks.
+    pub fn language_codes(&self) -> Vec<String> {
+        self.preferred_languages
+            .iter()
+            .map(|code| code.trim().to_lowercase())
+            .filter(|code| !code.is_empty())
+            .collect()
+    }
+
+    /// Whether a channel with `viewers` is within `min_viewers`..=`max_viewers`.
+    pub fn viewers_in_range(&self, viewers: u32) -> bool {
+        self.min_viewers.is_none_or(|min| viewers >= min)
+            && self.max_viewers.is_none_or(|max| viewers <= max)
+    }
+
+    /// Where a stream in `language` ranks: its place in `preferred_languages`,
+    /// or after all of them when it isn't there or isn't known.
+    pub fn language_rank(&self, language: Option<&str>) -> usize {
+        let codes = self.language_codes();
+        language
+            .and_then(|lang| {
+                codes
+                    .iter()
+                    .position(|code| code.eq_ignore_ascii_case(lang))
+            })
+            .unwrap_or(codes.len())
+    }
+
+    /// Milestones passed when a drop needing `required` minutes moves from
+    /// `before` to `now`. Completion itself is `drop-ready`'s job, so a jump
+    /// that finishes the drop reports nothing.
+    pub fn crossed_milestones(
+        &self,
+        before: i32,
+        now: i32,
+        required: i32,
+    ) -> Vec<ProgressMilestone> {
+        if required <= 0 || now <= before || now >= required {
+            return Vec::new();
+        }
+        let crossed = |at: i32| before < at && at <= now;
+        let mut milestones: Vec<ProgressMilestone> = self
+            .progress_milestone_percents
+            .iter()
+            .filter(|&&percent| percent > 0 && percent < 100)
+            .filter(|&&percent| crossed((required * percent as i32 + 99) / 100))
+            .map(|&percent| ProgressMilestone::Percent { percent })
+            .collect();
+        if let Some(minutes) = self.progress_milestone_minutes_remaining {
+            if minutes > 0 && crossed(required - minutes) {
+                milestones.push(ProgressMilestone::MinutesRemaining { minutes });
+            }
+        }
+        milestones
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum PriorityMode {
     PriorityOnly,  // Only collect priority games
@@ -292,6 +546,16 @@ impl Default for DropsSettings {
             prefer_favorites: false,
             // Recovery defaults
             recovery_settings: RecoverySettings::default(),
+            points_targets: Vec::new(),
+            skip_unlinked_campaigns: false,
+            prefer_followed_channels: false,
+            preferred_languages: Vec::new(),
+            min_viewers: None,
+            max_viewers: None,
+            score_weights: ChannelScoreWeights::default(),
+            notify_on_progress_milestones: false,
+            progress_milestone_percents: default_milestone_percents(),
+            progress_milestone_minutes_remaining: default_milestone_minutes_remaining(),
         }
     }
 }
@@ -348,8 +612,9 @@ pub enum RecoveryMode {
     ManualOnly,
 }
 
-/// A temporarily blacklisted streamer (in-memory, not persisted)
-#[derive(Debug, Clone)]
+/// A temporarily blacklisted streamer. The drops blacklist
+/// (`services::drops_blacklist`) persists these until they expire.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlacklistedStreamer {
     pub channel_id: String,
     pub channel_name: String,
@@ -369,6 +634,8 @@ pub enum BlacklistReason {
     StaleProgress,
     /// Streamer changed to non-drops game category
     GameCategoryChanged,
+    /// Added by the user
+    Manual,
 }
 
 /// A temporarily deprioritized campaign
@@ -454,7 +721,7 @@ impl Default for AutomationStatusExtended {
 /// Tracking state for the recovery watchdog (in-memory)
 #[derive(Debug, Clone)]
 pub struct RecoveryWatchdogState {
-    /// Blacklisted streamers (temporary, in-memory)
+    /// Blacklisted streamers (temporary; persisted by `services::drops_blacklist`)
     pub blacklisted_streamers: HashMap<String, BlacklistedStreamer>,
     /// Deprioritized campaigns (temporary, in-memory)
     pub deprioritized_campaigns: HashMap<String, DeprioritizedCampaign>,
@@ -743,6 +1010,16 @@ pub struct InventoryResponse {
     pub completed_drops: Vec<CompletedDrop>,
 }
 
+/// The last successfully synced inventory, for the drops page to render
+/// without network. `local_images` maps each remote image URL that has been
+/// prefetched to its cached file path; URLs missing from it weren't cached yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineInventory {
+    pub inventory: InventoryResponse,
+    pub synced_at: DateTime<Utc>,
+    pub local_images: HashMap<String, String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GameEventDrop {
@@ -762,3 +1039,73 @@ pub struct CompletedDrop {
     pub last_awarded_at: DateTime<Utc>,
     pub total_count: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn milestones_fire_once_when_crossed() {
+        let settings = DropsSettings::default();
+        // 120-minute drop: 50% at 60, 90% at 108, 15 remaining at 105.
+        assert_eq!(
+            settings.crossed_milestones(50, 62, 120),
+            [ProgressMilestone::Percent { percent: 50 }]
+        );
+        assert!(settings.crossed_milestones(62, 70, 120).is_empty());
+        assert_eq!(
+            settings.crossed_milestones(100, 110, 120),
+            [
+                ProgressMilestone::Percent { percent: 90 },
+                ProgressMilestone::MinutesRemaining { minutes: 15 },
+            ]
+        );
+        assert!(settings.crossed_milestones(110, 120, 120).is_empty());
+    }
+
+    #[test]
+    fn preferred_languages_rank_first_in_order() {
+        let settings = DropsSettings {
+            preferred_languages: vec![" DE".to_string(), "en".to_string(), String::new()],
+            ..Default::default()
+        };
+        assert_eq!(settings.language_codes(), ["de", "en"]);
+        assert_eq!(settings.language_rank(Some("de")), 0);
+        assert_eq!(settings.language_rank(Some("EN")), 1);
+        assert_eq!(settings.language_rank(Some("fr")), 2);
+        assert_eq!(settings.language_rank(None), 2);
+        assert_eq!(DropsSettings::default().language_rank(Some("en")), 0);
+    }
+
+    #[test]
+    fn viewer_bounds_are_inclusive_and_optional() {
+        let mut settings = DropsSettings::default();
+        assert!(settings.viewers_in_range(0) && settings.viewers_in_range(250_000));
+        settings.max_viewers = Some(50);
+        assert!(settings.viewers_in_range(50));
+        assert!(!settings.viewers_in_range(51));
+        settings.min_viewers = Some(5);
+        assert!(!settings.viewers_in_range(4));
+        assert!(settings.viewers_in_range(5));
+    }
+
+    #[test]
+    fn acl_campaigns_only_allow_listed_channels() {
+        let mut campaign: DropCampaign = serde_json::from_value(serde_json::json!({
+            "id": "c", "name": "C", "game_id": "g", "game_name": "G",
+            "description": "", "image_url": "",
+            "start_at": "2026-01-01T00:00:00Z", "end_at": "2026-02-01T00:00:00Z",
+            "time_based_drops": [],
+        }))
+        .unwrap();
+        assert!(campaign.allows_channel("1", "anyone"));
+        campaign.is_acl_based = true;
+        campaign.allowed_channels = vec![AllowedChannel {
+            id: "2".to_string(),
+            name: "Listed".to_string(),
+        }];
+        assert!(campaign.allows_channel("2", ""));
+        assert!(campaign.allows_channel("", "listed"));
+        assert!(!campaign.allows_channel("1", "anyone"));
+    }
+}
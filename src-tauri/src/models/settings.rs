@@ -91,6 +91,10 @@ pub struct VideoPlayerSettings {
     /// and governor target. Default 2.5.
     #[serde(default = "default_ll_target_latency")]
     pub ll_target_latency: f32,
+    /// Mute the player while a detected ad break runs and restore the volume
+    /// when it ends. Off by default.
+    #[serde(default)]
+    pub mute_during_ads: bool,
 }
 
 fn default_ll_target_latency() -> f32 {
@@ -110,6 +114,7 @@ impl Default for VideoPlayerSettings {
             audio_boost: AudioBoostSettings::default(),
             experimental_low_latency: false,
             ll_target_latency: 6.0,
+            mute_during_ads: false,
         }
     }
 }
@@ -118,6 +123,18 @@ impl Default for VideoPlayerSettings {
 pub struct CacheSettings {
     pub enabled: bool,
     pub expiry_days: u32,
+    /// Days a channel's own caches (emote dictionary, channel badges) are
+    /// kept after it was last opened; 0 keeps them forever. See
+    /// services::channel_caches.
+    #[serde(default = "default_channel_expiry_days")]
+    pub channel_expiry_days: u32,
+    /// Channel ids whose caches are never cleaned up.
+    #[serde(default)]
+    pub pinned_channel_caches: Vec<String>,
+}
+
+fn default_channel_expiry_days() -> u32 {
+    30
 }
 
 impl Default for CacheSettings {
@@ -125,10 +142,99 @@ impl Default for CacheSettings {
         Self {
             enabled: true,
             expiry_days: 7,
+            channel_expiry_days: default_channel_expiry_days(),
+            pinned_channel_caches: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessPriority {
+    Idle,
+    BelowNormal,
+    #[default]
+    Normal,
+    AboveNormal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GpuPreference {
+    /// Whatever the system picks.
+    #[default]
+    Default,
+    /// The integrated GPU, leaving the dedicated one to games.
+    PowerSaving,
+    HighPerformance,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PlaybackResourceSettings {
+    /// Priority of the app and its webview processes while a stream plays.
+    #[serde(default)]
+    pub priority: ProcessPriority,
+    /// Which GPU the webview decodes and draws on (Windows). Read at launch.
+    #[serde(default)]
+    pub gpu: GpuPreference,
+}
+
+/// What mined playback drops to while a game runs, sent to the drops plugin
+/// as `drops.set-playback`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMiningPlayback {
+    /// Leave it as it is.
+    Keep,
+    #[default]
+    AudioOnly,
+    /// No playback at all; watch payloads only.
+    None,
+}
+
+/// Shrink the app's footprint while one of `executables` runs (see
+/// services::game_mode).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GameModeSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Executable names (`eldenring.exe`, or without `.exe`), matched without
+    /// regard to case or folder.
+    #[serde(default)]
+    pub executables: Vec<String>,
+    /// Hold emote prefetch and favorite warm-up.
+    #[serde(default = "default_true")]
+    pub pause_prefetch: bool,
+    #[serde(default)]
+    pub mining_playback: GameMiningPlayback,
+    /// Stretch background polls as power saver does.
+    #[serde(default = "default_true")]
+    pub slow_polls: bool,
+}
+
+impl Default for GameModeSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            executables: Vec::new(),
+            pause_prefetch: true,
+            mining_playback: GameMiningPlayback::default(),
+            slow_polls: true,
         }
     }
 }
 
+/// How a channel plays regardless of Turbo/sub detection.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdFreeOverride {
+    /// Always Twitch's own playback; a playback plugin never takes it. For a
+    /// sub the detection misses, or a channel whose ads the user accepts.
+    Direct,
+    /// Hand it to a playback plugin even when the viewer looks entitled.
+    Workaround,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct StreamlinkSettings {
     /// Total budget (seconds) for the native resolver's retry-until-live loop.
@@ -185,6 +291,10 @@ pub struct ChatDesignSettings {
     pub compact_emote_tooltips: bool,
     #[serde(default = "default_true")]
     pub seventv_emote_notices: bool,
+    /// Convert oversized animated emotes to small WebP files ahead of time
+    /// (see services::emote_frames).
+    #[serde(default)]
+    pub optimize_animated_emotes: bool,
     #[serde(default = "default_true")]
     pub link_previews: bool,
     #[serde(default)]
@@ -267,6 +377,7 @@ impl Default for ChatDesignSettings {
             paint_mentions_in_body: true,
             compact_emote_tooltips: false,
             seventv_emote_notices: true,
+            optimize_animated_emotes: false,
             link_previews: true,
             link_preview_keep_link: false,
             shorten_links: true,
@@ -324,6 +435,10 @@ pub struct LiveNotificationSettings {
     pub toast_position: String,
     #[serde(default = "default_toast_edge_offset")]
     pub toast_edge_offset: u32,
+    /// Silences notification sounds. Kept in step with `audio_cues.muted`
+    /// (see services::audio_cues::sync_mute).
+    #[serde(default)]
+    pub do_not_disturb: bool,
 }
 
 fn default_true() -> bool {
@@ -358,6 +473,71 @@ impl Default for LiveNotificationSettings {
             quick_update_on_toast: false,
             toast_position: "bottom-right".to_string(),
             toast_edge_offset: 72,
+            do_not_disturb: false,
+        }
+    }
+}
+
+/// A sound played for one kind of event: a built-in sound id (see
+/// services::audio_cues::BUILTIN_SOUNDS) or the path of a WAV file.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AudioCue {
+    pub enabled: bool,
+    /// Empty plays the notification sound style (`live_notifications.sound_type`).
+    #[serde(default)]
+    pub sound: String,
+    /// 0-100.
+    #[serde(default = "default_cue_volume")]
+    pub volume: u8,
+}
+
+fn default_cue_volume() -> u8 {
+    100
+}
+
+impl AudioCue {
+    fn on() -> Self {
+        Self {
+            enabled: true,
+            sound: String::new(),
+            volume: default_cue_volume(),
+        }
+    }
+
+    /// Mentions never made a sound before cues existed, so they start off.
+    fn default_mention() -> Self {
+        Self {
+            enabled: false,
+            ..Self::on()
+        }
+    }
+}
+
+/// Per-event sounds, played by the frontend's audio-cue player when
+/// `live_notifications.play_sound` is on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AudioCueSettings {
+    /// Global mute, the same switch as `live_notifications.do_not_disturb`.
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default = "AudioCue::default_mention")]
+    pub mention: AudioCue,
+    #[serde(default = "AudioCue::on")]
+    pub whisper: AudioCue,
+    #[serde(default = "AudioCue::on")]
+    pub drop_claimed: AudioCue,
+    #[serde(default = "AudioCue::on")]
+    pub live: AudioCue,
+}
+
+impl Default for AudioCueSettings {
+    fn default() -> Self {
+        Self {
+            muted: false,
+            mention: AudioCue::default_mention(),
+            whisper: AudioCue::on(),
+            drop_claimed: AudioCue::on(),
+            live: AudioCue::on(),
         }
     }
 }
@@ -452,14 +632,28 @@ pub struct Settings {
     pub cache: CacheSettings,
     #[serde(default)]
     pub streamlink: StreamlinkSettings,
+    /// Per-channel say over Turbo/sub detection, keyed by lowercase login.
+    /// Channels not listed go by what Twitch reports.
+    #[serde(default)]
+    pub ad_free_overrides: HashMap<String, AdFreeOverride>,
     #[serde(default)]
     pub drops: DropsSettings,
     #[serde(default)]
     pub favorite_streamers: Vec<String>,
+    /// Quick-launch favorites, in the user's pinned order. Separate from
+    /// `favorite_streamers` (hearted follows); written only by the
+    /// commands in commands::favorites.
+    #[serde(default)]
+    pub pinned_channels: Vec<PinnedChannel>,
+    #[serde(default)]
+    pub favorite_warmup: FavoriteWarmup,
     #[serde(default)]
     pub chat_design: ChatDesignSettings,
     #[serde(default)]
     pub live_notifications: LiveNotificationSettings,
+    /// Sounds for mentions, whispers, claimed drops and channels going live.
+    #[serde(default)]
+    pub audio_cues: AudioCueSettings,
     #[serde(default)]
     pub last_seen_version: Option<String>,
     #[serde(default)]
@@ -476,11 +670,38 @@ pub struct Settings {
     pub glass_transparency: Option<u32>,
     #[serde(default)]
     pub setup_complete: bool,
+    /// Progress through the first-run wizard's steps, written only by
+    /// commands::setup.
+    #[serde(default)]
+    pub first_run_state: FirstRunState,
     #[serde(default)]
     pub compact_view: Option<CompactViewSettings>,
     /// Whether diagnostic logging is enabled (defaults to true)
     #[serde(default = "default_true")]
     pub error_reporting_enabled: bool,
+    /// Stretch non-critical polls and drop UI-only events while the main
+    /// window is minimized or hidden (see services::power_saver).
+    #[serde(default)]
+    pub power_saver: bool,
+    /// Keep the main window above other windows; written only by
+    /// commands::app::set_always_on_top (see services::window_modes).
+    #[serde(default)]
+    pub always_on_top: bool,
+    /// Away detection from OS idle time (see services::idle_monitor).
+    #[serde(default)]
+    pub idle: IdleSettings,
+    /// CPU priority and GPU choice for playback, so a stream (or mining in
+    /// the background) leaves room for a game (see services::playback_priority).
+    #[serde(default)]
+    pub playback_resources: PlaybackResourceSettings,
+    /// Pause prefetch, quiet mined playback and slow polls while a game
+    /// runs (see services::game_mode).
+    #[serde(default)]
+    pub game_mode: GameModeSettings,
+    /// Unlocks developer tools such as the raw API console
+    /// (commands::debug_api), which signs requests with stored credentials.
+    #[serde(default)]
+    pub developer_mode: bool,
     /// Persisted multi-stream grid configurations
     #[serde(default)]
     pub multi_nook_slots: Vec<MultiNookSlot>,
@@ -494,10 +715,58 @@ pub struct Settings {
     /// its user-assigned chord strings. Absent ids fall back to code defaults.
     #[serde(default)]
     pub keybindings: HashMap<String, Vec<String>>,
+    /// System-wide hotkeys: action id (see services::global_hotkeys::ACTIONS)
+    /// to accelerator string. Unlike `keybindings` these fire while the app
+    /// is unfocused.
+    #[serde(default)]
+    pub global_hotkeys: HashMap<String, String>,
+    /// Logins marked as friends (lowercase). Their first message in a chat
+    /// raises `friend-active-in-chat` (see services::chatter_tracker).
+    #[serde(default)]
+    pub chat_friends: Vec<String>,
+    /// How recommended streams are ordered: "twitch" (as Twitch returns
+    /// them), "personalized" or "hybrid" (see services::recommendations).
+    #[serde(default = "default_recommendation_strategy")]
+    pub recommendation_strategy: String,
+    /// Emote / badge / reward image downloads run at once (see
+    /// services::download_pool).
+    #[serde(default = "default_prefetch_concurrency")]
+    pub prefetch_concurrency: usize,
     /// Chat logging to plain text files (one folder per channel, one file per
     /// day), written by services::chat_logger_service.
     #[serde(default)]
     pub chat_logging: ChatLoggingSettings,
+    /// Folding floods of the same message into one counter row, done by
+    /// services::spam_collapse before messages reach the frontend.
+    #[serde(default)]
+    pub chat_spam_collapse: ChatSpamCollapseSettings,
+    /// What happens to a recording once the recording plugin finishes it,
+    /// see services::recording_postprocess.
+    #[serde(default)]
+    pub recording: RecordingSettings,
+    /// "When X then Y" rules, evaluated by services::automation_rules.
+    #[serde(default)]
+    pub automation_rules: Vec<AutomationRule>,
+    /// Raid trains and marathon events, tracked by services::raid_events.
+    #[serde(default)]
+    pub raid_events: Vec<RaidEvent>,
+    /// External programs run on app events (see services::script_hooks).
+    #[serde(default)]
+    pub script_hooks: Vec<ScriptHook>,
+    /// OBS Studio connection (obs-websocket v5), see services::obs_service.
+    #[serde(default)]
+    pub obs: ObsSettings,
+    /// Localhost control API for Stream Deck and scripts, see
+    /// services::local_api.
+    #[serde(default)]
+    pub local_api: LocalApiSettings,
+    /// Outbound webhook endpoints, see services::webhooks.
+    #[serde(default)]
+    pub webhooks: Vec<WebhookEndpoint>,
+    /// Where the portable settings profile is synced, see
+    /// services::settings_sync. Per machine, so it never syncs itself.
+    #[serde(default)]
+    pub settings_sync: SettingsSync,
     /// Catch-all for preference groups the frontend manages but this struct does
     /// not model field-by-field: highlight phrases, custom chat commands,
     /// moderation prefs, custom themes, the OLED accent, and any future ones.
@@ -509,10 +778,68 @@ pub struct Settings {
     pub extra: HashMap<String, serde_json::Value>,
 }
 
+/// A step of the first-run wizard, in the order it runs them.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStep {
+    /// Resolve and fetch a live stream to prove playback works here.
+    Playback,
+    /// Sign in to Twitch.
+    Login,
+    /// Pick what drops mining does on its own.
+    MiningDefaults,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 3] = [
+        SetupStep::Playback,
+        SetupStep::Login,
+        SetupStep::MiningDefaults,
+    ];
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupStepState {
+    Pending,
+    Done,
+    Failed,
+    Skipped,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SetupStepStatus {
+    pub step: SetupStep,
+    pub state: SetupStepState,
+    /// What was found, or why it failed, in words for the wizard to show.
+    #[serde(default)]
+    pub detail: Option<String>,
+    /// RFC 3339; None for a step never run.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FirstRunState {
+    /// Latest result of each step that was run or skipped.
+    #[serde(default)]
+    pub steps: Vec<SetupStepStatus>,
+    #[serde(default)]
+    pub completed_at: Option<String>,
+}
+
 fn default_theme() -> String {
     "winters-glass".to_string()
 }
 
+fn default_recommendation_strategy() -> String {
+    "hybrid".to_string()
+}
+
+fn default_prefetch_concurrency() -> usize {
+    crate::services::download_pool::DEFAULT_CONCURRENCY
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Self {
@@ -525,28 +852,292 @@ impl Default for Settings {
             video_player: VideoPlayerSettings::default(),
             cache: CacheSettings::default(),
             streamlink: StreamlinkSettings::default(),
+            ad_free_overrides: HashMap::new(),
             drops: DropsSettings::default(),
             favorite_streamers: vec![],
+            pinned_channels: Vec::new(),
+            favorite_warmup: FavoriteWarmup::default(),
             chat_design: ChatDesignSettings::default(),
             live_notifications: LiveNotificationSettings::default(),
+            audio_cues: AudioCueSettings::default(),
             last_seen_version: None,
             auto_switch: AutoSwitchSettings::default(),
             theme: default_theme(),
             font: None,
             glass_transparency: None,
             setup_complete: false, // New users need to complete setup
+            first_run_state: FirstRunState::default(),
             compact_view: None,
             error_reporting_enabled: true, // Diagnostics enabled by default
+            power_saver: false,
+            always_on_top: false,
+            idle: IdleSettings::default(),
+            playback_resources: PlaybackResourceSettings::default(),
+            game_mode: GameModeSettings::default(),
+            developer_mode: false,
             multi_nook_slots: Vec::new(),
             multi_nook_chat_hidden: false,
             show_mod_logs: false,
             keybindings: HashMap::new(),
+            global_hotkeys: HashMap::new(),
+            chat_friends: Vec::new(),
+            recommendation_strategy: default_recommendation_strategy(),
+            prefetch_concurrency: default_prefetch_concurrency(),
             chat_logging: ChatLoggingSettings::default(),
+            chat_spam_collapse: ChatSpamCollapseSettings::default(),
+            recording: RecordingSettings::default(),
+            automation_rules: Vec::new(),
+            raid_events: Vec::new(),
+            script_hooks: Vec::new(),
+            obs: ObsSettings::default(),
+            local_api: LocalApiSettings::default(),
+            webhooks: Vec::new(),
+            settings_sync: SettingsSync::default(),
             extra: HashMap::new(),
         }
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ObsSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_obs_host")]
+    pub host: String,
+    #[serde(default = "default_obs_port")]
+    pub port: u16,
+    /// obs-websocket server password; None when authentication is off.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Scene to switch to when a raid is started from StreamNook.
+    #[serde(default)]
+    pub brb_scene: Option<String>,
+    #[serde(default)]
+    pub switch_on_raid: bool,
+}
+
+fn default_obs_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_obs_port() -> u16 {
+    4455
+}
+
+impl Default for ObsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: default_obs_host(),
+            port: default_obs_port(),
+            password: None,
+            brb_scene: None,
+            switch_on_raid: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LocalApiSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Port on 127.0.0.1.
+    #[serde(default = "default_local_api_port")]
+    pub port: u16,
+}
+
+fn default_local_api_port() -> u16 {
+    47615
+}
+
+impl Default for LocalApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_local_api_port(),
+        }
+    }
+}
+
+/// An HTTP endpoint POSTed to when one of its `events` (the script-hook event
+/// names) fires.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookEndpoint {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// JSON body with `{{path}}` placeholders; the plain event document when
+    /// unset.
+    #[serde(default)]
+    pub body_template: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// A quick-launch favorite.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PinnedChannel {
+    pub user_id: String,
+    pub login: String,
+    pub display_name: String,
+    #[serde(default)]
+    pub profile_image_url: Option<String>,
+    /// Quality to start playback at; `Settings::quality` when unset.
+    #[serde(default)]
+    pub quality: Option<String>,
+    /// Unix seconds.
+    pub pinned_at: i64,
+}
+
+/// Fetch playback tokens for the first `count` pinned favorites as they go
+/// live, so clicking Watch soon after starts faster. See
+/// services::favorite_warmup.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FavoriteWarmup {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_warmup_count")]
+    pub count: u32,
+}
+
+fn default_warmup_count() -> u32 {
+    3
+}
+
+impl Default for FavoriteWarmup {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            count: default_warmup_count(),
+        }
+    }
+}
+
+/// A place to keep the settings profile shared between machines.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(tag = "provider", rename_all = "snake_case")]
+pub enum SettingsSync {
+    #[default]
+    Off,
+    /// A folder another tool syncs (Dropbox, OneDrive, Syncthing).
+    Folder { path: String },
+    /// A secret GitHub Gist. `gist_id` is filled in by the first push.
+    Gist {
+        token: String,
+        #[serde(default)]
+        gist_id: Option<String>,
+    },
+}
+
+/// An external program run with a JSON payload on stdin when `event`
+/// (`drop_claimed`, `went_live`, `stream_started`) happens.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScriptHook {
+    pub id: String,
+    pub event: String,
+    /// File name inside the hooks folder; nothing outside it runs.
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_hook_timeout_secs")]
+    pub timeout_secs: u64,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_hook_timeout_secs() -> u64 {
+    10
+}
+
+/// A user-defined automation rule: when `trigger` happens, run `action`.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AutomationRule {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub trigger: RuleTrigger,
+    pub action: RuleAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleTrigger {
+    /// The channel goes from offline to live.
+    ChannelLive { channel_login: String },
+    /// A drop is auto-claimed.
+    DropClaimed,
+    /// The channel's points balance rises past `threshold`.
+    PointsBalanceAbove {
+        channel_login: String,
+        threshold: i64,
+    },
+    /// The channel's live title starts containing `word` (case-insensitive).
+    TitleContains { channel_login: String, word: String },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleAction {
+    /// Handed to whichever plugin provides `recording.start`; defaults to the
+    /// trigger's channel.
+    StartRecording {
+        #[serde(default)]
+        channel_login: Option<String>,
+    },
+    /// Start drops collection on a campaign, or let the plugin pick one.
+    StartMining {
+        #[serde(default)]
+        campaign_id: Option<String>,
+    },
+    /// POST the rule and its trigger as JSON, through services::webhooks
+    /// (event `automation_rule`).
+    SendWebhook { url: String },
+    /// Switch OBS to `scene` (see services::obs_service).
+    SwitchObsScene { scene: String },
+    /// Raise `automation-rule-notification` for the UI to toast.
+    Notify {
+        #[serde(default)]
+        message: Option<String>,
+    },
+}
+
+/// A community event (raid train, marathon): channels that go live in turn
+/// within a time window. See services::raid_events.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RaidEvent {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Logins in raid order; each channel raids the next.
+    pub channels: Vec<String>,
+    /// Unix seconds.
+    pub starts_at: i64,
+    /// Unix seconds.
+    pub ends_at: i64,
+    #[serde(default)]
+    pub follow: RaidEventFollow,
+}
+
+/// What moves along the chain when an event channel goes offline.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RaidEventFollow {
+    /// Only track the event.
+    #[default]
+    Off,
+    /// Playback, when it's on an event channel.
+    Watch,
+    /// The drops session, when it mines an event channel.
+    Mine,
+}
+
 /// One channel entry in the chat-logging allowlist. The shape matches the
 /// frontend channel picker so the list round-trips with its display data;
 /// only `channel_login` drives the filter.
@@ -560,6 +1151,76 @@ pub struct ChatLogChannel {
     pub display_name: String,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordingSettings {
+    /// Where recordings go; empty uses Recordings under the app data dir.
+    /// Handed to the recording plugin with every `recording.start`.
+    #[serde(default)]
+    pub folder: String,
+    /// Most recordings running at once; 0 for no limit.
+    #[serde(default = "default_max_concurrent_recordings")]
+    pub max_concurrent: u32,
+    /// Recordings are stopped when their drive has less free space than
+    /// this, in MB; 0 turns the check off.
+    #[serde(default = "default_min_free_disk_mb")]
+    pub min_free_disk_mb: u64,
+    /// Queue post-processing for every recording the recording plugin
+    /// reports finished. Off by default; `postprocess_recording` still works.
+    #[serde(default)]
+    pub postprocess: bool,
+    /// What a queued job does, unless the caller asks otherwise.
+    #[serde(default)]
+    pub postprocess_options: PostProcessOptions,
+}
+
+fn default_max_concurrent_recordings() -> u32 {
+    2
+}
+
+fn default_min_free_disk_mb() -> u64 {
+    2048
+}
+
+impl Default for RecordingSettings {
+    fn default() -> Self {
+        Self {
+            folder: String::new(),
+            max_concurrent: default_max_concurrent_recordings(),
+            min_free_disk_mb: default_min_free_disk_mb(),
+            postprocess: false,
+            postprocess_options: PostProcessOptions::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PostProcessOptions {
+    /// Copy the streams into an MP4 (no re-encode).
+    #[serde(default = "default_true")]
+    pub remux: bool,
+    /// Leave out ad segments. Needs the recording as an HLS playlist, which
+    /// still has the ad markers; a bare `.ts` is kept whole.
+    #[serde(default = "default_true")]
+    pub trim_ads: bool,
+    /// Save a JPEG thumbnail beside the output.
+    #[serde(default = "default_true")]
+    pub thumbnail: bool,
+    /// Delete the original once the output is written.
+    #[serde(default)]
+    pub delete_source: bool,
+}
+
+impl Default for PostProcessOptions {
+    fn default() -> Self {
+        Self {
+            remux: true,
+            trim_ads: true,
+            thumbnail: true,
+            delete_source: false,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ChatLoggingSettings {
     /// Off by default: writing files to disk is the user's call.
@@ -591,6 +1252,69 @@ impl Default for ChatLoggingSettings {
     }
 }
 
+/// Repeated-message collapsing, see services::spam_collapse.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChatSpamCollapseSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Copies of one message that show normally before the rest are folded
+    /// into a counter row.
+    #[serde(default = "default_spam_threshold")]
+    pub threshold: u32,
+    /// A flood ends once no copy has arrived for this many seconds.
+    #[serde(default = "default_spam_window_secs")]
+    pub window_secs: u32,
+}
+
+fn default_spam_threshold() -> u32 {
+    3
+}
+
+fn default_spam_window_secs() -> u32 {
+    10
+}
+
+impl Default for ChatSpamCollapseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: default_spam_threshold(),
+            window_secs: default_spam_window_secs(),
+        }
+    }
+}
+
+/// Away detection, see services::idle_monitor.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IdleSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minutes without keyboard or mouse input before the user counts as away.
+    #[serde(default = "default_idle_threshold_mins")]
+    pub threshold_mins: u32,
+    /// Pause the player while away (drops mining keeps going).
+    #[serde(default)]
+    pub pause_playback: bool,
+    /// Show the idle Discord presence while away.
+    #[serde(default = "default_true")]
+    pub discord_idle: bool,
+}
+
+fn default_idle_threshold_mins() -> u32 {
+    10
+}
+
+impl Default for IdleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_mins: default_idle_threshold_mins(),
+            pause_playback: false,
+            discord_idle: true,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct AppState {
     pub settings: Arc<Mutex<Settings>>,
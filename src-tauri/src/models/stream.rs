@@ -25,6 +25,60 @@ pub struct TwitchStream {
     // "Speedrun"). Powers the category tag filter.
     #[serde(default)]
     pub tags: Option<Vec<String>>,
+    /// Broadcast language (ISO 639-1, e.g. "en"), from Helix `language` /
+    /// `broadcaster_language`. None where the source doesn't carry it.
+    #[serde(default, alias = "broadcaster_language")]
+    pub language: Option<String>,
+}
+
+/// Backend-side filter for stream listings (recommendations, category
+/// browsing). Empty lists don't filter. Matching is case-insensitive.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct StreamFilter {
+    /// Keep streams in any of these languages ("en", "de", ...). Streams
+    /// with no known language are dropped when this is set.
+    pub languages: Vec<String>,
+    /// Keep streams carrying every one of these tags (e.g. "DropsEnabled").
+    pub tags: Vec<String>,
+    /// Drop streams carrying any of these tags.
+    pub exclude_tags: Vec<String>,
+}
+
+impl StreamFilter {
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty() && self.tags.is_empty() && self.exclude_tags.is_empty()
+    }
+
+    pub fn matches(&self, stream: &TwitchStream) -> bool {
+        if !self.languages.is_empty() {
+            let Some(lang) = stream.language.as_deref() else {
+                return false;
+            };
+            if !self.languages.iter().any(|l| l.eq_ignore_ascii_case(lang)) {
+                return false;
+            }
+        }
+        let tags = stream.tags.as_deref().unwrap_or_default();
+        let has = |wanted: &String| tags.iter().any(|t| t.eq_ignore_ascii_case(wanted));
+        self.tags.iter().all(has) && !self.exclude_tags.iter().any(has)
+    }
+
+    pub fn apply(&self, streams: Vec<TwitchStream>) -> Vec<TwitchStream> {
+        if self.is_empty() {
+            return streams;
+        }
+        streams.into_iter().filter(|s| self.matches(s)).collect()
+    }
+
+    /// `&language=..` query parameters, so Helix filters language
+    /// server-side (tags still need `apply`).
+    pub fn helix_language_params(&self) -> String {
+        self.languages
+            .iter()
+            .map(|l| format!("&language={}", urlencoding::encode(&l.to_lowercase())))
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -112,3 +166,88 @@ pub struct ClipReactions {
     pub total: u32,
     pub like: u32,
 }
+
+/// What "share what I'm watching" copies: the channel link plus, while live,
+/// its title and category, and a ready-to-paste message built from them.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ShareInfo {
+    pub channel_login: String,
+    pub display_name: String,
+    pub url: String,
+    pub is_live: bool,
+    pub title: Option<String>,
+    pub game_name: Option<String>,
+    pub message: String,
+}
+
+/// One `stream-info-update` event: everything the player header shows about
+/// the watched channel, from a single backend poll. Offline channels carry
+/// their last-set title and category and no live fields.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StreamInfoUpdate {
+    pub channel_login: String,
+    pub is_live: bool,
+    pub title: String,
+    pub game_id: String,
+    pub game_name: String,
+    pub viewer_count: Option<u32>,
+    pub started_at: Option<String>,
+    pub uptime_secs: Option<i64>,
+    pub tags: Vec<String>,
+}
+
+/// One `stream-info-changed` event: the watched channel's title or category
+/// changed while it was being watched.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StreamInfoChange {
+    pub channel_login: String,
+    pub title_changed: bool,
+    pub game_changed: bool,
+    pub old_title: String,
+    pub title: String,
+    pub old_game_name: String,
+    pub game_name: String,
+    /// Empty when the source didn't report one.
+    pub game_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(language: Option<&str>, tags: &[&str]) -> TwitchStream {
+        serde_json::from_value(serde_json::json!({
+            "id": "1", "user_id": "1", "user_name": "A", "user_login": "a",
+            "title": "", "viewer_count": 0, "game_name": "", "thumbnail_url": "",
+            "started_at": "", "language": language,
+            "tags": tags,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn filter_by_language_and_tags() {
+        let filter = StreamFilter {
+            languages: vec!["EN".into()],
+            tags: vec!["dropsenabled".into()],
+            exclude_tags: vec!["Rerun".into()],
+        };
+        assert!(filter.matches(&stream(Some("en"), &["DropsEnabled", "English"])));
+        assert!(!filter.matches(&stream(Some("de"), &["DropsEnabled"])));
+        assert!(!filter.matches(&stream(None, &["DropsEnabled"])));
+        assert!(!filter.matches(&stream(Some("en"), &["English"])));
+        assert!(!filter.matches(&stream(Some("en"), &["DropsEnabled", "rerun"])));
+        assert!(StreamFilter::default().matches(&stream(None, &[])));
+    }
+
+    #[test]
+    fn search_results_read_broadcaster_language() {
+        let s: TwitchStream = serde_json::from_value(serde_json::json!({
+            "id": "1", "user_id": "1", "user_name": "A", "user_login": "a",
+            "title": "", "viewer_count": 0, "game_name": "", "thumbnail_url": "",
+            "started_at": "", "broadcaster_language": "fr",
+        }))
+        .unwrap();
+        assert_eq!(s.language.as_deref(), Some("fr"));
+    }
+}
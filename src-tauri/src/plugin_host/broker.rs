@@ -2,7 +2,7 @@
 //! call lands here; anything not granted is denied. Credential handovers
 //! require first-use consent and are written to the plugin's audit log.
 
-use log::debug;
+use log::{debug, warn};
 use serde_json::{json, Value};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -13,6 +13,7 @@ use tokio::time::timeout;
 use super::process::RpcErr;
 use super::registry::{self, InstalledPlugin};
 use super::{ConsentDecision, HostInner};
+use crate::models::drops::BlacklistReason;
 use crate::models::settings::AppState;
 use crate::services::drops_auth_service::DropsAuthService;
 use crate::services::twitch_service::TwitchService;
@@ -76,6 +77,46 @@ pub async fn handle_host_method(
             );
             Ok(json!({}))
         }
+        "blacklist_streamer" => {
+            require_method(record, "blacklist_streamer")?;
+            let channel_id = params
+                .get("channel_id")
+                .and_then(|v| v.as_str())
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| RpcErr::invalid_params("channel_id is required"))?;
+            let channel_name = params
+                .get("channel_name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            let reason: BlacklistReason = params
+                .get("reason")
+                .cloned()
+                .and_then(|r| serde_json::from_value(r).ok())
+                .ok_or_else(|| RpcErr::invalid_params("reason is not a known reason"))?;
+            let duration_secs = match params.get("duration_secs").and_then(|v| v.as_u64()) {
+                Some(secs) => secs,
+                None => {
+                    let state = host.app.state::<AppState>();
+                    let drops_service = state.drops_service.lock().await;
+                    drops_service
+                        .get_settings()
+                        .await
+                        .recovery_settings
+                        .streamer_blacklist_duration_seconds
+                }
+            };
+            let entry = crate::services::drops_blacklist::add(
+                channel_id,
+                channel_name,
+                reason,
+                duration_secs,
+            );
+            debug!(
+                "[PluginHost] {} blacklisted {} until {}",
+                record.id, channel_id, entry.expires_at
+            );
+            Ok(json!({ "expires_at": entry.expires_at }))
+        }
         "get_credential" => {
             let kind = params
                 .get("kind")
@@ -274,6 +315,8 @@ pub async fn fetch_followed_live(host: &Arc<HostInner>) -> Result<Vec<Value>, St
     Ok(streams
         .iter()
         .map(|s| {
+            let viewers = crate::services::viewer_stats_service::summary(&s.user_id);
+            let uptime = crate::services::stream_uptime_service::stats(&s.user_id);
             json!({
                 "channel_id": s.user_id,
                 "login": s.user_login,
@@ -282,6 +325,11 @@ pub async fn fetch_followed_live(host: &Arc<HostInner>) -> Result<Vec<Value>, St
                 "game_name": if s.game_name.is_empty() { Value::Null } else { json!(s.game_name) },
                 "started_at": if s.started_at.is_empty() { Value::Null } else { json!(s.started_at) },
                 "viewer_count": s.viewer_count,
+                "avg_viewer_count": viewers.as_ref().map(|v| v.average.round() as u64),
+                "viewer_stability": viewers.as_ref().map(|v| v.stability),
+                "viewer_trend": viewers.as_ref().map(|v| v.trend),
+                "typical_stream_hours": uptime.as_ref().and_then(|u| u.median_hours),
+                "uptime_penalty": uptime.as_ref().map(|u| u.uptime_penalty),
                 "favorite": favorites.contains(&s.user_id),
             })
         })
@@ -369,17 +417,119 @@ pub fn handle_set_status(host: &Arc<HostInner>, record: &InstalledPlugin, params
         return;
     }
     let value = params.get("value").cloned().unwrap_or(Value::Null);
+    let mut trace_id = None;
+    if slot == "drops.status" {
+        crate::services::mining_audit::observe_status(&value);
+        crate::services::campaign_eligibility::observe_status(&host.app, &value);
+        trace_id = crate::services::trace_ids::active(crate::services::trace_ids::MINING);
+    }
+    if let Ok(mut values) = host.status_values.lock() {
+        values.insert(slot.to_string(), value.clone());
+    }
+    if slot == crate::services::recording_guard::STATUS_SLOT {
+        crate::services::recording_guard::ensure_watchdog(&host.app);
+    }
     let _ = host.app.emit(
         "plugin://status",
-        json!({ "plugin_id": record.id, "slot": slot, "value": value }),
+        json!({ "plugin_id": record.id, "slot": slot, "value": value, "trace_id": trace_id }),
     );
 }
 
+/// `watch_payload_sent` notification: the drops plugin reports a watch payload
+/// it sent, for the mining audit. Dropped unless the method is granted.
+pub fn handle_watch_payload(record: &InstalledPlugin, params: &Value) {
+    if require_method(record, "watch_payload_sent").is_err() {
+        debug!(
+            "[PluginHost] {} sent an ungranted watch_payload_sent",
+            record.id
+        );
+        return;
+    }
+    let field = |key: &str| {
+        params
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let channel_id = field("channel_id");
+    if channel_id.is_empty() {
+        return;
+    }
+    let campaign_id = Some(field("campaign_id")).filter(|c| !c.is_empty());
+    crate::services::mining_audit::record_payload(crate::services::mining_audit::WatchPayload {
+        sent_at: chrono::Utc::now(),
+        channel_id,
+        channel_login: field("channel_login"),
+        broadcast_id: field("broadcast_id"),
+        campaign_id,
+    });
+}
+
+/// `recording_finished` notification: a recording plugin reports a file it
+/// finished writing. Added to the recordings index, and queued for
+/// post-processing when the user turned it on.
+pub fn handle_recording_finished(host: &Arc<HostInner>, record: &InstalledPlugin, params: &Value) {
+    if require_method(record, "recording_finished").is_err() {
+        debug!(
+            "[PluginHost] {} sent an ungranted recording_finished",
+            record.id
+        );
+        return;
+    }
+    let Some(path) = params.get("path").and_then(|v| v.as_str()) else {
+        return;
+    };
+    let recording = {
+        let state = host.app.state::<AppState>();
+        let settings = state.settings.lock().unwrap();
+        settings.recording.clone()
+    };
+    let text = |key: &str| {
+        params
+            .get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    crate::services::recordings_archive::record(
+        &recording,
+        std::path::Path::new(path),
+        crate::services::recordings_archive::RecordingMetadata {
+            channel_login: text("channel_login"),
+            game_name: text("game_name"),
+            title: text("title"),
+            started_at: params
+                .get("started_at")
+                .and_then(|v| serde_json::from_value(v.clone()).ok()),
+            duration_secs: params.get("duration_secs").and_then(|v| v.as_u64()),
+        },
+    );
+    if !recording.postprocess {
+        return;
+    }
+    if let Err(e) = crate::services::recording_postprocess::enqueue(
+        std::path::Path::new(path),
+        recording.postprocess_options,
+    ) {
+        warn!(
+            "[PluginHost] Not post-processing {} from {}: {}",
+            path, record.id, e
+        );
+    }
+}
+
 /// `log` notification from a plugin: append to its log file, never answered.
+/// A `trace_id` goes in front of the message.
 pub fn handle_log_notification(record: &InstalledPlugin, params: &Value) {
     let level = params.get("level").and_then(|v| v.as_str()).unwrap_or("info");
     let message = params.get("message").and_then(|v| v.as_str()).unwrap_or("");
-    append_plugin_log(&record.id, level, message);
+    match params.get("trace_id").and_then(|v| v.as_str()) {
+        Some(trace_id) => {
+            append_plugin_log(&record.id, level, &format!("{} {}", trace_id, message))
+        }
+        None => append_plugin_log(&record.id, level, message),
+    }
 }
 
 /// Appends one line to the plugin's own log file (plugin.log in its state dir).
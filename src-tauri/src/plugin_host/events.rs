@@ -1,4 +1,5 @@
-//! Background event emitters: the watch tick and the followed-live feed.
+//! Background event emitters: the watch tick and the followed-live feed, plus
+//! the resume burst sent after a system sleep.
 //! Both are lazy: nothing polls and nothing is emitted unless at least one
 //! running plugin subscribed to the event. Stream lifecycle events
 //! (on_stream_start, on_stream_stop, on_channel_change) are pushed by the
@@ -27,12 +28,7 @@ pub fn start_background_emitters(host: Arc<HostInner>) {
                 if !host.any_hook("on_watch_tick").await {
                     continue;
                 }
-                let active = host.active_channel.read().await.clone();
-                let params = json!({
-                    "active_channel_id": active.map(|c| c.channel_id),
-                    "ts": chrono::Utc::now().to_rfc3339(),
-                });
-                host.emit_event("on_watch_tick", params).await;
+                emit_watch_tick(&host).await;
             }
         });
     }
@@ -51,16 +47,43 @@ pub fn start_background_emitters(host: Arc<HostInner>) {
                 if !host.any_hook("on_followed_live").await {
                     continue;
                 }
-                match super::broker::fetch_followed_live(&host).await {
-                    Ok(channels) => {
-                        host.emit_event("on_followed_live", json!({ "channels": channels }))
-                            .await;
-                    }
-                    Err(e) => {
-                        debug!("[PluginHost] followed-live refresh failed: {e}");
-                    }
-                }
+                emit_followed_live(&host).await;
             }
         });
     }
 }
+
+/// The system woke from sleep: `on_system_resume`, then the watch tick and
+/// followed-live feed right away instead of on their next timer, so a plugin
+/// can re-check its channel before the old one is assumed alive.
+pub async fn emit_resume(host: &Arc<HostInner>, slept_secs: i64) {
+    host.emit_event("on_system_resume", json!({ "slept_secs": slept_secs }))
+        .await;
+    if host.any_hook("on_watch_tick").await {
+        emit_watch_tick(host).await;
+    }
+    if host.any_hook("on_followed_live").await {
+        emit_followed_live(host).await;
+    }
+}
+
+async fn emit_watch_tick(host: &Arc<HostInner>) {
+    let active = host.active_channel.read().await.clone();
+    let params = json!({
+        "active_channel_id": active.map(|c| c.channel_id),
+        "ts": chrono::Utc::now().to_rfc3339(),
+    });
+    host.emit_event("on_watch_tick", params).await;
+}
+
+async fn emit_followed_live(host: &Arc<HostInner>) {
+    match super::broker::fetch_followed_live(host).await {
+        Ok(channels) => {
+            host.emit_event("on_followed_live", json!({ "channels": channels }))
+                .await;
+        }
+        Err(e) => {
+            debug!("[PluginHost] followed-live refresh failed: {e}");
+        }
+    }
+}
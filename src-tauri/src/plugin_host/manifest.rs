@@ -10,6 +10,7 @@ pub const KNOWN_EVENTS: &[&str] = &[
     "on_stream_stop",
     "on_channel_change",
     "on_watch_tick",
+    "on_system_resume",
     "on_followed_live",
     "on_chat_message",
     "on_settings_change",
@@ -21,6 +22,9 @@ pub const KNOWN_EVENTS: &[&str] = &[
 pub const KNOWN_HOST_METHODS: &[&str] = &[
     "get_followed_live",
     "set_upstream",
+    "blacklist_streamer",
+    "watch_payload_sent",
+    "recording_finished",
     "notify",
     "log",
     "register_panel",
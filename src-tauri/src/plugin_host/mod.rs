@@ -60,6 +60,9 @@ pub struct HostInner {
     pub pending_installs: TokioMutex<HashMap<String, PendingInstall>>,
     pub active_channel: TokioRwLock<Option<ActiveChannel>>,
     pub notify_stamps: TokioMutex<HashMap<String, Vec<Instant>>>,
+    /// Latest value pushed into each status slot, so core commands can read
+    /// plugin state (e.g. the channel `drops.status` reports) on demand.
+    pub status_values: std::sync::Mutex<HashMap<String, Value>>,
     pub shutting_down: AtomicBool,
 }
 
@@ -223,6 +226,7 @@ impl PluginHost {
                 pending_installs: TokioMutex::new(HashMap::new()),
                 active_channel: TokioRwLock::new(None),
                 notify_stamps: TokioMutex::new(HashMap::new()),
+                status_values: std::sync::Mutex::new(HashMap::new()),
                 shutting_down: AtomicBool::new(false),
             }),
         }
@@ -726,6 +730,12 @@ impl PluginHost {
         Ok(())
     }
 
+    /// The system woke from sleep (see services::resume_watch). Forwards
+    /// `on_system_resume` and refreshes the timed events immediately.
+    pub async fn notify_resumed(&self, slept_secs: i64) {
+        events::emit_resume(&self.inner, slept_secs).await;
+    }
+
     /// True when at least one running plugin subscribed to chat messages.
     /// Chat parsing checks this before building the per-message payload, so
     /// the hot IRC path pays nothing while no plugin listens.
@@ -756,8 +766,19 @@ impl PluginHost {
 
     /// Invokes a named action on whichever running plugin handles it, and
     /// returns its result. This is how a core UI hands off a control to a
-    /// plugin without knowing which plugin (or that any plugin) exists.
+    /// plugin without knowing which plugin (or that any plugin) exists. Args
+    /// carrying a `trace_id` run the call in that trace's scope.
     pub async fn invoke_action(&self, action: &str, args: Value) -> Result<Value> {
+        match args.get("trace_id").and_then(|v| v.as_str()) {
+            Some(trace_id) => {
+                let trace_id = trace_id.to_string();
+                crate::services::trace_ids::scope(trace_id, self.send_action(action, args)).await
+            }
+            None => self.send_action(action, args).await,
+        }
+    }
+
+    async fn send_action(&self, action: &str, args: Value) -> Result<Value> {
         let cmd_tx = {
             let running = self.inner.running.read().await;
             running
@@ -793,6 +814,15 @@ impl PluginHost {
             .map(|(id, _)| id.clone())
     }
 
+    /// The last value a plugin pushed into a status slot, if any.
+    pub fn status(&self, slot: &str) -> Option<Value> {
+        self.inner
+            .status_values
+            .lock()
+            .ok()
+            .and_then(|values| values.get(slot).cloned())
+    }
+
     async fn find_info(&self, plugin_id: &str) -> Option<PluginInfo> {
         self.list_installed()
             .await
@@ -420,7 +420,8 @@ async fn route_frame(
                 let _ = write_frame(&mut *w, &response).await;
             });
         }
-        // Notification from the plugin. `log` and `set_status` are handled;
+        // Notification from the plugin. `log`, `set_status`,
+        // `watch_payload_sent` and `recording_finished` are handled;
         // everything else is dropped (notifications cannot receive errors).
         (false, Some(method)) => {
             let params = frame.get("params").cloned().unwrap_or(Value::Null);
@@ -428,6 +429,10 @@ async fn route_frame(
                 super::broker::handle_log_notification(record, &params);
             } else if method == "set_status" {
                 super::broker::handle_set_status(host, record, &params);
+            } else if method == "watch_payload_sent" {
+                super::broker::handle_watch_payload(record, &params);
+            } else if method == "recording_finished" {
+                super::broker::handle_recording_finished(host, record, &params);
             } else {
                 debug!(
                     "[PluginHost] {} sent unsupported notification '{}'",
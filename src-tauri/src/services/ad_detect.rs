@@ -32,6 +32,54 @@ pub struct AdDetectionState {
     pub matched_markers: Vec<String>,
     /// Count of distinct ad breaks seen on the current stream.
     pub ad_events: u32,
+    /// When the current break was first seen.
+    #[serde(skip)]
+    pub break_started: Option<std::time::Instant>,
+}
+
+/// `VideoPlayerSettings::mute_during_ads`, mirrored here at startup and on
+/// every settings save.
+static MUTE_DURING_ADS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_mute_during_ads(enabled: bool) {
+    MUTE_DURING_ADS.store(enabled, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Whether the player should mute for ad breaks (sent with
+/// `ad-break-started`).
+pub fn mute_during_ads() -> bool {
+    MUTE_DURING_ADS.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The advertised length of the ad break in `playlist`, in seconds: the sum of
+/// the distinct stitched-ad DATERANGEs' `DURATION` (or `PLANNED-DURATION`).
+/// None when the playlist doesn't say.
+pub fn break_duration(playlist: &str) -> Option<f64> {
+    let mut seen = std::collections::HashSet::new();
+    let mut total = 0.0;
+    for line in playlist.lines() {
+        let Some(attrs) = line.trim_start().strip_prefix("#EXT-X-DATERANGE:") else {
+            continue;
+        };
+        if !attrs.contains("stitched-ad") {
+            continue;
+        }
+        let attr = |name: &str| {
+            attrs.split(',').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                (key.trim() == name).then(|| value.trim().trim_matches('"'))
+            })
+        };
+        let duration = attr("DURATION")
+            .or_else(|| attr("PLANNED-DURATION"))
+            .and_then(|d| d.parse::<f64>().ok());
+        if let Some(duration) = duration {
+            if seen.insert(attr("ID").unwrap_or_default().to_string()) {
+                total += duration;
+            }
+        }
+    }
+    (total > 0.0).then_some(total)
 }
 
 /// Pure scan of a media playlist. Returns `(ads_present, matched_markers)`.
@@ -53,6 +101,7 @@ pub fn update(state: &mut AdDetectionState, playlist: &str) -> Option<u32> {
     if ads {
         if !state.ads_present {
             state.ad_events = state.ad_events.saturating_add(1);
+            state.break_started = Some(std::time::Instant::now());
             new_break = Some(state.ad_events);
         }
         state.ads_present = true;
@@ -60,6 +109,7 @@ pub fn update(state: &mut AdDetectionState, playlist: &str) -> Option<u32> {
     } else {
         state.ads_present = false;
         state.consecutive_ad_polls = 0;
+        state.break_started = None;
     }
     state.matched_markers = matched;
     new_break
@@ -278,6 +328,22 @@ ad0.ts\n";
         assert!(markers.is_empty());
     }
 
+    #[test]
+    fn break_duration_sums_distinct_dateranges() {
+        let pl = "#EXTM3U\n\
+#EXT-X-DATERANGE:ID=\"stitched-ad-1\",CLASS=\"twitch-stitched-ad\",DURATION=15.0\n\
+#EXT-X-DATERANGE:ID=\"stitched-ad-2\",CLASS=\"twitch-stitched-ad\",PLANNED-DURATION=30.5\n\
+#EXT-X-DATERANGE:ID=\"stitched-ad-1\",CLASS=\"twitch-stitched-ad\",DURATION=15.0\n\
+#EXT-X-DATERANGE:ID=\"source-1\",CLASS=\"twitch-session\",DURATION=99\n\
+#EXTINF:2.000,Amazon\n\
+ad0.ts\n";
+        assert_eq!(break_duration(pl), Some(45.5));
+        assert_eq!(
+            break_duration("#EXTM3U\n#EXTINF:2.000,Amazon\nad0.ts\n"),
+            None
+        );
+    }
+
     #[test]
     fn update_tracks_break_transitions() {
         let ad_pl = "#EXTM3U\n#EXTINF:2.000,Amazon\nad0.ts\n";
@@ -0,0 +1,96 @@
+//! Failures a caller can act on. A service that can tell what went wrong
+//! (the sign-in is gone, the user isn't a moderator, Twitch is rate limiting)
+//! returns an `ApiError` inside its `anyhow::Error`, with the message it
+//! always had; `kind_of` reads the kind back out, along with reqwest's
+//! transport failures. `commands::error::CommandError` is built from it.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The Twitch token is missing, expired or lacks a scope; sign in again.
+    AuthExpired,
+    /// The signed-in user isn't a moderator of the channel.
+    NotAModerator,
+    /// Twitch refused for another reason (channel settings, restricted
+    /// target).
+    Forbidden,
+    NotFound,
+    /// The channel isn't live.
+    Offline,
+    /// The channel turned the feature off.
+    Disabled,
+    RateLimited,
+    /// No answer: offline, DNS, timeout, or Twitch having an outage.
+    Network,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// The stored sign-in is missing, expired, or couldn't be refreshed.
+    pub fn auth_expired(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::AuthExpired, message)
+    }
+
+    /// A Helix call Twitch answered with `status`. `forbidden` is what a 403
+    /// means for the endpoint: `NotAModerator` where it's moderator-gated.
+    pub fn helix(
+        status: reqwest::StatusCode,
+        forbidden: ErrorKind,
+        message: impl Into<String>,
+    ) -> Self {
+        Self::new(status_kind(status, forbidden), message)
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+fn status_kind(status: reqwest::StatusCode, forbidden: ErrorKind) -> ErrorKind {
+    match status.as_u16() {
+        401 => ErrorKind::AuthExpired,
+        403 => forbidden,
+        404 => ErrorKind::NotFound,
+        429 => ErrorKind::RateLimited,
+        500..=599 => ErrorKind::Network,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// The kind of the first error in `error`'s chain that carries one: an
+/// `ApiError`, or a reqwest failure (no answer, or an error status).
+pub fn kind_of(error: &anyhow::Error) -> ErrorKind {
+    for cause in error.chain() {
+        if let Some(api) = cause.downcast_ref::<ApiError>() {
+            return api.kind;
+        }
+        if let Some(http) = cause.downcast_ref::<reqwest::Error>() {
+            if http.is_timeout() || http.is_connect() || http.is_request() {
+                return ErrorKind::Network;
+            }
+            if let Some(status) = http.status() {
+                return status_kind(status, ErrorKind::Forbidden);
+            }
+        }
+    }
+    ErrorKind::Other
+}
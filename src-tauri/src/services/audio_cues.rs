@@ -0,0 +1,178 @@
+//! Audio cues: the sounds played for mentions, whispers, claimed drops and
+//! channels going live (`Settings.audio_cues`).
+//!
+//! Playback is the frontend's (utils/audioCues.ts), through the same Web
+//! Audio engine as the notification sounds, or an `<audio>` element for a
+//! user's WAV. This side checks a WAV before it's chosen, so a file the
+//! webview can't play is refused up front instead of staying silent, and
+//! keeps the cue mute and Do Not Disturb the same switch.
+
+use crate::models::settings::Settings;
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::path::Path;
+
+/// Sound ids the frontend synthesizes (`SoundId` in utils/notificationSound.ts).
+pub const BUILTIN_SOUNDS: &[&str] = &["boop", "tick", "soft", "whisper", "gentle"];
+
+/// Cues are short; anything past this is almost certainly the wrong file.
+const MAX_CUE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// WAVE_FORMAT_PCM, WAVE_FORMAT_IEEE_FLOAT and WAVE_FORMAT_EXTENSIBLE, the
+/// encodings every webview decodes.
+const PLAYABLE_FORMATS: &[u16] = &[1, 3, 0xFFFE];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WavInfo {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub bits_per_sample: u16,
+    pub duration_ms: u64,
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Read a RIFF/WAVE file's format and length.
+fn parse_wav(data: &[u8]) -> Result<WavInfo> {
+    if data.get(0..4) != Some(&b"RIFF"[..]) || data.get(8..12) != Some(&b"WAVE"[..]) {
+        return Err(anyhow!("Not a WAV file"));
+    }
+    let mut format = None;
+    let mut data_len = None;
+    let mut at = 12;
+    while let (Some(id), Some(len)) = (data.get(at..at + 4), u32_at(data, at + 4)) {
+        let body = at + 8;
+        match id {
+            b"fmt " => {
+                format = Some((
+                    u16_at(data, body).ok_or_else(|| anyhow!("Truncated fmt chunk"))?,
+                    u16_at(data, body + 2).unwrap_or(0),
+                    u32_at(data, body + 4).unwrap_or(0),
+                    u32_at(data, body + 8).unwrap_or(0),
+                    u16_at(data, body + 14).unwrap_or(0),
+                ))
+            }
+            // Some writers leave the size at 0 or past the end while
+            // streaming; what's actually there is what plays.
+            b"data" => data_len = Some((len as usize).min(data.len().saturating_sub(body))),
+            _ => {}
+        }
+        if format.is_some() && data_len.is_some() {
+            break;
+        }
+        // Chunks are padded to an even length.
+        at = body + len as usize + (len as usize & 1);
+    }
+    let (tag, channels, sample_rate, byte_rate, bits_per_sample) =
+        format.ok_or_else(|| anyhow!("WAV file has no format chunk"))?;
+    if !PLAYABLE_FORMATS.contains(&tag) {
+        return Err(anyhow!(
+            "Compressed WAV (format {:#06x}) isn't supported; save it as PCM",
+            tag
+        ));
+    }
+    let data_len = data_len.ok_or_else(|| anyhow!("WAV file has no audio"))?;
+    if channels == 0 || sample_rate == 0 || byte_rate == 0 || data_len == 0 {
+        return Err(anyhow!("WAV file has no audio"));
+    }
+    Ok(WavInfo {
+        channels,
+        sample_rate,
+        bits_per_sample,
+        duration_ms: data_len as u64 * 1000 / byte_rate as u64,
+    })
+}
+
+/// Check a cue's sound: nothing to check for a built-in one, the WAV's
+/// format otherwise.
+pub fn check_sound(sound: &str) -> Result<Option<WavInfo>> {
+    if sound.is_empty() || BUILTIN_SOUNDS.contains(&sound) {
+        return Ok(None);
+    }
+    inspect_wav(Path::new(sound)).map(Some)
+}
+
+fn inspect_wav(path: &Path) -> Result<WavInfo> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| anyhow!("Couldn't open {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_CUE_BYTES {
+        return Err(anyhow!(
+            "{} is {} MB; cues must be under {} MB",
+            path.display(),
+            size / (1024 * 1024),
+            MAX_CUE_BYTES / (1024 * 1024)
+        ));
+    }
+    parse_wav(&std::fs::read(path)?)
+}
+
+/// Keep the cue mute and Do Not Disturb one switch: whichever `next` changed
+/// from `previous` wins.
+pub fn sync_mute(previous: &Settings, next: &mut Settings) {
+    let dnd = next.live_notifications.do_not_disturb;
+    if dnd != previous.live_notifications.do_not_disturb {
+        next.audio_cues.muted = dnd;
+    } else {
+        next.live_notifications.do_not_disturb = next.audio_cues.muted;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wav(format: u16, samples: usize) -> Vec<u8> {
+        let (channels, rate, bits) = (1u16, 8000u32, 16u16);
+        let block = channels * bits / 8;
+        let mut data = b"RIFF\0\0\0\0WAVE".to_vec();
+        data.extend(b"LIST\x03\0\0\0abc\0");
+        data.extend(b"fmt \x10\0\0\0");
+        data.extend(format.to_le_bytes());
+        data.extend(channels.to_le_bytes());
+        data.extend(rate.to_le_bytes());
+        data.extend((rate * block as u32).to_le_bytes());
+        data.extend(block.to_le_bytes());
+        data.extend(bits.to_le_bytes());
+        data.extend(b"data");
+        data.extend(((samples * block as usize) as u32).to_le_bytes());
+        data.extend(vec![0u8; samples * block as usize]);
+        data
+    }
+
+    #[test]
+    fn reads_pcm_and_refuses_what_wont_play() {
+        assert_eq!(
+            parse_wav(&wav(1, 4000)).unwrap(),
+            WavInfo {
+                channels: 1,
+                sample_rate: 8000,
+                bits_per_sample: 16,
+                duration_ms: 500,
+            }
+        );
+        assert!(parse_wav(&wav(0x11, 4000)).is_err());
+        assert!(parse_wav(&wav(1, 0)).is_err());
+        assert!(parse_wav(b"ID3\x04not a wav").is_err());
+    }
+
+    #[test]
+    fn mute_follows_whichever_side_changed() {
+        let previous = Settings::default();
+        let mut next = previous.clone();
+        next.live_notifications.do_not_disturb = true;
+        sync_mute(&previous, &mut next);
+        assert!(next.audio_cues.muted);
+
+        let previous = next.clone();
+        next.audio_cues.muted = false;
+        sync_mute(&previous, &mut next);
+        assert!(!next.live_notifications.do_not_disturb);
+    }
+}
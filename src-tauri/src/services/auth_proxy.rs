@@ -39,7 +39,51 @@ const SUB_TTL: Duration = Duration::from_secs(600); // 10 min per channel
 static TURBO_CACHE: OnceCell<Mutex<HashMap<String, (bool, Instant)>>> = OnceCell::new();
 static SUB_CACHE: OnceCell<Mutex<HashMap<(String, String), (bool, Instant)>>> = OnceCell::new();
 
-/// Drop every cached Turbo/sub verdict. Called on account switch and logout so
+// Masters fetched ahead of playback by `favorite_warmup`, keyed like the
+// entitlement caches by (token, channel). Each is used at most once and only
+// for `WARM_TTL`, well inside the lifetime of the token signed into it; the
+// player's own reloads always go to usher.
+const WARM_TTL: Duration = Duration::from_secs(300);
+static WARM_MASTERS: OnceCell<Mutex<HashMap<(String, String), (String, Instant)>>> =
+    OnceCell::new();
+
+fn warm_key(channel: &str, oauth_token: Option<&str>) -> (String, String) {
+    (
+        oauth_token.unwrap_or_default().to_string(),
+        channel.to_lowercase(),
+    )
+}
+
+/// The warmed master for `channel`, if one is still fresh. Removes it.
+fn take_warm_master(channel: &str, oauth_token: Option<&str>) -> Option<String> {
+    let lock = WARM_MASTERS.get_or_init(|| Mutex::new(HashMap::new()));
+    let (master, at) = lock
+        .lock()
+        .unwrap()
+        .remove(&warm_key(channel, oauth_token))?;
+    (at.elapsed() < WARM_TTL).then_some(master)
+}
+
+/// Fetch `channel`'s playback token and master now, without starting
+/// playback, so the next `fetch_auth_master` for it within `WARM_TTL` skips
+/// both round trips. Also fills the entitlement caches `resolve_live` checks
+/// first.
+pub(crate) async fn warm_master(channel: &str, oauth_token: Option<&str>) -> Result<()> {
+    if let Some(token) = oauth_token {
+        if !account_has_turbo(token).await {
+            is_subscribed(channel, token).await;
+        }
+    }
+    let master = fetch_auth_master(channel, oauth_token).await?;
+    WARM_MASTERS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap()
+        .insert(warm_key(channel, oauth_token), (master, Instant::now()));
+    Ok(())
+}
+
+/// Drop every cached Turbo/sub verdict and warmed master. Called on account switch and logout so
 /// the next resolution re-checks entitlement for the now-active account rather
 /// than serving a verdict cached against the previous account's token. The
 /// caches key by token, so a genuinely different token would re-check on its own
@@ -52,6 +96,9 @@ pub(crate) fn clear_entitlement_caches() {
     if let Some(cache) = SUB_CACHE.get() {
         cache.lock().unwrap().clear();
     }
+    if let Some(cache) = WARM_MASTERS.get() {
+        cache.lock().unwrap().clear();
+    }
 }
 
 /// POST an inline GQL query with the viewer's web cookie; return the JSON body.
@@ -189,6 +236,10 @@ pub(crate) async fn fetch_auth_master(channel: &str, oauth_token: Option<&str>)
     } else {
         oauth_token
     };
+    if let Some(master) = take_warm_master(channel, oauth_token) {
+        debug!("[AuthProxy] {channel}: using the warmed master");
+        return Ok(master);
+    }
     // Region unlock (below) only helps an authenticated viewer: an anonymous token
     // stays not-logged-in blocked from the high tiers regardless of fetch region.
     let authenticated = oauth_token.is_some();
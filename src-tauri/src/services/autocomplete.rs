@@ -0,0 +1,431 @@
+//! Chat autocomplete: per joined channel, an index of its emotes and recent
+//! chatters, ranked by frecency (how often and how recently the user sent
+//! each one), so the input box asks once per keystroke instead of filtering
+//! every emote in JS.
+//!
+//! The emote index is rebuilt whenever the IRC side stores a channel's emote
+//! set and dropped when the channel is left. Chatters come from the messages
+//! IRC parses. Frecency is kept across channels and sessions in
+//! `autocomplete_frecency.json`; each use counts 1 and halves every
+//! `HALF_LIFE_SECS`. Sending only marks the store dirty; a background task
+//! writes it at most every `SAVE_INTERVAL`.
+
+use crate::services::emote_service::{EmoteProvider, EmoteSet};
+use chrono::Utc;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex as StdMutex, RwLock as StdRwLock};
+use std::time::Duration;
+
+const HALF_LIFE_SECS: f64 = 7.0 * 24.0 * 3600.0;
+/// Recent chatters kept per channel; the longest-quiet go first.
+const MAX_CHATTERS: usize = 500;
+/// Terms kept in the frecency store; the lowest scores go first.
+const MAX_FRECENCY_TERMS: usize = 5000;
+const FRECENCY_FILE: &str = "autocomplete_frecency.json";
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionKind {
+    Emote,
+    User,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Completion {
+    pub kind: CompletionKind,
+    /// The emote name, or the chatter's display name.
+    pub name: String,
+    /// What to insert: the emote name, or `@login`.
+    pub insert: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<EmoteProvider>,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone)]
+struct EmoteEntry {
+    key: String,
+    name: String,
+    url: String,
+    provider: EmoteProvider,
+}
+
+#[derive(Default)]
+struct ChannelIndex {
+    /// Sorted by `key` (lowercase name) for prefix search.
+    emotes: Vec<EmoteEntry>,
+    /// login -> (display name, unix secs last seen).
+    chatters: HashMap<String, (String, i64)>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+struct Use {
+    score: f64,
+    /// Unix seconds `score` was last brought up to date.
+    at: i64,
+}
+
+impl Use {
+    fn decayed(&self, now: i64) -> f64 {
+        let age = (now - self.at).max(0) as f64;
+        self.score * 0.5f64.powf(age / HALF_LIFE_SECS)
+    }
+}
+
+static INDEX: Lazy<StdRwLock<HashMap<String, ChannelIndex>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+static FRECENCY: Lazy<StdMutex<HashMap<String, Use>>> = Lazy::new(|| StdMutex::new(load()));
+/// Set when `FRECENCY` has changes the save task hasn't written yet.
+static FRECENCY_DIRTY: AtomicBool = AtomicBool::new(false);
+static SAVE_TASK_STARTED: AtomicBool = AtomicBool::new(false);
+
+fn frecency_path() -> Option<PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(FRECENCY_FILE))
+}
+
+fn load() -> HashMap<String, Use> {
+    frecency_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save(uses: &HashMap<String, Use>) {
+    let Some(path) = frecency_path() else {
+        return;
+    };
+    match serde_json::to_string(uses) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                debug!("[Autocomplete] Couldn't save frecency: {}", e);
+            }
+        }
+        Err(e) => debug!("[Autocomplete] Couldn't serialize frecency: {}", e),
+    }
+}
+
+/// Start the task that writes dirty frecency on the blocking pool, so sending
+/// a message never waits on the disk.
+fn ensure_save_task() {
+    if SAVE_TASK_STARTED
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    // No runtime yet: stay dirty, and the next use starts the task.
+    if tokio::runtime::Handle::try_current().is_err() {
+        SAVE_TASK_STARTED.store(false, Ordering::SeqCst);
+        return;
+    }
+    tokio::spawn(async {
+        loop {
+            tokio::time::sleep(SAVE_INTERVAL).await;
+            if !FRECENCY_DIRTY.swap(false, Ordering::AcqRel) {
+                continue;
+            }
+            let snapshot = FRECENCY.lock().unwrap().clone();
+            let _ = tokio::task::spawn_blocking(move || save(&snapshot)).await;
+        }
+    });
+}
+
+fn channel_key(channel: &str) -> String {
+    channel.trim_start_matches('#').to_lowercase()
+}
+
+/// Frecency key: the emote name as is, `@login` for a chatter.
+fn term(kind: CompletionKind, name: &str) -> String {
+    match kind {
+        CompletionKind::Emote => name.to_string(),
+        CompletionKind::User => format!("@{}", name.to_lowercase()),
+    }
+}
+
+/// Rebuild `channel`'s emote index from its set.
+pub fn index_emotes(channel: &str, set: &EmoteSet) {
+    let mut emotes: Vec<EmoteEntry> = set
+        .twitch
+        .iter()
+        .chain(&set.seven_tv)
+        .chain(&set.bttv)
+        .chain(&set.ffz)
+        .chain(&set.kick)
+        .map(|e| EmoteEntry {
+            key: e.name.to_lowercase(),
+            name: e.name.clone(),
+            url: e.url.clone(),
+            provider: e.provider.clone(),
+        })
+        .collect();
+    emotes.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.name.cmp(&b.name)));
+    // The same name from two providers resolves to one emote in chat.
+    emotes.dedup_by(|a, b| a.name == b.name);
+    if let Ok(mut index) = INDEX.write() {
+        index.entry(channel_key(channel)).or_default().emotes = emotes;
+    }
+}
+
+/// A message from `login` in `channel`.
+pub fn note_chatter(channel: &str, login: &str, display_name: &str) {
+    let Ok(mut index) = INDEX.write() else {
+        return;
+    };
+    let chatters = &mut index.entry(channel_key(channel)).or_default().chatters;
+    let display = if display_name.is_empty() {
+        login
+    } else {
+        display_name
+    };
+    chatters.insert(
+        login.to_lowercase(),
+        (display.to_string(), Utc::now().timestamp()),
+    );
+    if chatters.len() > MAX_CHATTERS {
+        if let Some(oldest) = chatters
+            .iter()
+            .min_by_key(|(_, (_, seen))| *seen)
+            .map(|(login, _)| login.clone())
+        {
+            chatters.remove(&oldest);
+        }
+    }
+}
+
+/// Drop `channel`'s index (it was left).
+pub fn forget_channel(channel: &str) {
+    if let Ok(mut index) = INDEX.write() {
+        index.remove(&channel_key(channel));
+    }
+}
+
+/// Drop every channel's index (chat stopped). Frecency is kept.
+pub fn forget_all() {
+    if let Ok(mut index) = INDEX.write() {
+        index.clear();
+    }
+}
+
+/// Terms in a sent message that count as uses: `@mentions`, and words that
+/// are emotes in `channel` (any indexed channel when None).
+fn used_terms(
+    index: &HashMap<String, ChannelIndex>,
+    channel: Option<&str>,
+    message: &str,
+) -> Vec<String> {
+    let is_emote = |word: &str| {
+        let key = word.to_lowercase();
+        let has = |c: &ChannelIndex| {
+            let start = c.emotes.partition_point(|e| e.key < key);
+            c.emotes[start..]
+                .iter()
+                .take_while(|e| e.key == key)
+                .any(|e| e.name == word)
+        };
+        match channel {
+            Some(channel) => index.get(&channel_key(channel)).is_some_and(has),
+            None => index.values().any(has),
+        }
+    };
+    let mut terms: Vec<String> = message
+        .split_whitespace()
+        .filter_map(|word| {
+            if let Some(login) = word.strip_prefix('@') {
+                let login = login.trim_end_matches([',', ':', '.', '!', '?']);
+                (!login.is_empty()).then(|| term(CompletionKind::User, login))
+            } else {
+                is_emote(word).then(|| term(CompletionKind::Emote, word))
+            }
+        })
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+/// Count the emotes and mentions in a message the user sent.
+pub fn record_sent(channel: Option<&str>, message: &str) {
+    if message.trim_start().starts_with('/') {
+        return;
+    }
+    let terms = match INDEX.read() {
+        Ok(index) => used_terms(&index, channel, message),
+        Err(_) => return,
+    };
+    if terms.is_empty() {
+        return;
+    }
+    let now = Utc::now().timestamp();
+    let mut uses = FRECENCY.lock().unwrap();
+    for term in terms {
+        let entry = uses.entry(term).or_insert(Use {
+            score: 0.0,
+            at: now,
+        });
+        *entry = Use {
+            score: entry.decayed(now) + 1.0,
+            at: now,
+        };
+    }
+    if uses.len() > MAX_FRECENCY_TERMS {
+        let mut scored: Vec<(String, f64)> = uses
+            .iter()
+            .map(|(t, u)| (t.clone(), u.decayed(now)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        for (term, _) in scored.into_iter().skip(MAX_FRECENCY_TERMS) {
+            uses.remove(&term);
+        }
+    }
+    drop(uses);
+    FRECENCY_DIRTY.store(true, Ordering::Release);
+    ensure_save_task();
+}
+
+/// Order completions best first: frecency, then an exact-case prefix match,
+/// then the shorter name, then alphabetically.
+fn rank(completions: &mut [Completion], prefix: &str) {
+    completions.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.name.starts_with(prefix).cmp(&a.name.starts_with(prefix)))
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Up to `limit` completions for `prefix` in `channel` (every indexed channel
+/// when None). A prefix starting with `@` completes chatters only; otherwise
+/// emotes come first and chatters fill in behind equally-scored ones.
+pub fn autocomplete(channel: Option<&str>, prefix: &str, limit: usize) -> Vec<Completion> {
+    let users_only = prefix.starts_with('@');
+    let bare = prefix.trim_start_matches('@');
+    if bare.is_empty() || limit == 0 {
+        return Vec::new();
+    }
+    let key = bare.to_lowercase();
+    let Ok(index) = INDEX.read() else {
+        return Vec::new();
+    };
+    let uses = FRECENCY.lock().unwrap();
+    let now = Utc::now().timestamp();
+    let score = |kind, name: &str| {
+        uses.get(&term(kind, name))
+            .map(|u| u.decayed(now))
+            .unwrap_or(0.0)
+    };
+
+    let channels: Vec<&ChannelIndex> = match channel {
+        Some(channel) => index.get(&channel_key(channel)).into_iter().collect(),
+        None => index.values().collect(),
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut out = Vec::new();
+    for c in &channels {
+        if !users_only {
+            let start = c.emotes.partition_point(|e| e.key.as_str() < key.as_str());
+            for e in c.emotes[start..]
+                .iter()
+                .take_while(|e| e.key.starts_with(&key))
+            {
+                if seen.insert(term(CompletionKind::Emote, &e.name)) {
+                    out.push(Completion {
+                        kind: CompletionKind::Emote,
+                        name: e.name.clone(),
+                        insert: e.name.clone(),
+                        url: Some(e.url.clone()),
+                        provider: Some(e.provider.clone()),
+                        score: score(CompletionKind::Emote, &e.name),
+                    });
+                }
+            }
+        }
+        for (login, (display, _)) in &c.chatters {
+            if !login.starts_with(&key) && !display.to_lowercase().starts_with(&key) {
+                continue;
+            }
+            if seen.insert(term(CompletionKind::User, login)) {
+                out.push(Completion {
+                    kind: CompletionKind::User,
+                    name: display.clone(),
+                    insert: format!("@{}", login),
+                    url: None,
+                    provider: None,
+                    score: score(CompletionKind::User, login),
+                });
+            }
+        }
+    }
+    rank(&mut out, bare);
+    out.truncate(limit);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str) -> EmoteEntry {
+        EmoteEntry {
+            key: name.to_lowercase(),
+            name: name.to_string(),
+            url: String::new(),
+            provider: EmoteProvider::SevenTV,
+        }
+    }
+
+    #[test]
+    fn frecency_halves_every_half_life() {
+        let used = Use { score: 4.0, at: 0 };
+        assert_eq!(used.decayed(0), 4.0);
+        assert!((used.decayed(HALF_LIFE_SECS as i64) - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sent_messages_count_emotes_and_mentions() {
+        let mut index = HashMap::new();
+        index.insert(
+            "alpha".to_string(),
+            ChannelIndex {
+                emotes: vec![entry("catJAM"), entry("KEKW")],
+                chatters: HashMap::new(),
+            },
+        );
+        assert_eq!(
+            used_terms(&index, Some("#Alpha"), "KEKW @Bob, kekw catJAM KEKW"),
+            ["@bob", "KEKW", "catJAM"]
+        );
+        assert!(used_terms(&index, Some("beta"), "KEKW").is_empty());
+    }
+
+    #[test]
+    fn frecent_names_rank_first() {
+        let completion = |name: &str, score| Completion {
+            kind: CompletionKind::Emote,
+            name: name.to_string(),
+            insert: name.to_string(),
+            url: None,
+            provider: None,
+            score,
+        };
+        let mut list = vec![
+            completion("peepoHappy", 0.0),
+            completion("Pepega", 0.0),
+            completion("pepeLaugh", 2.5),
+            completion("pepe", 0.0),
+        ];
+        rank(&mut list, "pe");
+        let names: Vec<&str> = list.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["pepeLaugh", "pepe", "peepoHappy", "Pepega"]);
+    }
+}
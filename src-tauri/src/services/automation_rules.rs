@@ -0,0 +1,344 @@
+//! User-defined "when X then Y" rules (`Settings::automation_rules`).
+//!
+//! Producers report what they see with `observe`: the drops monitor reports
+//! claims, the channel-points listener reports balances, and the rules' own
+//! poll reports the live state of every channel a rule names. The evaluator
+//! `BackgroundService` starts matches each observation against the enabled
+//! rules and runs the actions of the ones that fire.
+//!
+//! Live and title triggers fire on the edge (offline -> live, title starts
+//! containing the word); the first poll after startup only records the
+//! baseline, so channels already live don't fire every rule at launch.
+
+use crate::models::settings::{AppState, AutomationRule, RuleAction, RuleTrigger};
+use crate::models::stream::TwitchStream;
+use crate::services::event_bus;
+use crate::services::twitch_service::TwitchService;
+use log::{debug, warn};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Observation {
+    /// One poll of a channel a rule names; `stream` is None while offline.
+    Channel {
+        login: String,
+        stream: Option<TwitchStream>,
+    },
+    DropClaimed {
+        drop_id: String,
+        campaign_id: String,
+    },
+    PointsBalance {
+        channel_login: String,
+        previous: Option<i64>,
+        balance: i64,
+    },
+}
+
+impl Observation {
+    fn channel_login(&self) -> Option<&str> {
+        match self {
+            Self::Channel { login, .. } => Some(login),
+            Self::PointsBalance { channel_login, .. } => Some(channel_login),
+            Self::DropClaimed { .. } => None,
+        }
+    }
+}
+
+static QUEUE: OnceCell<mpsc::UnboundedSender<Observation>> = OnceCell::new();
+
+/// Report an observation. Dropped until the evaluator is running.
+pub fn observe(observation: Observation) {
+    if let Some(tx) = QUEUE.get() {
+        let _ = tx.send(observation);
+    }
+}
+
+/// Whether `trigger` holds for `observation`; None if it's about something
+/// else.
+fn condition(trigger: &RuleTrigger, observation: &Observation) -> Option<bool> {
+    match (trigger, observation) {
+        (RuleTrigger::ChannelLive { channel_login }, Observation::Channel { login, stream })
+            if channel_login.eq_ignore_ascii_case(login) =>
+        {
+            Some(stream.is_some())
+        }
+        (
+            RuleTrigger::TitleContains {
+                channel_login,
+                word,
+            },
+            Observation::Channel { login, stream },
+        ) if channel_login.eq_ignore_ascii_case(login) => Some(
+            stream
+                .as_ref()
+                .is_some_and(|s| s.title.to_lowercase().contains(&word.trim().to_lowercase())),
+        ),
+        (RuleTrigger::DropClaimed, Observation::DropClaimed { .. }) => Some(true),
+        (
+            RuleTrigger::PointsBalanceAbove {
+                channel_login,
+                threshold,
+            },
+            Observation::PointsBalance {
+                channel_login: login,
+                previous,
+                balance,
+            },
+        ) if channel_login.eq_ignore_ascii_case(login) => {
+            Some(previous.is_some_and(|p| p <= *threshold) && balance > threshold)
+        }
+        _ => None,
+    }
+}
+
+/// Rules `observation` fires. `last` holds the previous condition of each
+/// edge-triggered (polled) rule.
+fn fired<'a>(
+    rules: &'a [AutomationRule],
+    observation: &Observation,
+    last: &mut HashMap<String, bool>,
+) -> Vec<&'a AutomationRule> {
+    let mut fired = Vec::new();
+    for rule in rules.iter().filter(|r| r.enabled) {
+        let Some(holds) = condition(&rule.trigger, observation) else {
+            continue;
+        };
+        if matches!(observation, Observation::Channel { .. }) {
+            if last.insert(rule.id.clone(), holds) == Some(false) && holds {
+                fired.push(rule);
+            }
+        } else if holds {
+            fired.push(rule);
+        }
+    }
+    fired
+}
+
+/// Logins named by enabled live/title rules.
+fn polled_logins(rules: &[AutomationRule]) -> Vec<String> {
+    let logins: HashSet<String> = rules
+        .iter()
+        .filter(|r| r.enabled)
+        .filter_map(|r| match &r.trigger {
+            RuleTrigger::ChannelLive { channel_login }
+            | RuleTrigger::TitleContains { channel_login, .. } => {
+                Some(channel_login.trim().to_lowercase())
+            }
+            _ => None,
+        })
+        .filter(|login| !login.is_empty())
+        .collect();
+    logins.into_iter().collect()
+}
+
+fn rules(app: &AppHandle) -> Vec<AutomationRule> {
+    app.state::<AppState>()
+        .settings
+        .lock()
+        .map(|s| s.automation_rules.clone())
+        .unwrap_or_default()
+}
+
+/// Start the evaluator and the channel poll. Called once by
+/// `BackgroundService::start`.
+pub fn start(app: AppHandle) {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    if QUEUE.set(tx).is_err() {
+        return;
+    }
+
+    let poll_app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let logins = polled_logins(&rules(&poll_app));
+            if logins.is_empty() {
+                continue;
+            }
+            let live = match TwitchService::check_streams_online(&logins).await {
+                Ok(live) => live,
+                Err(e) => {
+                    debug!("[Rules] Live check failed: {}", e);
+                    continue;
+                }
+            };
+            for login in logins {
+                let stream = live
+                    .iter()
+                    .find(|s| s.user_login.eq_ignore_ascii_case(&login))
+                    .cloned();
+                observe(Observation::Channel { login, stream });
+            }
+        }
+    });
+
+    tauri::async_runtime::spawn(async move {
+        let mut last: HashMap<String, bool> = HashMap::new();
+        while let Some(observation) = rx.recv().await {
+            let rules = rules(&app);
+            for rule in fired(&rules, &observation, &mut last) {
+                debug!("[Rules] '{}' fired on {:?}", rule.name, observation);
+                if let Err(e) = run_action(&app, rule, &observation).await {
+                    warn!("[Rules] Action for '{}' failed: {}", rule.name, e);
+                }
+            }
+        }
+    });
+}
+
+async fn run_action(
+    app: &AppHandle,
+    rule: &AutomationRule,
+    observation: &Observation,
+) -> anyhow::Result<()> {
+    let state = app.state::<AppState>();
+    match &rule.action {
+        RuleAction::StartRecording { channel_login } => {
+            let login = channel_login
+                .as_deref()
+                .or(observation.channel_login())
+                .ok_or_else(|| anyhow::anyhow!("No channel to record"))?;
+            crate::services::recording_guard::start(app, login).await?;
+        }
+        RuleAction::StartMining { campaign_id } => {
+            let (action, args) = match campaign_id {
+                Some(id) => ("drops.run", json!({ "campaign_id": id })),
+                None => ("drops.run-auto", json!({})),
+            };
+            let args = crate::commands::drops::drops_action_args(&state, action, args).await;
+            state.plugin_host.invoke_action(action, args).await?;
+        }
+        RuleAction::SendWebhook { url } => {
+            crate::services::webhooks::send(
+                url,
+                &format!("rule:{}", rule.id),
+                &rule.name,
+                "automation_rule",
+                json!({
+                    "rule_id": rule.id,
+                    "rule_name": rule.name,
+                    "trigger": observation,
+                }),
+            )
+            .await?;
+        }
+        RuleAction::SwitchObsScene { scene } => {
+            let obs = state.settings.lock().unwrap().obs.clone();
+            crate::services::obs_service::switch_scene(&obs, scene).await?;
+        }
+        RuleAction::Notify { message } => {
+            let _ = event_bus::emit(
+                "automation-rule-notification",
+                json!({
+                    "rule_id": rule.id,
+                    "rule_name": rule.name,
+                    "message": message.clone().unwrap_or_else(|| rule.name.clone()),
+                    "trigger": observation,
+                }),
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, trigger: RuleTrigger) -> AutomationRule {
+        AutomationRule {
+            id: id.to_string(),
+            name: id.to_string(),
+            enabled: true,
+            trigger,
+            action: RuleAction::Notify { message: None },
+        }
+    }
+
+    fn channel(login: &str, title: Option<&str>) -> Observation {
+        Observation::Channel {
+            login: login.to_string(),
+            stream: title.map(|title| {
+                serde_json::from_value(json!({
+                    "id": "1", "user_id": "1", "user_name": login, "user_login": login,
+                    "title": title, "viewer_count": 1, "game_name": "",
+                    "thumbnail_url": "", "started_at": "",
+                }))
+                .unwrap()
+            }),
+        }
+    }
+
+    #[test]
+    fn live_and_title_rules_fire_on_the_edge_only() {
+        let rules = vec![
+            rule(
+                "live",
+                RuleTrigger::ChannelLive {
+                    channel_login: "Alpha".into(),
+                },
+            ),
+            rule(
+                "title",
+                RuleTrigger::TitleContains {
+                    channel_login: "alpha".into(),
+                    word: "speedrun".into(),
+                },
+            ),
+        ];
+        let mut last = HashMap::new();
+        let ids = |fired: Vec<&AutomationRule>| -> Vec<String> {
+            fired.into_iter().map(|r| r.id.clone()).collect()
+        };
+        // Baseline: already live at startup doesn't fire.
+        assert!(fired(&rules, &channel("alpha", Some("chill")), &mut last).is_empty());
+        assert_eq!(
+            ids(fired(
+                &rules,
+                &channel("alpha", Some("SPEEDRUN any%")),
+                &mut last
+            )),
+            ["title"]
+        );
+        assert!(fired(&rules, &channel("alpha", None), &mut last).is_empty());
+        assert_eq!(
+            ids(fired(
+                &rules,
+                &channel("alpha", Some("Speedrun")),
+                &mut last
+            )),
+            ["live", "title"]
+        );
+    }
+
+    #[test]
+    fn balance_rule_fires_when_crossing_the_threshold() {
+        let rules = vec![rule(
+            "points",
+            RuleTrigger::PointsBalanceAbove {
+                channel_login: "alpha".into(),
+                threshold: 1000,
+            },
+        )];
+        let balance = |previous, balance| Observation::PointsBalance {
+            channel_login: "alpha".into(),
+            previous,
+            balance,
+        };
+        let mut last = HashMap::new();
+        assert_eq!(fired(&rules, &balance(Some(950), 1010), &mut last).len(), 1);
+        assert!(fired(&rules, &balance(Some(1010), 1060), &mut last).is_empty());
+        assert!(fired(&rules, &balance(None, 1060), &mut last).is_empty());
+    }
+}
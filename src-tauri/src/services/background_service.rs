@@ -2,16 +2,20 @@ use log::{debug, error};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Listener};
+use tauri::{AppHandle, Listener};
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 
 use chrono::Utc;
 
 use crate::models::drops::{ChannelPointsClaim, ChannelPointsClaimType};
+use crate::services::automation_rules::{self, Observation};
 use crate::services::channel_points_websocket_service::ChannelPointsWebSocketService;
 use crate::services::drops_auth_service::DropsAuthService;
 use crate::services::drops_service::DropsService;
+use crate::services::event_bus;
+use crate::services::feature_flags::{self, Feature};
+use crate::services::twitch_api::{HttpTwitchApi, TwitchApi, GQL_BATCH_LIMIT};
 
 /// How often the automation balance poll re-reads followed-channel balances. The
 /// plugin earns ~10 points/min passively and sweeps bonus chests every few
@@ -38,6 +42,8 @@ pub struct BackgroundService {
     /// the Autopilot master toggle (auto_claim_channel_points): `set_automation_active`
     /// spawns it on, aborts it off. `None` means no poll is running.
     points_poll: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Helix/GQL access for the balance poll.
+    api: Arc<dyn TwitchApi>,
 }
 
 impl BackgroundService {
@@ -49,6 +55,7 @@ impl BackgroundService {
             app_handle,
             watched: Arc::new(RwLock::new(None)),
             points_poll: Arc::new(Mutex::new(None)),
+            api: Arc::new(HttpTwitchApi::new()),
         }
     }
 
@@ -64,6 +71,9 @@ impl BackgroundService {
             *is_running = true;
         }
 
+        automation_rules::start(self.app_handle.clone());
+        crate::services::favorite_warmup::start(self.app_handle.clone());
+
         // Accumulate lifetime/history from every channel-points-earned event
         // (the watched channel's claims via claim_channel_points, and collected
         // channels via the balance poll). The single source for the lifetime
@@ -91,8 +101,26 @@ impl BackgroundService {
                     // and the points accolades.
                     if balance > 0 {
                         if let Some(cid) = channel_id.as_deref() {
-                            ds.update_channel_points_balance(cid, &channel_name, balance)
+                            let previous = ds
+                                .update_channel_points_balance(cid, &channel_name, balance)
                                 .await;
+                            automation_rules::observe(Observation::PointsBalance {
+                                channel_login: channel_name.clone(),
+                                previous: previous.map(i64::from),
+                                balance: balance as i64,
+                            });
+                            for target in ds.points_targets_crossed(cid, previous, balance).await {
+                                let _ = event_bus::emit(
+                                    "channel-points-target-reached",
+                                    serde_json::json!({
+                                        "channel_id": cid,
+                                        "channel_name": channel_name,
+                                        "target": target.target,
+                                        "label": target.label,
+                                        "balance": balance,
+                                    }),
+                                );
+                            }
                         }
                     }
 
@@ -111,19 +139,79 @@ impl BackgroundService {
                                 }
                                 _ => ChannelPointsClaimType::Watch,
                             },
+                            balance_after: (balance > 0).then_some(balance),
                         };
                         ds.add_channel_points_claim(claim).await;
                     }
                 }
             });
         });
+
+        // Spends (reward redemptions, prediction bets) go into the same history
+        // as negative entries so per-channel trends show net movement.
+        let drops_service_for_spent = self.drops_service.clone();
+        self.app_handle.listen("channel-points-spent", move |event| {
+            let drops_service = drops_service_for_spent.clone();
+            tokio::spawn(async move {
+                if let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload()) {
+                    let Some(channel_id) = payload["channel_id"].as_str() else {
+                        return;
+                    };
+                    let points = payload["points"].as_i64().unwrap_or(0) as i32;
+                    let balance = payload["balance"].as_i64().unwrap_or(0) as i32;
+                    if points <= 0 {
+                        return;
+                    }
+
+                    let ds = drops_service.lock().await;
+                    // The spent payload carries no name; reuse the stored one.
+                    let channel_name = ds
+                        .get_channel_points_balance(channel_id)
+                        .await
+                        .map(|b| b.channel_name)
+                        .unwrap_or_default();
+                    ds.update_channel_points_balance(channel_id, &channel_name, balance)
+                        .await;
+                    ds.record_points_spent(channel_id, &channel_name, points, balance)
+                        .await;
+                }
+            });
+        });
+
+        // Live drop minutes from the user-drop-events topic. Keeps the progress
+        // map current between the monitor's inventory polls.
+        let drops_service_for_progress = self.drops_service.clone();
+        self.app_handle.listen("drop-progress-update", move |event| {
+            let drops_service = drops_service_for_progress.clone();
+            tokio::spawn(async move {
+                if let Ok(payload) = serde_json::from_str::<serde_json::Value>(event.payload()) {
+                    let Some(drop_id) = payload["drop_id"].as_str() else {
+                        return;
+                    };
+                    let current = payload["current_minutes"].as_i64().unwrap_or(0) as i32;
+                    let required = payload["required_minutes"].as_i64().unwrap_or(0) as i32;
+                    drops_service
+                        .lock()
+                        .await
+                        .update_drop_progress_from_websocket(drop_id.into(), current, required)
+                        .await;
+                }
+            });
+        });
     }
 
     /// Point the realtime socket at the channel now on screen: subscribes its
     /// community-points (bonus-chest availability) and predictions topics.
-    /// Idempotent reconnect — calling it for a new channel drops the prior one.
+    /// The socket is shared across channel changes: an open session has the
+    /// prior channel's topics swapped for the new one's, and a fresh
+    /// connection is only opened when none exists (or the account changed).
     pub async fn set_watched_channel(&self, channel_id: String, login: String) {
-        *self.watched.write().await = Some((channel_id.clone(), login.clone()));
+        let previous = self
+            .watched
+            .write()
+            .await
+            .replace((channel_id.clone(), login.clone()))
+            .map(|(id, _)| id);
 
         let token = match DropsAuthService::get_token().await {
             Ok(t) => t,
@@ -137,24 +225,57 @@ impl BackgroundService {
 
         let mut ws = self.websocket_service.lock().await;
         ws.register_channel_mapping(&channel_id, &login, &login).await;
-        if let Err(e) = ws
-            .connect_to_channels(
-                vec![channel_id.clone()],
-                &user_id,
-                &token,
-                self.app_handle.clone(),
-            )
-            .await
-        {
-            error!("Failed to connect watched-channel socket: {}", e);
+
+        let has_session = ws.has_session_for(&user_id).await;
+        if has_session && previous.as_deref() == Some(channel_id.as_str()) {
+            // Same channel re-registered (e.g. a remount); already subscribed.
+            ws.register_active_channel(&channel_id).await;
+            return;
+        }
+
+        let swapped = has_session
+            && ws
+                .swap_channel(previous.as_deref(), &channel_id, &token)
+                .await;
+        if !swapped {
+            if let Err(e) = ws
+                .connect_to_channels(
+                    vec![channel_id.clone()],
+                    &user_id,
+                    &token,
+                    self.app_handle.clone(),
+                )
+                .await
+            {
+                error!("Failed to connect watched-channel socket: {}", e);
+            }
+            if let Some(prev) = previous.filter(|p| *p != channel_id) {
+                ws.unregister_active_channel(&prev).await;
+            }
         }
         ws.register_active_channel(&channel_id).await;
     }
 
+    /// Re-open the realtime socket on a fresh connection. After a system sleep
+    /// the old one is almost always dead without having noticed yet.
+    pub async fn reconnect_watched(&self) {
+        self.websocket_service.lock().await.disconnect_all().await;
+        // Taken so set_watched_channel connects instead of treating this as a
+        // same-channel re-register.
+        let watched = self.watched.write().await.take();
+        if let Some((channel_id, login)) = watched {
+            self.set_watched_channel(channel_id, login).await;
+        }
+    }
+
     /// Tear the realtime socket down when no channel is being watched.
     pub async fn clear_watched_channel(&self) {
-        *self.watched.write().await = None;
-        self.websocket_service.lock().await.disconnect_all().await;
+        let previous = self.watched.write().await.take();
+        let ws = self.websocket_service.lock().await;
+        if let Some((channel_id, _)) = previous {
+            ws.unregister_active_channel(&channel_id).await;
+        }
+        ws.disconnect_all().await;
     }
 
     /// Reflect the automation master toggle (auto_claim_channel_points). On, it
@@ -184,9 +305,8 @@ impl BackgroundService {
     /// channel, which `claim_channel_points` already notifies). The first cycle
     /// only seeds the baseline so existing holdings aren't reported as earns.
     fn spawn_points_poll(&self) -> JoinHandle<()> {
-        let app_handle = self.app_handle.clone();
-        let drops_service = self.drops_service.clone();
         let watched = self.watched.clone();
+        let api = self.api.clone();
 
         tokio::spawn(async move {
             let mut baseline: HashMap<String, i32> = HashMap::new();
@@ -195,8 +315,9 @@ impl BackgroundService {
 
             loop {
                 ticker.tick().await;
+                crate::services::power_saver::stretch_tick(AUTOMATION_POLL_INTERVAL).await;
 
-                let Some(balances) = Self::fetch_all_followed_balances().await else {
+                let Some(balances) = Self::fetch_all_followed_balances(api.as_ref()).await else {
                     continue;
                 };
 
@@ -206,32 +327,16 @@ impl BackgroundService {
                     .as_ref()
                     .map(|(id, _)| id.clone());
 
-                for (channel_id, login, display_name, balance) in &balances {
-                    let prev = baseline.insert(channel_id.clone(), *balance);
-
-                    // Skip the on-screen channel (claim_channel_points covers it)
-                    // and the seeding pass.
-                    if first || watched_id.as_ref() == Some(channel_id) {
-                        continue;
-                    }
-
-                    let Some(prev) = prev else { continue };
-                    if *balance <= prev {
-                        continue;
-                    }
-                    let delta = *balance - prev;
-
-                    {
-                        let ds = drops_service.lock().await;
-                        ds.update_channel_points_balance(channel_id, login, *balance)
-                            .await;
-                    }
-
+                let increases =
+                    balance_increases(&mut baseline, &balances, first, watched_id.as_deref());
+                for (channel_id, login, display_name, balance, delta) in increases {
+                    // The channel-points-earned listener stores the new balance
+                    // (and checks points targets against the old one).
                     debug!(
                         "[CP-Auto-Poll] +{} on {} (balance {})",
                         delta, login, balance
                     );
-                    let _ = app_handle.emit(
+                    let _ = event_bus::emit(
                         "channel-points-earned",
                         serde_json::json!({
                             "channel_id": channel_id,
@@ -250,50 +355,20 @@ impl BackgroundService {
     }
 
     /// Read the channel-points balance of every followed channel via the same
-    /// inline ChannelPointsContext GQL query the on-demand refresh uses, batched
-    /// 35 ops per request. Returns (channel_id, login, display_name, balance) for
-    /// channels with a positive balance, or None if the credential/list lookup fails.
-    async fn fetch_all_followed_balances() -> Option<Vec<(String, String, String, i32)>> {
-        use crate::services::twitch_service::TwitchService;
-        use serde_json::json;
-
-        const CLIENT_ID: &str = env!("TWITCH_WEB_CLIENT_ID");
-        const QUERY: &str = r#"
-        query ChannelPointsContext($channelLogin: String!) {
-            user(login: $channelLogin) {
-                channel {
-                    self {
-                        communityPoints {
-                            balance
-                        }
-                    }
-                }
-            }
-        }
-        "#;
-
-        let token = DropsAuthService::get_token().await.ok()?;
-        let client = crate::services::http::client();
-
-        // Drain the full followed list (live or offline): (login, channel_id, display_name).
-        let mut channels: Vec<(String, String, String)> = Vec::new();
+    /// ChannelPointsContext GQL query the on-demand refresh uses, batched
+    /// `GQL_BATCH_LIMIT` ops per request. Returns (channel_id, login, display_name,
+    /// balance) for channels with a positive balance, or None if the
+    /// credential/list lookup fails.
+    async fn fetch_all_followed_balances(
+        api: &dyn TwitchApi,
+    ) -> Option<Vec<(String, String, String, i32)>> {
+        // Drain the full followed list (live or offline).
+        let mut channels = Vec::new();
         let mut cursor: Option<String> = None;
         loop {
-            match TwitchService::get_all_followed_channels(100, cursor.clone()).await {
+            match api.followed_channels(cursor.clone()).await {
                 Ok((page, next)) => {
-                    for s in page {
-                        if !s.user_login.is_empty() && !s.user_id.is_empty() {
-                            // Keep the properly-cased display name so the earned-points
-                            // notification matches the PubSub path (which uses it too).
-                            // Falls back to the login only when the name is missing.
-                            let display_name = if s.user_name.trim().is_empty() {
-                                s.user_login.clone()
-                            } else {
-                                s.user_name
-                            };
-                            channels.push((s.user_login, s.user_id, display_name));
-                        }
-                    }
+                    channels.extend(page);
                     match next {
                         Some(c) => cursor = Some(c),
                         None => break,
@@ -307,59 +382,32 @@ impl BackgroundService {
         }
 
         let mut found: Vec<(String, String, String, i32)> = Vec::new();
-        for chunk in channels.chunks(35) {
-            let body: Vec<serde_json::Value> = chunk
-                .iter()
-                .map(|(login, _id, _name)| {
-                    json!({
-                        "operationName": "ChannelPointsContext",
-                        "query": QUERY,
-                        "variables": { "channelLogin": login.to_lowercase() }
-                    })
-                })
-                .collect();
-
-            let resp = match client
-                .post("https://gql.twitch.tv/gql")
-                .header("Client-Id", CLIENT_ID)
-                .header("Authorization", format!("OAuth {}", token))
-                .json(&body)
-                .send()
-                .await
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    debug!("[CP-Auto-Poll] balance batch failed: {}", e);
-                    continue;
+        for chunk in channels.chunks(GQL_BATCH_LIMIT) {
+            if !feature_flags::enabled(Feature::ChannelPoints) {
+                return None;
+            }
+            let logins: Vec<String> = chunk.iter().map(|c| c.login.clone()).collect();
+            let balances = match api.channel_points_balances(&logins).await {
+                Ok(b) => {
+                    feature_flags::record_success(Feature::ChannelPoints);
+                    b
                 }
-            };
-
-            let parsed: serde_json::Value = match resp.json().await {
-                Ok(v) => v,
                 Err(e) => {
-                    debug!("[CP-Auto-Poll] balance batch parse failed: {}", e);
+                    debug!("[CP-Auto-Poll] balance batch failed: {}", e);
+                    if !feature_flags::is_transport_error(&e) {
+                        feature_flags::record_failure(Feature::ChannelPoints, &e.to_string());
+                    }
                     continue;
                 }
             };
-
-            if let Some(arr) = parsed.as_array() {
-                for (idx, item) in arr.iter().enumerate() {
-                    let Some((login, channel_id, display_name)) = chunk.get(idx) else {
-                        continue;
-                    };
-                    if let Some(bal) = item
-                        .pointer("/data/user/channel/self/communityPoints/balance")
-                        .and_then(|v| v.as_i64())
-                    {
-                        if bal > 0 {
-                            found.push((
-                                channel_id.clone(),
-                                login.clone(),
-                                display_name.clone(),
-                                bal as i32,
-                            ));
-                        }
-                    }
+            for (channel, balance) in chunk.iter().zip(balances) {
+                if let Some(bal) = balance.filter(|b| *b > 0) {
+                    found.push((
+                        channel.channel_id.clone(),
+                        channel.login.clone(),
+                        channel.display_name.clone(),
+                        bal,
+                    ));
                 }
             }
         }
@@ -380,3 +428,85 @@ impl BackgroundService {
         json["user_id"].as_str().map(|s| s.to_string())
     }
 }
+
+/// Fold one poll's balances into `baseline` and return the channels whose
+/// balance rose since the previous poll, as (channel_id, login, display_name,
+/// balance, delta). The seeding pass (`first`) and the watched channel
+/// (`claim_channel_points` notifies it) only update the baseline.
+fn balance_increases(
+    baseline: &mut HashMap<String, i32>,
+    balances: &[(String, String, String, i32)],
+    first: bool,
+    watched_id: Option<&str>,
+) -> Vec<(String, String, String, i32, i32)> {
+    let mut increases = Vec::new();
+    for (channel_id, login, display_name, balance) in balances {
+        let prev = baseline.insert(channel_id.clone(), *balance);
+        if first || watched_id == Some(channel_id.as_str()) {
+            continue;
+        }
+        let Some(prev) = prev else { continue };
+        if *balance > prev {
+            increases.push((
+                channel_id.clone(),
+                login.clone(),
+                display_name.clone(),
+                *balance,
+                *balance - prev,
+            ));
+        }
+    }
+    increases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::twitch_api::{FollowedChannel, MockTwitchApi};
+
+    fn followed(n: usize) -> Vec<FollowedChannel> {
+        (0..n)
+            .map(|i| FollowedChannel {
+                channel_id: i.to_string(),
+                login: format!("chan{i}"),
+                display_name: format!("Chan{i}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn balances_are_read_across_pages_and_batches() {
+        let mut api = MockTwitchApi::new(followed(80));
+        api.page_size = 30;
+        api.balances.insert("chan0".into(), 100);
+        api.balances.insert("chan79".into(), 5);
+        api.balances.insert("chan40".into(), 0);
+
+        let found = BackgroundService::fetch_all_followed_balances(&api)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = found.iter().map(|f| f.0.as_str()).collect();
+        assert_eq!(ids, vec!["0", "79"]);
+        assert_eq!(found[0].2, "Chan0");
+    }
+
+    #[test]
+    fn only_rises_after_the_seeding_pass_are_reported() {
+        let mut baseline = HashMap::new();
+        let poll = |a: i32, b: i32| {
+            vec![
+                ("1".to_string(), "a".to_string(), "A".to_string(), a),
+                ("2".to_string(), "b".to_string(), "B".to_string(), b),
+            ]
+        };
+
+        assert!(balance_increases(&mut baseline, &poll(100, 50), true, None).is_empty());
+        let rises = balance_increases(&mut baseline, &poll(150, 40), false, None);
+        assert_eq!(rises.len(), 1);
+        assert_eq!((rises[0].0.as_str(), rises[0].4), ("1", 50));
+
+        // The watched channel is skipped but its baseline still moves.
+        assert!(balance_increases(&mut baseline, &poll(200, 40), false, Some("1")).is_empty());
+        assert!(balance_increases(&mut baseline, &poll(200, 40), false, None).is_empty());
+    }
+}
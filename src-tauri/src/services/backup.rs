@@ -0,0 +1,471 @@
+//! App data backup and restore: one zip with settings, favorites and
+//! history, so reinstalling the OS doesn't mean reconfiguring everything.
+//!
+//! Archive layout:
+//! - `manifest.json`: `BackupManifest` (format version, the app version that
+//!   wrote it, every entry with its SHA-256).
+//! - `local/<file>`: files from the app data dir (`cache_service`).
+//! - `config/<file>`: files from the config dir (`twitch_service`).
+//! - `tokens.json`, only when asked for: the sign-in token files, sealed with
+//!   ChaCha20-Poly1305 under a key derived from a password with Argon2id.
+//!   Without the password they're useless to whoever finds the zip.
+//!
+//! Restore checks the whole archive (format, entry names, checksums, the
+//! settings schema, the token password) before it writes a single file.
+
+use crate::models::settings::Settings;
+use anyhow::{anyhow, bail, Context, Result};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const TOKENS_ENTRY: &str = "tokens.json";
+const SETTINGS_ENTRY: &str = "local/settings.json";
+/// Largest entry restore will read. Settings and history are far smaller.
+const MAX_ENTRY_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy)]
+enum Root {
+    /// `cache_service::get_app_data_dir`: settings, caches, history.
+    Local,
+    /// `twitch_service::get_app_data_dir`: accounts and sign-in tokens.
+    Config,
+}
+
+impl Root {
+    fn prefix(self) -> &'static str {
+        match self {
+            Root::Local => "local",
+            Root::Config => "config",
+        }
+    }
+
+    fn dir(self) -> Result<PathBuf> {
+        match self {
+            Root::Local => crate::services::cache_service::get_app_data_dir(),
+            Root::Config => crate::services::twitch_service::get_app_data_dir(),
+        }
+    }
+}
+
+/// Settings, favorites and history, by where they live.
+const DATA_FILES: &[(Root, &str)] = &[
+    (Root::Local, "settings.json"),
+    (Root::Local, "cache/favorite_emotes.json"),
+    (Root::Local, "watch_history.json"),
+    (Root::Local, "stream_uptime.json"),
+    (Root::Local, "drops_lifetime_stats.json"),
+    (Root::Local, "drops_campaign_history.json"),
+    (Root::Local, "autocomplete_frecency.json"),
+    (Root::Local, "raid_event_stats.json"),
+    (Root::Local, "watch_sessions.json"),
+    (Root::Local, "drops_wishlist.json"),
+    (Root::Config, "accounts.json"),
+];
+
+/// Sign-in files in the config dir; only ever backed up sealed.
+const TOKEN_FILES: &[&str] = &[
+    ".twitch_token",
+    ".kick_token",
+    ".seventv_token",
+    ".modroom_token",
+    ".youtube_session",
+    "cookies.json",
+    "cookies_drops.json",
+];
+
+/// Per-account token files: one of these prefixes plus a Twitch user id.
+const TOKEN_PREFIXES: &[&str] = &[".twitch_account_", ".seventv_token_"];
+
+fn is_token_file(name: &str) -> bool {
+    TOKEN_FILES.contains(&name)
+        || TOKEN_PREFIXES.iter().any(|prefix| {
+            name.strip_prefix(prefix)
+                .is_some_and(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit()))
+        })
+}
+
+/// The file an archive entry restores to, if it's one a backup may hold.
+fn data_file_path(entry: &str) -> Result<Option<PathBuf>> {
+    for (root, name) in DATA_FILES {
+        if entry == format!("{}/{}", root.prefix(), name) {
+            return Ok(Some(root.dir()?.join(name)));
+        }
+    }
+    Ok(None)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEntry {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub format: u32,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<BackupEntry>,
+    /// Whether the archive has sealed sign-in tokens.
+    pub has_tokens: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreSummary {
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    /// Archive entries written back, e.g. `local/settings.json`.
+    pub restored: Vec<String>,
+    pub tokens_restored: usize,
+    /// The backup has tokens but no password was given, so they were left
+    /// alone.
+    pub tokens_skipped: bool,
+}
+
+/// `tokens.json`: the token files (name -> base64 contents) as JSON, sealed.
+#[derive(Debug, Serialize, Deserialize)]
+struct SealedTokens {
+    kdf: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+fn seal(files: &BTreeMap<String, Vec<u8>>, password: &str) -> Result<SealedTokens> {
+    let encoded: BTreeMap<&String, String> = files
+        .iter()
+        .map(|(name, bytes)| (name, BASE64.encode(bytes)))
+        .collect();
+    let plaintext = serde_json::to_vec(&encoded)?;
+    let salt: [u8; 16] = rand::random();
+    let nonce: [u8; 12] = rand::random();
+    let key = derive_key(password, &salt)?;
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| anyhow!("Failed to encrypt tokens"))?;
+    Ok(SealedTokens {
+        kdf: "argon2id".to_string(),
+        salt: BASE64.encode(salt),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn unseal(sealed: &SealedTokens, password: &str) -> Result<BTreeMap<String, Vec<u8>>> {
+    if sealed.kdf != "argon2id" {
+        bail!("Unsupported token encryption: {}", sealed.kdf);
+    }
+    let salt = BASE64.decode(&sealed.salt)?;
+    let nonce = BASE64.decode(&sealed.nonce)?;
+    if nonce.len() != 12 {
+        bail!("Damaged token section");
+    }
+    let ciphertext = BASE64.decode(&sealed.ciphertext)?;
+    let key = derive_key(password, &salt)?;
+    let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("Wrong password, or the token section is damaged"))?;
+    let encoded: BTreeMap<String, String> = serde_json::from_slice(&plaintext)?;
+    encoded
+        .into_iter()
+        .map(|(name, data)| {
+            if !is_token_file(&name) {
+                bail!("Unexpected token file in backup: {}", name);
+            }
+            Ok((name, BASE64.decode(data)?))
+        })
+        .collect()
+}
+
+fn read_token_files() -> Result<BTreeMap<String, Vec<u8>>> {
+    let dir = Root::Config.dir()?;
+    let mut files = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(files);
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if is_token_file(&name) && entry.path().is_file() {
+            let bytes =
+                std::fs::read(entry.path()).with_context(|| format!("Failed to read {}", name))?;
+            files.insert(name, bytes);
+        }
+    }
+    Ok(files)
+}
+
+/// The settings file minus its secrets: the OBS password (which, like with
+/// `export_settings`, stays on this machine) and the settings sync Gist
+/// token. The sync provider itself stays, with an empty token; restore keeps
+/// this machine's.
+fn without_secrets(settings: &[u8]) -> Result<Vec<u8>> {
+    let mut value: serde_json::Value =
+        serde_json::from_slice(settings).context("settings.json isn't valid JSON")?;
+    if let Some(obs) = value.get_mut("obs").and_then(|o| o.as_object_mut()) {
+        obs.remove("password");
+    }
+    if let Some(sync) = value
+        .get_mut("settings_sync")
+        .and_then(|s| s.as_object_mut())
+    {
+        if sync.contains_key("token") {
+            sync.insert("token".into(), serde_json::Value::String(String::new()));
+        }
+    }
+    Ok(serde_json::to_vec_pretty(&value)?)
+}
+
+/// Write a backup to `path`. Sign-in tokens go in only with a
+/// `token_password` to seal them.
+pub fn create(path: &Path, token_password: Option<&str>) -> Result<BackupManifest> {
+    let mut files = Vec::new();
+    for (root, name) in DATA_FILES {
+        let file = root.dir()?.join(name);
+        match std::fs::read(&file) {
+            Ok(bytes) => {
+                let entry = format!("{}/{}", root.prefix(), name);
+                let bytes = if entry == SETTINGS_ENTRY {
+                    without_secrets(&bytes)?
+                } else {
+                    bytes
+                };
+                files.push((entry, bytes));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", file.display())),
+        }
+    }
+    let sealed = match token_password {
+        Some(password) => Some(seal(&read_token_files()?, password)?),
+        None => None,
+    };
+
+    let manifest = BackupManifest {
+        format: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        entries: files
+            .iter()
+            .map(|(name, bytes)| BackupEntry {
+                name: name.clone(),
+                size: bytes.len() as u64,
+                sha256: sha256_hex(bytes),
+            })
+            .collect(),
+        has_tokens: sealed.is_some(),
+    };
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(MANIFEST_ENTRY, options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    for (name, bytes) in &files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(bytes)?;
+    }
+    if let Some(sealed) = &sealed {
+        zip.start_file(TOKENS_ENTRY, options)?;
+        zip.write_all(&serde_json::to_vec(sealed)?)?;
+    }
+    zip.finish()?;
+
+    info!(
+        "[Backup] Wrote {} ({} files{})",
+        path.display(),
+        files.len(),
+        if sealed.is_some() { " + tokens" } else { "" }
+    );
+    Ok(manifest)
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    zip: &mut zip::ZipArchive<R>,
+    name: &str,
+) -> Result<Vec<u8>> {
+    let entry = zip
+        .by_name(name)
+        .with_context(|| format!("Backup is missing {}", name))?;
+    if entry.size() > MAX_ENTRY_BYTES {
+        bail!("{} is too large", name);
+    }
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry.take(MAX_ENTRY_BYTES + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > MAX_ENTRY_BYTES {
+        bail!("{} is too large", name);
+    }
+    Ok(bytes)
+}
+
+/// A backup read back and checked, ready to write.
+struct Checked {
+    manifest: BackupManifest,
+    files: Vec<(PathBuf, String, Vec<u8>)>,
+    tokens: Option<BTreeMap<String, Vec<u8>>>,
+}
+
+fn check(path: &Path, token_password: Option<&str>) -> Result<Checked> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut zip = zip::ZipArchive::new(file).context("Not a StreamNook backup (not a zip)")?;
+    let manifest: BackupManifest = serde_json::from_slice(&read_entry(&mut zip, MANIFEST_ENTRY)?)
+        .context("Not a StreamNook backup (bad manifest)")?;
+    if manifest.format > FORMAT_VERSION {
+        bail!(
+            "This backup was made by a newer StreamNook ({}); update before restoring it",
+            manifest.app_version
+        );
+    }
+
+    let mut files = Vec::new();
+    for entry in &manifest.entries {
+        let target = data_file_path(&entry.name)?
+            .ok_or_else(|| anyhow!("Unexpected file in backup: {}", entry.name))?;
+        let bytes = read_entry(&mut zip, &entry.name)?;
+        if sha256_hex(&bytes) != entry.sha256 {
+            bail!("{} is damaged (checksum mismatch)", entry.name);
+        }
+        if entry.name == SETTINGS_ENTRY {
+            serde_json::from_slice::<Settings>(&bytes)
+                .context("The backed-up settings don't match this version's schema")?;
+        } else if entry.name.ends_with(".json") {
+            serde_json::from_slice::<serde_json::Value>(&bytes)
+                .with_context(|| format!("{} isn't valid JSON", entry.name))?;
+        }
+        files.push((target, entry.name.clone(), bytes));
+    }
+
+    let tokens = match (manifest.has_tokens, token_password) {
+        (true, Some(password)) => {
+            let sealed: SealedTokens = serde_json::from_slice(&read_entry(&mut zip, TOKENS_ENTRY)?)
+                .context("Damaged token section")?;
+            Some(unseal(&sealed, password)?)
+        }
+        _ => None,
+    };
+
+    Ok(Checked {
+        manifest,
+        files,
+        tokens,
+    })
+}
+
+/// Write `bytes` next to `path` and rename it into place, so a failure
+/// midway never leaves a half-written file.
+fn write_replacing(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("restore-tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Restore the backup at `path` over the current app data. Tokens are
+/// restored only when the backup has them and `token_password` opens them.
+/// The settings file is rewritten; the caller reloads what it keeps in
+/// memory, and puts back this machine's OBS password and Gist token, which
+/// backups never hold (see `without_secrets`).
+pub fn restore(path: &Path, token_password: Option<&str>) -> Result<RestoreSummary> {
+    let checked = check(path, token_password)?;
+
+    let mut restored = Vec::new();
+    for (target, name, bytes) in &checked.files {
+        write_replacing(target, bytes)?;
+        restored.push(name.clone());
+    }
+    let mut tokens_restored = 0;
+    if let Some(tokens) = &checked.tokens {
+        let dir = Root::Config.dir()?;
+        for (name, bytes) in tokens {
+            write_replacing(&dir.join(name), bytes)?;
+            tokens_restored += 1;
+        }
+    }
+
+    info!(
+        "[Backup] Restored {} ({} files, {} token files)",
+        path.display(),
+        restored.len(),
+        tokens_restored
+    );
+    Ok(RestoreSummary {
+        app_version: checked.manifest.app_version,
+        created_at: checked.manifest.created_at,
+        restored,
+        tokens_restored,
+        tokens_skipped: checked.manifest.has_tokens && checked.tokens.is_none(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_files_are_an_allowlist() {
+        assert!(is_token_file(".twitch_token"));
+        assert!(is_token_file(".twitch_account_123456"));
+        assert!(!is_token_file(".twitch_account_"));
+        assert!(!is_token_file(".twitch_account_../settings.json"));
+        assert!(!is_token_file("settings.json"));
+    }
+
+    #[test]
+    fn sealed_tokens_need_the_password() {
+        let files = BTreeMap::from([(".twitch_token".to_string(), b"secret".to_vec())]);
+        let sealed = seal(&files, "hunter2").unwrap();
+        assert!(!sealed.ciphertext.contains("secret"));
+        assert_eq!(unseal(&sealed, "hunter2").unwrap(), files);
+        assert!(unseal(&sealed, "hunter3").is_err());
+    }
+
+    #[test]
+    fn settings_secrets_stay_out_of_backups() {
+        let settings = serde_json::json!({
+            "obs": { "host": "127.0.0.1", "password": "obs-secret" },
+            "settings_sync": { "provider": "gist", "token": "ghp_secret", "gist_id": "abc" },
+        });
+        let stripped = without_secrets(&serde_json::to_vec(&settings).unwrap()).unwrap();
+        let text = String::from_utf8(stripped).unwrap();
+        assert!(!text.contains("obs-secret"));
+        assert!(!text.contains("ghp_secret"));
+        assert!(text.contains("\"gist_id\": \"abc\""));
+    }
+
+    #[test]
+    fn only_known_entries_map_to_files() {
+        assert!(data_file_path("local/settings.json").unwrap().is_some());
+        assert!(data_file_path("config/accounts.json").unwrap().is_some());
+        assert!(data_file_path("local/../../evil.dll").unwrap().is_none());
+        assert!(data_file_path("config/.twitch_token").unwrap().is_none());
+    }
+}
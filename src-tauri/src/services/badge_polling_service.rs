@@ -1,6 +1,7 @@
 use crate::commands::badges::get_cached_global_badges;
 use crate::models::settings::AppState;
 use crate::services::cache_service;
+use crate::services::event_bus;
 use crate::services::universal_cache_service::{get_cached_item, CacheType};
 use log::{debug, error};
 
@@ -10,7 +11,6 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 use tokio::time::{interval, Duration};
 
@@ -93,7 +93,7 @@ impl BadgePollingService {
         }
     }
 
-    pub async fn start(&self, app_handle: AppHandle, app_state: AppState) {
+    pub async fn start(&self, app_state: AppState) {
         // Check if already running
         {
             let mut running = self.running.write().await;
@@ -121,8 +121,7 @@ impl BadgePollingService {
             tokio::time::sleep(Duration::from_secs(INITIAL_DELAY_SECS)).await;
 
             // First poll
-            if let Err(e) = poll_once(&app_handle, &app_state, &running, &is_polling, &state).await
-            {
+            if let Err(e) = poll_once(&app_state, &running, &is_polling, &state).await {
                 error!("[BadgePolling] Initial poll failed: {e}");
             }
 
@@ -132,6 +131,8 @@ impl BadgePollingService {
 
             loop {
                 ticker.tick().await;
+                crate::services::power_saver::stretch_tick(Duration::from_secs(POLL_INTERVAL_SECS))
+                    .await;
 
                 // Stop if requested
                 if !*running.read().await {
@@ -149,9 +150,7 @@ impl BadgePollingService {
                     continue;
                 }
 
-                if let Err(e) =
-                    poll_once(&app_handle, &app_state, &running, &is_polling, &state).await
-                {
+                if let Err(e) = poll_once(&app_state, &running, &is_polling, &state).await {
                     error!("[BadgePolling] Poll failed: {e}");
                 }
             }
@@ -167,7 +166,6 @@ impl BadgePollingService {
 }
 
 async fn poll_once(
-    app_handle: &AppHandle,
     _app_state: &AppState,
     running: &Arc<RwLock<bool>>,
     is_polling: &Arc<RwLock<bool>>,
@@ -187,7 +185,7 @@ async fn poll_once(
         *polling = true;
     }
 
-    let result = poll_once_impl(app_handle, state).await;
+    let result = poll_once_impl(state).await;
 
     // Always clear polling flag
     *is_polling.write().await = false;
@@ -195,10 +193,7 @@ async fn poll_once(
     result
 }
 
-async fn poll_once_impl(
-    app_handle: &AppHandle,
-    state: &Arc<RwLock<BadgePollingState>>,
-) -> anyhow::Result<()> {
+async fn poll_once_impl(state: &Arc<RwLock<BadgePollingState>>) -> anyhow::Result<()> {
     // Get cached badges (already fetched by main.rs background prefetch)
     let cached_badges = match get_cached_global_badges().await {
         Ok(Some(b)) => b,
@@ -293,11 +288,11 @@ async fn poll_once_impl(
     // Emit only the latest badge notification (mirrors old TS behavior)
     if let Some(latest) = notifications.pop() {
         // Always emit the generic event
-        let _ = app_handle.emit("badge-notification", vec![latest.clone()]);
+        let _ = event_bus::emit("badge-notification", vec![latest.clone()]);
 
         // Emit the specific event for availability transitions
         if matches!(latest.status, BadgeNotificationStatus::Available) {
-            let _ = app_handle.emit("badge-available", vec![latest]);
+            let _ = event_bus::emit("badge-available", vec![latest]);
         }
     }
 
@@ -0,0 +1,246 @@
+//! Offline cache bundles: the universal cache (emotes, badges, cosmetics and
+//! their image files) in one zip, so a second machine, or one behind a
+//! firewall, can be primed without reaching Twitch or the third-party emote
+//! APIs.
+//!
+//! Archive layout:
+//! - `bundle.json`: `CacheBundleManifest` (format version, the app version
+//!   that wrote it, every manifest entry). File entries carry their
+//!   `local_path` relative to the cache folder, e.g. `emotes/123.webp`.
+//! - `files/<type>/<name>`: the cached files those entries point at.
+//!
+//! Import never replaces what the machine already has: an entry is skipped
+//! when it's cached here already (with its file, for file entries).
+
+use crate::services::universal_cache_service::{
+    get_universal_cache_dir, load_manifest, save_cached_items_batch, write_atomic, FileWrites,
+    UniversalCacheEntry, FILE_DIRS,
+};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::io::{Read, Write};
+use std::path::Path;
+
+const FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "bundle.json";
+const FILES_PREFIX: &str = "files/";
+/// Largest cached file import will read; animated emotes stay well under.
+const MAX_FILE_BYTES: u64 = 32 * 1024 * 1024;
+/// Largest `bundle.json` import will read.
+const MAX_MANIFEST_BYTES: u64 = 256 * 1024 * 1024;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CacheBundleManifest {
+    pub format: u32,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub entries: Vec<UniversalCacheEntry>,
+}
+
+/// Returned by `export_manifest_bundle` and `import_manifest_bundle`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CacheBundleSummary {
+    pub entries: usize,
+    pub files: usize,
+    /// Export: file entries whose file is gone. Import: entries already
+    /// cached here.
+    pub skipped: usize,
+}
+
+fn local_path(entry: &UniversalCacheEntry) -> Option<&str> {
+    entry.data.get("local_path").and_then(|p| p.as_str())
+}
+
+/// Check a bundle-relative file path (`<type>/<name>`): a known cache folder
+/// and a bare file name, so nothing is written outside the cache.
+fn safe_relative_path(relative: &str) -> Option<(&'static str, &str)> {
+    let (dir, name) = relative.split_once('/')?;
+    let dir = *FILE_DIRS.iter().find(|d| **d == dir)?;
+    let plain = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\', ':'])
+        && Path::new(name).file_name() == Some(OsStr::new(name));
+    plain.then_some((dir, name))
+}
+
+/// Write the cache to a bundle at `path`.
+pub fn export(path: &Path) -> Result<CacheBundleSummary> {
+    let cache_dir = get_universal_cache_dir()?;
+    let manifest = load_manifest()?;
+    let mut summary = CacheBundleSummary::default();
+    let mut entries = Vec::with_capacity(manifest.entries.len());
+
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    // Images are already compressed; deflating them again only costs time.
+    let stored =
+        zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    for mut entry in manifest.entries.into_values() {
+        if let Some(absolute) = local_path(&entry) {
+            let absolute = Path::new(absolute).to_path_buf();
+            let relative = absolute
+                .strip_prefix(&cache_dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"));
+            let Some(relative) = relative.filter(|r| safe_relative_path(r).is_some()) else {
+                summary.skipped += 1;
+                continue;
+            };
+            let Ok(bytes) = std::fs::read(&absolute) else {
+                summary.skipped += 1;
+                continue;
+            };
+            zip.start_file(format!("{}{}", FILES_PREFIX, relative), stored)?;
+            zip.write_all(&bytes)?;
+            entry.data["local_path"] = relative.into();
+            summary.files += 1;
+        }
+        entries.push(entry);
+    }
+    summary.entries = entries.len();
+
+    let bundle = CacheBundleManifest {
+        format: FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: Utc::now(),
+        entries,
+    };
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(MANIFEST_ENTRY, options)?;
+    zip.write_all(&serde_json::to_vec(&bundle)?)?;
+    zip.finish()?;
+
+    info!(
+        "[CacheBundle] Wrote {} ({} entries, {} files, {} skipped)",
+        path.display(),
+        summary.entries,
+        summary.files,
+        summary.skipped
+    );
+    Ok(summary)
+}
+
+fn read_entry<R: Read + std::io::Seek>(
+    zip: &mut zip::ZipArchive<R>,
+    name: &str,
+    limit: u64,
+) -> Result<Vec<u8>> {
+    let entry = zip
+        .by_name(name)
+        .with_context(|| format!("Bundle is missing {}", name))?;
+    if entry.size() > limit {
+        bail!("{} is too large", name);
+    }
+    let mut bytes = Vec::with_capacity(entry.size() as usize);
+    entry.take(limit + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > limit {
+        bail!("{} is too large", name);
+    }
+    Ok(bytes)
+}
+
+/// Unpack the bundle at `path` into the cache folder and return the entries
+/// to add to the manifest.
+fn unpack(path: &Path) -> Result<(Vec<UniversalCacheEntry>, CacheBundleSummary)> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let mut zip =
+        zip::ZipArchive::new(file).context("Not a StreamNook cache bundle (not a zip)")?;
+    let bundle: CacheBundleManifest =
+        serde_json::from_slice(&read_entry(&mut zip, MANIFEST_ENTRY, MAX_MANIFEST_BYTES)?)
+            .context("Not a StreamNook cache bundle (bad bundle.json)")?;
+    if bundle.format > FORMAT_VERSION {
+        bail!(
+            "This bundle was made by a newer StreamNook ({}); update before importing it",
+            bundle.app_version
+        );
+    }
+
+    let cache_dir = get_universal_cache_dir()?;
+    let existing = load_manifest()?.entries;
+    let mut summary = CacheBundleSummary::default();
+    let mut entries = Vec::new();
+    for mut entry in bundle.entries {
+        let cached_here = existing
+            .get(&entry.id)
+            .is_some_and(|e| match local_path(e) {
+                Some(p) => Path::new(p).is_file(),
+                None => true,
+            });
+        if cached_here {
+            summary.skipped += 1;
+            continue;
+        }
+        if let Some(relative) = local_path(&entry).map(str::to_string) {
+            let Some((dir, name)) = safe_relative_path(&relative) else {
+                bail!("Unexpected file in bundle: {}", relative);
+            };
+            let bytes = read_entry(
+                &mut zip,
+                &format!("{}{}", FILES_PREFIX, relative),
+                MAX_FILE_BYTES,
+            )?;
+            let target = cache_dir.join(dir).join(name);
+            std::fs::create_dir_all(cache_dir.join(dir))?;
+            write_atomic(&target, &bytes)
+                .with_context(|| format!("Failed to write {}", target.display()))?;
+            entry.data["local_path"] = target.to_string_lossy().to_string().into();
+            summary.files += 1;
+        }
+        entries.push(entry);
+    }
+    summary.entries = entries.len();
+    Ok((entries, summary))
+}
+
+/// Import the bundle at `path`: write its files into the cache folder and
+/// add its entries to the manifest, leaving anything already cached alone.
+pub async fn import(path: &Path) -> Result<CacheBundleSummary> {
+    let _writes = FileWrites::begin();
+    let path = path.to_path_buf();
+    let (entries, summary) = tokio::task::spawn_blocking(move || unpack(&path))
+        .await
+        .context("Cache bundle import task panicked")??;
+    save_cached_items_batch(entries).await?;
+    debug!(
+        "[CacheBundle] Imported {} entries ({} files, {} already cached)",
+        summary.entries, summary.files, summary.skipped
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_paths_stay_inside_the_cache_folders() {
+        assert_eq!(
+            safe_relative_path("emotes/123.webp"),
+            Some(("emotes", "123.webp"))
+        );
+        assert_eq!(
+            safe_relative_path("third-party-badges/ffz_1.png"),
+            Some(("third-party-badges", "ffz_1.png"))
+        );
+        for bad in [
+            "emotes/../settings.json",
+            "emotes/..",
+            "emotes/",
+            "emotes/a/b.png",
+            "emotes/a\\b.png",
+            "emotes/C:x.png",
+            "../emotes/1.png",
+            "plugins/evil.dll",
+            "1.png",
+        ] {
+            assert_eq!(safe_relative_path(bad), None, "{}", bad);
+        }
+    }
+}
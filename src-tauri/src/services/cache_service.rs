@@ -148,6 +148,7 @@ pub fn save_emote_cache(channel_id: &str, data: &str, expiry_days: u32) -> Resul
 
 /// Load emote cache from disk (legacy - kept for backwards compatibility)
 pub fn load_emote_cache(channel_id: &str) -> Result<Option<String>> {
+    crate::services::channel_caches::touch(channel_id);
     let cache_dir = get_cache_dir()?;
     let cache_file = cache_dir.join(format!("emotes_{}.json", channel_id));
 
@@ -200,6 +201,9 @@ pub fn save_badge_cache(
 
 /// Load badge cache from disk
 pub fn load_badge_cache(cache_type: &str, channel_id: Option<&str>) -> Result<Option<String>> {
+    if let Some(id) = channel_id {
+        crate::services::channel_caches::touch(id);
+    }
     let cache_dir = get_cache_dir()?;
     let filename = if let Some(id) = channel_id {
         format!("badges_{}_{}.json", cache_type, id)
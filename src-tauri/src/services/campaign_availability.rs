@@ -0,0 +1,188 @@
+//! Whether each active drop campaign can be earned right now
+//! (`get_campaign_availability`): is any channel it counts on live in its
+//! game, and how big is the biggest one.
+//!
+//! A campaign with a channel list checks those channels (Helix, 100 a
+//! request); an open one asks for the top drops-enabled stream in its game.
+//! Checks run a few at a time and are cached briefly, per channel list or per
+//! game, so several campaigns on one game share a lookup and reopening the
+//! drops page doesn't repeat them.
+
+use crate::models::drops::DropCampaign;
+use crate::services::twitch_service::TwitchService;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+const CACHE_TTL: Duration = Duration::from_secs(120);
+/// Lookups in flight at once.
+const CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CampaignAvailability {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub game_name: String,
+    /// A channel the campaign counts on is live in its game.
+    pub live: bool,
+    pub top_channel: Option<String>,
+    pub top_viewer_count: Option<u32>,
+    /// False when the lookup failed; `live` is then unknown, not false.
+    pub checked: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// The biggest eligible live channel, or None when nothing eligible is live.
+type Lookup = Option<(String, u32)>;
+
+static CACHE: Lazy<StdMutex<HashMap<String, (Instant, Lookup)>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+fn cache_key(campaign: &DropCampaign) -> String {
+    if campaign.is_acl_based {
+        format!("campaign:{}", campaign.id)
+    } else {
+        format!("game:{}", campaign.game_id)
+    }
+}
+
+fn cached(key: &str) -> Option<Lookup> {
+    let mut cache = CACHE.lock().unwrap();
+    cache.retain(|_, (at, _)| at.elapsed() < CACHE_TTL);
+    cache.get(key).map(|(_, lookup)| lookup.clone())
+}
+
+async fn look_up(campaign: &DropCampaign) -> anyhow::Result<Lookup> {
+    if !campaign.is_acl_based {
+        let (streams, _) =
+            TwitchService::get_drops_enabled_streams(Some(&campaign.game_id), None, 1).await?;
+        return Ok(streams
+            .into_iter()
+            .next()
+            .map(|s| (s.user_login, s.viewer_count)));
+    }
+    let logins: Vec<String> = campaign
+        .allowed_channels
+        .iter()
+        .map(|c| c.name.to_lowercase())
+        .collect();
+    if logins.is_empty() {
+        return Ok(None);
+    }
+    let streams = TwitchService::check_streams_online(&logins).await?;
+    Ok(streams
+        .into_iter()
+        .filter(|s| s.game_id == campaign.game_id)
+        .max_by_key(|s| s.viewer_count)
+        .map(|s| (s.user_login, s.viewer_count)))
+}
+
+fn availability(campaign: &DropCampaign, lookup: Option<&Lookup>) -> CampaignAvailability {
+    let top = lookup.and_then(|l| l.as_ref());
+    CampaignAvailability {
+        campaign_id: campaign.id.clone(),
+        campaign_name: campaign.name.clone(),
+        game_name: campaign.game_name.clone(),
+        live: top.is_some(),
+        top_channel: top.map(|(login, _)| login.clone()),
+        top_viewer_count: top.map(|(_, viewers)| *viewers),
+        checked: lookup.is_some(),
+        checked_at: Utc::now(),
+    }
+}
+
+/// Availability of every campaign running now, live ones first (by top
+/// viewer count), then the rest by name.
+pub async fn check(campaigns: Vec<DropCampaign>) -> Vec<CampaignAvailability> {
+    let now = Utc::now();
+    let campaigns: Vec<DropCampaign> = campaigns
+        .into_iter()
+        .filter(|c| c.start_at <= now && c.end_at > now)
+        .collect();
+
+    let mut lookups: HashMap<String, Lookup> = HashMap::new();
+    let mut pending: HashMap<String, &DropCampaign> = HashMap::new();
+    for campaign in &campaigns {
+        let key = cache_key(campaign);
+        match cached(&key) {
+            Some(lookup) => {
+                lookups.insert(key, lookup);
+            }
+            None => {
+                pending.entry(key).or_insert(campaign);
+            }
+        }
+    }
+
+    let fetched: Vec<(String, anyhow::Result<Lookup>)> = futures_util::stream::iter(
+        pending
+            .into_iter()
+            .map(|(key, campaign)| async move { (key, look_up(campaign).await) }),
+    )
+    .buffer_unordered(CONCURRENCY)
+    .collect()
+    .await;
+    for (key, result) in fetched {
+        match result {
+            Ok(lookup) => {
+                CACHE
+                    .lock()
+                    .unwrap()
+                    .insert(key.clone(), (Instant::now(), lookup.clone()));
+                lookups.insert(key, lookup);
+            }
+            Err(e) => debug!("[CampaignAvailability] {} failed: {}", key, e),
+        }
+    }
+
+    let mut result: Vec<CampaignAvailability> = campaigns
+        .iter()
+        .map(|c| availability(c, lookups.get(&cache_key(c))))
+        .collect();
+    sort(&mut result);
+    result
+}
+
+fn sort(result: &mut [CampaignAvailability]) {
+    result.sort_by(|a, b| {
+        b.top_viewer_count
+            .cmp(&a.top_viewer_count)
+            .then_with(|| a.campaign_name.cmp(&b.campaign_name))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, viewers: Option<u32>) -> CampaignAvailability {
+        CampaignAvailability {
+            campaign_id: name.to_string(),
+            campaign_name: name.to_string(),
+            game_name: String::new(),
+            live: viewers.is_some(),
+            top_channel: viewers.map(|_| "someone".to_string()),
+            top_viewer_count: viewers,
+            checked: true,
+            checked_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn live_campaigns_sort_first_by_viewers() {
+        let mut result = vec![
+            entry("b", None),
+            entry("small", Some(10)),
+            entry("a", None),
+            entry("big", Some(5000)),
+        ];
+        sort(&mut result);
+        let names: Vec<&str> = result.iter().map(|r| r.campaign_name.as_str()).collect();
+        assert_eq!(names, ["big", "small", "a", "b"]);
+    }
+}
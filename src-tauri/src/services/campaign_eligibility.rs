@@ -0,0 +1,285 @@
+//! Whether the drops account can earn a campaign on the channel it's mining
+//! (`check_campaign_eligibility`), checked before hours go into watching for
+//! nothing.
+//!
+//! Mining follows the drops plugin's `drops.status`: each time it reports a
+//! new campaign or channel, the pair is checked once, and an
+//! `ineligible-campaign` event {campaign_id, campaign_name, game_name,
+//! channel_login, reasons} goes out when anything stands in the way. What's
+//! checked: the game account being linked (`self.isAccountConnected`), the
+//! channel being on the campaign's list when it has one, a sub-only stream
+//! the account isn't subscribed to, and the campaign being offered to the
+//! account at all; Twitch leaves campaigns out of the drops dashboard for
+//! accounts outside their regions, so one missing from it is most likely
+//! region-locked.
+
+use crate::models::drops::DropCampaign;
+use crate::models::settings::AppState;
+use crate::services::drops_service::DropsService;
+use crate::services::event_bus;
+use crate::services::twitch_api::GqlOperation;
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, warn};
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+
+/// The (campaign_id, channel_login) last checked from `drops.status`.
+static LAST_CHECKED: StdMutex<Option<(String, String)>> = StdMutex::new(None);
+
+/// Twitch's `Stream.restrictionType` for a subscribers-only broadcast.
+const SUB_ONLY_RESTRICTION: &str = "SUB_ONLY_LIVE";
+
+const STREAM_ACCESS: GqlOperation = GqlOperation {
+    name: "StreamAccess",
+    hash: None,
+    query: Some(
+        r#"
+        query StreamAccess($login: String!) {
+            user(login: $login) {
+                id
+                stream { id restrictionType }
+                self { subscriptionBenefit { id } }
+            }
+        }
+        "#,
+    ),
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IneligibleReason {
+    /// The game account isn't linked, so the drops can't be granted.
+    AccountNotLinked { account_link: Option<String> },
+    /// The campaign counts only on listed channels, and this isn't one.
+    ChannelNotAllowed,
+    /// The stream is for subscribers only and the account isn't one.
+    SubOnlyStream,
+    /// Not in the account's drops dashboard: not offered where it is.
+    NotOffered,
+    /// The campaign isn't running.
+    NotRunning,
+}
+
+impl IneligibleReason {
+    fn message(&self) -> &'static str {
+        match self {
+            Self::AccountNotLinked { .. } => "The game account isn't linked to Twitch",
+            Self::ChannelNotAllowed => "This channel doesn't count toward the campaign",
+            Self::SubOnlyStream => "The stream is for subscribers only",
+            Self::NotOffered => {
+                "The campaign isn't offered to this account (most likely region-locked)"
+            }
+            Self::NotRunning => "The campaign isn't running right now",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Reason {
+    #[serde(flatten)]
+    pub reason: IneligibleReason,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CampaignEligibility {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub game_name: String,
+    pub channel_login: Option<String>,
+    pub eligible: bool,
+    pub reasons: Vec<Reason>,
+}
+
+/// What the channel's stream looks like to the drops account.
+#[derive(Debug, Default)]
+struct StreamAccess {
+    channel_id: String,
+    sub_only: bool,
+    subscribed: bool,
+}
+
+/// The reasons `campaign` can't be earned on the channel, if any.
+fn reasons(
+    campaign: Option<&DropCampaign>,
+    stream: Option<&StreamAccess>,
+    login: &str,
+) -> Vec<IneligibleReason> {
+    let Some(campaign) = campaign else {
+        return vec![IneligibleReason::NotOffered];
+    };
+    let mut reasons = Vec::new();
+    let now = Utc::now();
+    if campaign.start_at > now || campaign.end_at <= now {
+        reasons.push(IneligibleReason::NotRunning);
+    }
+    if !campaign.is_account_connected {
+        reasons.push(IneligibleReason::AccountNotLinked {
+            account_link: campaign.account_link.clone(),
+        });
+    }
+    if let Some(stream) = stream {
+        if !campaign.allows_channel(&stream.channel_id, login) {
+            reasons.push(IneligibleReason::ChannelNotAllowed);
+        }
+        if stream.sub_only && !stream.subscribed {
+            reasons.push(IneligibleReason::SubOnlyStream);
+        }
+    }
+    reasons
+}
+
+async fn stream_access(drops: &DropsService, login: &str) -> Result<StreamAccess> {
+    let response = drops
+        .gql(&STREAM_ACCESS, serde_json::json!({ "login": login }))
+        .await?;
+    let user = &response["data"]["user"];
+    Ok(StreamAccess {
+        channel_id: user["id"].as_str().unwrap_or_default().to_string(),
+        sub_only: user["stream"]["restrictionType"].as_str() == Some(SUB_ONLY_RESTRICTION),
+        subscribed: !user["self"]["subscriptionBenefit"].is_null(),
+    })
+}
+
+/// Check `campaign_id` for the drops account, on `channel_login` when given.
+pub async fn check(
+    state: &AppState,
+    campaign_id: &str,
+    channel_login: Option<&str>,
+) -> Result<CampaignEligibility> {
+    let drops_service = state.drops_service.lock().await.clone();
+    let campaigns = drops_service.get_all_active_campaigns_cached().await?;
+    let campaign = campaigns.iter().find(|c| c.id == campaign_id);
+    let stream = match channel_login {
+        Some(login) => match stream_access(&drops_service, login).await {
+            Ok(stream) => Some(stream),
+            Err(e) => {
+                debug!("[Eligibility] Couldn't look up {}: {}", login, e);
+                None
+            }
+        },
+        None => None,
+    };
+    let reasons: Vec<Reason> = reasons(campaign, stream.as_ref(), channel_login.unwrap_or(""))
+        .into_iter()
+        .map(|reason| Reason {
+            message: reason.message().to_string(),
+            reason,
+        })
+        .collect();
+    Ok(CampaignEligibility {
+        campaign_id: campaign_id.to_string(),
+        campaign_name: campaign.map(|c| c.name.clone()).unwrap_or_default(),
+        game_name: campaign.map(|c| c.game_name.clone()).unwrap_or_default(),
+        channel_login: channel_login.map(str::to_string),
+        eligible: reasons.is_empty(),
+        reasons,
+    })
+}
+
+/// Check what a `drops.status` push mines, once per campaign and channel.
+pub fn observe_status(app: &AppHandle, status: &serde_json::Value) {
+    if !status["is_mining"].as_bool().unwrap_or(false) {
+        *LAST_CHECKED.lock().unwrap() = None;
+        return;
+    }
+    let campaign_id = status["campaign_id"].as_str().unwrap_or("");
+    let login = status["channel_login"].as_str().unwrap_or("");
+    if campaign_id.is_empty() || login.is_empty() {
+        return;
+    }
+    let pair = (campaign_id.to_string(), login.to_lowercase());
+    {
+        let mut last = LAST_CHECKED.lock().unwrap();
+        if last.as_ref() == Some(&pair) {
+            return;
+        }
+        *last = Some(pair.clone());
+    }
+    let app = app.clone();
+    tokio::spawn(async move {
+        let state = app.state::<AppState>();
+        let eligibility = match check(&state, &pair.0, Some(&pair.1)).await {
+            Ok(eligibility) => eligibility,
+            Err(e) => {
+                debug!("[Eligibility] Couldn't check {}: {}", pair.0, e);
+                return;
+            }
+        };
+        if eligibility.eligible {
+            return;
+        }
+        warn!(
+            "[Eligibility] Mining '{}' on {} can't earn drops: {}",
+            eligibility.campaign_name,
+            pair.1,
+            eligibility
+                .reasons
+                .iter()
+                .map(|r| r.message.as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+        let _ = event_bus::emit("ineligible-campaign", &eligibility);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn campaign() -> DropCampaign {
+        DropCampaign {
+            id: "c1".into(),
+            name: "Campaign".into(),
+            game_id: "g1".into(),
+            game_name: "Game".into(),
+            description: String::new(),
+            image_url: String::new(),
+            start_at: Utc::now() - Duration::days(1),
+            end_at: Utc::now() + Duration::days(1),
+            time_based_drops: Vec::new(),
+            is_account_connected: true,
+            allowed_channels: Vec::new(),
+            is_acl_based: false,
+            details_url: None,
+            account_link: None,
+            unlinked_warning: false,
+        }
+    }
+
+    #[test]
+    fn flags_each_thing_standing_in_the_way() {
+        let stream = StreamAccess {
+            channel_id: "42".into(),
+            sub_only: true,
+            subscribed: false,
+        };
+        assert!(reasons(Some(&campaign()), None, "chan").is_empty());
+        assert_eq!(
+            reasons(None, None, "chan"),
+            vec![IneligibleReason::NotOffered]
+        );
+
+        let mut locked = campaign();
+        locked.is_account_connected = false;
+        locked.is_acl_based = true;
+        assert_eq!(
+            reasons(Some(&locked), Some(&stream), "chan"),
+            vec![
+                IneligibleReason::AccountNotLinked { account_link: None },
+                IneligibleReason::ChannelNotAllowed,
+                IneligibleReason::SubOnlyStream,
+            ]
+        );
+
+        let subscribed = StreamAccess {
+            subscribed: true,
+            ..stream
+        };
+        assert!(reasons(Some(&campaign()), Some(&subscribed), "chan").is_empty());
+    }
+}
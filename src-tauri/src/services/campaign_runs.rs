@@ -0,0 +1,192 @@
+//! Completion summaries for drop campaigns. The drops monitor reports the
+//! minutes each progress refresh credits to a campaign and the channel that
+//! earned them; once every collectible drop in a campaign it has been
+//! tracking is claimed, the run closes into a `CampaignSummary`. Summaries
+//! are appended to `drops_campaign_history.json` and raised as
+//! `campaign-summary` for the completion card.
+//!
+//! Only campaigns that earned minutes this session get a summary, so one
+//! finished weeks ago doesn't pop a card at startup.
+
+use crate::models::drops::DropCampaign;
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const HISTORY_FILE: &str = "drops_campaign_history.json";
+const MAX_HISTORY: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignSummary {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub game_name: String,
+    pub image_url: String,
+    /// When this session first saw the campaign earn minutes.
+    pub started_at: DateTime<Utc>,
+    pub completed_at: DateTime<Utc>,
+    pub elapsed_secs: i64,
+    /// Minutes credited while tracked (drops in a campaign progress together,
+    /// so this is campaign time, not the sum over drops).
+    pub minutes_watched: i32,
+    /// Channels that earned minutes, in first-seen order.
+    pub channels: Vec<String>,
+    /// Times the earning channel changed.
+    pub channel_switches: u32,
+    /// Benefit names of the campaign's collectible drops.
+    pub rewards: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Run {
+    started_at: DateTime<Utc>,
+    minutes: i32,
+    channels: Vec<String>,
+    last_channel: Option<String>,
+    switches: u32,
+}
+
+/// Open runs, keyed by campaign id.
+#[derive(Debug, Default)]
+pub struct CampaignRuns {
+    runs: HashMap<String, Run>,
+}
+
+impl CampaignRuns {
+    /// Credit `minutes` to `campaign_id`, earned on `channel` if known.
+    pub fn record(
+        &mut self,
+        campaign_id: &str,
+        minutes: i32,
+        channel: Option<&str>,
+        now: DateTime<Utc>,
+    ) {
+        if minutes <= 0 {
+            return;
+        }
+        let run = self
+            .runs
+            .entry(campaign_id.to_string())
+            .or_insert_with(|| Run {
+                started_at: now,
+                minutes: 0,
+                channels: Vec::new(),
+                last_channel: None,
+                switches: 0,
+            });
+        run.minutes += minutes;
+        if let Some(channel) = channel {
+            if run
+                .last_channel
+                .as_deref()
+                .is_some_and(|last| last != channel)
+            {
+                run.switches += 1;
+            }
+            if !run.channels.iter().any(|c| c == channel) {
+                run.channels.push(channel.to_string());
+            }
+            run.last_channel = Some(channel.to_string());
+        }
+    }
+
+    pub fn is_tracking(&self, campaign_id: &str) -> bool {
+        self.runs.contains_key(campaign_id)
+    }
+
+    /// Close `campaign`'s run, if one is open.
+    pub fn finish(
+        &mut self,
+        campaign: &DropCampaign,
+        now: DateTime<Utc>,
+    ) -> Option<CampaignSummary> {
+        let run = self.runs.remove(&campaign.id)?;
+        let rewards = campaign
+            .time_based_drops
+            .iter()
+            .filter(|d| d.is_collectible)
+            .flat_map(|d| d.benefit_edges.iter().map(|b| b.name.clone()))
+            .collect();
+        Some(CampaignSummary {
+            campaign_id: campaign.id.clone(),
+            campaign_name: campaign.name.clone(),
+            game_name: campaign.game_name.clone(),
+            image_url: campaign.image_url.clone(),
+            started_at: run.started_at,
+            completed_at: now,
+            elapsed_secs: now.signed_duration_since(run.started_at).num_seconds(),
+            minutes_watched: run.minutes,
+            channels: run.channels,
+            channel_switches: run.switches,
+            rewards,
+        })
+    }
+}
+
+fn history_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Stored summaries, oldest first.
+pub fn load_history() -> Vec<CampaignSummary> {
+    history_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Best-effort; the event still carries the summary if the write fails.
+pub fn append_history(summary: &CampaignSummary) {
+    let Some(path) = history_path() else {
+        return;
+    };
+    let mut history = load_history();
+    history.push(summary.clone());
+    let excess = history.len().saturating_sub(MAX_HISTORY);
+    history.drain(..excess);
+    match serde_json::to_string_pretty(&history) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[Drops] Failed to write campaign history: {}", e);
+            }
+        }
+        Err(e) => warn!("[Drops] Failed to serialize campaign history: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn summary_counts_minutes_channels_and_switches() {
+        let start = Utc::now();
+        let mut runs = CampaignRuns::default();
+        runs.record("c1", 30, Some("alpha"), start);
+        runs.record("c1", 0, Some("beta"), start);
+        runs.record("c1", 20, Some("beta"), start + Duration::minutes(30));
+        runs.record("c1", 10, Some("alpha"), start + Duration::minutes(50));
+        assert!(runs.is_tracking("c1"));
+
+        let campaign: DropCampaign = serde_json::from_value(serde_json::json!({
+            "id": "c1", "name": "Launch", "game_id": "g", "game_name": "Game",
+            "description": "", "image_url": "", "start_at": start, "end_at": start,
+            "time_based_drops": [{
+                "id": "d1", "name": "Hat", "required_minutes_watched": 60,
+                "benefit_edges": [{ "id": "b1", "name": "Hat", "image_url": "" }],
+            }],
+        }))
+        .unwrap();
+        let summary = runs.finish(&campaign, start + Duration::hours(1)).unwrap();
+        assert_eq!(summary.minutes_watched, 60);
+        assert_eq!(summary.channels, ["alpha", "beta"]);
+        assert_eq!(summary.channel_switches, 2);
+        assert_eq!(summary.rewards, ["Hat"]);
+        assert_eq!(summary.elapsed_secs, 3600);
+        assert!(!runs.is_tracking("c1"));
+    }
+}
@@ -0,0 +1,245 @@
+//! Cleanup of channel-specific caches for channels no longer watched.
+//!
+//! Each channel joined leaves files in the cache folder that nothing removes:
+//! its emote dictionary (`emote_sets/<id>.json`) and the legacy
+//! `emotes_<id>.json` / `badges_<type>_<id>.json`. Loading one of them marks
+//! the channel as accessed (`touch`), kept in `channel_cache_access.json`.
+//! Channels untouched for `CacheSettings.channel_expiry_days` lose their files,
+//! unless pinned in `CacheSettings.pinned_channel_caches`. Files from before
+//! tracking count from their modification time.
+//!
+//! Cleanup runs daily and with `cleanup_universal_cache`.
+
+use crate::models::settings::{AppState, CacheSettings};
+use crate::services::cache_service::get_cache_dir;
+use anyhow::Result;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+const ACCESS_FILE: &str = "channel_cache_access.json";
+/// A channel's access time is written to disk at most this often.
+const TOUCH_WRITE_SECS: u64 = 60 * 60;
+const DAY_SECS: u64 = 24 * 60 * 60;
+/// First cleanup after startup, then daily.
+const FIRST_RUN: Duration = Duration::from_secs(10 * 60);
+const INTERVAL: Duration = Duration::from_secs(DAY_SECS);
+
+/// Channel id to last access (unix seconds).
+static ACCESS: Lazy<StdMutex<HashMap<String, u64>>> =
+    Lazy::new(|| StdMutex::new(read_access().unwrap_or_default()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelCache {
+    pub channel_id: String,
+    pub last_accessed: u64,
+    pub bytes: u64,
+    pub files: usize,
+    pub pinned: bool,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn access_path() -> Result<PathBuf> {
+    Ok(get_cache_dir()?.join(ACCESS_FILE))
+}
+
+fn read_access() -> Result<HashMap<String, u64>> {
+    let bytes = fs::read(access_path()?)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn write_access(access: &HashMap<String, u64>) {
+    let result = access_path().and_then(|path| {
+        fs::write(path, serde_json::to_vec(access)?)?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        warn!("[ChannelCaches] Failed to save access times: {}", e);
+    }
+}
+
+/// Mark `channel_id`'s caches as used now.
+pub fn touch(channel_id: &str) {
+    if channel_id.is_empty() {
+        return;
+    }
+    let now = now_secs();
+    let mut access = ACCESS.lock().unwrap();
+    let last = access.get(channel_id).copied().unwrap_or(0);
+    if now.saturating_sub(last) >= TOUCH_WRITE_SECS {
+        access.insert(channel_id.to_string(), now);
+        write_access(&access);
+    }
+}
+
+/// The channel a cache file belongs to, from its name.
+fn channel_id_of(file_name: &str, in_emote_sets: bool) -> Option<&str> {
+    let stem = file_name.strip_suffix(".json")?;
+    let id = if in_emote_sets {
+        stem
+    } else if let Some(id) = stem.strip_prefix("emotes_") {
+        id
+    } else {
+        // badges_<type>_<id>; the global set is badges_<type>.
+        stem.strip_prefix("badges_")?.rsplit_once('_')?.1
+    };
+    (!id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())).then_some(id)
+}
+
+struct Found {
+    files: Vec<PathBuf>,
+    bytes: u64,
+    /// Newest modification time, for channels with no recorded access.
+    modified: u64,
+}
+
+fn channel_files() -> Result<HashMap<String, Found>> {
+    let cache_dir = get_cache_dir()?;
+    let mut found: HashMap<String, Found> = HashMap::new();
+    for (dir, in_emote_sets) in [
+        (cache_dir.clone(), false),
+        (cache_dir.join("emote_sets"), true),
+    ] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(id) = channel_id_of(&name, in_emote_sets) else {
+                continue;
+            };
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let channel = found.entry(id.to_string()).or_insert(Found {
+                files: Vec::new(),
+                bytes: 0,
+                modified: 0,
+            });
+            channel.files.push(entry.path());
+            channel.bytes += meta.len();
+            channel.modified = channel.modified.max(modified);
+        }
+    }
+    Ok(found)
+}
+
+/// Whether a channel last used at `last_accessed` is due for cleanup.
+fn is_stale(last_accessed: u64, now: u64, expiry_days: u32) -> bool {
+    expiry_days > 0 && now.saturating_sub(last_accessed) > u64::from(expiry_days) * DAY_SECS
+}
+
+/// The channels with caches on disk, least recently used first.
+pub fn list(settings: &CacheSettings) -> Result<Vec<ChannelCache>> {
+    let access = ACCESS.lock().unwrap().clone();
+    let mut channels: Vec<ChannelCache> = channel_files()?
+        .into_iter()
+        .map(|(channel_id, found)| ChannelCache {
+            last_accessed: access.get(&channel_id).copied().unwrap_or(found.modified),
+            bytes: found.bytes,
+            files: found.files.len(),
+            pinned: settings.pinned_channel_caches.contains(&channel_id),
+            channel_id,
+        })
+        .collect();
+    channels.sort_by_key(|c| c.last_accessed);
+    Ok(channels)
+}
+
+/// Delete the caches of unpinned channels untouched for the configured number
+/// of days. Returns how many channels were cleared.
+pub fn cleanup(settings: &CacheSettings) -> Result<usize> {
+    let now = now_secs();
+    let mut access = ACCESS.lock().unwrap();
+    let mut cleared = 0;
+    for (channel_id, found) in channel_files()? {
+        let last_accessed = access.get(&channel_id).copied().unwrap_or(found.modified);
+        if settings.pinned_channel_caches.contains(&channel_id)
+            || !is_stale(last_accessed, now, settings.channel_expiry_days)
+        {
+            continue;
+        }
+        for file in &found.files {
+            if let Err(e) = fs::remove_file(file) {
+                debug!("[ChannelCaches] Couldn't remove {}: {}", file.display(), e);
+            }
+        }
+        access.remove(&channel_id);
+        cleared += 1;
+    }
+    if cleared > 0 {
+        write_access(&access);
+        debug!(
+            "[ChannelCaches] Cleared caches of {} unused channels",
+            cleared
+        );
+    }
+    Ok(cleared)
+}
+
+/// Run `cleanup` daily. Call once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FIRST_RUN).await;
+        loop {
+            let settings = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .unwrap()
+                .cache
+                .clone();
+            if let Ok(Err(e)) = tokio::task::spawn_blocking(move || cleanup(&settings)).await {
+                warn!("[ChannelCaches] Cleanup failed: {}", e);
+            }
+            tokio::time::sleep(INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn files_map_to_their_channel() {
+        assert_eq!(channel_id_of("12345.json", true), Some("12345"));
+        assert_eq!(channel_id_of("emotes_12345.json", false), Some("12345"));
+        assert_eq!(
+            channel_id_of("badges_channel_12345.json", false),
+            Some("12345")
+        );
+        // Global and unrelated files aren't any channel's.
+        assert_eq!(channel_id_of("badges_global.json", false), None);
+        assert_eq!(channel_id_of("emote_abc.json", false), None);
+        assert_eq!(channel_id_of("favorite_emotes.json", false), None);
+        assert_eq!(channel_id_of("channel_cache_access.json", false), None);
+        assert_eq!(channel_id_of("12345.tmp", true), None);
+    }
+
+    #[test]
+    fn stale_after_the_expiry_unless_it_is_off() {
+        let now = 100 * DAY_SECS;
+        assert!(!is_stale(now - 30 * DAY_SECS, now, 30));
+        assert!(is_stale(now - 30 * DAY_SECS - 1, now, 30));
+        assert!(!is_stale(0, now, 0));
+    }
+}
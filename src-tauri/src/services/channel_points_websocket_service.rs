@@ -7,14 +7,15 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
-use tokio::sync::{Mutex as TokioMutex, RwLock};
+use tauri::AppHandle;
+use tokio::sync::{mpsc, Mutex as TokioMutex, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::{interval, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use uuid::Uuid;
 
 use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::event_bus;
 
 const PUBSUB_URL: &str = "wss://pubsub-edge.twitch.tv";
 const MAX_TOPICS_PER_CONNECTION: usize = 50;
@@ -51,10 +52,27 @@ pub struct ChannelPointsWebSocketService {
 #[derive(Debug)]
 struct WebSocketConnection {
     id: String,
+    /// Current topic set. Source of truth for the LISTEN sent on every
+    /// (re)connect, so topics added or removed at runtime survive a reconnect.
     topics: Vec<String>,
     is_connected: bool,
     last_ping: chrono::DateTime<Utc>,
     last_pong: chrono::DateTime<Utc>,
+    /// Frames queued for the socket's writer task (LISTEN / UNLISTEN issued
+    /// while connected). None while the socket is down.
+    outgoing: Option<mpsc::UnboundedSender<String>>,
+    reconnects: u32,
+}
+
+/// Snapshot of one PubSub socket for `get_websocket_status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PubSubConnectionStatus {
+    pub id: String,
+    pub topics: Vec<String>,
+    pub is_connected: bool,
+    pub last_ping: chrono::DateTime<Utc>,
+    pub last_pong: chrono::DateTime<Utc>,
+    pub reconnects: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -167,12 +185,12 @@ impl ChannelPointsWebSocketService {
         *self.auth_token.write().await = auth_token.to_string();
         *self.user_id.write().await = user_id.to_string();
 
-        // Each channel now generates 4 topics (video-playback, predictions, polls,
-        // community-points-channel) - removed raid.
-        // Plus 2 global topics (community-points-user and predictions-user)
-        // Use 10 channels per connection - testing shows 48 topics fails but 6 succeeds
-        // 10 channels * 4 topics + 2 global = 42 topics (safe, under the 50 cap)
-        const MAX_CHANNELS_PER_CONNECTION: usize = 10;
+        // Each channel now generates 5 topics (video-playback, predictions, polls,
+        // community-points-channel, broadcast-settings-update) - removed raid.
+        // Plus 2 global topics (community-points-user and predictions-user) on
+        // every connection, and user-drop-events on the first one only
+        // 9 channels * 5 topics + 3 global = 48 topics (under the 50 cap)
+        const MAX_CHANNELS_PER_CONNECTION: usize = 9;
 
         // Calculate how many WebSocket connections we need
         let num_connections =
@@ -201,7 +219,7 @@ impl ChannelPointsWebSocketService {
             }
 
             let connection_id = Uuid::new_v4().to_string();
-            let topics = self.build_topics_for_channels(chunk, user_id);
+            let topics = self.build_topics_for_channels(chunk, user_id, index == 0);
 
             // Store connection info
             {
@@ -212,12 +230,13 @@ impl ChannelPointsWebSocketService {
                     is_connected: false,
                     last_ping: Utc::now(),
                     last_pong: Utc::now(),
+                    outgoing: None,
+                    reconnects: 0,
                 });
             }
 
             // Spawn WebSocket connection handler
             let auth_token = auth_token.to_string();
-            let app_handle_clone = app_handle.clone();
             let connections = self.connections.clone();
             let channel_mappings = self.channel_mappings.clone();
             let active_viewing_channels = self.active_viewing_channels.clone();
@@ -228,7 +247,6 @@ impl ChannelPointsWebSocketService {
                     topics,
                     auth_token,
                     connections,
-                    app_handle_clone,
                     index,
                     channel_mappings,
                     active_viewing_channels,
@@ -247,38 +265,84 @@ impl ChannelPointsWebSocketService {
         Ok(())
     }
 
-    /// Build PubSub topics for a set of channels
-    fn build_topics_for_channels(&self, channel_ids: &[String], user_id: &str) -> Vec<String> {
+    /// Build PubSub topics for a set of channels. `first` is the session's
+    /// first connection, which also carries the once-per-user topics.
+    fn build_topics_for_channels(
+        &self,
+        channel_ids: &[String],
+        user_id: &str,
+        first: bool,
+    ) -> Vec<String> {
         let mut topics = Vec::new();
 
         // Add community points topics for the user (global) - MOST IMPORTANT
         topics.push(format!("community-points-user-v1.{}", user_id));
 
-        // For each channel, add only essential topics (2 per channel instead of 3)
+        // For each channel, add only essential topics
         for channel_id in channel_ids {
-            // Video playback events (stream up/down)
-            topics.push(format!("video-playback-by-id.{}", channel_id));
+            topics.extend(Self::channel_topics(channel_id));
+        }
 
-            // Predictions (if we want to participate)
-            topics.push(format!("predictions-channel-v1.{}", channel_id));
+        // User predictions results
+        topics.push(format!("predictions-user-v1.{}", user_id));
 
-            // Polls (channel-scoped: POLL_CREATE / POLL_UPDATE / POLL_COMPLETE)
-            topics.push(format!("polls.{}", channel_id));
+        // Account-wide drop progress and claim availability. Subscribed once:
+        // on every socket, each drop event would arrive once per connection.
+        if first {
+            topics.push(format!("user-drop-events.{}", user_id));
+        }
 
+        topics
+    }
+
+    /// The channel-scoped topics for one channel. Swapped as a unit when the
+    /// watched channel changes (see `swap_channel`).
+    fn channel_topics(channel_id: &str) -> Vec<String> {
+        vec![
+            // Video playback events (stream up/down)
+            format!("video-playback-by-id.{}", channel_id),
+            // Predictions (if we want to participate)
+            format!("predictions-channel-v1.{}", channel_id),
+            // Polls (channel-scoped: POLL_CREATE / POLL_UPDATE / POLL_COMPLETE)
+            format!("polls.{}", channel_id),
             // Channel-wide community points feed: every viewer's reward
             // redemption (reward-redeemed). Broadcast to all listeners with no
             // broadcaster auth, so it surfaces redemptions on any channel you
             // watch, not just ones you own or moderate.
-            topics.push(format!("community-points-channel-v1.{}", channel_id));
-        }
+            format!("community-points-channel-v1.{}", channel_id),
+            // Title / category changes, so a drops channel switching away from
+            // the campaign game is noticed without polling.
+            format!("broadcast-settings-update.{}", channel_id),
+        ]
+    }
 
-        // User predictions results
-        topics.push(format!("predictions-user-v1.{}", user_id));
+    /// Whether a socket session already exists for this user. A channel change
+    /// can then swap topics on it instead of opening a fresh connection.
+    pub async fn has_session_for(&self, user_id: &str) -> bool {
+        !self.connections.read().await.is_empty() && *self.user_id.read().await == user_id
+    }
 
-        // Note: Removed raid topics to reduce count per channel
-        // This allows 24 channels per connection: 24*2 + 2 global = 50 topics exactly
+    /// Move the channel-scoped topics from `from` to `to` on the existing
+    /// session: UNLISTEN the old channel, LISTEN the new one. The user-scoped
+    /// topics, the socket, and its reader task are left alone, so switching
+    /// channels for hours never stacks connections or listeners. Returns
+    /// false if the new topics didn't fit, in which case the caller should
+    /// fall back to `connect_to_channels`.
+    pub async fn swap_channel(&self, from: Option<&str>, to: &str, auth_token: &str) -> bool {
+        *self.auth_token.write().await = auth_token.to_string();
 
-        topics
+        if let Some(from) = from.filter(|f| *f != to) {
+            self.unlisten_topics(Self::channel_topics(from)).await;
+            self.unregister_active_channel(from).await;
+        }
+
+        let leftover = self.listen_topics(Self::channel_topics(to)).await;
+        if leftover.is_empty() {
+            debug!("Swapped PubSub channel topics {:?} -> {}", from, to);
+            true
+        } else {
+            false
+        }
     }
 
     /// Handle a single WebSocket connection
@@ -287,11 +351,21 @@ impl ChannelPointsWebSocketService {
         topics: Vec<String>,
         auth_token: String,
         connections: Arc<RwLock<Vec<WebSocketConnection>>>,
-        app_handle: AppHandle,
         index: usize,
         channel_mappings: Arc<RwLock<HashMap<String, ChannelMapping>>>,
         active_viewing_channels: Arc<RwLock<HashSet<String>>>,
     ) -> Result<()> {
+        // Resubscribe with the connection's CURRENT topic set, not the one it
+        // was spawned with, so runtime listen/unlisten survives a reconnect.
+        let topics = {
+            let conns = connections.read().await;
+            conns
+                .iter()
+                .find(|c| c.id == connection_id)
+                .map(|c| c.topics.clone())
+                .unwrap_or(topics)
+        };
+
         debug!(
             "Connecting WebSocket #{} with {} topics",
             index,
@@ -300,12 +374,14 @@ impl ChannelPointsWebSocketService {
 
         let (ws_stream, _) = connect_async(PUBSUB_URL).await?;
         let (mut write, mut read) = ws_stream.split();
+        let (outgoing_tx, mut outgoing_rx) = mpsc::unbounded_channel::<String>();
 
         // Mark as connected
         {
             let mut conns = connections.write().await;
             if let Some(conn) = conns.iter_mut().find(|c| c.id == connection_id) {
                 conn.is_connected = true;
+                conn.outgoing = Some(outgoing_tx);
             }
         }
 
@@ -331,44 +407,58 @@ impl ChannelPointsWebSocketService {
         let connections_ping = connections.clone();
         let connection_id_ping = connection_id.clone();
 
-        // Spawn ping task to keep connection alive
+        // Spawn the writer task: keeps the connection alive with PINGs and
+        // forwards runtime LISTEN/UNLISTEN frames queued by listen_topics /
+        // unlisten_topics.
         let ping_task = tokio::spawn(async move {
             let mut ping_interval = interval(Duration::from_secs(240)); // Ping every 4 minutes
             ping_interval.tick().await; // Skip first immediate tick
 
             loop {
-                ping_interval.tick().await;
-
-                let ping_message = json!({
-                    "type": "PING"
-                });
+                tokio::select! {
+                    _ = ping_interval.tick() => {
+                        let ping_message = json!({
+                            "type": "PING"
+                        });
+
+                        if let Err(e) = write.send(Message::text(ping_message.to_string())).await {
+                            error!("WebSocket #{} failed to send PING: {}", index, e);
+                            break;
+                        }
 
-                if let Err(e) = write.send(Message::text(ping_message.to_string())).await {
-                    error!("WebSocket #{} failed to send PING: {}", index, e);
-                    break;
-                }
+                        // Update last ping time
+                        {
+                            let mut conns = connections_ping.write().await;
+                            if let Some(conn) = conns.iter_mut().find(|c| c.id == connection_id_ping) {
+                                conn.last_ping = Utc::now();
+                            }
+                        }
 
-                // Update last ping time
-                {
-                    let mut conns = connections_ping.write().await;
-                    if let Some(conn) = conns.iter_mut().find(|c| c.id == connection_id_ping) {
-                        conn.last_ping = Utc::now();
+                        debug!("WebSocket #{} sent PING", index);
+                    }
+                    frame = outgoing_rx.recv() => {
+                        let Some(frame) = frame else { break };
+                        if let Err(e) = write.send(Message::text(frame)).await {
+                            error!("WebSocket #{} failed to send frame: {}", index, e);
+                            break;
+                        }
                     }
                 }
-
-                debug!("WebSocket #{} sent PING", index);
             }
         });
 
         // Handle incoming messages
         let mut should_reconnect = false;
+        // Set when the server asked us to move (RECONNECT). Twitch closes the
+        // socket ~30s later, so reconnect right away instead of waiting on the
+        // close and the regular 60s backoff.
+        let mut server_requested = false;
         while let Some(msg) = read.next().await {
             match msg {
                 Ok(Message::Text(text)) => {
                     if let Ok(pubsub_msg) = serde_json::from_str::<PubSubMessage>(&text) {
-                        Self::handle_pubsub_message(
+                        let reconnect_requested = Self::handle_pubsub_message(
                             pubsub_msg,
-                            &app_handle,
                             &connections,
                             &connection_id,
                             index,
@@ -376,6 +466,11 @@ impl ChannelPointsWebSocketService {
                             &active_viewing_channels,
                         )
                         .await;
+                        if reconnect_requested {
+                            should_reconnect = true;
+                            server_requested = true;
+                            break;
+                        }
                     }
                 }
                 Ok(Message::Close(_)) => {
@@ -400,12 +495,21 @@ impl ChannelPointsWebSocketService {
             let mut conns = connections.write().await;
             if let Some(conn) = conns.iter_mut().find(|c| c.id == connection_id) {
                 conn.is_connected = false;
+                conn.outgoing = None;
+                if should_reconnect {
+                    conn.reconnects += 1;
+                }
             }
         }
 
         // Attempt reconnection after delay if needed
         if should_reconnect {
-            tokio::time::sleep(Duration::from_secs(60)).await;
+            let delay = if server_requested {
+                Duration::from_secs(1)
+            } else {
+                Duration::from_secs(60)
+            };
+            tokio::time::sleep(delay).await;
             debug!("Attempting to reconnect WebSocket #{}...", index);
 
             // Recursively reconnect
@@ -414,7 +518,6 @@ impl ChannelPointsWebSocketService {
                 topics,
                 auth_token,
                 connections,
-                app_handle,
                 index,
                 channel_mappings,
                 active_viewing_channels,
@@ -425,16 +528,16 @@ impl ChannelPointsWebSocketService {
         }
     }
 
-    /// Handle incoming PubSub messages
+    /// Handle incoming PubSub messages. Returns true when the server asked
+    /// this socket to reconnect.
     async fn handle_pubsub_message(
         msg: PubSubMessage,
-        app_handle: &AppHandle,
         connections: &Arc<RwLock<Vec<WebSocketConnection>>>,
         connection_id: &str,
         index: usize,
         channel_mappings: &Arc<RwLock<HashMap<String, ChannelMapping>>>,
         active_viewing_channels: &Arc<RwLock<HashSet<String>>>,
-    ) {
+    ) -> bool {
         match msg.msg_type.as_str() {
             "MESSAGE" => {
                 if let Some(data) = msg.data {
@@ -452,7 +555,6 @@ impl ChannelPointsWebSocketService {
                             "community-points-user-v1" => {
                                 Self::handle_points_event(
                                     message_data,
-                                    app_handle,
                                     channel_id,
                                     channel_mappings,
                                 )
@@ -464,7 +566,6 @@ impl ChannelPointsWebSocketService {
                             "predictions-channel-v1" => {
                                 Self::handle_prediction_event(
                                     message_data,
-                                    app_handle,
                                     channel_id,
                                     channel_mappings,
                                     active_viewing_channels,
@@ -474,7 +575,6 @@ impl ChannelPointsWebSocketService {
                             "polls" => {
                                 Self::handle_poll_event(
                                     message_data,
-                                    app_handle,
                                     channel_id,
                                     active_viewing_channels,
                                 )
@@ -483,7 +583,17 @@ impl ChannelPointsWebSocketService {
                             "community-points-channel-v1" => {
                                 Self::handle_channel_redemption_event(
                                     message_data,
-                                    app_handle,
+                                    channel_id,
+                                    active_viewing_channels,
+                                )
+                                .await;
+                            }
+                            "user-drop-events" => {
+                                Self::handle_drop_event(message_data);
+                            }
+                            "broadcast-settings-update" => {
+                                Self::handle_broadcast_settings_event(
+                                    message_data,
                                     channel_id,
                                     active_viewing_channels,
                                 )
@@ -505,7 +615,7 @@ impl ChannelPointsWebSocketService {
             }
             "RECONNECT" => {
                 debug!("WebSocket #{} received RECONNECT request", index);
-                // Connection will automatically reconnect when closed
+                return true;
             }
             "RESPONSE" => {
                 if let Some(error) = msg.error {
@@ -521,12 +631,12 @@ impl ChannelPointsWebSocketService {
             }
             _ => {}
         }
+        false
     }
 
     /// Handle channel points events
     async fn handle_points_event(
         message_data: Value,
-        app_handle: &AppHandle,
         _topic_channel_id: Option<String>,
         channel_mappings: &Arc<RwLock<HashMap<String, ChannelMapping>>>,
     ) {
@@ -621,7 +731,7 @@ impl ChannelPointsWebSocketService {
                         channel_login_str
                     );
 
-                    let _ = app_handle.emit(
+                    let _ = event_bus::emit(
                         "channel-points-earned",
                         json!({
                             "channel_id": channel_id,
@@ -641,7 +751,7 @@ impl ChannelPointsWebSocketService {
 
                     debug!("Bonus claim available! ID: {}", claim_id);
 
-                    let _ = app_handle.emit(
+                    let _ = event_bus::emit(
                         "channel-points-claim-available",
                         json!({
                             "channel_id": claim_channel_id,
@@ -662,7 +772,7 @@ impl ChannelPointsWebSocketService {
 
                     debug!("Points spent: -{} - New balance: {}", points, balance);
 
-                    let _ = app_handle.emit(
+                    let _ = event_bus::emit(
                         "channel-points-spent",
                         json!({
                             "channel_id": spent_channel_id,
@@ -679,7 +789,6 @@ impl ChannelPointsWebSocketService {
     /// Handle prediction events
     async fn handle_prediction_event(
         message_data: Value,
-        app_handle: &AppHandle,
         channel_id: Option<String>,
         channel_mappings: &Arc<RwLock<HashMap<String, ChannelMapping>>>,
         active_viewing_channels: &Arc<RwLock<HashSet<String>>>,
@@ -792,7 +901,7 @@ impl ChannelPointsWebSocketService {
                         );
 
                         // Emit to frontend
-                        let emit_result = app_handle.emit(
+                        let emit_result = event_bus::emit(
                             "prediction-created",
                             json!({
                                 "channel_id": channel_id,
@@ -865,7 +974,7 @@ impl ChannelPointsWebSocketService {
                         );
 
                         // Emit update to frontend
-                        let _ = app_handle.emit(
+                        let _ = event_bus::emit(
                             "prediction-updated",
                             json!({
                                 "channel_id": channel_id,
@@ -886,7 +995,7 @@ impl ChannelPointsWebSocketService {
 
                         debug!("Prediction locked on {}: {}", channel_display, title);
 
-                        let _ = app_handle.emit(
+                        let _ = event_bus::emit(
                             "prediction-locked",
                             json!({
                                 "channel_id": channel_id,
@@ -910,7 +1019,7 @@ impl ChannelPointsWebSocketService {
                             channel_display, title, winner_display
                         );
 
-                        let _ = app_handle.emit(
+                        let _ = event_bus::emit(
                             "prediction-ended",
                             json!({
                                 "channel_id": channel_id,
@@ -936,7 +1045,6 @@ impl ChannelPointsWebSocketService {
     /// camelCase-ish shape the frontend PollOverlay expects.
     async fn handle_poll_event(
         message_data: Value,
-        app_handle: &AppHandle,
         channel_id: Option<String>,
         active_viewing_channels: &Arc<RwLock<HashSet<String>>>,
     ) {
@@ -1035,7 +1143,7 @@ impl ChannelPointsWebSocketService {
             emit_event, channel_id, title, total_voters, total_votes, status
         );
 
-        let _ = app_handle.emit(emit_event, payload);
+        let _ = event_bus::emit(emit_event, payload);
     }
 
     /// Handle the channel-wide community points feed (`community-points-channel-v1`).
@@ -1047,7 +1155,6 @@ impl ChannelPointsWebSocketService {
     /// message-style rewards already appear in chat on their own).
     async fn handle_channel_redemption_event(
         message_data: Value,
-        app_handle: &AppHandle,
         channel_id: Option<String>,
         active_viewing_channels: &Arc<RwLock<HashSet<String>>>,
     ) {
@@ -1114,7 +1221,7 @@ impl ChannelPointsWebSocketService {
             channel_id, user_name, reward_title, reward_cost
         );
 
-        let _ = app_handle.emit(
+        let _ = event_bus::emit(
             "channel-points-community-redemption",
             json!({
                 "channel_id": channel_id,
@@ -1134,6 +1241,168 @@ impl ChannelPointsWebSocketService {
         );
     }
 
+    /// Handle account-wide drop events (`user-drop-events`). `drop-progress`
+    /// carries the live minute count for the drop currently earning, so the
+    /// Drops center updates between inventory polls; `drop-claim` carries the
+    /// dropInstanceID needed to claim a finished drop.
+    fn handle_drop_event(message_data: Value) {
+        let data = &message_data["data"];
+        match message_data["type"].as_str() {
+            Some("drop-progress") => {
+                let Some(drop_id) = data["drop_id"].as_str() else {
+                    return;
+                };
+                let current = data["current_progress_min"].as_i64().unwrap_or(0);
+                let required = data["required_progress_min"].as_i64().unwrap_or(0);
+
+                debug!(
+                    "Drop progress: {}/{} minutes for drop {}",
+                    current, required, drop_id
+                );
+
+                let _ = event_bus::emit(
+                    "drop-progress-update",
+                    json!({
+                        "drop_id": drop_id,
+                        "channel_id": data["channel_id"].as_str(),
+                        "current_minutes": current,
+                        "required_minutes": required,
+                    }),
+                );
+            }
+            Some("drop-claim") => {
+                let Some(drop_id) = data["drop_id"].as_str() else {
+                    return;
+                };
+                debug!("Drop claim available for drop {}", drop_id);
+
+                let _ = event_bus::emit(
+                    "drop-claim-available",
+                    json!({
+                        "drop_id": drop_id,
+                        "drop_instance_id": data["drop_instance_id"].as_str(),
+                        "channel_id": data["channel_id"].as_str(),
+                    }),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle title / category changes (`broadcast-settings-update`) for a
+    /// watched channel.
+    async fn handle_broadcast_settings_event(
+        message_data: Value,
+        channel_id: Option<String>,
+        active_viewing_channels: &Arc<RwLock<HashSet<String>>>,
+    ) {
+        let Some(cid) = channel_id else { return };
+        if !active_viewing_channels.read().await.contains(&cid) {
+            return;
+        }
+
+        debug!(
+            "Broadcast settings changed on {}: game {:?} -> {:?}",
+            cid,
+            message_data["old_game"].as_str(),
+            message_data["game"].as_str()
+        );
+
+        let _ = event_bus::emit(
+            "broadcast-settings-update",
+            json!({
+                "channel_id": cid,
+                "old_title": message_data["old_status"].as_str(),
+                "title": message_data["status"].as_str(),
+                "old_game": message_data["old_game"].as_str(),
+                "game": message_data["game"].as_str(),
+                "old_game_id": message_data["old_game_id"].as_i64(),
+                "game_id": message_data["game_id"].as_i64(),
+            }),
+        );
+    }
+
+    /// Subscribe additional topics at runtime. Topics go to the first socket
+    /// with room under the per-connection cap and are LISTENed immediately if
+    /// that socket is up; either way they're part of its topic set for every
+    /// later reconnect. Returns the topics that didn't fit anywhere.
+    pub async fn listen_topics(&self, topics: Vec<String>) -> Vec<String> {
+        let token = self.auth_token.read().await.clone();
+        let mut conns = self.connections.write().await;
+
+        for conn in conns.iter_mut() {
+            let room = MAX_TOPICS_PER_CONNECTION.saturating_sub(conn.topics.len());
+            let fresh: Vec<String> = topics
+                .iter()
+                .filter(|t| !conn.topics.contains(t))
+                .cloned()
+                .collect();
+            if fresh.is_empty() {
+                return Vec::new();
+            }
+            if room < fresh.len() {
+                continue;
+            }
+
+            conn.topics.extend(fresh.iter().cloned());
+            if let Some(tx) = conn.outgoing.as_ref() {
+                let frame = json!({
+                    "type": "LISTEN",
+                    "nonce": Uuid::new_v4().to_string(),
+                    "data": { "topics": fresh, "auth_token": token }
+                });
+                let _ = tx.send(frame.to_string());
+            }
+            return Vec::new();
+        }
+
+        topics
+    }
+
+    /// Unsubscribe topics at runtime from whichever socket holds them.
+    pub async fn unlisten_topics(&self, topics: Vec<String>) {
+        let token = self.auth_token.read().await.clone();
+        let mut conns = self.connections.write().await;
+
+        for conn in conns.iter_mut() {
+            let held: Vec<String> = topics
+                .iter()
+                .filter(|t| conn.topics.contains(t))
+                .cloned()
+                .collect();
+            if held.is_empty() {
+                continue;
+            }
+
+            conn.topics.retain(|t| !held.contains(t));
+            if let Some(tx) = conn.outgoing.as_ref() {
+                let frame = json!({
+                    "type": "UNLISTEN",
+                    "nonce": Uuid::new_v4().to_string(),
+                    "data": { "topics": held, "auth_token": token }
+                });
+                let _ = tx.send(frame.to_string());
+            }
+        }
+    }
+
+    /// Per-socket connection state for the diagnostics surface.
+    pub async fn get_status(&self) -> Vec<PubSubConnectionStatus> {
+        self.connections
+            .read()
+            .await
+            .iter()
+            .map(|c| PubSubConnectionStatus {
+                id: c.id.clone(),
+                topics: c.topics.clone(),
+                is_connected: c.is_connected,
+                last_ping: c.last_ping,
+                last_pong: c.last_pong,
+                reconnects: c.reconnects,
+            })
+            .collect()
+    }
+
     /// Start ping keeper to maintain connections. Idempotent — if a keeper is
     /// already running, this is a no-op. `disconnect_all` aborts the keeper so
     /// the next `connect_to_channels` gets a fresh one.
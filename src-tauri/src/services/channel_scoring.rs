@@ -0,0 +1,242 @@
+//! How live channels rank for mining a campaign under
+//! `DropsSettings.score_weights`, for `preview_channel_scores`.
+//!
+//! The drops plugin does the actual picking; it's sent the same weights as
+//! `score_weights` (docs/plugins/HOOKS.md), so this preview is how the user
+//! sees what a change would do before mining with it. A channel's score is
+//! the sum of:
+//!
+//! - `priority_bonus` if it's on the priority list,
+//! - `acl_bonus` if the campaign names it on its own channel list,
+//! - `viewer_weight` × log10(viewers + 1),
+//! - minus `uptime_penalty` × the broadcast's 0..1 uptime penalty
+//!   (stream_uptime_service: rising from 9 to 13 hours live, and at least
+//!   half once past the channel's typical length),
+//! - `language_bonus` for the first preferred language, shrinking evenly
+//!   for each one after it and nothing for the rest.
+//!
+//! Blacklisted channels and those outside the viewer bounds are still
+//! scored, but marked as skipped.
+
+use crate::models::drops::{DropCampaign, DropsSettings};
+use crate::models::stream::TwitchStream;
+use crate::services::stream_uptime_service;
+use crate::services::twitch_service::TwitchService;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Live channels looked at for an open campaign (its game's biggest
+/// drops-enabled streams).
+const CANDIDATES: u32 = 100;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    pub priority: f64,
+    pub acl: f64,
+    pub viewers: f64,
+    pub uptime: f64,
+    pub language: f64,
+}
+
+impl ScoreBreakdown {
+    pub fn total(&self) -> f64 {
+        self.priority + self.acl + self.viewers + self.uptime + self.language
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelScore {
+    pub channel_id: String,
+    pub channel_login: String,
+    pub display_name: String,
+    pub viewer_count: u32,
+    pub language: Option<String>,
+    pub uptime_mins: i64,
+    pub score: f64,
+    pub breakdown: ScoreBreakdown,
+    /// Why mining would pass this channel over regardless of score.
+    pub skipped: Option<String>,
+}
+
+fn uptime_mins(stream: &TwitchStream, now: DateTime<Utc>) -> i64 {
+    DateTime::parse_from_rfc3339(&stream.started_at)
+        .map(|started| (now - started.with_timezone(&Utc)).num_minutes().max(0))
+        .unwrap_or(0)
+}
+
+/// Score `stream` for `campaign` under `settings`. `median_hours` is the
+/// channel's typical broadcast length, when one has been seen.
+pub fn score(
+    settings: &DropsSettings,
+    campaign: &DropCampaign,
+    stream: &TwitchStream,
+    median_hours: Option<f64>,
+    now: DateTime<Utc>,
+) -> ScoreBreakdown {
+    let weights = &settings.score_weights;
+    let prioritized = settings
+        .priority_channels
+        .iter()
+        .any(|c| c.channel_id == stream.user_id);
+    let listed =
+        campaign.is_acl_based && campaign.allows_channel(&stream.user_id, &stream.user_login);
+    let languages = settings.language_codes().len();
+    let rank = settings.language_rank(stream.language.as_deref());
+    let uptime_hours = uptime_mins(stream, now) as f64 / 60.0;
+    ScoreBreakdown {
+        priority: if prioritized {
+            weights.priority_bonus
+        } else {
+            0.0
+        },
+        acl: if listed { weights.acl_bonus } else { 0.0 },
+        viewers: weights.viewer_weight * (stream.viewer_count as f64 + 1.0).log10(),
+        uptime: -weights.uptime_penalty
+            * stream_uptime_service::uptime_penalty(uptime_hours, median_hours),
+        language: if rank < languages {
+            weights.language_bonus * (languages - rank) as f64 / languages as f64
+        } else {
+            0.0
+        },
+    }
+}
+
+/// The live channels `campaign` can be mined on, scored and best first.
+pub async fn preview(
+    settings: &DropsSettings,
+    campaign: &DropCampaign,
+) -> Result<Vec<ChannelScore>> {
+    let streams = if campaign.is_acl_based {
+        let logins: Vec<String> = campaign
+            .allowed_channels
+            .iter()
+            .map(|c| c.name.to_lowercase())
+            .collect();
+        if logins.is_empty() {
+            Vec::new()
+        } else {
+            TwitchService::check_streams_online(&logins).await?
+        }
+    } else {
+        TwitchService::get_drops_enabled_streams(Some(&campaign.game_id), None, CANDIDATES)
+            .await?
+            .0
+    };
+
+    let now = Utc::now();
+    let blacklisted = crate::services::drops_blacklist::channel_ids();
+    let mut scores: Vec<ChannelScore> = streams
+        .iter()
+        .filter(|s| s.game_id == campaign.game_id)
+        .filter(|s| campaign.allows_channel(&s.user_id, &s.user_login))
+        .map(|stream| {
+            let median_hours =
+                stream_uptime_service::stats(&stream.user_id).and_then(|s| s.median_hours);
+            let breakdown = score(settings, campaign, stream, median_hours, now);
+            let skipped = if blacklisted.contains(&stream.user_id) {
+                Some("Blacklisted".to_string())
+            } else if !settings.viewers_in_range(stream.viewer_count) {
+                Some("Outside the viewer bounds".to_string())
+            } else {
+                None
+            };
+            ChannelScore {
+                channel_id: stream.user_id.clone(),
+                channel_login: stream.user_login.clone(),
+                display_name: stream.user_name.clone(),
+                viewer_count: stream.viewer_count,
+                language: stream.language.clone(),
+                uptime_mins: uptime_mins(stream, now),
+                score: breakdown.total(),
+                breakdown,
+                skipped,
+            }
+        })
+        .collect();
+    // Eligible channels first, each group best first.
+    scores.sort_by(|a, b| {
+        a.skipped
+            .is_some()
+            .cmp(&b.skipped.is_some())
+            .then(b.score.total_cmp(&a.score))
+    });
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::drops::{AllowedChannel, PriorityChannel};
+
+    fn stream(id: &str, viewers: u32, language: &str, started_at: &str) -> TwitchStream {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "user_id": id, "user_login": id, "user_name": id,
+            "title": "", "viewer_count": viewers, "game_id": "g", "game_name": "G",
+            "thumbnail_url": "", "started_at": started_at, "language": language,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn each_weight_adds_its_own_term() {
+        let now: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+        let mut campaign: DropCampaign = serde_json::from_value(serde_json::json!({
+            "id": "c", "name": "C", "game_id": "g", "game_name": "G",
+            "description": "", "image_url": "",
+            "start_at": "2026-01-01T00:00:00Z", "end_at": "2026-04-01T00:00:00Z",
+            "time_based_drops": [],
+        }))
+        .unwrap();
+        campaign.is_acl_based = true;
+        campaign.allowed_channels = vec![AllowedChannel {
+            id: "a".to_string(),
+            name: "a".to_string(),
+        }];
+        let settings = DropsSettings {
+            priority_channels: vec![PriorityChannel {
+                channel_id: "a".to_string(),
+                channel_login: "a".to_string(),
+                display_name: "A".to_string(),
+            }],
+            preferred_languages: vec!["de".to_string(), "en".to_string()],
+            ..Default::default()
+        };
+
+        let a = score(
+            &settings,
+            &campaign,
+            &stream("a", 999, "en", "2026-03-01T01:00:00Z"),
+            None,
+            now,
+        );
+        assert_eq!(
+            a,
+            ScoreBreakdown {
+                priority: 100.0,
+                acl: 50.0,
+                viewers: 30.0,
+                uptime: -10.0,
+                language: 12.5,
+            }
+        );
+        assert_eq!(a.total(), 182.5);
+
+        let b = score(
+            &settings,
+            &campaign,
+            &stream("b", 0, "fr", "bad date"),
+            None,
+            now,
+        );
+        assert_eq!(b, ScoreBreakdown::default());
+
+        // Three hours in is past a two-hour channel's typical broadcast.
+        let c = stream("c", 0, "fr", "2026-03-01T09:00:00Z");
+        assert_eq!(score(&settings, &campaign, &c, None, now).uptime, 0.0);
+        assert_eq!(
+            score(&settings, &campaign, &c, Some(2.0), now).uptime,
+            -10.0
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Cache warm-up when a chat is joined, so the frontend waits on one
+//! `chat-ready` event instead of requesting each piece in turn.
+//!
+//! The essentials, the channel's emote sets (Twitch, 7TV, BTTV, FFZ) and its
+//! badges, load in parallel; `chat-ready` goes out once both have settled,
+//! saying which succeeded. Global and third-party badges and the channel's
+//! cheermotes are fetched alongside but don't hold it up; the cheermotes are
+//! what chat parsing matches `Cheer100`-style words against (`cheermote`).
+
+use crate::models::settings::AppState;
+use crate::services::irc_service::IrcService;
+use crate::services::twitch_service::TwitchService;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+/// How long an essential may take before `chat-ready` goes out without it.
+const ESSENTIAL_TIMEOUT: Duration = Duration::from_secs(10);
+/// Most channels whose cheermotes are kept; the least recently warmed go first.
+const MAX_CHEERMOTE_CHANNELS: usize = 32;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatReady {
+    pub channel: String,
+    /// None when the channel couldn't be looked up.
+    pub channel_id: Option<String>,
+    pub emotes: bool,
+    pub badges: bool,
+    pub elapsed_ms: u64,
+}
+
+/// Channels being warmed right now, so overlapping joins share one run.
+static WARMING: Lazy<StdMutex<HashSet<String>>> = Lazy::new(|| StdMutex::new(HashSet::new()));
+
+#[derive(Debug, Clone)]
+struct CheerTier {
+    min_bits: u32,
+    id: String,
+    color: String,
+    url: String,
+}
+
+/// A channel's cheermotes: lowercase prefix to its tiers, highest first.
+type ChannelCheermotes = HashMap<String, Vec<CheerTier>>;
+
+/// Cheermotes by channel login, with when they were warmed.
+static CHEERMOTES: Lazy<StdMutex<HashMap<String, (Instant, ChannelCheermotes)>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Helix `bits/cheermotes` entries to the lookup chat parsing uses.
+fn index_cheermotes(data: &[Value]) -> ChannelCheermotes {
+    let mut index = ChannelCheermotes::new();
+    for action in data {
+        let Some(prefix) = action.get("prefix").and_then(|p| p.as_str()) else {
+            continue;
+        };
+        let mut tiers: Vec<CheerTier> = action
+            .get("tiers")
+            .and_then(|t| t.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|tier| {
+                Some(CheerTier {
+                    min_bits: tier.get("min_bits")?.as_u64()? as u32,
+                    id: tier.get("id")?.as_str()?.to_string(),
+                    color: tier.get("color")?.as_str()?.to_string(),
+                    url: tier
+                        .pointer("/images/dark/animated/2")?
+                        .as_str()?
+                        .to_string(),
+                })
+            })
+            .collect();
+        if tiers.is_empty() {
+            continue;
+        }
+        tiers.sort_by(|a, b| b.min_bits.cmp(&a.min_bits));
+        index.insert(prefix.to_lowercase(), tiers);
+    }
+    index
+}
+
+async fn warm_cheermotes(channel: &str, channel_id: &str) -> anyhow::Result<()> {
+    let data = TwitchService::get_cheermotes(channel_id).await?;
+    let index = index_cheermotes(&data);
+    let mut cache = CHEERMOTES.lock().unwrap();
+    cache.insert(channel.to_string(), (Instant::now(), index));
+    if cache.len() > MAX_CHEERMOTE_CHANNELS {
+        let oldest = cache
+            .iter()
+            .min_by_key(|(_, (warmed, _))| *warmed)
+            .map(|(login, _)| login.clone());
+        if let Some(oldest) = oldest {
+            cache.remove(&oldest);
+        }
+    }
+    Ok(())
+}
+
+/// Match `word` (e.g. `Cheer500`) against `channel`'s warmed cheermotes,
+/// custom ones included. Returns `(prefix, bits, tier, color, url)`, or None
+/// when the channel hasn't been warmed or the prefix isn't one of its own.
+pub fn cheermote(channel: &str, word: &str) -> Option<(String, u32, String, String, String)> {
+    let prefix = word.trim_end_matches(|c: char| c.is_ascii_digit());
+    let bits: u32 = word[prefix.len()..].parse().ok()?;
+    if prefix.is_empty() || bits == 0 {
+        return None;
+    }
+    let prefix = prefix.to_lowercase();
+    let cache = CHEERMOTES.lock().unwrap();
+    let (_, index) = cache.get(channel.trim_start_matches('#'))?;
+    let tier = index.get(&prefix)?.iter().find(|t| t.min_bits <= bits)?;
+    Some((
+        prefix,
+        bits,
+        tier.id.clone(),
+        tier.color.clone(),
+        tier.url.clone(),
+    ))
+}
+
+async fn within<F: std::future::Future<Output = bool>>(step: F) -> bool {
+    tokio::time::timeout(ESSENTIAL_TIMEOUT, step)
+        .await
+        .unwrap_or(false)
+}
+
+async fn channel_badges(channel_id: &str) -> bool {
+    let Ok(service) = crate::commands::badge_service::get_service().await else {
+        return false;
+    };
+    let guard = service.read().await;
+    let Some(service) = guard.as_ref() else {
+        return false;
+    };
+    if service.get_channel_badges(channel_id).await.is_some() {
+        return true;
+    }
+    let Ok(token) = TwitchService::get_token().await else {
+        return false;
+    };
+    service
+        .fetch_channel_badges(channel_id, &token)
+        .await
+        .is_ok()
+}
+
+/// Badges and cheermotes nothing waits on.
+async fn warm_extras(channel: String, channel_id: String) {
+    let (_, _, cheer) = tokio::join!(
+        crate::commands::badge_service::prefetch_global_badges_unified(),
+        crate::commands::badge_service::prefetch_third_party_badges(),
+        warm_cheermotes(&channel, &channel_id),
+    );
+    if let Err(e) = cheer {
+        debug!("[ChatReady] Cheermotes for {} failed: {}", channel, e);
+    }
+}
+
+/// Warm `channel`'s caches and emit `chat-ready`. With `refresh_emotes` the
+/// emote sets are re-fetched even when already cached (a fresh JOIN);
+/// otherwise a cached set is kept.
+pub async fn warm(channel: &str, state: &AppState, refresh_emotes: bool) -> Option<ChatReady> {
+    let channel = channel.trim_start_matches('#').to_lowercase();
+    if !WARMING.lock().unwrap().insert(channel.clone()) {
+        return None;
+    }
+    let started = Instant::now();
+
+    let emote_service = state.emote_service.clone();
+    let emotes = within(async {
+        if refresh_emotes {
+            IrcService::fetch_and_store_emotes(&channel, emote_service)
+                .await
+                .is_some()
+        } else {
+            IrcService::ensure_channel_emotes_for_parse(&channel, emote_service).await;
+            true
+        }
+    });
+    let channel_id = async {
+        match TwitchService::get_user_by_login(&channel).await {
+            Ok(user) => {
+                tauri::async_runtime::spawn(warm_extras(channel.clone(), user.id.clone()));
+                let badges = within(channel_badges(&user.id)).await;
+                (Some(user.id), badges)
+            }
+            Err(e) => {
+                debug!("[ChatReady] Couldn't look up {}: {}", channel, e);
+                (None, false)
+            }
+        }
+    };
+    let (emotes, (channel_id, badges)) = tokio::join!(emotes, channel_id);
+
+    WARMING.lock().unwrap().remove(&channel);
+    let ready = ChatReady {
+        channel,
+        channel_id,
+        emotes,
+        badges,
+        elapsed_ms: started.elapsed().as_millis() as u64,
+    };
+    debug!("[ChatReady] {:?}", ready);
+    let _ = crate::services::event_bus::emit("chat-ready", &ready);
+    Some(ready)
+}
@@ -29,7 +29,14 @@ impl ChatService {
         reattach: bool,
         window: &str,
     ) -> Result<u16> {
-        IrcService::start(channel, state, claim, reattach, window).await
+        let port = IrcService::start(channel, state, claim, reattach, window).await?;
+        // The IRC side has fetched (or is fetching) the emote sets already;
+        // warm the rest and report `chat-ready`.
+        let (channel, state) = (channel.to_string(), state.clone());
+        tauri::async_runtime::spawn(async move {
+            crate::services::chat_ready::warm(&channel, &state, false).await;
+        });
+        Ok(port)
     }
 
     pub async fn send_message(
@@ -43,6 +50,7 @@ impl ChatService {
         // Slash-commands (/ban, /me, /timeout, ...) MUST go over IRC so Twitch
         // executes them. Helix Send Chat Message would post them as literal text.
         let is_command = message.trim_start().starts_with('/');
+        crate::services::autocomplete::record_sent(target_channel, message);
 
         // Is this being sent from a NON-primary (secondary) account? The primary
         // owns the IRC connection and the cached token; secondaries send via Helix
@@ -160,8 +168,15 @@ impl ChatService {
         // is keyed per channel). Previously every `start_chat` did a full
         // tear-down + re-fetch which masked the gap; with idempotent `start`,
         // additional-channel JOINs (channel switching, MultiChat tabs, etc.)
-        // now route through this path.
-        IrcService::fetch_and_store_emotes(channel, state.emote_service.clone()).await;
+        // now route through this path. The badges load alongside, and
+        // `chat-ready` reports when both are in.
+        if crate::services::chat_ready::warm(channel, state, true)
+            .await
+            .is_none()
+        {
+            // Another join is warming it; still make sure the parse map has it.
+            IrcService::ensure_channel_emotes_for_parse(channel, state.emote_service.clone()).await;
+        }
         Ok(())
     }
 
@@ -0,0 +1,223 @@
+//! Chat tabs: any number of channel chats open at once over the one shared
+//! IRC connection, each a session with its own id and lifecycle.
+//!
+//! Opening a tab claims its channel for the window (`ChatService::start`), so
+//! the channel is JOINed once however many tabs show it, and closing the last
+//! tab on a channel PARTs it. Messages reach tabs in batches: one task reads
+//! the chat bridge, groups frames by channel and every `FLUSH_INTERVAL` emits
+//! `chat-tab-messages` per tab instead of one event per message, which keeps
+//! five busy chats from flooding the webview.
+//!
+//! A destroyed window's tabs are dropped with its claims (see main.rs).
+
+use crate::models::settings::AppState;
+use crate::services::chat_service::ChatService;
+use crate::services::event_bus;
+use crate::services::irc_service::IrcService;
+use anyhow::{anyhow, Result};
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+const FLUSH_INTERVAL: Duration = Duration::from_millis(150);
+/// Most frames one channel holds between flushes; older ones are dropped.
+const MAX_BATCH: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatTab {
+    pub id: String,
+    pub channel: String,
+    /// Label of the window showing the tab.
+    pub window: String,
+    /// Unix seconds.
+    pub opened_at: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct TabMessages<'a> {
+    tab_id: &'a str,
+    channel: &'a str,
+    /// Bridge frames, oldest first, exactly as the chat socket would carry them.
+    messages: &'a [String],
+}
+
+static TABS: Lazy<StdMutex<Vec<ChatTab>>> = Lazy::new(|| StdMutex::new(Vec::new()));
+static BATCHER: Lazy<StdMutex<Option<JoinHandle<()>>>> = Lazy::new(|| StdMutex::new(None));
+
+/// The channel a bridge frame belongs to: the `channel` of a serialized chat
+/// message, or the `#channel` of a raw IRC line.
+fn frame_channel(frame: &str) -> Option<String> {
+    if frame.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(frame).ok()?;
+        return value
+            .get("channel")
+            .and_then(|c| c.as_str())
+            .map(|c| c.trim_start_matches('#').to_lowercase());
+    }
+    crate::services::irc_service::extract_channel_from_irc_line(frame)
+}
+
+fn push_frame(batch: &mut Vec<String>, frame: String) {
+    if batch.len() >= MAX_BATCH {
+        batch.remove(0);
+    }
+    batch.push(frame);
+}
+
+fn flush(pending: &mut HashMap<String, Vec<String>>) {
+    if pending.is_empty() {
+        return;
+    }
+    let tabs = TABS.lock().unwrap().clone();
+    for (channel, messages) in pending.drain() {
+        for tab in tabs.iter().filter(|t| t.channel == channel) {
+            let _ = event_bus::emit(
+                "chat-tab-messages",
+                TabMessages {
+                    tab_id: &tab.id,
+                    channel: &channel,
+                    messages: &messages,
+                },
+            );
+        }
+    }
+}
+
+/// Start the batcher unless it's running. It stops by itself when the chat
+/// bridge goes down, and the next `open` starts it again.
+async fn ensure_batcher() {
+    let Some(tx) = IrcService::broadcaster().await else {
+        return;
+    };
+    // Check and spawn under one guard, so two tabs opening at once can't both
+    // see no batcher and start two (which would deliver every frame twice).
+    let mut batcher = BATCHER.lock().unwrap();
+    if batcher.as_ref().is_some_and(|h| !h.is_finished()) {
+        return;
+    }
+    let mut rx = tx.subscribe();
+    *batcher = Some(tokio::spawn(async move {
+        let mut pending: HashMap<String, Vec<String>> = HashMap::new();
+        let mut ticker = tokio::time::interval(FLUSH_INTERVAL);
+        loop {
+            tokio::select! {
+                frame = rx.recv() => match frame {
+                    Ok(frame) => {
+                        let Some(channel) = frame_channel(&frame) else {
+                            continue;
+                        };
+                        if TABS.lock().unwrap().iter().any(|t| t.channel == channel) {
+                            push_frame(pending.entry(channel).or_default(), frame);
+                        }
+                    }
+                    Err(RecvError::Lagged(n)) => {
+                        debug!("[ChatTabs] Batcher lagged by {} frames", n);
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = ticker.tick() => flush(&mut pending),
+            }
+        }
+        flush(&mut pending);
+        debug!("[ChatTabs] Chat bridge closed; batcher stopped");
+    }));
+}
+
+fn emit_changed() {
+    let _ = event_bus::emit("chat-tabs-changed", list());
+}
+
+/// Open `channel` as a tab in `window`. A window has one tab per channel, so
+/// opening one it already has returns that tab.
+pub async fn open(channel: &str, window: &str, state: &AppState) -> Result<ChatTab> {
+    let channel = channel.trim().trim_start_matches('#').to_lowercase();
+    if channel.is_empty() {
+        return Err(anyhow!("Channel is empty"));
+    }
+    if let Some(tab) = TABS
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|t| t.channel == channel && t.window == window)
+    {
+        return Ok(tab.clone());
+    }
+
+    ChatService::start(&channel, state, true, false, window).await?;
+    let tab = ChatTab {
+        id: uuid::Uuid::new_v4().to_string(),
+        channel,
+        window: window.to_string(),
+        opened_at: chrono::Utc::now().timestamp(),
+    };
+    TABS.lock().unwrap().push(tab.clone());
+    ensure_batcher().await;
+    emit_changed();
+    Ok(tab)
+}
+
+/// Close tab `id`, releasing its channel claim.
+pub async fn close(id: &str) -> Result<()> {
+    let tab = {
+        let mut tabs = TABS.lock().unwrap();
+        let index = tabs
+            .iter()
+            .position(|t| t.id == id)
+            .ok_or_else(|| anyhow!("No chat tab {}", id))?;
+        tabs.remove(index)
+    };
+    ChatService::leave_channel(&tab.channel, &tab.window).await?;
+    emit_changed();
+    Ok(())
+}
+
+/// Every open tab, in the order opened.
+pub fn list() -> Vec<ChatTab> {
+    TABS.lock().unwrap().clone()
+}
+
+/// Drop a destroyed window's tabs. Its claims are released separately.
+pub fn forget_window(window: &str) {
+    let removed = {
+        let mut tabs = TABS.lock().unwrap();
+        let before = tabs.len();
+        tabs.retain(|t| t.window != window);
+        before != tabs.len()
+    };
+    if removed {
+        emit_changed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_are_routed_by_channel() {
+        assert_eq!(
+            frame_channel(r#"{"id":"1","channel":"Alpha","content":"hi"}"#).as_deref(),
+            Some("alpha")
+        );
+        assert_eq!(
+            frame_channel("@ban-duration=60 :tmi.twitch.tv CLEARCHAT #beta :someone").as_deref(),
+            Some("beta")
+        );
+        assert_eq!(frame_channel("HEARTBEAT"), None);
+    }
+
+    #[test]
+    fn batches_keep_the_newest_frames() {
+        let mut batch = Vec::new();
+        for i in 0..MAX_BATCH + 2 {
+            push_frame(&mut batch, i.to_string());
+        }
+        assert_eq!(batch.len(), MAX_BATCH);
+        assert_eq!(batch[0], "2");
+    }
+}
@@ -0,0 +1,412 @@
+//! Who's in each joined chat. IRC feeds membership (JOIN / PART / NAMES,
+//! from the `twitch.tv/membership` capability) and message senders in as they
+//! arrive; `get_chatters` serves the roster from Helix when the user can read
+//! it (moderator or broadcaster) and from this store otherwise.
+//!
+//! Twitch only sends membership for channels under ~1000 chatters, so the IRC
+//! roster of a big channel is just the people who have talked.
+//!
+//! Users marked as friends (`Settings.chat_friends`) raise
+//! `friend-active-in-chat` {channel, login, display_name} on their first
+//! message in a channel since it was joined.
+
+use crate::services::event_bus;
+use crate::services::memory_caches::{self, CacheStat};
+use crate::services::twitch_service::TwitchService;
+use anyhow::Result;
+use chrono::Utc;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock as StdRwLock;
+use std::time::{Duration, Instant};
+
+/// How long a `get_chatters` result is served from cache.
+const SNAPSHOT_TTL: Duration = Duration::from_secs(30);
+/// Per channel, for each of the present / talked sets. A big chat left open
+/// for days would otherwise remember every chatter it ever saw.
+const MAX_CHATTERS_PER_CHANNEL: usize = 10_000;
+/// Channels whose last `get_chatters` result is kept (the baseline for the
+/// next diff).
+const MAX_SNAPSHOTS: usize = 50;
+
+#[derive(Default)]
+struct ChannelChatters {
+    /// Logins currently in chat, as far as IRC has told us.
+    present: HashSet<String>,
+    /// Logins that have sent a message since the channel was joined.
+    talked: HashSet<String>,
+}
+
+/// Per-channel state, keyed by lowercase login.
+static CHANNELS: Lazy<StdRwLock<HashMap<String, ChannelChatters>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+/// room-id -> channel login, learned from ROOMSTATE / PRIVMSG tags.
+static ROOM_LOGINS: Lazy<StdRwLock<HashMap<String, String>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+/// Friend logins, lowercase. Mirrors `Settings.chat_friends`.
+static FRIENDS: Lazy<StdRwLock<HashSet<String>>> = Lazy::new(|| StdRwLock::new(HashSet::new()));
+/// Last `get_chatters` result per channel id, for the TTL and the next diff.
+static SNAPSHOTS: Lazy<StdRwLock<HashMap<String, (Instant, ChattersSnapshot)>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChattersSnapshot {
+    pub channel_id: String,
+    pub channel_login: Option<String>,
+    /// `"helix"` (full roster) or `"irc"` (membership + recent talkers).
+    pub source: String,
+    /// Sorted logins.
+    pub chatters: Vec<String>,
+    pub total: usize,
+    /// Hit the Helix page cap; the roster is incomplete.
+    pub truncated: bool,
+    /// Since the previous snapshot of this channel (empty on the first).
+    pub joined: Vec<String>,
+    pub left: Vec<String>,
+    pub fetched_at: String,
+}
+
+/// Replace the friend list (from settings).
+pub fn set_friends(logins: &[String]) {
+    if let Ok(mut friends) = FRIENDS.write() {
+        *friends = logins.iter().map(|l| l.to_lowercase()).collect();
+    }
+}
+
+/// Remember which channel a room id belongs to.
+pub fn note_room(room_id: &str, channel: &str) {
+    if room_id.is_empty() || channel.is_empty() {
+        return;
+    }
+    let known = ROOM_LOGINS
+        .read()
+        .ok()
+        .is_some_and(|m| m.get(room_id).is_some_and(|c| c == channel));
+    if !known {
+        if let Ok(mut m) = ROOM_LOGINS.write() {
+            m.insert(room_id.to_string(), channel.to_lowercase());
+        }
+    }
+}
+
+pub fn record_join(channel: &str, login: &str) {
+    with_channel(channel, |c| {
+        c.present.insert(login.to_lowercase());
+    });
+}
+
+pub fn record_part(channel: &str, login: &str) {
+    with_channel(channel, |c| {
+        c.present.remove(&login.to_lowercase());
+    });
+}
+
+/// A NAMES (353) batch: everyone listed is present.
+pub fn record_names(channel: &str, logins: &[&str]) {
+    with_channel(channel, |c| {
+        c.present.extend(logins.iter().map(|l| l.to_lowercase()));
+    });
+}
+
+/// A message from `login` in `channel`. Returns true when it's their first
+/// message since the channel was joined.
+pub fn record_message(channel: &str, login: &str) -> bool {
+    let login = login.to_lowercase();
+    let mut first = false;
+    with_channel(channel, |c| {
+        c.present.insert(login.clone());
+        first = c.talked.insert(login);
+    });
+    first
+}
+
+/// Record a message and, if it's a friend's first in this channel, raise
+/// `friend-active-in-chat`.
+pub fn on_message(channel: &str, login: &str, display_name: &str) {
+    if !record_message(channel, login) {
+        return;
+    }
+    let is_friend = FRIENDS
+        .read()
+        .ok()
+        .is_some_and(|f| f.contains(&login.to_lowercase()));
+    if !is_friend {
+        return;
+    }
+    debug!("[Chatters] Friend {} active in #{}", login, channel);
+    let _ = event_bus::emit(
+        "friend-active-in-chat",
+        json!({
+            "channel": channel.trim_start_matches('#').to_lowercase(),
+            "login": login.to_lowercase(),
+            "display_name": display_name,
+        }),
+    );
+}
+
+/// Drop everything known about `channel` (we left it).
+pub fn forget_channel(channel: &str) {
+    if let Ok(mut channels) = CHANNELS.write() {
+        channels.remove(&channel.to_lowercase());
+    }
+}
+
+fn with_channel(channel: &str, f: impl FnOnce(&mut ChannelChatters)) {
+    let key = channel.trim_start_matches('#').to_lowercase();
+    if key.is_empty() {
+        return;
+    }
+    if let Ok(mut channels) = CHANNELS.write() {
+        let channel = channels.entry(key).or_default();
+        f(channel);
+        memory_caches::trim_set(
+            "chatters.present",
+            &mut channel.present,
+            MAX_CHATTERS_PER_CHANNEL,
+        );
+        memory_caches::trim_set(
+            "chatters.talked",
+            &mut channel.talked,
+            MAX_CHATTERS_PER_CHANNEL,
+        );
+    }
+}
+
+/// Sizes of the chat rosters, for `get_memory_cache_stats`.
+pub fn cache_stats() -> Vec<CacheStat> {
+    let (channels, present, talked) = CHANNELS
+        .read()
+        .map(|c| {
+            (
+                c.len(),
+                c.values().map(|c| c.present.len()).sum(),
+                c.values().map(|c| c.talked.len()).sum(),
+            )
+        })
+        .unwrap_or_default();
+    let snapshots = SNAPSHOTS.read().map(|s| s.len()).unwrap_or(0);
+    vec![
+        CacheStat::new(
+            "chatters.present",
+            present,
+            channels * MAX_CHATTERS_PER_CHANNEL,
+        ),
+        CacheStat::new(
+            "chatters.talked",
+            talked,
+            channels * MAX_CHATTERS_PER_CHANNEL,
+        ),
+        CacheStat::new("chatters.snapshots", snapshots, MAX_SNAPSHOTS),
+    ]
+}
+
+fn irc_roster(channel: &str) -> Vec<String> {
+    let mut roster: Vec<String> = CHANNELS
+        .read()
+        .ok()
+        .and_then(|c| c.get(channel).map(|c| c.present.iter().cloned().collect()))
+        .unwrap_or_default();
+    roster.sort();
+    roster
+}
+
+/// The chatters in `channel_id`, with joins/leaves since the previous call.
+/// Cached for 30s.
+pub async fn get_chatters(channel_id: &str) -> Result<ChattersSnapshot> {
+    if let Some((at, cached)) = SNAPSHOTS
+        .read()
+        .ok()
+        .and_then(|s| s.get(channel_id).cloned())
+    {
+        if at.elapsed() < SNAPSHOT_TTL {
+            return Ok(cached);
+        }
+    }
+
+    let channel_login = ROOM_LOGINS
+        .read()
+        .ok()
+        .and_then(|m| m.get(channel_id).cloned());
+
+    let (source, mut chatters, truncated) =
+        match TwitchService::fetch_chatters_roster(channel_id).await {
+            Ok((roster, truncated)) => {
+                let logins = roster
+                    .iter()
+                    .filter_map(|c| c["user_login"].as_str())
+                    .map(str::to_lowercase)
+                    .collect();
+                ("helix", logins, truncated)
+            }
+            Err(e) => {
+                debug!(
+                    "[Chatters] Helix roster unavailable for {} ({}); using IRC",
+                    channel_id, e
+                );
+                let login = channel_login.as_deref().ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Not in chat for channel {}; no roster available",
+                        channel_id
+                    )
+                })?;
+                ("irc", irc_roster(login), false)
+            }
+        };
+    chatters.sort();
+    chatters.dedup();
+
+    let previous = SNAPSHOTS
+        .read()
+        .ok()
+        .and_then(|s| s.get(channel_id).map(|(_, p)| p.chatters.clone()));
+    let (joined, left) = previous
+        .map(|p| diff_rosters(&p, &chatters))
+        .unwrap_or_default();
+
+    let snapshot = ChattersSnapshot {
+        channel_id: channel_id.to_string(),
+        channel_login,
+        source: source.to_string(),
+        total: chatters.len(),
+        chatters,
+        truncated,
+        joined,
+        left,
+        fetched_at: Utc::now().to_rfc3339(),
+    };
+    if let Ok(mut snapshots) = SNAPSHOTS.write() {
+        snapshots.insert(channel_id.to_string(), (Instant::now(), snapshot.clone()));
+        memory_caches::trim_map(
+            "chatters.snapshots",
+            &mut snapshots,
+            MAX_SNAPSHOTS,
+            |(at, _)| *at,
+        );
+    }
+    Ok(snapshot)
+}
+
+/// (joined, left) between two sorted rosters.
+fn diff_rosters(previous: &[String], current: &[String]) -> (Vec<String>, Vec<String>) {
+    let before: HashSet<&String> = previous.iter().collect();
+    let after: HashSet<&String> = current.iter().collect();
+    let joined = current
+        .iter()
+        .filter(|l| !before.contains(l))
+        .cloned()
+        .collect();
+    let left = previous
+        .iter()
+        .filter(|l| !after.contains(l))
+        .cloned()
+        .collect();
+    (joined, left)
+}
+
+/// A membership line from IRC, or `None` for anything else.
+#[derive(Debug, PartialEq)]
+pub enum Membership<'a> {
+    Join {
+        channel: &'a str,
+        login: &'a str,
+    },
+    Part {
+        channel: &'a str,
+        login: &'a str,
+    },
+    Names {
+        channel: &'a str,
+        logins: Vec<&'a str>,
+    },
+}
+
+/// Parse JOIN, PART and NAMES (353) lines, e.g.
+/// `:nick!nick@nick.tmi.twitch.tv JOIN #chan` or
+/// `:me.tmi.twitch.tv 353 me = #chan :a b c`.
+pub fn parse_membership(line: &str) -> Option<Membership<'_>> {
+    // Membership lines carry no tags, but be tolerant.
+    let line = match line.strip_prefix('@') {
+        Some(tagged) => tagged.split_once(' ')?.1,
+        None => line,
+    };
+    let mut parts = line.splitn(3, ' ');
+    let prefix = parts.next()?.strip_prefix(':')?;
+    let command = parts.next()?;
+    let params = parts.next()?;
+    let login = prefix.split('!').next()?;
+
+    match command {
+        "JOIN" | "PART" => {
+            let channel = params.trim().strip_prefix('#')?;
+            Some(if command == "JOIN" {
+                Membership::Join { channel, login }
+            } else {
+                Membership::Part { channel, login }
+            })
+        }
+        "353" => {
+            let (head, names) = params.split_once(" :")?;
+            let channel = head.split(' ').find_map(|p| p.strip_prefix('#'))?;
+            Some(Membership::Names {
+                channel,
+                logins: names.split_whitespace().collect(),
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_membership_lines() {
+        assert_eq!(
+            parse_membership(":alice!alice@alice.tmi.twitch.tv JOIN #chan"),
+            Some(Membership::Join {
+                channel: "chan",
+                login: "alice"
+            })
+        );
+        assert_eq!(
+            parse_membership(":bob!bob@bob.tmi.twitch.tv PART #chan"),
+            Some(Membership::Part {
+                channel: "chan",
+                login: "bob"
+            })
+        );
+        assert_eq!(
+            parse_membership(":me.tmi.twitch.tv 353 me = #chan :a b c"),
+            Some(Membership::Names {
+                channel: "chan",
+                logins: vec!["a", "b", "c"]
+            })
+        );
+        assert_eq!(
+            parse_membership(":x!x@x.tmi.twitch.tv PRIVMSG #chan :JOIN #other"),
+            None
+        );
+    }
+
+    #[test]
+    fn diffs_rosters() {
+        let before = vec!["a".to_string(), "b".into(), "c".into()];
+        let after = vec!["b".to_string(), "c".into(), "d".into()];
+        assert_eq!(
+            diff_rosters(&before, &after),
+            (vec!["d".to_string()], vec!["a".to_string()])
+        );
+    }
+
+    #[test]
+    fn first_message_is_reported_once_per_channel() {
+        assert!(record_message("tracker_test_chan", "Alice"));
+        assert!(!record_message("tracker_test_chan", "alice"));
+        assert!(record_message("tracker_test_other", "alice"));
+        assert!(irc_roster("tracker_test_chan").contains(&"alice".to_string()));
+    }
+}
@@ -0,0 +1,423 @@
+//! Migration from other Twitch clients (`import_from_client`): Chatterino's
+//! highlights and channel tabs, and Streamlink Twitch GUI's per-channel
+//! settings, read into what StreamNook keeps for the same things.
+//!
+//! - Chatterino (its `Settings` folder): highlight phrases and highlighted
+//!   users become `chat_highlights` phrases and users; each channel open in
+//!   a tab (`window-layout.json`) is pinned. Ignore phrases have no
+//!   counterpart here and are reported as skipped.
+//! - Streamlink Twitch GUI (its data folder): per-channel streaming quality
+//!   pins the channel at that quality. SLTG keeps them in the webview's
+//!   localStorage, a LevelDB; the record JSON is picked out of its files
+//!   rather than opening the database, so a record still in a compressed
+//!   block can be missed.
+//!
+//! `read` only looks; the command resolves channels and writes settings.
+
+use crate::models::settings::Settings;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// For highlights Chatterino saved without a colour: the highlight settings'
+/// own defaults.
+const DEFAULT_PHRASE_COLOR: &str = "#fbbf24";
+const DEFAULT_USER_COLOR: &str = "#22d3ee";
+/// How deep to look for SLTG's LevelDB under the folder given.
+const SLTG_SEARCH_DEPTH: usize = 5;
+const SLTG_RECORDS_KEY: &str = r#"{"channelsettings":{"records":"#;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportSource {
+    Chatterino,
+    StreamlinkTwitchGui,
+}
+
+/// A channel to pin, by login or, from SLTG, by Twitch user id.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ImportedChannel {
+    pub login: Option<String>,
+    pub user_id: Option<String>,
+    pub quality: Option<String>,
+}
+
+/// What an import adds. New entries only: what StreamNook already has is
+/// left out.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientImport {
+    pub source: ImportSource,
+    /// In `chat_highlights.phrases` form.
+    pub highlight_phrases: Vec<Value>,
+    /// In `chat_highlights.users` form.
+    pub highlight_users: Vec<Value>,
+    pub channels: Vec<ImportedChannel>,
+    /// What couldn't be carried over, and why.
+    pub skipped: Vec<String>,
+}
+
+impl ClientImport {
+    fn new(source: ImportSource) -> Self {
+        Self {
+            source,
+            highlight_phrases: Vec::new(),
+            highlight_users: Vec::new(),
+            channels: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Couldn't read {}: {}", path.display(), e))?;
+    Ok(serde_json::from_str(&text)?)
+}
+
+fn existing_highlights<'a>(settings: &'a Settings, list: &str) -> &'a [Value] {
+    settings
+        .extra
+        .get("chat_highlights")
+        .and_then(|h| h.get(list))
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// Qt writes colours as `#AARRGGBB`; the highlight settings take `#RRGGBB`.
+fn rgb(color: Option<&str>) -> Option<String> {
+    let hex = color?.strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let rgb = match hex.len() {
+        8 => &hex[2..],
+        6 => hex,
+        _ => return None,
+    };
+    Some(format!("#{}", rgb.to_lowercase()))
+}
+
+/// Twitch channels open in Chatterino's tabs and splits, in order.
+fn layout_channels(layout: &Value, channels: &mut Vec<String>) {
+    match layout {
+        Value::Object(fields) => {
+            if fields.get("type").and_then(Value::as_str) == Some("twitch") {
+                if let Some(name) = fields.get("name").and_then(Value::as_str) {
+                    let login = name.trim().to_lowercase();
+                    if !login.is_empty() && !channels.contains(&login) {
+                        channels.push(login);
+                    }
+                }
+            }
+            fields.values().for_each(|v| layout_channels(v, channels));
+        }
+        Value::Array(items) => items.iter().for_each(|v| layout_channels(v, channels)),
+        _ => {}
+    }
+}
+
+fn chatterino(dir: &Path, settings: &Settings, import: &mut ClientImport) -> Result<()> {
+    let settings_path = dir.join("settings.json");
+    let layout_path = dir.join("window-layout.json");
+    if !settings_path.is_file() && !layout_path.is_file() {
+        return Err(anyhow!(
+            "No Chatterino settings.json or window-layout.json in {}",
+            dir.display()
+        ));
+    }
+
+    if settings_path.is_file() {
+        let chatterino = read_json(&settings_path)?;
+        let highlighting = &chatterino["highlighting"];
+
+        let phrases = existing_highlights(settings, "phrases");
+        for highlight in highlighting["highlights"].as_array().into_iter().flatten() {
+            let Some(pattern) = highlight["pattern"].as_str().filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            let known = phrases
+                .iter()
+                .chain(&import.highlight_phrases)
+                .any(|p| p["pattern"].as_str() == Some(pattern));
+            if known {
+                continue;
+            }
+            import.highlight_phrases.push(json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "pattern": pattern,
+                "enabled": true,
+                "case_sensitive": highlight["case"].as_bool().unwrap_or(false),
+                "whole_word": false,
+                "is_regex": highlight["regex"].as_bool().unwrap_or(false),
+                "color": rgb(highlight["color"].as_str())
+                    .unwrap_or_else(|| DEFAULT_PHRASE_COLOR.to_string()),
+            }));
+        }
+
+        let users = existing_highlights(settings, "users");
+        for user in highlighting["users"].as_array().into_iter().flatten() {
+            let Some(pattern) = user["pattern"].as_str().filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            if user["regex"].as_bool().unwrap_or(false) {
+                import.skipped.push(format!(
+                    "Highlighted users by pattern ({}): only exact names carry over",
+                    pattern
+                ));
+                continue;
+            }
+            let username = pattern.trim().to_lowercase();
+            let known = users
+                .iter()
+                .chain(&import.highlight_users)
+                .any(|u| u["username"].as_str() == Some(username.as_str()));
+            if known {
+                continue;
+            }
+            import.highlight_users.push(json!({
+                "id": uuid::Uuid::new_v4().to_string(),
+                "enabled": true,
+                "username": username,
+                "color": rgb(user["color"].as_str())
+                    .unwrap_or_else(|| DEFAULT_USER_COLOR.to_string()),
+            }));
+        }
+
+        let ignores = chatterino["ignore"]["phrases"]
+            .as_array()
+            .map_or(0, Vec::len);
+        if ignores > 0 {
+            import.skipped.push(format!(
+                "{} ignore phrase(s): StreamNook has no message filter for them",
+                ignores
+            ));
+        }
+    }
+
+    if layout_path.is_file() {
+        let mut logins = Vec::new();
+        layout_channels(&read_json(&layout_path)?, &mut logins);
+        import.channels.extend(
+            logins
+                .into_iter()
+                .filter(|login| !settings.pinned_channels.iter().any(|c| &c.login == login))
+                .map(|login| ImportedChannel {
+                    login: Some(login),
+                    ..Default::default()
+                }),
+        );
+    }
+    Ok(())
+}
+
+/// SLTG's quality presets as Streamlink quality names.
+fn sltg_quality(preset: &Value) -> Option<&'static str> {
+    let preset = match preset {
+        Value::Number(n) => match n.as_u64()? {
+            0 => "source",
+            1 => "high",
+            2 => "medium",
+            3 => "low",
+            4 => "mobile",
+            5 => "audio",
+            _ => return None,
+        },
+        Value::String(s) => s.as_str(),
+        _ => return None,
+    };
+    Some(match preset {
+        "source" => "best",
+        "high" => "720p60",
+        "medium" => "480p",
+        "low" => "360p",
+        "mobile" => "160p",
+        "audio" => "audio_only",
+        _ => return None,
+    })
+}
+
+fn leveldb_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.is_dir() {
+            if depth > 0 {
+                leveldb_files(&path, depth - 1, files);
+            }
+        } else if path.extension().is_some_and(|e| e == "log" || e == "ldb") {
+            files.push(path);
+        }
+    }
+}
+
+/// Every `channelsettings` records object in `text`, in order.
+fn sltg_records(text: &str) -> Vec<serde_json::Map<String, Value>> {
+    text.match_indices(SLTG_RECORDS_KEY)
+        .filter_map(|(at, key)| {
+            let rest = &text[at + key.len()..];
+            serde_json::Deserializer::from_str(rest)
+                .into_iter::<Value>()
+                .next()?
+                .ok()?
+                .as_object()
+                .cloned()
+        })
+        .collect()
+}
+
+/// localStorage values are Latin-1 or UTF-16, the latter at either byte
+/// alignment within the file; look in every reading.
+fn decodings(bytes: &[u8]) -> Vec<String> {
+    let utf16 = |bytes: &[u8]| {
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16_lossy(&units)
+    };
+    vec![
+        String::from_utf8_lossy(bytes).into_owned(),
+        utf16(bytes),
+        utf16(bytes.get(1..).unwrap_or_default()),
+    ]
+}
+
+fn streamlink_twitch_gui(dir: &Path, settings: &Settings, import: &mut ClientImport) -> Result<()> {
+    let mut files = Vec::new();
+    leveldb_files(dir, SLTG_SEARCH_DEPTH, &mut files);
+    if files.is_empty() {
+        return Err(anyhow!(
+            "No Streamlink Twitch GUI data (LevelDB files) under {}",
+            dir.display()
+        ));
+    }
+    // Log files are appended to, so sort by age and let later records win.
+    files.sort_by_key(|f| f.metadata().and_then(|m| m.modified()).ok());
+
+    let mut records = serde_json::Map::new();
+    for file in &files {
+        let Ok(bytes) = std::fs::read(file) else {
+            continue;
+        };
+        for text in decodings(&bytes) {
+            for found in sltg_records(&text) {
+                records.extend(found);
+            }
+        }
+    }
+    if records.is_empty() {
+        import
+            .skipped
+            .push("No per-channel settings found in Streamlink Twitch GUI's data".to_string());
+        return Ok(());
+    }
+
+    for (id, record) in records {
+        let Some(quality) = sltg_quality(&record["streaming_quality"]) else {
+            continue;
+        };
+        // Records are keyed by Twitch user id; old versions used the login.
+        let (login, user_id) = if id.bytes().all(|b| b.is_ascii_digit()) {
+            (None, Some(id))
+        } else {
+            (Some(id.to_lowercase()), None)
+        };
+        let pinned = settings
+            .pinned_channels
+            .iter()
+            .find(|c| Some(&c.user_id) == user_id.as_ref() || Some(&c.login) == login.as_ref());
+        if pinned.is_some_and(|c| c.quality.as_deref() == Some(quality)) {
+            continue;
+        }
+        import.channels.push(ImportedChannel {
+            login,
+            user_id,
+            quality: Some(quality.to_string()),
+        });
+    }
+    Ok(())
+}
+
+/// What importing `source` from `dir` would add to `settings`.
+pub fn read(source: ImportSource, dir: &Path, settings: &Settings) -> Result<ClientImport> {
+    let mut import = ClientImport::new(source);
+    match source {
+        ImportSource::Chatterino => chatterino(dir, settings, &mut import)?,
+        ImportSource::StreamlinkTwitchGui => streamlink_twitch_gui(dir, settings, &mut import)?,
+    }
+    Ok(import)
+}
+
+/// Add the import's highlights to `settings.chat_highlights`.
+pub fn apply_highlights(settings: &mut Settings, import: &ClientImport) {
+    if import.highlight_phrases.is_empty() && import.highlight_users.is_empty() {
+        return;
+    }
+    let highlights = settings
+        .extra
+        .entry("chat_highlights".to_string())
+        .or_insert_with(|| json!({ "phrases": [] }));
+    if !highlights.is_object() {
+        *highlights = json!({ "phrases": [] });
+    }
+    for (list, entries) in [
+        ("phrases", &import.highlight_phrases),
+        ("users", &import.highlight_users),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        let target = &mut highlights[list];
+        if !target.is_array() {
+            *target = json!([]);
+        }
+        if let Some(target) = target.as_array_mut() {
+            target.extend(entries.iter().cloned());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chatterino_layout_and_colors() {
+        let layout = json!({ "windows": [{ "tabs": [
+            { "splits2": { "type": "split", "data": { "type": "twitch", "name": "Forsen" } } },
+            { "splits2": { "type": "horizontal", "items": [
+                { "type": "split", "data": { "type": "twitch", "name": "xqc" } },
+                { "type": "split", "data": { "type": "mentions" } },
+                { "type": "split", "data": { "type": "twitch", "name": "forsen" } },
+            ] } },
+        ] }] });
+        let mut channels = Vec::new();
+        layout_channels(&layout, &mut channels);
+        assert_eq!(channels, ["forsen", "xqc"]);
+
+        assert_eq!(rgb(Some("#7f7f3f49")).as_deref(), Some("#7f3f49"));
+        assert_eq!(rgb(Some("#A0B0C0")).as_deref(), Some("#a0b0c0"));
+        assert_eq!(rgb(Some("red")), None);
+        assert_eq!(rgb(Some("#1é23456")), None);
+    }
+
+    #[test]
+    fn sltg_records_are_found_in_either_encoding() {
+        let blob = r#"{"channelsettings":{"records":{"12345":{"id":"12345","streaming_quality":"high"}}}}"#;
+        let mut log = b"\x01junk_channelsettings".to_vec();
+        log.extend(blob.encode_utf16().flat_map(|c| c.to_le_bytes()));
+        let found: Vec<_> = decodings(&log)
+            .iter()
+            .flat_map(|text| sltg_records(text))
+            .collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            sltg_quality(&found[0]["12345"]["streaming_quality"]),
+            Some("720p60")
+        );
+        assert_eq!(sltg_quality(&json!(0)), Some("best"));
+    }
+}
@@ -0,0 +1,159 @@
+//! One sign-in sequence for both Twitch tokens (`start_combined_login`).
+//!
+//! The site token and the drops token can't be shared: they belong to
+//! different client ids (the app's own and the Android app's), and Twitch
+//! binds a token to the client that asked for it. So the combined login runs
+//! the two device flows back to back, skipping any that already has a working
+//! token, and reports each step as `combined-login-progress`
+//! {step, state, user_code, verification_uri, error}. The site step also
+//! sends the usual `twitch-login-*` events.
+//!
+//! `cancel_login` stops the whole sequence (`combined-login-cancelled`).
+
+use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::event_bus;
+use crate::services::twitch_service::TwitchService;
+use log::{debug, warn};
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginStep {
+    /// The site token: chat, follows, playback.
+    Main,
+    /// The Android-client token drops mining needs.
+    Drops,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginStepState {
+    /// Show `user_code` and open `verification_uri`.
+    AwaitingCode,
+    Complete,
+    /// Already signed in for this step.
+    Skipped,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct CombinedLoginProgress {
+    step: LoginStep,
+    state: LoginStepState,
+    user_code: Option<String>,
+    verification_uri: Option<String>,
+    error: Option<String>,
+}
+
+static COMBINED_TASK: StdMutex<Option<tokio::task::AbortHandle>> = StdMutex::new(None);
+
+fn progress(step: LoginStep, state: LoginStepState) -> CombinedLoginProgress {
+    CombinedLoginProgress {
+        step,
+        state,
+        user_code: None,
+        verification_uri: None,
+        error: None,
+    }
+}
+
+fn emit(progress: CombinedLoginProgress) {
+    let _ = event_bus::emit("combined-login-progress", progress);
+}
+
+fn emit_failed(step: LoginStep, error: String) {
+    warn!("[CombinedLogin] {:?} step failed: {}", step, error);
+    emit(CombinedLoginProgress {
+        error: Some(error),
+        ..progress(step, LoginStepState::Failed)
+    });
+}
+
+/// Sign in for the site token unless there's already one. False when the
+/// sequence should stop.
+async fn main_step(app_handle: tauri::AppHandle) -> bool {
+    if TwitchService::get_token().await.is_ok() {
+        emit(progress(LoginStep::Main, LoginStepState::Skipped));
+        return true;
+    }
+    let (verification_uri, user_code, poller) = match TwitchService::start_login(app_handle).await {
+        Ok(started) => started,
+        Err(e) => {
+            emit_failed(LoginStep::Main, e.to_string());
+            return false;
+        }
+    };
+    emit(CombinedLoginProgress {
+        user_code: Some(user_code),
+        verification_uri: Some(verification_uri),
+        ..progress(LoginStep::Main, LoginStepState::AwaitingCode)
+    });
+    match poller.await {
+        Ok(true) => {
+            emit(progress(LoginStep::Main, LoginStepState::Complete));
+            true
+        }
+        // The poller already sent `twitch-login-error` with the reason.
+        Ok(false) => {
+            emit_failed(LoginStep::Main, "Twitch sign-in failed".to_string());
+            false
+        }
+        Err(_) => false,
+    }
+}
+
+async fn drops_step(app_handle: &tauri::AppHandle) {
+    if DropsAuthService::is_authenticated().await {
+        emit(progress(LoginStep::Drops, LoginStepState::Skipped));
+        return;
+    }
+    let device = match DropsAuthService::start_device_flow().await {
+        Ok(device) => device,
+        Err(e) => return emit_failed(LoginStep::Drops, e.to_string()),
+    };
+    emit(CombinedLoginProgress {
+        user_code: Some(device.user_code.clone()),
+        verification_uri: Some(device.verification_uri.clone()),
+        ..progress(LoginStep::Drops, LoginStepState::AwaitingCode)
+    });
+    match DropsAuthService::poll_for_token(&device.device_code, device.interval, device.expires_in)
+        .await
+    {
+        Ok(_) => {
+            crate::commands::twitch::dismiss_login_overlay(app_handle, "drops-login");
+            emit(progress(LoginStep::Drops, LoginStepState::Complete));
+        }
+        Err(e) => emit_failed(LoginStep::Drops, e.to_string()),
+    }
+}
+
+/// Start the sequence in the background, replacing one already running.
+pub fn start(app_handle: tauri::AppHandle) {
+    let task = tokio::spawn(async move {
+        debug!("[CombinedLogin] Starting");
+        if main_step(app_handle.clone()).await {
+            drops_step(&app_handle).await;
+        }
+    });
+    if let Some(previous) = COMBINED_TASK.lock().unwrap().replace(task.abort_handle()) {
+        previous.abort();
+    }
+}
+
+/// Stop a running sequence and close the drops sign-in overlay. Returns
+/// false when none was running. The site step's poller is stopped
+/// separately, by `TwitchService::cancel_login`.
+pub fn cancel(app_handle: &tauri::AppHandle) -> bool {
+    let Some(task) = COMBINED_TASK.lock().unwrap().take() else {
+        return false;
+    };
+    if task.is_finished() {
+        return false;
+    }
+    task.abort();
+    debug!("[CombinedLogin] Cancelled");
+    crate::commands::twitch::dismiss_login_overlay(app_handle, "drops-login");
+    let _ = event_bus::emit("combined-login-cancelled", ());
+    true
+}
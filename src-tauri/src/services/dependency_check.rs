@@ -0,0 +1,271 @@
+//! The dependency doctor (`run_dependency_check`): everything outside the app
+//! itself that playback, capture and storage lean on, each with what to do
+//! when it's missing and, where the app can do it, a fix to offer
+//! (`fix_dependency`).
+//!
+//! Streamlink, its ttvlol plugin and an external player used to be on this
+//! list. Live playback now resolves natively and plays in the built-in
+//! player, so they're reported as not needed; the only trace left is the
+//! old bundled `streamlink/` folder, which is offered for removal if the
+//! startup cleanup couldn't delete it.
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    /// Missing or broken, but only an optional feature needs it.
+    Warning,
+    /// Missing or broken, and the app can't work properly without it.
+    Error,
+    NotNeeded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub version: Option<String>,
+    pub detail: String,
+    /// What the user can do about it.
+    pub suggestion: Option<String>,
+    /// Pass to `fix_dependency` to have the app fix it.
+    pub fix_id: Option<&'static str>,
+}
+
+fn check(
+    id: &'static str,
+    name: &'static str,
+    status: CheckStatus,
+    detail: &str,
+) -> DependencyCheck {
+    DependencyCheck {
+        id,
+        name,
+        status,
+        version: None,
+        detail: detail.to_string(),
+        suggestion: None,
+        fix_id: None,
+    }
+}
+
+fn legacy_streamlink_dir() -> Option<PathBuf> {
+    let dir = std::env::current_exe().ok()?.parent()?.join("streamlink");
+    dir.exists().then_some(dir)
+}
+
+fn check_streamlink() -> DependencyCheck {
+    let mut result = check(
+        "streamlink",
+        "Streamlink",
+        CheckStatus::NotNeeded,
+        "Live playback resolves natively; Streamlink isn't used.",
+    );
+    if let Some(dir) = legacy_streamlink_dir() {
+        result.status = CheckStatus::Warning;
+        result.detail = format!(
+            "An old bundled Streamlink folder is still at {} and takes up space.",
+            dir.display()
+        );
+        result.suggestion = Some("Remove the folder; nothing uses it any more.".to_string());
+        result.fix_id = Some("remove_legacy_streamlink");
+    }
+    result
+}
+
+/// `ffmpeg -version`'s version word, e.g. `7.1`.
+async fn ffmpeg_version(ffmpeg: &Path) -> Option<String> {
+    let mut command = tokio::process::Command::new(ffmpeg);
+    command
+        .arg("-version")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    #[cfg(windows)]
+    {
+        command.creation_flags(0x0800_0000);
+    }
+    let output = tokio::time::timeout(Duration::from_secs(5), command.output())
+        .await
+        .ok()?
+        .ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(2)
+        .map(str::to_string)
+}
+
+async fn check_ffmpeg() -> DependencyCheck {
+    let Some(ffmpeg) = crate::services::frame_capture::ffmpeg_path() else {
+        let mut result = check(
+            "ffmpeg",
+            "ffmpeg",
+            CheckStatus::Warning,
+            "ffmpeg wasn't found on PATH. Stream frame captures and recordings need it.",
+        );
+        result.suggestion = Some(
+            "Install ffmpeg (ffmpeg.org, or winget install ffmpeg / brew install ffmpeg) \
+             and make sure it's on PATH, then restart StreamNook."
+                .to_string(),
+        );
+        return result;
+    };
+    let version = ffmpeg_version(&ffmpeg).await;
+    let mut result = check(
+        "ffmpeg",
+        "ffmpeg",
+        if version.is_some() {
+            CheckStatus::Ok
+        } else {
+            CheckStatus::Warning
+        },
+        &format!("Found at {}", ffmpeg.display()),
+    );
+    if version.is_none() {
+        result.detail = format!("{} is there but didn't run", ffmpeg.display());
+        result.suggestion = Some("Reinstall ffmpeg.".to_string());
+    }
+    result.version = version;
+    result
+}
+
+fn check_webview() -> DependencyCheck {
+    match tauri::webview_version() {
+        Ok(version) => {
+            let mut result = check(
+                "webview",
+                "Webview runtime",
+                CheckStatus::Ok,
+                "The interface and player run in it.",
+            );
+            result.version = Some(version);
+            result
+        }
+        Err(e) => {
+            let mut result = check(
+                "webview",
+                "Webview runtime",
+                CheckStatus::Error,
+                &format!("Couldn't read its version: {}", e),
+            );
+            result.suggestion = Some(if cfg!(windows) {
+                "Install or repair the Microsoft Edge WebView2 Runtime \
+                 (developer.microsoft.com/microsoft-edge/webview2)."
+                    .to_string()
+            } else {
+                "Install your distribution's WebKitGTK package.".to_string()
+            });
+            result
+        }
+    }
+}
+
+/// Write, read back and delete a probe file in the app data dir, creating
+/// the dir first if it has gone missing.
+fn probe_data_dir(dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let probe = dir.join(".write_probe");
+    std::fs::write(&probe, b"ok")?;
+    let read = std::fs::read(&probe)?;
+    std::fs::remove_file(&probe)?;
+    if read != b"ok" {
+        bail!("the probe file read back differently");
+    }
+    Ok(())
+}
+
+fn check_data_dir() -> DependencyCheck {
+    let dir = match crate::services::cache_service::get_app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            let mut result = check(
+                "data_dir",
+                "Data folder",
+                CheckStatus::Error,
+                &format!("Couldn't find it: {}", e),
+            );
+            result.suggestion = Some("Check your user profile folder's permissions.".to_string());
+            return result;
+        }
+    };
+    match probe_data_dir(&dir) {
+        Ok(()) => check(
+            "data_dir",
+            "Data folder",
+            CheckStatus::Ok,
+            &format!("{} is writable", dir.display()),
+        ),
+        Err(e) => {
+            let mut result = check(
+                "data_dir",
+                "Data folder",
+                CheckStatus::Error,
+                &format!(
+                    "{} isn't writable ({}); settings and caches can't be saved.",
+                    dir.display(),
+                    e
+                ),
+            );
+            result.suggestion = Some(
+                "Make sure the folder exists and isn't read-only or blocked by antivirus."
+                    .to_string(),
+            );
+            result.fix_id = Some("create_data_dir");
+            result
+        }
+    }
+}
+
+/// Every check, in the order the doctor lists them.
+pub async fn run() -> Vec<DependencyCheck> {
+    vec![
+        check_streamlink(),
+        check(
+            "ttvlol",
+            "ttvlol plugin",
+            CheckStatus::NotNeeded,
+            "Ad handling is up to playback plugins; no Streamlink plugin is used.",
+        ),
+        check(
+            "player",
+            "Video player",
+            CheckStatus::NotNeeded,
+            "Streams play in the built-in player; no external player is used.",
+        ),
+        check_ffmpeg().await,
+        check_webview(),
+        check_data_dir(),
+    ]
+}
+
+/// Apply the fix a check offered, then report that check again.
+pub async fn fix(fix_id: &str) -> Result<DependencyCheck> {
+    match fix_id {
+        "remove_legacy_streamlink" => {
+            if let Some(dir) = legacy_streamlink_dir() {
+                let target = dir.clone();
+                tokio::task::spawn_blocking(move || std::fs::remove_dir_all(target))
+                    .await?
+                    .map_err(|e| anyhow!("Couldn't remove {}: {}", dir.display(), e))?;
+            }
+            Ok(check_streamlink())
+        }
+        "create_data_dir" => {
+            let dir = crate::services::cache_service::get_app_data_dir()
+                .map_err(|e| anyhow!("Couldn't create the data folder: {:#}", e))?;
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| anyhow!("Couldn't create {}: {}", dir.display(), e))?;
+            probe_data_dir(&dir)
+                .map_err(|e| anyhow!("{} still isn't writable: {}", dir.display(), e))?;
+            Ok(check_data_dir())
+        }
+        other => bail!("Unknown fix '{}'", other),
+    }
+}
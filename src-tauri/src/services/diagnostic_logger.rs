@@ -28,9 +28,24 @@ pub fn init_logging() {
     } else {
         builder.filter_level(LevelFilter::Info);
     }
+    // Lines logged inside a `trace_ids::scope` carry its id after the module
+    // path, so one mining session or stream can be grepped out of the log.
     builder
-        .format_timestamp_millis()
-        .format_module_path(true)
+        .format(|buf, record| {
+            use std::io::Write;
+            let trace = crate::services::trace_ids::current()
+                .map(|id| format!(" {}", id))
+                .unwrap_or_default();
+            writeln!(
+                buf,
+                "[{} {:<5} {}{}] {}",
+                buf.timestamp_millis(),
+                record.level(),
+                record.module_path().unwrap_or(""),
+                trace,
+                record.args()
+            )
+        })
         .init();
 
     println!("[DiagnosticLogger] Logging system initialized (terminal: info; file capture: full)");
@@ -0,0 +1,246 @@
+//! One queue for every emote / badge / reward image download, so prefetching
+//! can't saturate the connection a stream is playing over. Callers used to
+//! fire their downloads ad hoc (16 at once for the AFK emote prefetch, 4 for
+//! reward art, one per frontend cache miss); they now submit here and the pool
+//! runs at most `prefetch_concurrency` at a time.
+//!
+//! Jobs run visible-first: `Visible` (on screen right now), then `Normal`,
+//! then `Background` (warm-up nobody is waiting on). While a live stream plays
+//! background jobs are held to `PLAYBACK_BACKGROUND_SLOTS` so the video keeps
+//! its bandwidth. `pause` stops background jobs (prefetch and warm-up) from
+//! starting; in-flight ones finish, and visible and normal jobs still run, so
+//! a paused prefetch never leaves an image on screen blank.
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::Mutex as StdMutex;
+use tokio::sync::oneshot;
+
+pub const DEFAULT_CONCURRENCY: usize = 6;
+/// Background jobs allowed in flight while a live stream plays.
+const PLAYBACK_BACKGROUND_SLOTS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    Visible = 0,
+    Normal = 1,
+    Background = 2,
+}
+
+impl Priority {
+    /// Unknown or missing values are `Normal`.
+    pub fn parse(value: Option<&str>) -> Self {
+        match value {
+            Some("visible") => Self::Visible,
+            Some("background") => Self::Background,
+            _ => Self::Normal,
+        }
+    }
+}
+
+struct Job {
+    group: &'static str,
+    run: Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>,
+}
+
+struct Pool {
+    /// Indexed by `Priority`.
+    queues: [VecDeque<Job>; 3],
+    in_flight: [usize; 3],
+    concurrency: usize,
+    paused: bool,
+    completed: u64,
+}
+
+impl Pool {
+    fn new() -> Self {
+        Pool {
+            queues: Default::default(),
+            in_flight: [0; 3],
+            concurrency: DEFAULT_CONCURRENCY,
+            paused: false,
+            completed: 0,
+        }
+    }
+}
+
+static POOL: Lazy<StdMutex<Pool>> = Lazy::new(|| StdMutex::new(Pool::new()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueStatus {
+    pub paused: bool,
+    pub concurrency: usize,
+    /// True while a live stream holds background jobs back.
+    pub throttled_for_playback: bool,
+    pub in_flight: usize,
+    pub queued_visible: usize,
+    pub queued_normal: usize,
+    pub queued_background: usize,
+    /// Queued jobs per submitter (`emote_prefetch`, `drop_rewards`, ...).
+    pub queued_by_group: HashMap<&'static str, usize>,
+    pub completed: u64,
+}
+
+/// Queue `task` and wait for it to run. Errors only if the job was cancelled
+/// before it started.
+pub async fn run<T, F>(priority: Priority, group: &'static str, task: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let (tx, rx) = oneshot::channel();
+    let job = Job {
+        group,
+        run: Box::new(move || {
+            async move {
+                let _ = tx.send(task.await);
+            }
+            .boxed()
+        }),
+    };
+    POOL.lock().unwrap().queues[priority as usize].push_back(job);
+    pump();
+    rx.await
+        .map_err(|_| anyhow!("Download cancelled before it started"))
+}
+
+/// Start as many queued jobs as the limits allow.
+fn pump() {
+    let playing = crate::services::stream_server::solo_session_active();
+    let mut pool = POOL.lock().unwrap();
+    while pool.in_flight.iter().sum::<usize>() < pool.concurrency {
+        let background = Priority::Background as usize;
+        let next = (0..3).find(|&p| {
+            !pool.queues[p].is_empty()
+                && !(p == background
+                    && (pool.paused || (playing && pool.in_flight[p] >= PLAYBACK_BACKGROUND_SLOTS)))
+        });
+        let Some(p) = next else {
+            break;
+        };
+        let job = pool.queues[p].pop_front().unwrap();
+        pool.in_flight[p] += 1;
+        tauri::async_runtime::spawn(async move {
+            (job.run)().await;
+            {
+                let mut pool = POOL.lock().unwrap();
+                pool.in_flight[p] -= 1;
+                pool.completed += 1;
+            }
+            pump();
+        });
+    }
+}
+
+pub fn set_concurrency(concurrency: usize) {
+    POOL.lock().unwrap().concurrency = concurrency.clamp(1, 32);
+    pump();
+}
+
+/// Hold queued background jobs until `resume`.
+pub fn pause() {
+    debug!("[DownloadPool] Paused");
+    POOL.lock().unwrap().paused = true;
+}
+
+pub fn resume() {
+    debug!("[DownloadPool] Resumed");
+    POOL.lock().unwrap().paused = false;
+    pump();
+}
+
+/// Drop `group`'s queued jobs; their callers get a cancellation error.
+/// In-flight ones finish.
+pub fn cancel_group(group: &str) {
+    let mut pool = POOL.lock().unwrap();
+    for queue in pool.queues.iter_mut() {
+        queue.retain(|job| job.group != group);
+    }
+}
+
+pub fn status() -> QueueStatus {
+    let playing = crate::services::stream_server::solo_session_active();
+    let pool = POOL.lock().unwrap();
+    let mut queued_by_group: HashMap<&'static str, usize> = HashMap::new();
+    for job in pool.queues.iter().flatten() {
+        *queued_by_group.entry(job.group).or_default() += 1;
+    }
+    QueueStatus {
+        paused: pool.paused,
+        concurrency: pool.concurrency,
+        throttled_for_playback: playing && !pool.queues[Priority::Background as usize].is_empty(),
+        in_flight: pool.in_flight.iter().sum(),
+        queued_visible: pool.queues[Priority::Visible as usize].len(),
+        queued_normal: pool.queues[Priority::Normal as usize].len(),
+        queued_background: pool.queues[Priority::Background as usize].len(),
+        queued_by_group,
+        completed: pool.completed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The pool is global: tests take turns and start from a fresh one.
+    static SERIAL: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+
+    fn reset() {
+        *POOL.lock().unwrap() = Pool::new();
+    }
+
+    #[tokio::test]
+    async fn visible_jobs_start_before_queued_background_ones() {
+        let _serial = SERIAL.lock().await;
+        reset();
+        set_concurrency(1);
+        // Holds the only slot until both jobs below are queued.
+        let (release, blocker) = oneshot::channel::<()>();
+        let blocking = tokio::spawn(run(Priority::Normal, "test", async move {
+            let _ = blocker.await;
+        }));
+        tokio::task::yield_now().await;
+
+        let order = std::sync::Arc::new(StdMutex::new(Vec::new()));
+        let submit = |priority, label: &'static str| {
+            let order = order.clone();
+            tokio::spawn(run(priority, "test", async move {
+                order.lock().unwrap().push(label);
+            }))
+        };
+        let background = submit(Priority::Background, "background");
+        tokio::task::yield_now().await;
+        let visible = submit(Priority::Visible, "visible");
+        tokio::task::yield_now().await;
+        assert_eq!(status().queued_by_group.get("test"), Some(&2));
+
+        release.send(()).unwrap();
+        blocking.await.unwrap().unwrap();
+        background.await.unwrap().unwrap();
+        visible.await.unwrap().unwrap();
+        assert_eq!(*order.lock().unwrap(), ["visible", "background"]);
+        reset();
+    }
+
+    #[tokio::test]
+    async fn pause_holds_only_background_jobs() {
+        let _serial = SERIAL.lock().await;
+        reset();
+        pause();
+        let background = tokio::spawn(run(Priority::Background, "test", async {}));
+        tokio::task::yield_now().await;
+        run(Priority::Visible, "test", async {}).await.unwrap();
+        assert_eq!(status().queued_background, 1);
+
+        resume();
+        background.await.unwrap().unwrap();
+        assert!(!status().paused);
+        reset();
+    }
+}
@@ -448,4 +448,39 @@ impl DropsAuthService {
 
         Ok(response.status().is_success())
     }
+
+    /// Sign in for drops with a token another app got (TwitchDropsMiner
+    /// logs in as the Android app too). Kept only when Twitch says it's live
+    /// and issued to the same client; returns the account's login.
+    pub async fn import_token(access_token: &str) -> Result<String> {
+        let response = HTTP_CLIENT
+            .get("https://id.twitch.tv/oauth2/validate")
+            .header("Authorization", format!("OAuth {}", access_token))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Twitch rejected the token"));
+        }
+        let info: serde_json::Value = response.json().await?;
+        if info["client_id"].as_str() != Some(DROPS_CLIENT_ID) {
+            return Err(anyhow::anyhow!(
+                "The token belongs to a different Twitch client"
+            ));
+        }
+        let token = StorableDropsToken {
+            access_token: access_token.to_string(),
+            refresh_token: String::new(),
+            expires_at: (Utc::now()
+                + ChronoDuration::seconds(info["expires_in"].as_i64().unwrap_or(0)))
+            .timestamp(),
+        };
+        Self::store_token_to_file(&token)?;
+        if let Err(e) = Self::store_token_to_cookies(&token).await {
+            error!(
+                "[DROPS_AUTH] Imported token saved to file but cookies failed: {:?}",
+                e
+            );
+        }
+        Ok(info["login"].as_str().unwrap_or_default().to_string())
+    }
 }
@@ -0,0 +1,157 @@
+//! The drops streamer blacklist: channels mining skips for a while, and why.
+//!
+//! Entries come from the mining plugin's recovery watchdog (the
+//! `blacklist_streamer` host method) or from the user, and are persisted to
+//! `drops_blacklist.json` in the app data dir until they expire, so a restart
+//! doesn't send mining straight back to a channel that just failed. The live
+//! ids ride along on every `drops.*` action as `skip_channel_ids`. The entries
+//! live in the watchdog's own `RecoveryWatchdogState`; this module only adds
+//! persistence and the change event.
+//!
+//! `drops-blacklist-changed` carries the full list whenever it changes.
+
+use crate::models::drops::{BlacklistReason, BlacklistedStreamer, RecoveryWatchdogState};
+use crate::services::event_bus;
+use chrono::Utc;
+use log::warn;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::RwLock as StdRwLock;
+
+const BLACKLIST_FILE: &str = "drops_blacklist.json";
+/// Longest a single entry may last (a week).
+pub const MAX_DURATION_SECS: u64 = 7 * 24 * 3600;
+
+static STATE: Lazy<StdRwLock<RecoveryWatchdogState>> = Lazy::new(|| {
+    StdRwLock::new(RecoveryWatchdogState {
+        blacklisted_streamers: load_from_disk(),
+        ..Default::default()
+    })
+});
+
+fn blacklist_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(BLACKLIST_FILE))
+}
+
+fn load_from_disk() -> HashMap<String, BlacklistedStreamer> {
+    let mut entries: HashMap<String, BlacklistedStreamer> = blacklist_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let now = Utc::now();
+    entries.retain(|_, e| e.expires_at > now);
+    entries
+}
+
+fn save_to_disk(entries: &HashMap<String, BlacklistedStreamer>) {
+    let Some(path) = blacklist_path() else { return };
+    match serde_json::to_string(entries) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[DropsBlacklist] Failed to write {}: {}", BLACKLIST_FILE, e);
+            }
+        }
+        Err(e) => warn!("[DropsBlacklist] Failed to serialize blacklist: {}", e),
+    }
+}
+
+fn sorted(entries: &HashMap<String, BlacklistedStreamer>) -> Vec<BlacklistedStreamer> {
+    let now = Utc::now();
+    let mut list: Vec<BlacklistedStreamer> = entries
+        .values()
+        .filter(|e| e.expires_at > now)
+        .cloned()
+        .collect();
+    list.sort_by_key(|e| e.expires_at);
+    list
+}
+
+/// Drop expired entries, saving if any were.
+fn prune(state: &mut RecoveryWatchdogState) {
+    let before = state.blacklisted_streamers.len();
+    state.cleanup_expired();
+    if state.blacklisted_streamers.len() != before {
+        save_to_disk(&state.blacklisted_streamers);
+    }
+}
+
+fn changed(entries: &HashMap<String, BlacklistedStreamer>) {
+    save_to_disk(entries);
+    let _ = event_bus::emit("drops-blacklist-changed", sorted(entries));
+}
+
+/// Live entries, soonest to expire first.
+pub fn list() -> Vec<BlacklistedStreamer> {
+    let Ok(mut state) = STATE.write() else {
+        return Vec::new();
+    };
+    prune(&mut state);
+    sorted(&state.blacklisted_streamers)
+}
+
+/// Ids of every channel currently blacklisted.
+pub fn channel_ids() -> Vec<String> {
+    list().into_iter().map(|e| e.channel_id).collect()
+}
+
+/// Blacklist `channel_id` for `duration_secs` (capped at a week), replacing
+/// any existing entry.
+pub fn add(
+    channel_id: &str,
+    channel_name: &str,
+    reason: BlacklistReason,
+    duration_secs: u64,
+) -> BlacklistedStreamer {
+    let mut state = STATE.write().unwrap();
+    state.blacklist_streamer(
+        channel_id.to_string(),
+        channel_name.to_string(),
+        reason,
+        duration_secs.min(MAX_DURATION_SECS),
+    );
+    changed(&state.blacklisted_streamers);
+    state.blacklisted_streamers[channel_id].clone()
+}
+
+/// Lift `channel_id`'s entry. False when it wasn't blacklisted.
+pub fn remove(channel_id: &str) -> bool {
+    let Ok(mut state) = STATE.write() else {
+        return false;
+    };
+    let removed = state.blacklisted_streamers.remove(channel_id).is_some();
+    if removed {
+        changed(&state.blacklisted_streamers);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, expires_in_secs: i64) -> (String, BlacklistedStreamer) {
+        let now = Utc::now();
+        (
+            id.to_string(),
+            BlacklistedStreamer {
+                channel_id: id.to_string(),
+                channel_name: id.to_string(),
+                reason: BlacklistReason::StaleProgress,
+                blacklisted_at: now,
+                expires_at: now + chrono::Duration::seconds(expires_in_secs),
+            },
+        )
+    }
+
+    #[test]
+    fn expired_entries_are_left_out() {
+        let entries: HashMap<String, BlacklistedStreamer> =
+            [entry("late", 600), entry("gone", -1), entry("soon", 60)]
+                .into_iter()
+                .collect();
+        let ids: Vec<String> = sorted(&entries).into_iter().map(|e| e.channel_id).collect();
+        assert_eq!(ids, ["soon", "late"]);
+    }
+}
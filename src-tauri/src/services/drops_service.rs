@@ -1,14 +1,20 @@
 use crate::models::drops::*;
+use crate::services::automation_rules::{self, Observation};
+use crate::services::campaign_runs::{self, CampaignRuns};
 use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::event_bus;
+use crate::services::memory_caches::{self, CacheStat};
+use crate::services::script_hooks;
+use crate::services::twitch_api::{send_operation, GqlOperation, HttpTwitchApi, TwitchApi};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use log::{debug, error, info, warn};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
 use uuid::Uuid;
@@ -18,11 +24,105 @@ use uuid::Uuid;
 const CLIENT_ID: &str = env!("TWITCH_ANDROID_CLIENT_ID");
 const CLIENT_URL: &str = "https://www.twitch.tv";
 
-// Persisted-query hash for the Inventory GQL operation (same one the Twitch
-// web client sends). Shared by the UI inventory fetch and the monitor's
-// progress overlay.
-const INVENTORY_QUERY_HASH: &str =
-    "d86775d0ef16a63a33ad52e80eaff963b2d5b72fada7c991504a57496e1d8e4b";
+// The drops operations the Twitch web client sends as persisted queries, with
+// full text to fall back on (see twitch_api::GqlOperation). The fallbacks ask
+// for just the fields parsed here. Inventory is shared by the UI inventory
+// fetch and the monitor's progress overlay.
+const INVENTORY: GqlOperation = GqlOperation {
+    name: "Inventory",
+    hash: Some("d86775d0ef16a63a33ad52e80eaff963b2d5b72fada7c991504a57496e1d8e4b"),
+    query: Some(
+        r#"
+        query Inventory {
+            currentUser {
+                id
+                inventory {
+                    dropCampaignsInProgress {
+                        id
+                        name
+                        status
+                        startAt
+                        endAt
+                        detailsURL
+                        accountLinkURL
+                        imageURL
+                        game { id name displayName boxArtURL }
+                        self { isAccountConnected }
+                        allow { isEnabled channels { id name displayName } }
+                        timeBasedDrops {
+                            id
+                            name
+                            startAt
+                            endAt
+                            requiredMinutesWatched
+                            benefitEdges {
+                                benefit { id name imageAssetURL distributionType }
+                            }
+                            self { currentMinutesWatched isClaimed dropInstanceID }
+                        }
+                    }
+                    gameEventDrops { id name imageURL lastAwardedAt }
+                }
+            }
+        }
+        "#,
+    ),
+};
+
+const CLAIM_DROP_REWARDS: GqlOperation = GqlOperation {
+    name: "DropsPage_ClaimDropRewards",
+    hash: Some("a455deea71bdc9015b78eb49f4acfbce8baa7ccbedd28e549bb025bd0f751930"),
+    query: Some(
+        r#"
+        mutation DropsPage_ClaimDropRewards($input: ClaimDropRewardsInput!) {
+            claimDropRewards(input: $input) {
+                status
+                dropInstanceID
+            }
+        }
+        "#,
+    ),
+};
+
+// Only we send this one, so it has no hash. It asks for the fields both the
+// campaign listing and the monitor's internal fetch parse.
+const DROP_CAMPAIGNS: GqlOperation = GqlOperation {
+    name: "DropCampaigns",
+    hash: None,
+    query: Some(
+        r#"
+        query DropCampaigns {
+            currentUser {
+                id
+                dropCampaigns {
+                    id
+                    name
+                    owner { id name }
+                    game { id name displayName boxArtURL }
+                    status
+                    startAt
+                    endAt
+                    description
+                    imageURL
+                    detailsURL
+                    accountLinkURL
+                    self { isAccountConnected }
+                    allow { isEnabled channels { id name } }
+                    timeBasedDrops {
+                        id
+                        name
+                        requiredMinutesWatched
+                        benefitEdges {
+                            benefit { id name imageAssetURL }
+                        }
+                        self { currentMinutesWatched isClaimed dropInstanceID }
+                    }
+                }
+            }
+        }
+        "#,
+    ),
+};
 
 // Your app's client ID (for reference - used for other Helix API calls)
 const APP_CLIENT_ID: &str = env!("TWITCH_APP_CLIENT_ID");
@@ -142,20 +242,40 @@ struct AvailableClaimInfo {
     points_earned_total: Option<i32>,
 }
 
+/// Every field is shared (`Arc`) or fixed at construction, so a clone is a
+/// handle on the same service: commands clone it out of `AppState`'s mutex
+/// and drop the guard before any network call.
+#[derive(Clone)]
 pub struct DropsService {
     client: Client,
     settings: Arc<RwLock<DropsSettings>>,
     drop_progress: Arc<RwLock<HashMap<String, DropProgress>>>,
     claimed_drops: Arc<RwLock<Vec<ClaimedDrop>>>,
     channel_points_history: Arc<RwLock<Vec<ChannelPointsClaim>>>,
-    /// Cumulative channel points the app has auto-claimed, persisted across sessions.
-    lifetime_points_collected: Arc<RwLock<i64>>,
+    /// Lifetime totals, persisted across sessions along with the claim and
+    /// points history (see `save_statistics`).
+    lifetime: Arc<RwLock<LifetimeTotals>>,
+    /// Set when anything `save_statistics` writes changed since the last save.
+    stats_dirty: Arc<std::sync::atomic::AtomicBool>,
     channel_points_balances: Arc<RwLock<HashMap<String, ChannelPointsBalance>>>,
     monitoring_active: Arc<RwLock<bool>>,
     current_channel: Arc<RwLock<Option<(String, String)>>>, // (channel_id, channel_name)
     cached_active_campaigns_count: Arc<RwLock<i32>>, // Cache campaign count to avoid repeated API calls
     cached_campaigns: Arc<RwLock<Option<(Vec<DropCampaign>, DateTime<Utc>)>>>, // Cache campaigns with timestamp
+    /// The snapshot's account while `cached_campaigns` still holds the list
+    /// restored from disk at startup. The first stale read serves it, if the
+    /// drops login is still that account, and refreshes in the background.
+    restored_campaigns_owner: Arc<std::sync::Mutex<Option<String>>>,
     attempted_claims: Arc<RwLock<std::collections::HashSet<String>>>, // Track drops we've already attempted to claim
+    /// Campaigns whose minutes were seen accruing while the game account isn't
+    /// connected. Drives `DropCampaign::unlinked_warning`.
+    unlinked_earning: Arc<RwLock<std::collections::HashSet<String>>>,
+    /// Set when a realtime update reports fewer minutes than the map holds;
+    /// the monitor re-reads the inventory on its next tick instead of waiting
+    /// out `PROGRESS_REFRESH_SECS`.
+    progress_recheck: Arc<std::sync::atomic::AtomicBool>,
+    /// Channel-points reads and claims, sent with this service's client identity.
+    api: Arc<dyn TwitchApi>,
     device_id: String,
     session_id: String,
 }
@@ -163,38 +283,224 @@ pub struct DropsService {
 /// File (in the app data dir) that persists lifetime drops-automation stats across sessions.
 const LIFETIME_STATS_FILE: &str = "drops_lifetime_stats.json";
 
+/// How often `run_statistics_saver` writes changed stats to disk. The app
+/// also saves on exit; this bounds what a crash can lose.
+const STATS_SAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Lifetime totals, plus the claim and points history that `get_statistics`
+/// reports, as stored in `LIFETIME_STATS_FILE`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LifetimeStats {
+    /// Cumulative channel points the app has auto-claimed. Older files call
+    /// it `points_mined`.
+    #[serde(default, alias = "points_mined")]
+    points_collected: i64,
+    #[serde(default)]
+    drops_claimed: i64,
+    /// Drop-progress minutes seen accruing in realtime updates.
+    #[serde(default)]
+    minutes_watched: i64,
+    #[serde(default)]
+    claimed_drops: Vec<ClaimedDrop>,
+    #[serde(default)]
+    channel_points_history: Vec<ChannelPointsClaim>,
+}
+
+/// The running lifetime totals.
+#[derive(Debug, Clone, Copy, Default)]
+struct LifetimeTotals {
+    points_collected: i64,
+    drops_claimed: i64,
+    minutes_watched: i64,
+}
+
 fn lifetime_stats_path() -> Option<std::path::PathBuf> {
     crate::services::cache_service::get_app_data_dir()
         .ok()
         .map(|dir| dir.join(LIFETIME_STATS_FILE))
 }
 
-/// Read the persisted cumulative auto-claimed channel points. Returns 0 if absent or unreadable.
-fn load_lifetime_points_collected() -> i64 {
-    let path = match lifetime_stats_path() {
-        Some(p) => p,
-        None => return 0,
+/// Read the persisted lifetime stats. Empty if absent or unreadable.
+fn load_lifetime_stats() -> LifetimeStats {
+    let Some(path) = lifetime_stats_path() else {
+        return LifetimeStats::default();
     };
     match std::fs::read_to_string(&path) {
-        Ok(contents) => serde_json::from_str::<serde_json::Value>(&contents)
-            .ok()
-            // Prefer the current key; fall back to the legacy key so an existing
-            // stats file keeps its cumulative total across the rename.
-            .and_then(|v| v["points_collected"].as_i64().or_else(|| v["points_mined"].as_i64()))
-            .unwrap_or(0),
-        Err(_) => 0,
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            warn!("[Drops] Ignoring unreadable lifetime stats: {}", e);
+            LifetimeStats::default()
+        }),
+        Err(_) => LifetimeStats::default(),
     }
 }
 
-/// Persist the cumulative auto-claimed channel points. Best-effort; IO errors are ignored.
-fn save_lifetime_points_collected(points: i64) {
-    if let Some(path) = lifetime_stats_path() {
-        if let Ok(json) =
-            serde_json::to_string_pretty(&serde_json::json!({ "points_collected": points }))
-        {
-            let _ = std::fs::write(&path, json);
+fn save_lifetime_stats(stats: &LifetimeStats) {
+    let Some(path) = lifetime_stats_path() else {
+        return;
+    };
+    match serde_json::to_string(stats) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[Drops] Failed to write lifetime stats: {}", e);
+            }
+        }
+        Err(e) => warn!("[Drops] Failed to serialize lifetime stats: {}", e),
+    }
+}
+
+/// File (in the app data dir) holding the last successfully fetched inventory,
+/// served by `get_inventory_offline` when the network is unavailable.
+const INVENTORY_SNAPSHOT_FILE: &str = "drops_inventory_snapshot.json";
+
+/// File (in the app data dir) holding the last fetched active-campaign list,
+/// served at startup while a fresh one is fetched.
+const CAMPAIGNS_CACHE_FILE: &str = "drops_campaigns_cache.json";
+/// Older snapshots are ignored: campaigns start and end daily, and per-drop
+/// progress that stale would mislead more than an empty list.
+const CAMPAIGNS_SNAPSHOT_MAX_AGE_HOURS: i64 = 24;
+
+/// Account (`currentUser.id`) the last campaign fetch was made for, so the
+/// snapshot can be tied to it.
+static CAMPAIGNS_USER_ID: std::sync::Mutex<Option<String>> = std::sync::Mutex::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct CampaignsSnapshot {
+    fetched_at: DateTime<Utc>,
+    /// Whose progress `campaigns` carries; served only to the same account.
+    user_id: String,
+    campaigns: Vec<DropCampaign>,
+}
+
+fn load_campaigns_snapshot() -> Option<CampaignsSnapshot> {
+    let path = crate::services::cache_service::get_app_data_dir()
+        .ok()?
+        .join(CAMPAIGNS_CACHE_FILE);
+    let contents = std::fs::read_to_string(path).ok()?;
+    let snapshot: CampaignsSnapshot = serde_json::from_str(&contents).ok()?;
+    let age = Utc::now().signed_duration_since(snapshot.fetched_at);
+    (age.num_hours() < CAMPAIGNS_SNAPSHOT_MAX_AGE_HOURS).then_some(snapshot)
+}
+
+/// Best-effort; a failed write only costs the next startup a fetch.
+fn save_campaigns_snapshot(campaigns: &[DropCampaign], fetched_at: DateTime<Utc>) {
+    let Ok(dir) = crate::services::cache_service::get_app_data_dir() else {
+        return;
+    };
+    let Some(user_id) = CAMPAIGNS_USER_ID.lock().ok().and_then(|id| id.clone()) else {
+        return;
+    };
+    let snapshot = CampaignsSnapshot {
+        fetched_at,
+        user_id,
+        campaigns: campaigns.to_vec(),
+    };
+    match serde_json::to_string(&snapshot) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(dir.join(CAMPAIGNS_CACHE_FILE), json) {
+                warn!("[Drops] Failed to write campaigns cache: {}", e);
+            }
+        }
+        Err(e) => warn!("[Drops] Failed to serialize campaigns cache: {}", e),
+    }
+}
+
+// In-memory caps (see services::memory_caches). Generous enough that a normal
+// session never hits them; they only matter for runs that last for days.
+const MAX_CLAIMED_DROPS: usize = 500;
+const MAX_POINTS_HISTORY: usize = 5_000;
+const MAX_DROP_PROGRESS: usize = 2_000;
+const MAX_ATTEMPTED_CLAIMS: usize = 2_000;
+const MAX_UNLINKED_EARNING: usize = 500;
+
+/// Campaign/reward art never changes under a given URL; keep it a month.
+const REWARD_IMAGE_EXPIRY_DAYS: u32 = 30;
+
+/// Reward images queued on the download pool at once. The pool decides how
+/// many actually run.
+const REWARD_IMAGE_PREFETCH_CONCURRENCY: usize = 4;
+
+#[derive(Serialize, Deserialize)]
+struct InventorySnapshot {
+    synced_at: DateTime<Utc>,
+    inventory: InventoryResponse,
+}
+
+/// Stable universal-cache id for a reward/campaign image URL.
+fn reward_image_cache_id(url: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let digest = Sha256::digest(url.as_bytes());
+    format!("{:x}", digest)[..32].to_string()
+}
+
+/// Every image URL an inventory renders: campaign art, per-drop reward art and
+/// the completed-drops shelf.
+fn inventory_image_urls(inventory: &InventoryResponse) -> Vec<String> {
+    let campaign_urls = inventory.items.iter().flat_map(|item| {
+        std::iter::once(item.campaign.image_url.clone()).chain(
+            item.campaign
+                .time_based_drops
+                .iter()
+                .flat_map(|d| d.benefit_edges.iter().map(|b| b.image_url.clone())),
+        )
+    });
+    let completed_urls = inventory
+        .completed_drops
+        .iter()
+        .map(|d| d.image_url.clone());
+
+    let mut urls: Vec<String> = campaign_urls
+        .chain(completed_urls)
+        .filter(|u| u.starts_with("http"))
+        .collect();
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// Merge a progress refresh into `map`. The campaign list lags earned minutes,
+/// so the inventory wins wherever it reports a drop; a campaign-only value
+/// lower than what the map already holds is a stale read and the held value
+/// is kept. Returns the drops whose reported minutes went backwards (as
+/// `(drop_id, held, reported)`), whether or not the lower value was taken.
+fn reconcile_progress(
+    map: &mut HashMap<String, DropProgress>,
+    campaigns: Option<HashMap<String, DropProgress>>,
+    inventory: Option<HashMap<String, DropProgress>>,
+) -> Vec<(String, i32, i32)> {
+    let previous = match campaigns {
+        Some(snapshot) => std::mem::replace(map, snapshot),
+        None => map.clone(),
+    };
+    let mut regressions = Vec::new();
+    for (drop_id, held) in &previous {
+        if let Some(entry) = map.get_mut(drop_id) {
+            let lagging = entry.current_minutes_watched < held.current_minutes_watched;
+            let from_inventory = inventory
+                .as_ref()
+                .is_some_and(|inv| inv.contains_key(drop_id));
+            if lagging && !entry.is_claimed && !from_inventory {
+                regressions.push((
+                    drop_id.clone(),
+                    held.current_minutes_watched,
+                    entry.current_minutes_watched,
+                ));
+                entry.current_minutes_watched = held.current_minutes_watched;
+            }
+        }
+    }
+    for (drop_id, entry) in inventory.into_iter().flatten() {
+        if let Some(held) = previous.get(&drop_id) {
+            if entry.current_minutes_watched < held.current_minutes_watched && !entry.is_claimed {
+                regressions.push((
+                    drop_id.clone(),
+                    held.current_minutes_watched,
+                    entry.current_minutes_watched,
+                ));
+            }
         }
+        map.insert(drop_id, entry);
     }
+    regressions
 }
 
 impl DropsService {
@@ -209,24 +515,58 @@ impl DropsService {
         let device_id = Uuid::new_v4().to_string().replace("-", "");
         let session_id = Uuid::new_v4().to_string().replace("-", "");
 
+        // Last session's campaigns, so the Drops center has something to show
+        // before the first fetch returns.
+        let snapshot = load_campaigns_snapshot();
+        let restored_progress = snapshot
+            .as_ref()
+            .map(|s| Self::progress_from_campaigns(&s.campaigns))
+            .unwrap_or_default();
+        let restored_count = snapshot.as_ref().map_or(0, |s| s.campaigns.len() as i32);
+        let restored_owner = snapshot.as_ref().map(|s| s.user_id.clone());
+
+        let stats = load_lifetime_stats();
+        let lifetime = LifetimeTotals {
+            points_collected: stats.points_collected,
+            drops_claimed: stats.drops_claimed,
+            minutes_watched: stats.minutes_watched,
+        };
+
+        let api = HttpTwitchApi::new().with_gql_headers({
+            let (device_id, session_id) = (device_id.clone(), session_id.clone());
+            move |token: &str| Self::gql_headers(token, &device_id, &session_id)
+        });
+
         Self {
             client: crate::services::http::client().clone(),
             settings: Arc::new(RwLock::new(initial_settings)),
-            drop_progress: Arc::new(RwLock::new(HashMap::new())),
-            claimed_drops: Arc::new(RwLock::new(Vec::new())),
-            channel_points_history: Arc::new(RwLock::new(Vec::new())),
-            lifetime_points_collected: Arc::new(RwLock::new(load_lifetime_points_collected())),
+            drop_progress: Arc::new(RwLock::new(restored_progress)),
+            claimed_drops: Arc::new(RwLock::new(stats.claimed_drops)),
+            channel_points_history: Arc::new(RwLock::new(stats.channel_points_history)),
+            lifetime: Arc::new(RwLock::new(lifetime)),
+            stats_dirty: Arc::new(std::sync::atomic::AtomicBool::new(false)),
             channel_points_balances: Arc::new(RwLock::new(HashMap::new())),
             monitoring_active: Arc::new(RwLock::new(false)),
             current_channel: Arc::new(RwLock::new(None)),
-            cached_active_campaigns_count: Arc::new(RwLock::new(0)),
-            cached_campaigns: Arc::new(RwLock::new(None)),
+            cached_active_campaigns_count: Arc::new(RwLock::new(restored_count)),
+            cached_campaigns: Arc::new(RwLock::new(snapshot.map(|s| (s.campaigns, s.fetched_at)))),
+            restored_campaigns_owner: Arc::new(std::sync::Mutex::new(restored_owner)),
             attempted_claims: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            unlinked_earning: Arc::new(RwLock::new(std::collections::HashSet::new())),
+            progress_recheck: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            api: Arc::new(api),
             device_id,
             session_id,
         }
     }
 
+    /// The same service reading and claiming channel points through `api`.
+    #[cfg(test)]
+    pub fn with_api(mut self, api: Arc<dyn TwitchApi>) -> Self {
+        self.api = api;
+        self
+    }
+
     /// Create headers for GQL requests (mimicking the Twitch web client's auth_state.headers())
     fn create_gql_headers(&self, token: &str) -> HeaderMap {
         Self::gql_headers(token, &self.device_id, &self.session_id)
@@ -254,6 +594,98 @@ impl DropsService {
         headers
     }
 
+    /// Persist the inventory for offline use and start caching its images in
+    /// the background. Called after every successful inventory fetch.
+    pub fn remember_inventory(inventory: &InventoryResponse) {
+        if let Ok(dir) = crate::services::cache_service::get_app_data_dir() {
+            let snapshot = InventorySnapshot {
+                synced_at: Utc::now(),
+                inventory: inventory.clone(),
+            };
+            match serde_json::to_string(&snapshot) {
+                Ok(json) => {
+                    if let Err(e) = std::fs::write(dir.join(INVENTORY_SNAPSHOT_FILE), json) {
+                        warn!("[Drops] Failed to write inventory snapshot: {}", e);
+                    }
+                }
+                Err(e) => warn!("[Drops] Failed to serialize inventory snapshot: {}", e),
+            }
+        }
+
+        let urls = inventory_image_urls(inventory);
+        tokio::spawn(Self::prefetch_reward_images(urls));
+    }
+
+    /// Download any of `urls` not already in the universal cache. Failures are
+    /// logged and skipped; the next inventory sync retries them.
+    pub async fn prefetch_reward_images(urls: Vec<String>) {
+        use crate::services::universal_cache_service::{
+            cache_file, get_cached_files_list, CacheType,
+        };
+        use futures::StreamExt;
+
+        let cached = get_cached_files_list(CacheType::DropReward)
+            .await
+            .unwrap_or_default();
+        let missing: Vec<String> = urls
+            .into_iter()
+            .filter(|u| !cached.contains_key(&reward_image_cache_id(u)))
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        debug!("[Drops] Prefetching {} reward images", missing.len());
+        futures::stream::iter(missing)
+            .for_each_concurrent(REWARD_IMAGE_PREFETCH_CONCURRENCY, |url| async move {
+                use crate::services::download_pool::{self, Priority};
+                let id = reward_image_cache_id(&url);
+                let download = cache_file(
+                    CacheType::DropReward,
+                    id,
+                    url.clone(),
+                    REWARD_IMAGE_EXPIRY_DAYS,
+                );
+                match download_pool::run(Priority::Background, "drop_rewards", download).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) | Err(e) => {
+                        debug!("[Drops] Reward image prefetch failed for {}: {}", url, e);
+                    }
+                }
+            })
+            .await;
+    }
+
+    /// The last inventory written by `remember_inventory`, with local paths for
+    /// every image that has been cached. `None` if no sync has happened yet.
+    pub async fn load_inventory_offline() -> Result<Option<OfflineInventory>> {
+        use crate::services::universal_cache_service::{get_cached_files_list, CacheType};
+
+        let path =
+            crate::services::cache_service::get_app_data_dir()?.join(INVENTORY_SNAPSHOT_FILE);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let snapshot: InventorySnapshot = serde_json::from_str(&contents)?;
+
+        let cached = get_cached_files_list(CacheType::DropReward).await?;
+        let local_images = inventory_image_urls(&snapshot.inventory)
+            .into_iter()
+            .filter_map(|url| {
+                let local = cached.get(&reward_image_cache_id(&url))?.clone();
+                Some((url, local))
+            })
+            .collect();
+
+        Ok(Some(OfflineInventory {
+            inventory: snapshot.inventory,
+            synced_at: snapshot.synced_at,
+            local_images,
+        }))
+    }
+
     pub async fn get_settings(&self) -> DropsSettings {
         self.settings.read().await.clone()
     }
@@ -280,27 +712,20 @@ impl DropsService {
         };
 
         // Use the exact same GQL operation as the Twitch web client
-        let response = self.client
-            .post("https://gql.twitch.tv/gql")
-            .headers(self.create_gql_headers(&token))
-            .json(&serde_json::json!({
-                "operationName": "Inventory",
-                "variables": {
-                    "fetchRewardCampaigns": false
-                },
-                "extensions": {
-                    "persistedQuery": {
-                        "version": 1,
-                        "sha256Hash": INVENTORY_QUERY_HASH
-                    }
-                }
-            }))
-            .send()
-            .await?;
-
-        debug!("[fetch_inventory] Response status: {}", response.status());
+        let headers = self.create_gql_headers(&token);
+        let (status, response_text) = send_operation(
+            || {
+                self.client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &INVENTORY,
+            serde_json::json!({ "fetchRewardCampaigns": false }),
+        )
+        .await?;
+
+        debug!("[fetch_inventory] Response status: {}", status);
 
-        let response_text = response.text().await?;
         let response_json: serde_json::Value = match serde_json::from_str(&response_text) {
             Ok(json) => json,
             Err(e) => {
@@ -581,7 +1006,11 @@ impl DropsService {
                 allowed_channels,
                 is_acl_based,
                 details_url: None,
-                account_link: None,
+                account_link: campaign_json["accountLinkURL"]
+                    .as_str()
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string()),
+                unlinked_warning: false,
             };
 
             items.push(InventoryItem {
@@ -652,19 +1081,40 @@ impl DropsService {
         const CACHE_TTL_SECONDS: i64 = 300; // 5 minutes
 
         // Check if we have valid cached data
+        let mut restored = None;
         {
             let cache = self.cached_campaigns.read().await;
             if let Some((campaigns, cached_at)) = cache.as_ref() {
                 let age = Utc::now().signed_duration_since(*cached_at);
                 if age.num_seconds() < CACHE_TTL_SECONDS {
                     debug!("Using cached campaigns ({} seconds old)", age.num_seconds());
-                    return Ok(campaigns.clone());
+                    return Ok(self.with_link_warnings(campaigns.clone()).await);
+                } else if let Some(owner) = self.take_restored_owner() {
+                    restored = Some((campaigns.clone(), owner, age.num_seconds()));
                 } else {
                     debug!("Campaign cache expired ({} seconds old)", age.num_seconds());
                 }
             }
         }
 
+        // First read after startup: answer with last session's list now and
+        // refresh behind it, as long as it's the same account's.
+        if let Some((campaigns, owner, age_secs)) = restored {
+            let token = DropsAuthService::get_token().await?;
+            match self.get_user_id_from_token(&token).await {
+                Ok(user_id) if user_id == owner => {
+                    debug!(
+                        "Serving campaigns restored from disk ({} seconds old), refreshing",
+                        age_secs
+                    );
+                    self.spawn_campaign_refresh();
+                    return Ok(self.with_link_warnings(campaigns).await);
+                }
+                Ok(_) => debug!("Campaigns on disk belong to another account, refetching"),
+                Err(e) => debug!("Could not check the campaigns on disk: {}", e),
+            }
+        }
+
         // Cache miss or expired - fetch from API
         debug!("Fetching fresh campaigns from API");
         let campaigns = self.fetch_all_active_campaigns_from_api().await?;
@@ -676,7 +1126,115 @@ impl DropsService {
         // watched for every campaign until a automation websocket event happens.
         self.update_campaigns_and_progress(&campaigns).await;
 
-        Ok(campaigns)
+        Ok(self.with_link_warnings(campaigns).await)
+    }
+
+    /// The restored snapshot's account, if the cache still holds it; from
+    /// then on the cache counts as fetched.
+    fn take_restored_owner(&self) -> Option<String> {
+        self.restored_campaigns_owner
+            .lock()
+            .ok()
+            .and_then(|mut owner| owner.take())
+    }
+
+    /// Fetch campaigns in the background and store them like a foreground
+    /// fetch would. Used to revalidate the list restored from disk.
+    fn spawn_campaign_refresh(&self) {
+        let client = self.client.clone();
+        let device_id = self.device_id.clone();
+        let session_id = self.session_id.clone();
+        let progress = self.drop_progress.clone();
+        let count = self.cached_active_campaigns_count.clone();
+        let cache = self.cached_campaigns.clone();
+        tokio::spawn(async move {
+            match Self::fetch_active_campaigns(&client, &device_id, &session_id).await {
+                Ok(campaigns) => {
+                    Self::store_campaigns(&progress, &count, &cache, &campaigns).await;
+                    debug!("Background campaign refresh: {} campaigns", campaigns.len());
+                }
+                Err(e) => warn!("[Drops] Background campaign refresh failed: {}", e),
+            }
+        });
+    }
+
+    /// Active campaigns that still need their game account linked, soonest
+    /// ending first.
+    pub async fn get_unlinked_campaigns(&self) -> Result<Vec<UnlinkedCampaign>> {
+        let mut unlinked: Vec<UnlinkedCampaign> = self
+            .get_all_active_campaigns_cached()
+            .await?
+            .into_iter()
+            .filter(|c| !c.is_account_connected)
+            .map(|c| UnlinkedCampaign {
+                campaign_id: c.id,
+                campaign_name: c.name,
+                game_name: c.game_name,
+                image_url: c.image_url,
+                account_link: c.account_link,
+                end_at: c.end_at,
+            })
+            .collect();
+        unlinked.sort_by_key(|c| c.end_at);
+        Ok(unlinked)
+    }
+
+    /// One page of live drops-enabled streams, in `game_id` or across every
+    /// category, each with the active campaigns it earns toward. Streams in
+    /// the preferred languages come first, by viewer count within each.
+    pub async fn get_drops_enabled_streams(
+        &self,
+        game_id: Option<&str>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<DropsStreamsPage> {
+        let (campaigns, page) = tokio::join!(
+            self.get_all_active_campaigns_cached(),
+            crate::services::twitch_service::TwitchService::get_drops_enabled_streams(
+                game_id, cursor, limit
+            ),
+        );
+        // The page is still worth showing without campaign details.
+        let campaigns = campaigns.unwrap_or_else(|e| {
+            warn!("[Drops] Campaigns unavailable for the browse page: {}", e);
+            Vec::new()
+        });
+        let (mut streams, cursor) = page?;
+        let settings = self.get_settings().await;
+        streams.sort_by_key(|s| settings.language_rank(s.language.as_deref()));
+        let now = Utc::now();
+        let streams = streams
+            .into_iter()
+            .map(|stream| {
+                let campaigns = campaigns
+                    .iter()
+                    .filter(|c| c.game_id == stream.game_id && c.start_at <= now && c.end_at > now)
+                    .filter(|c| c.allows_channel(&stream.user_id, &stream.user_login))
+                    .map(|c| StreamCampaign {
+                        campaign_id: c.id.clone(),
+                        campaign_name: c.name.clone(),
+                        image_url: c.image_url.clone(),
+                        end_at: c.end_at,
+                        is_account_connected: c.is_account_connected,
+                    })
+                    .collect();
+                DropsEnabledStream { stream, campaigns }
+            })
+            .collect();
+        Ok(DropsStreamsPage { streams, cursor })
+    }
+
+    /// Flag campaigns the monitor has seen earning minutes without a linked
+    /// game account. Cleared as soon as a fetch reports the account connected.
+    async fn with_link_warnings(&self, mut campaigns: Vec<DropCampaign>) -> Vec<DropCampaign> {
+        let mut unlinked = self.unlinked_earning.write().await;
+        for campaign in campaigns.iter_mut() {
+            if campaign.is_account_connected {
+                unlinked.remove(&campaign.id);
+            }
+            campaign.unlinked_warning = unlinked.contains(&campaign.id);
+        }
+        campaigns
     }
 
     /// Internal method to fetch campaigns from API (no caching)
@@ -689,8 +1247,11 @@ impl DropsService {
     /// connection-status refresh, which must not disturb in-flight automation progress the way a
     /// full `update_campaigns_and_progress` would.
     pub async fn prime_campaign_cache(&self, campaigns: &[DropCampaign]) {
+        self.take_restored_owner();
+        let now = Utc::now();
         let mut cache = self.cached_campaigns.write().await;
-        *cache = Some((campaigns.to_vec(), Utc::now()));
+        *cache = Some((campaigns.to_vec(), now));
+        save_campaigns_snapshot(campaigns, now);
     }
 
     /// Fetches all active drop campaigns with per-account progress (each drop's
@@ -721,73 +1282,21 @@ impl DropsService {
 
         debug!("Fetching drops campaigns using Android app client ID...");
 
-        // Use a full GraphQL query that includes timeBasedDrops with requiredMinutesWatched
-        // The ViewerDropsDashboard persisted query doesn't include these fields
-        let query = r#"
-        query DropCampaigns {
-            currentUser {
-                id
-                dropCampaigns {
-                    id
-                    name
-                    owner { id name }
-                    game {
-                        id
-                        displayName
-                        boxArtURL
-                    }
-                    status
-                    startAt
-                    endAt
-                    description
-                    detailsURL
-                    accountLinkURL
-                    self {
-                        isAccountConnected
-                    }
-                    allow {
-                        isEnabled
-                        channels {
-                            id
-                            name
-                        }
-                    }
-                    timeBasedDrops {
-                        id
-                        name
-                        requiredMinutesWatched
-                        benefitEdges {
-                            benefit {
-                                id
-                                name
-                                imageAssetURL
-                            }
-                        }
-                        self {
-                            currentMinutesWatched
-                            isClaimed
-                            dropInstanceID
-                        }
-                    }
-                }
-            }
-        }
-        "#;
-
-        let response = client
-            .post("https://gql.twitch.tv/gql")
-            .headers(Self::gql_headers(&token, device_id, session_id))
-            .json(&serde_json::json!({
-                "query": query,
-                "variables": {}
-            }))
-            .send()
-            .await?;
-
-        debug!("Response status: {}", response.status());
-
-        // Get the raw response text first
-        let response_text = response.text().await?;
+        // The full DropCampaigns query: the ViewerDropsDashboard persisted query
+        // doesn't include timeBasedDrops with requiredMinutesWatched
+        let headers = Self::gql_headers(&token, device_id, session_id);
+        let (status, response_text) = send_operation(
+            || {
+                client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &DROP_CAMPAIGNS,
+            serde_json::json!({}),
+        )
+        .await?;
+
+        debug!("Response status: {}", status);
 
         // Try to parse it as JSON
         let response_json: serde_json::Value = match serde_json::from_str(&response_text) {
@@ -826,6 +1335,11 @@ impl DropsService {
                 "Authentication mismatch: Token was issued for app client ID but drops API requires web client ID"
             ));
         }
+        if let Ok(mut user_id) = CAMPAIGNS_USER_ID.lock() {
+            *user_id = response_json["data"]["currentUser"]["id"]
+                .as_str()
+                .map(str::to_string);
+        }
 
         let mut result = Vec::new();
 
@@ -1053,6 +1567,7 @@ impl DropsService {
                     is_acl_based,
                     details_url,
                     account_link,
+                    unlinked_warning: false,
                 });
             }
         }
@@ -1091,20 +1606,18 @@ impl DropsService {
     ) -> Result<HashMap<String, DropProgress>> {
         let token = DropsAuthService::get_token().await?;
 
-        let response = client
-            .post("https://gql.twitch.tv/gql")
-            .headers(Self::gql_headers(&token, device_id, session_id))
-            .json(&serde_json::json!({
-                "operationName": "Inventory",
-                "variables": { "fetchRewardCampaigns": false },
-                "extensions": {
-                    "persistedQuery": { "version": 1, "sha256Hash": INVENTORY_QUERY_HASH }
-                }
-            }))
-            .send()
-            .await?;
-
-        let body: serde_json::Value = response.json().await?;
+        let headers = Self::gql_headers(&token, device_id, session_id);
+        let (_, text) = send_operation(
+            || {
+                client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &INVENTORY,
+            serde_json::json!({ "fetchRewardCampaigns": false }),
+        )
+        .await?;
+        let body: serde_json::Value = serde_json::from_str(&text)?;
         let mut map = HashMap::new();
         let Some(campaigns) =
             body["data"]["currentUser"]["inventory"]["dropCampaignsInProgress"].as_array()
@@ -1147,18 +1660,42 @@ impl DropsService {
 
     /// Updates the service's internal state with fresh campaign data and calculates progress.
     pub async fn update_campaigns_and_progress(&self, campaigns: &[DropCampaign]) {
+        self.take_restored_owner();
+        Self::store_campaigns(
+            &self.drop_progress,
+            &self.cached_active_campaigns_count,
+            &self.cached_campaigns,
+            campaigns,
+        )
+        .await;
+    }
+
+    async fn store_campaigns(
+        progress: &RwLock<HashMap<String, DropProgress>>,
+        count: &RwLock<i32>,
+        cache: &RwLock<Option<(Vec<DropCampaign>, DateTime<Utc>)>>,
+        campaigns: &[DropCampaign],
+    ) {
         {
-            let mut progress_map = self.drop_progress.write().await;
+            let mut progress_map = progress.write().await;
             *progress_map = Self::progress_from_campaigns(campaigns);
+            memory_caches::trim_map(
+                "drops.progress",
+                &mut progress_map,
+                MAX_DROP_PROGRESS,
+                |p| p.last_updated,
+            );
         }
 
         // Update cached campaign count
-        let mut cached_count = self.cached_active_campaigns_count.write().await;
-        *cached_count = campaigns.len() as i32;
+        *count.write().await = campaigns.len() as i32;
 
-        // Update campaigns cache when automation fetches them
-        let mut cache = self.cached_campaigns.write().await;
-        *cache = Some((campaigns.to_vec(), Utc::now()));
+        // Update campaigns cache when automation fetches them, and keep the
+        // on-disk copy for the next startup
+        let now = Utc::now();
+        *cache.write().await = Some((campaigns.to_vec(), now));
+        save_campaigns_snapshot(campaigns, now);
+        crate::services::drops_wishlist::observe_campaigns(campaigns);
     }
 
     /// Get active campaigns with settings filters applied (for automation)
@@ -1258,29 +1795,17 @@ impl DropsService {
         );
 
         // Use persisted query format like the Twitch web client does
-        let response = self
-            .client
-            .post("https://gql.twitch.tv/gql")
-            .headers(self.create_gql_headers(&token))
-            .json(&serde_json::json!({
-                "operationName": "DropsPage_ClaimDropRewards",
-                "variables": {
-                    "input": {
-                        "dropInstanceID": drop_instance_id
-                    }
-                },
-                "extensions": {
-                    "persistedQuery": {
-                        "version": 1,
-                        "sha256Hash": "a455deea71bdc9015b78eb49f4acfbce8baa7ccbedd28e549bb025bd0f751930"
-                    }
-                }
-            }))
-            .send()
-            .await?;
-
-        let status = response.status();
-        let response_text = response.text().await?;
+        let headers = self.create_gql_headers(&token);
+        let (status, response_text) = send_operation(
+            || {
+                self.client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &CLAIM_DROP_REWARDS,
+            serde_json::json!({ "input": { "dropInstanceID": drop_instance_id } }),
+        )
+        .await?;
 
         debug!("Claim response status: {}", status);
         debug!("Claim response: {}", response_text);
@@ -1349,99 +1874,60 @@ impl DropsService {
         Ok(user_id.to_string())
     }
 
+    /// Send `operation` as the drops account, with the Android client's headers,
+    /// and return the response JSON.
+    pub(crate) async fn gql(
+        &self,
+        operation: &GqlOperation,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let token = DropsAuthService::get_token().await?;
+        let headers = self.create_gql_headers(&token);
+        let (_, text) = send_operation(
+            || {
+                self.client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            operation,
+            variables,
+        )
+        .await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
     pub async fn check_channel_points(
         &self,
         channel_id: &str,
         channel_name: &str,
     ) -> Result<Option<ChannelPointsClaim>> {
-        let token = DropsAuthService::get_token().await?;
-
-        // Use persisted query like the Twitch web client
-        let response = self
-            .client
-            .post("https://gql.twitch.tv/gql")
-            .headers(self.create_gql_headers(&token))
-            .json(&serde_json::json!({
-                "operationName": "ChannelPointsContext",
-                "variables": {
-                    "channelLogin": channel_name.to_lowercase()
-                },
-                "extensions": {
-                    "persistedQuery": {
-                        "version": 1,
-                        "sha256Hash": "9988086babc615a918a1e9a722ff41d98847acac822645209ac7379eecb27152"
-                    }
-                }
-            }))
-            .send()
-            .await?;
-
-        let response_json: serde_json::Value = response.json().await?;
-
-        // Check for errors
-        if let Some(errors) = response_json.get("errors") {
-            return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
-        }
-
-        // Parse the response - structure is: data.channel.self.communityPoints
-        if let Some(data) = response_json.get("data") {
-            if let Some(channel) = data.get("channel") {
-                let channel_id_from_response = channel
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or(channel_id);
-
-                if let Some(self_data) = channel.get("self") {
-                    if let Some(community_points) = self_data.get("communityPoints") {
-                        let balance_val = community_points
-                            .get("balance")
-                            .and_then(|v| v.as_i64())
-                            .unwrap_or(0) as i32;
-
-                        // Update balance
-                        let balance = ChannelPointsBalance {
-                            channel_id: channel_id_from_response.to_string(),
-                            channel_name: channel_name.to_string(),
-                            balance: balance_val,
-                            last_updated: Utc::now(),
-                            points_name: None, // Not fetched via persisted query
-                            points_icon_url: None,
-                        };
-
-                        let mut balances = self.channel_points_balances.write().await;
-                        balances.insert(channel_id_from_response.to_string(), balance);
-
-                        // Check if there's a claim available
-                        if let Some(available_claim) = community_points.get("availableClaim") {
-                            if !available_claim.is_null() {
-                                let claim_id = available_claim
-                                    .get("id")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("")
-                                    .to_string();
-                                let points_earned = available_claim
-                                    .get("pointsEarnedTotal")
-                                    .or_else(|| available_claim.get("pointsEarnedBaseline"))
-                                    .and_then(|v| v.as_i64())
-                                    .unwrap_or(50)
-                                    as i32;
-
-                                return Ok(Some(ChannelPointsClaim {
-                                    id: claim_id,
-                                    channel_id: channel_id_from_response.to_string(),
-                                    channel_name: channel_name.to_string(),
-                                    points_earned,
-                                    claimed_at: Utc::now(),
-                                    claim_type: ChannelPointsClaimType::Watch,
-                                }));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(None)
+        let Some(context) = self.api.channel_points_context(channel_name).await? else {
+            return Ok(None);
+        };
+        let channel_id = context.channel_id.as_deref().unwrap_or(channel_id);
+
+        let balance = ChannelPointsBalance {
+            channel_id: channel_id.to_string(),
+            channel_name: channel_name.to_string(),
+            balance: context.balance,
+            last_updated: Utc::now(),
+            points_name: None, // Not fetched via persisted query
+            points_icon_url: None,
+        };
+        self.channel_points_balances
+            .write()
+            .await
+            .insert(channel_id.to_string(), balance);
+
+        Ok(context.claim.map(|claim| ChannelPointsClaim {
+            id: claim.id,
+            channel_id: channel_id.to_string(),
+            channel_name: channel_name.to_string(),
+            points_earned: claim.points_earned,
+            claimed_at: Utc::now(),
+            claim_type: ChannelPointsClaimType::Watch,
+            balance_after: Some(context.balance),
+        }))
     }
 
     pub async fn claim_channel_points(
@@ -1450,71 +1936,55 @@ impl DropsService {
         _channel_name: &str,
         claim_id: &str,
     ) -> Result<BonusClaimResult> {
-        let token = DropsAuthService::get_token().await?;
-
-        // Field selection mirrors the official web client's ClaimCommunityPoints
-        // (verified capture): `claim.pointsEarnedTotal` is the exact credited
-        // amount (multipliers included), `currentPoints` is the new balance.
-        let mutation = r#"
-        mutation ClaimCommunityPoints($input: ClaimCommunityPointsInput!) {
-            claimCommunityPoints(input: $input) {
-                claim {
-                    pointsEarnedTotal
-                    pointsEarnedBaseline
-                }
-                currentPoints
-                error {
-                    code
-                }
-            }
-        }
-        "#;
-
-        let response = self
-            .client
-            .post("https://gql.twitch.tv/gql")
-            .headers(self.create_gql_headers(&token))
-            .json(&serde_json::json!({
-                "query": mutation,
-                "variables": {
-                    "input": {
-                        "channelID": channel_id,
-                        "claimID": claim_id
-                    }
-                }
-            }))
-            .send()
-            .await?;
-
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow::anyhow!(
-                "Failed to claim channel points: {}",
-                error_text
-            ));
-        }
-
-        let result: serde_json::Value = response.json().await?;
-        let payload = &result["data"]["claimCommunityPoints"];
-        let new_balance = payload["currentPoints"].as_i64().unwrap_or(0) as i32;
-        // Bonus chests are 50 at baseline; fall back to that if the response
-        // omits the earned fields for any reason.
-        let points_earned = payload["claim"]["pointsEarnedTotal"]
-            .as_i64()
-            .or_else(|| payload["claim"]["pointsEarnedBaseline"].as_i64())
-            .unwrap_or(50) as i32;
-
-        Ok(BonusClaimResult {
-            new_balance,
-            points_earned,
-        })
+        self.api.claim_community_points(channel_id, claim_id).await
     }
 
     pub async fn get_drop_progress(&self) -> Vec<DropProgress> {
         self.drop_progress.read().await.values().cloned().collect()
     }
 
+    /// Sizes of the in-memory stores, for `get_memory_cache_stats`.
+    pub async fn cache_stats(&self) -> Vec<CacheStat> {
+        let campaigns = self
+            .cached_campaigns
+            .read()
+            .await
+            .as_ref()
+            .map_or(0, |(c, _)| c.len());
+        let balances = self.channel_points_balances.read().await.len();
+        vec![
+            // Replaced wholesale on every fetch; sized by Twitch, not by us.
+            CacheStat::new("drops.campaigns", campaigns, campaigns),
+            CacheStat::new(
+                "drops.progress",
+                self.drop_progress.read().await.len(),
+                MAX_DROP_PROGRESS,
+            ),
+            CacheStat::new(
+                "drops.claimed_drops",
+                self.claimed_drops.read().await.len(),
+                MAX_CLAIMED_DROPS,
+            ),
+            CacheStat::new(
+                "drops.points_history",
+                self.channel_points_history.read().await.len(),
+                MAX_POINTS_HISTORY,
+            ),
+            CacheStat::new(
+                "drops.attempted_claims",
+                self.attempted_claims.read().await.len(),
+                MAX_ATTEMPTED_CLAIMS,
+            ),
+            CacheStat::new(
+                "drops.unlinked_earning",
+                self.unlinked_earning.read().await.len(),
+                MAX_UNLINKED_EARNING,
+            ),
+            // One per channel points were seen in; the leaderboard needs all.
+            CacheStat::new("drops.points_balances", balances, balances),
+        ]
+    }
+
     pub async fn get_claimed_drops(&self) -> Vec<ClaimedDrop> {
         self.claimed_drops.read().await.clone()
     }
@@ -1528,10 +1998,10 @@ impl DropsService {
         let channel_points_history = self.channel_points_history.read().await;
         let drop_progress = self.drop_progress.read().await;
 
-        // Lifetime cumulative of points the app has auto-claimed, persisted across sessions,
-        // rather than only this session's history. Clamp into the i32 wire type.
-        let total_channel_points_earned: i32 =
-            (*self.lifetime_points_collected.read().await).clamp(0, i32::MAX as i64) as i32;
+        // Lifetime totals, persisted across sessions, rather than only this
+        // session's history. Clamp into the i32 wire type.
+        let lifetime = *self.lifetime.read().await;
+        let to_i32 = |n: i64| n.clamp(0, i32::MAX as i64) as i32;
 
         let drops_in_progress = drop_progress
             .values()
@@ -1542,8 +2012,9 @@ impl DropsService {
         let active_campaigns = *self.cached_active_campaigns_count.read().await;
 
         DropsStatistics {
-            total_drops_claimed: claimed_drops.len() as i32,
-            total_channel_points_earned,
+            total_drops_claimed: to_i32(lifetime.drops_claimed),
+            total_channel_points_earned: to_i32(lifetime.points_collected),
+            total_minutes_watched: lifetime.minutes_watched.max(0),
             active_campaigns,
             drops_in_progress,
             recent_claims: claimed_drops.iter().rev().take(10).cloned().collect(),
@@ -1557,8 +2028,30 @@ impl DropsService {
     }
 
     pub async fn add_claimed_drop(&self, claimed_drop: ClaimedDrop) {
-        let mut claimed_drops = self.claimed_drops.write().await;
-        claimed_drops.push(claimed_drop);
+        Self::record_claim(
+            &self.claimed_drops,
+            &self.lifetime,
+            &self.stats_dirty,
+            claimed_drop,
+        )
+        .await;
+    }
+
+    /// Add a claim to the history and the lifetime count. Shared with the
+    /// monitor task, which holds clones of the Arcs rather than `&self`.
+    async fn record_claim(
+        claimed_drops: &RwLock<Vec<ClaimedDrop>>,
+        lifetime: &RwLock<LifetimeTotals>,
+        stats_dirty: &std::sync::atomic::AtomicBool,
+        claimed_drop: ClaimedDrop,
+    ) {
+        {
+            let mut claimed_drops = claimed_drops.write().await;
+            claimed_drops.push(claimed_drop);
+            memory_caches::trim_front("drops.claimed_drops", &mut claimed_drops, MAX_CLAIMED_DROPS);
+        }
+        lifetime.write().await.drops_claimed += 1;
+        stats_dirty.store(true, std::sync::atomic::Ordering::SeqCst);
     }
 
     pub async fn add_channel_points_claim(&self, claim: ChannelPointsClaim) {
@@ -1566,17 +2059,128 @@ impl DropsService {
         {
             let mut history = self.channel_points_history.write().await;
             history.push(claim);
+            memory_caches::trim_front("drops.points_history", &mut history, MAX_POINTS_HISTORY);
         }
         if claimed_points > 0 {
-            let total = {
-                let mut lifetime = self.lifetime_points_collected.write().await;
-                *lifetime += claimed_points;
-                *lifetime
-            };
-            save_lifetime_points_collected(total);
+            self.lifetime.write().await.points_collected += claimed_points;
+        }
+        self.stats_dirty
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Record points leaving a channel's balance (reward redemption, prediction
+    /// bet) as a negative history entry. Lifetime stats only count earns, so this
+    /// doesn't touch them.
+    pub async fn record_points_spent(
+        &self,
+        channel_id: &str,
+        channel_name: &str,
+        points: i32,
+        balance: i32,
+    ) {
+        let mut history = self.channel_points_history.write().await;
+        history.push(ChannelPointsClaim {
+            id: uuid::Uuid::new_v4().to_string(),
+            channel_id: channel_id.to_string(),
+            channel_name: channel_name.to_string(),
+            points_earned: -points.abs(),
+            claimed_at: Utc::now(),
+            claim_type: ChannelPointsClaimType::Redemption,
+            balance_after: Some(balance),
+        });
+        memory_caches::trim_front("drops.points_history", &mut history, MAX_POINTS_HISTORY);
+        self.stats_dirty
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    /// Write the lifetime stats and history to disk if they changed since the
+    /// last save. Called periodically by `run_statistics_saver` and on exit.
+    pub async fn save_statistics(&self) {
+        if !self
+            .stats_dirty
+            .swap(false, std::sync::atomic::Ordering::SeqCst)
+        {
+            return;
+        }
+        let lifetime = *self.lifetime.read().await;
+        let stats = LifetimeStats {
+            points_collected: lifetime.points_collected,
+            drops_claimed: lifetime.drops_claimed,
+            minutes_watched: lifetime.minutes_watched,
+            claimed_drops: self.claimed_drops.read().await.clone(),
+            channel_points_history: self.channel_points_history.read().await.clone(),
+        };
+        tokio::task::spawn_blocking(move || save_lifetime_stats(&stats))
+            .await
+            .ok();
+    }
+
+    /// Save changed statistics every `STATS_SAVE_INTERVAL` for the life of
+    /// the app, so a crash loses at most one interval.
+    pub async fn run_statistics_saver(service: Arc<tokio::sync::Mutex<DropsService>>) {
+        let mut ticker = tokio::time::interval(STATS_SAVE_INTERVAL);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            service.lock().await.save_statistics().await;
+        }
+    }
+
+    /// Earned vs spent and the balance series for one channel, oldest first.
+    pub async fn get_points_trend(&self, channel_id: &str) -> PointsTrend {
+        let history = self.channel_points_history.read().await;
+        let samples: Vec<PointsTrendSample> = history
+            .iter()
+            .filter(|c| c.channel_id == channel_id)
+            .map(|c| PointsTrendSample {
+                at: c.claimed_at,
+                delta: c.points_earned,
+                balance: c.balance_after,
+            })
+            .collect();
+
+        let total_earned: i32 = samples.iter().map(|s| s.delta.max(0)).sum();
+        let total_spent: i32 = samples.iter().map(|s| (-s.delta).max(0)).sum();
+        let current_balance = self
+            .channel_points_balances
+            .read()
+            .await
+            .get(channel_id)
+            .map(|b| b.balance)
+            .or_else(|| samples.iter().rev().find_map(|s| s.balance));
+
+        PointsTrend {
+            channel_id: channel_id.to_string(),
+            current_balance,
+            total_earned,
+            total_spent,
+            net: total_earned - total_spent,
+            samples,
         }
     }
 
+    /// Targets for this channel that a move from `previous` to `balance` rose
+    /// across. An unknown previous balance (first reading) never alerts, so
+    /// existing holdings aren't reported as a goal being reached.
+    pub async fn points_targets_crossed(
+        &self,
+        channel_id: &str,
+        previous: Option<i32>,
+        balance: i32,
+    ) -> Vec<PointsTarget> {
+        let Some(previous) = previous else {
+            return Vec::new();
+        };
+        self.settings
+            .read()
+            .await
+            .points_targets
+            .iter()
+            .filter(|t| t.channel_id == channel_id && previous < t.target && balance >= t.target)
+            .cloned()
+            .collect()
+    }
+
     pub async fn get_channel_points_balance(
         &self,
         channel_id: &str,
@@ -1593,14 +2197,16 @@ impl DropsService {
     /// Upsert a channel's current balance. Fed by the realtime points-earned
     /// socket, which reports the new balance with every earn — the only thing
     /// that keeps this store current now that the automation loop is gone. Powers
-    /// the channel-points leaderboard and the points accolades.
+    /// the channel-points leaderboard and the points accolades. Returns the
+    /// previous balance, if one was known.
     pub async fn update_channel_points_balance(
         &self,
         channel_id: &str,
         channel_name: &str,
         balance: i32,
-    ) {
+    ) -> Option<i32> {
         let mut balances = self.channel_points_balances.write().await;
+        let previous = balances.get(channel_id).map(|b| b.balance);
         balances
             .entry(channel_id.to_string())
             .and_modify(|b| {
@@ -1620,6 +2226,7 @@ impl DropsService {
                 points_name: None,
                 points_icon_url: None,
             });
+        previous
     }
 
     pub async fn start_monitoring(
@@ -1647,9 +2254,13 @@ impl DropsService {
         let settings = self.settings.clone();
         let drop_progress = self.drop_progress.clone();
         let claimed_drops = self.claimed_drops.clone();
+        let lifetime = self.lifetime.clone();
+        let stats_dirty = self.stats_dirty.clone();
         let monitoring_active = self.monitoring_active.clone();
         let current_channel = self.current_channel.clone();
         let attempted_claims = self.attempted_claims.clone();
+        let unlinked_earning = self.unlinked_earning.clone();
+        let progress_recheck = self.progress_recheck.clone();
         let client = self.client.clone();
         let device_id = self.device_id.clone();
         let session_id = self.session_id.clone();
@@ -1677,6 +2288,12 @@ impl DropsService {
             // once per drop instead of on every check tick.
             let mut notified_ready: std::collections::HashSet<String> =
                 std::collections::HashSet::new();
+            // (drop_id, milestone) pairs already announced, so a progress
+            // value that dips and recovers between refreshes doesn't repeat one.
+            let mut notified_milestones: std::collections::HashSet<(String, ProgressMilestone)> =
+                std::collections::HashSet::new();
+            // Campaigns earning minutes this session, summarized on completion.
+            let mut campaign_runs = CampaignRuns::default();
 
             loop {
                 // Check if monitoring should continue
@@ -1704,27 +2321,32 @@ impl DropsService {
                     // center display and the auto-claim below. This is the core,
                     // always-on watched-channel path; the background plugin is a
                     // separate opt-in plugin.
-                    let refresh_due = last_progress_refresh
-                        .map(|t| {
-                            Utc::now().signed_duration_since(t).num_seconds()
-                                >= PROGRESS_REFRESH_SECS
-                        })
-                        .unwrap_or(true);
+                    let refresh_due = progress_recheck
+                        .swap(false, std::sync::atomic::Ordering::SeqCst)
+                        || last_progress_refresh
+                            .map(|t| {
+                                Utc::now().signed_duration_since(t).num_seconds()
+                                    >= PROGRESS_REFRESH_SECS
+                            })
+                            .unwrap_or(true);
                     if refresh_due {
-                        let campaign_snapshot =
+                        let fetched_campaigns =
                             match Self::fetch_active_campaigns(&client, &device_id, &session_id)
                                 .await
                             {
-                                Ok(campaigns) => Some(Self::progress_from_campaigns(&campaigns)),
+                                Ok(campaigns) => Some(campaigns),
                                 Err(e) => {
                                     debug!("Watched-channel drop progress refresh failed: {}", e);
                                     None
                                 }
                             };
+                        let campaign_snapshot = fetched_campaigns
+                            .as_deref()
+                            .map(Self::progress_from_campaigns);
                         // The campaign list lags earned minutes and never carries
                         // dropInstanceIDs; the inventory is the live source. Overlay
                         // it so the auto-claim check below actually sees completion.
-                        let inventory_overlay =
+                        let mut inventory_overlay =
                             match Self::fetch_inventory_progress(&client, &device_id, &session_id)
                                 .await
                             {
@@ -1734,15 +2356,182 @@ impl DropsService {
                                     None
                                 }
                             };
+                        // Campaign numbers alone that would move a drop backwards
+                        // get one more inventory read before anything is applied.
+                        let lagging = match (&inventory_overlay, &campaign_snapshot) {
+                            (None, Some(snapshot)) => {
+                                let held = drop_progress.read().await;
+                                snapshot.values().any(|p| {
+                                    held.get(&p.drop_id).is_some_and(|h| {
+                                        p.current_minutes_watched < h.current_minutes_watched
+                                    })
+                                })
+                            }
+                            _ => false,
+                        };
+                        if lagging {
+                            debug!("[Drops] Campaign progress behind held values; re-reading inventory");
+                            inventory_overlay =
+                                Self::fetch_inventory_progress(&client, &device_id, &session_id)
+                                    .await
+                                    .ok();
+                        }
                         if campaign_snapshot.is_some() || inventory_overlay.is_some() {
                             let mut progress_map = drop_progress.write().await;
-                            if let Some(snapshot) = campaign_snapshot {
-                                *progress_map = snapshot;
-                            }
-                            if let Some(overlay) = inventory_overlay {
-                                progress_map.extend(overlay);
+                            let before: HashMap<String, i32> = progress_map
+                                .iter()
+                                .map(|(id, p)| (id.clone(), p.current_minutes_watched))
+                                .collect();
+                            for (drop_id, held, reported) in reconcile_progress(
+                                &mut progress_map,
+                                campaign_snapshot,
+                                inventory_overlay,
+                            ) {
+                                warn!(
+                                    "[Drops] Progress for drop {} went backwards ({} -> {} min); now {}",
+                                    drop_id,
+                                    held,
+                                    reported,
+                                    progress_map
+                                        .get(&drop_id)
+                                        .map_or(reported, |p| p.current_minutes_watched)
+                                );
                             }
                             last_progress_refresh = Some(Utc::now());
+
+                            // Minutes moving on a campaign whose game account isn't
+                            // linked are minutes that won't pay out. Warn once per
+                            // campaign while it stays unlinked.
+                            for campaign in fetched_campaigns
+                                .iter()
+                                .flatten()
+                                .filter(|c| !c.is_account_connected)
+                            {
+                                let earning = campaign.time_based_drops.iter().any(|d| {
+                                    progress_map.get(&d.id).is_some_and(|p| {
+                                        !p.is_claimed
+                                            && before
+                                                .get(&d.id)
+                                                .is_some_and(|m| p.current_minutes_watched > *m)
+                                    })
+                                });
+                                let newly_unlinked = earning && {
+                                    let mut unlinked = unlinked_earning.write().await;
+                                    let inserted = unlinked.insert(campaign.id.clone());
+                                    memory_caches::trim_set(
+                                        "drops.unlinked_earning",
+                                        &mut unlinked,
+                                        MAX_UNLINKED_EARNING,
+                                    );
+                                    inserted
+                                };
+                                if newly_unlinked {
+                                    warn!(
+                                        "[Drops] Earning minutes on '{}' but its game account isn't linked",
+                                        campaign.name
+                                    );
+                                    let _ = event_bus::emit(
+                                        "drops-account-link-required",
+                                        serde_json::json!({
+                                            "campaign_id": campaign.id,
+                                            "campaign_name": campaign.name,
+                                            "game_name": campaign.game_name,
+                                            "account_link": campaign.account_link,
+                                        }),
+                                    );
+                                }
+                            }
+
+                            // Milestones on the drop being earned, computed from
+                            // the minutes it gained this refresh.
+                            if current_settings.notify_on_progress_milestones {
+                                for progress in progress_map.values().filter(|p| !p.is_claimed) {
+                                    let Some(&was) = before.get(&progress.drop_id) else {
+                                        continue;
+                                    };
+                                    for milestone in current_settings.crossed_milestones(
+                                        was,
+                                        progress.current_minutes_watched,
+                                        progress.required_minutes_watched,
+                                    ) {
+                                        if !notified_milestones
+                                            .insert((progress.drop_id.clone(), milestone))
+                                        {
+                                            continue;
+                                        }
+                                        debug!(
+                                            "[Drops] Drop {} reached {:?}",
+                                            progress.drop_id, milestone
+                                        );
+                                        let _ = event_bus::emit(
+                                            "drop-progress-milestone",
+                                            serde_json::json!({
+                                                "drop_id": progress.drop_id,
+                                                "campaign_id": progress.campaign_id,
+                                                "milestone": milestone,
+                                                "current_minutes": progress.current_minutes_watched,
+                                                "required_minutes": progress.required_minutes_watched,
+                                                "minutes_remaining": progress.required_minutes_watched
+                                                    - progress.current_minutes_watched,
+                                            }),
+                                        );
+                                    }
+                                }
+                            }
+
+                            // Credit each campaign the largest gain among its drops
+                            // (they progress together), then close the runs whose
+                            // collectible drops are all claimed.
+                            let mut gains: HashMap<&str, i32> = HashMap::new();
+                            for progress in progress_map.values() {
+                                let Some(&was) = before.get(&progress.drop_id) else {
+                                    continue;
+                                };
+                                let gained = progress.current_minutes_watched - was;
+                                if gained > 0 {
+                                    let best =
+                                        gains.entry(progress.campaign_id.as_str()).or_default();
+                                    *best = (*best).max(gained);
+                                }
+                            }
+                            let earning_channel =
+                                channel_info.as_ref().map(|(_, name)| name.as_str());
+                            for (campaign_id, gained) in gains {
+                                campaign_runs.record(
+                                    campaign_id,
+                                    gained,
+                                    earning_channel,
+                                    Utc::now(),
+                                );
+                            }
+                            let completed: Vec<&DropCampaign> = fetched_campaigns
+                                .iter()
+                                .flatten()
+                                .filter(|c| campaign_runs.is_tracking(&c.id))
+                                .filter(|c| {
+                                    let mut collectible = c
+                                        .time_based_drops
+                                        .iter()
+                                        .filter(|d| d.is_collectible)
+                                        .peekable();
+                                    collectible.peek().is_some()
+                                        && collectible.all(|d| {
+                                            progress_map.get(&d.id).is_some_and(|p| p.is_claimed)
+                                        })
+                                })
+                                .collect();
+                            for campaign in completed {
+                                if let Some(summary) = campaign_runs.finish(campaign, Utc::now()) {
+                                    info!(
+                                        "[Drops] Campaign '{}' complete: {} min over {} channel(s)",
+                                        summary.campaign_name,
+                                        summary.minutes_watched,
+                                        summary.channels.len()
+                                    );
+                                    campaign_runs::append_history(&summary);
+                                    let _ = event_bus::emit("campaign-summary", &summary);
+                                }
+                            }
                         }
                     }
 
@@ -1769,7 +2558,7 @@ impl DropsService {
                         if current_settings.notify_on_drop_available
                             && notified_ready.insert(progress.drop_id.clone())
                         {
-                            let _ = app_handle.emit("drop-ready", &progress);
+                            let _ = event_bus::emit("drop-ready", &progress);
                         }
 
                         // Auto-claim if enabled
@@ -1795,10 +2584,15 @@ impl DropsService {
                                 Ok(_) => {
                                     debug!("Auto-claimed drop: {}", progress.drop_id);
                                     failed_claims.remove(&progress.drop_id);
-                                    attempted_claims
-                                        .write()
-                                        .await
-                                        .insert(progress.drop_id.clone());
+                                    {
+                                        let mut attempted = attempted_claims.write().await;
+                                        attempted.insert(progress.drop_id.clone());
+                                        memory_caches::trim_set(
+                                            "drops.attempted_claims",
+                                            &mut attempted,
+                                            MAX_ATTEMPTED_CLAIMS,
+                                        );
+                                    }
 
                                     // Create claimed drop record
                                     let claimed = ClaimedDrop {
@@ -1812,12 +2606,31 @@ impl DropsService {
                                         claimed_at: Utc::now(),
                                     };
 
-                                    let mut claimed_drops_lock = claimed_drops.write().await;
-                                    claimed_drops_lock.push(claimed.clone());
+                                    Self::record_claim(
+                                        &claimed_drops,
+                                        &lifetime,
+                                        &stats_dirty,
+                                        claimed.clone(),
+                                    )
+                                    .await;
 
                                     if current_settings.notify_on_drop_claimed {
-                                        let _ = app_handle.emit("drop-claimed", &claimed);
+                                        let _ = event_bus::emit("drop-claimed", &claimed);
                                     }
+                                    automation_rules::observe(Observation::DropClaimed {
+                                        drop_id: claimed.drop_id.clone(),
+                                        campaign_id: claimed.campaign_id.clone(),
+                                    });
+                                    script_hooks::fire(
+                                        &app_handle,
+                                        script_hooks::DROP_CLAIMED,
+                                        serde_json::json!(claimed),
+                                    );
+                                    crate::services::webhooks::fire(
+                                        &app_handle,
+                                        script_hooks::DROP_CLAIMED,
+                                        serde_json::json!(claimed),
+                                    );
                                 }
                                 Err(e) => {
                                     let entry = failed_claims
@@ -1864,6 +2677,24 @@ impl DropsService {
         let mut progress_map = self.drop_progress.write().await;
 
         if let Some(progress) = progress_map.get_mut(&drop_id) {
+            // A realtime value below the held one is out of order or wrong;
+            // keep the held minutes and let the monitor settle it from the
+            // inventory instead of flickering between the two.
+            if current_minutes < progress.current_minutes_watched && !progress.is_claimed {
+                warn!(
+                    "[Drops] WebSocket progress for drop {} went backwards ({} -> {} min); re-checking inventory",
+                    drop_id, progress.current_minutes_watched, current_minutes
+                );
+                self.progress_recheck
+                    .store(true, std::sync::atomic::Ordering::SeqCst);
+                return;
+            }
+            let earned = current_minutes - progress.current_minutes_watched;
+            if earned > 0 && !progress.is_claimed {
+                self.lifetime.write().await.minutes_watched += earned as i64;
+                self.stats_dirty
+                    .store(true, std::sync::atomic::Ordering::SeqCst);
+            }
             // Update existing progress
             progress.current_minutes_watched = current_minutes;
             progress.required_minutes_watched = required_minutes;
@@ -1885,6 +2716,12 @@ impl DropsService {
                 drop_instance_id: None, // Will be populated when we fetch from API
             };
             progress_map.insert(drop_id.clone(), progress);
+            memory_caches::trim_map(
+                "drops.progress",
+                &mut progress_map,
+                MAX_DROP_PROGRESS,
+                |p| p.last_updated,
+            );
 
             debug!(
                 "Created new drop progress from WebSocket: {}/{} minutes for drop {}",
@@ -1913,52 +2750,18 @@ impl DropsService {
         headers.insert("Origin", HeaderValue::from_static(CLIENT_URL));
         headers.insert("Referer", HeaderValue::from_static(CLIENT_URL));
 
-        let query = r#"
-        query DropCampaigns {
-            currentUser {
-                dropCampaigns {
-                    id
-                    name
-                    game {
-                        id
-                        name
-                    }
-                    description
-                    imageURL
-                    startAt
-                    endAt
-                    timeBasedDrops {
-                        id
-                        name
-                        requiredMinutesWatched
-                        benefitEdges {
-                            benefit {
-                                id
-                                name
-                                imageAssetURL
-                            }
-                        }
-                        self {
-                            currentMinutesWatched
-                            isClaimed
-                        }
-                    }
-                }
-            }
-        }
-        "#;
-
-        let response = client
-            .post("https://gql.twitch.tv/gql")
-            .headers(headers)
-            .json(&serde_json::json!({
-                "query": query,
-                "variables": {}
-            }))
-            .send()
-            .await?;
+        let (_, text) = send_operation(
+            || {
+                client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &DROP_CAMPAIGNS,
+            serde_json::json!({}),
+        )
+        .await?;
 
-        let gql_response: GraphQLResponse<DropCampaignsData> = response.json().await?;
+        let gql_response: GraphQLResponse<DropCampaignsData> = serde_json::from_str(&text)?;
 
         if let Some(errors) = gql_response.errors {
             return Err(anyhow::anyhow!("GraphQL errors: {:?}", errors));
@@ -2035,8 +2838,15 @@ impl DropsService {
                 is_acl_based: false,
                 details_url: None, // Will be populated from the main fetch method
                 account_link: None,
+                unlinked_warning: false,
             });
         }
+        memory_caches::trim_map(
+            "drops.progress",
+            &mut progress_map,
+            MAX_DROP_PROGRESS,
+            |p| p.last_updated,
+        );
 
         Ok(result)
     }
@@ -2115,28 +2925,16 @@ impl DropsService {
         headers.insert("Referer", HeaderValue::from_static(CLIENT_URL));
 
         // Use persisted query format
-        let response = client
-            .post("https://gql.twitch.tv/gql")
-            .headers(headers)
-            .json(&serde_json::json!({
-                "operationName": "DropsPage_ClaimDropRewards",
-                "variables": {
-                    "input": {
-                        "dropInstanceID": drop_instance_id
-                    }
-                },
-                "extensions": {
-                    "persistedQuery": {
-                        "version": 1,
-                        "sha256Hash": "a455deea71bdc9015b78eb49f4acfbce8baa7ccbedd28e549bb025bd0f751930"
-                    }
-                }
-            }))
-            .send()
-            .await?;
-
-        let status = response.status();
-        let response_text = response.text().await?;
+        let (status, response_text) = send_operation(
+            || {
+                client
+                    .post("https://gql.twitch.tv/gql")
+                    .headers(headers.clone())
+            },
+            &CLAIM_DROP_REWARDS,
+            serde_json::json!({ "input": { "dropInstanceID": drop_instance_id } }),
+        )
+        .await?;
 
         debug!("[Auto] Claim response status: {}", status);
         debug!("[Auto] Claim response: {}", response_text);
@@ -2163,3 +2961,106 @@ impl DropsService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(drop_id: &str, minutes: i32) -> DropProgress {
+        DropProgress {
+            campaign_id: "c".to_string(),
+            drop_id: drop_id.to_string(),
+            current_minutes_watched: minutes,
+            required_minutes_watched: 120,
+            is_claimed: false,
+            last_updated: Utc::now(),
+            drop_instance_id: None,
+        }
+    }
+
+    fn map(entries: &[(&str, i32)]) -> HashMap<String, DropProgress> {
+        entries
+            .iter()
+            .map(|(id, m)| (id.to_string(), progress(id, *m)))
+            .collect()
+    }
+
+    #[test]
+    fn reconcile_keeps_held_minutes_over_lagging_campaigns_but_trusts_inventory() {
+        let mut held = map(&[("a", 60), ("b", 40)]);
+        let regressions = reconcile_progress(
+            &mut held,
+            Some(map(&[("a", 45), ("b", 30)])),
+            Some(map(&[("b", 20)])),
+        );
+        assert_eq!(held["a"].current_minutes_watched, 60);
+        assert_eq!(held["b"].current_minutes_watched, 20);
+        assert_eq!(regressions.len(), 2);
+
+        let regressions = reconcile_progress(&mut held, Some(map(&[("a", 70)])), None);
+        assert!(regressions.is_empty());
+        assert_eq!(held["a"].current_minutes_watched, 70);
+        assert!(!held.contains_key("b"));
+    }
+
+    #[test]
+    fn lifetime_stats_read_older_files() {
+        let legacy: LifetimeStats = serde_json::from_str(r#"{"points_mined": 1200}"#).unwrap();
+        assert_eq!(legacy.points_collected, 1200);
+        assert_eq!(legacy.drops_claimed, 0);
+        assert!(legacy.claimed_drops.is_empty());
+
+        let current: LifetimeStats =
+            serde_json::from_str(r#"{"points_collected": 50, "minutes_watched": 90}"#).unwrap();
+        assert_eq!(current.points_collected, 50);
+        assert_eq!(current.minutes_watched, 90);
+    }
+
+    #[tokio::test]
+    async fn a_waiting_bonus_is_found_and_claimed_through_the_api() {
+        use crate::services::twitch_api::{AvailableClaim, FollowedChannel, MockTwitchApi};
+
+        let mut api = MockTwitchApi::new(vec![FollowedChannel {
+            channel_id: "77".into(),
+            login: "alpha".into(),
+            display_name: "Alpha".into(),
+        }]);
+        api.balances.insert("alpha".into(), 1200);
+        api.bonuses.insert(
+            "alpha".into(),
+            AvailableClaim {
+                id: "claim-1".into(),
+                points_earned: 60,
+            },
+        );
+        let api = Arc::new(api);
+        let service = DropsService::new().with_api(api.clone());
+
+        assert!(service
+            .check_channel_points("0", "nobody")
+            .await
+            .unwrap()
+            .is_none());
+
+        // The id Twitch reports wins over the caller's.
+        let claim = service
+            .check_channel_points("0", "alpha")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (claim.id.as_str(), claim.channel_id.as_str()),
+            ("claim-1", "77")
+        );
+        assert_eq!(claim.balance_after, Some(1200));
+        let stored = service.get_channel_points_balance("77").await.unwrap();
+        assert_eq!(stored.balance, 1200);
+
+        let result = service
+            .claim_channel_points(&claim.channel_id, "alpha", &claim.id)
+            .await
+            .unwrap();
+        assert_eq!((result.new_balance, result.points_earned), (1260, 60));
+        assert_eq!(api.claimed.lock().unwrap().as_slice(), ["claim-1"]);
+    }
+}
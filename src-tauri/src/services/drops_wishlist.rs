@@ -0,0 +1,307 @@
+//! Starred drop rewards, followed across campaigns (`add_to_wishlist`,
+//! `get_wishlist_status`).
+//!
+//! A wished reward is keyed by its benefit id, which Twitch keeps when a
+//! reward comes back in a later campaign; a rerun that got a new id still
+//! matches on the same name in the same game. Every campaign refresh runs
+//! through `observe_campaigns`: a campaign holding a wished reward that
+//! hadn't been seen with it before sends `wishlist-reward-available`
+//! {reward, campaign_id, campaign_name}, and the campaigns holding wished
+//! rewards go to the mining plugin as `priority_campaign_ids`.
+//!
+//! Persisted to `drops_wishlist.json` in the app data dir.
+
+use crate::models::drops::{DropBenefit, DropCampaign};
+use crate::services::event_bus;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock as StdRwLock;
+
+const WISHLIST_FILE: &str = "drops_wishlist.json";
+/// Campaign ids remembered per reward; the oldest are dropped past this.
+const MAX_SEEN: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WishedReward {
+    pub benefit_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub image_url: String,
+    pub game_name: String,
+    pub added_at: DateTime<Utc>,
+    /// Campaigns already announced for this reward.
+    #[serde(default)]
+    seen_campaign_ids: Vec<String>,
+}
+
+/// Where a wished reward can be earned right now.
+#[derive(Debug, Clone, Serialize)]
+pub struct WishedRewardCampaign {
+    pub campaign_id: String,
+    pub campaign_name: String,
+    pub drop_name: String,
+    pub end_at: DateTime<Utc>,
+    pub current_minutes: i32,
+    pub required_minutes: i32,
+    pub claimed: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WishlistStatus {
+    pub reward: WishedReward,
+    /// Empty when no active campaign has it.
+    pub campaigns: Vec<WishedRewardCampaign>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct RewardAvailable<'a> {
+    reward: &'a WishedReward,
+    campaign_id: &'a str,
+    campaign_name: &'a str,
+}
+
+#[derive(Default)]
+struct WishlistStore {
+    rewards: Vec<WishedReward>,
+    /// Unclaimed campaigns holding a wished reward, from the last refresh.
+    priority_campaign_ids: Vec<String>,
+}
+
+static STORE: Lazy<StdRwLock<WishlistStore>> = Lazy::new(|| {
+    StdRwLock::new(WishlistStore {
+        rewards: load_from_disk(),
+        priority_campaign_ids: Vec::new(),
+    })
+});
+
+fn wishlist_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(WISHLIST_FILE))
+}
+
+fn load_from_disk() -> Vec<WishedReward> {
+    wishlist_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(rewards: &[WishedReward]) {
+    let Some(path) = wishlist_path() else { return };
+    match serde_json::to_string(rewards) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[DropsWishlist] Failed to write {}: {}", WISHLIST_FILE, e);
+            }
+        }
+        Err(e) => warn!("[DropsWishlist] Failed to serialize wishlist: {}", e),
+    }
+}
+
+fn matches(reward: &WishedReward, benefit: &DropBenefit, game_name: &str) -> bool {
+    benefit.id == reward.benefit_id
+        || (benefit.name.eq_ignore_ascii_case(&reward.name)
+            && game_name.eq_ignore_ascii_case(&reward.game_name))
+}
+
+/// The drops in `campaigns` that grant `reward`.
+fn campaigns_with(reward: &WishedReward, campaigns: &[DropCampaign]) -> Vec<WishedRewardCampaign> {
+    let mut found = Vec::new();
+    for campaign in campaigns {
+        for drop in &campaign.time_based_drops {
+            if !drop
+                .benefit_edges
+                .iter()
+                .any(|b| matches(reward, b, &campaign.game_name))
+            {
+                continue;
+            }
+            let progress = drop.progress.as_ref();
+            found.push(WishedRewardCampaign {
+                campaign_id: campaign.id.clone(),
+                campaign_name: campaign.name.clone(),
+                drop_name: drop.name.clone(),
+                end_at: campaign.end_at,
+                current_minutes: progress.map_or(0, |p| p.current_minutes_watched),
+                required_minutes: drop.required_minutes_watched,
+                claimed: progress.is_some_and(|p| p.is_claimed),
+            });
+        }
+    }
+    found
+}
+
+/// Star the reward `benefit_id`, which has to be in one of `campaigns`.
+/// Campaigns that already hold it aren't announced again.
+pub fn add(benefit_id: &str, campaigns: &[DropCampaign]) -> Result<WishedReward> {
+    let (benefit, game_name) = campaigns
+        .iter()
+        .flat_map(|c| {
+            c.time_based_drops
+                .iter()
+                .flat_map(|d| d.benefit_edges.iter())
+                .map(move |b| (b, &c.game_name))
+        })
+        .find(|(b, _)| b.id == benefit_id)
+        .ok_or_else(|| anyhow!("No active campaign has the reward {}", benefit_id))?;
+    let mut reward = WishedReward {
+        benefit_id: benefit.id.clone(),
+        name: benefit.name.clone(),
+        image_url: benefit.image_url.clone(),
+        game_name: game_name.clone(),
+        added_at: Utc::now(),
+        seen_campaign_ids: Vec::new(),
+    };
+    reward.seen_campaign_ids = campaigns_with(&reward, campaigns)
+        .into_iter()
+        .map(|c| c.campaign_id)
+        .collect();
+
+    let mut store = STORE.write().map_err(|_| anyhow!("Wishlist unavailable"))?;
+    store.rewards.retain(|r| r.benefit_id != reward.benefit_id);
+    store.rewards.push(reward.clone());
+    save_to_disk(&store.rewards);
+    drop(store);
+    observe_campaigns(campaigns);
+    Ok(reward)
+}
+
+/// Unstar `benefit_id`. False when it wasn't on the wishlist.
+pub fn remove(benefit_id: &str) -> bool {
+    let Ok(mut store) = STORE.write() else {
+        return false;
+    };
+    let before = store.rewards.len();
+    store.rewards.retain(|r| r.benefit_id != benefit_id);
+    let removed = store.rewards.len() != before;
+    if removed {
+        save_to_disk(&store.rewards);
+    }
+    removed
+}
+
+/// Every wished reward and the active campaigns that grant it.
+pub fn status(campaigns: &[DropCampaign]) -> Vec<WishlistStatus> {
+    let Ok(store) = STORE.read() else {
+        return Vec::new();
+    };
+    store
+        .rewards
+        .iter()
+        .map(|reward| WishlistStatus {
+            reward: reward.clone(),
+            campaigns: campaigns_with(reward, campaigns),
+        })
+        .collect()
+}
+
+/// Campaigns to mine first: unclaimed ones holding a wished reward.
+pub fn priority_campaign_ids() -> Vec<String> {
+    STORE
+        .read()
+        .map(|s| s.priority_campaign_ids.clone())
+        .unwrap_or_default()
+}
+
+/// Announce wished rewards in campaigns not seen with them before, and
+/// refresh the campaigns to prioritize.
+pub fn observe_campaigns(campaigns: &[DropCampaign]) {
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+    let store = &mut *store;
+    let mut priority: Vec<String> = Vec::new();
+    let mut changed = false;
+    for reward in &mut store.rewards {
+        for found in campaigns_with(reward, campaigns) {
+            if !found.claimed && !priority.contains(&found.campaign_id) {
+                priority.push(found.campaign_id.clone());
+            }
+            if reward.seen_campaign_ids.contains(&found.campaign_id) {
+                continue;
+            }
+            reward.seen_campaign_ids.push(found.campaign_id.clone());
+            if reward.seen_campaign_ids.len() > MAX_SEEN {
+                let excess = reward.seen_campaign_ids.len() - MAX_SEEN;
+                reward.seen_campaign_ids.drain(..excess);
+            }
+            changed = true;
+            let _ = event_bus::emit(
+                "wishlist-reward-available",
+                RewardAvailable {
+                    reward,
+                    campaign_id: &found.campaign_id,
+                    campaign_name: &found.campaign_name,
+                },
+            );
+        }
+    }
+    store.priority_campaign_ids = priority;
+    if changed {
+        save_to_disk(&store.rewards);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::drops::TimeBasedDrop;
+
+    fn campaign(id: &str, game: &str, benefit_id: &str, benefit_name: &str) -> DropCampaign {
+        DropCampaign {
+            id: id.to_string(),
+            name: id.to_string(),
+            game_id: String::new(),
+            game_name: game.to_string(),
+            description: String::new(),
+            image_url: String::new(),
+            start_at: Utc::now(),
+            end_at: Utc::now(),
+            time_based_drops: vec![TimeBasedDrop {
+                id: format!("{}-drop", id),
+                name: "Drop".to_string(),
+                required_minutes_watched: 60,
+                benefit_edges: vec![DropBenefit {
+                    id: benefit_id.to_string(),
+                    name: benefit_name.to_string(),
+                    image_url: String::new(),
+                    distribution_type: None,
+                }],
+                progress: None,
+                is_collectible: true,
+            }],
+            is_account_connected: true,
+            allowed_channels: Vec::new(),
+            is_acl_based: false,
+            details_url: None,
+            account_link: None,
+            unlinked_warning: false,
+        }
+    }
+
+    #[test]
+    fn rewards_match_by_id_or_by_name_within_the_game() {
+        let reward = WishedReward {
+            benefit_id: "b1".to_string(),
+            name: "Golden Skin".to_string(),
+            image_url: String::new(),
+            game_name: "Rust".to_string(),
+            added_at: Utc::now(),
+            seen_campaign_ids: Vec::new(),
+        };
+        let campaigns = [
+            campaign("same-id", "Rust", "b1", "Renamed"),
+            campaign("rerun", "Rust", "b9", "golden skin"),
+            campaign("other-game", "Apex Legends", "b7", "Golden Skin"),
+        ];
+        let ids: Vec<String> = campaigns_with(&reward, &campaigns)
+            .into_iter()
+            .map(|c| c.campaign_id)
+            .collect();
+        assert_eq!(ids, ["same-id", "rerun"]);
+    }
+}
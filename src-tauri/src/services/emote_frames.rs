@@ -0,0 +1,336 @@
+//! Pre-decoded animated emotes (`ChatDesignSettings::optimize_animated_emotes`).
+//!
+//! A busy chat full of large animated WebP/GIF emotes makes the webview
+//! decode every frame of every copy at full size. With the option on,
+//! `prepare` reads each animation's metadata once (frame count, duration) and
+//! re-encodes the oversized ones as animated WebP at one of the fixed chat
+//! sizes, cached under `emote_frames/` in the app data dir. Static images and
+//! animations that are already small are left to the webview.
+
+use anyhow::{anyhow, Result};
+use image::codecs::gif::GifDecoder;
+use image::{AnimationDecoder, ImageDecoder, RgbaImage};
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::path::PathBuf;
+use webp_animation::{Encoder, EncoderOptions, EncodingConfig, EncodingType};
+
+/// Heights an emote is converted to (1x, 2x and 4x of the chat line).
+pub const SIZES: [u32; 3] = [28, 56, 112];
+/// Animations bigger than this are converted even when already short enough.
+const OVERSIZED_BYTES: usize = 512 * 1024;
+/// Largest source file downloaded.
+const MAX_SOURCE_BYTES: usize = 16 * 1024 * 1024;
+/// Most frames decoded from one animation.
+const MAX_FRAMES: usize = 1000;
+/// Most pixels decoded across all of an animation's frames (about 256 MB of
+/// RGBA), so a small file can't expand into gigabytes.
+const MAX_TOTAL_PIXELS: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AnimationInfo {
+    /// `gif` or `webp`.
+    pub format: String,
+    pub width: u32,
+    pub height: u32,
+    pub frame_count: u32,
+    pub duration_ms: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PreparedEmote {
+    pub id: String,
+    /// None when the image isn't an animation (or couldn't be read).
+    pub info: Option<AnimationInfo>,
+    /// The converted file, when the animation was oversized.
+    pub path: Option<String>,
+}
+
+struct Decoded {
+    format: &'static str,
+    width: u32,
+    height: u32,
+    /// Each frame with the time it starts, in ms.
+    frames: Vec<(RgbaImage, u32)>,
+    duration_ms: u32,
+}
+
+impl Decoded {
+    fn info(&self) -> AnimationInfo {
+        AnimationInfo {
+            format: self.format.to_string(),
+            width: self.width,
+            height: self.height,
+            frame_count: self.frames.len() as u32,
+            duration_ms: self.duration_ms,
+        }
+    }
+}
+
+/// Fail once `frames` frames of `width`x`height` would pass the caps.
+fn check_budget(frames: usize, width: u32, height: u32) -> Result<()> {
+    if frames > MAX_FRAMES {
+        return Err(anyhow!("More than {} frames", MAX_FRAMES));
+    }
+    if frames as u64 * width as u64 * height as u64 > MAX_TOTAL_PIXELS {
+        return Err(anyhow!("Animation too large to decode"));
+    }
+    Ok(())
+}
+
+fn decode(bytes: &[u8]) -> Result<Decoded> {
+    if bytes.starts_with(b"GIF8") {
+        let decoder = GifDecoder::new(Cursor::new(bytes))?;
+        let (width, height) = decoder.dimensions();
+        check_budget(1, width, height)?;
+        let mut frames = Vec::new();
+        let mut at = 0u32;
+        for frame in decoder.into_frames() {
+            check_budget(frames.len() + 1, width, height)?;
+            let frame = frame?;
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            let delay = if denom == 0 { 0 } else { numer / denom };
+            frames.push((frame.into_buffer(), at));
+            at += delay;
+        }
+        let (width, height) = frames
+            .first()
+            .map(|(f, _)| f.dimensions())
+            .ok_or_else(|| anyhow!("GIF has no frames"))?;
+        return Ok(Decoded {
+            format: "gif",
+            width,
+            height,
+            frames,
+            duration_ms: at,
+        });
+    }
+    if bytes.len() > 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        let decoder = webp_animation::Decoder::new(bytes).map_err(|e| anyhow!("{:?}", e))?;
+        let (width, height) = decoder.dimensions();
+        check_budget(1, width, height)?;
+        let mut frames = Vec::new();
+        let mut at = 0u32;
+        // The decoder stamps each frame with the time it ends.
+        for frame in decoder.into_iter() {
+            check_budget(frames.len() + 1, width, height)?;
+            let image = RgbaImage::from_raw(width, height, frame.data().to_vec())
+                .ok_or_else(|| anyhow!("WebP frame size mismatch"))?;
+            frames.push((image, at));
+            at = frame.timestamp().max(0) as u32;
+        }
+        return Ok(Decoded {
+            format: "webp",
+            width,
+            height,
+            frames,
+            duration_ms: at,
+        });
+    }
+    Err(anyhow!("Not a GIF or WebP"))
+}
+
+/// The nearest of `SIZES` to `requested`.
+pub fn snap_size(requested: u32) -> u32 {
+    *SIZES
+        .iter()
+        .min_by_key(|s| s.abs_diff(requested))
+        .expect("SIZES is not empty")
+}
+
+/// `width`x`height` scaled down to `size` tall, keeping the aspect ratio.
+fn target_dims(width: u32, height: u32, size: u32) -> (u32, u32) {
+    if height <= size {
+        return (width, height);
+    }
+    let scaled = (width as f64 * size as f64 / height as f64).round() as u32;
+    (scaled.max(1), size)
+}
+
+fn needs_converting(info: &AnimationInfo, source_bytes: usize, size: u32) -> bool {
+    info.frame_count > 1 && (info.height > size || source_bytes > OVERSIZED_BYTES)
+}
+
+fn encode(decoded: &Decoded, size: u32) -> Result<Vec<u8>> {
+    let (w, h) = target_dims(decoded.width, decoded.height, size);
+    let mut encoder = Encoder::new_with_options(
+        (w, h),
+        EncoderOptions {
+            encoding_config: Some(EncodingConfig {
+                encoding_type: EncodingType::Lossy(Default::default()),
+                quality: 80.0,
+                method: 4,
+            }),
+            ..Default::default()
+        },
+    )
+    .map_err(|e| anyhow!("webp encoder init: {:?}", e))?;
+    for (i, (frame, at)) in decoded.frames.iter().enumerate() {
+        let frame = if (w, h) == frame.dimensions() {
+            frame.clone()
+        } else {
+            image::imageops::resize(frame, w, h, image::imageops::FilterType::Triangle)
+        };
+        encoder
+            .add_frame(frame.as_raw(), *at as i32)
+            .map_err(|e| anyhow!("webp add_frame[{}]: {:?}", i, e))?;
+    }
+    let webp = encoder
+        .finalize(decoded.duration_ms.max(1) as i32)
+        .map_err(|e| anyhow!("webp finalize: {:?}", e))?;
+    Ok(webp.to_vec())
+}
+
+/// The animation's metadata and, when it needs it, the converted WebP.
+fn convert(bytes: &[u8], size: u32) -> (Option<AnimationInfo>, Option<Vec<u8>>) {
+    let Ok(decoded) = decode(bytes) else {
+        return (None, None);
+    };
+    let info = decoded.info();
+    let converted = if needs_converting(&info, bytes.len(), size) {
+        encode(&decoded, size).ok()
+    } else {
+        None
+    };
+    (Some(info), converted)
+}
+
+fn cache_dir() -> Result<PathBuf> {
+    let dir = crate::services::cache_service::get_app_data_dir()?.join("emote_frames");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// File-name-safe form of an emote id.
+fn file_stem(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Metadata for emote `id` and, when it's an oversized animation, its
+/// converted file at `size` (snapped to `SIZES`). Cached on disk, so only the
+/// first call per emote downloads and decodes it.
+pub async fn prepare(id: &str, url: &str, size: u32) -> Result<PreparedEmote> {
+    let size = snap_size(size);
+    let dir = cache_dir()?;
+    let stem = file_stem(id);
+    let info_path = dir.join(format!("{}.json", stem));
+    let webp_path = dir.join(format!("{}_{}.webp", stem, size));
+
+    let cached_info: Option<Option<AnimationInfo>> = std::fs::read_to_string(&info_path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok());
+    if let Some(info) = &cached_info {
+        let converted = webp_path.exists();
+        let settled = match info {
+            Some(info) => converted || info.frame_count <= 1 || info.height <= size,
+            None => true,
+        };
+        if settled {
+            return Ok(PreparedEmote {
+                id: id.to_string(),
+                info: info.clone(),
+                path: converted.then(|| webp_path.to_string_lossy().to_string()),
+            });
+        }
+    }
+
+    let mut response = crate::services::http::client()
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?;
+    if response
+        .content_length()
+        .is_some_and(|len| len as usize > MAX_SOURCE_BYTES)
+    {
+        return Err(anyhow!("{} is too large to convert", id));
+    }
+    // Content-Length can be missing or wrong, so cap the body as it streams.
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if bytes.len() + chunk.len() > MAX_SOURCE_BYTES {
+            return Err(anyhow!("{} is too large to convert", id));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    let (info, converted) = tokio::task::spawn_blocking(move || convert(&bytes, size)).await?;
+
+    std::fs::write(&info_path, serde_json::to_string(&info)?)?;
+    let path = match converted {
+        Some(webp) => {
+            std::fs::write(&webp_path, webp)?;
+            Some(webp_path.to_string_lossy().to_string())
+        }
+        None => None,
+    };
+    Ok(PreparedEmote {
+        id: id.to_string(),
+        info,
+        path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::codecs::gif::GifEncoder;
+    use image::{Delay, Frame, Rgba};
+
+    fn gif(width: u32, height: u32, frames: u32) -> Vec<u8> {
+        let mut out = Vec::new();
+        {
+            let mut encoder = GifEncoder::new(&mut out);
+            for i in 0..frames {
+                let image = RgbaImage::from_pixel(width, height, Rgba([i as u8 * 40, 0, 0, 255]));
+                encoder
+                    .encode_frame(Frame::from_parts(
+                        image,
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(50, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn sizes_snap_and_scale_down_only() {
+        assert_eq!(snap_size(30), 28);
+        assert_eq!(snap_size(90), 112);
+        assert_eq!(target_dims(200, 100, 56), (112, 56));
+        assert_eq!(target_dims(20, 20, 56), (20, 20));
+    }
+
+    #[test]
+    fn decoding_stops_at_the_caps() {
+        assert!(check_budget(MAX_FRAMES, 1, 1).is_ok());
+        assert!(check_budget(MAX_FRAMES + 1, 1, 1).is_err());
+        assert!(check_budget(1, 16_384, 16_384).is_err());
+        assert!(decode(&gif(8, 8, 3)).is_ok());
+    }
+
+    #[test]
+    fn oversized_gif_is_read_and_converted() {
+        let decoded = decode(&gif(96, 64, 3)).unwrap();
+        let info = decoded.info();
+        assert_eq!((info.frame_count, info.duration_ms), (3, 150));
+        assert!(needs_converting(&info, 1000, 28));
+        assert!(!needs_converting(&info, 1000, 112));
+
+        let webp = encode(&decoded, 28).unwrap();
+        let converted = decode(&webp).unwrap();
+        assert_eq!((converted.width, converted.height), (42, 28));
+        assert_eq!(converted.frames.len(), 3);
+    }
+}
@@ -16,29 +16,33 @@
 // are not hammered, and dedup means a global/shared emote downloads exactly once
 // across all follows.
 
+use futures::stream::{FuturesUnordered, StreamExt};
 use log::{debug, warn};
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 use tokio::task::JoinSet;
 
+use crate::services::download_pool::{self, Priority};
 use crate::services::emote_service::{
     seventv_circuit_open, Emote, EmoteProvider, EmoteService, EmoteSet,
 };
+use crate::services::event_bus;
 use crate::services::twitch_service::TwitchService;
 use crate::services::universal_cache_service::{
-    download_file_to_disk, get_cached_files_list, save_cached_items_batch, CacheType,
+    download_file_to_disk, get_cached_files_list, save_cached_items_batch, CacheType, FileWrites,
     UniversalCacheEntry,
 };
 
 /// How many channels' emote lists to fetch at once during planning. Each fetch
 /// internally fans out to 4 providers, so keep this modest.
 const SCAN_CONCURRENCY: usize = 8;
-/// How many emote image downloads to keep in flight. AFK, so aggressive, but
-/// capped to stay a decent citizen to the provider CDNs.
+/// How many emote image downloads to keep queued on the download pool, which
+/// decides how many actually run (and holds them back while a stream plays).
 const DOWNLOAD_CONCURRENCY: usize = 16;
+/// Download pool group, for its status and for cancelling our queued jobs.
+const POOL_GROUP: &str = "emote_prefetch";
 /// Flush the manifest to memory once this many file entries have accumulated.
 const MANIFEST_FLUSH_EVERY: usize = 150;
 /// Emit a progress event at most every this many completed downloads.
@@ -131,7 +135,7 @@ impl EmotePrefetchService {
 
     /// Phase 1: scan all follows and compute the to-download list. Spawns a
     /// background task and returns immediately; progress arrives via events.
-    pub async fn plan(&self, app_handle: AppHandle, tier: String) {
+    pub async fn plan(&self, tier: String) {
         {
             let p = self.progress.read().await;
             if p.phase == "scanning" || p.phase == "downloading" {
@@ -152,13 +156,13 @@ impl EmotePrefetchService {
         let cancel = self.cancel.clone();
 
         tokio::spawn(async move {
-            run_plan(emote_service, progress, plan, cancel, app_handle, tier).await;
+            run_plan(emote_service, progress, plan, cancel, tier).await;
         });
     }
 
     /// Phase 2: download the stashed to-download list. Spawns a background task
     /// and returns immediately; progress arrives via events.
-    pub async fn start(&self, app_handle: AppHandle) {
+    pub async fn start(&self) {
         {
             let p = self.progress.read().await;
             if p.phase == "downloading" {
@@ -180,7 +184,7 @@ impl EmotePrefetchService {
         let cancel = self.cancel.clone();
 
         tokio::spawn(async move {
-            run_downloads(items, progress, cancel, app_handle).await;
+            run_downloads(items, progress, cancel).await;
         });
     }
 }
@@ -214,9 +218,9 @@ fn set_emotes(set: &EmoteSet) -> impl Iterator<Item = &Emote> {
         .chain(set.kick.iter())
 }
 
-async fn emit_progress(progress: &Arc<RwLock<PrefetchProgress>>, app_handle: &AppHandle) {
+async fn emit_progress(progress: &Arc<RwLock<PrefetchProgress>>) {
     let snapshot = progress.read().await.clone();
-    let _ = app_handle.emit(EVENT_PROGRESS, &snapshot);
+    let _ = event_bus::emit(EVENT_PROGRESS, &snapshot);
 }
 
 async fn run_plan(
@@ -224,7 +228,6 @@ async fn run_plan(
     progress: Arc<RwLock<PrefetchProgress>>,
     plan: Arc<RwLock<Vec<PrefetchItem>>>,
     cancel: Arc<RwLock<bool>>,
-    app_handle: AppHandle,
     tier: String,
 ) {
     // Auth token for user-specific Twitch emotes (sub/follower/bits). Optional —
@@ -244,7 +247,7 @@ async fn run_plan(
                 "7TV's API is unavailable right now, so 7TV emotes can't be scanned. This count will be incomplete — re-scan when 7TV recovers.".to_string(),
             );
         }
-        emit_progress(&progress, &app_handle).await;
+        emit_progress(&progress).await;
     }
 
     // Drain the full followed-channels list (live or offline).
@@ -277,7 +280,7 @@ async fn run_plan(
         let mut p = progress.write().await;
         p.channels_total = channels.len();
     }
-    emit_progress(&progress, &app_handle).await;
+    emit_progress(&progress).await;
 
     // Discovered emotes deduped by cache key -> url.
     let mut discovered: HashMap<String, String> = HashMap::new();
@@ -321,7 +324,7 @@ async fn run_plan(
                 p.current_channel = Some(login);
                 p.total_emotes = discovered.len();
             }
-            emit_progress(&progress, &app_handle).await;
+            emit_progress(&progress).await;
         }
 
         if *cancel.read().await {
@@ -330,7 +333,7 @@ async fn run_plan(
             p.phase = "cancelled".to_string();
             p.current_channel = None;
             drop(p);
-            emit_progress(&progress, &app_handle).await;
+            emit_progress(&progress).await;
             return;
         }
 
@@ -385,7 +388,7 @@ async fn run_plan(
         already_cached,
         to_download_count
     );
-    emit_progress(&progress, &app_handle).await;
+    emit_progress(&progress).await;
 }
 
 fn spawn_scan(
@@ -409,29 +412,25 @@ async fn run_downloads(
     items: Vec<PrefetchItem>,
     progress: Arc<RwLock<PrefetchProgress>>,
     cancel: Arc<RwLock<bool>>,
-    app_handle: AppHandle,
 ) {
+    let _writes = FileWrites::begin();
     let mut iter = items.into_iter();
-    let mut join_set: JoinSet<anyhow::Result<UniversalCacheEntry>> = JoinSet::new();
+    let mut in_flight = FuturesUnordered::new();
     let mut buffer: Vec<UniversalCacheEntry> = Vec::new();
     let mut since_emit = 0usize;
+    let submit = |item: PrefetchItem| {
+        download_pool::run(
+            Priority::Background,
+            POOL_GROUP,
+            download_file_to_disk(CacheType::Emote, item.key, item.url, EXPIRY_DAYS),
+        )
+    };
 
     // Prime the download pool.
-    for _ in 0..DOWNLOAD_CONCURRENCY {
-        if let Some(item) = iter.next() {
-            join_set.spawn(download_file_to_disk(
-                CacheType::Emote,
-                item.key,
-                item.url,
-                EXPIRY_DAYS,
-            ));
-        } else {
-            break;
-        }
-    }
+    in_flight.extend(iter.by_ref().take(DOWNLOAD_CONCURRENCY).map(submit));
 
-    while let Some(joined) = join_set.join_next().await {
-        match joined {
+    while let Some(result) = in_flight.next().await {
+        match result {
             Ok(Ok(entry)) => {
                 buffer.push(entry);
                 progress.write().await.downloaded += 1;
@@ -449,21 +448,22 @@ async fn run_downloads(
         since_emit += 1;
         if since_emit >= PROGRESS_EMIT_EVERY {
             since_emit = 0;
-            emit_progress(&progress, &app_handle).await;
+            emit_progress(&progress).await;
+        }
+
+        // Hold while a game runs (services::game_mode); what's already in
+        // flight finishes.
+        while crate::services::game_mode::prefetch_paused() && !*cancel.read().await {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
         }
 
         if *cancel.read().await {
-            join_set.abort_all();
+            download_pool::cancel_group(POOL_GROUP);
             break;
         }
 
         if let Some(item) = iter.next() {
-            join_set.spawn(download_file_to_disk(
-                CacheType::Emote,
-                item.key,
-                item.url,
-                EXPIRY_DAYS,
-            ));
+            in_flight.push(submit(item));
         }
     }
 
@@ -482,7 +482,7 @@ async fn run_downloads(
         };
         p.current_channel = None;
     }
-    emit_progress(&progress, &app_handle).await;
+    emit_progress(&progress).await;
     let final_snapshot = progress.read().await.clone();
-    let _ = app_handle.emit(EVENT_COMPLETE, &final_snapshot);
+    let _ = event_bus::emit(EVENT_COMPLETE, &final_snapshot);
 }
@@ -88,6 +88,7 @@ pub async fn fetch_personal_emote_set(set_id: &str) -> Vec<Emote> {
                 url: format!("https://cdn.7tv.app/emote/{}/1x.avif", id),
                 provider: EmoteProvider::SevenTV,
                 is_zero_width: Some((flags & 256) == 256),
+                animated: data.get("animated").and_then(|v| v.as_bool()),
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -121,6 +122,9 @@ pub struct Emote {
     /// Emote width in pixels (for aspect ratio sorting - wide emotes > 32)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub width: Option<u32>,
+    /// Whether the image moves; None when the provider doesn't say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animated: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -580,6 +584,9 @@ impl EmoteService {
                             ),
                             provider: EmoteProvider::Twitch,
                             is_zero_width: None,
+                            animated: emote_data["format"]
+                                .as_array()
+                                .map(|f| f.iter().any(|v| v == "animated")),
                             local_url: None,
                             emote_type,
                             owner_id,
@@ -645,6 +652,7 @@ impl EmoteService {
                                     url: format!("https://cdn.betterttv.net/emote/{}/1x", id),
                                     provider: EmoteProvider::BTTV,
                                     is_zero_width: Some(is_modifier),
+                                    animated: item.get("animated").and_then(|v| v.as_bool()),
                                     local_url: None,
                                     emote_type: None,
                                     owner_id: None,
@@ -692,6 +700,7 @@ impl EmoteService {
                                         url: format!("https://cdn.betterttv.net/emote/{}/1x", id),
                                         provider: EmoteProvider::BTTV,
                                         is_zero_width: Some(is_modifier),
+                                        animated: item.get("animated").and_then(|v| v.as_bool()),
                                         local_url: None,
                                         emote_type: None,
                                         owner_id: None,
@@ -720,6 +729,7 @@ impl EmoteService {
                                         url: format!("https://cdn.betterttv.net/emote/{}/1x", id),
                                         provider: EmoteProvider::BTTV,
                                         is_zero_width: Some(is_modifier),
+                                        animated: item.get("animated").and_then(|v| v.as_bool()),
                                         local_url: None,
                                         emote_type: None,
                                         owner_id: None,
@@ -777,6 +787,7 @@ impl EmoteService {
                         defaultName
                         flags {
                             zeroWidth
+                            animated
                         }
                         host {
                             files {
@@ -831,6 +842,8 @@ impl EmoteService {
                                     .pointer("/flags/zeroWidth")
                                     .and_then(|v| v.as_bool())
                                     .unwrap_or(false);
+                                let animated =
+                                    item.pointer("/flags/animated").and_then(|v| v.as_bool());
                                 let width = item
                                     .pointer("/host/files/0/width")
                                     .and_then(|v| v.as_u64())
@@ -841,6 +854,7 @@ impl EmoteService {
                                     url: format!("https://cdn.7tv.app/emote/{}/1x.avif", id),
                                     provider: EmoteProvider::SevenTV,
                                     is_zero_width: Some(is_zero_width),
+                                    animated,
                                     local_url: None,
                                     emote_type: None,
                                     owner_id: None,
@@ -888,6 +902,7 @@ impl EmoteService {
                                     url: format!("https://cdn.7tv.app/emote/{}/1x.avif", id),
                                     provider: EmoteProvider::SevenTV,
                                     is_zero_width: Some((flags & 256) == 256),
+                                    animated: emote_data.get("animated").and_then(|v| v.as_bool()),
                                     local_url: None,
                                     emote_type: None,
                                     owner_id: None,
@@ -946,6 +961,9 @@ impl EmoteService {
                                         url: format!("https://cdn.7tv.app/emote/{}/1x.avif", id),
                                         provider: EmoteProvider::SevenTV,
                                         is_zero_width: Some((flags & 256) == 256),
+                                        animated: emote_data
+                                            .get("animated")
+                                            .and_then(|v| v.as_bool()),
                                         local_url: None,
                                         emote_type: None,
                                         owner_id: None,
@@ -1019,6 +1037,9 @@ impl EmoteService {
                                             url,
                                             provider: EmoteProvider::FFZ,
                                             is_zero_width: None,
+                                            animated: Some(
+                                                item.get("animated").is_some_and(|v| !v.is_null()),
+                                            ),
                                             local_url: None,
                                             emote_type: None,
                                             owner_id: None,
@@ -1067,6 +1088,10 @@ impl EmoteService {
                                                 url,
                                                 provider: EmoteProvider::FFZ,
                                                 is_zero_width: None,
+                                                animated: Some(
+                                                    item.get("animated")
+                                                        .is_some_and(|v| !v.is_null()),
+                                                ),
                                                 local_url: None,
                                                 emote_type: None,
                                                 owner_id: None,
@@ -1096,6 +1121,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/25/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1108,6 +1134,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/354/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1121,6 +1148,7 @@ impl EmoteService {
                     .to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1134,6 +1162,7 @@ impl EmoteService {
                     .to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1146,6 +1175,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/88/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1158,6 +1188,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/81273/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1170,6 +1201,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/81248/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1182,6 +1214,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/81249/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1194,6 +1227,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/81274/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1206,6 +1240,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/81997/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1219,6 +1254,7 @@ impl EmoteService {
                     .to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1232,6 +1268,7 @@ impl EmoteService {
                     .to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1245,6 +1282,7 @@ impl EmoteService {
                     .to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1257,6 +1295,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/245/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -1269,6 +1308,7 @@ impl EmoteService {
                 url: "https://static-cdn.jtvnw.net/emoticons/v2/1902/default/dark/3.0".to_string(),
                 provider: EmoteProvider::Twitch,
                 is_zero_width: None,
+                animated: None,
                 local_url: None,
                 emote_type: None,
                 owner_id: None,
@@ -64,6 +64,11 @@ fn now_secs() -> u64 {
 /// refresh in the background. Returns None when there is no file or it can't be
 /// parsed (a corrupt file is treated as absent and gets overwritten on next save).
 pub fn load(channel_id: &str) -> Option<EmoteSet> {
+    crate::services::channel_caches::touch(channel_id);
+    read(channel_id)
+}
+
+fn read(channel_id: &str) -> Option<EmoteSet> {
     let path = path_for(channel_id).ok()?;
     let bytes = match fs::read(&path) {
         Ok(b) => b,
@@ -90,7 +95,7 @@ pub fn load(channel_id: &str) -> Option<EmoteSet> {
 /// go through. Use this when the set's completeness is uncertain (e.g. the AFK
 /// prefetch). For a fetch known to be authoritative, use [`save_force`].
 pub fn save(channel_id: &str, set: &EmoteSet) {
-    if let Some(existing) = load(channel_id) {
+    if let Some(existing) = read(channel_id) {
         if set.seven_tv.len() < existing.seven_tv.len() {
             debug!(
                 "[EmoteSetCache] keeping stored set for {} (7TV {} >= incoming {}), not shrinking",
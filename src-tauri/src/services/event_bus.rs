@@ -0,0 +1,130 @@
+//! Where services send app-wide events, instead of holding an `AppHandle`
+//! just to call `emit`.
+//!
+//! The app installs `TauriBus` during setup, which broadcasts to every
+//! webview and to backend `listen` handlers (BackgroundService keeps its
+//! stats off `channel-points-earned` and friends), so it stays installed in
+//! headless mode too. `LogBus` and `NoopBus` stand in where there is no
+//! Tauri app: tests and tools. Until setup installs a bus, events are
+//! dropped, as they were before any window was listening.
+//!
+//! Window-targeted emits (media keys, global hotkeys to the main window)
+//! still go straight to that window.
+
+use anyhow::Result;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+use tauri::{AppHandle, Emitter};
+
+pub trait EventBus: Send + Sync {
+    fn emit(&self, event: &str, payload: Value) -> Result<()>;
+}
+
+/// Broadcasts through the Tauri event system.
+pub struct TauriBus(pub AppHandle);
+
+impl EventBus for TauriBus {
+    fn emit(&self, event: &str, payload: Value) -> Result<()> {
+        self.0.emit(event, payload)?;
+        Ok(())
+    }
+}
+
+/// Logs every event at debug level.
+pub struct LogBus;
+
+impl EventBus for LogBus {
+    fn emit(&self, event: &str, payload: Value) -> Result<()> {
+        debug!("[EventBus] {} {}", event, payload);
+        Ok(())
+    }
+}
+
+pub struct NoopBus;
+
+impl EventBus for NoopBus {
+    fn emit(&self, _event: &str, _payload: Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+static BUS: Lazy<RwLock<Arc<dyn EventBus>>> = Lazy::new(|| RwLock::new(Arc::new(NoopBus)));
+
+/// Route every later `emit` to `bus`.
+pub fn install(bus: Arc<dyn EventBus>) {
+    *BUS.write().unwrap() = bus;
+}
+
+/// Send `event` to whatever bus is installed.
+pub fn emit<S: Serialize>(event: &str, payload: S) -> Result<()> {
+    let payload = serde_json::to_value(payload)?;
+    let bus = BUS.read().unwrap().clone();
+    bus.emit(event, payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Mutex, MutexGuard};
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(String, Value)>>);
+
+    impl EventBus for Recorder {
+        fn emit(&self, event: &str, payload: Value) -> Result<()> {
+            self.0.lock().unwrap().push((event.to_string(), payload));
+            Ok(())
+        }
+    }
+
+    /// Held by any test that swaps the process-wide bus.
+    static BUS_SWAP: Mutex<()> = Mutex::new(());
+
+    /// `bus` stays installed while this lives; dropping it (a failed
+    /// assertion included) puts `NoopBus` back.
+    struct Installed {
+        _swap: MutexGuard<'static, ()>,
+    }
+
+    impl Installed {
+        fn new(bus: Arc<dyn EventBus>) -> Self {
+            let swap = BUS_SWAP.lock().unwrap_or_else(|e| e.into_inner());
+            install(bus);
+            Self { _swap: swap }
+        }
+    }
+
+    impl Drop for Installed {
+        fn drop(&mut self) {
+            install(Arc::new(NoopBus));
+        }
+    }
+
+    #[test]
+    fn emits_reach_the_installed_bus() {
+        let recorder = Arc::new(Recorder::default());
+        let installed = Installed::new(recorder.clone());
+        emit("event-bus-test", serde_json::json!({ "active": true })).unwrap();
+        emit("event-bus-test", 2).unwrap();
+        drop(installed);
+        emit("event-bus-test", 3).unwrap();
+
+        // Other tests run alongside and may emit their own events meanwhile.
+        let events = recorder.0.lock().unwrap();
+        let payloads: Vec<&Value> = events
+            .iter()
+            .filter(|(event, _)| event == "event-bus-test")
+            .map(|(_, payload)| payload)
+            .collect();
+        assert_eq!(
+            payloads,
+            [
+                &serde_json::json!({ "active": true }),
+                &serde_json::json!(2)
+            ]
+        );
+    }
+}
@@ -25,11 +25,11 @@ use log::{debug, error, warn};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, OnceLock};
-use tauri::{AppHandle, Emitter};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{sleep, timeout, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+use crate::services::event_bus;
 use crate::services::twitch_service::TwitchService;
 
 const EVENTSUB_URL: &str = "wss://eventsub.wss.twitch.tv/ws";
@@ -60,7 +60,7 @@ static SERVICE: OnceLock<Service> = OnceLock::new();
 /// Initialize the singleton. The socket is opened lazily on the first subscribe
 /// (Twitch closes a subscription-less connection), so this just wires up the
 /// command channel and spawns the background task. Idempotent.
-pub fn init(app_handle: AppHandle) {
+pub fn init() {
     if SERVICE.get().is_some() {
         return;
     }
@@ -73,7 +73,7 @@ pub fn init(app_handle: AppHandle) {
     // init() runs from the Tauri setup hook, which is OUTSIDE the Tokio runtime,
     // so a bare tokio::spawn panics. Use tauri::async_runtime::spawn (same idiom
     // as seventv_eventapi).
-    tauri::async_runtime::spawn(connection_loop(app_handle, subs, cmd_rx));
+    tauri::async_runtime::spawn(connection_loop(subs, cmd_rx));
     let _ = SERVICE.set(service);
 }
 
@@ -222,7 +222,6 @@ async fn delete_subscription(sub_id: &str) {
 }
 
 async fn connection_loop(
-    app_handle: AppHandle,
     subs: Arc<RwLock<HashMap<String, ChannelSub>>>,
     mut cmd_rx: mpsc::UnboundedReceiver<Cmd>,
 ) {
@@ -240,7 +239,7 @@ async fn connection_loop(
             }
         }
 
-        if let Err(e) = connect_and_run(&app_handle, &subs, &mut cmd_rx).await {
+        if let Err(e) = connect_and_run(&subs, &mut cmd_rx).await {
             error!("[EventSub Mod] connection ended: {}", e);
         }
 
@@ -253,7 +252,6 @@ async fn connection_loop(
 }
 
 async fn connect_and_run(
-    app_handle: &AppHandle,
     subs: &Arc<RwLock<HashMap<String, ChannelSub>>>,
     cmd_rx: &mut mpsc::UnboundedReceiver<Cmd>,
 ) -> Result<()> {
@@ -342,7 +340,7 @@ async fn connect_and_run(
                     }
                     Ok(None) => return Ok(()),
                     Ok(Some(Ok(Message::Text(txt)))) => {
-                        if handle_text(&txt, app_handle) {
+                        if handle_text(&txt) {
                             return Ok(()); // server asked us to reconnect
                         }
                     }
@@ -381,7 +379,7 @@ async fn connect_and_run(
 }
 
 /// Handle one server frame. Returns true if the server asked us to reconnect.
-fn handle_text(txt: &str, app_handle: &AppHandle) -> bool {
+fn handle_text(txt: &str) -> bool {
     let Ok(v) = serde_json::from_str::<Value>(txt) else {
         return false;
     };
@@ -398,7 +396,7 @@ fn handle_text(txt: &str, app_handle: &AppHandle) -> bool {
                 if let Some(event) = v.pointer("/payload/event") {
                     // Same event name the stream EventSub service used; the main
                     // window and each popout feed their own mod-log store.
-                    let _ = app_handle.emit("eventsub://channel-moderate", event);
+                    let _ = event_bus::emit("eventsub://channel-moderate", event);
                 }
             }
             false
@@ -1,3 +1,4 @@
+use crate::services::event_bus;
 use crate::services::twitch_service::TwitchService;
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
@@ -5,7 +6,6 @@ use log::{debug, error};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{interval, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
@@ -351,11 +351,7 @@ impl EventSubService {
         }
     }
 
-    pub async fn connect_and_listen(
-        &self,
-        broadcaster_id: String,
-        app_handle: AppHandle,
-    ) -> Result<()> {
+    pub async fn connect_and_listen(&self, broadcaster_id: String) -> Result<()> {
         // First, stop any existing connection
         self.disconnect().await;
 
@@ -386,7 +382,6 @@ impl EventSubService {
                 }
                 result = Self::run_connection(
                     broadcaster_id.clone(),
-                    app_handle.clone(),
                     connected.clone(),
                     session_id.clone(),
                     subscriptions.clone(),
@@ -420,7 +415,6 @@ impl EventSubService {
 
     async fn run_connection(
         broadcaster_id: String,
-        app_handle: AppHandle,
         connected: Arc<RwLock<bool>>,
         session_id: Arc<RwLock<Option<String>>>,
         subscriptions: Arc<RwLock<Vec<String>>>,
@@ -516,19 +510,13 @@ impl EventSubService {
 
         // Subscribe to events using the session ID
         let sess_id = session_info.id.clone();
-        let app_handle_subs = app_handle.clone();
         tokio::spawn(async move {
             // Wait a moment for the connection to stabilize
             tokio::time::sleep(Duration::from_secs(1)).await;
 
-            if let Err(e) = Self::subscribe_to_events(
-                &broadcaster_id,
-                &sess_id,
-                &app_handle_subs,
-                mod_channels,
-                mod_sub_ids,
-            )
-            .await
+            if let Err(e) =
+                Self::subscribe_to_events(&broadcaster_id, &sess_id, mod_channels, mod_sub_ids)
+                    .await
             {
                 error!("Failed to subscribe to EventSub events: {}", e);
             }
@@ -578,7 +566,7 @@ impl EventSubService {
                         *last_msg = std::time::Instant::now();
                     }
 
-                    if let Err(e) = Self::handle_message(&text, &app_handle).await {
+                    if let Err(e) = Self::handle_message(&text).await {
                         error!("Error handling message: {}", e);
                     }
                 }
@@ -607,7 +595,7 @@ impl EventSubService {
         Ok(())
     }
 
-    async fn handle_message(text: &str, app_handle: &AppHandle) -> Result<()> {
+    async fn handle_message(text: &str) -> Result<()> {
         // Handle potential empty messages
         if text.trim().is_empty() {
             debug!("Received empty message, ignoring");
@@ -632,7 +620,7 @@ impl EventSubService {
             }
             "notification" => {
                 let notification: NotificationPayload = serde_json::from_value(message.payload)?;
-                Self::handle_notification(notification, app_handle).await?;
+                Self::handle_notification(notification).await?;
             }
             "session_reconnect" => {
                 debug!("Server requested reconnection");
@@ -655,10 +643,7 @@ impl EventSubService {
         Ok(())
     }
 
-    async fn handle_notification(
-        notification: NotificationPayload,
-        app_handle: &AppHandle,
-    ) -> Result<()> {
+    async fn handle_notification(notification: NotificationPayload) -> Result<()> {
         debug!(
             "Received notification: {}",
             notification.subscription.subscription_type
@@ -676,7 +661,7 @@ impl EventSubService {
                         raid_event.to_broadcaster_user_name,
                         raid_event.viewers
                     );
-                    let _ = app_handle.emit("eventsub://raid", &raid_event);
+                    let _ = event_bus::emit("eventsub://raid", &raid_event);
                 }
             }
             "stream.offline" => {
@@ -685,7 +670,7 @@ impl EventSubService {
                     serde_json::from_value::<StreamOfflineEvent>(notification.event.clone())
                 {
                     debug!("Stream offline: {}", offline_event.broadcaster_user_name);
-                    let _ = app_handle.emit("eventsub://offline", &offline_event);
+                    let _ = event_bus::emit("eventsub://offline", &offline_event);
                 }
             }
             "stream.online" => {
@@ -694,7 +679,7 @@ impl EventSubService {
                     serde_json::from_value::<StreamOnlineEvent>(notification.event.clone())
                 {
                     debug!("Stream online: {}", online_event.broadcaster_user_name);
-                    let _ = app_handle.emit("eventsub://online", &online_event);
+                    let _ = event_bus::emit("eventsub://online", &online_event);
                 }
             }
             "channel.update" => {
@@ -706,7 +691,13 @@ impl EventSubService {
                         "Channel updated: \"{}\" - {}",
                         update_event.title, update_event.category_name
                     );
-                    let _ = app_handle.emit("eventsub://channel-update", &update_event);
+                    let _ = event_bus::emit("eventsub://channel-update", &update_event);
+                    crate::services::stream_info_poller::observe_channel_update(
+                        &update_event.broadcaster_user_login,
+                        &update_event.title,
+                        &update_event.category_id,
+                        &update_event.category_name,
+                    );
                 }
             }
             "channel.moderate" => {
@@ -719,12 +710,12 @@ impl EventSubService {
                         .and_then(|a| a.as_str())
                         .unwrap_or("unknown")
                 );
-                let _ = app_handle.emit("eventsub://channel-moderate", &notification.event);
+                let _ = event_bus::emit("eventsub://channel-moderate", &notification.event);
             }
             "channel.channel_points_automatic_reward_redemption.add" => {
                 // Handle automatic channel points rewards
                 debug!("Automatic channel points reward redeemed");
-                let _ = app_handle.emit("channel-points-automatic-reward", &notification.event);
+                let _ = event_bus::emit("channel-points-automatic-reward", &notification.event);
             }
             "channel.channel_points_custom_reward_redemption.add" => {
                 // Handle custom channel points reward redemptions
@@ -735,7 +726,7 @@ impl EventSubService {
                         "Custom reward redeemed: {} by {}",
                         redemption.reward.title, redemption.user_name
                     );
-                    let _ = app_handle.emit("channel-points-redemption", &redemption);
+                    let _ = event_bus::emit("channel-points-redemption", &redemption);
                 }
             }
             "user.whisper.message" => {
@@ -761,7 +752,7 @@ impl EventSubService {
                         "text": whisper_event.whisper.text
                     });
 
-                    let _ = app_handle.emit("whisper-received", &whisper_data);
+                    let _ = event_bus::emit("whisper-received", &whisper_data);
                 }
             }
             "channel.hype_train.begin" => {
@@ -772,7 +763,7 @@ impl EventSubService {
                         "Hype Train started! Level {} - Goal: {}/{}",
                         event.level, event.progress, event.goal
                     );
-                    let _ = app_handle.emit("eventsub://hype-train-begin", &event);
+                    let _ = event_bus::emit("eventsub://hype-train-begin", &event);
                 }
             }
             "channel.hype_train.progress" => {
@@ -783,7 +774,11 @@ impl EventSubService {
                         "Hype Train progress: Level {} - {}/{}",
                         event.level, event.progress, event.goal
                     );
-                    let _ = app_handle.emit("eventsub://hype-train-progress", &event);
+                    // Progress ticks only animate the on-screen meter; begin/end
+                    // still go out so the train's state stays right.
+                    if crate::services::power_saver::ui_events_allowed() {
+                        let _ = event_bus::emit("eventsub://hype-train-progress", &event);
+                    }
                 }
             }
             "channel.hype_train.end" => {
@@ -791,7 +786,7 @@ impl EventSubService {
                     serde_json::from_value::<HypeTrainEndEvent>(notification.event.clone())
                 {
                     debug!("Hype Train ended at Level {}!", event.level);
-                    let _ = app_handle.emit("eventsub://hype-train-end", &event);
+                    let _ = event_bus::emit("eventsub://hype-train-end", &event);
                 }
             }
             _ => {
@@ -808,7 +803,6 @@ impl EventSubService {
     async fn subscribe_to_events(
         broadcaster_id: &str,
         session_id: &str,
-        app_handle: &AppHandle,
         mod_channels: Arc<RwLock<HashSet<String>>>,
         mod_sub_ids: Arc<RwLock<HashMap<String, String>>>,
     ) -> Result<()> {
@@ -925,7 +919,7 @@ impl EventSubService {
                     error!("Failed to subscribe to {}: {}", event_type, error_text);
                     // Surface the failure to the UI so a silently-broken feed
                     // (e.g. channel.moderate missing a scope) isn't invisible.
-                    let _ = app_handle.emit(
+                    let _ = event_bus::emit(
                         "eventsub://subscription-failed",
                         serde_json::json!({
                             "type": event_type,
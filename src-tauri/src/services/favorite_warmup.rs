@@ -0,0 +1,109 @@
+//! Warm-up for the top pinned favorites (`Settings::favorite_warmup`).
+//!
+//! Every poll checks which of the first `count` pinned channels are live, and
+//! for each one that came online since the last poll fetches its playback
+//! token and master ahead of time (`auth_proxy::warm_master`). Clicking Watch
+//! within the next few minutes then resolves from the warmed master instead of
+//! waiting on GQL and usher. Nothing is played or downloaded beyond the
+//! master.
+//!
+//! The first poll treats every live favorite as new, so channels already live
+//! at launch are warmed too.
+
+use crate::models::settings::AppState;
+use crate::services::auth_proxy;
+use crate::services::twitch_service::TwitchService;
+use log::debug;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Logins in `live` that weren't in `previous`.
+fn newly_live(previous: &HashSet<String>, live: &HashSet<String>) -> Vec<String> {
+    let mut fresh: Vec<String> = live.difference(previous).cloned().collect();
+    fresh.sort();
+    fresh
+}
+
+/// The first `count` pinned logins, or none while warm-up is off.
+fn targets(app: &AppHandle) -> Vec<String> {
+    let state = app.state::<AppState>();
+    let settings = state.settings.lock().unwrap();
+    if !settings.favorite_warmup.enabled {
+        return Vec::new();
+    }
+    settings
+        .pinned_channels
+        .iter()
+        .take(settings.favorite_warmup.count as usize)
+        .map(|c| c.login.clone())
+        .collect()
+}
+
+/// Start the warm-up poll. Called once by `BackgroundService::start`.
+pub fn start(app: AppHandle) {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        let mut live: HashSet<String> = HashSet::new();
+        loop {
+            ticker.tick().await;
+            // Held while a game runs (services::game_mode).
+            if crate::services::game_mode::prefetch_paused() {
+                continue;
+            }
+            let logins = targets(&app);
+            if logins.is_empty() {
+                live.clear();
+                continue;
+            }
+            let streams = match TwitchService::check_streams_online(&logins).await {
+                Ok(streams) => streams,
+                Err(e) => {
+                    debug!("[Warmup] Live check failed: {}", e);
+                    continue;
+                }
+            };
+            let now: HashSet<String> = streams
+                .iter()
+                .map(|s| s.user_login.to_lowercase())
+                .collect();
+            let fresh = newly_live(&live, &now);
+            live = now;
+            if fresh.is_empty() {
+                continue;
+            }
+
+            let token = app.state::<AppState>().twitch_auth.get_token().await.ok();
+            for login in fresh {
+                match auth_proxy::warm_master(&login, token.as_deref()).await {
+                    Ok(()) => debug!("[Warmup] Warmed {}", login),
+                    Err(e) => debug!("[Warmup] Couldn't warm {}: {}", login, e),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(logins: &[&str]) -> HashSet<String> {
+        logins.iter().map(|l| l.to_string()).collect()
+    }
+
+    #[test]
+    fn only_channels_that_came_online_are_warmed() {
+        assert_eq!(newly_live(&set(&[]), &set(&["b", "a"])), ["a", "b"]);
+        assert!(newly_live(&set(&["a", "b"]), &set(&["a"])).is_empty());
+        assert_eq!(newly_live(&set(&["a"]), &set(&["a", "c"])), ["c"]);
+    }
+}
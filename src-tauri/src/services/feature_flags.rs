@@ -0,0 +1,206 @@
+//! Kill switches for the features that ride on undocumented Twitch
+//! endpoints (`get_feature_flags`).
+//!
+//! When Twitch changes a GraphQL operation or an ingest endpoint, the loop
+//! using it fails on every pass. Rather than retrying and logging forever, a
+//! feature that fails `FAILURE_THRESHOLD` times in a row is switched off: its
+//! loop checks `enabled` and skips the work, the user gets a
+//! `feature-disabled` event, and after a backoff one probe is let through. A
+//! probe that succeeds switches the feature back on (`feature-restored`); one
+//! that fails doubles the wait, up to two hours.
+//!
+//! Callers report only failures that point at the endpoint (GraphQL errors,
+//! rejected requests, unexpected response shapes), not dropped connections.
+//! State is per session; a restart starts with everything on.
+
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, info, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+
+/// Consecutive failures that switch a feature off.
+const FAILURE_THRESHOLD: u32 = 5;
+const INITIAL_BACKOFF_MINS: i64 = 10;
+const MAX_BACKOFF_MINS: i64 = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    /// Followed-channel balance polling (ChannelPointsContext).
+    ChannelPoints,
+    /// Minute-watched payloads for the watched channel (spade and
+    /// sendSpadeEvents).
+    SpadePayloads,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 2] = [Feature::ChannelPoints, Feature::SpadePayloads];
+
+    fn label(self) -> &'static str {
+        match self {
+            Feature::ChannelPoints => "Channel points",
+            Feature::SpadePayloads => "Watch-time reporting",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct FlagState {
+    failures: u32,
+    last_error: Option<String>,
+    /// Set while the feature is off; a probe is let through once it passes.
+    retry_at: Option<DateTime<Utc>>,
+    backoff_mins: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureStatus {
+    pub feature: Feature,
+    pub label: &'static str,
+    pub enabled: bool,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+struct FeatureEvent<'a> {
+    feature: Feature,
+    label: &'static str,
+    error: Option<&'a str>,
+    retry_at: Option<DateTime<Utc>>,
+}
+
+static FLAGS: Lazy<StdMutex<HashMap<Feature, FlagState>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Whether `feature`'s loop should run this pass: it's on, or it's off and
+/// due a probe.
+pub fn enabled(feature: Feature) -> bool {
+    let flags = FLAGS.lock().unwrap();
+    flags
+        .get(&feature)
+        .and_then(|f| f.retry_at)
+        .map_or(true, |at| Utc::now() >= at)
+}
+
+/// A pass of `feature` worked; switches it back on if it was off.
+pub fn record_success(feature: Feature) {
+    let mut flags = FLAGS.lock().unwrap();
+    let Some(state) = flags.remove(&feature) else {
+        return;
+    };
+    if state.retry_at.is_some() {
+        info!("[FeatureFlags] {} is working again", feature.label());
+        let _ = crate::services::event_bus::emit(
+            "feature-restored",
+            FeatureEvent {
+                feature,
+                label: feature.label(),
+                error: None,
+                retry_at: None,
+            },
+        );
+    }
+}
+
+/// A pass of `feature` failed in a way that points at the endpoint.
+pub fn record_failure(feature: Feature, error: &str) {
+    let mut flags = FLAGS.lock().unwrap();
+    let state = flags.entry(feature).or_default();
+    state.failures += 1;
+    state.last_error = Some(error.to_string());
+
+    if state.retry_at.is_some() {
+        // A failed probe: stay off, wait longer.
+        state.backoff_mins = (state.backoff_mins * 2).min(MAX_BACKOFF_MINS);
+        state.retry_at = Some(Utc::now() + Duration::minutes(state.backoff_mins));
+        debug!(
+            "[FeatureFlags] {} probe failed, next in {}m: {}",
+            feature.label(),
+            state.backoff_mins,
+            error
+        );
+        return;
+    }
+    if state.failures < FAILURE_THRESHOLD {
+        return;
+    }
+
+    state.backoff_mins = INITIAL_BACKOFF_MINS;
+    let retry_at = Utc::now() + Duration::minutes(INITIAL_BACKOFF_MINS);
+    state.retry_at = Some(retry_at);
+    warn!(
+        "[FeatureFlags] {} failed {} times in a row, turning it off until {}: {}",
+        feature.label(),
+        state.failures,
+        retry_at,
+        error
+    );
+    let _ = crate::services::event_bus::emit(
+        "feature-disabled",
+        FeatureEvent {
+            feature,
+            label: feature.label(),
+            error: Some(error),
+            retry_at: Some(retry_at),
+        },
+    );
+}
+
+/// True when `error` is a connection that never got an answer (offline,
+/// DNS, timeout), which says nothing about the endpoint.
+pub fn is_transport_error(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(|e| e.is_connect() || e.is_timeout() || e.is_request())
+}
+
+/// Switch `feature` back on now, without waiting for its probe.
+pub fn reset(feature: Feature) {
+    FLAGS.lock().unwrap().remove(&feature);
+}
+
+pub fn status() -> Vec<FeatureStatus> {
+    let flags = FLAGS.lock().unwrap();
+    Feature::ALL
+        .iter()
+        .map(|&feature| {
+            let state = flags.get(&feature).cloned().unwrap_or_default();
+            FeatureStatus {
+                feature,
+                label: feature.label(),
+                enabled: state.retry_at.is_none(),
+                consecutive_failures: state.failures,
+                last_error: state.last_error,
+                retry_at: state.retry_at,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_failures_switch_a_feature_off_until_it_works() {
+        let feature = Feature::SpadePayloads;
+        reset(feature);
+        for _ in 0..FAILURE_THRESHOLD - 1 {
+            record_failure(feature, "rejected");
+        }
+        assert!(enabled(feature));
+        record_failure(feature, "rejected");
+        assert!(!enabled(feature));
+
+        record_success(feature);
+        assert!(enabled(feature));
+        let status = status();
+        let entry = status.iter().find(|s| s.feature == feature).unwrap();
+        assert!(entry.enabled);
+        assert_eq!(entry.consecutive_failures, 0);
+    }
+}
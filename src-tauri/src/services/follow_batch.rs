@@ -0,0 +1,208 @@
+//! Unfollowing many channels at once, with an undo.
+//!
+//! `unfollow` starts a batch in the background and returns its id. The
+//! channels go one at a time, `PACE` apart, so hundreds of them don't trip
+//! Twitch's rate limit; a rate-limited request waits `BACKOFF` and is tried
+//! once more. Progress goes out as `unfollow-batch-progress` {batch_id, done,
+//! total, failed, current} and the end as `unfollow-batch-complete`
+//! {batch_id, unfollowed, failed, undo_secs}.
+//!
+//! For `UNDO_WINDOW` after a batch ends, `undo` follows its channels again
+//! (paced the same way, reported with `undo: true`). One batch runs at a time.
+
+use crate::services::event_bus;
+use crate::services::twitch_service::TwitchService;
+use anyhow::{bail, Result};
+use log::{debug, warn};
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+const PACE: Duration = Duration::from_millis(750);
+const BACKOFF: Duration = Duration::from_secs(10);
+pub const UNDO_WINDOW: Duration = Duration::from_secs(30);
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+static UNDO: StdMutex<Option<Undo>> = StdMutex::new(None);
+
+/// The channels a finished batch unfollowed, while they can be followed back.
+struct Undo {
+    batch_id: u64,
+    channel_ids: Vec<String>,
+    until: Instant,
+}
+
+impl Undo {
+    fn usable(&self, batch_id: u64, now: Instant) -> bool {
+        self.batch_id == batch_id && now < self.until
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedChannel {
+    pub channel_id: String,
+    pub error: String,
+}
+
+fn is_rate_limited(error: &str) -> bool {
+    error.contains("429") || error.to_lowercase().contains("rate limit")
+}
+
+/// Run `step` on each channel, paced, emitting progress. Returns the channels
+/// it succeeded for and those it didn't.
+async fn run<F, Fut>(
+    batch_id: u64,
+    undo: bool,
+    channel_ids: &[String],
+    step: F,
+) -> (Vec<String>, Vec<FailedChannel>)
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut done = Vec::new();
+    let mut failed = Vec::new();
+    for (i, channel_id) in channel_ids.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(PACE).await;
+        }
+        let mut result = step(channel_id.clone()).await;
+        if matches!(&result, Err(e) if is_rate_limited(&e.to_string())) {
+            debug!("[FollowBatch] Rate limited, waiting {:?}", BACKOFF);
+            tokio::time::sleep(BACKOFF).await;
+            result = step(channel_id.clone()).await;
+        }
+        match result {
+            Ok(()) => done.push(channel_id.clone()),
+            Err(e) => failed.push(FailedChannel {
+                channel_id: channel_id.clone(),
+                error: e.to_string(),
+            }),
+        }
+        let _ = event_bus::emit(
+            "unfollow-batch-progress",
+            json!({
+                "batch_id": batch_id,
+                "undo": undo,
+                "done": i + 1,
+                "total": channel_ids.len(),
+                "failed": failed.len(),
+                "current": channel_id,
+            }),
+        );
+    }
+    (done, failed)
+}
+
+/// Start unfollowing `channel_ids`. Returns the batch id the events carry.
+pub fn unfollow(channel_ids: Vec<String>) -> Result<u64> {
+    let mut seen = HashSet::new();
+    let mut channel_ids = channel_ids;
+    channel_ids.retain(|id| !id.is_empty() && seen.insert(id.clone()));
+    if channel_ids.is_empty() {
+        bail!("No channels to unfollow");
+    }
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        bail!("Another batch is still running");
+    }
+    let batch_id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    // A new batch replaces the last one's undo.
+    *UNDO.lock().unwrap() = None;
+
+    tauri::async_runtime::spawn(async move {
+        let (unfollowed, failed) = run(batch_id, false, &channel_ids, |id| async move {
+            TwitchService::unfollow_channel(&id).await
+        })
+        .await;
+        debug!(
+            "[FollowBatch] Batch {}: {} unfollowed, {} failed",
+            batch_id,
+            unfollowed.len(),
+            failed.len()
+        );
+        *UNDO.lock().unwrap() = Some(Undo {
+            batch_id,
+            channel_ids: unfollowed.clone(),
+            until: Instant::now() + UNDO_WINDOW,
+        });
+        RUNNING.store(false, Ordering::SeqCst);
+        let _ = event_bus::emit(
+            "unfollow-batch-complete",
+            json!({
+                "batch_id": batch_id,
+                "undo": false,
+                "unfollowed": unfollowed,
+                "failed": failed,
+                "undo_secs": UNDO_WINDOW.as_secs(),
+            }),
+        );
+    });
+    Ok(batch_id)
+}
+
+/// Follow back what batch `batch_id` unfollowed, if its undo window is still
+/// open. Returns how many channels will be followed again.
+pub fn undo(batch_id: u64) -> Result<usize> {
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        bail!("Another batch is still running");
+    }
+    let channel_ids = match UNDO.lock().unwrap().take() {
+        Some(undo) if undo.usable(batch_id, Instant::now()) => undo.channel_ids,
+        _ => {
+            RUNNING.store(false, Ordering::SeqCst);
+            bail!("The undo window for this batch has closed");
+        }
+    };
+    let count = channel_ids.len();
+    tauri::async_runtime::spawn(async move {
+        let (followed, failed) = run(batch_id, true, &channel_ids, |id| async move {
+            TwitchService::follow_channel(&id).await
+        })
+        .await;
+        if !failed.is_empty() {
+            warn!(
+                "[FollowBatch] Undo of batch {}: {} couldn't be followed again",
+                batch_id,
+                failed.len()
+            );
+        }
+        RUNNING.store(false, Ordering::SeqCst);
+        let _ = event_bus::emit(
+            "unfollow-batch-complete",
+            json!({
+                "batch_id": batch_id,
+                "undo": true,
+                "followed": followed,
+                "failed": failed,
+            }),
+        );
+    });
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_only_for_its_batch_within_the_window() {
+        let now = Instant::now();
+        let undo = Undo {
+            batch_id: 4,
+            channel_ids: vec!["1".to_string()],
+            until: now + UNDO_WINDOW,
+        };
+        assert!(undo.usable(4, now));
+        assert!(!undo.usable(3, now));
+        assert!(!undo.usable(4, now + UNDO_WINDOW));
+        assert!(is_rate_limited(
+            "GQL unfollow failed (HTTP 429 Too Many Requests)"
+        ));
+        assert!(!is_rate_limited("GQL unfollow errors: [not found]"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Still frames of the live stream being watched (`capture_stream_frame`).
+//!
+//! ffmpeg reads the solo relay's own playlist, the one the player pulls, from
+//! its newest segment and writes the first frame it decodes as a PNG, so the
+//! still is from within a few seconds of what's on screen. ffmpeg isn't
+//! bundled; it has to be on PATH.
+//!
+//! `stream-frame-captured` {channel, path} goes out on success so the frontend
+//! can show a notification with the file.
+
+use anyhow::{anyhow, bail, Result};
+use log::debug;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// ffmpeg has to open the playlist, fetch a segment and decode up to a
+/// keyframe; slower than this and the stream is stalled anyway.
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedFrame {
+    pub channel: String,
+    pub path: String,
+}
+
+pub(crate) fn ffmpeg_path() -> Option<PathBuf> {
+    let name = if cfg!(windows) {
+        "ffmpeg.exe"
+    } else {
+        "ffmpeg"
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(name))
+        .find(|p| p.is_file())
+}
+
+/// `path` with a `.png` extension, whatever it was given.
+fn with_png_extension(path: &Path) -> PathBuf {
+    path.with_extension("png")
+}
+
+/// `<Pictures>/StreamNook/<channel>_<timestamp>.png`, falling back to the app
+/// data dir when there's no Pictures folder.
+fn default_path(channel: &str) -> Result<PathBuf> {
+    let dir = match dirs::picture_dir() {
+        Some(pictures) => pictures.join("StreamNook"),
+        None => crate::services::cache_service::get_app_data_dir()?.join("captures"),
+    };
+    Ok(dir.join(format!(
+        "{}_{}.png",
+        channel,
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    )))
+}
+
+/// Capture the current frame of the live stream to `path` (a default under
+/// Pictures when None).
+pub async fn capture(path: Option<&str>) -> Result<CapturedFrame> {
+    let channel = crate::services::stream_server::solo_channel()
+        .ok_or_else(|| anyhow!("No live stream is playing"))?;
+    let playlist = crate::services::stream_server::relay_playlist_url()
+        .await
+        .ok_or_else(|| anyhow!("No live stream is playing"))?;
+    let ffmpeg = ffmpeg_path().ok_or_else(|| anyhow!("ffmpeg was not found on PATH"))?;
+
+    let path = match path {
+        Some(path) => with_png_extension(Path::new(path)),
+        None => default_path(&channel)?,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut command = tokio::process::Command::new(&ffmpeg);
+    command
+        .args(["-hide_banner", "-loglevel", "error", "-y"])
+        .args(["-live_start_index", "-1", "-i"])
+        .arg(&playlist)
+        .args(["-frames:v", "1", "-c:v", "png", "-f", "image2"])
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(windows)]
+    {
+        // CREATE_NO_WINDOW: no console flashing up over the player.
+        command.creation_flags(0x0800_0000);
+    }
+    let output = tokio::time::timeout(CAPTURE_TIMEOUT, command.output())
+        .await
+        .map_err(|_| anyhow!("ffmpeg timed out after {}s", CAPTURE_TIMEOUT.as_secs()))??;
+    if !output.status.success() || !path.is_file() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "ffmpeg exited with {}: {}",
+            output.status,
+            stderr.lines().last().unwrap_or("").trim()
+        );
+    }
+
+    let captured = CapturedFrame {
+        channel,
+        path: path.to_string_lossy().to_string(),
+    };
+    debug!("[FrameCapture] Saved {}", captured.path);
+    let _ = crate::services::event_bus::emit("stream-frame-captured", &captured);
+    Ok(captured)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn captures_are_always_png() {
+        assert_eq!(
+            with_png_extension(Path::new("shots/moment.jpg")),
+            PathBuf::from("shots/moment.png")
+        );
+        assert_eq!(
+            with_png_extension(Path::new("shots/moment")),
+            PathBuf::from("shots/moment.png")
+        );
+    }
+}
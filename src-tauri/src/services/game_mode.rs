@@ -0,0 +1,214 @@
+//! Game mode: while one of the executables in `Settings.game_mode` runs,
+//! StreamNook gets out of the way and comes back once it exits.
+//!
+//! - Emote prefetch holds between downloads and favorite warm-up skips its
+//!   polls (`prefetch_paused`).
+//! - Mined playback drops to audio only or none: the drops plugin is sent
+//!   `drops.set-playback { mode }` when the game starts and `video` when it
+//!   exits, and sessions started meanwhile carry `playback` (see
+//!   commands::drops::drops_action_args).
+//! - Background polls stretch as they do in power saver.
+//!
+//! The change is announced as `game-mode-changed` {active, game}. Processes
+//! are read every `TICK`, so a game is noticed within a few seconds of
+//! starting.
+
+use crate::models::settings::{AppState, GameMiningPlayback, GameModeSettings};
+use crate::services::{event_bus, power_saver};
+use log::debug;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const TICK: Duration = Duration::from_secs(10);
+
+/// The watch-list entry of the game running now.
+static GAME: StdMutex<Option<String>> = StdMutex::new(None);
+static PREFETCH_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// The game running now, as it's written in the watch list.
+pub fn running_game() -> Option<String> {
+    GAME.lock().unwrap().clone()
+}
+
+/// Whether prefetching should wait for the game to exit.
+pub fn prefetch_paused() -> bool {
+    PREFETCH_PAUSED.load(Ordering::Relaxed)
+}
+
+impl GameMiningPlayback {
+    /// The `drops.set-playback` mode, None when playback is left alone.
+    pub fn hook_mode(self) -> Option<&'static str> {
+        match self {
+            Self::Keep => None,
+            Self::AudioOnly => Some("audio"),
+            Self::None => Some("none"),
+        }
+    }
+}
+
+/// The playback a mining session should use right now, None for the usual.
+pub fn mining_playback(state: &AppState) -> Option<&'static str> {
+    running_game()?;
+    state
+        .settings
+        .lock()
+        .unwrap()
+        .game_mode
+        .mining_playback
+        .hook_mode()
+}
+
+/// An executable name as compared: no folder, no `.exe`, lowercase.
+fn normalize(name: &str) -> String {
+    let file = name.trim().rsplit(['/', '\\']).next().unwrap_or_default();
+    let lower = file.to_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+}
+
+/// The first entry of `watch_list` among `running` process names.
+fn find_game(watch_list: &[String], running: &[String]) -> Option<String> {
+    let running: Vec<String> = running.iter().map(|name| normalize(name)).collect();
+    watch_list
+        .iter()
+        .find(|entry| {
+            let entry = normalize(entry);
+            !entry.is_empty() && running.contains(&entry)
+        })
+        .cloned()
+}
+
+#[cfg(windows)]
+fn process_names() -> anyhow::Result<Vec<String>> {
+    let mut names = Vec::new();
+    crate::services::playback_priority::for_each_process(|entry| {
+        let len = entry
+            .szExeFile
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(entry.szExeFile.len());
+        names.push(String::from_utf16_lossy(&entry.szExeFile[..len]));
+    })?;
+    Ok(names)
+}
+
+#[cfg(not(windows))]
+fn process_names() -> anyhow::Result<Vec<String>> {
+    let output = std::process::Command::new("ps")
+        .args(["-A", "-o", "comm="])
+        .output()?;
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+async fn detect(settings: &GameModeSettings) -> Option<String> {
+    if !settings.enabled || settings.executables.is_empty() {
+        return None;
+    }
+    match tokio::task::spawn_blocking(process_names).await {
+        Ok(Ok(names)) => find_game(&settings.executables, &names),
+        Ok(Err(e)) => {
+            debug!("[GameMode] Couldn't list processes: {}", e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Tell the drops plugin to switch mined playback, if something is mining.
+async fn set_mining_playback(app: &AppHandle, mode: &str) {
+    let state = app.state::<AppState>();
+    let mining = state
+        .plugin_host
+        .status("drops.status")
+        .is_some_and(|s| s["is_mining"].as_bool() == Some(true));
+    if !mining {
+        return;
+    }
+    let args = json!({ "mode": mode });
+    let args = crate::commands::drops::drops_action_args(&state, "drops.set-playback", args).await;
+    // A plugin without the hook keeps its playback as it is.
+    if let Err(e) = state
+        .plugin_host
+        .invoke_action("drops.set-playback", args)
+        .await
+    {
+        debug!("[GameMode] drops.set-playback: {}", e);
+    }
+}
+
+/// Start watching for games. Call once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        // Whether mined playback was turned down for the current game.
+        let mut playback_reduced = false;
+        loop {
+            tokio::time::sleep(TICK).await;
+            let settings = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .unwrap()
+                .game_mode
+                .clone();
+            let game = detect(&settings).await;
+            // Options changed mid-game apply on the next tick.
+            PREFETCH_PAUSED.store(game.is_some() && settings.pause_prefetch, Ordering::Relaxed);
+            power_saver::set_game_running(game.is_some() && settings.slow_polls);
+            if game == running_game() {
+                continue;
+            }
+            *GAME.lock().unwrap() = game.clone();
+
+            match &game {
+                Some(name) => debug!("[GameMode] {} started", name),
+                None => debug!("[GameMode] Game exited"),
+            }
+            let _ = event_bus::emit(
+                "game-mode-changed",
+                json!({ "active": game.is_some(), "game": game }),
+            );
+            match (&game, settings.mining_playback.hook_mode()) {
+                (Some(_), Some(mode)) => {
+                    set_mining_playback(&app, mode).await;
+                    playback_reduced = true;
+                }
+                (None, _) if playback_reduced => {
+                    set_mining_playback(&app, "video").await;
+                    playback_reduced = false;
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn games_match_by_file_name_without_case_or_extension() {
+        let running = [
+            "System".to_string(),
+            "C:\\Games\\Elden Ring\\eldenring.exe".to_string(),
+            "/usr/bin/bash".to_string(),
+        ];
+        let watch = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            find_game(&watch(&["valorant.exe", "EldenRing"]), &running),
+            Some("EldenRing".to_string())
+        );
+        assert_eq!(
+            find_game(&watch(&["bash.exe"]), &running),
+            Some("bash.exe".to_string())
+        );
+        assert_eq!(find_game(&watch(&["ring", " "]), &running), None);
+        assert_eq!(find_game(&[], &running), None);
+    }
+}
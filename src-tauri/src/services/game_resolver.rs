@@ -0,0 +1,300 @@
+//! Category name to id resolution (`resolve_game`), shared by category
+//! search, drops/mining filters and followed games.
+//!
+//! A query goes through, in order: the user's aliases and the built-in ones
+//! ("lol" -> League of Legends), the local cache, Helix Get Games by exact
+//! name, and finally Helix category search. Only a category whose name matches
+//! the (alias-expanded) query case-insensitively is taken; a near miss
+//! resolves to nothing rather than to some other game. Whatever resolves is
+//! cached under both the query and the real name, so a name is looked up on
+//! Twitch once.
+//!
+//! Persisted to `game_ids.json` in the app data dir.
+
+use crate::services::twitch_service::TwitchService;
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock as StdRwLock;
+
+const CACHE_FILE: &str = "game_ids.json";
+/// Cached names remembered; the least recently resolved are dropped past this.
+const MAX_ENTRIES: usize = 2000;
+
+/// Common shorthand, keyed by normalized alias.
+const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("lol", "League of Legends"),
+    ("tft", "Teamfight Tactics"),
+    ("wow", "World of Warcraft"),
+    ("gta", "Grand Theft Auto V"),
+    ("gta5", "Grand Theft Auto V"),
+    ("gtav", "Grand Theft Auto V"),
+    ("cs", "Counter-Strike"),
+    ("cs2", "Counter-Strike"),
+    ("val", "VALORANT"),
+    ("apex", "Apex Legends"),
+    ("rl", "Rocket League"),
+    ("mc", "Minecraft"),
+    ("dota", "Dota 2"),
+    ("poe", "Path of Exile"),
+    ("poe2", "Path of Exile 2"),
+    ("jc", "Just Chatting"),
+    ("ffxiv", "FINAL FANTASY XIV ONLINE"),
+    ("ff14", "FINAL FANTASY XIV ONLINE"),
+    ("r6", "Tom Clancy's Rainbow Six Siege"),
+    ("hs", "Hearthstone"),
+    ("sc2", "StarCraft II"),
+    ("tarkov", "Escape from Tarkov"),
+    ("eft", "Escape from Tarkov"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedGame {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub box_art_url: String,
+    /// How the query matched: `exact`, `alias` or `search`.
+    #[serde(default)]
+    pub matched: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedGame {
+    id: String,
+    name: String,
+    #[serde(default)]
+    box_art_url: String,
+    resolved_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// By normalized query or name.
+    games: HashMap<String, CachedGame>,
+    /// User aliases, normalized alias -> category name.
+    #[serde(default)]
+    aliases: HashMap<String, String>,
+}
+
+static CACHE: Lazy<StdRwLock<CacheFile>> = Lazy::new(|| StdRwLock::new(load_from_disk()));
+
+fn cache_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(CACHE_FILE))
+}
+
+fn load_from_disk() -> CacheFile {
+    cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(cache: &CacheFile) {
+    let Some(path) = cache_path() else { return };
+    match serde_json::to_string(cache) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[GameResolver] Failed to write {}: {}", CACHE_FILE, e);
+            }
+        }
+        Err(e) => warn!("[GameResolver] Failed to serialize cache: {}", e),
+    }
+}
+
+/// Lowercase letters, digits and single spaces: "Tom Clancy's  R6" -> "tom
+/// clancys r6".
+fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Whether category `name` is `query`, ignoring case.
+fn same_name(query: &str, name: &str) -> bool {
+    query.trim().to_lowercase() == name.trim().to_lowercase()
+}
+
+fn alias_target(cache: &CacheFile, key: &str) -> Option<String> {
+    cache.aliases.get(key).cloned().or_else(|| {
+        BUILTIN_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == key)
+            .map(|(_, name)| name.to_string())
+    })
+}
+
+/// The cached category for normalized `key`, if it is named `name`.
+fn cached(cache: &CacheFile, key: &str, name: &str) -> Option<CachedGame> {
+    cache
+        .games
+        .get(key)
+        .filter(|game| same_name(name, &game.name))
+        .cloned()
+}
+
+fn remember(keys: &[&str], game: &ResolvedGame) {
+    let Ok(mut cache) = CACHE.write() else {
+        return;
+    };
+    let entry = CachedGame {
+        id: game.id.clone(),
+        name: game.name.clone(),
+        box_art_url: game.box_art_url.clone(),
+        resolved_at: Utc::now(),
+    };
+    for key in keys {
+        cache.games.insert(key.to_string(), entry.clone());
+    }
+    cache.games.insert(normalize(&game.name), entry);
+    while cache.games.len() > MAX_ENTRIES {
+        let Some(oldest) = cache
+            .games
+            .iter()
+            .min_by_key(|(_, g)| g.resolved_at)
+            .map(|(k, _)| k.clone())
+        else {
+            break;
+        };
+        cache.games.remove(&oldest);
+    }
+    save_to_disk(&cache);
+}
+
+fn from_json(game: &serde_json::Value, matched: &str) -> Option<ResolvedGame> {
+    let field = |key: &str| game.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let id = field("id");
+    if id.is_empty() {
+        return None;
+    }
+    Some(ResolvedGame {
+        id: id.to_string(),
+        name: field("name").to_string(),
+        box_art_url: field("box_art_url").to_string(),
+        matched: matched.to_string(),
+    })
+}
+
+/// Resolve a category name or alias to the category. None unless a category
+/// has exactly that name (ignoring case).
+pub async fn resolve(query: &str) -> Result<Option<ResolvedGame>> {
+    let key = normalize(query);
+    if key.is_empty() {
+        return Ok(None);
+    }
+
+    let (alias, hit) = {
+        let cache = CACHE.read().map_err(|_| anyhow!("cache poisoned"))?;
+        let alias = alias_target(&cache, &key);
+        let name = alias.clone().unwrap_or_else(|| query.to_string());
+        let hit = cached(&cache, &normalize(&name), &name);
+        (alias, hit)
+    };
+    let exact_match = if alias.is_some() { "alias" } else { "exact" };
+    if let Some(game) = hit {
+        return Ok(Some(ResolvedGame {
+            id: game.id,
+            name: game.name,
+            box_art_url: game.box_art_url,
+            matched: exact_match.to_string(),
+        }));
+    }
+
+    let name = alias.unwrap_or_else(|| query.trim().to_string());
+    if let Some(game) = TwitchService::get_game_by_name(&name)
+        .await?
+        .and_then(|g| from_json(&g, exact_match))
+        .filter(|g| same_name(&name, &g.name))
+    {
+        remember(&[&key], &game);
+        return Ok(Some(game));
+    }
+
+    let results = TwitchService::search_categories(&name, 10).await?;
+    let found = results
+        .iter()
+        .filter_map(|g| from_json(g, "search"))
+        .find(|g| same_name(&name, &g.name));
+    if let Some(game) = &found {
+        remember(&[&key], game);
+    }
+    Ok(found)
+}
+
+/// Point `alias` at category `name`, or remove it when `name` is None.
+pub fn set_alias(alias: &str, name: Option<&str>) -> Result<()> {
+    let key = normalize(alias);
+    if key.is_empty() {
+        bail!("Alias is empty");
+    }
+    let mut cache = CACHE.write().map_err(|_| anyhow!("cache poisoned"))?;
+    match name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => {
+            cache.aliases.insert(key.clone(), name.to_string());
+        }
+        None => {
+            cache.aliases.remove(&key);
+        }
+    }
+    // A cached resolution of the alias as a plain name would shadow it.
+    cache.games.remove(&key);
+    save_to_disk(&cache);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_with(names: &[&str]) -> CacheFile {
+        let mut cache = CacheFile::default();
+        for (i, name) in names.iter().enumerate() {
+            cache.games.insert(
+                normalize(name),
+                CachedGame {
+                    id: i.to_string(),
+                    name: name.to_string(),
+                    box_art_url: String::new(),
+                    resolved_at: Utc::now(),
+                },
+            );
+        }
+        cache
+    }
+
+    #[test]
+    fn names_normalize_and_aliases_expand() {
+        assert_eq!(
+            normalize("  Tom Clancy's  Rainbow-Six "),
+            "tom clancys rainbowsix"
+        );
+        let mut cache = CacheFile::default();
+        assert_eq!(
+            alias_target(&cache, "lol").as_deref(),
+            Some("League of Legends")
+        );
+        cache
+            .aliases
+            .insert("lol".to_string(), "Lots of Lag".to_string());
+        assert_eq!(alias_target(&cache, "lol").as_deref(), Some("Lots of Lag"));
+    }
+
+    #[test]
+    fn only_exact_names_hit_the_cache() {
+        let cache = cache_with(&["League of Legends", "Minecraft", "Just Chatting"]);
+        assert_eq!(cached(&cache, "minecraft", "MINECRAFT").unwrap().id, "1");
+        assert!(cached(&cache, "minecrat", "minecrat").is_none());
+        assert!(cached(&cache, "league", "league").is_none());
+        assert!(same_name(" Just Chatting", "just chatting"));
+        assert!(!same_name("Just Chating", "Just Chatting"));
+    }
+}
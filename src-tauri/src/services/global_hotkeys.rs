@@ -0,0 +1,178 @@
+//! System-wide hotkeys, registered with the OS so they fire while StreamNook
+//! isn't focused (or is hidden in the tray). Bindings persist in
+//! `Settings.global_hotkeys` (action id -> accelerator such as
+//! `CmdOrCtrl+Shift+M`).
+//!
+//! Window (show/hide, mini player, always-on-top) and drops-mining toggles
+//! are handled here. Mute and stream start/stop belong to the player, so they
+//! are forwarded to the main window as a `global-hotkey` event.
+
+use crate::models::settings::AppState;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::RwLock as StdRwLock;
+use tauri::plugin::TauriPlugin;
+use tauri::{AppHandle, Emitter, Manager, Wry};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+pub const TOGGLE_MUTE: &str = "toggle_mute";
+pub const TOGGLE_STREAM: &str = "toggle_stream";
+pub const TOGGLE_WINDOW: &str = "toggle_window";
+pub const TOGGLE_MINING: &str = "toggle_mining";
+pub const TOGGLE_MINI_MODE: &str = "toggle_mini_mode";
+pub const TOGGLE_ALWAYS_ON_TOP: &str = "toggle_always_on_top";
+pub const ACTIONS: &[&str] = &[
+    TOGGLE_MUTE,
+    TOGGLE_STREAM,
+    TOGGLE_WINDOW,
+    TOGGLE_MINING,
+    TOGGLE_MINI_MODE,
+    TOGGLE_ALWAYS_ON_TOP,
+];
+
+/// Registered bindings, action id -> shortcut.
+static BINDINGS: Lazy<StdRwLock<HashMap<String, Shortcut>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+
+/// The plugin, with the dispatcher for every registered shortcut.
+pub fn plugin() -> TauriPlugin<Wry> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, shortcut, event| {
+            if event.state() != ShortcutState::Pressed {
+                return;
+            }
+            let action = BINDINGS.read().ok().and_then(|b| {
+                b.iter()
+                    .find(|(_, s)| *s == shortcut)
+                    .map(|(a, _)| a.clone())
+            });
+            if let Some(action) = action {
+                dispatch(app, &action);
+            }
+        })
+        .build()
+}
+
+/// Register the persisted bindings, at startup and whenever the settings are
+/// replaced. Actions missing from `bindings` are unbound. A binding that
+/// can't be registered (bad accelerator, or another app holds it) is logged
+/// and skipped; the settings UI surfaces the error when the user rebinds it.
+pub fn register_saved(app: &AppHandle, bindings: &HashMap<String, String>) {
+    // Release every binding that changes first, so keys that moved between
+    // actions aren't refused as already in use.
+    for action in ACTIONS {
+        let wanted = bindings
+            .get(*action)
+            .and_then(|a| a.parse::<Shortcut>().ok());
+        let current = BINDINGS.read().ok().and_then(|b| b.get(*action).cloned());
+        if current.is_some() && current != wanted {
+            let _ = set(app, action, None);
+        }
+    }
+    for (action, accelerator) in bindings {
+        if let Err(e) = set(app, action, Some(accelerator)) {
+            warn!("[Hotkeys] Skipping {} ({}): {}", action, accelerator, e);
+        }
+    }
+}
+
+/// Bind `action` to `accelerator`, or clear it with `None`. Fails without
+/// changing the current binding when the accelerator doesn't parse, is
+/// already bound to another action, or is held by another application.
+pub fn set(app: &AppHandle, action: &str, accelerator: Option<&str>) -> Result<(), String> {
+    if !ACTIONS.contains(&action) {
+        return Err(format!("Unknown hotkey action: {}", action));
+    }
+    let shortcut = accelerator
+        .map(|a| {
+            a.parse::<Shortcut>()
+                .map_err(|e| format!("Invalid shortcut '{}': {}", a, e))
+        })
+        .transpose()?;
+
+    let mut bindings = BINDINGS.write().map_err(|e| e.to_string())?;
+    if let Some(shortcut) = &shortcut {
+        if let Some((other, _)) = bindings
+            .iter()
+            .find(|(a, s)| *s == shortcut && a.as_str() != action)
+        {
+            return Err(format!("Already used for {}", other));
+        }
+    }
+    if bindings.get(action) == shortcut.as_ref() {
+        return Ok(());
+    }
+
+    let global = app.global_shortcut();
+    if let Some(shortcut) = shortcut {
+        global.register(shortcut).map_err(|e| {
+            format!(
+                "Couldn't register {}; another application may be using it ({})",
+                accelerator.unwrap_or_default(),
+                e
+            )
+        })?;
+    }
+    if let Some(previous) = bindings.remove(action) {
+        let _ = global.unregister(previous);
+    }
+    if let Some(shortcut) = shortcut {
+        bindings.insert(action.to_string(), shortcut);
+    }
+    debug!("[Hotkeys] {} -> {:?}", action, accelerator);
+    Ok(())
+}
+
+fn dispatch(app: &AppHandle, action: &str) {
+    debug!("[Hotkeys] {}", action);
+    match action {
+        TOGGLE_WINDOW => match app.get_webview_window("main") {
+            Some(main)
+                if main.is_visible().unwrap_or(false) && !main.is_minimized().unwrap_or(false) =>
+            {
+                let _ = main.hide();
+                crate::services::power_saver::set_window_background(true);
+            }
+            _ => crate::show_main_window(app),
+        },
+        TOGGLE_MINING => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app.state::<AppState>();
+                let mining = state
+                    .plugin_host
+                    .status("drops.status")
+                    .is_some_and(|s| s["is_mining"].as_bool() == Some(true));
+                let action = if mining {
+                    "drops.stop"
+                } else {
+                    "drops.run-auto"
+                };
+                let args =
+                    crate::commands::drops::drops_action_args(&state, action, json!({})).await;
+                if let Err(e) = state.plugin_host.invoke_action(action, args).await {
+                    warn!("[Hotkeys] {} failed: {}", action, e);
+                }
+            });
+        }
+        TOGGLE_MINI_MODE => {
+            if let Err(e) = crate::services::window_modes::toggle_mini(app) {
+                warn!("[Hotkeys] Mini mode failed: {}", e);
+            }
+        }
+        TOGGLE_ALWAYS_ON_TOP => {
+            let on_top = !crate::services::window_modes::is_always_on_top();
+            if let Err(e) = crate::commands::app::pin_main_window(app, on_top) {
+                warn!("[Hotkeys] Always on top failed: {}", e);
+            }
+        }
+        // Player actions: the main window owns playback.
+        _ => {
+            if let Some(main) = app.get_webview_window("main") {
+                let _ = main.emit("global-hotkey", json!({ "action": action }));
+            }
+        }
+    }
+}
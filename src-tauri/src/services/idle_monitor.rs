@@ -0,0 +1,119 @@
+//! Away detection from the OS's idle time (no keyboard or mouse input), per
+//! `Settings.idle`.
+//!
+//! Going away and coming back raise `user-idle-changed` {idle, idle_secs};
+//! the frontend pauses the player when `pause_playback` is on (drops mining
+//! runs in its plugin and is left alone) and resumes it on return. With
+//! `discord_idle`, Discord shows the idle presence while away, and the
+//! frontend puts the watching presence back on return. Watch time counted
+//! while away is kept apart in the watch history (`idle_watch_secs`).
+//!
+//! Idle time comes from `GetLastInputInfo` on Windows; elsewhere it isn't
+//! available and the user is never marked away.
+
+use crate::models::settings::AppState;
+use crate::services::discord_service::DiscordService;
+use crate::services::event_bus;
+use log::{debug, warn};
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// How often idle time is read.
+const TICK: Duration = Duration::from_secs(15);
+
+static IDLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the user is away right now.
+pub fn is_idle() -> bool {
+    IDLE.load(Ordering::Relaxed)
+}
+
+/// Seconds since the last keyboard or mouse input, None where the OS doesn't
+/// say.
+#[cfg(windows)]
+fn os_idle_secs() -> Option<u64> {
+    use windows::Win32::System::SystemInformation::GetTickCount;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return None;
+        }
+        // Both are milliseconds since boot, wrapping every ~49.7 days.
+        Some(GetTickCount().wrapping_sub(info.dwTime) as u64 / 1000)
+    }
+}
+
+#[cfg(not(windows))]
+fn os_idle_secs() -> Option<u64> {
+    None
+}
+
+/// The away state after a reading, when it changes. Unknown idle time counts
+/// as present, so a failed read never leaves the user stuck away.
+fn transition(was_idle: bool, idle_secs: Option<u64>, threshold_secs: u64) -> Option<bool> {
+    let idle = idle_secs.is_some_and(|secs| secs >= threshold_secs);
+    (idle != was_idle).then_some(idle)
+}
+
+/// Start watching idle time. Call once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(TICK).await;
+            let settings = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .unwrap()
+                .idle
+                .clone();
+            let idle_secs = if settings.enabled {
+                os_idle_secs()
+            } else {
+                None
+            };
+            let threshold_secs = u64::from(settings.threshold_mins.max(1)) * 60;
+            let Some(idle) = transition(is_idle(), idle_secs, threshold_secs) else {
+                continue;
+            };
+            IDLE.store(idle, Ordering::Relaxed);
+            debug!(
+                "[Idle] {} ({}s without input)",
+                if idle { "Away" } else { "Back" },
+                idle_secs.unwrap_or(0)
+            );
+            let _ = event_bus::emit(
+                "user-idle-changed",
+                json!({ "idle": idle, "idle_secs": idle_secs.unwrap_or(0) }),
+            );
+            if idle && settings.discord_idle {
+                let state = app.state::<AppState>();
+                if let Err(e) = DiscordService::set_idle_presence(&state).await {
+                    warn!("[Idle] Couldn't set the Discord idle presence: {}", e);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn away_past_the_threshold_and_back_on_input() {
+        assert_eq!(transition(false, Some(599), 600), None);
+        assert_eq!(transition(false, Some(600), 600), Some(true));
+        assert_eq!(transition(true, Some(900), 600), None);
+        assert_eq!(transition(true, Some(2), 600), Some(false));
+        // Unknown, or turned off while away.
+        assert_eq!(transition(true, None, 600), Some(false));
+        assert_eq!(transition(false, None, 600), None);
+    }
+}
@@ -0,0 +1,118 @@
+//! Followed channels that have stopped streaming, for pruning the follow list.
+//!
+//! Every follow is paged in from Helix and checked against its last broadcast
+//! (`TwitchService::get_offline_last_broadcasts`: when the last stream started,
+//! or when the latest VOD ended if that's later). A channel is
+//! inactive when that was more than the given number of days ago, or when it
+//! has never streamed. Channels Twitch returns nothing for (banned, deleted, a
+//! failed lookup) are left out rather than guessed at. The result feeds
+//! `follow_batch::unfollow`.
+
+use crate::services::twitch_service::TwitchService;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use serde::Serialize;
+
+const PAGE_SIZE: u32 = 100;
+/// The GQL users query takes at most this many ids.
+const LOOKUP_CHUNK: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InactiveFollow {
+    pub channel_id: String,
+    pub login: String,
+    pub display_name: String,
+    pub profile_image_url: Option<String>,
+    pub followed_at: String,
+    /// RFC3339; None when the channel has never streamed.
+    pub last_streamed: Option<String>,
+    /// Whole days since `last_streamed`.
+    pub days_inactive: Option<i64>,
+}
+
+/// Days since `last_streamed` if that's more than `days` ago, `Some(None)` for
+/// a channel that never streamed, `None` for one that's active.
+fn inactive_for(last_streamed: Option<&str>, now: DateTime<Utc>, days: u32) -> Option<Option<i64>> {
+    let Some(last_streamed) = last_streamed else {
+        return Some(None);
+    };
+    // An unreadable timestamp still means the channel streamed at some point.
+    let last = DateTime::parse_from_rfc3339(last_streamed).ok()?;
+    let inactive = (now - last.with_timezone(&Utc)).num_days();
+    (inactive >= i64::from(days)).then_some(Some(inactive))
+}
+
+/// The followed channels that haven't streamed in `days` days: never-streamed
+/// first, then longest inactive first.
+pub async fn list(days: u32) -> Result<Vec<InactiveFollow>> {
+    let mut follows = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next) = TwitchService::get_all_followed_channels(PAGE_SIZE, cursor).await?;
+        let last_page = page.is_empty();
+        follows.extend(page);
+        match next {
+            Some(next) if !last_page => cursor = Some(next),
+            _ => break,
+        }
+    }
+
+    let now = Utc::now();
+    let mut inactive = Vec::new();
+    for chunk in follows.chunks(LOOKUP_CHUNK) {
+        let ids = chunk.iter().map(|f| f.user_id.clone()).collect();
+        let last_broadcasts = match TwitchService::get_offline_last_broadcasts(ids).await {
+            Ok(map) => map,
+            Err(e) => {
+                warn!("[InactiveFollows] Last broadcast lookup failed: {}", e);
+                continue;
+            }
+        };
+        for follow in chunk {
+            let Some(last_streamed) = last_broadcasts.get(&follow.user_id) else {
+                continue;
+            };
+            let Some(days_inactive) = inactive_for(last_streamed.as_deref(), now, days) else {
+                continue;
+            };
+            inactive.push(InactiveFollow {
+                channel_id: follow.user_id.clone(),
+                login: follow.user_login.clone(),
+                display_name: follow.user_name.clone(),
+                profile_image_url: follow.profile_image_url.clone(),
+                // get_all_followed_channels keeps followed_at in started_at.
+                followed_at: follow.started_at.clone(),
+                last_streamed: last_streamed.clone(),
+                days_inactive,
+            });
+        }
+    }
+    inactive.sort_by_key(|f| std::cmp::Reverse(f.days_inactive.unwrap_or(i64::MAX)));
+    debug!(
+        "[InactiveFollows] {} of {} follows inactive for {}+ days",
+        inactive.len(),
+        follows.len(),
+        days
+    );
+    Ok(inactive)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inactive_after_the_given_days_or_never_streamed() {
+        let now = DateTime::parse_from_rfc3339("2026-06-30T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            inactive_for(Some("2026-03-01T12:00:00+00:00"), now, 90),
+            Some(Some(121))
+        );
+        assert_eq!(inactive_for(Some("2026-06-20T12:00:00Z"), now, 90), None);
+        assert_eq!(inactive_for(None, now, 90), Some(None));
+        assert_eq!(inactive_for(Some("not a date"), now, 90), None);
+    }
+}
@@ -1,12 +1,17 @@
 use crate::models::chat_layout::{
-    Badge, ChatMessage, EmotePos, LayoutResult, MessageMetadata, MessageSegment, ReplyInfo,
+    Badge, ChatMessage, EmotePos, LayoutResult, MessageMetadata, MessageSegment, RenderHints,
+    ReplyInfo,
 };
 use crate::models::settings::AppState;
 use crate::plugin_host::PluginHost;
+use crate::services::autocomplete;
 use crate::services::chat_logger_service::ChatLoggerService;
+use crate::services::chatter_tracker::{self, Membership};
 use crate::services::emoji_service;
 use crate::services::emote_service::{Emote, EmoteService, EmoteSet};
 use crate::services::layout_service::LayoutService;
+use crate::services::render_hints;
+use crate::services::spam_collapse;
 use crate::services::twitch_service::TwitchService;
 use crate::services::user_message_history_service::UserMessageHistoryService;
 use anyhow::Result;
@@ -74,6 +79,8 @@ static CHANNEL_CONSUMERS: OnceLock<Mutex<HashMap<String, HashSet<String>>>> = On
 // Handle to the plugin host so parsed chat lines can be forwarded to plugins
 // subscribed to on_chat_message. Set once, on the first chat start.
 static PLUGIN_HOST: OnceLock<Arc<PluginHost>> = OnceLock::new();
+// Wakes the read loop so it drops the current connection and reconnects.
+static FORCE_RECONNECT: OnceLock<tokio::sync::Notify> = OnceLock::new();
 // The logged-in user's (login, user id), for attributing locally sent
 // messages: Twitch IRC does not echo your own PRIVMSG back.
 static OWN_IDENTITY: OnceLock<Mutex<Option<(String, String)>>> = OnceLock::new();
@@ -111,6 +118,10 @@ fn get_message_queue() -> &'static Mutex<VecDeque<String>> {
     MESSAGE_QUEUE.get_or_init(|| Mutex::new(VecDeque::new()))
 }
 
+fn get_force_reconnect() -> &'static tokio::sync::Notify {
+    FORCE_RECONNECT.get_or_init(tokio::sync::Notify::new)
+}
+
 fn get_irc_handle() -> &'static Mutex<Option<tokio::task::JoinHandle<()>>> {
     IRC_HANDLE.get_or_init(|| Mutex::new(None))
 }
@@ -151,6 +162,14 @@ fn get_own_identity() -> &'static Mutex<Option<(String, String)>> {
     OWN_IDENTITY.get_or_init(|| Mutex::new(None))
 }
 
+async fn own_login() -> Option<String> {
+    get_own_identity()
+        .lock()
+        .await
+        .as_ref()
+        .map(|(login, _)| login.clone())
+}
+
 #[allow(clippy::type_complexity)]
 fn get_personal_emotes() -> &'static Mutex<HashMap<String, (String, HashMap<String, Emote>)>> {
     PERSONAL_EMOTES.get_or_init(|| Mutex::new(HashMap::new()))
@@ -193,7 +212,7 @@ fn chat_event_params(msg: &ChatMessage) -> Value {
 // Extract the channel name (lowercase, no leading #) from a raw IRC line.
 // Used by ROOMSTATE/USERSTATE/CLEARMSG/CLEARCHAT parsing to key per-channel
 // caches and tag synthetic WS messages.
-fn extract_channel_from_irc_line(line: &str) -> Option<String> {
+pub(crate) fn extract_channel_from_irc_line(line: &str) -> Option<String> {
     let idx = line.find(" #")?;
     let after = &line[idx + 2..];
     let end = after.find([' ', '\r', '\n']).unwrap_or(after.len());
@@ -224,6 +243,7 @@ impl IrcService {
         let emote_service = state.emote_service.clone();
         let _ = PLUGIN_HOST.set(state.plugin_host.clone());
         ChatLoggerService::init(state.settings.clone());
+        spam_collapse::init(state.settings.clone());
 
         // Idempotency: if the IRC service is already running, don't tear it
         // down. Instead, JOIN the requested channel onto the existing
@@ -559,21 +579,28 @@ impl IrcService {
             // Listen for messages
             loop {
                 line.clear();
-                let should_reconnect = match reader.read_line(&mut line).await {
-                    Ok(0) => {
-                        debug!("[IRC Chat] Connection closed by server");
-                        true
-                    }
-                    Ok(_) => {
-                        if let Err(e) =
-                            Self::handle_irc_message(&line, &tx, &writer, &layout_service).await
-                        {
-                            error!("[IRC Chat] Error handling message: {}", e);
+                let should_reconnect = tokio::select! {
+                    read = reader.read_line(&mut line) => match read {
+                        Ok(0) => {
+                            debug!("[IRC Chat] Connection closed by server");
+                            true
                         }
-                        false
-                    }
-                    Err(e) => {
-                        error!("[IRC Chat] Read error: {}", e);
+                        Ok(_) => {
+                            if let Err(e) =
+                                Self::handle_irc_message(&line, &tx, &writer, &layout_service)
+                                    .await
+                            {
+                                error!("[IRC Chat] Error handling message: {}", e);
+                            }
+                            false
+                        }
+                        Err(e) => {
+                            error!("[IRC Chat] Read error: {}", e);
+                            true
+                        }
+                    },
+                    _ = get_force_reconnect().notified() => {
+                        debug!("[IRC Chat] Reconnect requested");
                         true
                     }
                 };
@@ -652,6 +679,22 @@ impl IrcService {
                     has_reply: chat_msg.metadata.reply_info.is_some(),
                     is_first_message: chat_msg.metadata.is_first_message,
                 };
+                chat_msg.metadata.render_hints =
+                    render_hints::compute(&chat_msg, own_login().await.as_deref());
+
+                if let Some(room_id) = chat_msg.tags.get("room-id") {
+                    chatter_tracker::note_room(room_id, &chat_msg.channel);
+                }
+                chatter_tracker::on_message(
+                    &chat_msg.channel,
+                    &chat_msg.username,
+                    &chat_msg.display_name,
+                );
+                autocomplete::note_chatter(
+                    &chat_msg.channel,
+                    &chat_msg.username,
+                    &chat_msg.display_name,
+                );
 
                 // Store a compact summary (id/content/timestamp/color) in the user
                 // history LRU for profile cards. Avoids cloning the full ChatMessage.
@@ -670,6 +713,16 @@ impl IrcService {
                     }
                 }
 
+                // Floods of one message reach the frontend as a counter row
+                // instead of every copy.
+                let (show, collapse_events) = spam_collapse::check(&chat_msg);
+                for event in collapse_events {
+                    let _ = tx.send(event);
+                }
+                if !show {
+                    return Ok(());
+                }
+
                 if let Ok(json_msg) = serde_json::to_string(&chat_msg) {
                     if tx.send(json_msg).is_err() {
                         // debug!("[IRC Chat] No active receivers, queueing message");
@@ -724,6 +777,8 @@ impl IrcService {
                     has_reply: false,
                     is_first_message: false,
                 };
+                chat_msg.metadata.render_hints =
+                    render_hints::compute(&chat_msg, own_login().await.as_deref());
 
                 debug!(
                     "[IRC Chat] Parsed USERNOTICE: type={:?}, user_content_len={}",
@@ -767,6 +822,12 @@ impl IrcService {
             // Extract channel so the synthetic message is routable and the cache
             // is keyed per channel.
             let channel_name = extract_channel_from_irc_line(trimmed);
+            if let (Some(ch), Some(room_id)) = (
+                channel_name.as_deref(),
+                Self::extract_tag_value(trimmed, "room-id"),
+            ) {
+                chatter_tracker::note_room(&room_id, ch);
+            }
 
             // Forward room state to frontend — only include tags actually present
             // Twitch sends FULL roomstate on join, PARTIAL on setting changes
@@ -960,6 +1021,16 @@ impl IrcService {
                 "message": notice_text,
             });
             let _ = tx.send(notice_event.to_string());
+        } else if let Some(membership) = chatter_tracker::parse_membership(trimmed) {
+            // JOIN / PART / NAMES from the membership capability: roster only,
+            // nothing to render.
+            match membership {
+                Membership::Join { channel, login } => chatter_tracker::record_join(channel, login),
+                Membership::Part { channel, login } => chatter_tracker::record_part(channel, login),
+                Membership::Names { channel, logins } => {
+                    chatter_tracker::record_names(channel, &logins)
+                }
+            }
         }
 
         Ok(())
@@ -1532,6 +1603,8 @@ impl IrcService {
         drop(w);
 
         get_current_channels().lock().await.remove(key);
+        chatter_tracker::forget_channel(key);
+        autocomplete::forget_channel(key);
 
         // Drop per-channel caches so PARTed channels don't accumulate memory.
         // If the user re-JOINs later, fetch_and_store_emotes runs again and
@@ -1586,6 +1659,7 @@ impl IrcService {
                             channel_name,
                             disk_set.seven_tv.len()
                         );
+                        autocomplete::index_emotes(&key, &disk_set);
                         map.insert(key.clone(), disk_set);
                     }
                 }
@@ -1619,6 +1693,7 @@ impl IrcService {
                             );
                             if seven_tv_ok {
                                 crate::services::emote_set_cache::save_force(&user.id, &emote_set);
+                                autocomplete::index_emotes(&key, &emote_set);
                                 get_channel_emotes().lock().await.insert(key, emote_set);
                             } else {
                                 debug!(
@@ -1814,6 +1889,7 @@ impl IrcService {
                     emote_id: Some(seventv_emote.id.clone()),
                     emote_url: seventv_emote.url.clone(),
                     is_zero_width: seventv_emote.is_zero_width,
+                    animated: seventv_emote.animated,
                 });
             } else {
                 // Use Twitch emote
@@ -1822,6 +1898,7 @@ impl IrcService {
                     emote_id: Some(emote.id.clone()),
                     emote_url: emote.url.clone(),
                     is_zero_width: None,
+                    animated: None,
                 });
             }
 
@@ -1918,7 +1995,7 @@ impl IrcService {
                     url,
                 });
             } else if let Some((prefix, bits, tier, color, cheermote_url)) =
-                Self::parse_cheermote(word)
+                Self::parse_cheermote(word, channel)
             {
                 // Found a cheermote pattern (e.g., Cheer500, Party1000)
                 segments.push(MessageSegment::Cheermote {
@@ -1936,6 +2013,7 @@ impl IrcService {
                     emote_id: Some(emote.id.clone()),
                     emote_url: emote.url.clone(),
                     is_zero_width: emote.is_zero_width,
+                    animated: emote.animated,
                 });
             } else {
                 // Convert emoji shortcodes first
@@ -1969,8 +2047,13 @@ impl IrcService {
     }
 
     /// Parse a potential cheermote pattern (e.g., Cheer500, Party1000)
-    /// Returns Some((prefix, bits, tier, color, url)) if valid, None otherwise
-    fn parse_cheermote(word: &str) -> Option<(String, u32, String, String, String)> {
+    /// Returns Some((prefix, bits, tier, color, url)) if valid, None otherwise.
+    /// The channel's own cheermotes (warmed on join) are tried first.
+    fn parse_cheermote(word: &str, channel: &str) -> Option<(String, u32, String, String, String)> {
+        if let Some(found) = crate::services::chat_ready::cheermote(channel, word) {
+            return Some(found);
+        }
+
         // Known cheermote prefixes on Twitch
         // Only these specific prefixes should be treated as cheermotes
         const CHEERMOTE_PREFIXES: &[&str] = &[
@@ -2322,6 +2405,7 @@ impl IrcService {
             msg_type,
             bits_amount,
             system_message,
+            render_hints: RenderHints::default(), // Filled in by handle_irc_message
         };
 
         // Extract channel
@@ -2544,6 +2628,7 @@ impl IrcService {
             msg_type,
             bits_amount: None,
             system_message,
+            render_hints: RenderHints::default(), // Filled in by handle_irc_message
         };
 
         // Extract channel
@@ -2628,6 +2713,15 @@ impl IrcService {
         results
     }
 
+    /// Drop the live IRC connection and reconnect (the normal 5s reconnect
+    /// path, re-JOINing every tracked channel). For when the socket is known
+    /// to be dead but hasn't errored yet, e.g. after a system sleep. A request
+    /// made while the read loop is busy handling a line is kept until it next
+    /// waits, rather than lost.
+    pub fn force_reconnect() {
+        get_force_reconnect().notify_one();
+    }
+
     pub async fn stop() -> Result<()> {
         debug!("[IRC Chat] Stopping chat service");
 
@@ -2655,6 +2749,7 @@ impl IrcService {
 
         // Clear all per-channel caches
         get_channel_emotes().lock().await.clear();
+        autocomplete::forget_all();
         get_user_badges_cache().lock().await.clear();
         get_room_state_cache().lock().await.clear();
         get_channel_consumers().lock().await.clear();
@@ -2686,6 +2781,7 @@ impl IrcService {
         get_current_channels().lock().await.clear();
         get_shared_chat_rooms().lock().await.clear();
         get_channel_emotes().lock().await.clear();
+        autocomplete::forget_all();
         get_user_badges_cache().lock().await.clear();
         get_room_state_cache().lock().await.clear();
         get_channel_consumers().lock().await.clear();
@@ -1,11 +1,12 @@
 use crate::models::settings::AppState;
+use crate::services::event_bus;
 use crate::services::twitch_service::TwitchService;
 use anyhow::Result;
 use log::{debug, error};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 use tokio::sync::RwLock;
 use tokio::time::{interval, Duration};
 
@@ -157,7 +158,17 @@ impl LiveNotificationService {
         };
 
         // Emit event to frontend (for in-app notifications)
-        app_handle.emit("streamer-went-live", &notification)?;
+        event_bus::emit("streamer-went-live", &notification)?;
+        crate::services::script_hooks::fire(
+            app_handle,
+            crate::services::script_hooks::WENT_LIVE,
+            serde_json::json!(notification),
+        );
+        crate::services::webhooks::fire(
+            app_handle,
+            crate::services::script_hooks::WENT_LIVE,
+            serde_json::json!(notification),
+        );
 
         debug!(
             "[In-App Notification] {} is now live!",
@@ -0,0 +1,575 @@
+//! Localhost control API (`Settings::local_api`) for external controllers
+//! such as an Elgato Stream Deck plugin. It serves small JSON status
+//! documents, PNG key icons (mining on/off, channel live), mining
+//! start/stop/toggle actions, and drop progress for stream overlays, also as
+//! server-sent events; docs/LOCAL_API.md documents the payloads.
+//!
+//! The server binds 127.0.0.1 only and every request carries the token kept
+//! in `local_api_token` in the app data dir (shown in settings). Icon and
+//! status reads may pass it as `?token=` so a plugin can point an image URL
+//! at them; actions need the `X-StreamNook-Token` header, which a web page
+//! can't attach cross-origin without a CORS preflight this server never
+//! answers. Reads and actions are rate-limited separately.
+
+use crate::models::settings::{AppState, LocalApiSettings};
+use crate::models::stream::TwitchStream;
+use crate::services::ll_origin::opt_raw_query;
+use crate::services::twitch_service::TwitchService;
+use anyhow::{anyhow, Result};
+use image::RgbaImage;
+use log::{info, warn};
+use once_cell::sync::{Lazy, OnceCell};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use warp::http::{HeaderMap, HeaderValue, Method, Response, StatusCode};
+use warp::Filter;
+
+const TOKEN_FILE: &str = "local_api_token";
+pub const TOKEN_HEADER: &str = "x-streamnook-token";
+const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+/// Stream Deck XL key size; the standard deck scales it down.
+const ICON: u32 = 144;
+const LIVE_CACHE_TTL: Duration = Duration::from_secs(30);
+/// How often the overlay event stream looks for a change to send.
+const OVERLAY_POLL: Duration = Duration::from_secs(1);
+const IDEMPOTENCY_TTL: Duration = Duration::from_secs(60);
+
+static APP: OnceCell<AppHandle> = OnceCell::new();
+
+/// Set in headless mode, where the API is the only way to drive the app.
+static ALWAYS_ON: AtomicBool = AtomicBool::new(false);
+
+/// The running server: bound port and its task.
+static SERVER: Lazy<Mutex<Option<(u16, tokio::task::JoinHandle<()>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// A token bucket: `capacity` requests at once, refilled at `per_sec`.
+struct Bucket {
+    capacity: f64,
+    per_sec: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl Bucket {
+    fn new(capacity: f64, per_sec: f64, now: Instant) -> Self {
+        Self {
+            capacity,
+            per_sec,
+            tokens: capacity,
+            last: now,
+        }
+    }
+
+    /// Take one request, or how long until one is available.
+    fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.last = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+        }
+    }
+}
+
+/// Polling a full deck of status keys every second fits; a runaway loop
+/// doesn't.
+static READS: Lazy<StdMutex<Bucket>> =
+    Lazy::new(|| StdMutex::new(Bucket::new(40.0, 20.0, Instant::now())));
+/// Mining actions reach the plugin host, so one per second with a small burst.
+static ACTIONS: Lazy<StdMutex<Bucket>> =
+    Lazy::new(|| StdMutex::new(Bucket::new(3.0, 1.0, Instant::now())));
+
+/// Live status per login, refreshed at most every `LIVE_CACHE_TTL`.
+static LIVE_CACHE: Lazy<StdMutex<HashMap<String, (Instant, Option<TwitchStream>)>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Replies to recent actions by `Idempotency-Key`, so a retried key press
+/// doesn't toggle twice.
+static IDEMPOTENCY: Lazy<StdMutex<HashMap<String, (Instant, Value)>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// The API token, generated on first use.
+pub fn token() -> Result<String> {
+    let path = crate::services::cache_service::get_app_data_dir()?.join(TOKEN_FILE);
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    std::fs::write(&path, &token)?;
+    Ok(token)
+}
+
+/// The port the server is listening on, if it's running.
+pub async fn running_port() -> Option<u16> {
+    SERVER.lock().await.as_ref().map(|(port, _)| *port)
+}
+
+/// Keep the server running whatever `Settings::local_api.enabled` says.
+pub fn keep_running() {
+    ALWAYS_ON.store(true, Ordering::Relaxed);
+}
+
+/// Start, stop or rebind the server to match `settings`. Called at startup
+/// and on every settings save.
+pub async fn apply(app: &AppHandle, settings: &LocalApiSettings) -> Result<()> {
+    let _ = APP.set(app.clone());
+    let mut server = SERVER.lock().await;
+    let enabled = settings.enabled || ALWAYS_ON.load(Ordering::Relaxed);
+    let wanted = enabled.then_some(settings.port);
+    if server.as_ref().map(|(port, _)| *port) == wanted {
+        return Ok(());
+    }
+    if let Some((port, handle)) = server.take() {
+        handle.abort();
+        info!("[LocalApi] Stopped on port {}", port);
+    }
+    let Some(port) = wanted else {
+        return Ok(());
+    };
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let socket = tokio::net::TcpSocket::new_v4()?;
+    socket
+        .bind(addr)
+        .map_err(|e| anyhow!("Couldn't listen on {}: {}", addr, e))?;
+    let listener = socket.listen(64)?;
+    let events = warp::path!("v1" / "overlay" / "events")
+        .and(warp::get())
+        .and(opt_raw_query())
+        .and(warp::header::headers_cloned())
+        .and_then(overlay_events);
+    let routes = events
+        .or(warp::path::full()
+            .and(warp::method())
+            .and(opt_raw_query())
+            .and(warp::header::headers_cloned())
+            .and_then(route)
+            .map(|response: Response<Vec<u8>>| Box::new(response) as Box<dyn warp::Reply>))
+        .unify()
+        .boxed();
+    let handle = tokio::spawn(async move {
+        warp::serve(routes).incoming(listener).run().await;
+    });
+    *server = Some((port, handle));
+    info!("[LocalApi] Listening on {}", addr);
+    Ok(())
+}
+
+fn respond(status: StatusCode, content_type: &str, body: Vec<u8>) -> Response<Vec<u8>> {
+    Response::builder()
+        .status(status)
+        .header("Content-Type", content_type)
+        .header("Cache-Control", "no-store")
+        .body(body)
+        .unwrap()
+}
+
+/// Let a page from any origin read the response. Overlay pages are served from
+/// elsewhere (or a file) and read the overlay routes with `fetch` or
+/// `EventSource`, and the token still guards them.
+fn allow_any_origin(mut response: Response<Vec<u8>>) -> Response<Vec<u8>> {
+    response
+        .headers_mut()
+        .insert("Access-Control-Allow-Origin", HeaderValue::from_static("*"));
+    response
+}
+
+fn json_response(status: StatusCode, body: Value) -> Response<Vec<u8>> {
+    respond(status, "application/json", body.to_string().into_bytes())
+}
+
+fn error(status: StatusCode, message: &str) -> Response<Vec<u8>> {
+    json_response(status, json!({ "error": message }))
+}
+
+fn query_param(query: &str, key: &str) -> Option<String> {
+    query.split('&').find_map(|pair| {
+        let (k, v) = pair.split_once('=')?;
+        (k == key).then(|| v.to_string())
+    })
+}
+
+/// Compare without stopping at the first differing byte.
+fn token_matches(provided: Option<&str>, expected: &str) -> bool {
+    provided.is_some_and(|p| {
+        p.len() == expected.len()
+            && p.bytes()
+                .zip(expected.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    })
+}
+
+fn is_login(login: &str) -> bool {
+    (1..=25).contains(&login.len()) && login.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Token check, then rate limit, for a request; the error reply when either
+/// fails. Checked in that order so requests without the token can't use up
+/// the budget of the client that has it.
+fn admit(is_action: bool, query: &str, headers: &HeaderMap) -> Result<(), Response<Vec<u8>>> {
+    let header_token = headers
+        .get(TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let provided = if is_action {
+        header_token
+    } else {
+        header_token.or_else(|| query_param(query, "token"))
+    };
+    let authorized = match token() {
+        Ok(expected) => token_matches(provided.as_deref(), &expected),
+        Err(e) => {
+            warn!("[LocalApi] Couldn't read the token: {}", e);
+            false
+        }
+    };
+    if !authorized {
+        return Err(error(StatusCode::UNAUTHORIZED, "missing or wrong token"));
+    }
+
+    let limiter = if is_action { &ACTIONS } else { &READS };
+    if let Err(wait) = limiter.lock().unwrap().take(Instant::now()) {
+        let mut response = error(StatusCode::TOO_MANY_REQUESTS, "rate limited");
+        response
+            .headers_mut()
+            .insert("Retry-After", HeaderValue::from(wait.as_secs() + 1));
+        return Err(response);
+    }
+    Ok(())
+}
+
+async fn route(
+    path: warp::path::FullPath,
+    method: Method,
+    query: String,
+    headers: HeaderMap,
+) -> Result<Response<Vec<u8>>, warp::Rejection> {
+    let Some(app) = APP.get() else {
+        return Ok(error(StatusCode::SERVICE_UNAVAILABLE, "starting"));
+    };
+    let is_action = method == Method::POST;
+    if let Err(response) = admit(is_action, &query, &headers) {
+        return Ok(response);
+    }
+
+    let segments: Vec<&str> = path.as_str().trim_matches('/').split('/').collect();
+    let response = match (method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "state"]) => json_response(StatusCode::OK, state(app)),
+        ("GET", ["v1", "overlay"]) => {
+            let response = json_response(StatusCode::OK, current_overlay_state(app));
+            allow_any_origin(response)
+        }
+        ("GET", ["v1", "channels", login]) => match channel(login).await {
+            Ok(body) => json_response(StatusCode::OK, body),
+            Err(response) => response,
+        },
+        ("GET", ["v1", "icons", "mining.png"]) => {
+            let on = mining_status(app).0;
+            icon_response(if on { MINING } else { OFF }, on)
+        }
+        ("GET", ["v1", "icons", "live", file]) => match file.strip_suffix(".png") {
+            Some(login) => match channel(login).await {
+                Ok(body) => {
+                    let on = body["live"] == true;
+                    icon_response(if on { LIVE } else { OFF }, on)
+                }
+                Err(response) => response,
+            },
+            None => error(StatusCode::NOT_FOUND, "not found"),
+        },
+        ("POST", ["v1", "mining", op @ ("start" | "stop" | "toggle")]) => {
+            let key = headers
+                .get(IDEMPOTENCY_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .map(|k| format!("mining/{op}/{k}"));
+            match key.as_deref().and_then(replay) {
+                Some(body) => json_response(StatusCode::OK, body),
+                None => match set_mining(app, op).await {
+                    Ok(body) => {
+                        if let Some(key) = key {
+                            remember(key, body.clone());
+                        }
+                        json_response(StatusCode::OK, body)
+                    }
+                    Err(e) => error(StatusCode::BAD_GATEWAY, &e.to_string()),
+                },
+            }
+        }
+        _ => error(StatusCode::NOT_FOUND, "not found"),
+    };
+    Ok(response)
+}
+
+/// (is_mining, channel) from the drops plugin's status slot.
+fn mining_status(app: &AppHandle) -> (bool, Option<String>) {
+    let status = app.state::<AppState>().plugin_host.status("drops.status");
+    let mining = status
+        .as_ref()
+        .is_some_and(|s| s["is_mining"].as_bool() == Some(true));
+    let channel = status
+        .filter(|_| mining)
+        .and_then(|s| s["channel_login"].as_str().map(String::from));
+    (mining, channel)
+}
+
+fn state(app: &AppHandle) -> Value {
+    let (mining, mining_channel) = mining_status(app);
+    json!({
+        "mining": mining,
+        "mining_channel": mining_channel,
+        "watching": crate::services::stream_server::solo_channel(),
+    })
+}
+
+fn current_overlay_state(app: &AppHandle) -> Value {
+    let status = app.state::<AppState>().plugin_host.status("drops.status");
+    overlay_state(status.as_ref())
+}
+
+/// Drop progress for a stream overlay, from a `drops.status` value: the drop
+/// and channel, percent done and the time left. A credited minute takes a
+/// minute of watching, so `eta_secs` is the minutes left; none while paused.
+/// It only changes when the plugin pushes progress, so an overlay counts down
+/// between updates itself.
+pub fn overlay_state(status: Option<&Value>) -> Value {
+    let Some(status) = status.filter(|s| s["is_mining"].as_bool() == Some(true)) else {
+        return json!({ "mining": false });
+    };
+    let paused = status["paused"].as_bool().unwrap_or(false);
+    let current = status["current_minutes"].as_u64();
+    let required = status["required_minutes"].as_u64().filter(|r| *r > 0);
+    let (percent, eta_secs) = match (current, required) {
+        (Some(current), Some(required)) => {
+            let current = current.min(required);
+            let percent = (current as f64 * 1000.0 / required as f64).round() / 10.0;
+            (
+                Some(percent),
+                (!paused).then_some((required - current) * 60),
+            )
+        }
+        _ => (None, None),
+    };
+    let text = |key: &str| status[key].as_str().filter(|s| !s.is_empty());
+    json!({
+        "mining": true,
+        "paused": paused,
+        "drop_name": text("drop_name"),
+        "game_name": text("game_name"),
+        "channel_login": text("channel_login"),
+        "current_minutes": current,
+        "required_minutes": required,
+        "percent": percent,
+        "eta_secs": eta_secs,
+    })
+}
+
+/// `GET /v1/overlay/events`: the overlay state as server-sent `overlay`
+/// events, one on connect and one per change.
+async fn overlay_events(
+    query: String,
+    headers: HeaderMap,
+) -> Result<Box<dyn warp::Reply>, warp::Rejection> {
+    let Some(app) = APP.get().cloned() else {
+        return Ok(Box::new(allow_any_origin(error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "starting",
+        ))));
+    };
+    if let Err(response) = admit(false, &query, &headers) {
+        return Ok(Box::new(allow_any_origin(response)));
+    }
+    let events = futures_util::stream::unfold((app, None::<Value>), |(app, last)| async move {
+        loop {
+            let state = current_overlay_state(&app);
+            if last.as_ref() != Some(&state) {
+                let event = warp::sse::Event::default()
+                    .event("overlay")
+                    .json_data(&state);
+                return Some((event, (app, Some(state))));
+            }
+            tokio::time::sleep(OVERLAY_POLL).await;
+        }
+    });
+    Ok(Box::new(warp::reply::with_header(
+        warp::sse::reply(warp::sse::keep_alive().stream(events)),
+        "Access-Control-Allow-Origin",
+        "*",
+    )))
+}
+
+async fn channel(login: &str) -> Result<Value, Response<Vec<u8>>> {
+    let login = login.to_lowercase();
+    if !is_login(&login) {
+        return Err(error(StatusCode::BAD_REQUEST, "invalid channel login"));
+    }
+    let cached = LIVE_CACHE
+        .lock()
+        .unwrap()
+        .get(&login)
+        .filter(|(at, _)| at.elapsed() < LIVE_CACHE_TTL)
+        .map(|(_, stream)| stream.clone());
+    let stream = match cached {
+        Some(stream) => stream,
+        None => {
+            let live = TwitchService::check_streams_online(std::slice::from_ref(&login))
+                .await
+                .map_err(|e| error(StatusCode::BAD_GATEWAY, &e.to_string()))?;
+            let stream = live
+                .into_iter()
+                .find(|s| s.user_login.eq_ignore_ascii_case(&login));
+            LIVE_CACHE
+                .lock()
+                .unwrap()
+                .insert(login.clone(), (Instant::now(), stream.clone()));
+            stream
+        }
+    };
+    Ok(json!({
+        "login": login,
+        "live": stream.is_some(),
+        "title": stream.as_ref().map(|s| &s.title),
+        "game_name": stream.as_ref().map(|s| &s.game_name),
+        "viewer_count": stream.as_ref().map(|s| s.viewer_count),
+        "started_at": stream.as_ref().map(|s| &s.started_at),
+    }))
+}
+
+/// Start, stop or toggle mining. Start and stop are no-ops when mining is
+/// already in that state.
+async fn set_mining(app: &AppHandle, op: &str) -> Result<Value> {
+    let state = app.state::<AppState>();
+    let mining = mining_status(app).0;
+    let want = match op {
+        "start" => true,
+        "stop" => false,
+        _ => !mining,
+    };
+    if want == mining {
+        return Ok(json!({ "changed": false, "mining": mining }));
+    }
+    let action = if want { "drops.run-auto" } else { "drops.stop" };
+    let args = crate::commands::drops::drops_action_args(&state, action, json!({})).await;
+    state.plugin_host.invoke_action(action, args).await?;
+    Ok(json!({ "changed": true, "mining": want }))
+}
+
+fn replay(key: &str) -> Option<Value> {
+    let mut seen = IDEMPOTENCY.lock().unwrap();
+    seen.retain(|_, (at, _)| at.elapsed() < IDEMPOTENCY_TTL);
+    seen.get(key).map(|(_, body)| body.clone())
+}
+
+fn remember(key: String, body: Value) {
+    IDEMPOTENCY
+        .lock()
+        .unwrap()
+        .insert(key, (Instant::now(), body));
+}
+
+const MINING: [u8; 3] = [0x91, 0x47, 0xff];
+const LIVE: [u8; 3] = [0xe9, 0x1e, 0x3a];
+const OFF: [u8; 3] = [0x53, 0x53, 0x5f];
+const BACKGROUND: [u8; 4] = [0x18, 0x18, 0x1b, 255];
+
+/// A dark key with a disc in `color`: filled when on, a ring when off, so the
+/// state reads without relying on color alone.
+fn icon_rgba(color: [u8; 3], on: bool) -> Vec<u8> {
+    let mut rgba = vec![0u8; (ICON * ICON * 4) as usize];
+    let center = (ICON as f32 - 1.0) / 2.0;
+    let (outer, inner) = (ICON as f32 * 0.3, ICON as f32 * 0.22);
+    let disc = [color[0], color[1], color[2], 255];
+    for y in 0..ICON {
+        for x in 0..ICON {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            let r = (dx * dx + dy * dy).sqrt();
+            let lit = r <= outer && (on || r >= inner);
+            let px = ((y * ICON + x) * 4) as usize;
+            rgba[px..px + 4].copy_from_slice(if lit { &disc } else { &BACKGROUND });
+        }
+    }
+    rgba
+}
+
+fn icon_png(color: [u8; 3], on: bool) -> Result<Vec<u8>> {
+    let image = RgbaImage::from_raw(ICON, ICON, icon_rgba(color, on))
+        .ok_or_else(|| anyhow!("icon buffer size mismatch"))?;
+    let mut buf = Vec::new();
+    image.write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)?;
+    Ok(buf)
+}
+
+fn icon_response(color: [u8; 3], on: bool) -> Response<Vec<u8>> {
+    match icon_png(color, on) {
+        Ok(png) => respond(StatusCode::OK, "image/png", png),
+        Err(e) => error(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_allows_a_burst_then_refills() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(3.0, 1.0, start);
+        assert!((0..3).all(|_| bucket.take(start).is_ok()));
+        let wait = bucket.take(start).unwrap_err();
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+        assert!(bucket.take(start + Duration::from_secs(1)).is_ok());
+        assert!(bucket.take(start + Duration::from_secs(1)).is_err());
+    }
+
+    #[test]
+    fn token_must_match_exactly() {
+        assert!(token_matches(Some("abc123"), "abc123"));
+        assert!(!token_matches(Some("abc124"), "abc123"));
+        assert!(!token_matches(Some("abc"), "abc123"));
+        assert!(!token_matches(None, "abc123"));
+    }
+
+    #[test]
+    fn overlay_state_has_percent_and_eta() {
+        assert_eq!(overlay_state(None), json!({ "mining": false }));
+        let status = json!({
+            "is_mining": true, "game_name": "Game", "channel_login": "chan",
+            "current_minutes": 45, "required_minutes": 60,
+        });
+        let state = overlay_state(Some(&status));
+        assert_eq!(state["percent"], 75.0);
+        assert_eq!(state["eta_secs"], 900);
+        assert_eq!(state["drop_name"], Value::Null);
+
+        let paused = json!({ "is_mining": true, "paused": true, "current_minutes": 1, "required_minutes": 3 });
+        let state = overlay_state(Some(&paused));
+        assert_eq!(state["percent"], 33.3);
+        assert_eq!(state["eta_secs"], Value::Null);
+    }
+
+    #[test]
+    fn icons_are_filled_when_on_and_rings_when_off() {
+        let center = (((ICON / 2) * ICON + ICON / 2) * 4) as usize;
+        let on = icon_rgba(LIVE, true);
+        let off = icon_rgba(LIVE, false);
+        assert_eq!(on.len(), (ICON * ICON * 4) as usize);
+        assert_eq!(on[center..center + 4], [LIVE[0], LIVE[1], LIVE[2], 255]);
+        assert_eq!(off[center..center + 4], BACKGROUND);
+        assert!(off.chunks(4).any(|p| p == [LIVE[0], LIVE[1], LIVE[2], 255]));
+        assert_eq!(&icon_png(OFF, false).unwrap()[1..4], b"PNG");
+    }
+}
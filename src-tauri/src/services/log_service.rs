@@ -14,6 +14,13 @@ const MAX_LOGS: usize = 500;
 const MAX_ACTIVITY_HISTORY: usize = 15;
 // Rotate the local crash log once it grows past ~1 MB so it can't balloon unbounded.
 const MAX_CRASH_LOG_BYTES: u64 = 1_000_000;
+// Structured error records kept in errors.json (oldest dropped first).
+const MAX_ERROR_RECORDS: usize = 50;
+// Errors included in an issue draft, and the cap on its body so the new-issue
+// URL stays well under what browsers and GitHub accept.
+const ISSUE_DRAFT_ERRORS: usize = 10;
+const MAX_ISSUE_BODY_CHARS: usize = 6000;
+const NEW_ISSUE_URL: &str = "https://github.com/winters27/StreamNook/issues/new";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -45,6 +52,20 @@ pub struct LogEntry {
     pub data: Option<serde_json::Value>,
 }
 
+/// One captured error in errors.json: a backend panic or an error-level log
+/// entry (which is how frontend error events arrive).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub timestamp: String,
+    /// `panic` or `log`.
+    pub source: String,
+    pub category: String,
+    pub message: String,
+    /// `file:line` for panics.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct ActivityEntry {
     pub timestamp: String,
@@ -61,6 +82,9 @@ lazy_static! {
         logs: VecDeque::with_capacity(MAX_LOGS),
         activity_history: VecDeque::with_capacity(MAX_ACTIVITY_HISTORY),
     }));
+    // Held across errors.json's read-modify-write. A std mutex, since the
+    // panic hook can't await.
+    static ref ERROR_RECORDS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
 }
 
 pub struct LogService;
@@ -110,6 +134,15 @@ impl LogService {
             let activity: Vec<ActivityEntry> = state.activity_history.iter().cloned().collect();
             drop(state); // Release lock before file I/O
 
+            let record = ErrorRecord {
+                timestamp: entry.timestamp.clone(),
+                source: "log".to_string(),
+                category: entry.category.clone(),
+                message: entry.message.clone(),
+                location: None,
+            };
+            tokio::task::spawn_blocking(move || Self::record_error(record));
+
             tokio::spawn(async move {
                 if let Err(e) = LogService::append_to_crash_log(entry, breadcrumbs, activity).await
                 {
@@ -319,4 +352,168 @@ impl LogService {
         file.write_all(block.as_bytes()).await?;
         Ok(())
     }
+
+    /// Capture backend panics into errors.json before the default hook runs.
+    /// Call once at startup.
+    pub fn install_panic_hook() {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic".to_string());
+            Self::record_error(ErrorRecord {
+                timestamp: Utc::now().to_rfc3339(),
+                source: "panic".to_string(),
+                category: format!(
+                    "thread {}",
+                    std::thread::current().name().unwrap_or("unnamed")
+                ),
+                message,
+                location: info
+                    .location()
+                    .map(|l| format!("{}:{}", l.file(), l.line())),
+            });
+            previous(info);
+        }));
+    }
+
+    /// `<app_data>/logs/errors.json`.
+    fn error_records_path() -> Result<std::path::PathBuf> {
+        Ok(Self::crash_log_path()?.with_file_name("errors.json"))
+    }
+
+    /// Captured errors, oldest first. Empty if none were recorded.
+    pub fn get_error_records() -> Vec<ErrorRecord> {
+        Self::error_records_path()
+            .ok()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Append to errors.json. Synchronous so the panic hook can use it.
+    /// Serialized so two errors at once can't each drop the other's record.
+    fn record_error(record: ErrorRecord) {
+        let Ok(path) = Self::error_records_path() else {
+            return;
+        };
+        // A panic elsewhere while holding it leaves nothing half-done worth
+        // refusing over; the file is rewritten whole.
+        let _guard = ERROR_RECORDS_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut records = Self::get_error_records();
+        records.push(record);
+        if records.len() > MAX_ERROR_RECORDS {
+            records.drain(..records.len() - MAX_ERROR_RECORDS);
+        }
+        if let Ok(json) = serde_json::to_string_pretty(&records) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+
+    /// Pre-filled GitHub new-issue URL: version, OS, and the most recent
+    /// captured errors, with tokens and the home directory scrubbed. Nothing is
+    /// sent; the user reviews and submits it in their browser.
+    pub fn issue_draft_url(title: Option<String>, description: Option<String>) -> String {
+        let records = Self::get_error_records();
+        let recent = &records[records.len().saturating_sub(ISSUE_DRAFT_ERRORS)..];
+
+        let mut body = String::new();
+        body.push_str("**What happened?**\n");
+        body.push_str(
+            description
+                .as_deref()
+                .filter(|d| !d.trim().is_empty())
+                .unwrap_or("<!-- What were you doing, and what did you expect? -->"),
+        );
+        body.push_str(&format!(
+            "\n\n**Environment**\n- StreamNook {}\n- {} ({})\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ));
+        if !recent.is_empty() {
+            body.push_str("\n**Recent errors**\n```\n");
+            for r in recent {
+                let message: String = r.message.chars().take(300).collect();
+                body.push_str(&format!(
+                    "{} [{}] [{}] {}{}\n",
+                    r.timestamp,
+                    r.source,
+                    r.category,
+                    message,
+                    r.location
+                        .as_deref()
+                        .map(|l| format!(" at {}", l))
+                        .unwrap_or_default()
+                ));
+            }
+            body.push_str("```\n");
+        }
+
+        let mut body = sanitize_report(&body);
+        if body.chars().count() > MAX_ISSUE_BODY_CHARS {
+            body = body.chars().take(MAX_ISSUE_BODY_CHARS).collect();
+            body.push_str("\n…(truncated)\n```");
+        }
+        let title = sanitize_report(title.as_deref().unwrap_or_default().trim());
+        format!(
+            "{}?title={}&body={}",
+            NEW_ISSUE_URL,
+            urlencoding::encode(&title),
+            urlencoding::encode(&body)
+        )
+    }
+}
+
+/// Strip credentials and the user's home path from text headed off-device.
+fn sanitize_report(text: &str) -> String {
+    static TOKEN_PREFIX: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"(?i)\b(oauth:|bearer\s+|oauth\s+)[a-z0-9._-]{8,}").unwrap()
+    });
+    static TOKEN_PARAM: once_cell::sync::Lazy<regex::Regex> = once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(
+            r#"(?i)\b(access_token|refresh_token|auth-token|auth_token|client_secret|token)(["']?\s*[=:]\s*["']?)[^&\s"',}]+"#,
+        )
+        .unwrap()
+    });
+
+    let mut out = TOKEN_PREFIX
+        .replace_all(text, "${1}[redacted]")
+        .into_owned();
+    out = TOKEN_PARAM
+        .replace_all(&out, "${1}${2}[redacted]")
+        .into_owned();
+    if let Some(home) = dirs::home_dir().and_then(|h| h.to_str().map(str::to_string)) {
+        if home.len() > 1 {
+            out = out.replace(&home, "~");
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_sanitizer_redacts_tokens() {
+        let text = "irc PASS oauth:abcdef0123456789 | Authorization: Bearer xyz987654321 \
+                    | url?access_token=secret123&x=1 | {\"refresh_token\":\"r3fr3sh\"}";
+        let out = sanitize_report(text);
+        for secret in ["abcdef0123456789", "xyz987654321", "secret123", "r3fr3sh"] {
+            assert!(!out.contains(secret), "{secret} leaked: {out}");
+        }
+        assert!(out.contains("&x=1"));
+    }
+
+    #[test]
+    fn report_sanitizer_leaves_plain_text_alone() {
+        let text = "Failed to load emotes for channel foo (HTTP 500)";
+        assert_eq!(sanitize_report(text), text);
+    }
 }
@@ -0,0 +1,103 @@
+//! The watched stream as an OS media session (Windows SMTC, Linux MPRIS,
+//! macOS Now Playing), so media keys and the system media flyout show what's
+//! playing and can stop / restart it.
+//!
+//! Playback lives in the frontend player, so control events are forwarded to
+//! the main window as `media-session-control` {action: play|pause|toggle|stop}.
+
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde_json::json;
+use souvlaki::{MediaControlEvent, MediaControls, MediaMetadata, MediaPlayback, PlatformConfig};
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// `None` until `init`, or when the platform refused a session.
+static CONTROLS: Lazy<StdMutex<Option<MediaControls>>> = Lazy::new(|| StdMutex::new(None));
+
+/// Create the session. Call once from setup, after the main window exists
+/// (SMTC is bound to its HWND).
+pub fn init(app: &AppHandle) {
+    #[cfg(windows)]
+    let hwnd = match app.get_webview_window("main").and_then(|w| w.hwnd().ok()) {
+        Some(hwnd) => Some(hwnd.0 as *mut std::ffi::c_void),
+        None => {
+            warn!("[MediaSession] No main window handle; media session disabled");
+            return;
+        }
+    };
+    #[cfg(not(windows))]
+    let hwnd = None;
+
+    let config = PlatformConfig {
+        dbus_name: "streamnook",
+        display_name: "StreamNook",
+        hwnd,
+    };
+    let mut controls = match MediaControls::new(config) {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("[MediaSession] Unavailable: {:?}", e);
+            return;
+        }
+    };
+
+    let handle = app.clone();
+    let attached = controls.attach(move |event| {
+        let action = match event {
+            MediaControlEvent::Play => "play",
+            MediaControlEvent::Pause => "pause",
+            MediaControlEvent::Toggle => "toggle",
+            MediaControlEvent::Stop => "stop",
+            _ => return,
+        };
+        debug!("[MediaSession] {}", action);
+        if let Some(main) = handle.get_webview_window("main") {
+            let _ = main.emit("media-session-control", json!({ "action": action }));
+        }
+    });
+    if let Err(e) = attached {
+        warn!("[MediaSession] Couldn't attach handler: {:?}", e);
+        return;
+    }
+
+    if let Ok(mut slot) = CONTROLS.lock() {
+        *slot = Some(controls);
+    }
+}
+
+/// Show `channel` as playing. The title reads "Channel — Game" so both fit in
+/// flyouts that only show one line.
+pub fn set_playing(channel: &str, game_name: &str, thumbnail_url: Option<&str>) {
+    let title = if game_name.is_empty() {
+        channel.to_string()
+    } else {
+        format!("{} — {}", channel, game_name)
+    };
+    with_controls(|c| {
+        c.set_metadata(MediaMetadata {
+            title: Some(&title),
+            artist: Some("Twitch"),
+            cover_url: thumbnail_url,
+            ..Default::default()
+        })?;
+        c.set_playback(MediaPlayback::Playing { progress: None })
+    });
+}
+
+/// Mark the session stopped. Metadata is kept so the flyout still names the
+/// last channel and its play button restarts it.
+pub fn set_stopped() {
+    with_controls(|c| c.set_playback(MediaPlayback::Stopped));
+}
+
+fn with_controls(f: impl FnOnce(&mut MediaControls) -> Result<(), souvlaki::Error>) {
+    let Ok(mut slot) = CONTROLS.lock() else {
+        return;
+    };
+    if let Some(controls) = slot.as_mut() {
+        if let Err(e) = f(controls) {
+            debug!("[MediaSession] Update failed: {:?}", e);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Caps for in-memory collections that otherwise grow for as long as the app
+//! runs (claim history, drop progress, chat rosters). A week-long mining
+//! session used to carry every entry it ever saw; each owner now trims on
+//! insert with these helpers and reports its size through
+//! `get_memory_cache_stats`.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Mutex as StdMutex;
+
+/// Entries evicted per cache since startup.
+static EVICTED: Lazy<StdMutex<HashMap<&'static str, u64>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStat {
+    pub name: &'static str,
+    pub entries: usize,
+    pub capacity: usize,
+    /// Entries dropped to stay under `capacity` since startup.
+    pub evicted: u64,
+}
+
+impl CacheStat {
+    pub fn new(name: &'static str, entries: usize, capacity: usize) -> Self {
+        let evicted = EVICTED
+            .lock()
+            .map(|e| e.get(name).copied().unwrap_or(0))
+            .unwrap_or(0);
+        Self {
+            name,
+            entries,
+            capacity,
+            evicted,
+        }
+    }
+}
+
+fn record(name: &'static str, count: usize) {
+    if count == 0 {
+        return;
+    }
+    if let Ok(mut evicted) = EVICTED.lock() {
+        *evicted.entry(name).or_default() += count as u64;
+    }
+}
+
+/// Drop the oldest (front) entries of an append-only list beyond `cap`.
+pub fn trim_front<T>(name: &'static str, items: &mut Vec<T>, cap: usize) {
+    let excess = items.len().saturating_sub(cap);
+    items.drain(..excess);
+    record(name, excess);
+}
+
+/// Drop the entries with the smallest `age_key` (the stalest) beyond `cap`.
+pub fn trim_map<K, V, O>(
+    name: &'static str,
+    map: &mut HashMap<K, V>,
+    cap: usize,
+    age_key: impl Fn(&V) -> O,
+) where
+    K: Eq + Hash + Clone,
+    O: Ord,
+{
+    let excess = map.len().saturating_sub(cap);
+    if excess == 0 {
+        return;
+    }
+    let mut by_age: Vec<(O, K)> = map.iter().map(|(k, v)| (age_key(v), k.clone())).collect();
+    by_age.sort_by(|a, b| a.0.cmp(&b.0));
+    for (_, key) in by_age.into_iter().take(excess) {
+        map.remove(&key);
+    }
+    record(name, excess);
+}
+
+/// Sets carry no age, so past `cap` they're cut back to half of it, whichever
+/// entries those are. Only for sets where losing an entry just costs a redo
+/// (a repeated claim attempt, a re-announced chatter).
+pub fn trim_set<T: Eq + Hash + Clone>(name: &'static str, set: &mut HashSet<T>, cap: usize) {
+    if set.len() <= cap {
+        return;
+    }
+    let excess = set.len() - cap / 2;
+    let evict: Vec<T> = set.iter().take(excess).cloned().collect();
+    for item in &evict {
+        set.remove(item);
+    }
+    record(name, excess);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_keep_the_newest_entries() {
+        let mut list: Vec<u32> = (0..10).collect();
+        trim_front("test_list", &mut list, 4);
+        assert_eq!(list, [6, 7, 8, 9]);
+
+        let mut map: HashMap<&str, u32> = HashMap::from([("a", 1), ("b", 5), ("c", 3)]);
+        trim_map("test_map", &mut map, 2, |v| *v);
+        assert!(!map.contains_key("a"));
+        assert_eq!(map.len(), 2);
+
+        let mut set: HashSet<u32> = (0..11).collect();
+        trim_set("test_set", &mut set, 10);
+        assert_eq!(set.len(), 5);
+        assert_eq!(CacheStat::new("test_list", 4, 4).evicted, 6);
+    }
+}
@@ -0,0 +1,287 @@
+//! Watch-minute accounting for drops mining (`get_mining_efficiency`): the
+//! watch payloads the mining plugin says it sent, next to the minutes Twitch
+//! actually credited, to explain progress that runs slower than real time.
+//!
+//! Payloads come in through the plugin's `watch_payload_sent` notification.
+//! Credits are read off `drops.status`: every time `current_minutes` goes up
+//! on the same campaign, the increase is a credit. A drop finishing resets
+//! the counter, so the minute that completes a drop isn't counted; the ratio
+//! runs a touch low across drop boundaries.
+//!
+//! Both are kept for two days, persisted to `mining_audit.json` in the app
+//! data dir at most every `SAVE_INTERVAL_SECS`, and on exit (`flush`).
+
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::RwLock as StdRwLock;
+
+const AUDIT_FILE: &str = "mining_audit.json";
+const RETENTION_HOURS: i64 = 48;
+/// Minimum gap between disk writes; payloads arrive about once a minute.
+const SAVE_INTERVAL_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchPayload {
+    pub sent_at: DateTime<Utc>,
+    pub channel_id: String,
+    pub channel_login: String,
+    pub broadcast_id: String,
+    #[serde(default)]
+    pub campaign_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct MinuteCredit {
+    credited_at: DateTime<Utc>,
+    campaign_id: String,
+    minutes: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AuditLog {
+    payloads: Vec<WatchPayload>,
+    credits: Vec<MinuteCredit>,
+}
+
+#[derive(Default)]
+struct AuditStore {
+    log: AuditLog,
+    /// (campaign_id, current_minutes) from the last `drops.status`.
+    last_progress: Option<(String, u32)>,
+    last_saved: Option<DateTime<Utc>>,
+    /// Changes made since the last save.
+    unsaved: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelPayloads {
+    pub channel_login: String,
+    pub payloads: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CampaignEfficiency {
+    pub campaign_id: String,
+    pub payloads_sent: u32,
+    pub minutes_credited: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MiningEfficiency {
+    pub since: DateTime<Utc>,
+    pub payloads_sent: u32,
+    pub minutes_credited: u32,
+    /// Minutes credited per payload sent; None before any payload.
+    pub efficiency: Option<f64>,
+    pub last_payload_at: Option<DateTime<Utc>>,
+    pub channels: Vec<ChannelPayloads>,
+    pub campaigns: Vec<CampaignEfficiency>,
+}
+
+static STORE: Lazy<StdRwLock<AuditStore>> = Lazy::new(|| {
+    StdRwLock::new(AuditStore {
+        log: load_from_disk(),
+        ..Default::default()
+    })
+});
+
+fn audit_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(AUDIT_FILE))
+}
+
+fn load_from_disk() -> AuditLog {
+    audit_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(log: &AuditLog) {
+    let Some(path) = audit_path() else { return };
+    match serde_json::to_string(log) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[MiningAudit] Failed to write {}: {}", AUDIT_FILE, e);
+            }
+        }
+        Err(e) => warn!("[MiningAudit] Failed to serialize audit log: {}", e),
+    }
+}
+
+/// Drop entries past retention and save if a save is due.
+fn trim_and_save(store: &mut AuditStore) {
+    let now = Utc::now();
+    let cutoff = now - Duration::hours(RETENTION_HOURS);
+    store.log.payloads.retain(|p| p.sent_at > cutoff);
+    store.log.credits.retain(|c| c.credited_at > cutoff);
+    let save_due = !store
+        .last_saved
+        .is_some_and(|t| (now - t).num_seconds() < SAVE_INTERVAL_SECS);
+    if save_due {
+        store.last_saved = Some(now);
+        store.unsaved = false;
+        save_to_disk(&store.log);
+    } else {
+        store.unsaved = true;
+    }
+}
+
+/// Write out changes still waiting for the save interval. Called at exit.
+pub fn flush() {
+    // Never touched this session: nothing to write.
+    let Some(store) = Lazy::get(&STORE) else {
+        return;
+    };
+    let Ok(mut store) = store.write() else {
+        return;
+    };
+    if store.unsaved {
+        store.unsaved = false;
+        store.last_saved = Some(Utc::now());
+        save_to_disk(&store.log);
+    }
+}
+
+/// Record a watch payload the mining plugin sent.
+pub fn record_payload(payload: WatchPayload) {
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+    store.log.payloads.push(payload);
+    trim_and_save(&mut store);
+}
+
+/// Pick minute credits out of a `drops.status` push.
+pub fn observe_status(status: &serde_json::Value) {
+    let campaign_id = status["campaign_id"].as_str().unwrap_or("");
+    let Some(minutes) = status["current_minutes"].as_u64() else {
+        return;
+    };
+    if campaign_id.is_empty() {
+        return;
+    }
+    let minutes = minutes as u32;
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+    let credited = match &store.last_progress {
+        Some((last_campaign, last)) if last_campaign == campaign_id && minutes > *last => {
+            minutes - last
+        }
+        _ => 0,
+    };
+    store.last_progress = Some((campaign_id.to_string(), minutes));
+    if credited > 0 {
+        store.log.credits.push(MinuteCredit {
+            credited_at: Utc::now(),
+            campaign_id: campaign_id.to_string(),
+            minutes: credited,
+        });
+        trim_and_save(&mut store);
+    }
+}
+
+fn summarize(log: &AuditLog, since: DateTime<Utc>) -> MiningEfficiency {
+    let payloads: Vec<&WatchPayload> = log.payloads.iter().filter(|p| p.sent_at > since).collect();
+    let credits: Vec<&MinuteCredit> = log
+        .credits
+        .iter()
+        .filter(|c| c.credited_at > since)
+        .collect();
+
+    let mut channels: BTreeMap<&str, u32> = BTreeMap::new();
+    let mut campaigns: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for payload in &payloads {
+        *channels.entry(&payload.channel_login).or_default() += 1;
+        if let Some(campaign_id) = &payload.campaign_id {
+            campaigns.entry(campaign_id).or_default().0 += 1;
+        }
+    }
+    for credit in &credits {
+        campaigns.entry(&credit.campaign_id).or_default().1 += credit.minutes;
+    }
+
+    let payloads_sent = payloads.len() as u32;
+    let minutes_credited = credits.iter().map(|c| c.minutes).sum();
+    let mut channels: Vec<ChannelPayloads> = channels
+        .into_iter()
+        .map(|(login, payloads)| ChannelPayloads {
+            channel_login: login.to_string(),
+            payloads,
+        })
+        .collect();
+    channels.sort_by(|a, b| b.payloads.cmp(&a.payloads));
+    MiningEfficiency {
+        since,
+        payloads_sent,
+        minutes_credited,
+        efficiency: (payloads_sent > 0).then(|| minutes_credited as f64 / payloads_sent as f64),
+        last_payload_at: payloads.iter().map(|p| p.sent_at).max(),
+        channels,
+        campaigns: campaigns
+            .into_iter()
+            .map(
+                |(id, (payloads_sent, minutes_credited))| CampaignEfficiency {
+                    campaign_id: id.to_string(),
+                    payloads_sent,
+                    minutes_credited,
+                },
+            )
+            .collect(),
+    }
+}
+
+/// Payloads sent against minutes credited over the last `hours` (at most the
+/// two days kept).
+pub fn efficiency(hours: u32) -> MiningEfficiency {
+    let hours = (hours as i64).clamp(1, RETENTION_HOURS);
+    let since = Utc::now() - Duration::hours(hours);
+    match STORE.read() {
+        Ok(store) => summarize(&store.log, since),
+        Err(_) => summarize(&AuditLog::default(), since),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(channel: &str, campaign: &str, mins_ago: i64) -> WatchPayload {
+        WatchPayload {
+            sent_at: Utc::now() - Duration::minutes(mins_ago),
+            channel_id: channel.to_string(),
+            channel_login: channel.to_string(),
+            broadcast_id: "b".to_string(),
+            campaign_id: Some(campaign.to_string()),
+        }
+    }
+
+    #[test]
+    fn efficiency_is_credits_per_payload_in_the_window() {
+        let log = AuditLog {
+            payloads: vec![
+                payload("alpha", "c1", 3),
+                payload("alpha", "c1", 2),
+                payload("beta", "c1", 1),
+                payload("beta", "c1", 0),
+                payload("alpha", "c1", 120),
+            ],
+            credits: vec![MinuteCredit {
+                credited_at: Utc::now(),
+                campaign_id: "c1".to_string(),
+                minutes: 3,
+            }],
+        };
+        let summary = summarize(&log, Utc::now() - Duration::hours(1));
+        assert_eq!(summary.payloads_sent, 4);
+        assert_eq!(summary.minutes_credited, 3);
+        assert_eq!(summary.efficiency, Some(0.75));
+        assert_eq!(summary.campaigns[0].payloads_sent, 4);
+        assert_eq!(summary.channels.len(), 2);
+    }
+}
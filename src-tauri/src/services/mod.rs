@@ -5,53 +5,117 @@ pub mod bttv_pro_service;
 
 pub mod account_store;
 pub mod ad_detect;
+pub mod api_error;
+pub mod audio_cues;
 pub mod auth_proxy;
+pub mod autocomplete;
+pub mod automation_rules;
+pub mod backup;
+pub mod cache_bundle;
 pub mod cache_service;
+pub mod campaign_availability;
+pub mod campaign_eligibility;
+pub mod campaign_runs;
 pub mod ll_diagnostics;
+pub mod channel_caches;
 pub mod channel_points_websocket_service;
+pub mod channel_scoring;
 pub mod chat_logger_service;
+pub mod chat_ready;
 pub mod chat_service;
+pub mod chat_tabs;
+pub mod chatter_tracker;
+pub mod client_import;
+pub mod combined_login;
 pub mod cookie_jar_service;
+pub mod dependency_check;
 pub mod diagnostic_logger;
 pub mod hls_projection;
 pub mod discord_service;
+pub mod download_pool;
 pub mod drops_auth_service;
+pub mod drops_blacklist;
 pub mod drops_service;
+pub mod drops_wishlist;
 pub mod emoji_service;
+pub mod emote_frames;
 pub mod emote_prefetch_service;
 pub mod emote_service;
 pub mod emote_set_cache;
+pub mod event_bus;
 pub mod eventsub_moderation;
 pub mod eventsub_service;
+pub mod favorite_warmup;
+pub mod feature_flags;
+pub mod follow_batch;
+pub mod frame_capture;
+pub mod game_mode;
+pub mod game_resolver;
+pub mod global_hotkeys;
 pub mod http;
+pub mod idle_monitor;
+pub mod inactive_follows;
 pub mod irc_service;
 pub mod kick_auth_service;
 pub mod layout_service;
 pub mod modroom_auth_service;
 pub mod youtube_auth_service;
 pub mod live_notification_service;
+pub mod local_api;
 pub mod ll_origin;
 #[cfg(test)]
 mod ll_soak;
 pub mod log_service;
+pub mod media_session;
+pub mod memory_caches;
+pub mod mining_audit;
 pub mod runtime_watchdog;
 pub mod ui_hang_watchdog;
 pub mod mod_log_storage_service;
 pub mod multi_nook_server;
+pub mod obs_service;
+pub mod playback_priority;
+pub mod power_saver;
 pub mod profile_cache_service;
 pub mod providers;
 pub mod quality;
+pub mod raid_events;
+pub mod recommendations;
+pub mod recording_guard;
+pub mod recording_postprocess;
+pub mod recordings_archive;
+pub mod render_hints;
+pub mod resume_watch;
+pub mod script_hooks;
+pub mod settings_sync;
 pub mod seventv_auth_service;
 pub mod seventv_eventapi;
 pub mod song_id;
+pub mod spam_collapse;
+pub mod squad_streams;
+pub mod startup;
+pub mod stream_info_poller;
 pub mod stream_server;
+pub mod stream_uptime_service;
+pub mod tdm_import;
+pub mod trace_ids;
 pub mod ts_fmp4;
+pub mod twitch_api;
+#[cfg(test)]
+mod twitch_api_harness;
 pub mod twitch_auth_service;
 pub mod twitch_resolver;
 pub mod twitch_service;
 pub mod universal_cache_service;
 pub mod user_message_history_service;
+pub mod viewer_stats_service;
 pub mod watch_heartbeat_service;
+pub mod watch_history;
+pub mod watch_sessions;
+pub mod webhooks;
+pub mod whats_new;
 pub mod whisper_history_service;
 pub mod whisper_service;
 pub mod whisper_storage_service;
+pub mod window_badge;
+pub mod window_modes;
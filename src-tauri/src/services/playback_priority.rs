@@ -0,0 +1,197 @@
+//! CPU priority and GPU choice for playback (`settings.playback_resources`),
+//! so a stream, or mining playback in the background, leaves room for a game.
+//!
+//! There's no player child process: the video decodes in the webview's own
+//! processes. So `apply` sets the priority on this process and every process
+//! under it (on Windows the WebView2 browser, GPU and renderer processes) when
+//! a stream starts, and `reset` puts it back to normal when it stops. What was
+//! last applied is tracked, so the reset follows it rather than the setting,
+//! which may have changed while the stream played. The GPU
+//! preference is a WebView2 browser argument, read once when the first
+//! webview starts, so a change takes effect on the next launch.
+
+use crate::models::settings::{GpuPreference, ProcessPriority};
+use log::debug;
+use std::sync::Mutex as StdMutex;
+
+/// The priority the process tree was last set to.
+static APPLIED: StdMutex<ProcessPriority> = StdMutex::new(ProcessPriority::Normal);
+
+/// Add the GPU preference to the WebView2 browser arguments. Call before any
+/// window is created.
+#[cfg(windows)]
+pub(crate) use imp::for_each_process;
+
+pub fn apply_gpu_preference(preference: GpuPreference) {
+    let flag = match preference {
+        GpuPreference::Default => return,
+        GpuPreference::PowerSaving => "--force_low_power_gpu",
+        GpuPreference::HighPerformance => "--force_high_performance_gpu",
+    };
+    const VAR: &str = "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS";
+    let args = match std::env::var(VAR) {
+        Ok(existing) if !existing.is_empty() => format!("{} {}", existing, flag),
+        _ => flag.to_string(),
+    };
+    std::env::set_var(VAR, args);
+}
+
+/// Set `priority` on this process and its descendants, unless it's what they
+/// already have.
+pub async fn apply(priority: ProcessPriority) {
+    if *APPLIED.lock().unwrap() == priority {
+        return;
+    }
+    let result = tokio::task::spawn_blocking(move || imp::set_tree_priority(priority)).await;
+    match result {
+        Ok(Ok(count)) => {
+            *APPLIED.lock().unwrap() = priority;
+            debug!("[PlaybackPriority] {:?} on {} process(es)", priority, count);
+        }
+        Ok(Err(e)) => debug!("[PlaybackPriority] Couldn't set {:?}: {}", priority, e),
+        Err(e) => debug!("[PlaybackPriority] {}", e),
+    }
+}
+
+/// Back to normal priority, once nothing plays; a no-op when nothing else
+/// was applied. Outside Windows only a
+/// privileged user can take a lowered priority back up, so there it stays.
+pub async fn reset() {
+    apply(ProcessPriority::Normal).await;
+}
+
+/// This process and everything under it, from (pid, parent pid) pairs.
+fn descendants_of(root: u32, processes: &[(u32, u32)]) -> Vec<u32> {
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        let parent = tree[i];
+        for &(pid, ppid) in processes {
+            if ppid == parent && pid != parent && !tree.contains(&pid) {
+                tree.push(pid);
+            }
+        }
+        i += 1;
+    }
+    tree
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::descendants_of;
+    use crate::models::settings::ProcessPriority;
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+    use windows::Win32::System::Threading::{
+        OpenProcess, SetPriorityClass, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_SET_INFORMATION,
+    };
+
+    /// Call `visit` with each running process, from one system snapshot.
+    /// Shared with game_mode's running-game check.
+    pub fn for_each_process(mut visit: impl FnMut(&PROCESSENTRY32W)) -> anyhow::Result<()> {
+        unsafe {
+            let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)?;
+            let mut entry = PROCESSENTRY32W {
+                dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+                ..Default::default()
+            };
+            if Process32FirstW(snapshot, &mut entry).is_ok() {
+                loop {
+                    visit(&entry);
+                    if Process32NextW(snapshot, &mut entry).is_err() {
+                        break;
+                    }
+                }
+            }
+            let _ = CloseHandle(snapshot);
+        }
+        Ok(())
+    }
+
+    fn processes() -> anyhow::Result<Vec<(u32, u32)>> {
+        let mut list = Vec::new();
+        for_each_process(|entry| list.push((entry.th32ProcessID, entry.th32ParentProcessID)))?;
+        Ok(list)
+    }
+
+    pub fn set_tree_priority(priority: ProcessPriority) -> anyhow::Result<usize> {
+        let class = match priority {
+            ProcessPriority::Idle => IDLE_PRIORITY_CLASS,
+            ProcessPriority::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            ProcessPriority::Normal => NORMAL_PRIORITY_CLASS,
+            ProcessPriority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+        };
+        let mut set = 0;
+        for pid in descendants_of(std::process::id(), &processes()?) {
+            unsafe {
+                let Ok(handle) = OpenProcess(PROCESS_SET_INFORMATION, false, pid) else {
+                    continue;
+                };
+                if SetPriorityClass(handle, class).is_ok() {
+                    set += 1;
+                }
+                let _ = CloseHandle(handle);
+            }
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    use super::descendants_of;
+    use crate::models::settings::ProcessPriority;
+    use std::process::Command;
+
+    fn processes() -> anyhow::Result<Vec<(u32, u32)>> {
+        let output = Command::new("ps")
+            .args(["-A", "-o", "pid=,ppid="])
+            .output()?;
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                Some((fields.next()?.parse().ok()?, fields.next()?.parse().ok()?))
+            })
+            .collect())
+    }
+
+    /// Raising priority (a negative niceness) needs privileges most users
+    /// don't have; `renice` refuses and the processes keep theirs.
+    pub fn set_tree_priority(priority: ProcessPriority) -> anyhow::Result<usize> {
+        let niceness = match priority {
+            ProcessPriority::Idle => 19,
+            ProcessPriority::BelowNormal => 10,
+            ProcessPriority::Normal => 0,
+            ProcessPriority::AboveNormal => -5,
+        };
+        let pids = descendants_of(std::process::id(), &processes()?);
+        let status = Command::new("renice")
+            .arg(niceness.to_string())
+            .arg("-p")
+            .args(pids.iter().map(u32::to_string))
+            .output()?
+            .status;
+        if !status.success() {
+            anyhow::bail!("renice exited with {}", status);
+        }
+        Ok(pids.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descendants_cover_the_whole_tree_and_nothing_else() {
+        let processes = [(10, 1), (11, 10), (12, 11), (13, 10), (20, 1), (21, 20)];
+        let mut tree = descendants_of(10, &processes);
+        tree.sort();
+        assert_eq!(tree, vec![10, 11, 12, 13]);
+    }
+}
@@ -0,0 +1,95 @@
+//! Power saver: while the main window is minimized or hidden to the tray,
+//! non-critical background polls run at a fraction of their normal rate and
+//! UI-only events (ones nothing but a visible window would render) are dropped.
+//! Cuts CPU wakeups during long unattended sessions such as overnight drops.
+//!
+//! Gated on the `power_saver` setting. Chat, notifications, and anything that
+//! earns or claims rewards keep their normal cadence.
+//!
+//! Polls also stretch while a watched game runs (services::game_mode), window
+//! in sight or not; UI events still go out then, since the window may be on
+//! another display.
+
+use crate::services::event_bus;
+use log::debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Multiplier applied to non-critical poll intervals while active.
+const STRETCH_FACTOR: u32 = 4;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static WINDOW_BACKGROUND: AtomicBool = AtomicBool::new(false);
+static GAME_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Apply the `power_saver` setting.
+pub fn set_enabled(enabled: bool) {
+    let was_active = polls_stretched();
+    ENABLED.store(enabled, Ordering::SeqCst);
+    notify_if_changed(was_active);
+}
+
+/// Record whether the main window is out of sight (minimized or hidden).
+pub fn set_window_background(background: bool) {
+    let was_active = polls_stretched();
+    WINDOW_BACKGROUND.store(background, Ordering::SeqCst);
+    notify_if_changed(was_active);
+}
+
+/// Record whether a watched game is running and polls should slow for it.
+pub fn set_game_running(running: bool) {
+    let was_active = polls_stretched();
+    GAME_RUNNING.store(running, Ordering::SeqCst);
+    notify_if_changed(was_active);
+}
+
+/// True when the setting is on and the main window is out of sight.
+pub fn is_active() -> bool {
+    ENABLED.load(Ordering::SeqCst) && WINDOW_BACKGROUND.load(Ordering::SeqCst)
+}
+
+/// True while non-critical polls run slower: power saving is active or a
+/// game is running.
+fn polls_stretched() -> bool {
+    is_active() || GAME_RUNNING.load(Ordering::SeqCst)
+}
+
+/// `base`, stretched while polls are slowed.
+pub fn stretch(base: Duration) -> Duration {
+    if polls_stretched() {
+        base * STRETCH_FACTOR
+    } else {
+        base
+    }
+}
+
+/// For loops driven by a fixed `tokio::time::interval`: wait out the extra
+/// time `stretch` would add on top of one tick. Returns immediately when
+/// polls aren't slowed.
+pub async fn stretch_tick(base: Duration) {
+    if polls_stretched() {
+        tokio::time::sleep(stretch(base) - base).await;
+    }
+}
+
+/// Whether UI-only events should be emitted right now.
+pub fn ui_events_allowed() -> bool {
+    !is_active()
+}
+
+fn notify_if_changed(was_active: bool) {
+    let active = polls_stretched();
+    if active == was_active {
+        return;
+    }
+    debug!(
+        "[PowerSaver] {}",
+        if active { "Active" } else { "Inactive" }
+    );
+    // The frontend stretches its own polls (thumbnails, recommendations) off
+    // this event.
+    let _ = event_bus::emit(
+        "power-saver-changed",
+        serde_json::json!({ "active": active }),
+    );
+}
@@ -108,6 +108,7 @@ pub async fn channel_emote_set(slug: &str) -> EmoteSet {
                     url: e.url.clone(),
                     provider: EmoteProvider::SevenTV,
                     is_zero_width: Some(e.zero_width),
+                    animated: None,
                     local_url: None,
                     emote_type: None,
                     owner_id: None,
@@ -129,6 +130,7 @@ pub async fn channel_emote_set(slug: &str) -> EmoteSet {
                     url: format!("https://files.kick.com/emotes/{}/fullsize", e.id),
                     provider: EmoteProvider::Kick,
                     is_zero_width: Some(false),
+                    animated: None,
                     local_url: None,
                     emote_type: Some(kick_set_label(&e.set, slug, display.as_deref())),
                     owner_id: None,
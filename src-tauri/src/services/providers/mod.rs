@@ -14,6 +14,7 @@ pub mod youtube;
 
 use crate::models::chat_layout::ChatMessage;
 use crate::services::irc_service::IrcService;
+use crate::services::render_hints;
 use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -131,9 +132,12 @@ pub async fn registry() -> &'static ProviderRegistry {
 
 /// Serialize a normalized chat message and publish it onto the local-WS bus the
 /// frontend already listens to. The bridge is brought up on demand so an adapter
-/// can publish whether or not a Twitch chat is open.
+/// can publish whether or not a Twitch chat is open. Render hints are filled in
+/// here, minus mentions: the user's login on these platforms isn't known.
 pub async fn publish_chat_message(msg: &ChatMessage) {
-    if let Ok(json) = serde_json::to_string(msg) {
+    let mut msg = msg.clone();
+    msg.metadata.render_hints = render_hints::compute(&msg, None);
+    if let Ok(json) = serde_json::to_string(&msg) {
         if let Some(tx) = IrcService::broadcaster().await {
             let _ = tx.send(json);
         }
@@ -0,0 +1,288 @@
+//! Raid trains and other marathon events (`Settings::raid_events`).
+//!
+//! While an enabled event's window is open, its channels are polled every
+//! minute. Each poll credits the time since the last one to every channel that
+//! was live (`live_secs`), the one being watched (`watched_secs`) and the one
+//! being mined (`mined_secs`); the totals are kept per event in
+//! `raid_event_stats.json`.
+//!
+//! When the channel playback or the drops session is on goes offline, the
+//! event's `follow` moves it to the next live channel in the chain: playback
+//! through `streamnook:watch`, mining through the drops plugin's
+//! `drops.switch-channel`. The player's own offline handling asks
+//! `next_channel` first, so it follows the chain without waiting for a poll.
+//!
+//! Every poll raises `raid-event-update` {event_id, live, watching, mining}.
+
+use crate::models::settings::{AppState, RaidEvent, RaidEventFollow};
+use crate::services::cache_service::get_app_data_dir;
+use crate::services::event_bus;
+use crate::services::stream_server;
+use crate::services::twitch_service::TwitchService;
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Longest gap credited for one poll, so a suspended machine doesn't count
+/// the hours it slept.
+const MAX_CREDIT: Duration = Duration::from_secs(2 * 60);
+const STATS_FILE: &str = "raid_event_stats.json";
+
+/// Event id to channel login to time tracked.
+type Stats = HashMap<String, HashMap<String, RaidChannelStats>>;
+
+static STATS: Lazy<StdMutex<Stats>> = Lazy::new(|| StdMutex::new(read_stats().unwrap_or_default()));
+/// Event id to the channels live at the last poll.
+static LIVE: Lazy<StdMutex<HashMap<String, HashSet<String>>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RaidChannelStats {
+    pub live_secs: u64,
+    pub watched_secs: u64,
+    pub mined_secs: u64,
+}
+
+fn stats_path() -> Result<PathBuf> {
+    Ok(get_app_data_dir()?.join(STATS_FILE))
+}
+
+fn read_stats() -> Result<Stats> {
+    let bytes = std::fs::read(stats_path()?)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+fn write_stats(stats: &Stats) {
+    let result = stats_path().and_then(|path| {
+        std::fs::write(path, serde_json::to_vec(stats)?)?;
+        Ok(())
+    });
+    if let Err(e) = result {
+        warn!("[RaidEvents] Failed to save stats: {}", e);
+    }
+}
+
+fn is_open(event: &RaidEvent, now: i64) -> bool {
+    event.enabled && !event.channels.is_empty() && event.starts_at <= now && now < event.ends_at
+}
+
+fn chain(event: &RaidEvent) -> Vec<String> {
+    event
+        .channels
+        .iter()
+        .map(|c| c.trim().to_lowercase())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// The channel to move to from `from`: the next live one after it in the
+/// chain, wrapping around.
+fn next_live(chain: &[String], from: &str, live: &HashSet<String>) -> Option<String> {
+    let start = chain.iter().position(|c| c == from)?;
+    (1..chain.len())
+        .map(|i| &chain[(start + i) % chain.len()])
+        .find(|c| live.contains(*c))
+        .cloned()
+}
+
+/// Where playback on `channel_login` should go now that it's offline, if it's
+/// part of an open event that follows with playback.
+pub fn next_channel(events: &[RaidEvent], channel_login: &str) -> Option<String> {
+    let login = channel_login.trim().to_lowercase();
+    let now = Utc::now().timestamp();
+    let live = LIVE.lock().unwrap();
+    events
+        .iter()
+        .filter(|e| is_open(e, now) && e.follow == RaidEventFollow::Watch)
+        .find_map(|e| {
+            let mut live = live.get(&e.id)?.clone();
+            live.remove(&login);
+            next_live(&chain(e), &login, &live)
+        })
+}
+
+/// Time tracked for event `event_id`, per channel login.
+pub fn stats(event_id: &str) -> HashMap<String, RaidChannelStats> {
+    STATS
+        .lock()
+        .unwrap()
+        .get(event_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Poll open events every minute. Call once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        let mut last_poll: Option<Instant> = None;
+        loop {
+            ticker.tick().await;
+            let now = Utc::now().timestamp();
+            let events: Vec<RaidEvent> = app
+                .state::<AppState>()
+                .settings
+                .lock()
+                .map(|s| s.raid_events.clone())
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|e| is_open(e, now))
+                .collect();
+            if events.is_empty() {
+                last_poll = None;
+                LIVE.lock().unwrap().clear();
+                continue;
+            }
+            let logins: Vec<String> = events
+                .iter()
+                .flat_map(chain)
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            let live: HashMap<String, String> =
+                match TwitchService::check_streams_online(&logins).await {
+                    Ok(streams) => streams
+                        .into_iter()
+                        .map(|s| (s.user_login.to_lowercase(), s.user_id))
+                        .collect(),
+                    Err(e) => {
+                        debug!("[RaidEvents] Live check failed: {}", e);
+                        continue;
+                    }
+                };
+            let elapsed = last_poll
+                .map(|t| t.elapsed().min(MAX_CREDIT).as_secs())
+                .unwrap_or(0);
+            last_poll = Some(Instant::now());
+            poll(&app, &events, &live, elapsed).await;
+        }
+    });
+}
+
+/// Credit time, move playback or mining along the chain, and report.
+async fn poll(app: &AppHandle, events: &[RaidEvent], live: &HashMap<String, String>, elapsed: u64) {
+    let state = app.state::<AppState>();
+    let watching = stream_server::solo_channel().map(|c| c.to_lowercase());
+    let mining = state
+        .plugin_host
+        .status("drops.status")
+        .filter(|s| s["is_mining"].as_bool().unwrap_or(false))
+        .and_then(|s| s["channel_login"].as_str().map(str::to_lowercase));
+
+    for event in events {
+        let channels = chain(event);
+        let live_here: HashSet<String> = channels
+            .iter()
+            .filter(|c| live.contains_key(*c))
+            .cloned()
+            .collect();
+        if elapsed > 0 {
+            let mut stats = STATS.lock().unwrap();
+            let event_stats = stats.entry(event.id.clone()).or_default();
+            for login in &live_here {
+                let channel = event_stats.entry(login.clone()).or_default();
+                channel.live_secs += elapsed;
+                if watching.as_ref() == Some(login) {
+                    channel.watched_secs += elapsed;
+                }
+                if mining.as_ref() == Some(login) {
+                    channel.mined_secs += elapsed;
+                }
+            }
+        }
+        LIVE.lock()
+            .unwrap()
+            .insert(event.id.clone(), live_here.clone());
+
+        match event.follow {
+            RaidEventFollow::Off => {}
+            RaidEventFollow::Watch => {
+                let from = watching.as_ref().filter(|c| !live_here.contains(*c));
+                if let Some(next) = from.and_then(|c| next_live(&channels, c, &live_here)) {
+                    debug!("[RaidEvents] '{}': playback on to {}", event.name, next);
+                    let _ = event_bus::emit("streamnook:watch", &next);
+                }
+            }
+            RaidEventFollow::Mine => {
+                let from = mining.as_ref().filter(|c| !live_here.contains(*c));
+                if let Some(next) = from.and_then(|c| next_live(&channels, c, &live_here)) {
+                    debug!("[RaidEvents] '{}': mining on to {}", event.name, next);
+                    let args = json!({ "channel_id": live[&next] });
+                    let args = crate::commands::drops::drops_action_args(
+                        &state,
+                        "drops.switch-channel",
+                        args,
+                    )
+                    .await;
+                    if let Err(e) = state
+                        .plugin_host
+                        .invoke_action("drops.switch-channel", args)
+                        .await
+                    {
+                        warn!("[RaidEvents] Couldn't move mining to {}: {}", next, e);
+                    }
+                }
+            }
+        }
+
+        let _ = event_bus::emit(
+            "raid-event-update",
+            json!({
+                "event_id": event.id,
+                "live": live_here,
+                "watching": watching.as_ref().filter(|c| channels.contains(*c)),
+                "mining": mining.as_ref().filter(|c| channels.contains(*c)),
+            }),
+        );
+    }
+    if elapsed > 0 {
+        write_stats(&STATS.lock().unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_is_the_following_live_channel_wrapping_around() {
+        let chain: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let live = |logins: &[&str]| logins.iter().map(|l| l.to_string()).collect::<HashSet<_>>();
+        assert_eq!(next_live(&chain, "a", &live(&["c", "d"])), Some("c".into()));
+        assert_eq!(next_live(&chain, "c", &live(&["a", "b"])), Some("a".into()));
+        assert_eq!(next_live(&chain, "b", &live(&["b"])), None);
+        assert_eq!(next_live(&chain, "x", &live(&["a"])), None);
+    }
+
+    #[test]
+    fn events_are_open_within_their_window() {
+        let event = RaidEvent {
+            id: "1".into(),
+            name: "Train".into(),
+            enabled: true,
+            channels: vec!["a".into()],
+            starts_at: 100,
+            ends_at: 200,
+            follow: RaidEventFollow::Off,
+        };
+        assert!(!is_open(&event, 99));
+        assert!(is_open(&event, 100));
+        assert!(!is_open(&event, 200));
+        assert!(!is_open(
+            &RaidEvent {
+                enabled: false,
+                ..event
+            },
+            150
+        ));
+    }
+}
@@ -0,0 +1,214 @@
+//! Local re-ranking of Twitch's recommended streams. Three signals, each 0..1:
+//!
+//! - followed-category affinity: share of currently live followed channels
+//!   streaming the stream's category,
+//! - watch-history affinity: share of local watch time spent in it
+//!   (`watch_history`),
+//! - channel size: how close its viewer count is (on a log scale) to the size
+//!   of channel the user usually watches.
+//!
+//! `recommendation_strategy` picks `twitch` (Twitch's order untouched),
+//! `personalized` (local score only) or `hybrid` (local score blended with
+//! Twitch's rank, the default).
+
+use crate::models::settings::AppState;
+use crate::models::stream::TwitchStream;
+use crate::services::twitch_service::TwitchService;
+use crate::services::watch_history;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+const FOLLOWED_WEIGHT: f64 = 0.4;
+const HISTORY_WEIGHT: f64 = 0.4;
+const SIZE_WEIGHT: f64 = 0.2;
+/// Share of the hybrid score that comes from Twitch's own order.
+const HYBRID_TWITCH_SHARE: f64 = 0.5;
+/// Followed live channels change slowly; refetch their categories this often.
+const FOLLOWED_TTL: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    Twitch,
+    Personalized,
+    Hybrid,
+}
+
+impl Strategy {
+    /// Unknown values fall back to hybrid.
+    pub fn from_setting(value: &str) -> Self {
+        match value {
+            "twitch" => Self::Twitch,
+            "personalized" => Self::Personalized,
+            _ => Self::Hybrid,
+        }
+    }
+}
+
+/// The inputs to `score`, gathered once per ranking.
+#[derive(Debug, Default, Clone)]
+pub struct Signals {
+    /// Lowercase category -> share of followed live channels in it.
+    pub followed_games: HashMap<String, f64>,
+    /// Lowercase category -> share of watch time.
+    pub history_games: HashMap<String, f64>,
+    pub preferred_size: Option<u32>,
+}
+
+static FOLLOWED_GAMES: Lazy<StdMutex<Option<(Instant, HashMap<String, f64>)>>> =
+    Lazy::new(|| StdMutex::new(None));
+
+impl Signals {
+    pub async fn gather(state: &AppState) -> Self {
+        Self {
+            followed_games: followed_games(state).await,
+            history_games: watch_history::game_affinity(),
+            preferred_size: watch_history::preferred_channel_size(),
+        }
+    }
+}
+
+async fn followed_games(state: &AppState) -> HashMap<String, f64> {
+    if let Some((at, games)) = FOLLOWED_GAMES.lock().unwrap().as_ref() {
+        if at.elapsed() < FOLLOWED_TTL {
+            return games.clone();
+        }
+    }
+    let Ok(followed) = TwitchService::get_followed_streams(state).await else {
+        return HashMap::new();
+    };
+    let games = category_shares(followed.iter().map(|s| s.game_name.as_str()));
+    *FOLLOWED_GAMES.lock().unwrap() = Some((Instant::now(), games.clone()));
+    games
+}
+
+fn category_shares<'a>(games: impl Iterator<Item = &'a str>) -> HashMap<String, f64> {
+    let mut counts: HashMap<String, f64> = HashMap::new();
+    let mut total = 0.0;
+    for game in games.filter(|g| !g.is_empty()) {
+        *counts.entry(game.to_lowercase()).or_default() += 1.0;
+        total += 1.0;
+    }
+    counts.values_mut().for_each(|c| *c /= total);
+    counts
+}
+
+/// Share relative to the top category, so the user's favorite scores 1.
+fn relative(shares: &HashMap<String, f64>, game: &str) -> f64 {
+    let max = shares.values().copied().fold(0.0, f64::max);
+    if max <= 0.0 {
+        return 0.0;
+    }
+    shares.get(&game.to_lowercase()).copied().unwrap_or(0.0) / max
+}
+
+/// Local score for one stream, 0..1.
+pub fn score(stream: &TwitchStream, signals: &Signals) -> f64 {
+    let followed = relative(&signals.followed_games, &stream.game_name);
+    let history = relative(&signals.history_games, &stream.game_name);
+    // 1 at the preferred size, 0 at a 100x difference either way.
+    let size = signals.preferred_size.map_or(0.0, |preferred| {
+        let distance =
+            ((stream.viewer_count as f64 + 1.0).ln() - (preferred as f64 + 1.0).ln()).abs();
+        (1.0 - distance / 100f64.ln()).max(0.0)
+    });
+    FOLLOWED_WEIGHT * followed + HISTORY_WEIGHT * history + SIZE_WEIGHT * size
+}
+
+/// Reorder `streams` (in Twitch's order) by `strategy`.
+pub fn rank(
+    streams: Vec<TwitchStream>,
+    strategy: Strategy,
+    signals: &Signals,
+) -> Vec<TwitchStream> {
+    if strategy == Strategy::Twitch || streams.len() < 2 {
+        return streams;
+    }
+    let n = streams.len() as f64;
+    let mut scored: Vec<(f64, TwitchStream)> = streams
+        .into_iter()
+        .enumerate()
+        .map(|(i, stream)| {
+            let local = score(&stream, signals);
+            let combined = match strategy {
+                Strategy::Hybrid => {
+                    let twitch = 1.0 - i as f64 / n;
+                    HYBRID_TWITCH_SHARE * twitch + (1.0 - HYBRID_TWITCH_SHARE) * local
+                }
+                _ => local,
+            };
+            (combined, stream)
+        })
+        .collect();
+    // Stable, so ties keep Twitch's order.
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().map(|(_, s)| s).collect()
+}
+
+/// Rank with the user's `recommendation_strategy`, gathering signals only
+/// when they're needed.
+pub async fn rank_for(state: &AppState, streams: Vec<TwitchStream>) -> Vec<TwitchStream> {
+    let strategy = Strategy::from_setting(&state.settings.lock().unwrap().recommendation_strategy);
+    if strategy == Strategy::Twitch {
+        return streams;
+    }
+    let signals = Signals::gather(state).await;
+    rank(streams, strategy, &signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(login: &str, game: &str, viewers: u32) -> TwitchStream {
+        serde_json::from_value(serde_json::json!({
+            "id": login, "user_id": login, "user_name": login, "user_login": login,
+            "title": "", "viewer_count": viewers, "game_name": game,
+            "thumbnail_url": "", "started_at": "",
+        }))
+        .unwrap()
+    }
+
+    fn logins(streams: &[TwitchStream]) -> Vec<&str> {
+        streams.iter().map(|s| s.user_login.as_str()).collect()
+    }
+
+    #[test]
+    fn personalized_puts_familiar_categories_first() {
+        let signals = Signals {
+            followed_games: category_shares(["Celeste", "Celeste", "Hades"].into_iter()),
+            history_games: HashMap::from([("hades".to_string(), 1.0)]),
+            preferred_size: None,
+        };
+        let streams = vec![
+            stream("a", "Just Chatting", 90_000),
+            stream("b", "Hades", 500),
+            stream("c", "Celeste", 800),
+        ];
+        let ranked = rank(streams.clone(), Strategy::Personalized, &signals);
+        assert_eq!(logins(&ranked), ["b", "c", "a"]);
+        assert_eq!(
+            logins(&rank(streams, Strategy::Twitch, &signals)),
+            ["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn size_preference_favors_similar_channels() {
+        let signals = Signals {
+            preferred_size: Some(200),
+            ..Default::default()
+        };
+        let big = score(&stream("big", "", 80_000), &signals);
+        let close = score(&stream("close", "", 250), &signals);
+        assert!(close > big);
+    }
+
+    #[test]
+    fn no_signals_keeps_twitch_order() {
+        let streams = vec![stream("a", "X", 10), stream("b", "Y", 20)];
+        let ranked = rank(streams, Strategy::Hybrid, &Signals::default());
+        assert_eq!(logins(&ranked), ["a", "b"]);
+    }
+}
@@ -0,0 +1,246 @@
+//! Limits on recordings: how many may run at once and how little free disk
+//! space they may leave.
+//!
+//! Recording is a plugin's job (`recording.*`, docs/plugins/HOOKS.md); the
+//! plugin pushes what it's recording to the `recording.status` slot. Core
+//! starts recordings through `start`, which refuses one past
+//! `settings.recording.max_concurrent` or on a drive already under
+//! `min_free_disk_mb`. While anything records, a watchdog checks each
+//! recording's drive every `CHECK_INTERVAL` and stops the recordings there
+//! once it runs low; recordings the plugin started by itself past the limit
+//! are stopped too, newest first. Every stop is sent to the plugin as
+//! `recording.stop` {channel_login, reason} and to the frontend as
+//! `recording-guard-stopped` {channel_login, path, reason, free_mb}.
+
+use crate::models::settings::{AppState, RecordingSettings};
+use crate::services::event_bus;
+use anyhow::{anyhow, bail, Result};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+pub const STATUS_SLOT: &str = "recording.status";
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+static WATCHDOG_RUNNING: AtomicBool = AtomicBool::new(false);
+/// Channels already sent `recording.stop`, until the plugin drops them from
+/// its status; keeps one stop from going out every check.
+static STOPPING: Lazy<StdMutex<HashSet<String>>> = Lazy::new(|| StdMutex::new(HashSet::new()));
+
+/// One recording the plugin reports running.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveRecording {
+    pub channel_login: String,
+    /// The file being written; empty until the plugin knows it.
+    #[serde(default)]
+    pub path: String,
+}
+
+/// Recordings in the plugin's last `recording.status` push, oldest first.
+pub fn active(state: &AppState) -> Vec<ActiveRecording> {
+    state
+        .plugin_host
+        .status(STATUS_SLOT)
+        .and_then(|value| value.get("recordings").cloned())
+        .and_then(|recordings| serde_json::from_value(recordings).ok())
+        .unwrap_or_default()
+}
+
+/// The folder recordings go to: the custom one when set, else Recordings
+/// under the app data dir.
+pub fn recordings_dir(settings: &RecordingSettings) -> Result<PathBuf> {
+    if !settings.folder.trim().is_empty() {
+        return Ok(PathBuf::from(settings.folder.trim()));
+    }
+    Ok(crate::services::cache_service::get_app_data_dir()?.join("Recordings"))
+}
+
+/// Bytes free for the user on the drive holding `dir`.
+#[cfg(windows)]
+async fn free_bytes(dir: &Path) -> Result<u64> {
+    use windows::core::HSTRING;
+    use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let mut available = 0u64;
+    unsafe {
+        GetDiskFreeSpaceExW(
+            &HSTRING::from(dir.as_os_str()),
+            Some(&mut available),
+            None,
+            None,
+        )?;
+    }
+    Ok(available)
+}
+
+/// Bytes free for the user on the drive holding `dir`.
+#[cfg(not(windows))]
+async fn free_bytes(dir: &Path) -> Result<u64> {
+    let output = tokio::process::Command::new("df")
+        .arg("-Pk")
+        .arg(dir)
+        .output()
+        .await?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // POSIX output: a header, then "fs blocks used available capacity mount".
+    let available_kb: u64 = text
+        .lines()
+        .nth(1)
+        .and_then(|line| line.split_whitespace().nth(3))
+        .and_then(|v| v.parse().ok())
+        .ok_or_else(|| anyhow!("couldn't read free space for {}", dir.display()))?;
+    Ok(available_kb * 1024)
+}
+
+/// The folder a recording's drive is checked through.
+fn recording_dir(recording: &ActiveRecording, default_dir: &Path) -> PathBuf {
+    Path::new(&recording.path)
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| default_dir.to_path_buf())
+}
+
+fn settings(app: &AppHandle) -> RecordingSettings {
+    let state = app.state::<AppState>();
+    let settings = state.settings.lock().unwrap();
+    settings.recording.clone()
+}
+
+/// Start recording `channel_login` through the recording plugin, unless that
+/// would go past the limit or the recordings drive is low.
+pub async fn start(app: &AppHandle, channel_login: &str) -> Result<()> {
+    let settings = settings(app);
+    let state = app.state::<AppState>();
+    let active = active(&state);
+    if active.iter().any(|r| r.channel_login == channel_login) {
+        bail!("{} is already being recorded", channel_login);
+    }
+    if settings.max_concurrent > 0 && active.len() >= settings.max_concurrent as usize {
+        bail!(
+            "Already recording {} channels, the most allowed at once",
+            active.len()
+        );
+    }
+    let dir = recordings_dir(&settings)?;
+    std::fs::create_dir_all(&dir)?;
+    if settings.min_free_disk_mb > 0 {
+        let free_mb = free_bytes(&dir).await? / (1024 * 1024);
+        if free_mb < settings.min_free_disk_mb {
+            bail!(
+                "Only {} MB free where recordings go (at least {} MB needed)",
+                free_mb,
+                settings.min_free_disk_mb
+            );
+        }
+    }
+    state
+        .plugin_host
+        .invoke_action(
+            "recording.start",
+            json!({ "channel_login": channel_login, "folder": dir }),
+        )
+        .await?;
+    ensure_watchdog(app);
+    Ok(())
+}
+
+async fn stop(app: &AppHandle, recording: &ActiveRecording, reason: &str, free_mb: Option<u64>) {
+    if !STOPPING
+        .lock()
+        .unwrap()
+        .insert(recording.channel_login.clone())
+    {
+        return;
+    }
+    warn!(
+        "[RecordingGuard] Stopping {} ({})",
+        recording.channel_login, reason
+    );
+    let state = app.state::<AppState>();
+    if let Err(e) = state
+        .plugin_host
+        .invoke_action(
+            "recording.stop",
+            json!({ "channel_login": recording.channel_login, "reason": reason }),
+        )
+        .await
+    {
+        warn!(
+            "[RecordingGuard] Couldn't stop {}: {}",
+            recording.channel_login, e
+        );
+        STOPPING.lock().unwrap().remove(&recording.channel_login);
+        return;
+    }
+    let _ = event_bus::emit(
+        "recording-guard-stopped",
+        json!({
+            "channel_login": recording.channel_login,
+            "path": recording.path,
+            "reason": reason,
+            "free_mb": free_mb,
+        }),
+    );
+}
+
+/// One pass over the running recordings. False once none are left.
+async fn check(app: &AppHandle) -> bool {
+    let settings = settings(app);
+    let active = active(&app.state::<AppState>());
+    {
+        let mut stopping = STOPPING.lock().unwrap();
+        stopping.retain(|login| active.iter().any(|r| &r.channel_login == login));
+    }
+    if active.is_empty() {
+        return false;
+    }
+
+    let limit = settings.max_concurrent as usize;
+    if limit > 0 && active.len() > limit {
+        for recording in active[limit..].iter().rev() {
+            stop(app, recording, "limit", None).await;
+        }
+    }
+
+    if settings.min_free_disk_mb > 0 {
+        let default_dir = recordings_dir(&settings).unwrap_or_default();
+        for recording in &active {
+            let dir = recording_dir(recording, &default_dir);
+            let free_mb = match free_bytes(&dir).await {
+                Ok(bytes) => bytes / (1024 * 1024),
+                Err(e) => {
+                    debug!("[RecordingGuard] {}", e);
+                    continue;
+                }
+            };
+            if free_mb < settings.min_free_disk_mb {
+                stop(app, recording, "low_disk", Some(free_mb)).await;
+            }
+        }
+    }
+    true
+}
+
+/// Keep the watchdog running while anything records.
+pub fn ensure_watchdog(app: &AppHandle) {
+    if WATCHDOG_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    tokio::spawn(async move {
+        debug!("[RecordingGuard] Watching recordings");
+        while check(&app).await {
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+        WATCHDOG_RUNNING.store(false, Ordering::SeqCst);
+        debug!("[RecordingGuard] No recordings left");
+    });
+}
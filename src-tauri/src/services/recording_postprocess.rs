@@ -0,0 +1,483 @@
+//! Post-processing for finished recordings (`postprocess_recording`): leave
+//! out the ad segments, remux into an MP4 and save a thumbnail, one job at a
+//! time through ffmpeg.
+//!
+//! Recording itself is a plugin's job. A recording plugin reports a finished
+//! file with the `recording_finished` notification, which queues a job when
+//! `settings.recording.postprocess` is on; the frontend can queue any file
+//! too. Ads can only be left out of a recording kept as an HLS playlist,
+//! where the segments still carry Twitch's ad markers (the same ones
+//! `ad_detect` reads); the trimmed playlist gets one discontinuity where each
+//! ad break was.
+//!
+//! Every change to a job goes out as `recording-postprocess-progress` {job}.
+//! A failed job is retried after `RETRY_DELAY_SECS`, up to `MAX_ATTEMPTS`
+//! times, and can be retried by hand after that (`retry_postprocess_job`).
+//! The queue lives for the session.
+
+use crate::models::settings::PostProcessOptions;
+use crate::services::event_bus;
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Duration, Utc};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex as StdMutex;
+
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_DELAY_SECS: i64 = 30;
+/// Finished jobs kept for the list; the oldest are dropped past this.
+const MAX_FINISHED_JOBS: usize = 50;
+/// Minimum gap between progress events while ffmpeg is running.
+const PROGRESS_INTERVAL_MS: i64 = 1000;
+/// Files a job takes.
+pub const RECORDING_EXTENSIONS: &[&str] = &["ts", "m3u8", "mp4", "mkv", "flv"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PostProcessJob {
+    pub id: u64,
+    pub input: String,
+    pub options: PostProcessOptions,
+    pub state: JobState,
+    /// `trimming_ads`, `remuxing` or `thumbnail` while running.
+    pub stage: Option<&'static str>,
+    /// How far into the recording ffmpeg is, in seconds.
+    pub processed_secs: Option<f64>,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub retry_at: Option<DateTime<Utc>>,
+    pub ad_segments_removed: u32,
+    pub outputs: Vec<String>,
+    pub queued_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Default)]
+struct Queue {
+    jobs: Vec<PostProcessJob>,
+    next_id: u64,
+    worker_running: bool,
+}
+
+static QUEUE: Lazy<StdMutex<Queue>> = Lazy::new(|| StdMutex::new(Queue::default()));
+
+fn emit(job: &PostProcessJob) {
+    let _ = event_bus::emit(
+        "recording-postprocess-progress",
+        serde_json::json!({ "job": job }),
+    );
+}
+
+/// Apply `change` to job `id` and send the result.
+fn update(id: u64, change: impl FnOnce(&mut PostProcessJob)) {
+    let mut queue = QUEUE.lock().unwrap();
+    if let Some(job) = queue.jobs.iter_mut().find(|j| j.id == id) {
+        change(job);
+        emit(job);
+    }
+}
+
+fn is_playlist(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("m3u8"))
+}
+
+/// Queue `input` for post-processing.
+pub fn enqueue(input: &Path, options: PostProcessOptions) -> Result<PostProcessJob> {
+    let extension = input
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !RECORDING_EXTENSIONS.contains(&extension.as_str()) {
+        bail!("{} isn't a recording", input.display());
+    }
+    if !input.is_file() {
+        bail!("{} doesn't exist", input.display());
+    }
+    let job = {
+        let mut queue = QUEUE.lock().unwrap();
+        queue.next_id += 1;
+        let job = PostProcessJob {
+            id: queue.next_id,
+            input: input.display().to_string(),
+            options,
+            state: JobState::Queued,
+            stage: None,
+            processed_secs: None,
+            attempts: 0,
+            error: None,
+            retry_at: None,
+            ad_segments_removed: 0,
+            outputs: Vec::new(),
+            queued_at: Utc::now(),
+            finished_at: None,
+        };
+        queue.jobs.push(job.clone());
+        job
+    };
+    debug!("[PostProcess] Queued job {} for {}", job.id, job.input);
+    emit(&job);
+    ensure_worker();
+    Ok(job)
+}
+
+/// Every job this session, oldest first.
+pub fn jobs() -> Vec<PostProcessJob> {
+    QUEUE.lock().unwrap().jobs.clone()
+}
+
+/// Queue a failed job again with a fresh set of attempts.
+pub fn retry(id: u64) -> Result<PostProcessJob> {
+    let job = {
+        let mut queue = QUEUE.lock().unwrap();
+        let job = queue
+            .jobs
+            .iter_mut()
+            .find(|j| j.id == id)
+            .ok_or_else(|| anyhow!("No post-processing job {}", id))?;
+        if job.state != JobState::Failed {
+            bail!("Job {} hasn't failed", id);
+        }
+        job.state = JobState::Queued;
+        job.attempts = 0;
+        job.retry_at = None;
+        job.clone()
+    };
+    emit(&job);
+    ensure_worker();
+    Ok(job)
+}
+
+fn ensure_worker() {
+    let mut queue = QUEUE.lock().unwrap();
+    if queue.worker_running {
+        return;
+    }
+    queue.worker_running = true;
+    tokio::spawn(worker());
+}
+
+/// The next job due, marked running; otherwise how long until one is due.
+fn take_next() -> Result<PostProcessJob, Option<std::time::Duration>> {
+    let mut queue = QUEUE.lock().unwrap();
+    let now = Utc::now();
+    if let Some(job) = queue
+        .jobs
+        .iter_mut()
+        .find(|j| j.state == JobState::Queued && j.retry_at.is_none_or(|at| at <= now))
+    {
+        job.state = JobState::Running;
+        job.attempts += 1;
+        job.retry_at = None;
+        emit(job);
+        return Ok(job.clone());
+    }
+    let wait = queue
+        .jobs
+        .iter()
+        .filter(|j| j.state == JobState::Queued)
+        .filter_map(|j| j.retry_at)
+        .min()
+        .map(|at| (at - now).to_std().unwrap_or_default());
+    if wait.is_none() {
+        queue.worker_running = false;
+    }
+    Err(wait)
+}
+
+async fn worker() {
+    loop {
+        let job = match take_next() {
+            Ok(job) => job,
+            Err(Some(wait)) => {
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+            Err(None) => return,
+        };
+        let result = process(&job).await;
+        let mut queue = QUEUE.lock().unwrap();
+        let Some(stored) = queue.jobs.iter_mut().find(|j| j.id == job.id) else {
+            continue;
+        };
+        stored.stage = None;
+        match result {
+            Ok(outputs) => {
+                stored.state = JobState::Done;
+                stored.outputs = outputs;
+                stored.error = None;
+                stored.finished_at = Some(Utc::now());
+            }
+            Err(e) => {
+                warn!(
+                    "[PostProcess] Job {} attempt {} failed: {}",
+                    job.id, stored.attempts, e
+                );
+                stored.error = Some(e.to_string());
+                if stored.attempts < MAX_ATTEMPTS {
+                    stored.state = JobState::Queued;
+                    stored.retry_at = Some(Utc::now() + Duration::seconds(RETRY_DELAY_SECS));
+                } else {
+                    stored.state = JobState::Failed;
+                    stored.finished_at = Some(Utc::now());
+                }
+            }
+        }
+        emit(stored);
+        let finished: Vec<u64> = queue
+            .jobs
+            .iter()
+            .filter(|j| matches!(j.state, JobState::Done | JobState::Failed))
+            .map(|j| j.id)
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            let drop_ids = &finished[..finished.len() - MAX_FINISHED_JOBS];
+            queue.jobs.retain(|j| !drop_ids.contains(&j.id));
+        }
+    }
+}
+
+const SEGMENT_TAGS: &[&str] = &[
+    "#EXTINF",
+    "#EXT-X-DISCONTINUITY",
+    "#EXT-X-PROGRAM-DATE-TIME",
+    "#EXT-X-DATERANGE",
+    "#EXT-X-BYTERANGE",
+];
+
+/// `playlist` without its ad segments, and how many were left out. A
+/// segment is an ad when its own tags or URI carry an ad marker; the next
+/// segment kept after an ad break starts with a discontinuity.
+fn strip_ad_segments(playlist: &str) -> (String, u32) {
+    let mut out = String::new();
+    let mut segment: Vec<&str> = Vec::new();
+    let mut removed = 0;
+    let mut discontinuity_due = false;
+    for line in playlist.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        if line.starts_with('#') {
+            if SEGMENT_TAGS.iter().any(|tag| line.starts_with(tag)) {
+                segment.push(line);
+            } else {
+                out.push_str(line);
+                out.push('\n');
+            }
+            continue;
+        }
+        segment.push(line);
+        let block = segment.join("\n");
+        if crate::services::ad_detect::scan(&block).0 {
+            removed += 1;
+            discontinuity_due = true;
+        } else {
+            if discontinuity_due && !block.contains("#EXT-X-DISCONTINUITY") {
+                out.push_str("#EXT-X-DISCONTINUITY\n");
+            }
+            discontinuity_due = false;
+            out.push_str(&block);
+            out.push('\n');
+        }
+        segment.clear();
+    }
+    (out, removed)
+}
+
+/// Run ffmpeg with `args`, reporting how far it got on job `id`.
+async fn run_ffmpeg(id: u64, ffmpeg: &Path, args: &[&std::ffi::OsStr]) -> Result<()> {
+    use tokio::io::AsyncBufReadExt;
+
+    let mut command = tokio::process::Command::new(ffmpeg);
+    command
+        .args(["-hide_banner", "-loglevel", "error", "-nostats", "-y"])
+        .args(["-progress", "pipe:1"])
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(windows)]
+    {
+        command.creation_flags(0x0800_0000);
+    }
+    let mut child = command.spawn()?;
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("no ffmpeg stdout"))?;
+    let mut lines = tokio::io::BufReader::new(stdout).lines();
+    let mut last_report: Option<DateTime<Utc>> = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        // Microseconds, despite the name.
+        let Some(micros) = line
+            .strip_prefix("out_time_ms=")
+            .and_then(|v| v.trim().parse::<i64>().ok())
+        else {
+            continue;
+        };
+        let now = Utc::now();
+        if last_report.is_some_and(|t| (now - t).num_milliseconds() < PROGRESS_INTERVAL_MS) {
+            continue;
+        }
+        last_report = Some(now);
+        update(id, |job| {
+            job.processed_secs = Some(micros as f64 / 1_000_000.0)
+        });
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "ffmpeg exited with {}: {}",
+            output.status,
+            stderr.lines().last().unwrap_or("").trim()
+        );
+    }
+    Ok(())
+}
+
+/// Where the remux goes: beside the input as `.mp4`, or `.remuxed.mp4` when
+/// the input already is one.
+fn remux_path(input: &Path) -> PathBuf {
+    let mp4 = input.with_extension("mp4");
+    if mp4 == input {
+        input.with_extension("remuxed.mp4")
+    } else {
+        mp4
+    }
+}
+
+async fn process(job: &PostProcessJob) -> Result<Vec<String>> {
+    let ffmpeg = crate::services::frame_capture::ffmpeg_path()
+        .ok_or_else(|| anyhow!("ffmpeg was not found on PATH"))?;
+    let input = PathBuf::from(&job.input);
+    if !input.is_file() {
+        bail!("{} is gone", input.display());
+    }
+    let mut outputs = Vec::new();
+    let mut source = input.clone();
+    let mut trimmed = None;
+
+    if job.options.trim_ads && is_playlist(&input) {
+        update(job.id, |j| j.stage = Some("trimming_ads"));
+        let playlist = tokio::fs::read_to_string(&input).await?;
+        let (stripped, removed) = strip_ad_segments(&playlist);
+        update(job.id, |j| j.ad_segments_removed = removed);
+        if removed > 0 {
+            let path = input.with_extension("trimmed.m3u8");
+            tokio::fs::write(&path, stripped).await?;
+            source = path.clone();
+            trimmed = Some(path);
+        }
+    }
+
+    let mut video = source.clone();
+    if job.options.remux {
+        update(job.id, |j| {
+            j.stage = Some("remuxing");
+            j.processed_secs = None;
+        });
+        let output = remux_path(&input);
+        run_ffmpeg(
+            job.id,
+            &ffmpeg,
+            &[
+                "-i".as_ref(),
+                source.as_os_str(),
+                "-map".as_ref(),
+                "0:v?".as_ref(),
+                "-map".as_ref(),
+                "0:a?".as_ref(),
+                "-c".as_ref(),
+                "copy".as_ref(),
+                "-bsf:a".as_ref(),
+                "aac_adtstoasc".as_ref(),
+                "-movflags".as_ref(),
+                "+faststart".as_ref(),
+                output.as_os_str(),
+            ],
+        )
+        .await?;
+        if let Some(path) = trimmed.take() {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        outputs.push(output.display().to_string());
+        video = output;
+    } else if let Some(path) = &trimmed {
+        outputs.push(path.display().to_string());
+    }
+
+    if job.options.thumbnail {
+        update(job.id, |j| {
+            j.stage = Some("thumbnail");
+            j.processed_secs = None;
+        });
+        let thumbnail = video.with_extension("jpg");
+        run_ffmpeg(
+            job.id,
+            &ffmpeg,
+            &[
+                "-i".as_ref(),
+                video.as_os_str(),
+                "-vf".as_ref(),
+                "thumbnail,scale=640:-2".as_ref(),
+                "-frames:v".as_ref(),
+                "1".as_ref(),
+                thumbnail.as_os_str(),
+            ],
+        )
+        .await?;
+        outputs.push(thumbnail.display().to_string());
+    }
+
+    // A playlist's segments are left alone; only a single-file source goes.
+    if job.options.delete_source && job.options.remux && !is_playlist(&input) {
+        tokio::fs::remove_file(&input).await?;
+    }
+    debug!("[PostProcess] Job {} wrote {:?}", job.id, outputs);
+    Ok(outputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ad_segments_are_left_out_with_one_discontinuity() {
+        let playlist = "#EXTM3U\n\
+            #EXT-X-TARGETDURATION:2\n\
+            #EXTINF:2.000,live\n\
+            seg1.ts\n\
+            #EXT-X-DISCONTINUITY\n\
+            #EXT-X-DATERANGE:ID=\"stitched-ad-1\",CLASS=\"twitch-stitched-ad\"\n\
+            #EXTINF:2.000,Amazon\n\
+            ad1.ts\n\
+            #EXTINF:2.000,Amazon\n\
+            ad2.ts\n\
+            #EXTINF:2.000,live\n\
+            seg2.ts\n\
+            #EXT-X-ENDLIST\n";
+        let (stripped, removed) = strip_ad_segments(playlist);
+        assert_eq!(removed, 2);
+        assert_eq!(
+            stripped,
+            "#EXTM3U\n\
+             #EXT-X-TARGETDURATION:2\n\
+             #EXTINF:2.000,live\n\
+             seg1.ts\n\
+             #EXT-X-DISCONTINUITY\n\
+             #EXTINF:2.000,live\n\
+             seg2.ts\n\
+             #EXT-X-ENDLIST\n"
+        );
+    }
+}
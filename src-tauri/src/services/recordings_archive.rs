@@ -0,0 +1,335 @@
+//! The recordings folder as a browsable archive (`list_recordings`,
+//! `get_recording_metadata`, `delete_recording`, `play_recording`).
+//!
+//! What a file alone can't tell (whose channel, which game, how long) is kept
+//! in `recordings.json` in the folder itself, so the index moves with the
+//! recordings. Entries are keyed by path relative to the folder, without the
+//! extension, so a recording and its remux share one; they're written when
+//! the recording plugin reports a file finished and dropped once no file is
+//! left for them. Files the index doesn't know are listed all the same, with
+//! what the file system has.
+
+use crate::models::settings::RecordingSettings;
+use crate::services::recording_guard::recordings_dir;
+use crate::services::recording_postprocess::RECORDING_EXTENSIONS;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex as StdMutex;
+
+const INDEX_FILE: &str = "recordings.json";
+/// Files beside a recording that belong to it: thumbnail and chapters.
+const SIDECAR_SUFFIXES: &[&str] = &[".jpg", ".ffmetadata", ".chapters.xml"];
+/// Suffixes post-processing adds to a recording's stem.
+const DERIVED_SUFFIXES: &[&str] = &[".remuxed", ".trimmed"];
+
+/// Serializes index read-modify-writes.
+static INDEX_LOCK: StdMutex<()> = StdMutex::new(());
+
+/// What the index knows about a recording.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordingMetadata {
+    #[serde(default)]
+    pub channel_login: String,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingEntry {
+    pub path: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub modified_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub metadata: RecordingMetadata,
+    /// The thumbnail post-processing saved, if any.
+    pub thumbnail: Option<String>,
+    /// Every file beside it that belongs to it.
+    pub sidecars: Vec<String>,
+}
+
+fn is_recording(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .is_some_and(|e| RECORDING_EXTENSIONS.contains(&e.as_str()))
+}
+
+/// The index key for `path` under `dir`: relative, `/`-separated, without
+/// the extension or a post-processing suffix.
+fn index_key(dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(dir).ok()?.with_extension("");
+    let mut key = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    for suffix in DERIVED_SUFFIXES {
+        if let Some(stripped) = key.strip_suffix(suffix) {
+            key = stripped.to_string();
+        }
+    }
+    Some(key)
+}
+
+fn load_index(dir: &Path) -> HashMap<String, RecordingMetadata> {
+    std::fs::read_to_string(dir.join(INDEX_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(dir: &Path, index: &HashMap<String, RecordingMetadata>) {
+    match serde_json::to_string_pretty(index) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(dir.join(INDEX_FILE), json) {
+                warn!("[Recordings] Failed to write {}: {}", INDEX_FILE, e);
+            }
+        }
+        Err(e) => warn!("[Recordings] Failed to serialize the index: {}", e),
+    }
+}
+
+/// Recording files in `dir` and its subfolders (one level, for per-channel
+/// folders).
+fn recording_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Ok(inner) = std::fs::read_dir(&path) {
+                files.extend(
+                    inner
+                        .flatten()
+                        .map(|e| e.path())
+                        .filter(|p| is_recording(p)),
+                );
+            }
+        } else if is_recording(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+fn sidecars(path: &Path) -> Vec<PathBuf> {
+    let stem = path.with_extension("");
+    SIDECAR_SUFFIXES
+        .iter()
+        .map(|suffix| PathBuf::from(format!("{}{}", stem.display(), suffix)))
+        .filter(|p| p.is_file())
+        .collect()
+}
+
+fn entry(
+    dir: &Path,
+    path: &Path,
+    index: &HashMap<String, RecordingMetadata>,
+) -> Option<RecordingEntry> {
+    let meta = std::fs::metadata(path).ok()?;
+    let sidecars = sidecars(path);
+    Some(RecordingEntry {
+        path: path.display().to_string(),
+        file_name: path.file_name()?.to_string_lossy().to_string(),
+        size_bytes: meta.len(),
+        modified_at: meta.modified().ok().map(DateTime::<Utc>::from),
+        metadata: index_key(dir, path)
+            .and_then(|key| index.get(&key).cloned())
+            .unwrap_or_default(),
+        thumbnail: sidecars
+            .iter()
+            .find(|p| p.extension().is_some_and(|e| e == "jpg"))
+            .map(|p| p.display().to_string()),
+        sidecars: sidecars.iter().map(|p| p.display().to_string()).collect(),
+    })
+}
+
+/// `path`, if it's a recording inside the recordings folder.
+fn resolve(settings: &RecordingSettings, path: &str) -> Result<(PathBuf, PathBuf)> {
+    let dir = recordings_dir(settings)?.canonicalize()?;
+    let path = Path::new(path).canonicalize()?;
+    if !path.starts_with(&dir) || !is_recording(&path) {
+        bail!(
+            "{} isn't a recording in the recordings folder",
+            path.display()
+        );
+    }
+    Ok((dir, path))
+}
+
+/// Every recording in the folder, newest first. Index entries with no file
+/// left are dropped.
+pub fn list(settings: &RecordingSettings) -> Result<Vec<RecordingEntry>> {
+    let dir = recordings_dir(settings)?;
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let files = recording_files(&dir);
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&dir);
+    let keys: std::collections::HashSet<String> =
+        files.iter().filter_map(|f| index_key(&dir, f)).collect();
+    let before = index.len();
+    index.retain(|key, _| keys.contains(key));
+    if index.len() != before {
+        save_index(&dir, &index);
+    }
+    let mut entries: Vec<RecordingEntry> = files
+        .iter()
+        .filter_map(|f| entry(&dir, f, &index))
+        .collect();
+    entries.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+    Ok(entries)
+}
+
+/// One recording, with what the index knows about it.
+pub fn metadata(settings: &RecordingSettings, path: &str) -> Result<RecordingEntry> {
+    let (dir, path) = resolve(settings, path)?;
+    let _guard = INDEX_LOCK.lock().unwrap();
+    entry(&dir, &path, &load_index(&dir))
+        .ok_or_else(|| anyhow::anyhow!("Couldn't read {}", path.display()))
+}
+
+/// Add what's known about a finished recording to the index. The game and
+/// title fall back to the last watch session on the channel, the duration to
+/// the time since `started_at`. Recordings outside the folder aren't indexed.
+pub fn record(settings: &RecordingSettings, path: &Path, mut metadata: RecordingMetadata) {
+    let Ok(dir) = recordings_dir(settings) else {
+        return;
+    };
+    let Some(key) = index_key(&dir, path) else {
+        return;
+    };
+    if metadata.game_name.is_empty() && !metadata.channel_login.is_empty() {
+        if let Some(session) = crate::services::watch_sessions::recent(20)
+            .into_iter()
+            .find(|s| s.login.eq_ignore_ascii_case(&metadata.channel_login))
+        {
+            metadata.game_name = session.game_name;
+            if metadata.title.is_empty() {
+                metadata.title = session.title;
+            }
+        }
+    }
+    if metadata.duration_secs.is_none() {
+        metadata.duration_secs = metadata
+            .started_at
+            .map(|t| (Utc::now() - t).num_seconds().max(0) as u64);
+    }
+    let _guard = INDEX_LOCK.lock().unwrap();
+    let mut index = load_index(&dir);
+    index.insert(key, metadata);
+    save_index(&dir, &index);
+}
+
+/// Delete a recording. A playlist takes its segments with it; the thumbnail,
+/// chapters and index entry go once no other file of the recording (its
+/// remux, say) is left.
+/// The file a playlist line names, when it's a plain relative path that stays
+/// inside `dir`. Absolute paths, `..` and links out of the folder are refused,
+/// so a crafted playlist can't get other files deleted.
+fn playlist_segment(parent: &Path, dir: &Path, line: &str) -> Option<PathBuf> {
+    let relative = Path::new(line);
+    if relative.is_absolute()
+        || relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return None;
+    }
+    let segment = parent.join(relative).canonicalize().ok()?;
+    (segment.starts_with(dir) && segment.is_file()).then_some(segment)
+}
+
+pub fn delete(settings: &RecordingSettings, path: &str) -> Result<()> {
+    let (dir, path) = resolve(settings, path)?;
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("m3u8"))
+    {
+        let parent = path.parent().unwrap_or(&dir);
+        for line in std::fs::read_to_string(&path)?.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(segment) = playlist_segment(parent, &dir, line) {
+                let _ = std::fs::remove_file(segment);
+            }
+        }
+    }
+    std::fs::remove_file(&path)?;
+
+    let key = index_key(&dir, &path);
+    let siblings_left = recording_files(&dir)
+        .iter()
+        .any(|f| index_key(&dir, f) == key);
+    if !siblings_left {
+        for sidecar in sidecars(&path) {
+            let _ = std::fs::remove_file(sidecar);
+        }
+        if let Some(key) = key {
+            let _guard = INDEX_LOCK.lock().unwrap();
+            let mut index = load_index(&dir);
+            if index.remove(&key).is_some() {
+                save_index(&dir, &index);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The recording, checked to be in the folder, for the system player.
+pub fn playable(settings: &RecordingSettings, path: &str) -> Result<PathBuf> {
+    resolve(settings, path).map(|(_, path)| path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_recording_and_its_remux_share_an_index_key() {
+        let dir = Path::new("/recordings");
+        let key = |p: &str| index_key(dir, Path::new(p));
+        assert_eq!(
+            key("/recordings/chan/2026-10-15.ts").as_deref(),
+            Some("chan/2026-10-15")
+        );
+        assert_eq!(
+            key("/recordings/chan/2026-10-15.mp4"),
+            key("/recordings/chan/2026-10-15.ts")
+        );
+        assert_eq!(key("/recordings/a.remuxed.mp4").as_deref(), Some("a"));
+        assert_eq!(key("/elsewhere/a.ts"), None);
+    }
+
+    #[test]
+    fn playlist_lines_cannot_leave_the_folder() {
+        let dir = std::env::temp_dir().join(format!("sn_rec_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = dir.canonicalize().unwrap();
+        std::fs::write(dir.join("seg0.ts"), b"").unwrap();
+        assert_eq!(
+            playlist_segment(&dir, &dir, "seg0.ts"),
+            Some(dir.join("seg0.ts"))
+        );
+        assert_eq!(playlist_segment(&dir, &dir, "../seg0.ts"), None);
+        assert_eq!(playlist_segment(&dir, &dir, "/etc/hosts"), None);
+        assert_eq!(playlist_segment(&dir, &dir, "missing.ts"), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,157 @@
+//! `MessageMetadata::render_hints` for parsed chat messages.
+//!
+//! Worked out once here, right after parsing, so the chat virtualizer can
+//! treat a row (mention highlight, animation budget, moderator styling, height
+//! estimate) without walking its segments and tags on the main thread. The
+//! IRC service fills them in for Twitch, `providers::publish_chat_message`
+//! for the other platforms. Emotes count as animated only when their
+//! provider says so (`Emote::animated`); Twitch's own emotes from IRC tags
+//! never do.
+
+use crate::models::chat_layout::{ChatMessage, LengthClass, MessageSegment, RenderHints};
+
+/// Roughly how many characters of width an emote or emoji takes up.
+const EMOTE_WIDTH: usize = 3;
+/// Up to this many characters fit on one line of a typical chat column.
+const SHORT_MAX: usize = 40;
+/// Beyond this, a message takes up a good part of the visible chat.
+const MEDIUM_MAX: usize = 160;
+const MOD_BADGES: [&str; 3] = ["broadcaster", "moderator", "lead_moderator"];
+
+/// Whether `text` names `login` as a word, with or without the `@`.
+fn names(text: &str, login: &str) -> bool {
+    text.split_whitespace().any(|word| {
+        word.trim_start_matches('@')
+            .trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_')
+            .eq_ignore_ascii_case(login)
+    })
+}
+
+/// The hints for `msg`. `own_login` is the logged-in user on the message's
+/// platform, if known; without it nothing counts as a mention.
+pub fn compute(msg: &ChatMessage, own_login: Option<&str>) -> RenderHints {
+    let meta = &msg.metadata;
+    let mut animated_emotes = 0;
+    let mut width = meta
+        .system_message
+        .as_ref()
+        .map_or(0, |s| s.chars().count());
+    let mut text = String::new();
+    for segment in &msg.segments {
+        match segment {
+            MessageSegment::Text { content } => {
+                width += content.chars().count();
+                text.push_str(content);
+                text.push(' ');
+            }
+            MessageSegment::Link { content, .. } => width += content.chars().count(),
+            MessageSegment::Emote { animated, .. } => {
+                width += EMOTE_WIDTH;
+                if *animated == Some(true) {
+                    animated_emotes += 1;
+                }
+            }
+            MessageSegment::Emoji { .. } => width += EMOTE_WIDTH,
+            MessageSegment::Cheermote { .. } => {
+                width += EMOTE_WIDTH;
+                animated_emotes += 1;
+            }
+        }
+    }
+
+    let mention = own_login.filter(|l| !l.is_empty()).is_some_and(|login| {
+        !msg.username.eq_ignore_ascii_case(login)
+            && (names(&text, login)
+                || meta
+                    .reply_info
+                    .as_ref()
+                    .is_some_and(|r| r.parent_user_login.eq_ignore_ascii_case(login)))
+    });
+    let from_moderator = meta.msg_type.as_deref() == Some("announcement")
+        || msg
+            .badges
+            .iter()
+            .any(|b| MOD_BADGES.contains(&b.name.as_str()));
+    let length = match width {
+        0..=SHORT_MAX => LengthClass::Short,
+        w if w <= MEDIUM_MAX => LengthClass::Medium,
+        _ => LengthClass::Long,
+    };
+    RenderHints {
+        mention,
+        animated_emotes,
+        from_moderator,
+        length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn message(username: &str, badges: &[&str], segments: serde_json::Value) -> ChatMessage {
+        serde_json::from_value(json!({
+            "id": "1",
+            "user_id": "2",
+            "username": username,
+            "display_name": username,
+            "color": null,
+            "badges": badges
+                .iter()
+                .map(|name| json!({ "name": name, "version": "1" }))
+                .collect::<Vec<_>>(),
+            "timestamp": "0",
+            "content": "",
+            "tags": {},
+            "layout": { "height": 0.0, "width": 0.0 },
+            "segments": segments,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn mentions_match_the_login_as_a_word() {
+        let text = |content: &str| json!([{ "type": "text", "content": content }]);
+        let mention = |msg: &ChatMessage| compute(msg, Some("streamnook_user")).mention;
+        assert!(mention(&message("a", &[], text("hey @StreamNook_User!"))));
+        assert!(mention(&message("a", &[], text("streamnook_user, gg"))));
+        assert!(!mention(&message("a", &[], text("streamnook_users gg"))));
+        assert!(!mention(&message(
+            "streamnook_user",
+            &[],
+            text("@streamnook_user")
+        )));
+        assert!(!compute(&message("a", &[], text("@streamnook_user")), None).mention);
+    }
+
+    #[test]
+    fn animation_moderation_and_length() {
+        let hints = compute(
+            &message(
+                "a",
+                &["moderator", "subscriber"],
+                json!([
+                    { "type": "emote", "content": "catJAM", "emote_url": "u", "animated": true },
+                    { "type": "emote", "content": "Kappa", "emote_url": "u" },
+                    { "type": "text", "content": " hi" },
+                ]),
+            ),
+            None,
+        );
+        assert_eq!(
+            hints,
+            RenderHints {
+                mention: false,
+                animated_emotes: 1,
+                from_moderator: true,
+                length: LengthClass::Short,
+            }
+        );
+
+        let long = json!([{ "type": "text", "content": "word ".repeat(40) }]);
+        let hints = compute(&message("a", &["subscriber"], long), None);
+        assert!(!hints.from_moderator);
+        assert_eq!(hints.length, LengthClass::Long);
+    }
+}
@@ -0,0 +1,75 @@
+//! System suspend/resume detection. Nothing tells the process the machine went
+//! to sleep, so a short heartbeat compares wall-clock time against the time it
+//! asked to sleep: a jump far past the tick means the whole process was frozen
+//! (lid closed, system sleep). After that, sockets and stream URLs are almost
+//! always dead, so everything that would otherwise wait for its next poll is
+//! refreshed right away.
+
+use crate::models::settings::AppState;
+use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::event_bus;
+use crate::services::irc_service::IrcService;
+use crate::services::twitch_service::TwitchService;
+use chrono::Utc;
+use log::{debug, info, warn};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Heartbeat cadence.
+const TICK: Duration = Duration::from_secs(10);
+/// Wall-clock time past the tick that counts as a sleep. Well above any
+/// scheduler delay or small clock correction.
+const RESUME_GAP_SECS: i64 = 45;
+
+/// Start the heartbeat. Call once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last = Utc::now();
+        loop {
+            tokio::time::sleep(TICK).await;
+            let now = Utc::now();
+            let slept_secs = (now - last).num_seconds() - TICK.as_secs() as i64;
+            last = now;
+            if slept_secs >= RESUME_GAP_SECS {
+                info!("[Resume] System resumed after ~{}s asleep", slept_secs);
+                on_resume(&app, slept_secs).await;
+            }
+        }
+    });
+}
+
+async fn on_resume(app: &AppHandle, slept_secs: i64) {
+    // The frontend restarts the playing live stream off this (App.tsx).
+    let _ = event_bus::emit(
+        "system-resumed",
+        serde_json::json!({ "slept_secs": slept_secs }),
+    );
+
+    let state = app.state::<AppState>();
+
+    // Tokens first, so the reconnects below authenticate with fresh ones.
+    state.twitch_auth.invalidate().await;
+    match TwitchService::verify_token_health().await {
+        Ok(status) if !status.is_valid => warn!(
+            "[Resume] Twitch token invalid after resume: {}",
+            status.error.unwrap_or_default()
+        ),
+        Ok(_) => {}
+        Err(e) => warn!("[Resume] Token check failed: {}", e),
+    }
+    if let Err(e) = DropsAuthService::validate_token().await {
+        debug!("[Resume] Drops token check failed: {}", e);
+    }
+
+    IrcService::force_reconnect();
+    state
+        .background_service
+        .lock()
+        .await
+        .reconnect_watched()
+        .await;
+
+    // Plugins (the drops miner) re-check their channel now instead of on the
+    // next 60s tick.
+    state.plugin_host.notify_resumed(slept_secs).await;
+}
@@ -0,0 +1,137 @@
+//! External programs run on app events (`Settings::script_hooks`), for
+//! wiring StreamNook into Home Assistant, OBS scripts or other tooling.
+//!
+//! A hook gets the event as JSON on stdin ({event, fired_at, data}) and its
+//! name in `STREAMNOOK_EVENT`. Only programs inside the hooks folder in the
+//! app data dir can run: a hook names a file there, never a path, so a
+//! settings file edited by something else can't point StreamNook at an
+//! arbitrary binary. Each run is killed after its `timeout_secs`.
+
+use crate::models::settings::{AppState, ScriptHook};
+use anyhow::{anyhow, bail, Result};
+use chrono::Utc;
+use log::{debug, warn};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::AsyncWriteExt;
+
+pub const DROP_CLAIMED: &str = "drop_claimed";
+pub const WENT_LIVE: &str = "went_live";
+pub const STREAM_STARTED: &str = "stream_started";
+
+const MAX_TIMEOUT_SECS: u64 = 300;
+
+/// The hooks folder, created on first use.
+pub fn hooks_dir() -> Result<PathBuf> {
+    let dir = crate::services::cache_service::get_app_data_dir()?.join("hooks");
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// A bare file name: no separators, no `..`, nothing absolute.
+fn is_plain_file_name(program: &str) -> bool {
+    !program.is_empty() && program != "." && program != ".." && !program.contains(['/', '\\', ':'])
+}
+
+/// Resolve `program` inside the hooks folder, following symlinks only as far
+/// as they stay in it.
+fn resolve(program: &str) -> Result<PathBuf> {
+    if !is_plain_file_name(program) {
+        bail!("'{}' is not a file name in the hooks folder", program);
+    }
+    let dir = hooks_dir()?.canonicalize()?;
+    let path = dir
+        .join(program)
+        .canonicalize()
+        .map_err(|e| anyhow!("{}: {}", program, e))?;
+    if !path.starts_with(&dir) || !path.is_file() {
+        bail!("'{}' is not a file in the hooks folder", program);
+    }
+    Ok(path)
+}
+
+/// Run every enabled hook for `event` in the background.
+pub fn fire(app: &AppHandle, event: &str, data: Value) {
+    let hooks: Vec<ScriptHook> = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .map(|s| {
+            s.script_hooks
+                .iter()
+                .filter(|h| h.enabled && h.event == event)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    if hooks.is_empty() {
+        return;
+    }
+    let payload = json!({ "event": event, "fired_at": Utc::now(), "data": data }).to_string();
+    for hook in hooks {
+        let event = event.to_string();
+        let payload = payload.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = run(&hook, &event, &payload).await {
+                warn!("[ScriptHooks] {} ({}) failed: {}", hook.program, event, e);
+            }
+        });
+    }
+}
+
+async fn run(hook: &ScriptHook, event: &str, payload: &str) -> Result<()> {
+    let path = resolve(&hook.program)?;
+    let mut command = tokio::process::Command::new(&path);
+    command
+        .args(&hook.args)
+        .current_dir(hooks_dir()?)
+        .env("STREAMNOOK_EVENT", event)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    #[cfg(windows)]
+    {
+        // CREATE_NO_WINDOW: hooks run in the background, never as consoles.
+        command.creation_flags(0x0800_0000);
+    }
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores stdin may close it early; that's not a failure.
+        let _ = stdin.write_all(payload.as_bytes()).await;
+    }
+    let timeout = Duration::from_secs(hook.timeout_secs.clamp(1, MAX_TIMEOUT_SECS));
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => {
+            let status = status?;
+            if !status.success() {
+                bail!("exited with {}", status);
+            }
+            debug!("[ScriptHooks] {} ran for {}", hook.program, event);
+            Ok(())
+        }
+        Err(_) => {
+            let _ = child.kill().await;
+            bail!("timed out after {}s", timeout.as_secs())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_bare_file_names_are_accepted() {
+        assert!(is_plain_file_name("notify.sh"));
+        assert!(is_plain_file_name("obs-brb.exe"));
+        assert!(!is_plain_file_name(""));
+        assert!(!is_plain_file_name(".."));
+        assert!(!is_plain_file_name("../bin/sh"));
+        assert!(!is_plain_file_name("/usr/bin/curl"));
+        assert!(!is_plain_file_name("C:\\Windows\\cmd.exe"));
+    }
+}
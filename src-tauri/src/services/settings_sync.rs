@@ -0,0 +1,398 @@
+//! Settings sync (`Settings::settings_sync`): push and pull the portable
+//! settings profile (what `export_settings` writes) through a folder another
+//! tool keeps in sync, or a secret GitHub Gist, so two machines stay
+//! configured alike.
+//!
+//! Conflicts are found three-way against what this machine last synced
+//! (`settings_sync_state.json`): when both the local settings and the remote
+//! copy changed since then, to different things, push and pull refuse unless
+//! forced. The first sync against a remote that already has a different
+//! profile counts as a conflict too, so the user picks which side wins.
+
+use crate::models::settings::SettingsSync;
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// File name in the sync folder, and in the Gist.
+const PROFILE_FILE: &str = "streamnook-settings.json";
+const STATE_FILE: &str = "settings_sync_state.json";
+const GIST_API: &str = "https://api.github.com/gists";
+const GIST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// What's stored remotely.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncedProfile {
+    pub updated_at: DateTime<Utc>,
+    /// Name of the machine that pushed it.
+    pub machine: String,
+    pub settings: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncStatus {
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// None before anything has been pushed.
+    pub remote_updated_at: Option<DateTime<Utc>>,
+    pub remote_machine: Option<String>,
+    pub local_changed: bool,
+    pub remote_changed: bool,
+    pub conflict: bool,
+}
+
+/// What this machine last synced with which target, by content hash.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    target: String,
+    local_hash: Option<String>,
+    remote_hash: Option<String>,
+    last_synced_at: Option<DateTime<Utc>>,
+}
+
+#[async_trait]
+trait SyncProvider: Send + Sync {
+    /// Identifies where the profile lives, so switching providers (or
+    /// folders) starts from a fresh baseline.
+    fn target(&self) -> String;
+    /// The stored profile; None before the first push.
+    async fn read(&self) -> Result<Option<SyncedProfile>>;
+    /// Store `profile`. Returns the provider config to save when writing
+    /// changed it (a Gist created on the first push).
+    async fn write(&self, profile: &SyncedProfile) -> Result<Option<SettingsSync>>;
+}
+
+struct FolderProvider {
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl SyncProvider for FolderProvider {
+    fn target(&self) -> String {
+        format!("folder:{}", self.dir.display())
+    }
+
+    async fn read(&self) -> Result<Option<SyncedProfile>> {
+        match tokio::fs::read_to_string(self.dir.join(PROFILE_FILE)).await {
+            Ok(json) => Ok(Some(
+                serde_json::from_str(&json).context("The synced profile is damaged")?,
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context("Couldn't read the sync folder"),
+        }
+    }
+
+    async fn write(&self, profile: &SyncedProfile) -> Result<Option<SettingsSync>> {
+        if !self.dir.is_dir() {
+            bail!("The sync folder {} doesn't exist", self.dir.display());
+        }
+        // Sync clients pick up a finished rename, never a half-written file.
+        let tmp = self.dir.join(format!("{}.tmp", PROFILE_FILE));
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(profile)?).await?;
+        tokio::fs::rename(&tmp, self.dir.join(PROFILE_FILE)).await?;
+        Ok(None)
+    }
+}
+
+struct GistProvider {
+    token: String,
+    gist_id: Option<String>,
+}
+
+impl GistProvider {
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        crate::services::http::client()
+            .request(method, url)
+            .timeout(GIST_TIMEOUT)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "StreamNook")
+    }
+
+    fn files(profile: &SyncedProfile) -> Result<Value> {
+        Ok(json!({ PROFILE_FILE: { "content": serde_json::to_string_pretty(profile)? } }))
+    }
+}
+
+#[async_trait]
+impl SyncProvider for GistProvider {
+    fn target(&self) -> String {
+        format!("gist:{}", self.gist_id.as_deref().unwrap_or(""))
+    }
+
+    async fn read(&self) -> Result<Option<SyncedProfile>> {
+        let Some(id) = &self.gist_id else {
+            return Ok(None);
+        };
+        let response = self
+            .request(reqwest::Method::GET, &format!("{}/{}", GIST_API, id))
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            bail!("Gist {} not found (deleted, or the token can't see it)", id);
+        }
+        let gist: Value = response.error_for_status()?.json().await?;
+        let Some(file) = gist["files"].get(PROFILE_FILE) else {
+            return Ok(None);
+        };
+        // Large files come back truncated; the raw URL has all of it.
+        let content = if file["truncated"].as_bool() == Some(true) {
+            let raw_url = file["raw_url"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Gist file has no raw URL"))?;
+            self.request(reqwest::Method::GET, raw_url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?
+        } else {
+            file["content"].as_str().unwrap_or_default().to_string()
+        };
+        Ok(Some(
+            serde_json::from_str(&content).context("The synced profile is damaged")?,
+        ))
+    }
+
+    async fn write(&self, profile: &SyncedProfile) -> Result<Option<SettingsSync>> {
+        let files = Self::files(profile)?;
+        match &self.gist_id {
+            Some(id) => {
+                self.request(reqwest::Method::PATCH, &format!("{}/{}", GIST_API, id))
+                    .json(&json!({ "files": files }))
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                Ok(None)
+            }
+            None => {
+                let created: Value = self
+                    .request(reqwest::Method::POST, GIST_API)
+                    .json(&json!({
+                        "description": "StreamNook settings",
+                        "public": false,
+                        "files": files,
+                    }))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                let id = created["id"]
+                    .as_str()
+                    .ok_or_else(|| anyhow!("GitHub didn't return a Gist id"))?;
+                info!("[SettingsSync] Created Gist {}", id);
+                Ok(Some(SettingsSync::Gist {
+                    token: self.token.clone(),
+                    gist_id: Some(id.to_string()),
+                }))
+            }
+        }
+    }
+}
+
+fn provider(config: &SettingsSync) -> Result<Box<dyn SyncProvider>> {
+    match config {
+        SettingsSync::Off => bail!("Settings sync is off"),
+        SettingsSync::Folder { path } if path.trim().is_empty() => {
+            bail!("Choose a sync folder first")
+        }
+        SettingsSync::Folder { path } => Ok(Box::new(FolderProvider {
+            dir: PathBuf::from(path.trim()),
+        })),
+        SettingsSync::Gist { token, .. } if token.trim().is_empty() => {
+            bail!("Add a GitHub token with the gist scope first")
+        }
+        SettingsSync::Gist { token, gist_id } => Ok(Box::new(GistProvider {
+            token: token.trim().to_string(),
+            gist_id: gist_id.clone().filter(|id| !id.trim().is_empty()),
+        })),
+    }
+}
+
+/// Content hash of a settings profile. serde_json keeps object keys sorted,
+/// so equal settings always serialize alike.
+fn hash(settings: &Value) -> String {
+    format!("{:x}", Sha256::digest(settings.to_string().as_bytes()))
+}
+
+fn machine_name() -> String {
+    std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+fn state_path() -> Result<PathBuf> {
+    Ok(crate::services::cache_service::get_app_data_dir()?.join(STATE_FILE))
+}
+
+/// The saved state for `target`; a fresh one if it was for another target.
+fn load_state(target: &str) -> SyncState {
+    state_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|json| serde_json::from_str::<SyncState>(&json).ok())
+        .filter(|state| state.target == target)
+        .unwrap_or_else(|| SyncState {
+            target: target.to_string(),
+            ..Default::default()
+        })
+}
+
+fn save_state(state: &SyncState) -> Result<()> {
+    std::fs::write(state_path()?, serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+/// `(local_changed, remote_changed, conflict)` from the hashes at the last
+/// sync and now. No remote yet is never a conflict.
+fn compare(state: &SyncState, local: &str, remote: Option<&str>) -> (bool, bool, bool) {
+    let local_changed = state.local_hash.as_deref() != Some(local);
+    let Some(remote) = remote else {
+        return (local_changed, false, false);
+    };
+    let remote_changed = state.remote_hash.as_deref() != Some(remote);
+    (
+        local_changed,
+        remote_changed,
+        local_changed && remote_changed && local != remote,
+    )
+}
+
+/// Compare `local` (the portable settings) with the synced copy.
+pub async fn status(config: &SettingsSync, local: &Value) -> Result<SyncStatus> {
+    let provider = provider(config)?;
+    let state = load_state(&provider.target());
+    let remote = provider.read().await?;
+    let (local_changed, remote_changed, conflict) = compare(
+        &state,
+        &hash(local),
+        remote.as_ref().map(|r| hash(&r.settings)).as_deref(),
+    );
+    Ok(SyncStatus {
+        last_synced_at: state.last_synced_at,
+        remote_updated_at: remote.as_ref().map(|r| r.updated_at),
+        remote_machine: remote.map(|r| r.machine),
+        local_changed,
+        remote_changed,
+        conflict,
+    })
+}
+
+/// Upload `local` (the portable settings). Returns the provider config to
+/// save when it changed.
+pub async fn push(
+    config: &SettingsSync,
+    local: &Value,
+    force: bool,
+) -> Result<Option<SettingsSync>> {
+    let provider = provider(config)?;
+    let remote = provider.read().await?;
+    let local_hash = hash(local);
+    let mut state = load_state(&provider.target());
+    let (_, _, conflict) = compare(
+        &state,
+        &local_hash,
+        remote.as_ref().map(|r| hash(&r.settings)).as_deref(),
+    );
+    if conflict && !force {
+        bail!(
+            "The synced settings changed on {} since this machine last synced; pull them first, or push anyway to overwrite",
+            remote.map(|r| r.machine).unwrap_or_default()
+        );
+    }
+
+    let updated = provider
+        .write(&SyncedProfile {
+            updated_at: Utc::now(),
+            machine: machine_name(),
+            settings: local.clone(),
+        })
+        .await?;
+    let target = match &updated {
+        Some(config) => self::provider(config)?.target(),
+        None => provider.target(),
+    };
+    state.target = target;
+    state.local_hash = Some(local_hash.clone());
+    state.remote_hash = Some(local_hash);
+    state.last_synced_at = Some(Utc::now());
+    save_state(&state)?;
+    Ok(updated)
+}
+
+/// Download the synced settings and hand them to `apply`, which merges them
+/// into the local settings and returns the resulting portable settings.
+pub async fn pull<F>(config: &SettingsSync, local: &Value, force: bool, apply: F) -> Result<()>
+where
+    F: FnOnce(&Value) -> Result<Value>,
+{
+    let provider = provider(config)?;
+    let remote = provider
+        .read()
+        .await?
+        .ok_or_else(|| anyhow!("Nothing has been pushed yet"))?;
+    let remote_hash = hash(&remote.settings);
+    let mut state = load_state(&provider.target());
+    let (_, _, conflict) = compare(&state, &hash(local), Some(&remote_hash));
+    if conflict && !force {
+        bail!("These settings changed here since the last sync; push them, or pull anyway to overwrite them");
+    }
+
+    let applied = apply(&remote.settings)?;
+    state.local_hash = Some(hash(&applied));
+    state.remote_hash = Some(remote_hash);
+    state.last_synced_at = Some(Utc::now());
+    save_state(&state)?;
+    info!(
+        "[SettingsSync] Pulled settings pushed by {} at {}",
+        remote.machine, remote.updated_at
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synced(local: &str, remote: &str) -> SyncState {
+        SyncState {
+            target: "folder:x".into(),
+            local_hash: Some(local.into()),
+            remote_hash: Some(remote.into()),
+            last_synced_at: None,
+        }
+    }
+
+    #[test]
+    fn conflicts_need_changes_on_both_sides() {
+        let state = synced("a", "a");
+        assert_eq!(compare(&state, "a", Some("a")), (false, false, false));
+        assert_eq!(compare(&state, "b", Some("a")), (true, false, false));
+        assert_eq!(compare(&state, "a", Some("c")), (false, true, false));
+        assert_eq!(compare(&state, "b", Some("c")), (true, true, true));
+        // Both sides made the same change.
+        assert_eq!(compare(&state, "b", Some("b")), (true, true, false));
+    }
+
+    #[test]
+    fn first_sync_against_a_different_remote_is_a_conflict() {
+        let state = SyncState::default();
+        assert_eq!(compare(&state, "a", None), (true, false, false));
+        assert!(compare(&state, "a", Some("b")).2);
+        assert!(!compare(&state, "a", Some("a")).2);
+    }
+
+    #[test]
+    fn hash_ignores_key_order() {
+        assert_eq!(
+            hash(&json!({ "a": 1, "b": [1, 2] })),
+            hash(&serde_json::from_str(r#"{"b":[1,2],"a":1}"#).unwrap())
+        );
+    }
+}
@@ -4,7 +4,7 @@
 // channel_points_websocket_service pattern. It subscribes to a channel's 7TV
 // resources when the channel is JOINed (refcount 0 to 1 in irc_service) and
 // unsubscribes when the last consumer leaves (1 to 0). Updates are pushed to
-// every WebView window via app_handle.emit, the same idiom eventsub_service
+// every WebView window through the event bus, the same idiom eventsub_service
 // uses, since these events are infrequent.
 //
 // Phase B (shipped here): emote_set.update -> live emote add/remove/rename in
@@ -18,12 +18,12 @@ use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::OnceLock;
-use tauri::{AppHandle, Emitter};
 use tokio::sync::{mpsc, RwLock};
 use tokio::time::{sleep, timeout, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::services::emote_service::{self, EmoteService};
+use crate::services::event_bus;
 use crate::services::irc_service::IrcService;
 
 const EVENTAPI_URL: &str = "wss://events.7tv.io/v3";
@@ -66,7 +66,7 @@ struct Service {
 static SERVICE: OnceLock<Service> = OnceLock::new();
 
 /// Initialize the singleton and spawn its connection task. Idempotent.
-pub fn init(app_handle: AppHandle, emote_service: Arc<RwLock<EmoteService>>) {
+pub fn init(emote_service: Arc<RwLock<EmoteService>>) {
     if SERVICE.get().is_some() {
         return;
     }
@@ -86,13 +86,7 @@ pub fn init(app_handle: AppHandle, emote_service: Arc<RwLock<EmoteService>>) {
     // Use tauri::async_runtime::spawn (not tokio::spawn): init() runs from the
     // Tauri setup hook, which is OUTSIDE the Tokio runtime context, so a bare
     // tokio::spawn panics with "there is no reactor running".
-    tauri::async_runtime::spawn(connection_loop(
-        app_handle,
-        emote_service,
-        http,
-        subs,
-        cmd_rx,
-    ));
+    tauri::async_runtime::spawn(connection_loop(emote_service, http, subs, cmd_rx));
 
     let _ = SERVICE.set(service);
 }
@@ -260,16 +254,13 @@ async fn bootstrap_presence(http: &reqwest::Client, sub: &ChannelSub, session_id
 }
 
 async fn connection_loop(
-    app_handle: AppHandle,
     emote_service: Arc<RwLock<EmoteService>>,
     http: reqwest::Client,
     subs: Arc<RwLock<HashMap<String, ChannelSub>>>,
     mut cmd_rx: mpsc::UnboundedReceiver<Cmd>,
 ) {
     loop {
-        if let Err(e) =
-            connect_and_run(&app_handle, &emote_service, &http, &subs, &mut cmd_rx).await
-        {
+        if let Err(e) = connect_and_run(&emote_service, &http, &subs, &mut cmd_rx).await {
             error!("[7TV EventAPI] connection ended: {}", e);
         }
         sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
@@ -278,7 +269,6 @@ async fn connection_loop(
 }
 
 async fn connect_and_run(
-    app_handle: &AppHandle,
     emote_service: &Arc<RwLock<EmoteService>>,
     http: &reqwest::Client,
     subs: &Arc<RwLock<HashMap<String, ChannelSub>>>,
@@ -339,7 +329,7 @@ async fn connect_and_run(
                     }
                     Ok(None) => return Ok(()),
                     Ok(Some(Ok(Message::Text(txt)))) => {
-                        handle_text(&txt, app_handle, emote_service, subs).await;
+                        handle_text(&txt, emote_service, subs).await;
                     }
                     Ok(Some(Ok(Message::Close(_)))) => return Ok(()),
                     Ok(Some(Err(e))) => return Err(e.into()),
@@ -368,7 +358,6 @@ async fn connect_and_run(
 
 async fn handle_text(
     txt: &str,
-    app_handle: &AppHandle,
     emote_service: &Arc<RwLock<EmoteService>>,
     subs: &Arc<RwLock<HashMap<String, ChannelSub>>>,
 ) {
@@ -386,10 +375,10 @@ async fn handle_text(
             let dispatch_type = d.get("type").and_then(|v| v.as_str()).unwrap_or("");
             if dispatch_type == "emote_set.update" {
                 if let Some(body) = d.get("body") {
-                    handle_emote_set_update(body, app_handle, emote_service, subs).await;
+                    handle_emote_set_update(body, emote_service, subs).await;
                 }
             } else if dispatch_type.starts_with("entitlement.") {
-                handle_entitlement(d, dispatch_type, app_handle);
+                handle_entitlement(d, dispatch_type);
             }
         }
         OP_RECONNECT | OP_END_OF_STREAM => {
@@ -402,7 +391,6 @@ async fn handle_text(
 
 async fn handle_emote_set_update(
     body: &Value,
-    app_handle: &AppHandle,
     emote_service: &Arc<RwLock<EmoteService>>,
     subs: &Arc<RwLock<HashMap<String, ChannelSub>>>,
 ) {
@@ -496,7 +484,7 @@ async fn handle_emote_set_update(
         .map(|(old, new)| json!({ "old": old, "new": new }))
         .collect();
 
-    let _ = app_handle.emit(
+    let _ = event_bus::emit(
         "7tv://emote-set-update",
         json!({
             "channel": channel_name,
@@ -528,7 +516,7 @@ async fn handle_emote_set_update(
 // BADGE / PAINT: cosmetics. We extract the Twitch id and let the frontend
 // re-resolve the authoritative render shape via the existing v4 GQL path (cheap,
 // cached, coalesced). The WS is the trigger; GQL is the resolver.
-fn handle_entitlement(d: &Value, dispatch_type: &str, app_handle: &AppHandle) {
+fn handle_entitlement(d: &Value, dispatch_type: &str) {
     let Some(body) = d.get("body") else {
         return;
     };
@@ -586,7 +574,7 @@ fn handle_entitlement(d: &Value, dispatch_type: &str, app_handle: &AppHandle) {
         return; // delete events without a user object, or non-twitch users
     };
 
-    let _ = app_handle.emit(
+    let _ = event_bus::emit(
         "7tv://cosmetic-update",
         json!({ "twitch_id": twitch_id, "action": action }),
     );
@@ -0,0 +1,422 @@
+//! Folds floods of one message ("PogChamp" x37 in a hype moment) into a
+//! single counter row, when `Settings::chat_spam_collapse` is on.
+//!
+//! Copies count as the same when their words match ignoring case and spacing
+//! (and the invisible character clients append to dodge Twitch's duplicate
+//! check), or, for emote walls, when they use the same emotes in the same
+//! order however often each is repeated. The first `threshold` copies show as
+//! usual; later ones are held back from the frontend and counted. A flood
+//! ends once no copy has arrived for `window_secs`.
+//!
+//! Held-back copies are reported as `{"type":"SPAM_COLLAPSE", id, channel,
+//! count, content, segments}` on the chat socket: right away for the first
+//! one, then at most every `UPDATE_INTERVAL` with the running total (copies
+//! shown included), also once chat goes quiet so a flood's final count
+//! arrives. The frontend keeps one row per `id` and updates it in place.
+//! Logging, history and plugins still see every copy; only the socket is
+//! spared. Messages that mention the user, or come from the broadcaster or a
+//! moderator, always show.
+
+use crate::models::chat_layout::{ChatMessage, MessageSegment};
+use crate::models::settings::{ChatSpamCollapseSettings, Settings};
+use crate::services::irc_service::IrcService;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+/// Appended by Chatterino and 7TV so the same line can be sent twice in a row.
+const DUPLICATE_BYPASS: char = '\u{E0000}';
+
+static SETTINGS: OnceLock<Arc<Mutex<Settings>>> = OnceLock::new();
+static COLLAPSER: Lazy<Mutex<Collapser>> = Lazy::new(|| Mutex::new(Collapser::default()));
+
+#[derive(Debug, Clone, Serialize)]
+struct CollapseUpdate {
+    id: String,
+    channel: String,
+    count: u32,
+    content: String,
+    segments: Vec<MessageSegment>,
+}
+
+struct Flood {
+    id: String,
+    count: u32,
+    /// `count` as of the last update sent, 0 before the first.
+    sent: u32,
+    last_seen: Instant,
+    content: String,
+    segments: Vec<MessageSegment>,
+}
+
+#[derive(Default)]
+struct Collapser {
+    /// (channel, key) to the flood of that message.
+    floods: HashMap<(String, String), Flood>,
+    last_sweep: Option<Instant>,
+    next_id: u64,
+}
+
+impl Collapser {
+    /// Count a copy of `key`. Returns whether it shows on its own, and the
+    /// updates due now.
+    fn observe(
+        &mut self,
+        channel: &str,
+        key: String,
+        message: (&str, &[MessageSegment]),
+        now: Instant,
+        threshold: u32,
+        window: Duration,
+    ) -> (bool, Vec<CollapseUpdate>) {
+        let mut updates = Vec::new();
+        let slot = (channel.to_string(), key);
+        let fresh = self
+            .floods
+            .get(&slot)
+            .map_or(true, |f| now.duration_since(f.last_seen) > window);
+        if fresh {
+            // An ended flood that wasn't swept yet still owes its last count.
+            if let Some(ended) = self.floods.remove(&slot) {
+                if ended.sent > 0 && ended.count > ended.sent {
+                    updates.push(update(channel, &ended));
+                }
+            }
+            self.next_id += 1;
+            let flood = Flood {
+                id: format!("spam-{}-{}", Utc::now().timestamp_millis(), self.next_id),
+                count: 0,
+                sent: 0,
+                last_seen: now,
+                content: message.0.to_string(),
+                segments: message.1.to_vec(),
+            };
+            self.floods.insert(slot.clone(), flood);
+        }
+        let flood = self.floods.get_mut(&slot).unwrap();
+        flood.count += 1;
+        flood.last_seen = now;
+        let show = flood.count <= threshold;
+
+        if !show && flood.sent == 0 {
+            flood.sent = flood.count;
+            updates.push(update(channel, flood));
+        }
+        updates.extend(self.tick(now, window));
+        (show, updates)
+    }
+
+    /// The running totals due at `now`, at most once per `UPDATE_INTERVAL`.
+    fn tick(&mut self, now: Instant, window: Duration) -> Vec<CollapseUpdate> {
+        if self
+            .last_sweep
+            .is_some_and(|t| now.duration_since(t) < UPDATE_INTERVAL)
+        {
+            return Vec::new();
+        }
+        self.last_sweep = Some(now);
+        self.sweep(now, window)
+    }
+
+    /// Running totals for floods that grew since their last update, and
+    /// forget floods that ended.
+    fn sweep(&mut self, now: Instant, window: Duration) -> Vec<CollapseUpdate> {
+        let mut updates = Vec::new();
+        self.floods.retain(|(channel, _), flood| {
+            if flood.sent > 0 && flood.count > flood.sent {
+                flood.sent = flood.count;
+                updates.push(update(channel, flood));
+            }
+            now.duration_since(flood.last_seen) <= window
+        });
+        updates
+    }
+}
+
+fn update(channel: &str, flood: &Flood) -> CollapseUpdate {
+    CollapseUpdate {
+        id: flood.id.clone(),
+        channel: channel.to_string(),
+        count: flood.count,
+        content: flood.content.clone(),
+        segments: flood.segments.clone(),
+    }
+}
+
+/// What makes copies of `msg` the same, and what the counter row shows. None
+/// for messages that are never folded: cheers, replies, anything but plain
+/// chat, and messages the render hints mark as a mention or as sent by the
+/// broadcaster or a moderator.
+fn flood_key(msg: &ChatMessage) -> Option<(String, String, Vec<MessageSegment>)> {
+    let meta = &msg.metadata;
+    if meta.bits_amount.is_some() || meta.reply_info.is_some() || meta.msg_type.is_some() {
+        return None;
+    }
+    if meta.render_hints.mention || meta.render_hints.from_moderator {
+        return None;
+    }
+    let mut words: Vec<String> = Vec::new();
+    let mut emotes: Vec<&MessageSegment> = Vec::new();
+    let mut wall = true;
+    for segment in &msg.segments {
+        match segment {
+            MessageSegment::Text { content } => {
+                for word in content
+                    .split_whitespace()
+                    .map(|w| w.trim_matches(DUPLICATE_BYPASS))
+                    .filter(|w| !w.is_empty())
+                {
+                    words.push(word.to_lowercase());
+                    wall = false;
+                }
+            }
+            MessageSegment::Emote { content, .. } | MessageSegment::Emoji { content, .. } => {
+                words.push(content.clone());
+                if emotes
+                    .last()
+                    .map_or(true, |e| segment_text(e) != content.as_str())
+                {
+                    emotes.push(segment);
+                }
+            }
+            MessageSegment::Link { url, .. } => {
+                words.push(url.clone());
+                wall = false;
+            }
+            MessageSegment::Cheermote { .. } => return None,
+        }
+    }
+    if words.is_empty() {
+        return None;
+    }
+    if wall {
+        let names: Vec<&str> = emotes.iter().map(|e| segment_text(e)).collect();
+        let mut segments = Vec::new();
+        for emote in emotes {
+            if !segments.is_empty() {
+                segments.push(MessageSegment::Text {
+                    content: " ".to_string(),
+                });
+            }
+            segments.push(emote.clone());
+        }
+        return Some((
+            format!("emotes:{}", names.join(" ")),
+            names.join(" "),
+            segments,
+        ));
+    }
+    let content = msg
+        .content
+        .trim_matches(DUPLICATE_BYPASS)
+        .trim()
+        .to_string();
+    Some((
+        format!("text:{}", words.join(" ")),
+        content,
+        msg.segments.clone(),
+    ))
+}
+
+fn segment_text(segment: &MessageSegment) -> &str {
+    match segment {
+        MessageSegment::Text { content }
+        | MessageSegment::Emote { content, .. }
+        | MessageSegment::Emoji { content, .. }
+        | MessageSegment::Link { content, .. }
+        | MessageSegment::Cheermote { content, .. } => content,
+    }
+}
+
+/// Hands the service the live settings handle and starts the flush that
+/// sends running totals while no copies arrive. Called whenever chat starts;
+/// only the first call wires it.
+pub fn init(settings: Arc<Mutex<Settings>>) {
+    if SETTINGS.set(settings).is_err() {
+        return;
+    }
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(UPDATE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(cfg) = config() else {
+                continue;
+            };
+            let updates = COLLAPSER.lock().unwrap().tick(Instant::now(), window(&cfg));
+            if updates.is_empty() {
+                continue;
+            }
+            if let Some(tx) = IrcService::broadcaster().await {
+                for event in encode(updates) {
+                    let _ = tx.send(event);
+                }
+            }
+        }
+    });
+}
+
+fn window(cfg: &ChatSpamCollapseSettings) -> Duration {
+    Duration::from_secs(u64::from(cfg.window_secs.max(1)))
+}
+
+/// `updates` as `SPAM_COLLAPSE` socket events.
+fn encode(updates: Vec<CollapseUpdate>) -> Vec<String> {
+    updates
+        .into_iter()
+        .filter_map(|u| {
+            let mut event = serde_json::to_value(u).ok()?;
+            event["type"] = json!("SPAM_COLLAPSE");
+            Some(event.to_string())
+        })
+        .collect()
+}
+
+fn config() -> Option<ChatSpamCollapseSettings> {
+    let settings = SETTINGS.get()?;
+    let guard = settings.lock().ok()?;
+    Some(guard.chat_spam_collapse.clone()).filter(|c| c.enabled)
+}
+
+/// Count `msg` toward its flood. Returns whether it should still be sent to
+/// the frontend, and the `SPAM_COLLAPSE` events to send with it.
+pub fn check(msg: &ChatMessage) -> (bool, Vec<String>) {
+    let Some(cfg) = config() else {
+        return (true, Vec::new());
+    };
+    let Some((key, content, segments)) = flood_key(msg) else {
+        return (true, Vec::new());
+    };
+    let (show, updates) = COLLAPSER.lock().unwrap().observe(
+        &msg.channel,
+        key,
+        (&content, &segments),
+        Instant::now(),
+        cfg.threshold.max(1),
+        window(&cfg),
+    );
+    (show, encode(updates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(content: &str) -> MessageSegment {
+        MessageSegment::Text {
+            content: content.to_string(),
+        }
+    }
+
+    fn emote(name: &str) -> MessageSegment {
+        MessageSegment::Emote {
+            content: name.to_string(),
+            emote_id: None,
+            emote_url: format!("https://cdn/{}", name),
+            is_zero_width: None,
+            animated: None,
+        }
+    }
+
+    fn message(segments: Vec<MessageSegment>) -> ChatMessage {
+        let content = segments
+            .iter()
+            .map(segment_text)
+            .collect::<Vec<_>>()
+            .join("");
+        serde_json::from_value(json!({
+            "id": "1",
+            "user_id": "2",
+            "username": "viewer",
+            "display_name": "viewer",
+            "color": null,
+            "badges": [],
+            "timestamp": "0",
+            "content": content,
+            "channel": "streamer",
+            "tags": {},
+            "layout": { "height": 0.0, "width": 0.0, "has_reply": false, "is_first_message": false },
+            "segments": segments,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn copies_match_ignoring_case_spacing_and_repeats() {
+        let key = |segments| flood_key(&message(segments)).unwrap().0;
+        assert_eq!(
+            key(vec![text("GG  wp")]),
+            key(vec![text("gg wp \u{E0000}")])
+        );
+        assert_ne!(key(vec![text("gg wp")]), key(vec![text("gg")]));
+
+        let wall = flood_key(&message(vec![
+            emote("PogChamp"),
+            text(" "),
+            emote("PogChamp"),
+            text(" "),
+            emote("Kappa"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            wall.0,
+            key(vec![emote("PogChamp"), text(" "), emote("Kappa")])
+        );
+        assert_eq!(wall.1, "PogChamp Kappa");
+        assert_eq!(wall.2.len(), 3);
+        assert_ne!(wall.0, key(vec![text("PogChamp Kappa")]));
+
+        let mut mention = message(vec![text("gg")]);
+        mention.metadata.render_hints.mention = true;
+        assert!(flood_key(&mention).is_none());
+        let mut moderator = message(vec![text("gg")]);
+        moderator.metadata.render_hints.from_moderator = true;
+        assert!(flood_key(&moderator).is_none());
+    }
+
+    #[test]
+    fn copies_past_the_threshold_fold_into_one_row() {
+        let mut collapser = Collapser::default();
+        let window = Duration::from_secs(10);
+        let start = Instant::now();
+        let observe = |collapser: &mut Collapser, ms: u64| {
+            collapser.observe(
+                "streamer",
+                "text:gg".into(),
+                ("gg", &[]),
+                start + Duration::from_millis(ms),
+                2,
+                window,
+            )
+        };
+
+        assert!(observe(&mut collapser, 0).0);
+        assert!(observe(&mut collapser, 100).0);
+        let (show, updates) = observe(&mut collapser, 200);
+        assert!(!show);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].count, 3);
+        let id = updates[0].id.clone();
+
+        // Throttled until a second after the last sweep.
+        assert!(observe(&mut collapser, 300).1.is_empty());
+        let (show, updates) = observe(&mut collapser, 1_100);
+        assert!(!show);
+        assert_eq!(updates.len(), 1);
+        assert_eq!((updates[0].id.as_str(), updates[0].count), (id.as_str(), 5));
+
+        // Once copies stop, the next tick still sends the final count.
+        assert!(observe(&mut collapser, 1_500).1.is_empty());
+        let updates = collapser.tick(start + Duration::from_millis(2_200), window);
+        assert_eq!((updates[0].id.as_str(), updates[0].count), (id.as_str(), 6));
+
+        // A quiet spell ends the flood; the next copy starts over and shows.
+        let (show, updates) = observe(&mut collapser, 20_000);
+        assert!(show);
+        assert!(updates.is_empty());
+    }
+}
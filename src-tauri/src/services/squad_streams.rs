@@ -0,0 +1,264 @@
+//! Co-streams: the channels a streamer is live together with.
+//!
+//! Twitch retired Squad Streams; collabs now show up as Shared Chat sessions
+//! (Helix `shared_chat/session`), the first place looked. A stream without one
+//! is checked for partners named in its title (`@name`, `w/ name`, `ft. name`),
+//! kept only if that channel is live too. Results are cached for `CACHE_TTL`.
+//!
+//! When the solo player starts a live channel, `check_in_background` raises
+//! `squad-detected` {channel_login, source, partners} if it has partners.
+//! `launch` opens the channel and its partners in MultiNook through
+//! `open-multiview` {channels}.
+
+use crate::models::stream::TwitchStream;
+use crate::services::event_bus;
+use crate::services::twitch_service::TwitchService;
+use anyhow::{bail, Result};
+use log::debug;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+const CLIENT_ID: &str = env!("TWITCH_APP_CLIENT_ID");
+const CACHE_TTL: Duration = Duration::from_secs(2 * 60);
+/// Partners beyond this many are left out of the grid.
+const MAX_PARTNERS: usize = 8;
+
+/// `@name` anywhere, or a name (optionally `@`) right after `w/`, `ft.` or
+/// `feat.`.
+static MENTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:@|\b(?:w/|ft\.|feat\.|ft\s|feat\s)\s*@?)([a-z0-9_]{3,25})\b").unwrap()
+});
+/// More names after the first: `w/ a, b & @c`.
+static MENTION_LIST: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^\s*(?:,|&|\+|\band\b)\s*@?([a-z0-9_]{3,25})\b").unwrap());
+
+/// Channel login to (when looked up, result).
+static CACHE: Lazy<StdMutex<HashMap<String, (Instant, Option<Squad>)>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SquadSource {
+    SharedChat,
+    Title,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SquadPartner {
+    pub channel_id: String,
+    pub login: String,
+    pub display_name: String,
+    pub title: String,
+    pub viewer_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Squad {
+    pub channel_login: String,
+    pub source: SquadSource,
+    pub partners: Vec<SquadPartner>,
+}
+
+/// Logins a stream title names as partners, in order, lowercased.
+fn title_mentions(title: &str) -> Vec<String> {
+    let mut logins: Vec<String> = Vec::new();
+    for caps in MENTION.captures_iter(title) {
+        let mut push = |login: &str| {
+            let login = login.to_lowercase();
+            if !logins.contains(&login) {
+                logins.push(login);
+            }
+        };
+        push(&caps[1]);
+        let mut rest = &title[caps.get(0).unwrap().end()..];
+        while let Some(more) = MENTION_LIST.captures(rest) {
+            push(&more[1]);
+            rest = &rest[more.get(0).unwrap().end()..];
+        }
+    }
+    logins
+}
+
+fn partner(stream: TwitchStream) -> SquadPartner {
+    SquadPartner {
+        channel_id: stream.user_id,
+        login: stream.user_login.to_lowercase(),
+        display_name: stream.user_name,
+        title: stream.title,
+        viewer_count: stream.viewer_count,
+    }
+}
+
+/// The broadcaster ids in `broadcaster_id`'s Shared Chat session, if any.
+async fn shared_chat_participants(broadcaster_id: &str) -> Result<Vec<String>> {
+    let token = TwitchService::get_token().await?;
+    let response = crate::services::http::client()
+        .get(format!(
+            "https://api.twitch.tv/helix/shared_chat/session?broadcaster_id={}",
+            broadcaster_id
+        ))
+        .header("Client-Id", CLIENT_ID)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<serde_json::Value>()
+        .await?;
+    Ok(response["data"][0]["participants"]
+        .as_array()
+        .map(|participants| {
+            participants
+                .iter()
+                .filter_map(|p| p["broadcaster_id"].as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// The live streams of `user_ids`.
+async fn live_by_ids(user_ids: &[String]) -> Result<Vec<TwitchStream>> {
+    let token = TwitchService::get_token().await?;
+    let params = user_ids
+        .iter()
+        .map(|id| format!("user_id={}", urlencoding::encode(id)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let response = crate::services::http::client()
+        .get(format!(
+            "https://api.twitch.tv/helix/streams?first=100&{}",
+            params
+        ))
+        .header("Client-Id", CLIENT_ID)
+        .header("Authorization", format!("Bearer {}", token))
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<serde_json::Value>()
+        .await?;
+    Ok(response["data"]
+        .as_array()
+        .map(|streams| {
+            streams
+                .iter()
+                .filter_map(|s| serde_json::from_value(s.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+async fn lookup(channel_login: &str) -> Result<Option<Squad>> {
+    let Some(stream) = TwitchService::check_streams_online(&[channel_login.to_string()])
+        .await?
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+
+    let participants: Vec<String> = shared_chat_participants(&stream.user_id)
+        .await
+        .unwrap_or_else(|e| {
+            debug!("[Squad] Shared chat lookup failed: {}", e);
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|id| *id != stream.user_id)
+        .take(MAX_PARTNERS)
+        .collect();
+    let (source, partners) = if !participants.is_empty() {
+        (SquadSource::SharedChat, live_by_ids(&participants).await?)
+    } else {
+        let mut mentioned = title_mentions(&stream.title);
+        mentioned.retain(|login| *login != channel_login);
+        mentioned.truncate(MAX_PARTNERS);
+        if mentioned.is_empty() {
+            return Ok(None);
+        }
+        (
+            SquadSource::Title,
+            TwitchService::check_streams_online(&mentioned).await?,
+        )
+    };
+    if partners.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Squad {
+        channel_login: channel_login.to_string(),
+        source,
+        partners: partners.into_iter().map(partner).collect(),
+    }))
+}
+
+/// Who `channel_login` is live together with, None when it's alone or
+/// offline.
+pub async fn detect(channel_login: &str) -> Result<Option<Squad>> {
+    let login = channel_login.trim().to_lowercase();
+    if let Some((at, squad)) = CACHE.lock().unwrap().get(&login) {
+        if at.elapsed() < CACHE_TTL {
+            return Ok(squad.clone());
+        }
+    }
+    let squad = lookup(&login).await?;
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(login, (Instant::now(), squad.clone()));
+    Ok(squad)
+}
+
+/// Look `channel_login` up off the playback path and raise `squad-detected`
+/// if it has partners.
+pub fn check_in_background(channel_login: String) {
+    tauri::async_runtime::spawn(async move {
+        match detect(&channel_login).await {
+            Ok(Some(squad)) => {
+                debug!(
+                    "[Squad] {} is live with {} others ({:?})",
+                    channel_login,
+                    squad.partners.len(),
+                    squad.source
+                );
+                let _ = event_bus::emit("squad-detected", &squad);
+            }
+            Ok(None) => {}
+            Err(e) => debug!("[Squad] Lookup for {} failed: {}", channel_login, e),
+        }
+    });
+}
+
+/// Open `channel_login` and its partners in MultiNook. Returns the logins.
+pub async fn launch(channel_login: &str) -> Result<Vec<String>> {
+    let Some(squad) = detect(channel_login).await? else {
+        bail!("{} isn't streaming with anyone right now", channel_login);
+    };
+    let channels: Vec<String> = std::iter::once(squad.channel_login)
+        .chain(squad.partners.into_iter().map(|p| p.login))
+        .collect();
+    let _ = event_bus::emit("open-multiview", json!({ "channels": channels }));
+    Ok(channels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partners_are_read_from_the_title() {
+        assert_eq!(
+            title_mentions("Lethal Company w/ @Alpha, bravo_2 & @charlie | !drops"),
+            ["alpha", "bravo_2", "charlie"]
+        );
+        assert_eq!(
+            title_mentions("ft. delta and echo - day 3 with @foxtrot"),
+            ["delta", "echo", "foxtrot"]
+        );
+        assert_eq!(title_mentions("W/ Golf"), ["golf"]);
+        assert!(title_mentions("chill stream with friends, 100% run").is_empty());
+        assert!(title_mentions("email me at x@y.z").is_empty());
+    }
+}
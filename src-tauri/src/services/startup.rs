@@ -0,0 +1,102 @@
+//! Deferred startup work. Badge databases and the drops campaign list used to
+//! be fetched from setup, racing the webview for the network and the runtime
+//! while the window was still blank. They now wait behind a readiness barrier
+//! the frontend releases after its first paint (`notify_first_paint`), with a
+//! timeout so a window that never reports (crashed webview, tray-only start)
+//! doesn't hold them forever.
+//!
+//! Each stage raises `startup-progress` {stage, status, completed, total,
+//! elapsed_ms} as it starts and finishes, so the UI can show what's still
+//! warming instead of guessing.
+
+use crate::models::settings::AppState;
+use crate::services::event_bus;
+use log::debug;
+use once_cell::sync::Lazy;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::watch;
+
+/// Run the deferred work anyway if the frontend hasn't painted by then.
+const PAINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+const STAGE_BADGES: &str = "badges";
+const STAGE_DROP_CAMPAIGNS: &str = "drop_campaigns";
+/// Deferred stages, not counting `first_paint` and `ready`.
+const DEFERRED_STAGES: usize = 2;
+
+static PAINTED: Lazy<watch::Sender<bool>> = Lazy::new(|| watch::channel(false).0);
+static STARTED_AT: Lazy<Instant> = Lazy::new(Instant::now);
+
+/// Release the barrier. Called once the main window has painted; later calls
+/// (a recreated main window) are no-ops.
+pub fn mark_first_paint() {
+    PAINTED.send_if_modified(|painted| !std::mem::replace(painted, true));
+}
+
+/// Wait until the first paint (or the timeout). Returns right away after that.
+pub async fn wait_ready() {
+    let mut rx = PAINTED.subscribe();
+    let _ = tokio::time::timeout(PAINT_TIMEOUT, rx.wait_for(|painted| *painted)).await;
+}
+
+fn emit_progress(stage: &str, status: &str, completed: usize) {
+    let _ = event_bus::emit(
+        "startup-progress",
+        json!({
+            "stage": stage,
+            "status": status,
+            "completed": completed,
+            "total": DEFERRED_STAGES,
+            "elapsed_ms": STARTED_AT.elapsed().as_millis() as u64,
+        }),
+    );
+}
+
+/// Spawn the deferred startup work. Call once from setup, after `AppState`
+/// is managed.
+pub fn start(app: AppHandle) {
+    Lazy::force(&STARTED_AT);
+    tauri::async_runtime::spawn(async move {
+        emit_progress("first_paint", "waiting", 0);
+        wait_ready().await;
+        let painted = *PAINTED.borrow();
+        debug!(
+            "[Startup] Barrier released after {}ms ({})",
+            STARTED_AT.elapsed().as_millis(),
+            if painted { "first paint" } else { "timeout" }
+        );
+        emit_progress("first_paint", if painted { "done" } else { "timeout" }, 0);
+
+        let completed = AtomicUsize::new(0);
+        let report = |stage: &str, status: &str| {
+            emit_progress(stage, status, completed.load(Ordering::SeqCst));
+        };
+        let finish = |stage: &str, ok: bool| {
+            completed.fetch_add(1, Ordering::SeqCst);
+            report(stage, if ok { "done" } else { "failed" });
+        };
+
+        let badges = async {
+            report(STAGE_BADGES, "started");
+            crate::commands::badge_service::initialize_badge_service().await;
+            finish(STAGE_BADGES, true);
+        };
+        let campaigns = async {
+            report(STAGE_DROP_CAMPAIGNS, "started");
+            let state = app.state::<AppState>();
+            let drops_service = state.drops_service.lock().await.clone();
+            let result = drops_service.get_all_active_campaigns_cached().await;
+            if let Err(e) = &result {
+                // Expected when logged out; the Drops center fetches on open.
+                debug!("[Startup] Drop campaign warm-up failed: {}", e);
+            }
+            finish(STAGE_DROP_CAMPAIGNS, result.is_ok());
+        };
+        tokio::join!(badges, campaigns);
+
+        emit_progress("ready", "done", DEFERRED_STAGES);
+    });
+}
@@ -0,0 +1,334 @@
+//! Pollers for channel header info (title, category, viewer count, uptime,
+//! tags), emitted as a consolidated `stream-info-update` every 30s instead of
+//! the frontend fetching each piece on its own timer.
+//!
+//! A channel is polled while it's the watched one (`subscribe`, torn down by
+//! `stop_stream`) or has a chat-only session (`subscribe_chat_only`); the two
+//! are independent, and a channel held by both is polled once. Only watched
+//! ticks feed `watch_history`.
+//!
+//! A title or category change on a polled channel, seen by a poll or by an
+//! EventSub `channel.update` (see `observe_channel_update`), is emitted as
+//! `stream-info-changed`.
+
+use crate::models::settings::AppState;
+use crate::models::stream::{StreamInfoChange, StreamInfoUpdate};
+use crate::services::event_bus;
+use crate::services::twitch_service::TwitchService;
+use chrono::{DateTime, Utc};
+use log::debug;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+
+const INTERVAL: Duration = Duration::from_secs(30);
+/// Most watch time one tick can add to the history (power saver stretches
+/// ticks to 2 minutes).
+const MAX_TICK_CREDIT_SECS: u64 = 180;
+
+struct Poller {
+    handle: JoinHandle<()>,
+    /// The channel on screen.
+    watched: bool,
+    /// Open in a chat-only session.
+    chat_only: bool,
+}
+
+static POLLERS: Lazy<StdMutex<HashMap<String, Poller>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// Title and category last seen for a polled channel.
+#[derive(Debug, Clone, PartialEq)]
+struct KnownInfo {
+    login: String,
+    title: String,
+    game_name: String,
+}
+
+static KNOWN: Lazy<StdMutex<HashMap<String, KnownInfo>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// The change from `previous` to `current`, if any. The first sighting of a
+/// channel is only the baseline.
+fn diff(
+    previous: Option<&KnownInfo>,
+    current: &KnownInfo,
+    game_id: &str,
+) -> Option<StreamInfoChange> {
+    let previous = previous.filter(|p| p.login == current.login)?;
+    let title_changed = previous.title != current.title;
+    let game_changed = previous.game_name != current.game_name;
+    (title_changed || game_changed).then(|| StreamInfoChange {
+        channel_login: current.login.clone(),
+        title_changed,
+        game_changed,
+        old_title: previous.title.clone(),
+        title: current.title.clone(),
+        old_game_name: previous.game_name.clone(),
+        game_name: current.game_name.clone(),
+        game_id: game_id.to_string(),
+    })
+}
+
+/// Record a polled channel's title and category, emitting
+/// `stream-info-changed` when they differ from the last ones seen.
+fn observe(login: &str, title: &str, game_id: &str, game_name: &str) {
+    let current = KnownInfo {
+        login: login.to_string(),
+        title: title.to_string(),
+        game_name: game_name.to_string(),
+    };
+    let change = {
+        let mut known = KNOWN.lock().unwrap();
+        let change = diff(known.get(login), &current, game_id);
+        known.insert(login.to_string(), current);
+        change
+    };
+    if let Some(change) = change {
+        debug!(
+            "[StreamInfo] {} changed: \"{}\" / {}",
+            change.channel_login, change.title, change.game_name
+        );
+        let _ = event_bus::emit("stream-info-changed", &change);
+    }
+}
+
+/// An EventSub `channel.update`. Only polled channels count; a change
+/// arrives here seconds before the next poll would see it.
+pub fn observe_channel_update(login: &str, title: &str, game_id: &str, game_name: &str) {
+    let login = login.to_lowercase();
+    if POLLERS.lock().unwrap().contains_key(&login) {
+        observe(&login, title, game_id, game_name);
+    }
+}
+
+fn normalize(channel: &str) -> String {
+    channel.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// Stop the pollers no session holds any more.
+fn prune(pollers: &mut HashMap<String, Poller>) {
+    pollers.retain(|login, poller| {
+        let keep = poller.watched || poller.chat_only;
+        if !keep {
+            debug!("[StreamInfo] Stopping poller for {}", login);
+            poller.handle.abort();
+            KNOWN.lock().unwrap().remove(login);
+        }
+        keep
+    });
+}
+
+/// Whether `login`'s poll should credit watch time.
+fn is_watched(login: &str) -> bool {
+    POLLERS
+        .lock()
+        .unwrap()
+        .get(login)
+        .is_some_and(|p| p.watched)
+}
+
+fn spawn(app: AppHandle, login: String) -> JoinHandle<()> {
+    debug!("[StreamInfo] Polling {}", login);
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(INTERVAL);
+        let mut last: Option<StreamInfoUpdate> = None;
+        let mut last_tick: Option<std::time::Instant> = None;
+        loop {
+            ticker.tick().await;
+            crate::services::power_saver::stretch_tick(INTERVAL).await;
+            // Watch time since the previous tick, capped so a suspend in
+            // between isn't counted. Chat-only ticks credit nothing.
+            let elapsed = last_tick
+                .replace(std::time::Instant::now())
+                .map(|t| t.elapsed().as_secs().min(MAX_TICK_CREDIT_SECS))
+                .unwrap_or(0);
+            let watched_secs = is_watched(&login).then_some(elapsed);
+            let Some(update) = fetch(&app, &login, watched_secs).await else {
+                continue;
+            };
+            observe(
+                &update.channel_login,
+                &update.title,
+                &update.game_id,
+                &update.game_name,
+            );
+            // Uptime moves every tick, so this only skips repeats while offline.
+            if last.as_ref() != Some(&update) {
+                let _ = event_bus::emit("stream-info-update", &update);
+                last = Some(update);
+            }
+        }
+    })
+}
+
+/// Poll `channel` as the watched channel, replacing the previous one. The
+/// first update is emitted right away unless the channel is already polled.
+pub fn subscribe(app: AppHandle, channel: &str) {
+    let login = normalize(channel);
+    let mut pollers = POLLERS.lock().unwrap();
+    for (other, poller) in pollers.iter_mut() {
+        poller.watched = *other == login;
+    }
+    prune(&mut pollers);
+    if pollers.contains_key(&login) {
+        return;
+    }
+    let handle = spawn(app, login.clone());
+    pollers.insert(
+        login,
+        Poller {
+            handle,
+            watched: true,
+            chat_only: false,
+        },
+    );
+}
+
+/// Stop polling the watched channel, unless a chat-only session holds it.
+pub fn unsubscribe() {
+    let mut pollers = POLLERS.lock().unwrap();
+    for poller in pollers.values_mut() {
+        poller.watched = false;
+    }
+    prune(&mut pollers);
+}
+
+/// Poll `channel` for a chat-only session, alongside the watched channel.
+pub fn subscribe_chat_only(app: AppHandle, channel: &str) {
+    let login = normalize(channel);
+    let mut pollers = POLLERS.lock().unwrap();
+    if let Some(poller) = pollers.get_mut(&login) {
+        poller.chat_only = true;
+        return;
+    }
+    let handle = spawn(app, login.clone());
+    pollers.insert(
+        login,
+        Poller {
+            handle,
+            watched: false,
+            chat_only: true,
+        },
+    );
+}
+
+/// End `channel`'s chat-only session; its poll stops unless it's watched.
+pub fn unsubscribe_chat_only(channel: &str) {
+    let login = normalize(channel);
+    let mut pollers = POLLERS.lock().unwrap();
+    if let Some(poller) = pollers.get_mut(&login) {
+        poller.chat_only = false;
+    }
+    prune(&mut pollers);
+}
+
+/// Channels with a chat-only session, sorted.
+pub fn chat_only_channels() -> Vec<String> {
+    let mut channels: Vec<String> = POLLERS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, p)| p.chat_only)
+        .map(|(login, _)| login.clone())
+        .collect();
+    channels.sort();
+    channels
+}
+
+/// One poll. `watched_secs` is the watch time to credit, None when the
+/// channel isn't being watched.
+async fn fetch(
+    app: &AppHandle,
+    login: &str,
+    watched_secs: Option<u64>,
+) -> Option<StreamInfoUpdate> {
+    match TwitchService::check_stream_online(login).await {
+        Ok(Some(stream)) => {
+            if let Some(watched_secs) = watched_secs {
+                crate::services::watch_history::record_watch(
+                    &stream.user_id,
+                    &stream.user_login,
+                    &stream.user_name,
+                    &stream.game_name,
+                    stream.viewer_count,
+                    watched_secs,
+                );
+                crate::services::watch_sessions::observe(&stream);
+            }
+            let uptime_secs = DateTime::parse_from_rfc3339(&stream.started_at)
+                .ok()
+                .map(|start| {
+                    (Utc::now() - start.with_timezone(&Utc))
+                        .num_seconds()
+                        .max(0)
+                });
+            Some(StreamInfoUpdate {
+                channel_login: login.to_string(),
+                is_live: true,
+                title: stream.title,
+                game_id: stream.game_id,
+                game_name: stream.game_name,
+                viewer_count: Some(stream.viewer_count),
+                started_at: Some(stream.started_at),
+                uptime_secs,
+                tags: stream.tags.unwrap_or_default(),
+            })
+        }
+        Ok(None) => {
+            let state = app.state::<AppState>();
+            let info = TwitchService::get_channel_info(login, &state).await.ok()?;
+            Some(StreamInfoUpdate {
+                channel_login: login.to_string(),
+                is_live: false,
+                title: info.title,
+                game_id: String::new(),
+                game_name: info.game_name,
+                viewer_count: None,
+                started_at: None,
+                uptime_secs: None,
+                tags: Vec::new(),
+            })
+        }
+        Err(e) => {
+            debug!("[StreamInfo] Poll for {} failed: {}", login, e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(login: &str, title: &str, game: &str) -> KnownInfo {
+        KnownInfo {
+            login: login.into(),
+            title: title.into(),
+            game_name: game.into(),
+        }
+    }
+
+    #[test]
+    fn changes_are_reported_after_the_baseline() {
+        let first = info("a", "Ranked grind", "Valorant");
+        assert_eq!(diff(None, &first, "1"), None);
+        assert_eq!(diff(Some(&first), &first, "1"), None);
+
+        let change = diff(
+            Some(&first),
+            &info("a", "Ranked grind", "Just Chatting"),
+            "2",
+        )
+        .unwrap();
+        assert!(change.game_changed && !change.title_changed);
+        assert_eq!(change.old_game_name, "Valorant");
+        assert_eq!(change.game_id, "2");
+
+        // Another channel is a new baseline, not a change.
+        assert_eq!(diff(Some(&first), &info("b", "x", "y"), ""), None);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::services::event_bus;
 use crate::services::ll_origin::{
     empty_cors, media_response, opt_raw_query, parse_directive, parse_part_path, playlist_response,
 };
@@ -8,7 +9,6 @@ use rand::Rng;
 use reqwest::Client;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tauri::{Emitter, Manager};
 use tokio::sync::Mutex;
 use warp::Filter;
 
@@ -53,7 +53,19 @@ pub fn ad_state() -> AdDetectionState {
 }
 
 fn reset_ad_state() {
-    *AD_STATE.lock().unwrap() = AdDetectionState::default();
+    let previous = std::mem::take(&mut *AD_STATE.lock().unwrap());
+    // A break still open on the old stream is over as far as the player goes.
+    if let Some(started) = previous.break_started {
+        let _ = event_bus::emit(
+            "ad-break-ended",
+            serde_json::json!({
+                "channel_login": null,
+                "source": "playlist",
+                "break_number": previous.ad_events,
+                "elapsed_secs": started.elapsed().as_secs_f64(),
+            }),
+        );
+    }
     // Tear down the LL-HLS origin (background reader + live edge) for the old stream.
     crate::services::ll_origin::stop();
     // Drop the stable-projection segment map for the old stream so a synthetic
@@ -65,15 +77,50 @@ fn reset_ad_state() {
 /// Read-only and for the core's own playback only: the relay serves the
 /// playlist untouched and only RECORDS whether ad markers are present, which
 /// gates the low-latency prefetch promotion (`ads_now`) so it never
-/// fast-forwards into an ad. The core never acts on ads beyond that and never
-/// reports them to a plugin; a resolution-owning plugin detects ads itself.
+/// fast-forwards into an ad, and announces each break to the UI as
+/// `ad-break-started` / `ad-break-ended`. The core never acts on ads beyond
+/// that and never reports them to a plugin; a resolution-owning plugin detects
+/// ads itself.
+///
+/// EventSub's `channel.ad_break.begin` would be earlier, but it needs the
+/// broadcaster's own token with `channel:read:ads`, so it could only ever
+/// cover the user's own channel.
 fn detect_ads_in_playlist(playlist: &str) {
-    let mut st = AD_STATE.lock().unwrap();
-    if let Some(n) = crate::services::ad_detect::update(&mut st, playlist) {
-        info!(
-            "[StreamServer] ad markers detected in live playlist (break #{}): {:?}",
-            n, st.matched_markers
-        );
+    let channel = solo_channel();
+    let event = {
+        let mut st = AD_STATE.lock().unwrap();
+        let started = st.break_started;
+        if let Some(n) = crate::services::ad_detect::update(&mut st, playlist) {
+            info!(
+                "[StreamServer] ad markers detected in live playlist (break #{}): {:?}",
+                n, st.matched_markers
+            );
+            Some((
+                "ad-break-started",
+                serde_json::json!({
+                    "channel_login": channel,
+                    "source": "playlist",
+                    "break_number": n,
+                    "duration_secs": crate::services::ad_detect::break_duration(playlist),
+                    "mute": crate::services::ad_detect::mute_during_ads(),
+                }),
+            ))
+        } else if started.is_some() && !st.ads_present {
+            Some((
+                "ad-break-ended",
+                serde_json::json!({
+                    "channel_login": channel,
+                    "source": "playlist",
+                    "break_number": st.ad_events,
+                    "elapsed_secs": started.map(|t| t.elapsed().as_secs_f64()),
+                }),
+            ))
+        } else {
+            None
+        }
+    };
+    if let Some((name, payload)) = event {
+        let _ = event_bus::emit(name, payload);
     }
 }
 
@@ -86,7 +133,6 @@ pub const SOLO_STREAM_ID: &str = "solo";
 /// session addressable by plugins.
 static SOLO_CHANNEL: Lazy<std::sync::Mutex<Option<String>>> =
     Lazy::new(|| std::sync::Mutex::new(None));
-static APP_HANDLE: once_cell::sync::OnceCell<tauri::AppHandle> = once_cell::sync::OnceCell::new();
 /// True when the LL-HLS origin is actively serving parts for this stream — what the
 /// player keys `lowLatencyMode` on. A real spec LL-HLS playlist (`#EXT-X-PART` +
 /// blocking reload) is being served, so hls.js's native low-latency controller has
@@ -96,12 +142,6 @@ pub fn is_low_latency() -> bool {
     crate::services::ll_origin::is_active()
 }
 
-/// Store the app handle so the relay can emit reload events and reach the
-/// plugin host for `on_ad_window` notifications.
-pub fn set_app_handle(handle: tauri::AppHandle) {
-    let _ = APP_HANDLE.set(handle);
-}
-
 /// Record (or clear) the live channel the solo relay serves. Live starts set
 /// it; VOD/clip starts and stops clear it.
 pub fn set_solo_session(channel: Option<String>) {
@@ -114,6 +154,17 @@ pub fn solo_session_active() -> bool {
     SOLO_CHANNEL.lock().unwrap().is_some()
 }
 
+/// The live channel the solo relay serves, if any.
+pub fn solo_channel() -> Option<String> {
+    SOLO_CHANNEL.lock().unwrap().clone()
+}
+
+/// The playlist URL the player loads from the relay, while the relay is up.
+pub async fn relay_playlist_url() -> Option<String> {
+    let port = (*CURRENT_PORT.lock().await)?;
+    Some(format!("http://localhost:{}/stream.m3u8", port))
+}
+
 /// Replace the solo relay's upstream playlist with one a resolution-owning
 /// plugin supplied via `set_upstream`, and tell the player to reload onto it.
 /// This is the mid-stream escalation path (e.g. the plugin re-resolved through
@@ -125,17 +176,15 @@ pub async fn swap_upstream(playlist_url: String) -> Result<()> {
         .clone()
         .ok_or_else(|| anyhow::anyhow!("no live solo relay session"))?;
     let port = StreamServer::start_proxy_server(playlist_url).await?;
-    if let Some(app) = APP_HANDLE.get() {
-        let url = format!(
-            "http://localhost:{}/stream.m3u8?t={}",
-            port,
-            chrono::Utc::now().timestamp_millis()
-        );
-        let _ = app.emit(
-            "ad-pivot",
-            serde_json::json!({ "url": url, "channel": channel }),
-        );
-    }
+    let url = format!(
+        "http://localhost:{}/stream.m3u8?t={}",
+        port,
+        chrono::Utc::now().timestamp_millis()
+    );
+    let _ = event_bus::emit(
+        "ad-pivot",
+        serde_json::json!({ "url": url, "channel": channel }),
+    );
     info!(
         "[StreamServer] {} upstream swapped by a playback plugin",
         channel
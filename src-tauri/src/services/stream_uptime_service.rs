@@ -0,0 +1,251 @@
+//! Per-channel broadcast lengths, learned from followed-streams status checks
+//! (each live stream reports its `started_at`; the last check that still sees
+//! it bounds its end). Channel pickers use this to avoid channels that have
+//! been live a long time and are likely to end mid-drop.
+//!
+//! Persisted to `stream_uptime.json` in the app data dir so typical stream
+//! lengths survive restarts.
+
+use chrono::{DateTime, Duration, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock as StdRwLock;
+
+const UPTIME_FILE: &str = "stream_uptime.json";
+/// Broadcasts remembered per channel.
+const MAX_SESSIONS: usize = 20;
+/// Live this long, a stream starts taking a score penalty...
+const PENALTY_START_HOURS: f64 = 9.0;
+/// ...which reaches its maximum here.
+const PENALTY_FULL_HOURS: f64 = 13.0;
+/// Minimum gap between disk writes while only `last_seen` is moving.
+const SAVE_INTERVAL_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StreamSession {
+    started_at: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+impl StreamSession {
+    fn hours(&self) -> f64 {
+        (self.last_seen - self.started_at).num_seconds().max(0) as f64 / 3600.0
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChannelSessions {
+    login: String,
+    sessions: Vec<StreamSession>,
+}
+
+#[derive(Default)]
+struct UptimeStore {
+    channels: HashMap<String, ChannelSessions>,
+    last_saved: Option<DateTime<Utc>>,
+}
+
+static STORE: Lazy<StdRwLock<UptimeStore>> = Lazy::new(|| {
+    StdRwLock::new(UptimeStore {
+        channels: load_from_disk(),
+        last_saved: None,
+    })
+});
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ChannelUptimeStats {
+    pub channel_id: String,
+    pub login: String,
+    pub sessions_tracked: usize,
+    /// Typical broadcast length; `None` until a finished broadcast was seen.
+    pub median_hours: Option<f64>,
+    pub longest_hours: Option<f64>,
+    /// How long the current broadcast has run, if the channel is live.
+    pub current_uptime_hours: Option<f64>,
+    /// 0..1 score penalty for the current broadcast: 0 below 9 hours live,
+    /// rising to 1 at 13 hours, and at least 0.5 once past the channel's own
+    /// median length.
+    pub uptime_penalty: f64,
+}
+
+fn uptime_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(UPTIME_FILE))
+}
+
+fn load_from_disk() -> HashMap<String, ChannelSessions> {
+    uptime_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(channels: &HashMap<String, ChannelSessions>) {
+    let Some(path) = uptime_path() else { return };
+    match serde_json::to_string(channels) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[Uptime] Failed to write {}: {}", UPTIME_FILE, e);
+            }
+        }
+        Err(e) => warn!("[Uptime] Failed to serialize uptime store: {}", e),
+    }
+}
+
+/// Record that `channel_id` is live with a broadcast that began at
+/// `started_at` (the Helix RFC 3339 string). Unparseable timestamps are ignored.
+pub fn observe(channel_id: &str, login: &str, started_at: &str) {
+    let Ok(started_at) = DateTime::parse_from_rfc3339(started_at) else {
+        return;
+    };
+    let now = Utc::now();
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+
+    let entry = store.channels.entry(channel_id.to_string()).or_default();
+    entry.login = login.to_string();
+    let started_at = started_at.with_timezone(&Utc);
+    let new_session = match entry.sessions.last_mut() {
+        Some(last) if last.started_at == started_at => {
+            last.last_seen = now;
+            false
+        }
+        _ => {
+            entry.sessions.push(StreamSession {
+                started_at,
+                last_seen: now,
+            });
+            if entry.sessions.len() > MAX_SESSIONS {
+                entry.sessions.remove(0);
+            }
+            true
+        }
+    };
+
+    let save_due = !store
+        .last_saved
+        .is_some_and(|t| (now - t).num_seconds() < SAVE_INTERVAL_SECS);
+    if new_session || save_due {
+        store.last_saved = Some(now);
+        save_to_disk(&store.channels);
+    }
+}
+
+/// Uptime stats for a channel, looked up by channel id or login.
+pub fn stats(channel: &str) -> Option<ChannelUptimeStats> {
+    let store = STORE.read().ok()?;
+    let (channel_id, entry) = store.channels.get_key_value(channel).or_else(|| {
+        store
+            .channels
+            .iter()
+            .find(|(_, c)| c.login.eq_ignore_ascii_case(channel))
+    })?;
+    Some(build_stats(channel_id, entry, Utc::now()))
+}
+
+fn build_stats(
+    channel_id: &str,
+    entry: &ChannelSessions,
+    now: DateTime<Utc>,
+) -> ChannelUptimeStats {
+    // The newest session is "current" if a status check saw it recently.
+    let current = entry
+        .sessions
+        .last()
+        .filter(|s| now - s.last_seen < Duration::minutes(15));
+    let finished: Vec<f64> = entry
+        .sessions
+        .iter()
+        .filter(|s| !current.is_some_and(|c| c.started_at == s.started_at))
+        .map(StreamSession::hours)
+        .collect();
+
+    let median_hours = median(&finished);
+    let longest_hours = finished.iter().copied().reduce(f64::max);
+    let current_uptime_hours =
+        current.map(|s| (now - s.started_at).num_seconds().max(0) as f64 / 3600.0);
+    let uptime_penalty = current_uptime_hours
+        .map(|h| uptime_penalty(h, median_hours))
+        .unwrap_or(0.0);
+
+    ChannelUptimeStats {
+        channel_id: channel_id.to_string(),
+        login: entry.login.clone(),
+        sessions_tracked: entry.sessions.len(),
+        median_hours,
+        longest_hours,
+        current_uptime_hours,
+        uptime_penalty,
+    }
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let mid = sorted.len() / 2;
+    Some(if sorted.len() % 2 == 0 {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+/// 0..1 penalty for a broadcast live `uptime_hours`, given the channel's
+/// typical length when known (see `ChannelUptimeStats::uptime_penalty`).
+pub fn uptime_penalty(uptime_hours: f64, median_hours: Option<f64>) -> f64 {
+    let long_stream = ((uptime_hours - PENALTY_START_HOURS)
+        / (PENALTY_FULL_HOURS - PENALTY_START_HOURS))
+        .clamp(0.0, 1.0);
+    let past_typical = match median_hours {
+        Some(m) if m > 0.0 && uptime_hours > m => 0.5,
+        _ => 0.0,
+    };
+    long_stream.max(past_typical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn penalty_ramps_after_nine_hours() {
+        assert_eq!(uptime_penalty(2.0, None), 0.0);
+        assert_eq!(uptime_penalty(11.0, None), 0.5);
+        assert_eq!(uptime_penalty(20.0, None), 1.0);
+    }
+
+    #[test]
+    fn outlasting_the_usual_length_is_penalized() {
+        assert_eq!(uptime_penalty(4.0, Some(3.0)), 0.5);
+        assert_eq!(uptime_penalty(2.0, Some(3.0)), 0.0);
+    }
+
+    #[test]
+    fn current_session_is_excluded_from_history() {
+        let now = Utc::now();
+        let entry = ChannelSessions {
+            login: "somechannel".into(),
+            sessions: vec![
+                StreamSession {
+                    started_at: now - Duration::hours(30),
+                    last_seen: now - Duration::hours(26),
+                },
+                StreamSession {
+                    started_at: now - Duration::hours(10),
+                    last_seen: now - Duration::minutes(1),
+                },
+            ],
+        };
+        let stats = build_stats("1", &entry, now);
+        assert_eq!(stats.median_hours, Some(4.0));
+        assert!(stats.current_uptime_hours.unwrap() >= 10.0);
+        assert!(stats.uptime_penalty >= 0.5);
+    }
+}
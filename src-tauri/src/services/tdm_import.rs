@@ -0,0 +1,246 @@
+//! Migration from TwitchDropsMiner (`import_twitch_drops_miner`).
+//!
+//! TDM keeps `settings.json` and `cookies.jar` next to its executable. The
+//! settings carry the games to mine first (`priority`), the games never to
+//! mine (`exclude`) and the campaign order (`priority_mode`), which map onto
+//! `DropsSettings` as they are. TDM writes sets and enums through its own
+//! JSON encoder as `{"__type": ..., "data": ...}`; plain values are read too.
+//!
+//! The cookie jar is a Python pickle of aiohttp's jar. Rather than run a
+//! pickle VM, the strings in it are read in order and the `auth-token`
+//! cookie's value picked out. TDM signs in as the Twitch Android app, the
+//! same client drops mining uses here, so the token carries over once Twitch
+//! confirms it's valid.
+
+use crate::models::drops::{DropsSettings, PriorityMode};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+const SETTINGS_FILE: &str = "settings.json";
+const COOKIES_FILE: &str = "cookies.jar";
+const AUTH_COOKIE: &str = "auth-token";
+
+/// What a TDM folder holds, and what importing it changes.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TdmImport {
+    /// TDM's priority games not already prioritized here, in TDM's order.
+    pub priority_games: Vec<String>,
+    /// TDM's excluded games not already excluded here.
+    pub excluded_games: Vec<String>,
+    pub priority_mode: Option<PriorityMode>,
+    /// `cookies.jar` had a sign-in token.
+    pub auth_token_found: bool,
+    /// The drops account the token signed in, once imported.
+    pub signed_in_as: Option<String>,
+    /// Anything that couldn't be read or carried over.
+    pub warnings: Vec<String>,
+}
+
+/// A value TDM's encoder may have wrapped as `{"__type", "data"}`.
+fn unwrap_typed(value: &Value) -> &Value {
+    match value.get("__type") {
+        Some(_) => value.get("data").unwrap_or(&Value::Null),
+        None => value,
+    }
+}
+
+fn game_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .map(unwrap_typed)
+        .and_then(Value::as_array)
+        .map(|games| {
+            games
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::trim)
+                .filter(|g| !g.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// TDM's `PriorityMode`: 0 priority only, 1 ending soonest, 2 low
+/// availability first; older versions stored the name.
+fn priority_mode(value: Option<&Value>) -> Option<PriorityMode> {
+    let value = unwrap_typed(value?);
+    let mode = match value.as_u64() {
+        Some(n) => n,
+        None => match value.as_str()?.to_ascii_uppercase().as_str() {
+            "PRIORITY_ONLY" => 0,
+            "ENDING_SOONEST" => 1,
+            "LOW_AVBL_FIRST" => 2,
+            _ => return None,
+        },
+    };
+    match mode {
+        0 => Some(PriorityMode::PriorityOnly),
+        1 => Some(PriorityMode::EndingSoonest),
+        2 => Some(PriorityMode::LowAvailFirst),
+        _ => None,
+    }
+}
+
+/// Unicode strings in a pickle, in the order written.
+fn pickle_strings(data: &[u8]) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut i = 0;
+    while i < data.len() {
+        // SHORT_BINUNICODE (1-byte length) and BINUNICODE (4-byte length).
+        let (header, len) = match data[i] {
+            0x8c if i + 1 < data.len() => (2, data[i + 1] as usize),
+            b'X' if i + 5 <= data.len() => (
+                5,
+                u32::from_le_bytes([data[i + 1], data[i + 2], data[i + 3], data[i + 4]]) as usize,
+            ),
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let start = i + header;
+        match data.get(start..start + len).map(std::str::from_utf8) {
+            Some(Ok(s)) if !s.is_empty() => {
+                strings.push(s.to_string());
+                i = start + len;
+            }
+            _ => i += 1,
+        }
+    }
+    strings
+}
+
+/// The `auth-token` cookie in a pickled aiohttp cookie jar.
+fn auth_token(jar: &[u8]) -> Option<String> {
+    let strings = pickle_strings(jar);
+    let at = strings.iter().position(|s| s == AUTH_COOKIE)?;
+    strings[at + 1..]
+        .iter()
+        .find(|s| s.len() == 30 && s.bytes().all(|b| b.is_ascii_alphanumeric()))
+        .cloned()
+}
+
+/// Read a TDM folder against the current settings. The token, if any, is
+/// returned separately so a preview never holds on to it.
+pub fn read(folder: &Path, current: &DropsSettings) -> Result<(TdmImport, Option<String>)> {
+    let settings_path = folder.join(SETTINGS_FILE);
+    let cookies_path = folder.join(COOKIES_FILE);
+    if !settings_path.is_file() && !cookies_path.is_file() {
+        return Err(anyhow!(
+            "No TwitchDropsMiner {} or {} in {}",
+            SETTINGS_FILE,
+            COOKIES_FILE,
+            folder.display()
+        ));
+    }
+
+    let mut import = TdmImport::default();
+    match std::fs::read_to_string(&settings_path) {
+        Ok(text) => match serde_json::from_str::<Value>(&text) {
+            Ok(settings) => {
+                let has =
+                    |list: &[String], game: &str| list.iter().any(|g| g.eq_ignore_ascii_case(game));
+                for game in game_list(settings.get("priority")) {
+                    if !has(&current.priority_games, &game) && !has(&import.priority_games, &game) {
+                        import.priority_games.push(game);
+                    }
+                }
+                let excluded: Vec<String> = current.excluded_games.iter().cloned().collect();
+                for game in game_list(settings.get("exclude")) {
+                    if !has(&excluded, &game) && !has(&import.excluded_games, &game) {
+                        import.excluded_games.push(game);
+                    }
+                }
+                import.priority_mode = priority_mode(settings.get("priority_mode"));
+            }
+            Err(e) => import
+                .warnings
+                .push(format!("Couldn't read {}: {}", SETTINGS_FILE, e)),
+        },
+        Err(_) => import
+            .warnings
+            .push(format!("No {}; games weren't imported", SETTINGS_FILE)),
+    }
+
+    let token = std::fs::read(&cookies_path)
+        .ok()
+        .and_then(|jar| auth_token(&jar));
+    import.auth_token_found = token.is_some();
+    if token.is_none() {
+        import
+            .warnings
+            .push("No sign-in in TwitchDropsMiner's cookies; sign in for drops here".to_string());
+    }
+    Ok((import, token))
+}
+
+/// `current` with the import merged in: games appended, mode replaced.
+pub fn merge(current: &DropsSettings, import: &TdmImport) -> DropsSettings {
+    let mut settings = current.clone();
+    settings
+        .priority_games
+        .extend(import.priority_games.iter().cloned());
+    settings
+        .excluded_games
+        .extend(import.excluded_games.iter().cloned());
+    if let Some(mode) = &import.priority_mode {
+        settings.priority_mode = mode.clone();
+    }
+    settings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn short_unicode(s: &str) -> Vec<u8> {
+        let mut bytes = vec![0x8c, s.len() as u8];
+        bytes.extend_from_slice(s.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn reads_tdm_settings_in_either_encoding() {
+        let settings = serde_json::json!({
+            "priority": ["Rust", "Apex Legends"],
+            "exclude": { "__type": "set", "data": ["Fortnite"] },
+            "priority_mode": { "__type": "PriorityMode", "data": 1 },
+        });
+        assert_eq!(
+            game_list(settings.get("priority")),
+            ["Rust", "Apex Legends"]
+        );
+        assert_eq!(game_list(settings.get("exclude")), ["Fortnite"]);
+        assert_eq!(
+            priority_mode(settings.get("priority_mode")),
+            Some(PriorityMode::EndingSoonest)
+        );
+        assert_eq!(
+            priority_mode(Some(&serde_json::json!("LOW_AVBL_FIRST"))),
+            Some(PriorityMode::LowAvailFirst)
+        );
+    }
+
+    #[test]
+    fn finds_the_auth_token_in_a_pickled_jar() {
+        let token = "abcdefghij0123456789klmnopqrst";
+        let mut jar = vec![0x80, 0x04, 0x95];
+        for s in [
+            "twitch.tv",
+            "/",
+            "unique_id",
+            "0123abcd",
+            AUTH_COOKIE,
+            "value",
+        ] {
+            jar.extend(short_unicode(s));
+            jar.push(0x94);
+        }
+        jar.extend(short_unicode(token));
+        jar.extend(b"\x94u.");
+        assert_eq!(auth_token(&jar).as_deref(), Some(token));
+        assert_eq!(auth_token(&short_unicode("unrelated")), None);
+    }
+}
@@ -0,0 +1,78 @@
+//! Correlation ids for user actions that run for a long time (a mining
+//! session, a stream being watched), so one of them can be followed through
+//! days of logs.
+//!
+//! `begin` mints an id like `mining-3f9a1c2e` and keeps it as the operation's
+//! active id until `end` or the next `begin`. Log lines written inside
+//! `scope` get it after the module path. Work that outlives the scope
+//! (spawned tasks, the mining plugin's own process) reads `active` instead;
+//! the mining plugin is handed its id as `trace_id` in every `drops.*` action
+//! (`drops_action_args`).
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex as StdMutex;
+
+pub const MINING: &str = "mining";
+pub const STREAM: &str = "stream";
+
+tokio::task_local! {
+    static CURRENT: String;
+}
+
+static ACTIVE: Lazy<StdMutex<HashMap<&'static str, String>>> =
+    Lazy::new(|| StdMutex::new(HashMap::new()));
+
+/// A new id for `operation`, now its active one.
+pub fn begin(operation: &'static str) -> String {
+    let id = format!(
+        "{}-{}",
+        operation,
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    ACTIVE.lock().unwrap().insert(operation, id.clone());
+    log::info!("[Trace] {} started as {}", operation, id);
+    id
+}
+
+/// `operation`'s active id, if it hasn't ended.
+pub fn active(operation: &str) -> Option<String> {
+    ACTIVE.lock().unwrap().get(operation).cloned()
+}
+
+/// `operation` is over; returns the id it ran under.
+pub fn end(operation: &str) -> Option<String> {
+    let id = ACTIVE.lock().unwrap().remove(operation);
+    if let Some(id) = &id {
+        log::info!("[Trace] {} ended", id);
+    }
+    id
+}
+
+/// The id of the scope this task is running in.
+pub fn current() -> Option<String> {
+    CURRENT.try_with(|id| id.clone()).ok()
+}
+
+/// Run `future` with `id` as the current id.
+pub async fn scope<F: Future>(id: String, future: F) -> F::Output {
+    CURRENT.scope(id, future).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn ids_are_current_inside_their_scope_and_active_until_ended() {
+        let id = begin(STREAM);
+        assert!(id.starts_with("stream-"));
+        assert_eq!(active(STREAM).as_deref(), Some(id.as_str()));
+        assert_eq!(current(), None);
+        let inside = scope(id.clone(), async { current() }).await;
+        assert_eq!(inside, Some(id.clone()));
+        assert_eq!(end(STREAM), Some(id));
+        assert_eq!(active(STREAM), None);
+    }
+}
@@ -0,0 +1,572 @@
+//! The Helix and GQL operations behind channel-points collection, behind a
+//! trait so that logic can run against canned data. `HttpTwitchApi` is the
+//! real client (stored credentials, production endpoints by default); tests
+//! use `MockTwitchApi`, or point `HttpTwitchApi` at a local server to exercise
+//! the request/response handling itself (see `twitch_api_harness`).
+//!
+//! The trait covers the whole points path: the followed list and balance poll
+//! in `BackgroundService`, and the watched channel's bonus check and claim in
+//! `DropsService`. Drops mining itself lives in the mining plugin, and the
+//! rest of the app's Twitch calls (chat, streams, rewards, predictions) go
+//! through `TwitchService` and the commands that own them.
+
+use crate::models::drops::BonusClaimResult;
+use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::twitch_service::TwitchService;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex as StdMutex};
+
+const HELIX_BASE: &str = "https://api.twitch.tv/helix";
+const GQL_URL: &str = "https://gql.twitch.tv/gql";
+const WEB_CLIENT_ID: &str = env!("TWITCH_WEB_CLIENT_ID");
+const APP_CLIENT_ID: &str = env!("TWITCH_APP_CLIENT_ID");
+/// GQL accepts at most this many operations per batched request.
+pub const GQL_BATCH_LIMIT: usize = 35;
+
+// Only we send this one, so it has no hash; its own name keeps it apart from
+// Twitch's ChannelPointsContext in `UNKNOWN_HASHES`.
+const CHANNEL_POINTS_BALANCE: GqlOperation = GqlOperation {
+    name: "ChannelPointsBalance",
+    hash: None,
+    query: Some(
+        r#"
+query ChannelPointsBalance($channelLogin: String!) {
+    user(login: $channelLogin) {
+        channel {
+            self {
+                communityPoints {
+                    balance
+                }
+            }
+        }
+    }
+}
+"#,
+    ),
+};
+
+const CHANNEL_POINTS_CONTEXT: GqlOperation = GqlOperation {
+    name: "ChannelPointsContext",
+    hash: Some("9988086babc615a918a1e9a722ff41d98847acac822645209ac7379eecb27152"),
+    query: Some(
+        r#"
+        query ChannelPointsContext($channelLogin: String!) {
+            channel(name: $channelLogin) {
+                id
+                self {
+                    communityPoints {
+                        balance
+                        availableClaim { id }
+                    }
+                }
+            }
+        }
+        "#,
+    ),
+};
+
+// Field selection mirrors the official web client's ClaimCommunityPoints
+// (verified capture): `claim.pointsEarnedTotal` is the exact credited amount
+// (multipliers included), `currentPoints` is the new balance.
+const CLAIM_COMMUNITY_POINTS: GqlOperation = GqlOperation {
+    name: "ClaimCommunityPoints",
+    hash: Some("46aaeebe02c99afdf4fc97c7c0cba964124bf6b0af229395f1f6d1feed05b3d0"),
+    query: Some(
+        r#"
+        mutation ClaimCommunityPoints($input: ClaimCommunityPointsInput!) {
+            claimCommunityPoints(input: $input) {
+                claim {
+                    pointsEarnedTotal
+                    pointsEarnedBaseline
+                }
+                currentPoints
+                error {
+                    code
+                }
+            }
+        }
+        "#,
+    ),
+};
+
+/// A GQL operation as Twitch's own clients send it: the sha256 of a query
+/// Twitch already knows (a persisted query), which keeps the request small and
+/// looks like first-party traffic. `query` is the full text to send instead
+/// when Twitch stops recognizing the hash (`PersistedQueryNotFound`); after
+/// one miss the operation goes as full text for the rest of the session. An
+/// operation only we send has no hash, and one whose query text we don't
+/// have has no fallback.
+#[derive(Debug)]
+pub struct GqlOperation {
+    pub name: &'static str,
+    pub hash: Option<&'static str>,
+    pub query: Option<&'static str>,
+}
+
+/// Operations whose hash Twitch rejected this session.
+static UNKNOWN_HASHES: Lazy<StdMutex<HashSet<&'static str>>> =
+    Lazy::new(|| StdMutex::new(HashSet::new()));
+
+impl GqlOperation {
+    fn persisted(&self) -> Option<&'static str> {
+        self.hash
+            .filter(|_| !UNKNOWN_HASHES.lock().unwrap().contains(self.name))
+    }
+
+    fn full_body(&self, variables: &Value) -> Option<Value> {
+        Some(json!({
+            "operationName": self.name,
+            "query": self.query?,
+            "variables": variables,
+        }))
+    }
+
+    /// The request body: persisted while Twitch knows the hash, full text
+    /// otherwise.
+    pub fn body(&self, variables: Value) -> Value {
+        match self.persisted() {
+            Some(hash) => json!({
+                "operationName": self.name,
+                "variables": variables,
+                "extensions": {
+                    "persistedQuery": { "version": 1, "sha256Hash": hash }
+                }
+            }),
+            None => self.full_body(&variables).unwrap_or(Value::Null),
+        }
+    }
+}
+
+/// Whether a GQL response says the persisted query's hash is unknown.
+fn persisted_query_missing(body: &str) -> bool {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v.get("errors").and_then(Value::as_array).cloned())
+        .is_some_and(|errors| {
+            errors.iter().any(|e| {
+                matches!(
+                    e["message"].as_str(),
+                    Some("PersistedQueryNotFound" | "PersistedQueryNotSupported")
+                )
+            })
+        })
+}
+
+/// Send `operation` on the request `request` builds (URL and headers), and
+/// return the status and body. A persisted query Twitch doesn't know is sent
+/// again as full text, when the operation has it.
+pub async fn send_operation(
+    request: impl Fn() -> reqwest::RequestBuilder,
+    operation: &GqlOperation,
+    variables: Value,
+) -> Result<(reqwest::StatusCode, String)> {
+    let persisted = operation.persisted().is_some();
+    let response = request()
+        .json(&operation.body(variables.clone()))
+        .send()
+        .await?;
+    let status = response.status();
+    let body = response.text().await?;
+    if !persisted || !persisted_query_missing(&body) {
+        return Ok((status, body));
+    }
+    let Some(full) = operation.full_body(&variables) else {
+        return Ok((status, body));
+    };
+    log::warn!(
+        "[GQL] Twitch no longer knows the {} hash; sending the full query",
+        operation.name
+    );
+    UNKNOWN_HASHES.lock().unwrap().insert(operation.name);
+    let response = request().json(&full).send().await?;
+    let status = response.status();
+    Ok((status, response.text().await?))
+}
+
+/// A followed channel, live or not.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FollowedChannel {
+    pub channel_id: String,
+    pub login: String,
+    pub display_name: String,
+}
+
+/// The signed-in user's standing in one channel's points program.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointsContext {
+    /// As Twitch reports it; `None` if the response left it out.
+    pub channel_id: Option<String>,
+    pub balance: i32,
+    /// The bonus waiting to be claimed, if any.
+    pub claim: Option<AvailableClaim>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AvailableClaim {
+    pub id: String,
+    pub points_earned: i32,
+}
+
+#[async_trait]
+pub trait TwitchApi: Send + Sync {
+    /// One page of the signed-in user's followed channels, with the cursor
+    /// for the next page (`None` on the last one).
+    async fn followed_channels(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<(Vec<FollowedChannel>, Option<String>)>;
+
+    /// Channel-points balances for up to `GQL_BATCH_LIMIT` logins, in the same
+    /// order. `None` where the channel has no points program or the lookup
+    /// failed for that entry.
+    async fn channel_points_balances(&self, logins: &[String]) -> Result<Vec<Option<i32>>>;
+
+    /// Balance and waiting bonus in `login`'s channel. `None` when the
+    /// channel has no points program for the user.
+    async fn channel_points_context(&self, login: &str) -> Result<Option<PointsContext>>;
+
+    /// Claim the bonus `claim_id` in `channel_id`.
+    async fn claim_community_points(
+        &self,
+        channel_id: &str,
+        claim_id: &str,
+    ) -> Result<BonusClaimResult>;
+}
+
+/// Fixed credentials, for pointing the real client at a test server.
+#[derive(Debug, Clone)]
+pub struct ApiAuth {
+    pub helix_token: String,
+    pub user_id: String,
+    pub gql_token: String,
+}
+
+/// Builds the GQL request headers from the token.
+type GqlHeaders = Arc<dyn Fn(&str) -> HeaderMap + Send + Sync>;
+
+pub struct HttpTwitchApi {
+    helix_base: String,
+    gql_url: String,
+    /// `None` reads the stored credentials on each call.
+    auth: Option<ApiAuth>,
+    /// `None` sends the web client's id and the token.
+    gql_headers: Option<GqlHeaders>,
+}
+
+impl HttpTwitchApi {
+    pub fn new() -> Self {
+        Self {
+            helix_base: HELIX_BASE.to_string(),
+            gql_url: GQL_URL.to_string(),
+            auth: None,
+            gql_headers: None,
+        }
+    }
+
+    /// Same client against other endpoints with fixed credentials.
+    pub fn with_endpoints(helix_base: &str, gql_url: &str, auth: ApiAuth) -> Self {
+        Self {
+            helix_base: helix_base.trim_end_matches('/').to_string(),
+            gql_url: gql_url.to_string(),
+            auth: Some(auth),
+            gql_headers: None,
+        }
+    }
+
+    /// Send GQL with the headers `headers` builds from the token, for a
+    /// caller that presents its own client identity.
+    pub fn with_gql_headers(
+        mut self,
+        headers: impl Fn(&str) -> HeaderMap + Send + Sync + 'static,
+    ) -> Self {
+        self.gql_headers = Some(Arc::new(headers));
+        self
+    }
+
+    async fn helix_auth(&self) -> Result<(String, String)> {
+        if let Some(auth) = &self.auth {
+            return Ok((auth.helix_token.clone(), auth.user_id.clone()));
+        }
+        let token = TwitchService::get_token().await?;
+        let user_id = TwitchService::get_user_info().await?.id;
+        Ok((token, user_id))
+    }
+
+    async fn gql_token(&self) -> Result<String> {
+        match &self.auth {
+            Some(auth) => Ok(auth.gql_token.clone()),
+            None => DropsAuthService::get_token().await,
+        }
+    }
+
+    fn gql_request(&self, token: &str) -> Result<impl Fn() -> reqwest::RequestBuilder + '_> {
+        let headers = match &self.gql_headers {
+            Some(build) => build(token),
+            None => {
+                let mut headers = HeaderMap::new();
+                headers.insert("Client-Id", HeaderValue::from_static(WEB_CLIENT_ID));
+                headers.insert(
+                    AUTHORIZATION,
+                    HeaderValue::from_str(&format!("OAuth {}", token))?,
+                );
+                headers
+            }
+        };
+        Ok(move || {
+            crate::services::http::client()
+                .post(&self.gql_url)
+                .headers(headers.clone())
+        })
+    }
+}
+
+impl Default for HttpTwitchApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl TwitchApi for HttpTwitchApi {
+    async fn followed_channels(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<(Vec<FollowedChannel>, Option<String>)> {
+        let (token, user_id) = self.helix_auth().await?;
+        let mut query = vec![("user_id", user_id), ("first", "100".to_string())];
+        if let Some(c) = cursor {
+            query.push(("after", c));
+        }
+
+        let response: Value = crate::services::http::client()
+            .get(format!("{}/channels/followed", self.helix_base))
+            .query(&query)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Client-Id", APP_CLIENT_ID)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let channels = response["data"]
+            .as_array()
+            .map(|data| data.iter().filter_map(parse_followed).collect())
+            .unwrap_or_default();
+        let next = response["pagination"]["cursor"]
+            .as_str()
+            .filter(|c| !c.is_empty())
+            .map(str::to_string);
+        Ok((channels, next))
+    }
+
+    async fn channel_points_balances(&self, logins: &[String]) -> Result<Vec<Option<i32>>> {
+        if logins.len() > GQL_BATCH_LIMIT {
+            return Err(anyhow!(
+                "{} logins exceeds the GQL batch limit of {}",
+                logins.len(),
+                GQL_BATCH_LIMIT
+            ));
+        }
+        let token = self.gql_token().await?;
+        let body: Vec<Value> = logins
+            .iter()
+            .map(|login| {
+                CHANNEL_POINTS_BALANCE.body(json!({ "channelLogin": login.to_lowercase() }))
+            })
+            .collect();
+
+        let request = self.gql_request(&token)?;
+        let response: Value = request().json(&body).send().await?.json().await?;
+
+        let results = response
+            .as_array()
+            .ok_or_else(|| anyhow!("GQL batch response was not an array"))?;
+        if !results.is_empty() && results.iter().all(|r| r.get("errors").is_some()) {
+            return Err(anyhow!("GQL errors: {}", results[0]["errors"]));
+        }
+        Ok((0..logins.len())
+            .map(|i| {
+                results
+                    .get(i)
+                    .and_then(|r| r.pointer("/data/user/channel/self/communityPoints/balance"))
+                    .and_then(Value::as_i64)
+                    .map(|b| b as i32)
+            })
+            .collect())
+    }
+
+    async fn channel_points_context(&self, login: &str) -> Result<Option<PointsContext>> {
+        let token = self.gql_token().await?;
+        let (_, text) = send_operation(
+            self.gql_request(&token)?,
+            &CHANNEL_POINTS_CONTEXT,
+            json!({ "channelLogin": login.to_lowercase() }),
+        )
+        .await?;
+        let response: Value = serde_json::from_str(&text)?;
+        if let Some(errors) = response.get("errors") {
+            return Err(anyhow!("GraphQL errors: {:?}", errors));
+        }
+        Ok(parse_points_context(&response["data"]["channel"]))
+    }
+
+    async fn claim_community_points(
+        &self,
+        channel_id: &str,
+        claim_id: &str,
+    ) -> Result<BonusClaimResult> {
+        let token = self.gql_token().await?;
+        let (status, text) = send_operation(
+            self.gql_request(&token)?,
+            &CLAIM_COMMUNITY_POINTS,
+            json!({ "input": { "channelID": channel_id, "claimID": claim_id } }),
+        )
+        .await?;
+        if !status.is_success() {
+            return Err(anyhow!("Failed to claim channel points: {}", text));
+        }
+
+        let result: Value = serde_json::from_str(&text)?;
+        let payload = &result["data"]["claimCommunityPoints"];
+        let new_balance = payload["currentPoints"].as_i64().unwrap_or(0) as i32;
+        // Bonus chests are 50 at baseline; fall back to that if the response
+        // omits the earned fields for any reason.
+        let points_earned = payload["claim"]["pointsEarnedTotal"]
+            .as_i64()
+            .or_else(|| payload["claim"]["pointsEarnedBaseline"].as_i64())
+            .unwrap_or(50) as i32;
+
+        Ok(BonusClaimResult {
+            new_balance,
+            points_earned,
+        })
+    }
+}
+
+/// `ChannelPointsContext`'s `data.channel`. `None` without a points entry for
+/// the user.
+fn parse_points_context(channel: &Value) -> Option<PointsContext> {
+    let points = channel.pointer("/self/communityPoints")?;
+    if points.is_null() {
+        return None;
+    }
+    let claim = points
+        .get("availableClaim")
+        .filter(|c| !c.is_null())
+        .map(|claim| AvailableClaim {
+            id: claim["id"].as_str().unwrap_or("").to_string(),
+            points_earned: claim
+                .get("pointsEarnedTotal")
+                .or_else(|| claim.get("pointsEarnedBaseline"))
+                .and_then(Value::as_i64)
+                .unwrap_or(50) as i32,
+        });
+    Some(PointsContext {
+        channel_id: channel["id"].as_str().map(str::to_string),
+        balance: points["balance"].as_i64().unwrap_or(0) as i32,
+        claim,
+    })
+}
+
+/// Helix `channels/followed` entry. The display name falls back to the login
+/// when missing.
+fn parse_followed(entry: &Value) -> Option<FollowedChannel> {
+    let channel_id = entry["broadcaster_id"].as_str().filter(|s| !s.is_empty())?;
+    let login = entry["broadcaster_login"]
+        .as_str()
+        .filter(|s| !s.is_empty())?;
+    let display_name = entry["broadcaster_name"]
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(login);
+    Some(FollowedChannel {
+        channel_id: channel_id.to_string(),
+        login: login.to_string(),
+        display_name: display_name.to_string(),
+    })
+}
+
+/// In-memory `TwitchApi` for tests: a fixed followed list served in pages,
+/// a balance table keyed by login, and waiting bonuses keyed by login. A
+/// claim pays the bonus into the balance and records the claim id.
+#[cfg(test)]
+pub struct MockTwitchApi {
+    pub followed: Vec<FollowedChannel>,
+    pub balances: std::collections::HashMap<String, i32>,
+    pub bonuses: std::collections::HashMap<String, AvailableClaim>,
+    pub claimed: StdMutex<Vec<String>>,
+    pub page_size: usize,
+}
+
+#[cfg(test)]
+impl MockTwitchApi {
+    pub fn new(followed: Vec<FollowedChannel>) -> Self {
+        Self {
+            followed,
+            balances: std::collections::HashMap::new(),
+            bonuses: std::collections::HashMap::new(),
+            claimed: StdMutex::new(Vec::new()),
+            page_size: 100,
+        }
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl TwitchApi for MockTwitchApi {
+    async fn followed_channels(
+        &self,
+        cursor: Option<String>,
+    ) -> Result<(Vec<FollowedChannel>, Option<String>)> {
+        let start: usize = cursor.as_deref().unwrap_or("0").parse()?;
+        let end = (start + self.page_size).min(self.followed.len());
+        let next = (end < self.followed.len()).then(|| end.to_string());
+        Ok((self.followed[start..end].to_vec(), next))
+    }
+
+    async fn channel_points_balances(&self, logins: &[String]) -> Result<Vec<Option<i32>>> {
+        Ok(logins
+            .iter()
+            .map(|l| self.balances.get(l).copied())
+            .collect())
+    }
+
+    async fn channel_points_context(&self, login: &str) -> Result<Option<PointsContext>> {
+        let Some(balance) = self.balances.get(login).copied() else {
+            return Ok(None);
+        };
+        let channel_id = self
+            .followed
+            .iter()
+            .find(|c| c.login == login)
+            .map(|c| c.channel_id.clone());
+        Ok(Some(PointsContext {
+            channel_id,
+            balance,
+            claim: self.bonuses.get(login).cloned(),
+        }))
+    }
+
+    async fn claim_community_points(
+        &self,
+        channel_id: &str,
+        claim_id: &str,
+    ) -> Result<BonusClaimResult> {
+        let (login, bonus) = self
+            .bonuses
+            .iter()
+            .find(|(_, b)| b.id == claim_id)
+            .ok_or_else(|| anyhow!("no bonus {} in {}", claim_id, channel_id))?;
+        self.claimed.lock().unwrap().push(claim_id.to_string());
+        Ok(BonusClaimResult {
+            new_balance: self.balances.get(login).copied().unwrap_or(0) + bonus.points_earned,
+            points_earned: bonus.points_earned,
+        })
+    }
+}
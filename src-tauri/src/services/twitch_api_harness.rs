@@ -0,0 +1,250 @@
+//! Integration harness for `HttpTwitchApi`: a local warp server stands in for
+//! Helix and GQL, so request building (auth headers, pagination, GQL batching,
+//! single operations) and response parsing are tested against real HTTP round trips without
+//! touching Twitch. Logic built on `TwitchApi` is tested with `MockTwitchApi`
+//! next to it instead.
+
+use super::twitch_api::{
+    send_operation, ApiAuth, AvailableClaim, FollowedChannel, GqlOperation, HttpTwitchApi,
+    PointsContext, TwitchApi,
+};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use warp::Filter;
+
+/// Authorization headers the server saw, in order.
+type SeenAuth = Arc<Mutex<Vec<String>>>;
+
+async fn start_mock_twitch() -> (SocketAddr, SeenAuth) {
+    let seen: SeenAuth = Arc::new(Mutex::new(Vec::new()));
+
+    let helix_seen = seen.clone();
+    let followed = warp::get()
+        .and(warp::path!("helix" / "channels" / "followed"))
+        .and(warp::header::<String>("authorization"))
+        .and(warp::query::<HashMap<String, String>>())
+        .map(move |auth: String, q: HashMap<String, String>| {
+            helix_seen.lock().unwrap().push(auth);
+            assert_eq!(q.get("user_id").map(String::as_str), Some("42"));
+            let page = match q.get("after").map(String::as_str) {
+                None => json!({
+                    "data": [
+                        { "broadcaster_id": "1", "broadcaster_login": "alpha", "broadcaster_name": "Alpha" },
+                        { "broadcaster_id": "2", "broadcaster_login": "beta", "broadcaster_name": "" },
+                    ],
+                    "pagination": { "cursor": "page2" }
+                }),
+                Some("page2") => json!({
+                    "data": [
+                        { "broadcaster_id": "3", "broadcaster_login": "gamma", "broadcaster_name": "Gamma" },
+                    ],
+                    "pagination": {}
+                }),
+                Some(_) => json!({ "data": [], "pagination": {} }),
+            };
+            warp::reply::json(&page)
+        });
+
+    let gql_seen = seen.clone();
+    let balances: HashMap<&str, i64> = [("alpha", 1200), ("gamma", 35)].into();
+    let gql = warp::post()
+        .and(warp::path("gql"))
+        .and(warp::header::<String>("authorization"))
+        .and(warp::body::json())
+        .map(move |auth: String, body: Value| {
+            gql_seen.lock().unwrap().push(auth);
+            // A batch is the balance poll; a single operation is answered by name.
+            let Some(ops) = body.as_array() else {
+                return warp::reply::json(&single_operation(&body, &balances));
+            };
+            let results: Vec<Value> = ops
+                .iter()
+                .map(|op| {
+                    assert_eq!(op["operationName"], "ChannelPointsBalance");
+                    let login = op["variables"]["channelLogin"].as_str().unwrap_or("");
+                    match balances.get(login) {
+                        Some(b) => json!({ "data": { "user": { "channel": { "self": {
+                            "communityPoints": { "balance": b }
+                        }}}}}),
+                        None => json!({ "data": { "user": null } }),
+                    }
+                })
+                .collect();
+            warp::reply::json(&results)
+        });
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        warp::serve(followed.or(gql)).incoming(listener).run().await;
+    });
+    (addr, seen)
+}
+
+/// `alpha` has a bonus waiting; claiming it pays 50 on top of the balance.
+fn single_operation(op: &Value, balances: &HashMap<&str, i64>) -> Value {
+    let variables = &op["variables"];
+    match op["operationName"].as_str() {
+        Some("ChannelPointsContext") => {
+            let login = variables["channelLogin"].as_str().unwrap_or("");
+            let Some(balance) = balances.get(login) else {
+                return json!({ "data": { "channel": null } });
+            };
+            let claim = (login == "alpha").then(|| json!({ "id": "bonus-1" }));
+            json!({ "data": { "channel": { "id": "1", "self": { "communityPoints": {
+                "balance": balance, "availableClaim": claim
+            }}}}})
+        }
+        Some("ClaimCommunityPoints") => {
+            assert_eq!(variables["input"]["claimID"], "bonus-1");
+            json!({ "data": { "claimCommunityPoints": {
+                "claim": { "pointsEarnedTotal": 50, "pointsEarnedBaseline": 50 },
+                "currentPoints": 1250,
+                "error": null
+            }}})
+        }
+        other => json!({ "errors": [{ "message": format!("unknown operation {other:?}") }] }),
+    }
+}
+
+fn api_for(addr: SocketAddr) -> HttpTwitchApi {
+    HttpTwitchApi::with_endpoints(
+        &format!("http://{addr}/helix"),
+        &format!("http://{addr}/gql"),
+        ApiAuth {
+            helix_token: "helix-token".into(),
+            user_id: "42".into(),
+            gql_token: "gql-token".into(),
+        },
+    )
+}
+
+#[tokio::test]
+async fn followed_channels_page_through_helix() {
+    let (addr, seen) = start_mock_twitch().await;
+    let api = api_for(addr);
+
+    let (first, cursor) = api.followed_channels(None).await.unwrap();
+    assert_eq!(cursor.as_deref(), Some("page2"));
+    assert_eq!(
+        first[1],
+        FollowedChannel {
+            channel_id: "2".into(),
+            login: "beta".into(),
+            display_name: "beta".into(),
+        }
+    );
+
+    let (second, cursor) = api.followed_channels(cursor).await.unwrap();
+    assert_eq!(second.len(), 1);
+    assert_eq!(cursor, None);
+    assert!(seen
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|a| a == "Bearer helix-token"));
+}
+
+#[tokio::test]
+async fn balances_come_back_in_login_order() {
+    let (addr, seen) = start_mock_twitch().await;
+    let api = api_for(addr);
+
+    let logins = vec!["gamma".to_string(), "beta".into(), "Alpha".into()];
+    let balances = api.channel_points_balances(&logins).await.unwrap();
+    assert_eq!(balances, vec![Some(35), None, Some(1200)]);
+    assert_eq!(seen.lock().unwrap().as_slice(), ["OAuth gql-token"]);
+}
+
+#[tokio::test]
+async fn a_bonus_is_read_and_claimed_with_the_callers_headers() {
+    let (addr, seen) = start_mock_twitch().await;
+    let api = api_for(addr).with_gql_headers(|token| {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            "Authorization",
+            format!("OAuth {token} device").parse().unwrap(),
+        );
+        headers
+    });
+
+    assert_eq!(api.channel_points_context("beta").await.unwrap(), None);
+    let context = api.channel_points_context("Alpha").await.unwrap();
+    assert_eq!(
+        context,
+        Some(PointsContext {
+            channel_id: Some("1".into()),
+            balance: 1200,
+            claim: Some(AvailableClaim {
+                id: "bonus-1".into(),
+                points_earned: 50,
+            }),
+        })
+    );
+
+    let result = api.claim_community_points("1", "bonus-1").await.unwrap();
+    assert_eq!((result.new_balance, result.points_earned), (1250, 50));
+    assert!(seen
+        .lock()
+        .unwrap()
+        .iter()
+        .all(|a| a == "OAuth gql-token device"));
+}
+
+#[tokio::test]
+async fn oversized_batches_are_refused_before_sending() {
+    let (addr, seen) = start_mock_twitch().await;
+    let logins: Vec<String> = (0..36).map(|i| format!("chan{i}")).collect();
+    assert!(api_for(addr)
+        .channel_points_balances(&logins)
+        .await
+        .is_err());
+    assert!(seen.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn an_unknown_persisted_hash_falls_back_to_the_full_query() {
+    let bodies: Arc<Mutex<Vec<Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let seen = bodies.clone();
+    let gql = warp::post()
+        .and(warp::path("gql"))
+        .and(warp::body::json())
+        .map(move |body: Value| {
+            seen.lock().unwrap().push(body.clone());
+            let reply = if body.get("query").is_some() {
+                json!({ "data": { "ok": true } })
+            } else {
+                json!({ "errors": [{ "message": "PersistedQueryNotFound" }] })
+            };
+            warp::reply::json(&reply)
+        });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        warp::serve(gql).incoming(listener).run().await;
+    });
+
+    const OPERATION: GqlOperation = GqlOperation {
+        name: "HarnessFallback",
+        hash: Some("abc123"),
+        query: Some("query HarnessFallback { ok }"),
+    };
+    let client = reqwest::Client::new();
+    let request = || client.post(format!("http://{addr}/gql"));
+    for _ in 0..2 {
+        let (_, body) = send_operation(request, &OPERATION, json!({}))
+            .await
+            .unwrap();
+        assert!(body.contains("\"ok\":true"));
+    }
+    // Persisted, full, then straight to full once the hash is known to miss.
+    let bodies = bodies.lock().unwrap();
+    assert_eq!(bodies.len(), 3);
+    assert_eq!(
+        bodies[0]["extensions"]["persistedQuery"]["sha256Hash"],
+        "abc123"
+    );
+    assert!(bodies[1]["query"].is_string() && bodies[2]["query"].is_string());
+}
@@ -1,9 +1,11 @@
 use crate::models::settings::AppState;
 use crate::models::{
-    stream::TwitchStream,
+    stream::{StreamFilter, TwitchStream},
     user::{ChannelInfo, UserInfo},
 };
+use crate::services::api_error::{ApiError, ErrorKind};
 use crate::services::cookie_jar_service::CookieJarService;
+use crate::services::event_bus;
 use anyhow::Result;
 use chrono::{Duration as ChronoDuration, Utc};
 use keyring::Entry;
@@ -13,8 +15,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex as StdMutex;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tauri::Emitter;
 
 const CLIENT_ID: &str = env!("TWITCH_APP_CLIENT_ID");
 const CLIENT_SECRET: &str = env!("TWITCH_APP_CLIENT_SECRET");
@@ -25,6 +27,19 @@ const REDIRECT_URI: &str = "http://localhost:3000/callback";
 const SCOPES: &str = "user:read:follows user:read:email chat:read chat:edit channel:read:redemptions channel:manage:redemptions moderator:read:followers openid user:manage:whispers user:read:whispers user:read:emotes channel:read:hype_train moderator:read:blocked_terms moderator:manage:chat_settings moderator:manage:unban_requests moderator:manage:banned_users moderator:manage:chat_messages moderator:read:warnings moderator:read:moderators moderator:read:vips moderator:read:chatters channel:manage:moderators channel:manage:vips moderator:manage:suspicious_users user:manage:chat_color user:manage:blocked_users user:read:blocked_users moderator:manage:announcements moderator:manage:shoutouts channel:edit:commercial channel:manage:raids channel:manage:broadcast moderation:read user:write:chat clips:edit";
 const TOKEN_FILE_NAME: &str = ".twitch_token";
 
+/// The device-code poller spawned by `login`, so `cancel_login` can stop it
+/// and a second login replaces the first instead of running alongside it.
+static LOGIN_TASK: StdMutex<Option<tokio::task::AbortHandle>> = StdMutex::new(None);
+
+/// `twitch-login-progress` payload, sent on every poll of the device code.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoginProgress {
+    pub seconds_remaining: u64,
+    pub poll_interval: u64,
+    /// Twitch asked us to poll less often on this poll.
+    pub slow_down: bool,
+}
+
 /// Get the app data directory (works consistently in dev and release)
 pub(crate) fn get_app_data_dir() -> Result<PathBuf> {
     // Try to use the standard config directory first
@@ -406,6 +421,16 @@ impl TwitchService {
         _state: &AppState,
         app_handle: tauri::AppHandle,
     ) -> Result<(String, String)> {
+        let (verification_uri, user_code, _) = Self::start_login(app_handle).await?;
+        Ok((verification_uri, user_code))
+    }
+
+    /// `login`, also handing back the poller, which resolves to whether the
+    /// token was received and stored (false on failure; a cancelled poller
+    /// resolves to a `JoinError`).
+    pub(crate) async fn start_login(
+        app_handle: tauri::AppHandle,
+    ) -> Result<(String, String, tokio::task::JoinHandle<bool>)> {
         let client = crate::services::http::client().clone();
 
         // Start device flow
@@ -424,7 +449,7 @@ impl TwitchService {
         let verification_uri = device_response.verification_uri.clone();
 
         // Spawn a task to poll for token
-        tokio::task::spawn(async move {
+        let task = tokio::task::spawn(async move {
             debug!("[LOGIN] Starting token polling task...");
             let result = Self::poll_for_token(&client, &device_code, interval, expires_in).await;
 
@@ -500,44 +525,68 @@ impl TwitchService {
 
                             // Emit success event
                             debug!("[LOGIN] Emitting twitch-login-complete event...");
-                            if let Err(e) = app_handle.emit("twitch-login-complete", ()) {
+                            if let Err(e) = event_bus::emit("twitch-login-complete", ()) {
                                 error!("[LOGIN] Failed to emit login-complete event: {}", e);
                             } else {
                                 debug!("[LOGIN] Event emitted successfully");
                             }
+                            true
                         }
                         (Ok(_), Err(e)) => {
                             error!("[LOGIN] Token saved to file but cookies failed: {:?}", e);
                             // Still emit success since file storage worked
-                            let _ = app_handle.emit("twitch-login-complete", ());
+                            let _ = event_bus::emit("twitch-login-complete", ());
+                            true
                         }
                         (Err(e), Ok(_)) => {
                             error!("[LOGIN] Token saved to cookies but file failed: {:?}", e);
                             // Still emit success since cookies worked
-                            let _ = app_handle.emit("twitch-login-complete", ());
+                            let _ = event_bus::emit("twitch-login-complete", ());
+                            true
                         }
                         (Err(file_err), Err(cookie_err)) => {
                             error!(
                                 "[LOGIN] Failed to store token anywhere! File: {:?}, Cookie: {:?}",
                                 file_err, cookie_err
                             );
-                            let _ = app_handle.emit(
+                            let _ = event_bus::emit(
                                 "twitch-login-error",
                                 format!("Failed to store token: {}", file_err),
                             );
+                            false
                         }
                     }
                 }
                 Err(e) => {
                     error!("[LOGIN] Token polling failed: {}", e);
-                    let _ = app_handle.emit("twitch-login-error", e.to_string());
+                    let _ = event_bus::emit("twitch-login-error", e.to_string());
+                    false
                 }
             }
         });
+        if let Some(previous) = LOGIN_TASK.lock().unwrap().replace(task.abort_handle()) {
+            previous.abort();
+        }
 
         // Return the verification URI and the matching user code for the frontend
         // to open/display. Both come from the single device flow polled above.
-        Ok((verification_uri, user_code))
+        Ok((verification_uri, user_code, task))
+    }
+
+    /// Stop the poller of a login still waiting for the user to authorize.
+    /// Returns false when no login was pending.
+    pub fn cancel_login(app_handle: &tauri::AppHandle) -> bool {
+        let Some(task) = LOGIN_TASK.lock().unwrap().take() else {
+            return false;
+        };
+        if task.is_finished() {
+            return false;
+        }
+        task.abort();
+        debug!("[LOGIN] Login cancelled");
+        crate::commands::twitch::dismiss_login_overlay(app_handle, "twitch-login");
+        let _ = event_bus::emit("twitch-login-cancelled", ());
+        true
     }
 
     // Device code flow methods (kept for backward compatibility if needed)
@@ -686,6 +735,14 @@ impl TwitchService {
                 ));
             }
 
+            let _ = event_bus::emit(
+                "twitch-login-progress",
+                LoginProgress {
+                    seconds_remaining: expiry_time - current_time,
+                    poll_interval,
+                    slow_down: false,
+                },
+            );
             tokio::time::sleep(Duration::from_secs(poll_interval)).await;
 
             let params = [
@@ -714,6 +771,15 @@ impl TwitchService {
             } else if error_text.contains("slow_down") {
                 // Twitch wants us to slow down
                 poll_interval += 2;
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+                let _ = event_bus::emit(
+                    "twitch-login-progress",
+                    LoginProgress {
+                        seconds_remaining: expiry_time.saturating_sub(now),
+                        poll_interval,
+                        slow_down: true,
+                    },
+                );
                 continue;
             } else if error_text.contains("expired_token") {
                 return Err(anyhow::anyhow!(
@@ -815,15 +881,17 @@ impl TwitchService {
                             }
                             Err(e) => {
                                 error!("[GET_TOKEN] Failed to refresh token: {:?}", e);
-                                return Err(anyhow::anyhow!(
-                                    "Token expired and refresh failed. Please log in again."
-                                ));
+                                return Err(ApiError::auth_expired(
+                                    "Token expired and refresh failed. Please log in again.",
+                                )
+                                .into());
                             }
                         }
                     } else {
-                        return Err(anyhow::anyhow!(
-                            "Token expired and no refresh token available. Please log in again."
-                        ));
+                        return Err(ApiError::auth_expired(
+                            "Token expired and no refresh token available. Please log in again.",
+                        )
+                        .into());
                     }
                 }
 
@@ -860,9 +928,9 @@ impl TwitchService {
                                             e
                                         );
                                         let _ = Self::delete_cookies().await;
-                                        return Err(anyhow::anyhow!(
+                                        return Err(ApiError::auth_expired(
                                             "Token expired and refresh failed. Please log in again."
-                                        ));
+                                        ).into());
                                     }
                                 }
                             } else {
@@ -880,9 +948,10 @@ impl TwitchService {
                                 if response.status() == 401 {
                                     debug!("[GET_TOKEN] Cookie token is invalid, clearing cookies");
                                     let _ = Self::delete_cookies().await;
-                                    return Err(anyhow::anyhow!(
-                                        "Not authenticated. Please log in to Twitch first."
-                                    ));
+                                    return Err(ApiError::auth_expired(
+                                        "Not authenticated. Please log in to Twitch first.",
+                                    )
+                                    .into());
                                 }
                             }
                         }
@@ -907,9 +976,10 @@ impl TwitchService {
                                             "[GET_TOKEN] Failed to parse keyring token: {:?}",
                                             e
                                         );
-                                        return Err(anyhow::anyhow!(
-                                            "Not authenticated. Please log in to Twitch first."
-                                        ));
+                                        return Err(ApiError::auth_expired(
+                                            "Not authenticated. Please log in to Twitch first.",
+                                        )
+                                        .into());
                                     }
                                 };
 
@@ -932,9 +1002,9 @@ impl TwitchService {
                                                     "[GET_TOKEN] Failed to refresh keyring token: {:?}",
                                                     e
                                                 );
-                                                return Err(anyhow::anyhow!(
+                                                return Err(ApiError::auth_expired(
                                                     "Token expired and refresh failed. Please log in again."
-                                                ));
+                                                ).into());
                                             }
                                         }
                                     }
@@ -945,9 +1015,10 @@ impl TwitchService {
                         }
 
                         error!("[GET_TOKEN] No token found in file, cookies, or keyring storage");
-                        Err(anyhow::anyhow!(
-                            "Not authenticated. Please log in to Twitch first."
-                        ))
+                        Err(ApiError::auth_expired(
+                            "Not authenticated. Please log in to Twitch first.",
+                        )
+                        .into())
                     }
                 }
             }
@@ -1227,6 +1298,21 @@ impl TwitchService {
                     }
                 }
 
+                // Every status check doubles as a viewer-count and uptime
+                // sample for channel scoring (viewer_stats_service,
+                // stream_uptime_service).
+                for stream in &streams {
+                    crate::services::viewer_stats_service::record(
+                        &stream.user_id,
+                        stream.viewer_count,
+                    );
+                    crate::services::stream_uptime_service::observe(
+                        &stream.user_id,
+                        &stream.user_login,
+                        &stream.started_at,
+                    );
+                }
+
                 Ok(streams)
             }
             None => Ok(Vec::new()), // Return empty vec if no data
@@ -1536,17 +1622,43 @@ impl TwitchService {
         Ok(user_info)
     }
 
+    /// The cheermotes usable in `broadcaster_id`'s chat (global ones plus the
+    /// channel's own), as Helix returns them.
+    pub async fn get_cheermotes(broadcaster_id: &str) -> Result<Vec<serde_json::Value>> {
+        let token = Self::get_token().await?;
+        let response = crate::services::http::client()
+            .get("https://api.twitch.tv/helix/bits/cheermotes")
+            .query(&[("broadcaster_id", broadcaster_id)])
+            .header(AUTHORIZATION, format!("Bearer {}", token))
+            .header("Client-Id", CLIENT_ID)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<serde_json::Value>()
+            .await?;
+        Ok(response
+            .get("data")
+            .and_then(|d| d.as_array())
+            .cloned()
+            .unwrap_or_default())
+    }
+
     pub async fn get_recommended_streams_paginated(
         _state: &AppState,
         cursor: Option<String>,
         limit: u32,
+        filter: &StreamFilter,
     ) -> Result<(Vec<TwitchStream>, Option<String>)> {
         // Try to get token, but don't fail if not authenticated
         let token = Self::get_token().await.ok();
         let client = crate::services::http::client().clone();
 
         // Build URL with pagination
-        let mut url = format!("https://api.twitch.tv/helix/streams?first={}", limit);
+        let mut url = format!(
+            "https://api.twitch.tv/helix/streams?first={}{}",
+            limit,
+            filter.helix_language_params()
+        );
         if let Some(cursor) = cursor {
             url.push_str(&format!("&after={}", cursor));
         }
@@ -1572,8 +1684,9 @@ impl TwitchService {
 
         match data {
             Some(arr) => {
-                let mut streams: Vec<TwitchStream> =
-                    serde_json::from_value(serde_json::Value::Array(arr.clone()))?;
+                let mut streams: Vec<TwitchStream> = filter.apply(serde_json::from_value(
+                    serde_json::Value::Array(arr.clone()),
+                )?);
 
                 // Fetch broadcaster types for all streams in a batch
                 if !streams.is_empty() && token.is_some() {
@@ -1626,7 +1739,9 @@ impl TwitchService {
     }
 
     pub async fn get_recommended_streams(_state: &AppState) -> Result<Vec<TwitchStream>> {
-        let (streams, _) = Self::get_recommended_streams_paginated(_state, None, 20).await?;
+        let (streams, _) =
+            Self::get_recommended_streams_paginated(_state, None, 20, &StreamFilter::default())
+                .await?;
 
         Ok(streams)
     }
@@ -1784,13 +1899,16 @@ impl TwitchService {
         game_id: &str,
         cursor: Option<String>,
         limit: u32,
+        filter: &StreamFilter,
     ) -> Result<(Vec<TwitchStream>, Option<String>)> {
         let token = Self::get_token().await.ok();
         let client = crate::services::http::client().clone();
 
         let mut url = format!(
-            "https://api.twitch.tv/helix/streams?game_id={}&first={}",
-            game_id, limit
+            "https://api.twitch.tv/helix/streams?game_id={}&first={}{}",
+            game_id,
+            limit,
+            filter.helix_language_params()
         );
         if let Some(cursor) = cursor {
             url.push_str(&format!("&after={}", cursor));
@@ -1814,8 +1932,9 @@ impl TwitchService {
 
         match data {
             Some(arr) => {
-                let mut streams: Vec<TwitchStream> =
-                    serde_json::from_value(serde_json::Value::Array(arr.clone()))?;
+                let mut streams: Vec<TwitchStream> = filter.apply(serde_json::from_value(
+                    serde_json::Value::Array(arr.clone()),
+                )?);
 
                 if !streams.is_empty() && token.is_some() {
                     let user_ids: Vec<String> = streams.iter().map(|s| s.user_id.clone()).collect();
@@ -1941,7 +2060,16 @@ impl TwitchService {
                             has_shared_chat: None, // Will be populated later
                             profile_image_url: Some(thumbnail_url.clone()), // Preserve the actual profile picture from search
                             is_live: channel.get("is_live").and_then(|v| v.as_bool()),
-                            tags: None,
+                            tags: channel.get("tags").and_then(|t| {
+                                serde_json::from_value::<Vec<String>>(t.clone())
+                                    .ok()
+                                    .filter(|t| !t.is_empty())
+                            }),
+                            language: channel
+                                .get("broadcaster_language")
+                                .and_then(|v| v.as_str())
+                                .filter(|s| !s.is_empty())
+                                .map(|s| s.to_string()),
                         });
                     }
                 }
@@ -2044,6 +2172,7 @@ impl TwitchService {
                             profile_image_url: exact_user.profile_image_url,
                             is_live: Some(false),
                             tags: None,
+                            language: None,
                         };
                         streams.insert(0, synthesize);
                     }
@@ -2429,6 +2558,7 @@ impl TwitchService {
                             profile_image_url: None,
                             is_live: Some(false),
                             tags: None,
+                            language: None,
                         });
                     }
                 }
@@ -2527,38 +2657,25 @@ impl TwitchService {
             {
                 for user in users_data {
                     if let Some(id) = user.get("id").and_then(|i| i.as_str()) {
-                        let mut final_time = None;
-
-                        // Try calculating exact true end time using their most recent VOD/Stream duration
-                        if let Some(edges) = user
-                            .get("videos")
-                            .and_then(|v| v.get("edges"))
-                            .and_then(|e| e.as_array())
-                        {
-                            if let Some(node) = edges.first().and_then(|e| e.get("node")) {
-                                if let (Some(created_at), Some(length)) = (
-                                    node.get("createdAt").and_then(|c| c.as_str()),
-                                    node.get("lengthSeconds").and_then(|l| l.as_i64()),
-                                ) {
-                                    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(created_at)
-                                    {
-                                        let end_time = dt + ChronoDuration::seconds(length);
-                                        // Format back to RFC3339 string so frontend can trivially parse it using new Date()
-                                        final_time = Some(end_time.to_rfc3339());
-                                    }
-                                }
-                            }
-                        }
-
-                        // Fallback to start time for streamers who completely disable all VOD recordings
-                        if final_time.is_none() {
-                            final_time = user
-                                .get("lastBroadcast")
-                                .and_then(|lb| lb.get("startedAt"))
-                                .and_then(|sa| sa.as_str())
-                                .map(|s| s.to_string());
-                        }
+                        let started_at = user
+                            .get("lastBroadcast")
+                            .and_then(|lb| lb.get("startedAt"))
+                            .and_then(|sa| sa.as_str())
+                            .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok());
+
+                        // When the most recent VOD ended. The latest video can be an old
+                        // upload or highlight, so it only counts when it's later than the
+                        // last broadcast's start (and for streamers who disable VODs, the
+                        // start is all there is).
+                        let vod_end = user.pointer("/videos/edges/0/node").and_then(|node| {
+                            let created_at = node.get("createdAt")?.as_str()?;
+                            let length = node.get("lengthSeconds")?.as_i64()?;
+                            let dt = chrono::DateTime::parse_from_rfc3339(created_at).ok()?;
+                            Some(dt + ChronoDuration::seconds(length))
+                        });
 
+                        // RFC3339 so the frontend can trivially parse it using new Date()
+                        let final_time = started_at.max(vod_end).map(|t| t.to_rfc3339());
                         result_map.insert(id.to_string(), final_time);
                     }
                 }
@@ -2695,6 +2812,16 @@ impl TwitchService {
 
     /// Get the game ID by game name
     pub async fn get_game_id_by_name(game_name: &str) -> Result<Option<String>> {
+        Ok(Self::get_game_by_name(game_name).await?.and_then(|game| {
+            game.get("id")
+                .and_then(|id| id.as_str())
+                .map(|s| s.to_string())
+        }))
+    }
+
+    /// The category named exactly `game_name` (`{id, name, box_art_url}`), via
+    /// Helix Get Games.
+    pub async fn get_game_by_name(game_name: &str) -> Result<Option<serde_json::Value>> {
         let token = Self::get_token().await.ok();
         let client = crate::services::http::client().clone();
 
@@ -2711,15 +2838,11 @@ impl TwitchService {
 
         let response = request.send().await?.json::<serde_json::Value>().await?;
 
-        let game_id = response
+        Ok(response
             .get("data")
             .and_then(|d| d.as_array())
             .and_then(|arr| arr.first())
-            .and_then(|game| game.get("id"))
-            .and_then(|id| id.as_str())
-            .map(|s| s.to_string());
-
-        Ok(game_id)
+            .cloned())
     }
 
     /// Resolve category ids → their objects (`{id, name, box_art_url}`) via Helix
@@ -2821,9 +2944,10 @@ impl TwitchService {
         cursor: Option<&str>,
         limit: u32,
     ) -> Result<(Vec<TwitchStream>, Option<String>)> {
-        // Resolve the display name to a category id, then reuse the id-based path.
-        let game_id = match Self::get_game_id_by_name(game_name).await? {
-            Some(id) => id,
+        // Resolve the display name to a category id (cached and
+        // alias-aware), then reuse the id-based path.
+        let game_id = match crate::services::game_resolver::resolve(game_name).await? {
+            Some(game) => game.id,
             None => return Ok((Vec::new(), None)), // Game not found
         };
 
@@ -3117,6 +3241,7 @@ impl TwitchService {
                     profile_image_url,
                     is_live: Some(true),
                     tags: if stream_tags.is_empty() { None } else { Some(stream_tags) },
+                    language: None,
                 });
             }
         }
@@ -3130,6 +3255,85 @@ impl TwitchService {
         Ok((streams, if has_next { last_cursor } else { None }))
     }
 
+    /// Live streams with drops enabled (Twitch's `DROPS_ENABLED` system
+    /// filter), by viewer count, in one category or across all of them.
+    pub async fn get_drops_enabled_streams(
+        game_id: Option<&str>,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> Result<(Vec<TwitchStream>, Option<String>)> {
+        let client = crate::services::http::client().clone();
+
+        let streams_field = "streams(first: $first, after: $after, \
+                options: { sort: VIEWER_COUNT, systemFilters: [DROPS_ENABLED] }) { \
+                edges { cursor node { \
+                    id title viewersCount createdAt \
+                    previewImageURL \
+                    freeformTags { name } \
+                    game { id name } \
+                    broadcaster { id login displayName profileImageURL(width: 70) roles { isPartner isAffiliate } \
+                        broadcastSettings { language } } \
+                } } \
+                pageInfo { hasNextPage } \
+            }";
+        let (query, streams_path) = match game_id {
+            Some(_) => (
+                format!(
+                    "query($id: ID!, $first: Int!, $after: Cursor) {{ game(id: $id) {{ {} }} }}",
+                    streams_field
+                ),
+                "/data/game/streams",
+            ),
+            None => (
+                format!(
+                    "query($first: Int!, $after: Cursor) {{ {} }}",
+                    streams_field
+                ),
+                "/data/streams",
+            ),
+        };
+
+        let mut variables = serde_json::json!({ "first": limit, "after": cursor });
+        if let Some(id) = game_id {
+            variables["id"] = serde_json::json!(id);
+        }
+        let body = serde_json::json!({ "query": query, "variables": variables });
+
+        let response = client
+            .post("https://gql.twitch.tv/gql")
+            .header("Client-Id", TWITCH_GQL_CLIENT_ID)
+            .json(&body)
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await?;
+
+        let streams_node = response.pointer(streams_path);
+        let edges = streams_node
+            .and_then(|s| s.get("edges"))
+            .and_then(|e| e.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        let mut streams = Vec::with_capacity(edges.len());
+        let mut last_cursor: Option<String> = None;
+        for edge in &edges {
+            if let Some(c) = edge.get("cursor").and_then(|c| c.as_str()) {
+                last_cursor = Some(c.to_string());
+            }
+            if let Some(stream) = edge.get("node").and_then(stream_from_gql_node) {
+                streams.push(stream);
+            }
+        }
+
+        let has_next = streams_node
+            .and_then(|s| s.pointer("/pageInfo/hasNextPage"))
+            .and_then(|h| h.as_bool())
+            .unwrap_or(false);
+
+        Ok((streams, if has_next { last_cursor } else { None }))
+    }
+
     pub async fn get_clips_by_game(
         game_id: &str,
         limit: u32,
@@ -3751,12 +3955,21 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!(
                 "[TwitchService] Failed to update chat settings: {}",
                 error_text
             );
-            return Err(anyhow::anyhow!("Failed to update chat settings"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::NotAModerator,
+                format!(
+                    "Failed to update chat settings (HTTP {}): {}",
+                    status, error_text
+                ),
+            )
+            .into());
         }
 
         Ok(())
@@ -3782,9 +3995,15 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to clear chat: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to clear chat"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::NotAModerator,
+                format!("Failed to clear chat (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -3819,11 +4038,12 @@ impl TwitchService {
             // Surface Twitch's status + reason so the caller can tell apart a
             // restricted delete (broadcaster/other-mod message, >6h old) from a
             // scope/auth failure instead of an opaque "Failed to delete message".
-            return Err(anyhow::anyhow!(
-                "Twitch rejected delete (HTTP {}): {}",
+            return Err(ApiError::helix(
                 status,
-                error_text
-            ));
+                ErrorKind::NotAModerator,
+                format!("Twitch rejected delete (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -3869,11 +4089,12 @@ impl TwitchService {
                 "[TwitchService] Failed to pin message (HTTP {}): {}",
                 status, error_text
             );
-            return Err(anyhow::anyhow!(
-                "Twitch rejected pin (HTTP {}): {}",
+            return Err(ApiError::helix(
                 status,
-                error_text
-            ));
+                ErrorKind::NotAModerator,
+                format!("Twitch rejected pin (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -3910,11 +4131,12 @@ impl TwitchService {
                 "[TwitchService] Failed to unpin message (HTTP {}): {}",
                 status, error_text
             );
-            return Err(anyhow::anyhow!(
-                "Twitch rejected unpin (HTTP {}): {}",
+            return Err(ApiError::helix(
                 status,
-                error_text
-            ));
+                ErrorKind::NotAModerator,
+                format!("Twitch rejected unpin (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -3963,9 +4185,18 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to ban/timeout user: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to ban or timeout user"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::NotAModerator,
+                format!(
+                    "Failed to ban or timeout user (HTTP {}): {}",
+                    status, error_text
+                ),
+            )
+            .into());
         }
 
         Ok(())
@@ -3991,9 +4222,15 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to unban user: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to unban user"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::NotAModerator,
+                format!("Failed to unban user (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -4017,9 +4254,15 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to add moderator: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to add moderator"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::Forbidden,
+                format!("Failed to add moderator (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -4043,9 +4286,18 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to remove moderator: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to remove moderator"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::Forbidden,
+                format!(
+                    "Failed to remove moderator (HTTP {}): {}",
+                    status, error_text
+                ),
+            )
+            .into());
         }
 
         Ok(())
@@ -4069,9 +4321,15 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to add VIP: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to add VIP"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::Forbidden,
+                format!("Failed to add VIP (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -4095,9 +4353,15 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() && response.status() != 204 {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!("[TwitchService] Failed to remove VIP: {}", error_text);
-            return Err(anyhow::anyhow!("Failed to remove VIP"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::Forbidden,
+                format!("Failed to remove VIP (HTTP {}): {}", status, error_text),
+            )
+            .into());
         }
 
         Ok(())
@@ -4134,12 +4398,21 @@ impl TwitchService {
             .await?;
 
         if !response.status().is_success() {
+            let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             error!(
                 "[TwitchService] Failed to update suspicious user status: {}",
                 error_text
             );
-            return Err(anyhow::anyhow!("Failed to update suspicious user status"));
+            return Err(ApiError::helix(
+                status,
+                ErrorKind::NotAModerator,
+                format!(
+                    "Failed to update suspicious user status (HTTP {}): {}",
+                    status, error_text
+                ),
+            )
+            .into());
         }
 
         Ok(())
@@ -4473,26 +4746,21 @@ impl TwitchService {
         }))
     }
 
-    /// Get the full official chatters roster for a channel, grouped by role.
-    ///
-    /// Uses Helix Get Chatters, which requires the caller to be a moderator or the
-    /// broadcaster of the channel and to hold the `moderator:read:chatters` scope.
-    /// That endpoint returns logins only, so we compose it with the GQL Mods/VIPs
-    /// lookup (`get_chatters_by_role`, which works for any authenticated user) to
-    /// bucket each chatter into broadcaster / moderators / vips / viewers.
-    pub async fn get_channel_chatters(
+    /// Page through Helix Get Chatters for `broadcaster_id`. Returns the raw
+    /// entries (`user_id`, `user_login`, `user_name`) and whether the page cap
+    /// cut the roster short. Requires the caller to be a moderator or the
+    /// broadcaster; a 401 comes back as `REAUTH` (token predates the
+    /// `moderator:read:chatters` scope).
+    pub async fn fetch_chatters_roster(
         broadcaster_id: &str,
-        channel_login: &str,
-    ) -> Result<serde_json::Value> {
-        use std::collections::HashSet;
-
+    ) -> Result<(Vec<serde_json::Value>, bool)> {
         let token = Self::get_token().await?;
         let client = crate::services::http::client().clone();
 
         // The roster endpoint requires moderator_id = the authenticated user's id.
         let moderator_id = Self::get_user_info().await?.id;
 
-        // 1. Page through the official chatters roster (first=1000 is the max page).
+        // first=1000 is the max page.
         const MAX_PAGES: usize = 10; // ~10k chatters; surfaced via `truncated` if hit.
         let mut roster: Vec<serde_json::Value> = Vec::new();
         let mut cursor: Option<String> = None;
@@ -4545,6 +4813,24 @@ impl TwitchService {
                 truncated = true;
             }
         }
+        Ok((roster, truncated))
+    }
+
+    /// Get the full official chatters roster for a channel, grouped by role.
+    ///
+    /// Uses Helix Get Chatters, which requires the caller to be a moderator or the
+    /// broadcaster of the channel and to hold the `moderator:read:chatters` scope.
+    /// That endpoint returns logins only, so we compose it with the GQL Mods/VIPs
+    /// lookup (`get_chatters_by_role`, which works for any authenticated user) to
+    /// bucket each chatter into broadcaster / moderators / vips / viewers.
+    pub async fn get_channel_chatters(
+        broadcaster_id: &str,
+        channel_login: &str,
+    ) -> Result<serde_json::Value> {
+        use std::collections::HashSet;
+
+        // 1. The official chatters roster.
+        let (roster, truncated) = Self::fetch_chatters_roster(broadcaster_id).await?;
 
         // 2. Role sets from the GQL Mods/VIPs lookup (best-effort; an empty result
         //    just means everyone falls into the viewers bucket).
@@ -4881,3 +5167,72 @@ impl TwitchService {
         Ok(())
     }
 }
+
+/// A GQL `Stream` node (with `game` and `broadcaster`) as a `TwitchStream`.
+fn stream_from_gql_node(node: &serde_json::Value) -> Option<TwitchStream> {
+    let broadcaster = node.get("broadcaster").filter(|b| !b.is_null())?;
+    let str_at = |v: &serde_json::Value, key: &str| -> String {
+        v.get(key)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let user_login = str_at(broadcaster, "login");
+    let user_name = Some(str_at(broadcaster, "displayName"))
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| user_login.clone());
+    let role = |key: &str| {
+        broadcaster
+            .pointer(&format!("/roles/{}", key))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    };
+    let broadcaster_type = if role("isPartner") {
+        Some("partner".to_string())
+    } else if role("isAffiliate") {
+        Some("affiliate".to_string())
+    } else {
+        None
+    };
+    let tags: Vec<String> = node
+        .get("freeformTags")
+        .and_then(|t| t.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|t| t.get("name").and_then(|n| n.as_str()).map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let game = node.get("game").cloned().unwrap_or_default();
+
+    Some(TwitchStream {
+        id: str_at(node, "id"),
+        user_id: str_at(broadcaster, "id"),
+        user_name,
+        user_login,
+        title: str_at(node, "title"),
+        viewer_count: node
+            .get("viewersCount")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0) as u32,
+        game_id: str_at(&game, "id"),
+        game_name: str_at(&game, "name"),
+        thumbnail_url: str_at(node, "previewImageURL"),
+        started_at: str_at(node, "createdAt"),
+        broadcaster_type,
+        has_shared_chat: None,
+        profile_image_url: broadcaster
+            .get("profileImageURL")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        is_live: Some(true),
+        tags: if tags.is_empty() { None } else { Some(tags) },
+        // GQL spells it as an uppercase enum ("EN"); Helix and the rest of the
+        // app use the lowercase code.
+        language: broadcaster
+            .pointer("/broadcastSettings/language")
+            .and_then(|v| v.as_str())
+            .filter(|lang| !lang.is_empty())
+            .map(str::to_lowercase),
+    })
+}
@@ -4,12 +4,12 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::io::Cursor;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Mutex as StdMutex;
 use std::sync::RwLock as StdRwLock;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::Mutex as TokioMutex;
 
 use crate::services::cache_service::get_cache_dir;
@@ -23,6 +23,15 @@ static ASYNC_MANIFEST_LOCK: Lazy<TokioMutex<()>> = Lazy::new(|| TokioMutex::new(
 // Flag to prevent concurrent downloads
 static DOWNLOAD_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
+// Writers putting files into the cache folders before their manifest
+// entries are saved (see FileWrites). Until then the files look orphaned, so
+// verify_cache_integrity won't repair while any are running.
+static FILE_WRITERS: AtomicUsize = AtomicUsize::new(0);
+
+// Set when the manifest on disk couldn't be parsed at startup and was set
+// aside; reported by verify_cache_integrity.
+static MANIFEST_RECOVERED: AtomicBool = AtomicBool::new(false);
+
 // One shared HTTP client for ALL cache-file downloads (emotes + badges).
 // Reusing a single client keeps its connection pool warm, so concurrent
 // downloads to the same CDN host multiplex over one HTTP/2 connection instead
@@ -124,6 +133,8 @@ pub enum CacheType {
     #[serde(rename = "thirdpartybadge")]
     ThirdPartyBadge,
     Cosmetic,
+    /// Drop campaign art and reward images, prefetched from the inventory.
+    DropReward,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -213,6 +224,7 @@ fn load_manifest_from_disk() -> Result<UniversalCacheManifest> {
             );
             let backup_path = manifest_path.with_extension("json.backup");
             let _ = fs::rename(&manifest_path, &backup_path);
+            MANIFEST_RECOVERED.store(true, Ordering::Release);
             debug!(
                 "[UniversalCache] Backed up corrupted manifest to {:?}",
                 backup_path
@@ -227,13 +239,48 @@ fn load_manifest_from_disk() -> Result<UniversalCacheManifest> {
     }
 }
 
+/// Suffix of a write in progress; see `write_atomic`.
+const TEMP_SUFFIX: &str = ".tmp";
+
+/// Write `bytes` to `path` so that a kill mid-write leaves either the old
+/// file or the new one, never a torn one: write a sibling temp file, flush it
+/// to disk, then rename it over `path`. A leftover temp file is all a crash
+/// can leave behind, and `verify_cache_integrity` clears those.
+pub(crate) fn write_atomic(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(TEMP_SUFFIX);
+    let temp = PathBuf::from(temp);
+    let mut file = fs::File::create(&temp)?;
+    file.write_all(bytes)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp, path)
+}
+
+/// Held while files are written into the cache folders ahead of their
+/// manifest entries: a single download, the emote prefetch, a bundle import.
+pub(crate) struct FileWrites(());
+
+impl FileWrites {
+    pub(crate) fn begin() -> Self {
+        FILE_WRITERS.fetch_add(1, Ordering::SeqCst);
+        FileWrites(())
+    }
+}
+
+impl Drop for FileWrites {
+    fn drop(&mut self) {
+        FILE_WRITERS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 /// Write the manifest to disk. Private; callers should use `save_manifest()`
 /// which updates the in-memory mirror and schedules a debounced flush. This
 /// is only invoked by the background flush task.
 fn save_manifest_to_disk(manifest: &UniversalCacheManifest) -> Result<()> {
     let manifest_path = get_universal_cache_dir()?.join("manifest.json");
     let json = serde_json::to_string_pretty(manifest)?;
-    fs::write(&manifest_path, json).context("Failed to write manifest file")?;
+    write_atomic(&manifest_path, json.as_bytes()).context("Failed to write manifest file")?;
     Ok(())
 }
 
@@ -274,6 +321,7 @@ pub async fn fetch_universal_cache_data(
         CacheType::Emote => "emotes",
         CacheType::ThirdPartyBadge => "third-party-badges",
         CacheType::Cosmetic => "cosmetics",
+        CacheType::DropReward => "drop-rewards",
     };
 
     // Try to fetch from universal cache repository
@@ -710,6 +758,7 @@ pub async fn sync_universal_cache(item_types: Vec<CacheType>) -> Result<usize> {
             CacheType::Emote => "emotes",
             CacheType::ThirdPartyBadge => "third-party-badges",
             CacheType::Cosmetic => "cosmetics",
+            CacheType::DropReward => "drop-rewards",
         };
 
         let index_url = format!("{}/{}/index.json", UNIVERSAL_CACHE_URL, type_str);
@@ -807,6 +856,7 @@ pub fn get_universal_cache_stats() -> Result<UniversalCacheStats> {
             CacheType::Emote => "emotes",
             CacheType::ThirdPartyBadge => "third-party-badges",
             CacheType::Cosmetic => "cosmetics",
+            CacheType::DropReward => "drop-rewards",
         };
 
         *entries_by_type.entry(type_str.to_string()).or_insert(0) += 1;
@@ -1150,12 +1200,14 @@ pub async fn cache_file(
     url: String,
     expiry_days: u32,
 ) -> Result<String> {
+    let _writes = FileWrites::begin();
     let cache_dir = get_universal_cache_dir()?;
     let type_str = match cache_type {
         CacheType::Badge => "badges",
         CacheType::Emote => "emotes",
         CacheType::ThirdPartyBadge => "third-party-badges",
         CacheType::Cosmetic => "cosmetics",
+        CacheType::DropReward => "drop-rewards",
     };
 
     let type_dir = cache_dir.join(type_str);
@@ -1194,13 +1246,9 @@ pub async fn cache_file(
     // and a sync write per file on the runtime saturates the worker threads
     // (the join-time freeze). The HTTP fetch above is already async.
     let write_path = file_path.clone();
-    tokio::task::spawn_blocking(move || -> std::io::Result<()> {
-        let mut file = fs::File::create(&write_path)?;
-        std::io::copy(&mut Cursor::new(bytes), &mut file)?;
-        Ok(())
-    })
-    .await
-    .context("cache_file write task panicked")??;
+    tokio::task::spawn_blocking(move || write_atomic(&write_path, &bytes))
+        .await
+        .context("cache_file write task panicked")??;
 
     let path_str = file_path.to_string_lossy().to_string();
     let manifest_id = format!("file:{}", id);
@@ -1244,6 +1292,7 @@ pub async fn download_file_to_disk(
         CacheType::Emote => "emotes",
         CacheType::ThirdPartyBadge => "third-party-badges",
         CacheType::Cosmetic => "cosmetics",
+        CacheType::DropReward => "drop-rewards",
     };
 
     let type_dir = cache_dir.join(type_str);
@@ -1276,13 +1325,9 @@ pub async fn download_file_to_disk(
     // and a sync write per file on the runtime saturates the worker threads
     // (the join-time freeze). The HTTP fetch above is already async.
     let write_path = file_path.clone();
-    tokio::task::spawn_blocking(move || -> std::io::Result<()> {
-        let mut file = fs::File::create(&write_path)?;
-        std::io::copy(&mut Cursor::new(bytes), &mut file)?;
-        Ok(())
-    })
-    .await
-    .context("cache_file write task panicked")??;
+    tokio::task::spawn_blocking(move || write_atomic(&write_path, &bytes))
+        .await
+        .context("cache_file write task panicked")??;
 
     let path_str = file_path.to_string_lossy().to_string();
     let manifest_id = format!("file:{}", id);
@@ -1461,3 +1506,207 @@ pub async fn auto_sync_if_stale() -> Result<bool> {
         Ok(false)
     }
 }
+
+/// What `verify_cache_integrity` found, and whether it was fixed.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct CacheIntegrityReport {
+    pub entries_checked: usize,
+    /// File entries whose file is gone or empty; repair drops the entry so
+    /// the asset is fetched again.
+    pub broken_entries: Vec<String>,
+    /// Files in the cache folders that no entry points at; repair deletes them.
+    pub orphaned_files: Vec<String>,
+    /// Temp files left by an interrupted write; repair deletes them.
+    pub leftover_temp_files: Vec<String>,
+    /// The manifest was unreadable at startup and was replaced by an empty
+    /// one (the old file is kept as manifest.json.backup).
+    pub manifest_recovered: bool,
+    pub repaired: bool,
+}
+
+/// The folders `cache_file` writes into.
+pub(crate) const FILE_DIRS: &[&str] = &[
+    "badges",
+    "emotes",
+    "third-party-badges",
+    "cosmetics",
+    "drop-rewards",
+];
+
+/// Compare the manifest's file entries with the files on disk. `file_size`
+/// is None for a file that doesn't exist.
+fn find_inconsistencies(
+    manifest: &UniversalCacheManifest,
+    files_on_disk: &[PathBuf],
+    file_size: impl Fn(&Path) -> Option<u64>,
+) -> CacheIntegrityReport {
+    let mut report = CacheIntegrityReport {
+        entries_checked: manifest.entries.len(),
+        ..Default::default()
+    };
+    let mut referenced = std::collections::HashSet::new();
+    for (key, entry) in &manifest.entries {
+        let Some(path) = entry.data.get("local_path").and_then(|p| p.as_str()) else {
+            continue;
+        };
+        let path = PathBuf::from(path);
+        if !matches!(file_size(&path), Some(size) if size > 0) {
+            report.broken_entries.push(key.clone());
+        }
+        referenced.insert(path);
+    }
+    for file in files_on_disk {
+        let name = file.to_string_lossy().to_string();
+        if name.ends_with(TEMP_SUFFIX) {
+            report.leftover_temp_files.push(name);
+        } else if !referenced.contains(file) {
+            report.orphaned_files.push(name);
+        }
+    }
+    report.broken_entries.sort();
+    report.orphaned_files.sort();
+    report.leftover_temp_files.sort();
+    report
+}
+
+/// A temp file younger than this may still be being written.
+const TEMP_FILE_GRACE: Duration = Duration::from_secs(600);
+
+/// Whether `path` is a temp file a write may still be using.
+fn fresh_temp_file(path: &Path) -> bool {
+    path.to_string_lossy().ends_with(TEMP_SUFFIX)
+        && fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|age| age < TEMP_FILE_GRACE)
+}
+
+/// Check the manifest against the cache folders and, with `repair`, fix what
+/// doesn't match: drop entries for missing or empty files, delete files no
+/// entry uses and temp files from interrupted writes. Temp files a write may
+/// still be using are left out, and repair refuses to run while a download or
+/// the emote prefetch is writing files.
+pub async fn verify_cache_integrity(repair: bool) -> Result<CacheIntegrityReport> {
+    if repair
+        && (DOWNLOAD_IN_PROGRESS.load(Ordering::SeqCst) || FILE_WRITERS.load(Ordering::SeqCst) > 0)
+    {
+        anyhow::bail!("Emotes or badges are downloading; try the repair again when they finish");
+    }
+    let _async_lock = ASYNC_MANIFEST_LOCK.lock().await;
+    tokio::task::spawn_blocking(move || check_cache_integrity(repair))
+        .await
+        .context("Cache integrity task panicked")?
+}
+
+fn check_cache_integrity(repair: bool) -> Result<CacheIntegrityReport> {
+    let _lock = MANIFEST_LOCK.lock().unwrap();
+    let cache_dir = get_universal_cache_dir()?;
+    let mut files_on_disk = Vec::new();
+    for dir in FILE_DIRS {
+        let Ok(entries) = fs::read_dir(cache_dir.join(dir)) else {
+            continue;
+        };
+        files_on_disk.extend(
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && !fresh_temp_file(p)),
+        );
+    }
+    let manifest_temp = cache_dir.join(format!("manifest.json{}", TEMP_SUFFIX));
+    if manifest_temp.exists() && !fresh_temp_file(&manifest_temp) {
+        files_on_disk.push(manifest_temp);
+    }
+
+    let mut manifest = load_manifest()?;
+    let mut report = find_inconsistencies(&manifest, &files_on_disk, |path| {
+        fs::metadata(path).ok().map(|m| m.len())
+    });
+    report.manifest_recovered = MANIFEST_RECOVERED.load(Ordering::Acquire);
+    if !repair {
+        return Ok(report);
+    }
+
+    for key in &report.broken_entries {
+        if let Some(path) = manifest
+            .entries
+            .remove(key)
+            .and_then(|e| e.data.get("local_path")?.as_str().map(PathBuf::from))
+        {
+            let _ = fs::remove_file(path);
+        }
+    }
+    for file in report
+        .orphaned_files
+        .iter()
+        .chain(&report.leftover_temp_files)
+    {
+        if let Err(e) = fs::remove_file(file) {
+            debug!("[UniversalCache] Couldn't remove {}: {}", file, e);
+        }
+    }
+    if !report.broken_entries.is_empty() {
+        save_manifest(&manifest)?;
+    }
+    MANIFEST_RECOVERED.store(false, Ordering::Release);
+    report.repaired = true;
+    debug!(
+        "[UniversalCache] Repaired: {} broken entries, {} orphaned files, {} temp files",
+        report.broken_entries.len(),
+        report.orphaned_files.len(),
+        report.leftover_temp_files.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_entry(path: &str) -> UniversalCacheEntry {
+        UniversalCacheEntry {
+            id: String::new(),
+            cache_type: CacheType::Emote,
+            data: serde_json::json!({ "local_path": path }),
+            metadata: CacheMetadata {
+                timestamp: 0,
+                expiry_days: 0,
+                source: String::new(),
+                version: CACHE_VERSION,
+            },
+            position: None,
+        }
+    }
+
+    #[test]
+    fn finds_broken_entries_orphans_and_torn_writes() {
+        let mut manifest = UniversalCacheManifest::default();
+        for (key, path) in [
+            ("file:ok", "/c/emotes/ok.png"),
+            ("file:gone", "/c/emotes/gone.png"),
+            ("file:empty", "/c/emotes/empty.png"),
+        ] {
+            manifest.entries.insert(key.to_string(), file_entry(path));
+        }
+        let on_disk: Vec<PathBuf> = [
+            "/c/emotes/ok.png",
+            "/c/emotes/empty.png",
+            "/c/emotes/stray.webp",
+            "/c/emotes/new.png.tmp",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        let report =
+            find_inconsistencies(&manifest, &on_disk, |path| match path.to_str().unwrap() {
+                "/c/emotes/ok.png" => Some(512),
+                "/c/emotes/empty.png" => Some(0),
+                _ => None,
+            });
+        assert_eq!(report.entries_checked, 3);
+        assert_eq!(report.broken_entries, ["file:empty", "file:gone"]);
+        assert_eq!(report.orphaned_files, ["/c/emotes/stray.webp"]);
+        assert_eq!(report.leftover_temp_files, ["/c/emotes/new.png.tmp"]);
+    }
+}
@@ -0,0 +1,174 @@
+//! Rolling viewer-count samples per channel, fed by every followed-streams
+//! status check. Channel pickers read the average and stability instead of one
+//! instantaneous count: a channel whose audience is steady or growing is less
+//! likely to end its stream soon than one that's draining.
+//!
+//! In-memory only. A few hours of samples is all the scoring needs, and a
+//! fresh session rebuilds them within a couple of status checks. A channel
+//! drops out once its newest sample is `MAX_SAMPLE_AGE_HOURS` old, i.e. it
+//! stopped showing up live, so the map only holds channels seen recently.
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock as StdRwLock;
+
+/// Samples kept per channel.
+const MAX_SAMPLES: usize = 48;
+/// Checks closer together than this overwrite the newest sample instead of
+/// adding one, so a burst of refreshes can't crowd out the history.
+const MIN_SAMPLE_SPACING_SECS: i64 = 120;
+/// Older samples most likely belong to a previous broadcast.
+const MAX_SAMPLE_AGE_HOURS: i64 = 12;
+
+static HISTORY: Lazy<StdRwLock<HashMap<String, VecDeque<ViewerSample>>>> =
+    Lazy::new(|| StdRwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ViewerSample {
+    pub at: DateTime<Utc>,
+    pub viewers: u32,
+}
+
+/// Summary of a channel's recent audience.
+#[derive(Debug, Clone, Serialize)]
+pub struct ViewerCountSummary {
+    pub channel_id: String,
+    pub latest: u32,
+    pub average: f64,
+    /// 0..1; 1 is a flat audience, 0 swings as wide as the average itself
+    /// (1 minus the coefficient of variation, clamped).
+    pub stability: f64,
+    /// Change from the oldest to the newest sample as a fraction of the
+    /// average. Negative means the audience is draining.
+    pub trend: f64,
+    pub samples: Vec<ViewerSample>,
+}
+
+/// Record an observed viewer count.
+pub fn record(channel_id: &str, viewers: u32) {
+    record_at(channel_id, viewers, Utc::now());
+}
+
+fn record_at(channel_id: &str, viewers: u32, at: DateTime<Utc>) {
+    let Ok(mut history) = HISTORY.write() else {
+        return;
+    };
+    let cutoff = at - Duration::hours(MAX_SAMPLE_AGE_HOURS);
+    history.retain(|_, samples| samples.back().is_some_and(|s| s.at >= cutoff));
+    let samples = history.entry(channel_id.to_string()).or_default();
+
+    while samples.front().is_some_and(|s| s.at < cutoff) {
+        samples.pop_front();
+    }
+
+    let sample = ViewerSample { at, viewers };
+    match samples.back_mut() {
+        Some(last) if (at - last.at).num_seconds() < MIN_SAMPLE_SPACING_SECS => *last = sample,
+        _ => samples.push_back(sample),
+    }
+    while samples.len() > MAX_SAMPLES {
+        samples.pop_front();
+    }
+}
+
+/// Summary for one channel, or `None` if it hasn't been seen live recently.
+pub fn summary(channel_id: &str) -> Option<ViewerCountSummary> {
+    let history = HISTORY.read().ok()?;
+    let cutoff = Utc::now() - Duration::hours(MAX_SAMPLE_AGE_HOURS);
+    let samples: Vec<ViewerSample> = history
+        .get(channel_id)?
+        .iter()
+        .filter(|s| s.at >= cutoff)
+        .copied()
+        .collect();
+    summarize(channel_id, samples)
+}
+
+fn summarize(channel_id: &str, samples: Vec<ViewerSample>) -> Option<ViewerCountSummary> {
+    let first = samples.first()?.viewers as f64;
+    let latest = samples.last()?.viewers;
+
+    let n = samples.len() as f64;
+    let average = samples.iter().map(|s| s.viewers as f64).sum::<f64>() / n;
+    let (stability, trend) = if average > 0.0 {
+        let variance = samples
+            .iter()
+            .map(|s| (s.viewers as f64 - average).powi(2))
+            .sum::<f64>()
+            / n;
+        let cv = variance.sqrt() / average;
+        (
+            (1.0 - cv).clamp(0.0, 1.0),
+            (latest as f64 - first) / average,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+
+    Some(ViewerCountSummary {
+        channel_id: channel_id.to_string(),
+        latest,
+        average,
+        stability,
+        trend,
+        samples,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(counts: &[u32]) -> Vec<ViewerSample> {
+        let start = Utc::now();
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, &viewers)| ViewerSample {
+                at: start + Duration::minutes(5 * i as i64),
+                viewers,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flat_audience_is_fully_stable() {
+        let s = summarize("a", samples(&[500, 500, 500])).unwrap();
+        assert_eq!(s.average, 500.0);
+        assert_eq!(s.stability, 1.0);
+        assert_eq!(s.trend, 0.0);
+    }
+
+    #[test]
+    fn draining_audience_has_negative_trend() {
+        let s = summarize("a", samples(&[1000, 700, 400])).unwrap();
+        assert!(s.trend < -0.5);
+        assert!(s.stability < 1.0);
+    }
+
+    #[test]
+    fn close_checks_overwrite_the_newest_sample() {
+        let t = Utc::now();
+        record_at("spacing-test", 10, t);
+        record_at("spacing-test", 20, t + Duration::seconds(30));
+        record_at(
+            "spacing-test",
+            30,
+            t + Duration::seconds(MIN_SAMPLE_SPACING_SECS + 30),
+        );
+        let s = summary("spacing-test").unwrap();
+        let counts: Vec<u32> = s.samples.iter().map(|s| s.viewers).collect();
+        assert_eq!(counts, vec![20, 30]);
+    }
+
+    #[test]
+    fn channels_not_seen_live_for_the_max_age_are_dropped() {
+        let t = Utc::now() - Duration::hours(MAX_SAMPLE_AGE_HOURS + 1);
+        record_at("offline-test", 10, t);
+        record_at("online-test", 10, Utc::now());
+        assert!(!HISTORY.read().unwrap().contains_key("offline-test"));
+        assert!(summary("online-test").is_some());
+    }
+}
@@ -33,6 +33,8 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
 use crate::services::drops_auth_service::DropsAuthService;
+use crate::services::feature_flags::{self, Feature};
+use crate::services::twitch_api::{send_operation, GqlOperation};
 
 const CLIENT_ID: &str = env!("TWITCH_ANDROID_CLIENT_ID");
 
@@ -44,6 +46,31 @@ const SPADE_URL: &str = "https://spade.twitch.tv/track";
 /// restart get a new broadcast id; the official player re-learns it too.
 const BROADCAST_REFRESH: Duration = Duration::from_secs(900);
 
+const GET_STREAM_INFO: GqlOperation = GqlOperation {
+    name: "GetStreamInfo",
+    hash: None,
+    query: Some(
+        r#"
+        query GetStreamInfo($channelID: ID!) {
+            user(id: $channelID) {
+                stream {
+                    id
+                    game { id name }
+                }
+            }
+        }
+        "#,
+    ),
+};
+
+const SEND_EVENTS: GqlOperation = GqlOperation {
+    name: "SendEvents",
+    hash: None,
+    query: Some(
+        "\n mutation SendEvents($input: SendSpadeEventsInput!) {\n sendSpadeEvents(input: $input) {\n statusCode\n}\n}\n",
+    ),
+};
+
 #[derive(Clone)]
 struct WatchTarget {
     channel_id: String,
@@ -175,35 +202,71 @@ impl WatchHeartbeatService {
         let Some(broadcast_id) = target.broadcast_id.clone() else {
             return;
         };
+        if !feature_flags::enabled(Feature::SpadePayloads) {
+            return;
+        }
+        // A send that never reached Twitch says nothing about the endpoints.
+        let mut transport_error = false;
 
-        match self
+        let drops_ok = match self
             .send_minute_watched(&target, &broadcast_id, &token)
             .await
         {
-            Ok(true) => debug!(
-                "[Heartbeat] minute-watched credited for {} ({})",
-                target.login, target.channel_id
-            ),
-            Ok(false) => debug!(
-                "[Heartbeat] minute-watched not credited for {}",
-                target.login
-            ),
-            Err(e) => warn!("[Heartbeat] send failed for {}: {e}", target.login),
-        }
+            Ok(true) => {
+                debug!(
+                    "[Heartbeat] minute-watched credited for {} ({})",
+                    target.login, target.channel_id
+                );
+                true
+            }
+            Ok(false) => {
+                debug!(
+                    "[Heartbeat] minute-watched not credited for {}",
+                    target.login
+                );
+                false
+            }
+            Err(e) => {
+                warn!("[Heartbeat] send failed for {}: {e}", target.login);
+                transport_error |= feature_flags::is_transport_error(&e);
+                false
+            }
+        };
 
-        match self
+        let points_ok = match self
             .send_minute_watched_legacy(&target, &broadcast_id, &token)
             .await
         {
-            Ok(true) => debug!(
-                "[Heartbeat] points minute-watched accepted for {} ({})",
-                target.login, target.channel_id
-            ),
-            Ok(false) => debug!(
-                "[Heartbeat] points minute-watched rejected for {}",
-                target.login
-            ),
-            Err(e) => warn!("[Heartbeat] points send failed for {}: {e}", target.login),
+            Ok(true) => {
+                debug!(
+                    "[Heartbeat] points minute-watched accepted for {} ({})",
+                    target.login, target.channel_id
+                );
+                true
+            }
+            Ok(false) => {
+                debug!(
+                    "[Heartbeat] points minute-watched rejected for {}",
+                    target.login
+                );
+                false
+            }
+            Err(e) => {
+                warn!("[Heartbeat] points send failed for {}: {e}", target.login);
+                transport_error |= feature_flags::is_transport_error(&e);
+                false
+            }
+        };
+
+        // Both paths refusing the same minute means the endpoints changed,
+        // not the channel.
+        if drops_ok || points_ok {
+            feature_flags::record_success(Feature::SpadePayloads);
+        } else if !transport_error {
+            feature_flags::record_failure(
+                Feature::SpadePayloads,
+                "minute-watched rejected on both transports",
+            );
         }
     }
 
@@ -214,26 +277,19 @@ impl WatchHeartbeatService {
         channel_id: &str,
         token: &str,
     ) -> Result<Option<(String, String, String)>> {
-        let query = r#"
-        query GetStreamInfo($channelID: ID!) {
-            user(id: $channelID) {
-                stream {
-                    id
-                    game { id name }
-                }
-            }
-        }
-        "#;
-        let response = self
-            .client
-            .post("https://gql.twitch.tv/gql")
-            .header("Client-Id", CLIENT_ID)
-            .header("Authorization", format!("Bearer {token}"))
-            .json(&json!({ "query": query, "variables": { "channelID": channel_id } }))
-            .timeout(Duration::from_secs(10))
-            .send()
-            .await?;
-        let body: serde_json::Value = response.json().await?;
+        let (_, text) = send_operation(
+            || {
+                self.client
+                    .post("https://gql.twitch.tv/gql")
+                    .header("Client-Id", CLIENT_ID)
+                    .header("Authorization", format!("Bearer {token}"))
+                    .timeout(Duration::from_secs(10))
+            },
+            &GET_STREAM_INFO,
+            json!({ "channelID": channel_id }),
+        )
+        .await?;
+        let body: serde_json::Value = serde_json::from_str(&text)?;
         let stream = &body["data"]["user"]["stream"];
         let Some(id) = stream["id"].as_str() else {
             return Ok(None);
@@ -345,12 +401,9 @@ impl WatchHeartbeatService {
         let mut gz = GzEncoder::new(Vec::new(), Compression::default());
         gz.write_all(minified.as_bytes())?;
         let g64 = general_purpose::STANDARD.encode(gz.finish()?);
-        let mutation = json!({
-            "query": "\n mutation SendEvents($input: SendSpadeEventsInput!) {\n sendSpadeEvents(input: $input) {\n statusCode\n}\n}\n",
-            "variables": {
-                "input": { "data": g64, "repository": "twilight", "encoding": "GZIP_B64" }
-            }
-        });
+        let mutation = SEND_EVENTS.body(json!({
+            "input": { "data": g64, "repository": "twilight", "encoding": "GZIP_B64" }
+        }));
         let gql_ok = match Self::send_once_retrying(|| {
             self.client
                 .post("https://gql.twitch.tv/gql")
@@ -0,0 +1,321 @@
+//! Local watch history: which live channels were watched, for how long, and
+//! in which categories. Fed by the watched-stream poller
+//! (`stream_info_poller`), one tick per 30s of live playback, so time spent
+//! with the stream stopped or on a VOD isn't counted.
+//!
+//! Also keeps the playback position of partly watched VODs, reported by the
+//! player, for the continue-watching rail.
+//!
+//! Persisted to `watch_history.json` in the app data dir. Used to personalize
+//! recommendations.
+
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock as StdRwLock;
+
+const HISTORY_FILE: &str = "watch_history.json";
+/// Channels remembered; the least recently watched are dropped past this.
+const MAX_CHANNELS: usize = 200;
+/// Minimum gap between disk writes while only watch time is moving.
+const SAVE_INTERVAL_SECS: i64 = 300;
+/// VOD positions remembered; the least recently updated are dropped past this.
+const MAX_VODS: usize = 50;
+/// Positions this close to the start aren't worth resuming.
+const VOD_MIN_POSITION_SECS: u64 = 60;
+/// Positions this close to the end count as finished and are dropped.
+const VOD_FINISHED_MARGIN_SECS: u64 = 120;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchedChannel {
+    pub user_id: String,
+    pub login: String,
+    pub display_name: String,
+    /// Category at the last tick.
+    pub last_game_name: String,
+    pub last_watched: DateTime<Utc>,
+    pub total_watch_secs: u64,
+    /// Viewer count at the last tick, for the channel-size preference.
+    #[serde(default)]
+    pub last_viewer_count: u32,
+    /// The part of `total_watch_secs` the user was away for (see
+    /// services::idle_monitor); the rest was watched actively.
+    #[serde(default)]
+    pub idle_watch_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    channels: HashMap<String, WatchedChannel>,
+    /// Watch seconds per category, keyed by lowercase name.
+    games: HashMap<String, u64>,
+    /// Resume positions, keyed by video id.
+    #[serde(default)]
+    vods: HashMap<String, VodPosition>,
+}
+
+/// Where the user left off in a VOD.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VodPosition {
+    pub video_id: String,
+    #[serde(default)]
+    pub user_id: String,
+    #[serde(default)]
+    pub user_login: String,
+    #[serde(default)]
+    pub user_name: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub thumbnail_url: String,
+    pub position_secs: u64,
+    /// 0 when the player didn't know the length.
+    #[serde(default)]
+    pub duration_secs: u64,
+    /// Set on save; ignored when sent by the frontend.
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct HistoryStore {
+    data: HistoryFile,
+    last_saved: Option<DateTime<Utc>>,
+}
+
+static STORE: Lazy<StdRwLock<HistoryStore>> = Lazy::new(|| {
+    StdRwLock::new(HistoryStore {
+        data: load_from_disk(),
+        last_saved: None,
+    })
+});
+
+fn history_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(HISTORY_FILE))
+}
+
+fn load_from_disk() -> HistoryFile {
+    history_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(data: &HistoryFile) {
+    let Some(path) = history_path() else { return };
+    match serde_json::to_string(data) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[WatchHistory] Failed to write {}: {}", HISTORY_FILE, e);
+            }
+        }
+        Err(e) => warn!("[WatchHistory] Failed to serialize history: {}", e),
+    }
+}
+
+/// Record `secs` of live watching on a channel. `secs == 0` just marks it as
+/// watched now (the poller's first tick).
+pub fn record_watch(
+    user_id: &str,
+    login: &str,
+    display_name: &str,
+    game_name: &str,
+    viewer_count: u32,
+    secs: u64,
+) {
+    if user_id.is_empty() {
+        return;
+    }
+    let now = Utc::now();
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+
+    let is_new = !store.data.channels.contains_key(user_id);
+    let entry = store
+        .data
+        .channels
+        .entry(user_id.to_string())
+        .or_insert_with(|| WatchedChannel {
+            user_id: user_id.to_string(),
+            login: String::new(),
+            display_name: String::new(),
+            last_game_name: String::new(),
+            last_watched: now,
+            total_watch_secs: 0,
+            last_viewer_count: 0,
+            idle_watch_secs: 0,
+        });
+    entry.login = login.to_lowercase();
+    entry.display_name = display_name.to_string();
+    entry.last_game_name = game_name.to_string();
+    entry.last_watched = now;
+    entry.total_watch_secs += secs;
+    if crate::services::idle_monitor::is_idle() {
+        entry.idle_watch_secs += secs;
+    }
+    entry.last_viewer_count = viewer_count;
+    if secs > 0 && !game_name.is_empty() {
+        *store
+            .data
+            .games
+            .entry(game_name.to_lowercase())
+            .or_default() += secs;
+    }
+
+    if store.data.channels.len() > MAX_CHANNELS {
+        if let Some(oldest) = store
+            .data
+            .channels
+            .values()
+            .min_by_key(|c| c.last_watched)
+            .map(|c| c.user_id.clone())
+        {
+            store.data.channels.remove(&oldest);
+        }
+    }
+
+    let save_due = !store
+        .last_saved
+        .is_some_and(|t| (now - t).num_seconds() < SAVE_INTERVAL_SECS);
+    if is_new || save_due {
+        store.last_saved = Some(now);
+        save_to_disk(&store.data);
+    }
+}
+
+/// Channels by most recently watched.
+pub fn recent_channels(limit: usize) -> Vec<WatchedChannel> {
+    let Ok(store) = STORE.read() else {
+        return Vec::new();
+    };
+    let mut channels: Vec<WatchedChannel> = store.data.channels.values().cloned().collect();
+    channels.sort_by(|a, b| b.last_watched.cmp(&a.last_watched));
+    channels.truncate(limit);
+    channels
+}
+
+/// Store a VOD's playback position. Positions near the start are ignored and
+/// ones near the end clear the entry (the VOD was finished).
+pub fn set_vod_position(mut position: VodPosition) {
+    if position.video_id.is_empty() {
+        return;
+    }
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+    if !is_resumable(position.position_secs, position.duration_secs) {
+        if store.data.vods.remove(&position.video_id).is_some() {
+            save_to_disk(&store.data);
+        }
+        return;
+    }
+
+    position.updated_at = Utc::now();
+    store.data.vods.insert(position.video_id.clone(), position);
+    if store.data.vods.len() > MAX_VODS {
+        if let Some(oldest) = store
+            .data
+            .vods
+            .values()
+            .min_by_key(|v| v.updated_at)
+            .map(|v| v.video_id.clone())
+        {
+            store.data.vods.remove(&oldest);
+        }
+    }
+    // The player only reports every 15s, so no throttle here.
+    store.last_saved = Some(Utc::now());
+    save_to_disk(&store.data);
+}
+
+fn is_resumable(position_secs: u64, duration_secs: u64) -> bool {
+    let finished = duration_secs > 0 && position_secs + VOD_FINISHED_MARGIN_SECS >= duration_secs;
+    !finished && position_secs >= VOD_MIN_POSITION_SECS
+}
+
+/// VODs with a resume position, most recently watched first.
+pub fn vod_positions(limit: usize) -> Vec<VodPosition> {
+    let Ok(store) = STORE.read() else {
+        return Vec::new();
+    };
+    let mut vods: Vec<VodPosition> = store.data.vods.values().cloned().collect();
+    vods.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    vods.truncate(limit);
+    vods
+}
+
+/// Share of watch time per category (lowercase name -> 0..1).
+pub fn game_affinity() -> HashMap<String, f64> {
+    let Ok(store) = STORE.read() else {
+        return HashMap::new();
+    };
+    let total: u64 = store.data.games.values().sum();
+    if total == 0 {
+        return HashMap::new();
+    }
+    store
+        .data
+        .games
+        .iter()
+        .map(|(game, secs)| (game.clone(), *secs as f64 / total as f64))
+        .collect()
+}
+
+/// Median viewer count across watched channels, weighted by watch time:
+/// the channel size the user tends to watch. `None` without history.
+pub fn preferred_channel_size() -> Option<u32> {
+    let store = STORE.read().ok()?;
+    let mut sized: Vec<(u32, u64)> = store
+        .data
+        .channels
+        .values()
+        .filter(|c| c.last_viewer_count > 0 && c.total_watch_secs > 0)
+        .map(|c| (c.last_viewer_count, c.total_watch_secs))
+        .collect();
+    weighted_median(&mut sized)
+}
+
+fn weighted_median(values: &mut [(u32, u64)]) -> Option<u32> {
+    let total: u64 = values.iter().map(|(_, w)| w).sum();
+    if total == 0 {
+        return None;
+    }
+    values.sort_by_key(|(v, _)| *v);
+    let mut seen = 0;
+    for (value, weight) in values.iter() {
+        seen += weight;
+        if seen * 2 >= total {
+            return Some(*value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_median_follows_watch_time() {
+        assert_eq!(weighted_median(&mut []), None);
+        // Mostly watched a 300-viewer channel, briefly a huge one.
+        assert_eq!(
+            weighted_median(&mut [(50_000, 60), (300, 3_600), (20, 600)]),
+            Some(300)
+        );
+    }
+
+    #[test]
+    fn only_partly_watched_vods_are_resumable() {
+        assert!(!is_resumable(10, 3_600));
+        assert!(is_resumable(1_200, 3_600));
+        assert!(!is_resumable(3_550, 3_600));
+        // Unknown length: never counts as finished.
+        assert!(is_resumable(90_000, 0));
+    }
+}
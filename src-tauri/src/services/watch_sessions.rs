@@ -0,0 +1,548 @@
+//! Watch sessions and the bookmarks taken during them, exported as a list of
+//! highlights (`export_session_highlights`).
+//!
+//! A session is one broadcast watched live: the watched-stream poller calls
+//! `observe` every tick, which opens a session the first time a broadcast is
+//! seen and otherwise moves its end forward. A bookmark is a note pinned to
+//! the moment it was taken, kept as an offset into the broadcast so it can be
+//! turned into a VOD link once the archive shows up.
+//!
+//! Every change of category or title during a session starts a chapter. The
+//! chapter list is written next to a recording of the broadcast as
+//! `.ffmetadata` (for ffmpeg to mux in) and Matroska `.chapters.xml`
+//! (`write_recording_chapters`), so recorded files open with chapters in
+//! media players.
+//!
+//! Persisted to `watch_sessions.json` in the app data dir.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use log::warn;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock as StdRwLock;
+
+const SESSIONS_FILE: &str = "watch_sessions.json";
+/// Sessions remembered; the oldest are dropped past this.
+const MAX_SESSIONS: usize = 100;
+/// Minimum gap between disk writes while only a session's end is moving.
+const SAVE_INTERVAL_SECS: i64 = 300;
+/// A session is current while its last tick is this recent (the poller ticks
+/// every 30s).
+const CURRENT_WITHIN_SECS: i64 = 90;
+/// How far an archive's creation may be from the broadcast start and still be
+/// taken as its VOD.
+const VOD_MATCH_SECS: i64 = 600;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub created_at: DateTime<Utc>,
+    /// Seconds into the broadcast; None when its start wasn't known.
+    pub offset_secs: Option<u64>,
+    pub note: String,
+    pub game_name: String,
+}
+
+/// A stretch of the broadcast under one category and title.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub started_at: DateTime<Utc>,
+    pub game_name: String,
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchSession {
+    /// The broadcast's stream id.
+    pub id: String,
+    pub user_id: String,
+    pub login: String,
+    pub display_name: String,
+    /// Title and category at the last tick.
+    pub title: String,
+    #[serde(default)]
+    pub game_name: String,
+    pub stream_started_at: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// The broadcast's archive, once found.
+    #[serde(default)]
+    pub vod_id: Option<String>,
+    /// Oldest first; the first starts with the session.
+    #[serde(default)]
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Default)]
+struct SessionStore {
+    /// Oldest first.
+    sessions: Vec<WatchSession>,
+    last_saved: Option<DateTime<Utc>>,
+}
+
+static STORE: Lazy<StdRwLock<SessionStore>> = Lazy::new(|| {
+    StdRwLock::new(SessionStore {
+        sessions: load_from_disk(),
+        last_saved: None,
+    })
+});
+
+fn sessions_path() -> Option<std::path::PathBuf> {
+    crate::services::cache_service::get_app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SESSIONS_FILE))
+}
+
+fn load_from_disk() -> Vec<WatchSession> {
+    sessions_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_to_disk(sessions: &[WatchSession]) {
+    let Some(path) = sessions_path() else { return };
+    match serde_json::to_string(sessions) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                warn!("[WatchSessions] Failed to write {}: {}", SESSIONS_FILE, e);
+            }
+        }
+        Err(e) => warn!("[WatchSessions] Failed to serialize sessions: {}", e),
+    }
+}
+
+/// Record a tick of live watching on `stream`.
+pub fn observe(stream: &crate::models::stream::TwitchStream) {
+    if stream.user_id.is_empty() {
+        return;
+    }
+    let now = Utc::now();
+    let id = if stream.id.is_empty() {
+        format!("{}-{}", stream.user_id, stream.started_at)
+    } else {
+        stream.id.clone()
+    };
+    let Ok(mut store) = STORE.write() else {
+        return;
+    };
+
+    let is_new = !store.sessions.iter().any(|s| s.id == id);
+    if is_new {
+        store.sessions.push(WatchSession {
+            id: id.clone(),
+            user_id: stream.user_id.clone(),
+            login: stream.user_login.to_lowercase(),
+            display_name: stream.user_name.clone(),
+            title: String::new(),
+            game_name: String::new(),
+            stream_started_at: DateTime::parse_from_rfc3339(&stream.started_at)
+                .ok()
+                .map(|t| t.with_timezone(&Utc)),
+            started_at: now,
+            last_seen_at: now,
+            bookmarks: Vec::new(),
+            vod_id: None,
+            chapters: Vec::new(),
+        });
+        if store.sessions.len() > MAX_SESSIONS {
+            let excess = store.sessions.len() - MAX_SESSIONS;
+            store.sessions.drain(..excess);
+        }
+    }
+    let mut new_chapter = false;
+    if let Some(session) = store.sessions.iter_mut().find(|s| s.id == id) {
+        session.title = stream.title.clone();
+        session.game_name = stream.game_name.clone();
+        session.last_seen_at = now;
+        let changed = session
+            .chapters
+            .last()
+            .is_none_or(|c| c.game_name != stream.game_name || c.title != stream.title);
+        if changed {
+            session.chapters.push(Chapter {
+                started_at: if session.chapters.is_empty() {
+                    session.started_at
+                } else {
+                    now
+                },
+                game_name: stream.game_name.clone(),
+                title: stream.title.clone(),
+            });
+            new_chapter = true;
+        }
+    }
+
+    let save_due = !store
+        .last_saved
+        .is_some_and(|t| (now - t).num_seconds() < SAVE_INTERVAL_SECS);
+    if is_new || new_chapter || save_due {
+        store.last_saved = Some(now);
+        save_to_disk(&store.sessions);
+    }
+}
+
+/// Bookmark the current moment of the broadcast being watched.
+pub fn add_bookmark(note: &str) -> Result<Bookmark> {
+    let now = Utc::now();
+    let mut store = STORE
+        .write()
+        .map_err(|_| anyhow!("Watch sessions unavailable"))?;
+    let session = store
+        .sessions
+        .iter_mut()
+        .rev()
+        .find(|s| (now - s.last_seen_at).num_seconds() <= CURRENT_WITHIN_SECS)
+        .ok_or_else(|| anyhow!("No live stream is being watched"))?;
+    let bookmark = Bookmark {
+        created_at: now,
+        offset_secs: session
+            .stream_started_at
+            .map(|start| (now - start).num_seconds().max(0) as u64),
+        note: note.trim().to_string(),
+        game_name: session.game_name.clone(),
+    };
+    session.bookmarks.push(bookmark.clone());
+    store.last_saved = Some(now);
+    save_to_disk(&store.sessions);
+    Ok(bookmark)
+}
+
+/// Sessions, most recent first.
+pub fn recent(limit: usize) -> Vec<WatchSession> {
+    let Ok(store) = STORE.read() else {
+        return Vec::new();
+    };
+    store.sessions.iter().rev().take(limit).cloned().collect()
+}
+
+/// `1h2m3s`, the form Twitch's `?t=` takes.
+fn vod_timestamp(secs: u64) -> String {
+    format!("{}h{}m{}s", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+/// `1:02:03`.
+fn clock(secs: u64) -> String {
+    format!("{}:{:02}:{:02}", secs / 3600, secs % 3600 / 60, secs % 60)
+}
+
+fn vod_link(vod_id: &str, offset_secs: Option<u64>) -> String {
+    match offset_secs {
+        Some(secs) => format!(
+            "https://www.twitch.tv/videos/{}?t={}",
+            vod_id,
+            vod_timestamp(secs)
+        ),
+        None => format!("https://www.twitch.tv/videos/{}", vod_id),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn render_markdown(session: &WatchSession) -> String {
+    let mut out = format!("## {}: {}\n\n", session.display_name, session.title);
+    out.push_str(&format!(
+        "Watched {} to {} UTC\n",
+        session.started_at.format("%Y-%m-%d %H:%M"),
+        session.last_seen_at.format("%H:%M")
+    ));
+    if let Some(vod_id) = &session.vod_id {
+        out.push_str(&format!("VOD: {}\n", vod_link(vod_id, None)));
+    }
+    out.push('\n');
+    for bookmark in &session.bookmarks {
+        let time = match bookmark.offset_secs {
+            Some(secs) => clock(secs),
+            None => format!("{} UTC", bookmark.created_at.format("%H:%M:%S")),
+        };
+        let note = if bookmark.note.is_empty() {
+            "(no note)"
+        } else {
+            &bookmark.note
+        };
+        out.push_str(&format!("- **{}** {}", time, note));
+        if let Some(vod_id) = &session.vod_id {
+            out.push_str(&format!(
+                " ([VOD]({}))",
+                vod_link(vod_id, bookmark.offset_secs)
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn render_csv(session: &WatchSession) -> String {
+    let mut out = String::from("stream_time,taken_at,category,note,vod_link\n");
+    for bookmark in &session.bookmarks {
+        let link = session
+            .vod_id
+            .as_ref()
+            .map(|id| vod_link(id, bookmark.offset_secs))
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{},{},{},{},{}\n",
+            bookmark.offset_secs.map(clock).unwrap_or_default(),
+            bookmark.created_at.to_rfc3339(),
+            csv_field(&bookmark.game_name),
+            csv_field(&bookmark.note),
+            link
+        ));
+    }
+    out
+}
+
+/// The chapters as (start, end, chapter) in milliseconds from `base`, the
+/// moment the recording started. Chapters that ended before it are dropped
+/// and one running at the time starts at 0.
+fn chapter_spans(session: &WatchSession, base: DateTime<Utc>) -> Vec<(i64, i64, &Chapter)> {
+    let end_of_session = (session.last_seen_at - base).num_milliseconds();
+    let starts: Vec<i64> = session
+        .chapters
+        .iter()
+        .map(|c| (c.started_at - base).num_milliseconds().max(0))
+        .collect();
+    session
+        .chapters
+        .iter()
+        .enumerate()
+        .map(|(i, chapter)| {
+            let end = starts.get(i + 1).copied().unwrap_or(end_of_session);
+            (starts[i], end, chapter)
+        })
+        .filter(|(start, end, _)| end > start)
+        .collect()
+}
+
+fn chapter_name(chapter: &Chapter) -> String {
+    match (chapter.game_name.is_empty(), chapter.title.is_empty()) {
+        (false, false) => format!("{} - {}", chapter.game_name, chapter.title),
+        (false, true) => chapter.game_name.clone(),
+        _ => chapter.title.clone(),
+    }
+}
+
+/// An ffmpeg metadata file (`ffmpeg -i rec.mkv -i rec.ffmetadata
+/// -map_metadata 1 -map_chapters 1 -c copy out.mkv`).
+fn render_ffmetadata(session: &WatchSession, base: DateTime<Utc>) -> String {
+    let escape = |value: &str| {
+        value
+            .replace('\\', "\\\\")
+            .replace(['\n', '\r'], " ")
+            .replace('=', "\\=")
+            .replace(';', "\\;")
+            .replace('#', "\\#")
+    };
+    let mut out = String::from(";FFMETADATA1\n");
+    out.push_str(&format!(
+        "title={}\n",
+        escape(&format!("{}: {}", session.display_name, session.title))
+    ));
+    for (start, end, chapter) in chapter_spans(session, base) {
+        out.push_str(&format!(
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            start,
+            end,
+            escape(&chapter_name(chapter))
+        ));
+    }
+    out
+}
+
+/// `01:02:03.456`.
+fn xml_time(ms: i64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        ms / 3_600_000,
+        ms % 3_600_000 / 60_000,
+        ms % 60_000 / 1000,
+        ms % 1000
+    )
+}
+
+/// Matroska chapters XML (`mkvmerge --chapters rec.chapters.xml`).
+fn render_chapters_xml(session: &WatchSession, base: DateTime<Utc>) -> String {
+    let escape = |value: &str| {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE Chapters SYSTEM \"matroskachapters.dtd\">\n\
+         <Chapters>\n  <EditionEntry>\n",
+    );
+    for (start, end, chapter) in chapter_spans(session, base) {
+        out.push_str(&format!(
+            "    <ChapterAtom>\n      \
+             <ChapterTimeStart>{}</ChapterTimeStart>\n      \
+             <ChapterTimeEnd>{}</ChapterTimeEnd>\n      \
+             <ChapterDisplay>\n        \
+             <ChapterString>{}</ChapterString>\n        \
+             <ChapterLanguage>und</ChapterLanguage>\n      \
+             </ChapterDisplay>\n    \
+             </ChapterAtom>\n",
+            xml_time(start),
+            xml_time(end),
+            escape(&chapter_name(chapter))
+        ));
+    }
+    out.push_str("  </EditionEntry>\n</Chapters>\n");
+    out
+}
+
+fn find_session(session_id: &str) -> Result<WatchSession> {
+    STORE
+        .read()
+        .map_err(|_| anyhow!("Watch sessions unavailable"))?
+        .sessions
+        .iter()
+        .find(|s| s.id == session_id)
+        .cloned()
+        .ok_or_else(|| anyhow!("No watch session {}", session_id))
+}
+
+/// Write session `session_id`'s chapters beside the recording at
+/// `recording_path`, as `<name>.ffmetadata` and `<name>.chapters.xml`, timed
+/// from `recording_started_at` (the session's start when None). Returns the
+/// files written.
+pub fn write_recording_chapters(
+    session_id: &str,
+    recording_path: &Path,
+    recording_started_at: Option<DateTime<Utc>>,
+) -> Result<Vec<PathBuf>> {
+    let session = find_session(session_id)?;
+    if session.chapters.is_empty() {
+        bail!("Session {} has no chapters", session_id);
+    }
+    let base = recording_started_at.unwrap_or(session.started_at);
+    let ffmetadata = recording_path.with_extension("ffmetadata");
+    let xml = recording_path.with_extension("chapters.xml");
+    std::fs::write(&ffmetadata, render_ffmetadata(&session, base))?;
+    std::fs::write(&xml, render_chapters_xml(&session, base))?;
+    Ok(vec![ffmetadata, xml])
+}
+
+/// Look up the session's archive on the channel and remember it.
+async fn find_vod(session: &WatchSession) -> Option<String> {
+    let started = session.stream_started_at?;
+    let (videos, _) = crate::services::twitch_service::TwitchService::get_user_videos(
+        &session.user_id,
+        "time",
+        Some("archive"),
+        10,
+        None,
+    )
+    .await
+    .ok()?;
+    let vod = videos.into_iter().find(|v| {
+        v.stream_id.as_deref() == Some(session.id.as_str())
+            || DateTime::parse_from_rfc3339(&v.created_at).is_ok_and(|t| {
+                (t.with_timezone(&Utc) - started).num_seconds().abs() <= VOD_MATCH_SECS
+            })
+    })?;
+    if let Ok(mut store) = STORE.write() {
+        if let Some(stored) = store.sessions.iter_mut().find(|s| s.id == session.id) {
+            stored.vod_id = Some(vod.id.clone());
+            save_to_disk(&store.sessions);
+        }
+    }
+    Some(vod.id)
+}
+
+/// Session `session_id`'s bookmarks as `markdown` or `csv`, with VOD links
+/// when the broadcast's archive exists, or its chapters as `ffmetadata` or
+/// `xml`, timed from the session's start.
+pub async fn export(session_id: &str, format: &str) -> Result<String> {
+    let mut session = find_session(session_id)?;
+    match format.to_lowercase().as_str() {
+        "ffmetadata" => return Ok(render_ffmetadata(&session, session.started_at)),
+        "xml" => return Ok(render_chapters_xml(&session, session.started_at)),
+        _ => {}
+    }
+    if session.vod_id.is_none() {
+        session.vod_id = find_vod(&session).await;
+    }
+    match format.to_lowercase().as_str() {
+        "markdown" | "md" => Ok(render_markdown(&session)),
+        "csv" => Ok(render_csv(&session)),
+        other => bail!("Unknown export format '{}'", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(vod_id: Option<&str>) -> WatchSession {
+        let at = DateTime::parse_from_rfc3339("2026-10-15T18:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        WatchSession {
+            id: "42".to_string(),
+            user_id: "1".to_string(),
+            login: "alpha".to_string(),
+            display_name: "Alpha".to_string(),
+            title: "Ranked".to_string(),
+            game_name: "Valorant".to_string(),
+            stream_started_at: Some(at),
+            started_at: at,
+            last_seen_at: at + chrono::Duration::hours(1),
+            bookmarks: vec![Bookmark {
+                created_at: at,
+                offset_secs: Some(3725),
+                note: "clutch, \"1v3\"".to_string(),
+                game_name: "Valorant".to_string(),
+            }],
+            vod_id: vod_id.map(str::to_string),
+            chapters: vec![
+                Chapter {
+                    started_at: at,
+                    game_name: "Just Chatting".to_string(),
+                    title: "Ranked".to_string(),
+                },
+                Chapter {
+                    started_at: at + chrono::Duration::minutes(20),
+                    game_name: "Valorant".to_string(),
+                    title: "Ranked".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn highlights_link_into_the_vod() {
+        let markdown = render_markdown(&session(Some("99")));
+        assert!(markdown.contains(
+            "- **1:02:05** clutch, \"1v3\" ([VOD](https://www.twitch.tv/videos/99?t=1h2m5s))"
+        ));
+        let csv = render_csv(&session(None));
+        assert_eq!(
+            csv.lines().nth(1),
+            Some("1:02:05,2026-10-15T18:00:00+00:00,Valorant,\"clutch, \"\"1v3\"\"\",")
+        );
+    }
+
+    #[test]
+    fn chapters_are_timed_from_the_recording_start() {
+        let session = session(None);
+        // Recording started ten minutes into the session.
+        let base = session.started_at + chrono::Duration::minutes(10);
+        let ffmetadata = render_ffmetadata(&session, base);
+        assert!(ffmetadata.contains("START=0\nEND=600000\ntitle=Just Chatting - Ranked\n"));
+        assert!(ffmetadata.contains("START=600000\nEND=3000000\ntitle=Valorant - Ranked\n"));
+        let xml = render_chapters_xml(&session, base);
+        assert!(xml.contains("<ChapterTimeStart>00:10:00.000</ChapterTimeStart>"));
+    }
+}
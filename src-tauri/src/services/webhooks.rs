@@ -0,0 +1,327 @@
+//! Outbound webhooks (`Settings::webhooks`): each endpoint subscribes to app
+//! events (the `script_hooks` event names) and gets a POST when one fires.
+//!
+//! The body is the event document ({event, fired_at, data}) unless the
+//! endpoint has a `body_template`: a JSON document whose strings may contain
+//! `{{path}}` placeholders into that event document (`{{data.title}}`,
+//! `{{event}}`). A string that is only a placeholder takes the value as-is
+//! (number, object, ...); otherwise the value is interpolated as text. That
+//! covers Discord (`{"content": "{{data.streamer_name}} is live"}`) and most
+//! chat or automation services without custom code.
+//!
+//! Network errors, 429s and 5xx responses are retried with exponential
+//! backoff. Every delivery is recorded in an in-memory history for
+//! `get_webhook_deliveries`.
+
+use crate::models::settings::{AppState, WebhookEndpoint};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_ATTEMPTS: u32 = 4;
+const BASE_BACKOFF: Duration = Duration::from_secs(2);
+/// A `Retry-After` longer than this gives up instead of waiting.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+const MAX_DELIVERIES: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookDelivery {
+    pub webhook_id: String,
+    pub webhook_name: String,
+    pub event: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub success: bool,
+    /// HTTP status of the last attempt, if one got a response.
+    pub status: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// Newest last.
+static DELIVERIES: Lazy<StdMutex<VecDeque<WebhookDelivery>>> =
+    Lazy::new(|| StdMutex::new(VecDeque::new()));
+
+/// Recorded deliveries, newest first, optionally for one endpoint.
+pub fn deliveries(webhook_id: Option<&str>) -> Vec<WebhookDelivery> {
+    DELIVERIES
+        .lock()
+        .unwrap()
+        .iter()
+        .rev()
+        .filter(|d| webhook_id.is_none_or(|id| d.webhook_id == id))
+        .cloned()
+        .collect()
+}
+
+fn record(delivery: WebhookDelivery) {
+    let mut deliveries = DELIVERIES.lock().unwrap();
+    deliveries.push_back(delivery);
+    while deliveries.len() > MAX_DELIVERIES {
+        deliveries.pop_front();
+    }
+}
+
+/// The value at a dotted `path` in `doc`; array elements by index.
+fn lookup<'a>(doc: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.').try_fold(doc, |value, key| match value {
+        Value::Array(items) => items.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    })
+}
+
+/// Interpolate `{{path}}` placeholders in `text`; unknown paths become empty.
+fn interpolate(text: &str, doc: &Value) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        match lookup(doc, rest[start + 2..start + 2 + len].trim()) {
+            Some(Value::String(s)) => out.push_str(s),
+            Some(Value::Null) | None => {}
+            Some(other) => out.push_str(&other.to_string()),
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Fill a parsed body template from the event document.
+fn render(template: &Value, doc: &Value) -> Value {
+    match template {
+        Value::String(s) => {
+            let trimmed = s.trim();
+            let whole = trimmed
+                .strip_prefix("{{")
+                .and_then(|t| t.strip_suffix("}}"))
+                .filter(|inner| !inner.contains("{{") && !inner.contains("}}"));
+            match whole {
+                Some(path) => lookup(doc, path.trim()).cloned().unwrap_or(Value::Null),
+                None => Value::String(interpolate(s, doc)),
+            }
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| render(v, doc)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), render(v, doc)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// The request body for `endpoint`.
+fn body(endpoint: &WebhookEndpoint, doc: &Value) -> Result<Value> {
+    match endpoint
+        .body_template
+        .as_deref()
+        .filter(|t| !t.trim().is_empty())
+    {
+        Some(template) => {
+            let template: Value = serde_json::from_str(template)
+                .map_err(|e| anyhow!("body template isn't valid JSON: {}", e))?;
+            Ok(render(&template, doc))
+        }
+        None => Ok(doc.clone()),
+    }
+}
+
+/// Delay before retry number `attempt` (1-based).
+fn backoff(attempt: u32) -> Duration {
+    BASE_BACKOFF * 2u32.pow(attempt.saturating_sub(1))
+}
+
+/// POST `event` to every enabled endpoint subscribed to it, in the
+/// background.
+pub fn fire(app: &AppHandle, event: &str, data: Value) {
+    let endpoints: Vec<WebhookEndpoint> = app
+        .state::<AppState>()
+        .settings
+        .lock()
+        .map(|s| {
+            s.webhooks
+                .iter()
+                .filter(|w| w.enabled && w.events.iter().any(|e| e == event))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    if endpoints.is_empty() {
+        return;
+    }
+    let doc = json!({ "event": event, "fired_at": Utc::now(), "data": data });
+    for endpoint in endpoints {
+        let event = event.to_string();
+        let doc = doc.clone();
+        tauri::async_runtime::spawn(async move {
+            let delivery = deliver(&endpoint, &event, &doc).await;
+            if !delivery.success {
+                warn!(
+                    "[Webhooks] {} ({}) failed after {} attempt(s): {}",
+                    endpoint.name,
+                    event,
+                    delivery.attempts,
+                    delivery.error.as_deref().unwrap_or("unknown error")
+                );
+            }
+            record(delivery);
+        });
+    }
+}
+
+/// POST one event to `url` outside `Settings::webhooks`, with the same body
+/// shape, retries and delivery history. Automation rules' `SendWebhook`
+/// action uses this; `id` and `name` label it in the history.
+pub async fn send(url: &str, id: &str, name: &str, event: &str, data: Value) -> Result<()> {
+    let endpoint = WebhookEndpoint {
+        id: id.to_string(),
+        name: name.to_string(),
+        url: url.to_string(),
+        events: vec![event.to_string()],
+        body_template: None,
+        enabled: true,
+    };
+    let doc = json!({ "event": event, "fired_at": Utc::now(), "data": data });
+    let delivery = deliver(&endpoint, event, &doc).await;
+    let result = if delivery.success {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "{} after {} attempt(s)",
+            delivery.error.as_deref().unwrap_or("unknown error"),
+            delivery.attempts
+        ))
+    };
+    record(delivery);
+    result
+}
+
+async fn deliver(endpoint: &WebhookEndpoint, event: &str, doc: &Value) -> WebhookDelivery {
+    let started_at = Utc::now();
+    let mut delivery = WebhookDelivery {
+        webhook_id: endpoint.id.clone(),
+        webhook_name: endpoint.name.clone(),
+        event: event.to_string(),
+        started_at,
+        finished_at: started_at,
+        attempts: 0,
+        success: false,
+        status: None,
+        error: None,
+    };
+    let body = match body(endpoint, doc) {
+        Ok(body) => body,
+        Err(e) => {
+            delivery.error = Some(e.to_string());
+            return delivery;
+        }
+    };
+
+    while delivery.attempts < MAX_ATTEMPTS {
+        if delivery.attempts > 0 {
+            tokio::time::sleep(backoff(delivery.attempts)).await;
+        }
+        delivery.attempts += 1;
+        let result = crate::services::http::client()
+            .post(&endpoint.url)
+            .timeout(TIMEOUT)
+            .json(&body)
+            .send()
+            .await;
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                delivery.error = Some(e.to_string());
+                continue;
+            }
+        };
+        let status = response.status();
+        delivery.status = Some(status.as_u16());
+        if status.is_success() {
+            delivery.success = true;
+            delivery.error = None;
+            debug!("[Webhooks] {} delivered {}", endpoint.name, event);
+            break;
+        }
+        delivery.error = Some(format!("HTTP {}", status));
+        if status.as_u16() == 429 {
+            let retry_after = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .map(Duration::from_secs_f64);
+            match retry_after {
+                Some(wait) if wait > MAX_RETRY_AFTER => break,
+                Some(wait) if delivery.attempts < MAX_ATTEMPTS => tokio::time::sleep(wait).await,
+                _ => {}
+            }
+        } else if !status.is_server_error() {
+            // Other 4xx won't change on retry.
+            break;
+        }
+    }
+    delivery.finished_at = Utc::now();
+    delivery
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc() -> Value {
+        json!({
+            "event": "went_live",
+            "data": {
+                "streamer_name": "Alpha",
+                "viewers": 42,
+                "tags": ["en", "speedrun"],
+                "game": null,
+            },
+        })
+    }
+
+    #[test]
+    fn templates_interpolate_text_and_keep_whole_values_typed() {
+        let template = json!({
+            "content": "{{data.streamer_name}} is live ({{ data.viewers }} viewers){{data.game}}",
+            "count": "{{data.viewers}}",
+            "tag": ["{{data.tags.1}}", "{{missing}}"],
+            "fixed": true,
+        });
+        assert_eq!(
+            render(&template, &doc()),
+            json!({
+                "content": "Alpha is live (42 viewers)",
+                "count": 42,
+                "tag": ["speedrun", null],
+                "fixed": true,
+            })
+        );
+    }
+
+    #[test]
+    fn unterminated_placeholders_are_left_alone() {
+        assert_eq!(interpolate("a {{event} b", &doc()), "a {{event} b");
+        assert_eq!(interpolate("{{event}} {{", &doc()), "went_live {{");
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        assert_eq!(backoff(1), Duration::from_secs(2));
+        assert_eq!(backoff(2), Duration::from_secs(4));
+        assert_eq!(backoff(3), Duration::from_secs(8));
+    }
+}
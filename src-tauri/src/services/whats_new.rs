@@ -0,0 +1,253 @@
+//! The release changelog, machine-readable, and the what's-new popup after an
+//! update.
+//!
+//! CHANGELOG.md is fetched from the repo and split per release; each keeps
+//! its markdown body plus the bullets of its Features and Fixes sections.
+//! At startup, once the window has painted, a version different from
+//! `Settings.last_seen_version` raises `show-whats-new` {from, to, releases}
+//! with only the releases after the last one run, newest first. The
+//! frontend records `last_seen_version` when the popup closes; until then
+//! `get_whats_new` returns the same payload, for a window that missed the
+//! event.
+
+use crate::models::settings::AppState;
+use crate::services::{event_bus, startup};
+use anyhow::{bail, Result};
+use log::{debug, warn};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Manager};
+
+const CHANGELOG_URL: &str =
+    "https://raw.githubusercontent.com/winters27/StreamNook/main/CHANGELOG.md";
+
+/// Version headers look like: ## [2.9.0] - 2025-11-26
+static VERSION_HEADER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^##\s*\[?v?(\d+\.\d+\.\d+)\]?\s*-?\s*(\d{4}-\d{2}-\d{2})?").unwrap());
+
+static PENDING: StdMutex<Option<WhatsNew>> = StdMutex::new(None);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChangelogRelease {
+    pub version: String,
+    /// `YYYY-MM-DD`, empty when the header has no date.
+    pub published_at: String,
+    pub body: String,
+    pub features: Vec<String>,
+    pub fixes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WhatsNew {
+    pub from: String,
+    pub to: String,
+    /// Newest first. Empty when the changelog couldn't be fetched.
+    pub releases: Vec<ChangelogRelease>,
+}
+
+pub async fn fetch_changelog() -> Result<String> {
+    let response = crate::services::http::client()
+        .get(CHANGELOG_URL)
+        .header("User-Agent", "StreamNook")
+        .send()
+        .await?;
+    if !response.status().is_success() {
+        bail!("HTTP {}", response.status());
+    }
+    Ok(response.text().await?)
+}
+
+/// Which list a `###` section's bullets go in.
+enum Section {
+    Features,
+    Fixes,
+    Other,
+}
+
+/// Split a changelog into releases, in file order (newest first).
+pub fn parse(markdown: &str) -> Vec<ChangelogRelease> {
+    let mut releases: Vec<ChangelogRelease> = Vec::new();
+    let mut body: Vec<&str> = Vec::new();
+    let mut section = Section::Other;
+    for line in markdown.lines() {
+        if let Some(caps) = VERSION_HEADER.captures(line) {
+            finish(releases.last_mut(), &mut body);
+            releases.push(ChangelogRelease {
+                version: caps[1].to_string(),
+                published_at: caps.get(2).map_or("", |m| m.as_str()).to_string(),
+                ..Default::default()
+            });
+            section = Section::Other;
+            continue;
+        }
+        let Some(release) = releases.last_mut() else {
+            continue;
+        };
+        body.push(line);
+        if let Some(heading) = line.strip_prefix("###") {
+            let heading = heading.to_lowercase();
+            section = if heading.contains("feature") {
+                Section::Features
+            } else if heading.contains("fix") {
+                Section::Fixes
+            } else {
+                Section::Other
+            };
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            match section {
+                Section::Features => release.features.push(item.trim().to_string()),
+                Section::Fixes => release.fixes.push(item.trim().to_string()),
+                Section::Other => {}
+            }
+        }
+    }
+    finish(releases.last_mut(), &mut body);
+    releases
+}
+
+/// Close off the release being read with the body lines gathered for it.
+fn finish(release: Option<&mut ChangelogRelease>, body: &mut Vec<&str>) {
+    if let Some(release) = release {
+        release.body = body.join("\n").trim().to_string();
+    }
+    body.clear();
+}
+
+fn version_key(version: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = version.trim().trim_start_matches('v').split('.');
+    let key = (
+        parts.next()?.parse().ok()?,
+        parts.next()?.parse().ok()?,
+        parts.next()?.parse().ok()?,
+    );
+    parts.next().is_none().then_some(key)
+}
+
+/// The releases after `from` up to and including `to`.
+pub fn delta(releases: &[ChangelogRelease], from: &str, to: &str) -> Vec<ChangelogRelease> {
+    let (Some(from), Some(to)) = (version_key(from), version_key(to)) else {
+        return Vec::new();
+    };
+    releases
+        .iter()
+        .filter(|r| version_key(&r.version).is_some_and(|v| v > from && v <= to))
+        .cloned()
+        .collect()
+}
+
+/// The what's-new payload waiting to be shown, if any.
+pub fn pending() -> Option<WhatsNew> {
+    PENDING.lock().unwrap().clone()
+}
+
+/// Check for an update since the last run and raise `show-whats-new`. Call
+/// once, after `AppState` is managed.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        startup::wait_ready().await;
+        let current = env!("CARGO_PKG_VERSION");
+        let last_seen = app
+            .state::<AppState>()
+            .settings
+            .lock()
+            .unwrap()
+            .last_seen_version
+            .clone();
+        // First run (nothing seen yet) and same version: nothing new.
+        let Some(last_seen) = last_seen.filter(|v| v != current) else {
+            return;
+        };
+        if version_key(&last_seen) > version_key(current) {
+            debug!("[WhatsNew] Downgraded from {}, nothing to show", last_seen);
+            return;
+        }
+        let releases = match fetch_changelog().await {
+            Ok(markdown) => delta(&parse(&markdown), &last_seen, current),
+            Err(e) => {
+                // The popup still opens; it loads the notes itself.
+                warn!("[WhatsNew] Couldn't fetch the changelog: {}", e);
+                Vec::new()
+            }
+        };
+        debug!(
+            "[WhatsNew] Updated {} -> {} ({} releases)",
+            last_seen,
+            current,
+            releases.len()
+        );
+        let whats_new = WhatsNew {
+            from: last_seen,
+            to: current.to_string(),
+            releases,
+        };
+        *PENDING.lock().unwrap() = Some(whats_new.clone());
+        let _ = event_bus::emit("show-whats-new", &whats_new);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANGELOG: &str = "# Changelog
+
+## [8.3.8] - 2026-07-20
+
+### ✨ Features
+- Synced VOD chat.
+- **All drops** filter.
+
+## [8.3.7] - 2026-07-17
+
+## 🎉 New: Subscriber milestone badges
+> Intro.
+
+### ✨ Features
+- Prediction emotes.
+
+### 🐛 Bug Fixes
+- Gift-sub cards.
+
+### ⚡ Performance
+- Smoother backdrop.
+
+## [8.3.6]
+- Loose note.
+";
+
+    #[test]
+    fn releases_split_with_their_features_and_fixes() {
+        let releases = parse(CHANGELOG);
+        assert_eq!(releases.len(), 3);
+        assert_eq!(releases[0].version, "8.3.8");
+        assert_eq!(releases[0].published_at, "2026-07-20");
+        assert_eq!(
+            releases[0].features,
+            ["Synced VOD chat.", "**All drops** filter."]
+        );
+        assert!(releases[0].body.starts_with("### ✨ Features"));
+        assert!(releases[0].body.ends_with("filter."));
+        assert_eq!(releases[1].features, ["Prediction emotes."]);
+        assert_eq!(releases[1].fixes, ["Gift-sub cards."]);
+        assert!(releases[1].body.contains("Subscriber milestone badges"));
+        assert_eq!(releases[2].published_at, "");
+        assert!(releases[2].features.is_empty());
+    }
+
+    #[test]
+    fn delta_is_what_came_after_the_last_run() {
+        let releases = parse(CHANGELOG);
+        let versions = |from: &str, to: &str| {
+            delta(&releases, from, to)
+                .into_iter()
+                .map(|r| r.version)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions("8.3.6", "8.3.8"), ["8.3.8", "8.3.7"]);
+        assert_eq!(versions("v8.3.7", "8.3.8"), ["8.3.8"]);
+        assert!(versions("8.3.8", "8.3.8").is_empty());
+        assert!(versions("dev", "8.3.8").is_empty());
+    }
+}
@@ -1,9 +1,9 @@
+use crate::services::event_bus;
 use futures_util::{SinkExt, StreamExt};
 use log::{debug, error};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::Arc;
-use tauri::{AppHandle, Emitter};
 use tokio::sync::RwLock;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
@@ -62,7 +62,6 @@ impl WhisperService {
         &self,
         user_id: String,
         access_token: String,
-        app_handle: AppHandle,
     ) -> Result<(), String> {
         // Check if already connected and mark as connecting immediately to prevent race conditions
         {
@@ -168,9 +167,10 @@ impl WhisperService {
                                                     );
 
                                                     // Emit to frontend
-                                                    if let Err(e) = app_handle
-                                                        .emit("whisper-received", &whisper_event)
-                                                    {
+                                                    if let Err(e) = event_bus::emit(
+                                                        "whisper-received",
+                                                        &whisper_event,
+                                                    ) {
                                                         error!(
                                                             "[WhisperService] Failed to emit whisper event: {}",
                                                             e
@@ -0,0 +1,228 @@
+//! Window title / taskbar badge. A backend tick re-renders it every 30s so the
+//! live viewer count stays current without a frontend timer (which webviews
+//! throttle while hidden anyway).
+//!
+//! The title carries either the watched channel's viewer count or the unread
+//! mention count. The mention count also goes on the taskbar: a numbered
+//! overlay icon on Windows, the dock/launcher badge elsewhere. A viewer count
+//! doesn't fit in a 16px overlay, so it stays title-only.
+
+use crate::services::twitch_service::TwitchService;
+use log::debug;
+use once_cell::sync::Lazy;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const BASE_TITLE: &str = "StreamNook";
+const TICK: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeSource {
+    Off,
+    Viewers,
+    Mentions,
+}
+
+impl BadgeSource {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "off" | "" => Ok(Self::Off),
+            "viewers" => Ok(Self::Viewers),
+            "mentions" => Ok(Self::Mentions),
+            other => Err(format!(
+                "Unknown badge source '{}' (expected viewers, mentions or off)",
+                other
+            )),
+        }
+    }
+}
+
+struct BadgeState {
+    source: BadgeSource,
+    unread_mentions: u32,
+    /// What was last applied, to skip no-op window calls.
+    rendered: Option<(String, u32)>,
+}
+
+static STATE: Lazy<StdMutex<BadgeState>> = Lazy::new(|| {
+    StdMutex::new(BadgeState {
+        source: BadgeSource::Off,
+        unread_mentions: 0,
+        rendered: None,
+    })
+});
+
+/// Start the render tick. Call once from setup.
+pub fn start(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut ticker = tokio::time::interval(TICK);
+        loop {
+            ticker.tick().await;
+            crate::services::power_saver::stretch_tick(TICK).await;
+            refresh(&app).await;
+        }
+    });
+}
+
+/// Change what the badge shows and/or the unread mention count (`None`
+/// leaves that part as is), then re-render right away.
+pub async fn set(app: &AppHandle, source: Option<BadgeSource>, unread_mentions: Option<u32>) {
+    {
+        let mut state = STATE.lock().unwrap();
+        if let Some(source) = source {
+            state.source = source;
+        }
+        if let Some(count) = unread_mentions {
+            state.unread_mentions = count;
+        }
+    }
+    refresh(app).await;
+}
+
+async fn refresh(app: &AppHandle) {
+    let (source, mentions) = {
+        let state = STATE.lock().unwrap();
+        (state.source, state.unread_mentions)
+    };
+
+    let title = match source {
+        BadgeSource::Off => BASE_TITLE.to_string(),
+        BadgeSource::Mentions if mentions > 0 => {
+            format!("({}) {}", mentions, BASE_TITLE)
+        }
+        BadgeSource::Mentions => BASE_TITLE.to_string(),
+        BadgeSource::Viewers => match crate::services::stream_server::solo_channel() {
+            Some(channel) => match TwitchService::check_stream_online(&channel).await {
+                Ok(Some(stream)) => format!(
+                    "{} · {} viewers — {}",
+                    stream.user_name,
+                    compact_count(stream.viewer_count),
+                    BASE_TITLE
+                ),
+                _ => BASE_TITLE.to_string(),
+            },
+            None => BASE_TITLE.to_string(),
+        },
+    };
+    let count = if source == BadgeSource::Mentions {
+        mentions
+    } else {
+        0
+    };
+
+    {
+        let mut state = STATE.lock().unwrap();
+        let next = Some((title.clone(), count));
+        if state.rendered == next {
+            return;
+        }
+        state.rendered = next;
+    }
+
+    let Some(main) = app.get_webview_window("main") else {
+        return;
+    };
+    debug!("[WindowBadge] {} (badge {})", title, count);
+    let _ = main.set_title(&title);
+
+    #[cfg(windows)]
+    let _ = main.set_overlay_icon(
+        (count > 0).then(|| tauri::image::Image::new_owned(overlay_rgba(count), ICON, ICON)),
+    );
+    #[cfg(not(windows))]
+    let _ = main.set_badge_count((count > 0).then_some(count as i64));
+}
+
+/// 1234 -> "1.2K", 1234567 -> "1.2M".
+fn compact_count(n: u32) -> String {
+    match n {
+        0..=999 => n.to_string(),
+        // 999_950 and up would round to "1000.0K".
+        1_000..=999_949 => format!("{:.1}K", n as f64 / 1_000.0),
+        _ => format!("{:.1}M", n as f64 / 1_000_000.0),
+    }
+}
+
+/// Overlay icon edge, in pixels.
+const ICON: u32 = 16;
+
+/// 3x5 digit glyphs, one row per `u8` (low three bits, leftmost pixel high).
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+const PLUS: [u8; 5] = [0b000, 0b010, 0b111, 0b010, 0b000];
+
+/// A red disc with the count in white (`9+` above nine), as RGBA.
+fn overlay_rgba(count: u32) -> Vec<u8> {
+    let glyphs: Vec<[u8; 5]> = if count > 9 {
+        vec![DIGITS[9], PLUS]
+    } else {
+        vec![DIGITS[count as usize]]
+    };
+    // Glyphs drawn at 2x; one blank column between them.
+    let scale = 2;
+    let text_w = glyphs.len() as u32 * 3 * scale + (glyphs.len() as u32 - 1) * scale;
+    let text_h = 5 * scale;
+    let (x0, y0) = ((ICON - text_w) / 2, (ICON - text_h) / 2);
+
+    let mut rgba = vec![0u8; (ICON * ICON * 4) as usize];
+    let center = (ICON as f32 - 1.0) / 2.0;
+    for y in 0..ICON {
+        for x in 0..ICON {
+            let (dx, dy) = (x as f32 - center, y as f32 - center);
+            if (dx * dx + dy * dy).sqrt() > ICON as f32 / 2.0 {
+                continue;
+            }
+            let lit = x >= x0 && y >= y0 && y < y0 + text_h && {
+                let gx = (x - x0) / scale;
+                let gy = ((y - y0) / scale) as usize;
+                let (index, col) = ((gx / 4) as usize, gx % 4);
+                col < 3
+                    && glyphs
+                        .get(index)
+                        .is_some_and(|g| (g[gy] >> (2 - col)) & 1 == 1)
+            };
+            let px = ((y * ICON + x) * 4) as usize;
+            let color = if lit {
+                [255, 255, 255, 255]
+            } else {
+                [0xe9, 0x1e, 0x3a, 255]
+            };
+            rgba[px..px + 4].copy_from_slice(&color);
+        }
+    }
+    rgba
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewer_counts_are_compacted() {
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(12_345), "12.3K");
+        assert_eq!(compact_count(2_500_000), "2.5M");
+        assert_eq!(compact_count(999_949), "999.9K");
+        assert_eq!(compact_count(999_950), "1.0M");
+    }
+
+    #[test]
+    fn overlay_is_a_full_icon_with_white_glyph_pixels() {
+        for count in [1, 7, 42] {
+            let rgba = overlay_rgba(count);
+            assert_eq!(rgba.len(), (ICON * ICON * 4) as usize);
+            assert!(rgba.chunks(4).any(|p| p == [255, 255, 255, 255]));
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Main-window modes driven from the backend: always-on-top, the mini player
+//! (`enter_mini_mode` / `exit_mini_mode`), also bound to global hotkeys (see
+//! services::global_hotkeys), and borderless fullscreen on a chosen display
+//! (`set_fullscreen`, with `get_monitors` listing them).
+//!
+//! The mini player is a small borderless 16:9 window pinned above other
+//! windows in the bottom-right corner of the display it's on. Entering it
+//! remembers where the window was, how big, whether it was maximized and
+//! pinned; leaving puts all of that back. Hiding chat is the frontend's part:
+//! it follows the `mini-mode-changed` event {active} the same way compact view
+//! hides chat.
+//!
+//! Displays are numbered left to right, then top to bottom, so index 0 is
+//! the leftmost whatever order the OS reports them in. Playback always runs
+//! in the main window, so that's the window moved.
+
+use crate::services::event_bus;
+use anyhow::{anyhow, Result};
+use log::debug;
+use serde::Serialize;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex as StdMutex;
+use tauri::{
+    AppHandle, LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalSize, Size, WebviewWindow,
+};
+
+/// Width of the mini player's video, in logical pixels.
+const MINI_WIDTH: f64 = 480.0;
+/// The title bar the mini player keeps, so it can still be dragged and closed.
+const TITLE_BAR_HEIGHT: f64 = 40.0;
+/// Gap to the work area's edges, in logical pixels.
+const CORNER_MARGIN: f64 = 16.0;
+
+/// Whether the main window is pinned above other windows by the user.
+static ALWAYS_ON_TOP: AtomicBool = AtomicBool::new(false);
+
+/// Where the window was before mini mode; `Some` while in it.
+static SAVED_LAYOUT: StdMutex<Option<SavedLayout>> = StdMutex::new(None);
+
+#[derive(Debug, Clone, Copy)]
+struct SavedLayout {
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    maximized: bool,
+}
+
+fn main_window(app: &AppHandle) -> Result<WebviewWindow> {
+    app.get_webview_window("main")
+        .ok_or_else(|| anyhow!("The main window isn't open"))
+}
+
+pub fn is_mini() -> bool {
+    SAVED_LAYOUT.lock().unwrap().is_some()
+}
+
+pub fn is_always_on_top() -> bool {
+    ALWAYS_ON_TOP.load(Ordering::Relaxed)
+}
+
+/// Pin or unpin the main window. In mini mode the window stays on top and
+/// the choice applies once it's left.
+pub fn set_always_on_top(app: &AppHandle, on_top: bool) -> Result<()> {
+    ALWAYS_ON_TOP.store(on_top, Ordering::Relaxed);
+    if !is_mini() {
+        main_window(app)?.set_always_on_top(on_top)?;
+    }
+    debug!("[WindowModes] Always on top: {}", on_top);
+    Ok(())
+}
+
+/// The mini player's outer size and position in the bottom-right corner of
+/// a `work_area` at `scale`, all in physical pixels.
+fn mini_bounds(
+    work_position: PhysicalPosition<i32>,
+    work_size: PhysicalSize<u32>,
+    scale: f64,
+) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    let width = (MINI_WIDTH * scale).round();
+    let height = ((MINI_WIDTH * 9.0 / 16.0 + TITLE_BAR_HEIGHT) * scale).round();
+    let margin = (CORNER_MARGIN * scale).round();
+    let x = work_position.x as f64 + work_size.width as f64 - width - margin;
+    let y = work_position.y as f64 + work_size.height as f64 - height - margin;
+    (
+        PhysicalPosition::new(
+            x.max(work_position.x as f64) as i32,
+            y.max(work_position.y as f64) as i32,
+        ),
+        PhysicalSize::new(width as u32, height as u32),
+    )
+}
+
+/// Shrink the main window into the mini player. A no-op when already in it.
+pub fn enter_mini(app: &AppHandle) -> Result<()> {
+    let window = main_window(app)?;
+    // The lock isn't held across window calls: those wait on the main thread,
+    // which may be dispatching a hotkey that checks `is_mini`.
+    if is_mini() {
+        return Ok(());
+    }
+    let monitor = window
+        .current_monitor()?
+        .ok_or_else(|| anyhow!("Couldn't tell which display the window is on"))?;
+    let layout = SavedLayout {
+        position: window.outer_position()?,
+        size: window.inner_size()?,
+        maximized: window.is_maximized()?,
+    };
+
+    if layout.maximized {
+        window.unmaximize()?;
+    }
+    if window.is_fullscreen()? {
+        window.set_fullscreen(false)?;
+    }
+    let work_area = monitor.work_area();
+    let (position, size) = mini_bounds(work_area.position, work_area.size, monitor.scale_factor());
+    window.set_min_size(None::<Size>)?;
+    window.set_size(size)?;
+    window.set_position(position)?;
+    window.set_always_on_top(true)?;
+    *SAVED_LAYOUT.lock().unwrap() = Some(layout);
+
+    debug!("[WindowModes] Mini mode at {:?} {:?}", position, size);
+    let _ = event_bus::emit("mini-mode-changed", json!({ "active": true }));
+    Ok(())
+}
+
+/// The main window's minimum size, from its tauri.conf.json entry. main.rs
+/// builds the window with it; mini mode lifts it while active.
+pub fn main_min_size(app: &AppHandle) -> Option<LogicalSize<f64>> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == "main")?;
+    Some(LogicalSize::new(config.min_width?, config.min_height?))
+}
+
+/// Put the window back the way it was before mini mode. A no-op when not in it.
+pub fn exit_mini(app: &AppHandle) -> Result<()> {
+    let window = main_window(app)?;
+    let Some(layout) = SAVED_LAYOUT.lock().unwrap().take() else {
+        return Ok(());
+    };
+    window.set_always_on_top(is_always_on_top())?;
+    window.set_min_size(main_min_size(app))?;
+    window.set_size(layout.size)?;
+    window.set_position(layout.position)?;
+    if layout.maximized {
+        window.maximize()?;
+    }
+
+    debug!("[WindowModes] Left mini mode");
+    let _ = event_bus::emit("mini-mode-changed", json!({ "active": false }));
+    Ok(())
+}
+
+pub fn toggle_mini(app: &AppHandle) -> Result<()> {
+    if is_mini() {
+        exit_mini(app)
+    } else {
+        enter_mini(app)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonitorInfo {
+    pub index: usize,
+    pub name: Option<String>,
+    /// Position and size in physical pixels, on the virtual desktop.
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub primary: bool,
+    /// The main window is on this display.
+    pub current: bool,
+}
+
+/// `monitors` sorted left to right, then top to bottom, and numbered.
+fn number_monitors<T>(mut monitors: Vec<(MonitorInfo, T)>) -> Vec<(MonitorInfo, T)> {
+    monitors.sort_by_key(|(m, _)| (m.x, m.y));
+    for (index, (monitor, _)) in monitors.iter_mut().enumerate() {
+        monitor.index = index;
+    }
+    monitors
+}
+
+fn monitor_list(window: &WebviewWindow) -> Result<Vec<(MonitorInfo, Monitor)>> {
+    let origin = |m: &Monitor| *m.position();
+    let primary = window.primary_monitor()?.map(|m| origin(&m));
+    let current = window.current_monitor()?.map(|m| origin(&m));
+    let monitors = window
+        .available_monitors()?
+        .into_iter()
+        .map(|m| {
+            let info = MonitorInfo {
+                index: 0,
+                name: m.name().cloned(),
+                x: m.position().x,
+                y: m.position().y,
+                width: m.size().width,
+                height: m.size().height,
+                scale_factor: m.scale_factor(),
+                primary: primary == Some(origin(&m)),
+                current: current == Some(origin(&m)),
+            };
+            (info, m)
+        })
+        .collect();
+    Ok(number_monitors(monitors))
+}
+
+pub fn monitors(app: &AppHandle) -> Result<Vec<MonitorInfo>> {
+    let window = main_window(app)?;
+    Ok(monitor_list(&window)?
+        .into_iter()
+        .map(|(info, _)| info)
+        .collect())
+}
+
+/// Borderless fullscreen on display `monitor_index`, or on the display the
+/// window is on with `None`. Leaves the mini player first.
+pub fn set_fullscreen(app: &AppHandle, monitor_index: Option<usize>) -> Result<()> {
+    let window = main_window(app)?;
+    exit_mini(app)?;
+    if let Some(index) = monitor_index {
+        let mut monitors = monitor_list(&window)?;
+        if index >= monitors.len() {
+            return Err(anyhow!(
+                "There's no display {} ({} connected)",
+                index,
+                monitors.len()
+            ));
+        }
+        let (_, monitor) = monitors.swap_remove(index);
+        // Fullscreen covers the display the window is on, so move it there
+        // first. A borderless window that's maximized keeps its maximized
+        // bounds (and the taskbar) in fullscreen, and can't be moved anyway.
+        if window.is_fullscreen()? {
+            window.set_fullscreen(false)?;
+        }
+        if window.is_maximized()? {
+            window.unmaximize()?;
+        }
+        window.set_position(*monitor.position())?;
+        debug!("[WindowModes] Fullscreen on display {}", index);
+    } else if window.is_maximized()? {
+        window.unmaximize()?;
+    }
+    window.set_fullscreen(true)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(x: i32, y: i32) -> MonitorInfo {
+        MonitorInfo {
+            index: 9,
+            name: None,
+            x,
+            y,
+            width: 1920,
+            height: 1080,
+            scale_factor: 1.0,
+            primary: x == 0 && y == 0,
+            current: false,
+        }
+    }
+
+    #[test]
+    fn displays_are_numbered_left_to_right() {
+        let numbered = number_monitors(vec![
+            (monitor(0, 0), ()),
+            (monitor(1920, -1080), ()),
+            (monitor(-1920, 0), ()),
+        ]);
+        assert_eq!(
+            numbered
+                .iter()
+                .map(|(m, _)| (m.index, m.x, m.y))
+                .collect::<Vec<_>>(),
+            [(0, -1920, 0), (1, 0, 0), (2, 1920, -1080)]
+        );
+    }
+
+    #[test]
+    fn mini_player_sits_in_the_bottom_right_corner() {
+        let (position, size) = mini_bounds(
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(1920, 1040),
+            1.0,
+        );
+        assert_eq!(size, PhysicalSize::new(480, 310));
+        assert_eq!(position, PhysicalPosition::new(1424, 714));
+
+        // A second display to the left, at 150%.
+        let (position, size) = mini_bounds(
+            PhysicalPosition::new(-2560, 0),
+            PhysicalSize::new(2560, 1400),
+            1.5,
+        );
+        assert_eq!(size, PhysicalSize::new(720, 465));
+        assert_eq!(position, PhysicalPosition::new(-744, 911));
+    }
+}
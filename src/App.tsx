@@ -17,7 +17,7 @@ import SettingsDialog from './components/SettingsDialog';
 import PublicProfileOverlay from './components/PublicProfileOverlay';
 import CommandPalette from './components/CommandPalette';
 import { useCommandPaletteHotkey } from './hooks/useCommandPaletteHotkey';
-import { useKeybindings } from './keybindings';
+import { useKeybindings, getPlayerControls } from './keybindings';
 import { startSnippetSync } from './stores/snippetStore';
 import PluginUiHost from './plugins-ui/PluginUiHost';
 import PluginUpdatesChecker from './components/plugins/PluginUpdatesChecker';
@@ -56,11 +56,13 @@ import { listen } from '@tauri-apps/api/event';
 import { applyModerateEvent } from './utils/applyModerateEvent';
 import { handleSeventvEmoteSetUpdate, handleSeventvCosmeticUpdate, type EmoteSetUpdatePayload, type CosmeticUpdatePayload } from './services/seventvEventApi';
 import { invoke } from '@tauri-apps/api/core';
+import type { AdBreakEnded, AdBreakStarted, StreamInfoChange, StreamInfoUpdate, WhatsNew } from './types';
 import { getCurrentWindow, LogicalSize } from '@tauri-apps/api/window';
 import { getThemeById, applyTheme, DEFAULT_THEME_ID, getThemeByIdWithCustom, applyGlassStrength, DEFAULT_GLASS_TRANSPARENCY, applyFont, DEFAULT_FONT_ID, OLED_THEME_ID, getOledTheme } from './themes';
 import { getSelectedCompactViewPreset } from './constants/compactViewPresets';
 
 import { Logger } from './utils/logger';
+import { afterFirstPaint } from './utils/firstPaint';
 interface BadgeVersion {
   id: string;
   image_url_1x: string;
@@ -165,6 +167,8 @@ function App() {
   
   const [showChangelog, setShowChangelog] = useState(false);
   const [changelogVersion, setChangelogVersion] = useState<string | null>(null);
+  // The releases since the version last run, when the changelog opened after an update.
+  const [whatsNew, setWhatsNew] = useState<WhatsNew | null>(null);
   // Dev-only: a changelog opened by the simulated-update reload, so its close
   // doesn't persist last_seen_version (the version isn't really installed).
   const devForcedChangelogRef = useRef(false);
@@ -397,6 +401,138 @@ function App() {
     };
   }, []);
 
+  // The backend owns the mini player's window layout (hotkey or command);
+  // the store follows it to hide and restore chat.
+  useEffect(() => {
+    const unlistenPromise = listen<{ active: boolean }>('mini-mode-changed', (event) => {
+      useAppStore.getState().setMiniMode(event.payload.active);
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // Away detection (services/idle_monitor.rs): pause the player while away and
+  // resume it on return, but only if it was us that paused it. The backend sets
+  // the idle Discord presence; coming back puts the watching one back.
+  useEffect(() => {
+    let pausedForIdle = false;
+    const unlistenPromise = listen<{ idle: boolean; idle_secs: number }>('user-idle-changed', (event) => {
+      const { settings, refreshDiscordPresence } = useAppStore.getState();
+      const controls = getPlayerControls();
+      if (event.payload.idle) {
+        if (settings.idle?.pause_playback && controls?.isActive() && !controls.isPaused()) {
+          controls.togglePlay();
+          pausedForIdle = true;
+        }
+        return;
+      }
+      if (pausedForIdle && controls?.isActive() && controls.isPaused()) {
+        controls.togglePlay();
+      }
+      pausedForIdle = false;
+      if (settings.idle?.discord_idle ?? true) {
+        void refreshDiscordPresence();
+      }
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // The backend polls the watched channel's header info (subscribe_stream_info)
+  // and pushes it here every 30s; fold it into the playing stream.
+  useEffect(() => {
+    const unlistenPromise = listen<StreamInfoUpdate>('stream-info-update', (event) => {
+      const info = event.payload;
+      const { currentStream } = useAppStore.getState();
+      if (!info.is_live || !currentStream) return;
+      if (currentStream.user_login.toLowerCase() !== info.channel_login.toLowerCase()) return;
+      useAppStore.setState({
+        currentStream: {
+          ...currentStream,
+          title: info.title,
+          game_id: info.game_id,
+          game_name: info.game_name,
+          viewer_count: info.viewer_count ?? currentStream.viewer_count,
+          started_at: info.started_at ?? currentStream.started_at,
+          tags: info.tags,
+        },
+      });
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // Ad breaks spotted in the playlist (stream_server.rs). With "mute during ads"
+  // on, the start payload carries `mute`; we mute for the break and unmute at
+  // the end, but only if it was us that muted it.
+  useEffect(() => {
+    let mutedForAd = false;
+    const unlistenStart = listen<AdBreakStarted>('ad-break-started', (event) => {
+      Logger.debug('[Ads] Ad break started:', event.payload);
+      const controls = getPlayerControls();
+      if (event.payload.mute && controls?.isActive() && !controls.isMuted()) {
+        controls.toggleMute();
+        mutedForAd = true;
+      }
+    });
+    const unlistenEnd = listen<AdBreakEnded>('ad-break-ended', (event) => {
+      Logger.debug('[Ads] Ad break ended:', event.payload);
+      const controls = getPlayerControls();
+      if (mutedForAd && controls?.isActive() && controls.isMuted()) {
+        controls.toggleMute();
+      }
+      mutedForAd = false;
+    });
+    return () => {
+      unlistenStart.then((unlisten) => unlisten());
+      unlistenEnd.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // Title / category changes on the watched channel, from a poll or EventSub
+  // (stream_info_poller.rs). Applied right away, and a category switch is
+  // announced since it usually means the stream is about to look different.
+  useEffect(() => {
+    const unlistenPromise = listen<StreamInfoChange>('stream-info-changed', (event) => {
+      const change = event.payload;
+      const { currentStream, addToast } = useAppStore.getState();
+      if (!currentStream) return;
+      if (currentStream.user_login.toLowerCase() !== change.channel_login.toLowerCase()) return;
+      useAppStore.setState({
+        currentStream: {
+          ...currentStream,
+          title: change.title,
+          game_name: change.game_name,
+          game_id: change.game_id || currentStream.game_id,
+        },
+      });
+      if (change.game_changed && change.game_name) {
+        addToast(`${currentStream.user_name} switched to ${change.game_name}`, 'info');
+      }
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  // After a system sleep (services/resume_watch.rs) the relay's stream URL is
+  // almost always dead; restart the live stream instead of waiting for the
+  // player to stall out.
+  useEffect(() => {
+    const unlistenPromise = listen<{ slept_secs: number }>('system-resumed', (event) => {
+      const { currentStream, currentMediaType, restartStream } = useAppStore.getState();
+      if (!currentStream || (currentMediaType && currentMediaType !== 'live')) return;
+      Logger.info(`[Stream] System resumed after ~${event.payload.slept_secs}s, restarting stream`);
+      void restartStream();
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   // Flush a deep link that arrived mid-boot, once the app is ready.
   useEffect(() => {
     if (!isBooting && pendingWatchChannelRef.current) {
@@ -525,6 +661,9 @@ function App() {
     });
     cleanupFunctions.push(() => unlistenSettingsSync?.());
 
+    // Releases the backend's deferred startup work once the shell has painted.
+    void afterFirstPaint();
+
     const initializeApp = async () => {
       try {
         await loadSettings();
@@ -548,6 +687,9 @@ function App() {
       localStorage.removeItem('streamnook_known_badges');
       localStorage.removeItem('streamnook_notified_available_badges');
 
+      // Everything below is warm-up; let the first frame land before it.
+      await afterFirstPaint();
+
       // Load active drops cache on startup (cached for 1 hour)
       loadActiveDropsCache();
 
@@ -936,12 +1078,9 @@ function App() {
           localStorage.setItem(V220_RELOGIN_MIGRATION_KEY, 'true');
         }
 
-        // If there's no last seen version (first run) or the version has changed
-        if (lastSeenVersion && lastSeenVersion !== currentVersion) {
-          Logger.debug('[App] Version changed, showing changelog');
-          setChangelogVersion(currentVersion);
-          setShowChangelog(true);
-        } else if (!lastSeenVersion) {
+        // A changed version is announced by the backend (show-whats-new, below)
+        // with the releases since this one.
+        if (!lastSeenVersion) {
           // First run - just update the last seen version without showing changelog
           Logger.debug('[App] First run, setting initial version');
           updateSettings({ ...settings, last_seen_version: currentVersion });
@@ -957,6 +1096,25 @@ function App() {
     }
   }, [settings.quality, updateSettings, addToast]);
 
+  // After an update the backend raises show-whats-new with the changelog delta.
+  // It may go out before this listener exists, so also ask for it once.
+  useEffect(() => {
+    const open = (payload: WhatsNew | null) => {
+      if (!payload || useAppStore.getState().settings.last_seen_version === payload.to) return;
+      Logger.debug('[App] Updated from', payload.from, 'to', payload.to);
+      setWhatsNew(payload);
+      setChangelogVersion(payload.to);
+      setShowChangelog(true);
+    };
+    const unlistenPromise = listen<WhatsNew>('show-whats-new', (event) => open(event.payload));
+    invoke<WhatsNew | null>('get_whats_new')
+      .then(open)
+      .catch((err) => Logger.warn('[App] get_whats_new failed:', err));
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
   // Dev-only: after a simulated-update reload, pop the changelog for the version
   // we "updated" to, mirroring how production shows it after a real update. The
   // flag survives the webview reload via sessionStorage.
@@ -974,6 +1132,7 @@ function App() {
   // Handle changelog close - update the last seen version
   const handleChangelogClose = async () => {
     setShowChangelog(false);
+    setWhatsNew(null);
 
     // A dev-forced preview never really installed that version, so don't record
     // it as seen (that would suppress the real changelog or mis-trigger it later).
@@ -1894,6 +2053,7 @@ function App() {
         {showChangelog && changelogVersion && (
           <ChangelogOverlay
             version={changelogVersion}
+            whatsNew={whatsNew}
             onClose={handleChangelogClose}
           />
         )}
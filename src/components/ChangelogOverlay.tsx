@@ -1,7 +1,7 @@
 import { X, ExternalLink, ChevronDown, Github, Heart } from 'lucide-react';
-import { useEffect, useRef, useState } from 'react';
+import { useEffect, useMemo, useRef, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import type { ReleaseNotes } from '../types';
+import type { ReleaseNotes, WhatsNew } from '../types';
 import { parseInlineMarkdown } from '../services/markdownService';
 import { motion } from 'framer-motion';
 import { Logger } from '../utils/logger';
@@ -29,6 +29,9 @@ const formatShortDate = (iso: string): string => {
 
 interface ChangelogOverlayProps {
   version: string;
+  // Set when opened after an update: only the releases since the version last
+  // run are offered.
+  whatsNew?: WhatsNew | null;
   onClose: () => void;
 }
 
@@ -254,7 +257,7 @@ const ReleaseBody = ({ content }: { content: string }) => {
   );
 };
 
-const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
+const ChangelogOverlay = ({ version, whatsNew, onClose }: ChangelogOverlayProps) => {
   // The release list carries every version's body, so switching is instant once
   // it's loaded. Seed from cache so a known list shows without waiting.
   const [releases, setReleases] = useState<GitHubRelease[] | null>(
@@ -285,6 +288,8 @@ const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
         setIsLoading(false); // cached releases already in state
       } else if (releases && releases.length) {
         setIsLoading(false); // fetch failed but we have a cached list
+      } else if (whatsNew?.releases.length) {
+        setIsLoading(false); // the backend's changelog delta stands in for the list
       } else {
         // No list at all — fall back to the single-version notes from the backend
         // so the changelog still shows something (no switcher in this case).
@@ -305,6 +310,24 @@ const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [version]);
 
+  // After an update, narrow the list to the delta. Releases missing from
+  // GitHub's list (or with no list at all) come from the backend's changelog.
+  const shownReleases = useMemo<GitHubRelease[] | null>(() => {
+    if (!whatsNew?.releases.length) return releases;
+    const versions = whatsNew.releases.map((r) => r.version);
+    const fromGitHub = releases?.filter((r) => versions.includes(normalizeTag(r.tag_name))) ?? [];
+    if (fromGitHub.length) return fromGitHub;
+    return whatsNew.releases.map((r) => ({
+      tag_name: r.version,
+      name: null,
+      body: r.body,
+      published_at: r.published_at,
+      html_url: '',
+      draft: false,
+      prerelease: false,
+    }));
+  }, [releases, whatsNew]);
+
   // Default the open changelog to what you just updated to (the `version` prop is
   // the installed version, i.e. the most recent release). If that exact tag isn't
   // in the fetched list, fall back to the newest release available. Re-evaluates
@@ -312,11 +335,11 @@ const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
   // cache — so a stale cache can't pin the popup to an older release. Skips once
   // the user has manually picked a version from the switcher.
   useEffect(() => {
-    if (userPickedRef.current || !releases || !releases.length) return;
+    if (userPickedRef.current || !shownReleases || !shownReleases.length) return;
     const wanted = normalizeTag(version);
-    const hasWanted = releases.some((r) => normalizeTag(r.tag_name) === wanted);
-    setSelectedTag(hasWanted ? wanted : normalizeTag(releases[0].tag_name));
-  }, [releases, version]);
+    const hasWanted = shownReleases.some((r) => normalizeTag(r.tag_name) === wanted);
+    setSelectedTag(hasWanted ? wanted : normalizeTag(shownReleases[0].tag_name));
+  }, [shownReleases, version]);
 
   // Close the version menu on an outside click.
   useEffect(() => {
@@ -330,12 +353,12 @@ const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
     return () => document.removeEventListener('mousedown', onDown);
   }, [menuOpen]);
 
-  const currentRelease = releases?.find((r) => normalizeTag(r.tag_name) === selectedTag);
+  const currentRelease = shownReleases?.find((r) => normalizeTag(r.tag_name) === selectedTag);
   const body = currentRelease?.body ?? fallbackBody;
   const displayVersion = currentRelease
     ? normalizeTag(currentRelease.tag_name)
     : normalizeTag(fallbackVersion);
-  const hasSwitcher = !!releases && releases.length > 1;
+  const hasSwitcher = !!shownReleases && shownReleases.length > 1;
 
   return (
     <motion.div
@@ -422,7 +445,7 @@ const ChangelogOverlay = ({ version, onClose }: ChangelogOverlayProps) => {
                     background: 'color-mix(in srgb, var(--color-background-tertiary) 98%, transparent)',
                   }}
                 >
-                  {releases!.map((r) => {
+                  {shownReleases!.map((r) => {
                     const tag = normalizeTag(r.tag_name);
                     const active = tag === selectedTag;
                     return (
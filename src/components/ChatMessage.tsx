@@ -28,6 +28,7 @@ import { MAJOR_COLOGNE_THEME_ID } from '../services/cologneEvent';
 import { matchHighlightPhrase, matchHighlightUser, matchHighlightBadge, type HighlightMatch } from '../utils/chatHighlightMatcher';
 import { flashTitle } from '../utils/titleFlasher';
 import { playSoundThrottled } from '../utils/notificationSound';
+import { playCueThrottled } from '../utils/audioCues';
 import { getDisplayedName, getColorOverride } from '../utils/userChatOverrides';
 import { CHANNEL_SPECIFIC_TWITCH_BADGES, orderTwitchBadges } from '../utils/badgeOrder';
 import { LinkPreviewCard } from './chat/LinkPreviewCard';
@@ -101,6 +102,7 @@ function getTwitchBadgeUrl(badgeKey: string, badgeInfo: { localUrl?: string; url
 import { BackendChatMessage } from '../services/twitchChat';
 
 import { Logger } from '../utils/logger';
+import { commandErrorMessage } from '../utils/commandError';
 
 interface ChatMessageProps {
   message: string | BackendChatMessage; // Raw IRC message or Backend Message Object
@@ -529,6 +531,7 @@ const ChatMessage = memo(function ChatMessageInner({ message, onUsernameClick, o
       }
     } catch (err) {
       Logger.error('[ChatMessage] mod ban/timeout failed:', err);
+      useAppStore.getState().addToast(commandErrorMessage(err, durationSeconds == null ? "Couldn't ban that user" : "Couldn't time out that user"), 'error');
     }
   };
 
@@ -549,6 +552,7 @@ const ChatMessage = memo(function ChatMessageInner({ message, onUsernameClick, o
       }
     } catch (err) {
       Logger.error('[ChatMessage] delete failed:', err);
+      useAppStore.getState().addToast(commandErrorMessage(err, "Couldn't delete that message"), 'error');
     }
   };
 
@@ -733,6 +737,18 @@ const ChatMessage = memo(function ChatMessageInner({ message, onUsernameClick, o
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [phraseMatch]);
 
+  // Mention audio cue, for @mentions and replies to the current user. Same
+  // backfill guard + cooldown as phrase sounds, so history loads stay quiet.
+  useEffect(() => {
+    if (!isMentioned && !isReplyToMe) return;
+    if (currentUser?.user_id && userId === currentUser.user_id) return;
+    const sentTsRaw = parsed.tags.get('tmi-sent-ts');
+    const sentTs = sentTsRaw ? parseInt(sentTsRaw, 10) : NaN;
+    playCueThrottled('mention', settings, Number.isFinite(sentTs) ? sentTs : null);
+    // Fire when the mention is detected, not on later settings changes.
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [isMentioned, isReplyToMe]);
+
   // Window-title flash. Fires on any highlight match (phrase/user/badge) when
   // the user has opted in globally AND the window is currently blurred.
   // Backfill-safe: skip messages older than 5s so loading history doesn't
@@ -2904,7 +2920,7 @@ const ChatMessage = memo(function ChatMessageInner({ message, onUsernameClick, o
                     usePinStore.getState().requestRefresh();
                   } catch (err) {
                     Logger.error('[ChatMessage] Pin/unpin failed:', err);
-                    useAppStore.getState().addToast(isThisPinned ? "Couldn't unpin that message" : "Couldn't pin that message", 'error');
+                    useAppStore.getState().addToast(commandErrorMessage(err, isThisPinned ? "Couldn't unpin that message" : "Couldn't pin that message"), 'error');
                   }
                 }}
                 className={`p-1.5 rounded-md transition-colors ${isThisPinned ? 'text-accent hover:text-red-400 hover:bg-red-500/15' : 'text-white/50 hover:text-accent hover:bg-accent/15'}`}
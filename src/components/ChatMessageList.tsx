@@ -1,7 +1,7 @@
 import React, { useRef, useEffect, useCallback, useMemo, memo } from 'react';
 import ChatMessage from './ChatMessage';
 import { EmoteSet } from '../services/emoteService';
-import { BackendChatMessage } from '../services/twitchChat';
+import { BackendChatMessage, type RenderHints } from '../services/twitchChat';
 import { ModerationContext } from '../hooks/useTwitchChat';
 import { useAppStore } from '../stores/AppStore';
 import { useChatUserStore } from '../stores/chatUserStore';
@@ -22,17 +22,25 @@ import type { ProviderId } from '../types/providers';
  * like it was sent many times over. Painting these (few) rows normally lets the
  * compositor track their scroll correctly. Plain rows have no composited layer,
  * never ghost, and keep full virtualization.
+ *
+ * The backend's render hints (services/render_hints.rs) pick out mentions,
+ * which also paint always, so they are laid out at their real height and
+ * never pop in while scrolling back to find them. A mention with animated
+ * emotes stays virtualized even then, so its animations stop costing frames
+ * once it scrolls away.
  */
 const MessageRow = function MessageRow({
   messageId,
   userId,
   isModFocus,
+  hints,
   intrinsicSizeCSS,
   children,
 }: {
   messageId: string | null;
   userId: string | undefined;
   isModFocus: boolean;
+  hints: RenderHints | undefined;
   intrinsicSizeCSS: string;
   children: React.ReactNode;
 }) {
@@ -43,17 +51,20 @@ const MessageRow = function MessageRow({
     // they need the same always-paint treatment to dodge the ghost bug.
     return !!(u?.atmosphereId || u?.cologne);
   });
+  const paintAlways = hasAtmosphere || (!!hints?.mention && !hints.animated_emotes);
   return (
     <div
       data-message-id={messageId || undefined}
       className={`chat-message-row${isModFocus ? ' is-mod-focus' : ''}`}
       style={{
         // Native virtualization for normal rows; atmosphere rows paint always
-        // to dodge the content-visibility compositing-ghost bug (see above).
-        contentVisibility: hasAtmosphere ? 'visible' : 'auto',
-        // Off-screen size hint, computed per-user from font size, spacing, and
-        // whether timestamps are on. Ignored when content-visibility is visible.
-        containIntrinsicBlockSize: hasAtmosphere ? undefined : intrinsicSizeCSS,
+        // to dodge the content-visibility compositing-ghost bug, and
+        // mentions so they never pop in (see above).
+        contentVisibility: paintAlways ? 'visible' : 'auto',
+        // Off-screen size hint, computed from font size, spacing, whether
+        // timestamps are on, and the message's length hint. Ignored when
+        // content-visibility is visible.
+        containIntrinsicBlockSize: paintAlways ? undefined : intrinsicSizeCSS,
       }}
     >
       {children}
@@ -61,6 +72,10 @@ const MessageRow = function MessageRow({
   );
 };
 
+// Content lines assumed for a row before it first renders, by the backend's
+// length hint (services/render_hints.rs).
+const HINT_LINES: Record<RenderHints['length'], number> = { short: 1, medium: 2, long: 4 };
+
 interface ChatMessageListProps {
   messages: (string | BackendChatMessage)[];
   isPaused: boolean;
@@ -162,7 +177,8 @@ const ChatMessageList = memo(function ChatMessageList({
   // FIRST render of each message — the prior hardcoded 50px was tuned for
   // single-line messages, which was way off when timestamps add a second line.
   // The `auto` keyword still lets the browser remember the actual size after
-  // first render, so this is just the initial-paint guess.
+  // first render, so this is just the initial-paint guess. Longer messages
+  // get more lines from the backend's length hint.
   const chatDesign = useAppStore((s) => s.settings.chat_design);
   const intrinsicSizeCSS = useMemo(() => {
     const fontSize = chatDesign?.font_size ?? 14;
@@ -175,8 +191,10 @@ const ChatMessageList = memo(function ChatMessageList({
     // Timestamp row is a 10px text line with line-height tight (~1.25) plus
     // mb-0.5 (~2px). Total ≈ 14-15px when enabled.
     const timestampHeight = chatDesign?.show_timestamps ? 15 : 0;
-    const total = contentLine + timestampHeight + padding + 1; // +1 for divider
-    return `auto ${total}px`;
+    return (lines: number) => {
+      const total = contentLine * lines + timestampHeight + padding + 1; // +1 for divider
+      return `auto ${total}px`;
+    };
   }, [chatDesign?.font_size, chatDesign?.message_spacing, chatDesign?.show_timestamps]);
 
   // Source provider logo (blended feed): a touch larger than the text and scaling
@@ -579,7 +597,12 @@ const ChatMessageList = memo(function ChatMessageList({
               messageId={messageId}
               userId={userId}
               isModFocus={!!modFocusId && messageId === modFocusId}
-              intrinsicSizeCSS={intrinsicSizeCSS}
+              hints={typeof message !== 'string' ? message.metadata?.render_hints : undefined}
+              intrinsicSizeCSS={intrinsicSizeCSS(
+                typeof message !== 'string'
+                  ? HINT_LINES[message.metadata?.render_hints?.length ?? 'short']
+                  : 1
+              )}
             >
               {sourceProvider ? (
                 <div className="flex items-center gap-1.5 pl-1">
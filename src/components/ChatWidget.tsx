@@ -96,6 +96,7 @@ import { SegmentedSelect } from './settings/_primitives';
 import type { TwitchStream, HypeTrainData } from '../types';
 
 import { Logger } from '../utils/logger';
+import { commandErrorMessage } from '../utils/commandError';
 import { useVisibleInterval } from '../utils/useVisibleInterval';
 
 // Channel Points hover tooltip — portalled to document.body to escape overflow-hidden
@@ -2095,7 +2096,7 @@ const ChatWidget = ({ channelOverride, hypeTrainOverride }: ChatWidgetProps = {}
         }
       } catch (err) {
         Logger.error('[Mod] keyboard action failed:', err);
-        useAppStore.getState().addToast('Moderation action failed', 'error');
+        useAppStore.getState().addToast(commandErrorMessage(err, 'Moderation action failed'), 'error');
       }
     };
 
@@ -3590,6 +3591,7 @@ const ChatWidget = ({ channelOverride, hypeTrainOverride }: ChatWidgetProps = {}
                               usePinStore.getState().requestRefresh();
                             } catch (err) {
                               Logger.error('[ChatWidget] Failed to unpin message:', err);
+                              useAppStore.getState().addToast(commandErrorMessage(err, "Couldn't unpin that message"), 'error');
                             }
                           }}
                           className="absolute top-2 right-2 z-10 p-1.5 rounded-lg text-textSecondary/70 hover:text-red-400 hover:bg-red-500/15 transition-colors pointer-events-auto"
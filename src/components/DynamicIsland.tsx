@@ -7,6 +7,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { useAppStore } from '../stores/AppStore';
 import { Logger } from '../utils/logger';
 import { playSound, type SoundId } from '../utils/notificationSound';
+import { isNotificationSoundMuted, playCue } from '../utils/audioCues';
 import { liveActivityText } from '../utils/liveActivity';
 import { Tooltip } from './ui/Tooltip';
 import type {
@@ -18,6 +19,10 @@ import type {
     ChannelPointsNotificationData,
     BadgeNotificationData,
     SystemNotificationData,
+    AutomationRuleNotification,
+    RecordingGuardStopped,
+    CampaignEligibility,
+    AudioCueEvent,
 } from '../types';
 
 const MAX_NOTIFICATIONS = 20;
@@ -216,7 +221,8 @@ const DynamicIsland = () => {
 
     const { startStream, settings, openWhisperWithUser, openSettings, addToast, setShowDropsOverlay, setShowBadgesOverlay, setUpdateInfo, isSettingsOpen } = useAppStore();
 
-    const soundEnabled = settings.live_notifications?.play_sound ?? true;
+    // Off with the Notification Sound toggle, and under Do Not Disturb.
+    const soundEnabled = !isNotificationSoundMuted(settings);
     const notificationsEnabled = settings.live_notifications?.enabled ?? true;
     const showLiveNotifications = settings.live_notifications?.show_live_notifications ?? true;
     const showWhisperNotifications = settings.live_notifications?.show_whisper_notifications ?? true;
@@ -299,9 +305,14 @@ const DynamicIsland = () => {
     // Play notification sound. Uses the shared Web-Audio engine (same one the
     // toast path uses) so the notification-center sound honors the user's chosen
     // Sound Style instead of a hardcoded tone, and reuses one AudioContext.
-    const playNotificationSound = useCallback(() => {
+    // Events with an audio cue play the cue's own sound and volume instead.
+    const playNotificationSound = useCallback((cue?: AudioCueEvent) => {
+        if (cue) {
+            playCue(cue, settings);
+            return;
+        }
         playSound((settings.live_notifications?.sound_type as SoundId | undefined) ?? 'boop');
-    }, [settings.live_notifications?.sound_type]);
+    }, [settings]);
 
     // Send native Windows desktop notification (disabled - plugin not installed)
     const sendNativeNotification = useCallback(async (_title: string, _body: string) => {
@@ -475,7 +486,7 @@ const DynamicIsland = () => {
                 if (useToast) {
                     emit('show-live-toast', data);
                 } else if (soundEnabled) {
-                    playNotificationSound();
+                    playNotificationSound('live');
                 }
                 return;
             }
@@ -510,7 +521,7 @@ const DynamicIsland = () => {
             // ToastManager plays it; otherwise the notification-center entry
             // covers it here. This keeps the two paths from doubling up.
             if (soundEnabled && useDynamicIsland && !useToast) {
-                playNotificationSound();
+                playNotificationSound('live');
             }
 
             // Send native notification (note: backend also sends one, but this ensures frontend settings are respected)
@@ -562,7 +573,7 @@ const DynamicIsland = () => {
                 addNotification(notification);
 
                 if (soundEnabled) {
-                    playNotificationSound();
+                    playNotificationSound('whisper');
                 }
             }
 
@@ -623,7 +634,7 @@ const DynamicIsland = () => {
                 addNotification(notification);
 
                 if (soundEnabled) {
-                    playNotificationSound();
+                    playNotificationSound('drop_claimed');
                 }
             }
 
@@ -652,7 +663,49 @@ const DynamicIsland = () => {
         };
     }, [addNotification, notificationsEnabled, showDropsNotifications, useDynamicIsland, useToast, addToast, soundEnabled, playNotificationSound, setShowDropsOverlay, sendNativeNotification]);
 
-    // Listen for channel points earned notifications with clustering
+    // Automation rules with a notify action
+    useEffect(() => {
+        const unlisten = listen<AutomationRuleNotification>('automation-rule-notification', (event) => {
+            if (!notificationsEnabled) return;
+            addToast(event.payload.message, 'info');
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, [notificationsEnabled, addToast]);
+
+    // Recordings the recording guard stopped (limit or low disk space)
+    useEffect(() => {
+        const unlisten = listen<RecordingGuardStopped>('recording-guard-stopped', (event) => {
+            const { channel_login, reason, free_mb } = event.payload;
+            const message = reason === 'low_disk'
+                ? `Stopped recording ${channel_login}: only ${free_mb ?? 0} MB of disk space left`
+                : `Stopped recording ${channel_login}: too many recordings at once`;
+            addToast(message, 'warning');
+            sendNativeNotification('Recording stopped', message);
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, [addToast, sendNativeNotification]);
+
+    // Drops mining on a campaign or channel the account can't earn on
+    useEffect(() => {
+        const unlisten = listen<CampaignEligibility>('ineligible-campaign', (event) => {
+            const { campaign_name, channel_login, reasons } = event.payload;
+            const name = campaign_name || 'this campaign';
+            const message = `Mining ${name} on ${channel_login} won't earn drops: ${reasons.map((r) => r.message).join('; ')}`;
+            addToast(message, 'warning');
+            sendNativeNotification('Drops not earnable', message);
+        });
+
+        return () => {
+            unlisten.then((fn) => fn());
+        };
+    }, [addToast, sendNativeNotification]);
+
     // Ref to track clustered channel points
     const channelPointsClusterRef = useRef<ClusteredChannelPoints>({
         totalPoints: 0,
@@ -1,5 +1,5 @@
 import { Window } from '@tauri-apps/api/window';
-import { Gift, User, Settings, Store, Proportions, MessageCircle, Pickaxe, Clock, Tv, Download, LogIn, Sparkles, Check } from 'lucide-react';
+import { Gift, User, Settings, Store, Proportions, PictureInPicture2, MessageCircle, Pickaxe, Clock, Tv, Download, LogIn, Sparkles, Check } from 'lucide-react';
 import { Minus, X, CornersOut, CornersIn, ArrowsOut, ArrowsIn, Medal } from 'phosphor-react';
 import { useState, useEffect, useLayoutEffect, useRef, useMemo, useCallback } from 'react';
 import { createPortal } from 'react-dom';
@@ -43,7 +43,7 @@ const getUpdateStageProgress = (stage: string | null): number => {
 const TitleBar = () => {
   const store = useAppStore();
 
-  const { openSettings, setShowDropsOverlay, setShowMarketplaceOverlay, setShowBadgesOverlay, setShowWhispersOverlay, isAuthenticated, currentUser, dropProgressActive, dropProgressComplete, isTheaterMode, toggleTheaterMode, isWindowFullscreen, toggleWindowFullscreen, streamUrl, settings, whisperImportState, updateInfo, addToast } = store;
+  const { openSettings, setShowDropsOverlay, setShowMarketplaceOverlay, setShowBadgesOverlay, setShowWhispersOverlay, isAuthenticated, currentUser, dropProgressActive, dropProgressComplete, isTheaterMode, toggleTheaterMode, isMiniMode, toggleMiniMode, isWindowFullscreen, toggleWindowFullscreen, streamUrl, settings, whisperImportState, updateInfo, addToast } = store;
   // Count of installed plugins with an update available, for the Marketplace badge.
   const pluginUpdateCount = usePluginUpdates((s) => s.ids.length);
   // Update flow: 'idle' → 'installing' (download/extract) → 'installed' (staged;
@@ -594,6 +594,11 @@ const TitleBar = () => {
                       <span className="text-textSecondary truncate max-w-[160px]">
                         {dropProgress.current_channel.display_name}
                       </span>
+                      {dropProgress.current_channel.language && (
+                        <span className="text-[10px] uppercase text-textMuted">
+                          {dropProgress.current_channel.language}
+                        </span>
+                      )}
                     </div>
                   )}
                 </div>
@@ -789,6 +794,18 @@ const TitleBar = () => {
               </button>
             </Tooltip>
           )}
+
+          {/* Mini Player Button - corner player, always on top */}
+          {streamUrl && (
+            <Tooltip content={isMiniMode ? 'Exit Mini Player' : 'Mini Player'} delay={200}>
+              <button
+                onClick={() => toggleMiniMode()}
+                className={`titlebar-icon-btn ${isMiniMode ? '!text-accent !bg-accent/15' : ''}`}
+              >
+                <PictureInPicture2 size={14} />
+              </button>
+            </Tooltip>
+          )}
           </div>
 
           {/* Window controls — kept adjacent but not grouped into a pill */}
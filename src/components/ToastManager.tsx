@@ -7,7 +7,7 @@ import { parseEmojisProxied, EmojiSegment } from '../services/emojiService';
 import { ToastPosition, DEFAULT_TOAST_POSITION, DEFAULT_TOAST_EDGE_OFFSET } from '../types';
 
 import { Logger } from '../utils/logger';
-import { playSound, type SoundId } from '../utils/notificationSound';
+import { playCue } from '../utils/audioCues';
 import { liveActivityText } from '../utils/liveActivity';
 interface LiveNotification {
   streamer_name: string;
@@ -182,13 +182,6 @@ const ToastManager = () => {
     settings.live_notifications?.toast_edge_offset ?? DEFAULT_TOAST_EDGE_OFFSET,
   );
 
-  // Thin wrapper so existing callers keep the (soundType?: string) signature.
-  // Actual sound generation lives in utils/notificationSound so chat highlights
-  // can share the same AudioContext + envelope library.
-  const playNotificationSound = useCallback((soundType?: string) => {
-    playSound((soundType as SoundId | undefined) ?? 'boop');
-  }, []);
-
   // Listen for live stream notifications from backend
   // This creates the decorated toast with avatar, game image, title
   // Only shows if use_toast setting is enabled (checked by DynamicIsland which emits custom event)
@@ -196,10 +189,8 @@ const ToastManager = () => {
     const unlisten = listen<LiveNotification>('show-live-toast', (event) => {
       const notification = event.payload;
 
-      // Play sound if enabled
-      if (settings.live_notifications?.play_sound) {
-        playNotificationSound(settings.live_notifications?.sound_type);
-      }
+      // Play the go-live audio cue (skipped when sounds are off or muted)
+      playCue('live', settings);
 
       // Create a rich notification message with all available data
       const toastContent = (
@@ -289,7 +280,7 @@ const ToastManager = () => {
     return () => {
       unlisten.then((fn) => fn());
     };
-  }, [addToast, settings.live_notifications?.play_sound, settings.live_notifications?.sound_type, playNotificationSound]);
+  }, [addToast, settings]);
 
   const getToastIcon = (type: string) => {
     switch (type) {
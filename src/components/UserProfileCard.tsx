@@ -10,6 +10,7 @@ import { computePaintStyle, getBadgeImageUrls, getBadgeFallbackUrls, queueCosmet
 import { FallbackImage } from './FallbackImage';
 import { formatIVRDate, formatSubTenure } from '../services/ivrService';
 import { Logger } from '../utils/logger';
+import { commandErrorMessage } from '../utils/commandError';
 import {
   getProfileFromMemoryCache,
   getFullProfileWithFallback,
@@ -1699,7 +1700,7 @@ const UserProfileCard = ({
                           useAppStore.getState().addToast(`Purged messages for ${login}`, 'success');
                         } catch (err) {
                           Logger.error('[UserProfileCard] Failed to purge:', err);
-                          useAppStore.getState().addToast('Failed to purge user', 'error');
+                          useAppStore.getState().addToast(commandErrorMessage(err, 'Failed to purge user'), 'error');
                         }
                       }}
                       className="py-1.5 glass-button text-xs font-semibold text-white/70 hover:text-white hover:bg-orange-500/20 border hover:border-orange-500/30 rounded flex items-center justify-center transition-colors"
@@ -1717,7 +1718,7 @@ const UserProfileCard = ({
                           useAppStore.getState().addToast(`Timed out ${login} for 10m`, 'success');
                         } catch (err) {
                           Logger.error('[UserProfileCard] Failed to timeout:', err);
-                          useAppStore.getState().addToast('Failed to timeout user', 'error');
+                          useAppStore.getState().addToast(commandErrorMessage(err, 'Failed to timeout user'), 'error');
                         }
                       }}
                       className="py-1.5 glass-button text-xs font-semibold text-white/70 hover:text-white hover:bg-yellow-500/20 border hover:border-yellow-500/30 rounded flex items-center justify-center transition-colors"
@@ -1735,7 +1736,7 @@ const UserProfileCard = ({
                           useAppStore.getState().addToast(`Timed out ${login} for 24h`, 'success');
                         } catch (err) {
                           Logger.error('[UserProfileCard] Failed to timeout:', err);
-                          useAppStore.getState().addToast('Failed to timeout user', 'error');
+                          useAppStore.getState().addToast(commandErrorMessage(err, 'Failed to timeout user'), 'error');
                         }
                       }}
                       className="py-1.5 glass-button text-xs font-semibold text-white/70 hover:text-white hover:bg-orange-600/20 border hover:border-orange-600/30 rounded flex items-center justify-center transition-colors"
@@ -1755,7 +1756,7 @@ const UserProfileCard = ({
                             onClose();
                           } catch (err) {
                             Logger.error('[UserProfileCard] Failed to ban:', err);
-                            useAppStore.getState().addToast('Failed to ban user', 'error');
+                            useAppStore.getState().addToast(commandErrorMessage(err, 'Failed to ban user'), 'error');
                           }
                         }
                       }}
@@ -1775,7 +1776,7 @@ const UserProfileCard = ({
                           useAppStore.getState().addToast(`Unbanned ${login}`, 'success');
                         } catch (err) {
                           Logger.error('[UserProfileCard] Failed to unban:', err);
-                          useAppStore.getState().addToast('Failed to unban user', 'error');
+                          useAppStore.getState().addToast(commandErrorMessage(err, 'Failed to unban user'), 'error');
                         }
                       }
                     }}
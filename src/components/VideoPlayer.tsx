@@ -1,5 +1,6 @@
 import { useRef, useEffect, useCallback, useState } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import Hls from 'hls.js';
 import Plyr from 'plyr';
 import 'plyr/dist/plyr.css';
@@ -28,7 +29,7 @@ import {
   audioBoostFaderDefs,
   audioBoostResetPatch,
 } from '../utils/audioBoost';
-import type { AudioBoostSettings } from '../types';
+import type { AudioBoostSettings, Squad } from '../types';
 import { Fader, Toggle } from './AudioBoostFaders';
 import { open as openExternalUrl } from '@tauri-apps/plugin-shell';
 import { setActiveVideo } from '../utils/activeVideo';
@@ -1817,7 +1818,13 @@ const VideoPlayer = () => {
         const v = videoRef.current;
         return v && Number.isFinite(v.currentTime) ? v.currentTime : null;
       },
+      getDuration: () => {
+        const v = videoRef.current;
+        return v && Number.isFinite(v.duration) ? v.duration : null;
+      },
+      isPaused: () => videoRef.current?.paused ?? true,
       togglePlay: () => playerRef.current?.togglePlay(),
+      isMuted: () => playerRef.current?.muted ?? true,
       toggleMute: () => {
         const p = playerRef.current;
         if (p) p.muted = !p.muted;
@@ -2074,6 +2081,44 @@ const VideoPlayer = () => {
     await exitStream({ preserveBackend: true });
   }, [currentStream, exitStream]);
 
+  // Co-streams (services::squad_streams): when the channel turns out to be live
+  // together with others, offer to watch them all. Taking the offer makes the
+  // backend raise `open-multiview` with the lineup, which replaces the grid and
+  // hands over to MultiNook the same way as the button above.
+  useEffect(() => {
+    const unlistenDetected = listen<Squad>('squad-detected', (event) => {
+      const squad = event.payload;
+      const { currentStream: playing, addToast } = useAppStore.getState();
+      if (!playing || squad.channel_login !== playing.user_login.toLowerCase()) return;
+      const names = squad.partners.map((p) => p.display_name).join(', ');
+      addToast(`${playing.user_name} is live with ${names}`, 'info', {
+        label: 'Watch all',
+        onClick: () => {
+          invoke('launch_squad_multiview', { channelLogin: squad.channel_login }).catch((e) =>
+            addToast(typeof e === 'string' ? e : 'Could not open the squad', 'error')
+          );
+        },
+      });
+    });
+    const unlistenOpen = listen<{ channels: string[] }>('open-multiview', async (event) => {
+      const playing = useAppStore.getState().currentStream;
+      const mn = usemultiNookStore.getState();
+      await mn.loadPresetChannels(
+        event.payload.channels.map((channelLogin) => ({ channelLogin })),
+        'replace'
+      );
+      if (playing?.user_id) mn.setActiveChatChannelId(playing.user_id);
+      if (!usemultiNookStore.getState().isMultiNookActive) {
+        usemultiNookStore.getState().toggleMultiNook();
+      }
+      if (playing) await exitStream({ preserveBackend: true });
+    });
+    return () => {
+      unlistenDetected.then((unlisten) => unlisten());
+      unlistenOpen.then((unlisten) => unlisten());
+    };
+  }, [exitStream]);
+
   // Letterbox fill behind the object-fit video. Cinema Mode keeps it solid black;
   // otherwise it mirrors the title bar exactly (its secondary tint composited over
   // the app base), so the bars and title bar read as one surface and track theme
@@ -10,6 +10,7 @@ import { Tooltip } from './ui/Tooltip';
 import type { WhisperConversation, Whisper, UserInfo } from '../types';
 
 import { Logger } from '../utils/logger';
+import { playCue } from '../utils/audioCues';
 interface WhisperFromBackend {
     from_user_id: string;
     from_user_login: string;
@@ -517,26 +518,10 @@ const WhispersWidget = ({ isOpen, onClose }: WhispersWidgetProps) => {
         return () => document.removeEventListener('mousedown', handleClickOutside);
     }, []);
 
-    // Play notification sound
+    // Play the whisper audio cue (its sound, volume, and the global mute)
     const playNotificationSound = useCallback(() => {
-        try {
-            const audioContext = new (window.AudioContext || (window as unknown as { webkitAudioContext: typeof AudioContext }).webkitAudioContext)();
-            const oscillator = audioContext.createOscillator();
-            const gainNode = audioContext.createGain();
-            oscillator.connect(gainNode);
-            gainNode.connect(audioContext.destination);
-            oscillator.type = 'sine';
-            oscillator.frequency.setValueAtTime(520, audioContext.currentTime);
-            oscillator.frequency.exponentialRampToValueAtTime(380, audioContext.currentTime + 0.12);
-            gainNode.gain.setValueAtTime(0, audioContext.currentTime);
-            gainNode.gain.linearRampToValueAtTime(0.07, audioContext.currentTime + 0.02);
-            gainNode.gain.exponentialRampToValueAtTime(0.001, audioContext.currentTime + 0.25);
-            oscillator.start(audioContext.currentTime);
-            oscillator.stop(audioContext.currentTime + 0.25);
-        } catch (err) {
-            Logger.warn('Could not play notification sound:', err);
-        }
-    }, []);
+        playCue('whisper', settings);
+    }, [settings]);
 
     // Listen for incoming whispers
     useEffect(() => {
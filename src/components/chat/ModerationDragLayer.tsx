@@ -9,6 +9,7 @@ import { computePaintStyle } from '../../services/seventvService';
 import { useDragModerationStore } from '../../stores/dragModerationStore';
 import { usePinStore } from '../../stores/pinStore';
 import { Logger } from '../../utils/logger';
+import { commandErrorMessage } from '../../utils/commandError';
 
 type BucketKind = 'neutral' | 'danger';
 interface Bucket {
@@ -260,7 +261,7 @@ export default function ModerationDragLayer() {
               .then(() => app.addToast(`Deleted a message from ${displayName}`, 'success'))
               .catch((err) => {
                 Logger.error('[DragMod] Delete failed:', err);
-                app.addToast(`Couldn't delete that message`, 'error');
+                app.addToast(commandErrorMessage(err, `Couldn't delete that message`), 'error');
               });
           }
           break;
@@ -275,7 +276,7 @@ export default function ModerationDragLayer() {
             .then(() => app.addToast(`Timed out ${displayName} for ${formatDuration(s)}`, 'success', undo))
             .catch((err) => {
               Logger.error('[DragMod] Timeout failed:', err);
-              app.addToast(`Couldn't time out ${displayName}`, 'error');
+              app.addToast(commandErrorMessage(err, `Couldn't time out ${displayName}`), 'error');
             });
           break;
         }
@@ -289,7 +290,7 @@ export default function ModerationDragLayer() {
             .then(() => app.addToast(`Banned ${displayName}`, 'success', undo))
             .catch((err) => {
               Logger.error('[DragMod] Ban failed:', err);
-              app.addToast(`Couldn't ban ${displayName}`, 'error');
+              app.addToast(commandErrorMessage(err, `Couldn't ban ${displayName}`), 'error');
             });
           break;
         case 'pin':
@@ -301,7 +302,7 @@ export default function ModerationDragLayer() {
               })
               .catch((err) => {
                 Logger.error('[DragMod] Pin failed:', err);
-                app.addToast(`Couldn't pin that message`, 'error');
+                app.addToast(commandErrorMessage(err, `Couldn't pin that message`), 'error');
               });
           }
           break;
@@ -314,7 +315,7 @@ export default function ModerationDragLayer() {
               })
               .catch((err) => {
                 Logger.error('[DragMod] Unpin failed:', err);
-                app.addToast(`Couldn't unpin that message`, 'error');
+                app.addToast(commandErrorMessage(err, `Couldn't unpin that message`), 'error');
               });
           }
           break;
@@ -6,6 +6,7 @@ import { Tooltip } from '../ui/Tooltip';
 import { GlassSelect } from '../ui/GlassSelect';
 import { invoke } from '@tauri-apps/api/core';
 import { Logger } from '../../utils/logger';
+import { commandErrorMessage } from '../../utils/commandError';
 import { useAppStore } from '../../stores/AppStore';
 
 interface ModeratorMenuProps {
@@ -74,6 +75,7 @@ const ModeratorMenu: React.FC<ModeratorMenuProps> = ({ broadcasterId, roomState
       
     } catch (err) {
       Logger.error(`[ModeratorMenu] Failed to update chat setting ${setting}:`, err);
+      useAppStore.getState().addToast(commandErrorMessage(err, "Couldn't change that chat setting"), 'error');
     } finally {
       setIsPending(false);
     }
@@ -90,6 +92,7 @@ const ModeratorMenu: React.FC<ModeratorMenuProps> = ({ broadcasterId, roomState
       setIsOpen(false);
     } catch (err) {
       Logger.error('[ModeratorMenu] Failed to clear chat:', err);
+      useAppStore.getState().addToast(commandErrorMessage(err, "Couldn't clear chat"), 'error');
     } finally {
       setIsPending(false);
     }
@@ -172,6 +175,7 @@ const ModeratorMenu: React.FC<ModeratorMenuProps> = ({ broadcasterId, roomState
                            Logger.debug(`[ModeratorMenu] Updated slow mode to ${numericVal}s`);
                         }).catch(err => {
                            Logger.error(`[ModeratorMenu] Failed to update slow mode:`, err);
+                           useAppStore.getState().addToast(commandErrorMessage(err, "Couldn't change slow mode"), 'error');
                         }).finally(() => {
                            setIsPending(false);
                         });
@@ -0,0 +1,136 @@
+// Mining channel scoring: the weights (DropsSettings.score_weights) and a
+// preview of how they rank the live channels of a campaign, from
+// preview_channel_scores. The drops add-on is sent the same weights, so the
+// preview is what it would pick from.
+
+import { useState, useEffect } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Loader2 } from 'lucide-react';
+import { Dropdown } from '../ui/Dropdown';
+import { Logger } from '../../utils/logger';
+import type { ChannelScore, ChannelScoreWeights, DropCampaign } from '../../types';
+
+export const DEFAULT_SCORE_WEIGHTS: ChannelScoreWeights = {
+  priority_bonus: 100,
+  acl_bonus: 50,
+  viewer_weight: 10,
+  uptime_penalty: 20,
+  language_bonus: 25,
+};
+
+const WEIGHT_FIELDS: { key: keyof ChannelScoreWeights; label: string; hint: string }[] = [
+  { key: 'priority_bonus', label: 'Priority channel', hint: 'Added for channels on your priority list' },
+  { key: 'acl_bonus', label: 'Campaign channel', hint: "Added for channels on the campaign's own list" },
+  { key: 'viewer_weight', label: 'Viewers', hint: 'Per tenfold viewers; negative favors small channels' },
+  { key: 'uptime_penalty', label: 'Uptime penalty', hint: "Taken off in full from 13 hours live, or half once past the channel's usual length" },
+  { key: 'language_bonus', label: 'Language', hint: 'Added for your first preferred language' },
+];
+
+interface ChannelScorePreviewProps {
+  weights?: ChannelScoreWeights;
+  onChange: (weights: ChannelScoreWeights) => void;
+}
+
+export default function ChannelScorePreview({ weights, onChange }: ChannelScorePreviewProps) {
+  const current = { ...DEFAULT_SCORE_WEIGHTS, ...(weights ?? {}) };
+  const [campaigns, setCampaigns] = useState<DropCampaign[]>([]);
+  const [campaignId, setCampaignId] = useState('');
+  const [scores, setScores] = useState<ChannelScore[] | null>(null);
+  const [isLoading, setIsLoading] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<DropCampaign[]>('get_active_drop_campaigns')
+      .then((list) => setCampaigns([...list].sort((a, b) => a.game_name.localeCompare(b.game_name))))
+      .catch((err) => Logger.warn('[ChannelScores] Failed to load campaigns:', err));
+  }, []);
+
+  const preview = async () => {
+    if (!campaignId) return;
+    setIsLoading(true);
+    setError(null);
+    try {
+      setScores(await invoke<ChannelScore[]>('preview_channel_scores', { campaignId }));
+    } catch (err) {
+      setError(String(err));
+      setScores(null);
+    } finally {
+      setIsLoading(false);
+    }
+  };
+
+  return (
+    <div className="space-y-4">
+      <div className="grid grid-cols-2 gap-3">
+        {WEIGHT_FIELDS.map((field) => (
+          <label key={field.key} className="text-xs text-textSecondary" title={field.hint}>
+            {field.label}
+            <input
+              type="number"
+              step="any"
+              value={current[field.key]}
+              onChange={(e) => {
+                const value = parseFloat(e.target.value);
+                if (Number.isFinite(value)) onChange({ ...current, [field.key]: value });
+              }}
+              className="mt-1 w-full px-3 py-2 glass-input text-textPrimary text-sm focus:outline-none"
+            />
+          </label>
+        ))}
+        <div className="flex items-end">
+          <button
+            onClick={() => onChange(DEFAULT_SCORE_WEIGHTS)}
+            className="glass-button w-full px-3 py-2 rounded-lg text-textSecondary hover:text-textPrimary text-sm"
+          >
+            Reset to defaults
+          </button>
+        </div>
+      </div>
+
+      <div className="flex gap-2">
+        <Dropdown
+          value={campaignId}
+          onChange={setCampaignId}
+          className="flex-1"
+          ariaLabel="Campaign to preview"
+          options={[
+            { value: '', label: 'Pick a campaign to preview' },
+            ...campaigns.map((c) => ({ value: c.id, label: `${c.game_name}: ${c.name}` })),
+          ]}
+        />
+        <button
+          onClick={preview}
+          disabled={!campaignId || isLoading}
+          className="glass-button px-4 py-2 rounded-lg text-textPrimary disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium"
+        >
+          {isLoading ? <Loader2 size={16} className="animate-spin" /> : 'Preview'}
+        </button>
+      </div>
+
+      {error && <p className="text-xs text-red-400">{error}</p>}
+      {scores && scores.length === 0 && (
+        <p className="text-xs text-textSecondary italic">No channel can earn this campaign right now.</p>
+      )}
+      {scores && scores.length > 0 && (
+        <div className="space-y-1 max-h-72 overflow-y-auto custom-scrollbar">
+          {scores.slice(0, 25).map((s, index) => (
+            <div
+              key={s.channel_id}
+              className={`flex items-center gap-3 bg-background px-3 py-2 rounded-lg border border-borderLight text-sm ${s.skipped ? 'opacity-50' : ''}`}
+              title={`Priority ${s.breakdown.priority.toFixed(1)} · Campaign ${s.breakdown.acl.toFixed(1)} · Viewers ${s.breakdown.viewers.toFixed(1)} · Uptime ${s.breakdown.uptime.toFixed(1)} · Language ${s.breakdown.language.toFixed(1)}`}
+            >
+              <span className="text-textSecondary font-mono text-xs w-6 text-center">{index + 1}</span>
+              <span className="flex-1 truncate text-textPrimary">
+                {s.display_name}
+                {s.language && <span className="ml-2 text-xs uppercase text-textSecondary">{s.language}</span>}
+                {s.skipped && <span className="ml-2 text-xs text-amber-400">{s.skipped}</span>}
+              </span>
+              <span className="text-xs text-textSecondary">{s.viewer_count.toLocaleString()} viewers</span>
+              <span className="font-mono text-xs text-textPrimary w-14 text-right">{s.score.toFixed(1)}</span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
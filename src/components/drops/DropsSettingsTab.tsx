@@ -1,9 +1,11 @@
 import { useState, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
-import { Settings, TrendingUp, X, Plus, Ban, Star, Shield, Lock, Users, ListFilter, LayoutList, Activity, Loader2, Heart } from 'lucide-react';
+import { Settings, TrendingUp, X, Plus, Ban, Star, Shield, Lock, Users, ListFilter, LayoutList, Activity, Loader2, Heart, Languages, Scale } from 'lucide-react';
 import { Logger } from '../../utils/logger';
 import { useAppStore } from '../../stores/AppStore';
 import { Dropdown } from '../ui/Dropdown';
+import ChannelScorePreview from './ChannelScorePreview';
+import type { ChannelScoreWeights } from '../../types';
 
 type RecoveryMode = 'Automatic' | 'Relaxed' | 'ManualOnly';
 
@@ -35,6 +37,10 @@ interface DropsSettings {
     auto_reserve_on_watch?: boolean;
     priority_channels?: Array<{ channel_id: string; channel_login: string; display_name: string }>;
     prefer_favorites?: boolean;
+    preferred_languages?: string[];
+    min_viewers?: number | null;
+    max_viewers?: number | null;
+    score_weights?: ChannelScoreWeights;
 }
 
 interface ChannelSearchResult {
@@ -148,6 +154,7 @@ export default function DropsSettingsTab({
     const [activeTab, setActiveTab] = useState<'drops' | 'channel_points'>('drops');
     const [priorityInput, setPriorityInput] = useState('');
     const [excludedInput, setExcludedInput] = useState('');
+    const [languageInput, setLanguageInput] = useState('');
     const [channelInput, setChannelInput] = useState('');
     const [isSearching, setIsSearching] = useState(false);
     const [searchResults, setSearchResults] = useState<ChannelSearchResult[]>([]);
@@ -258,6 +265,28 @@ export default function DropsSettingsTab({
         onUpdateSettings({ priority_games: newPriority });
     };
 
+    const preferredLanguages = settings.preferred_languages ?? [];
+
+    // Two-letter broadcast language codes, as Twitch reports them ("en", "de").
+    const addLanguage = () => {
+        const code = languageInput.trim().toLowerCase();
+        if (/^[a-z]{2}$/.test(code) && !preferredLanguages.includes(code)) {
+            onUpdateSettings({ preferred_languages: [...preferredLanguages, code] });
+            setLanguageInput('');
+        }
+    };
+
+    const removeLanguage = (code: string) => {
+        onUpdateSettings({ preferred_languages: preferredLanguages.filter((c) => c !== code) });
+    };
+
+    // Empty clears a bound.
+    const setViewerBound = (key: 'min_viewers' | 'max_viewers', value: string) => {
+        const parsed = parseInt(value, 10);
+        const bound = Number.isFinite(parsed) && parsed >= 0 ? parsed : null;
+        onUpdateSettings(key === 'min_viewers' ? { min_viewers: bound } : { max_viewers: bound });
+    };
+
     const addExcludedGame = () => {
         const game = excludedInput.trim();
         if (game && !settings.excluded_games.includes(game)) {
@@ -510,6 +539,140 @@ export default function DropsSettingsTab({
                                 </div>
                             </div>
 
+                            {/* Stream Languages Card */}
+                            <div className="glass-panel p-6">
+                                <div className="flex justify-between items-center mb-4">
+                                    <h4 className="text-base font-semibold text-textPrimary flex items-center gap-2">
+                                        <Languages size={18} className="text-sky-400" />
+                                        Stream Languages
+                                    </h4>
+                                    <span className="text-xs text-textSecondary bg-glass px-2 py-1 rounded">
+                                        Preferred first
+                                    </span>
+                                </div>
+
+                                <p className="text-xs text-textSecondary mb-4">
+                                    Channels broadcasting in these languages are picked before others, in this order. Others are still used when none of these is live.
+                                </p>
+
+                                <div className="flex flex-wrap gap-2 mb-4">
+                                    {preferredLanguages.length > 0 ? (
+                                        preferredLanguages.map((code) => (
+                                            <span
+                                                key={code}
+                                                className="flex items-center gap-1.5 bg-background px-3 py-1.5 rounded-lg border border-borderLight text-sm text-textPrimary uppercase font-mono"
+                                            >
+                                                {code}
+                                                <button
+                                                    onClick={() => removeLanguage(code)}
+                                                    className="text-textSecondary hover:text-red-400 transition-colors"
+                                                    aria-label={`Remove ${code}`}
+                                                >
+                                                    <X size={12} />
+                                                </button>
+                                            </span>
+                                        ))
+                                    ) : (
+                                        <div className="w-full text-xs text-textSecondary italic text-center p-4 bg-background/50 rounded-lg border border-dashed border-borderLight">
+                                            No preference. Channels are picked in any language.
+                                        </div>
+                                    )}
+                                </div>
+
+                                <div className="flex gap-2">
+                                    <input
+                                        type="text"
+                                        placeholder="Language code, e.g. en"
+                                        maxLength={2}
+                                        value={languageInput}
+                                        onChange={(e) => setLanguageInput(e.target.value)}
+                                        onKeyDown={(e) => {
+                                            if (e.key === 'Enter') {
+                                                addLanguage();
+                                            }
+                                        }}
+                                        className="flex-1 px-4 py-2.5 glass-input text-textPrimary text-sm placeholder:text-textSecondary focus:outline-none"
+                                    />
+                                    <button
+                                        onClick={addLanguage}
+                                        disabled={!/^[a-zA-Z]{2}$/.test(languageInput.trim())}
+                                        className="glass-button px-4 py-2.5 rounded-lg text-textPrimary disabled:opacity-50 disabled:cursor-not-allowed text-sm font-medium flex items-center gap-1.5"
+                                    >
+                                        <Plus size={16} />
+                                        Add
+                                    </button>
+                                </div>
+                            </div>
+
+                            {/* Channel Size Card */}
+                            <div className="glass-panel p-6">
+                                <div className="flex justify-between items-center mb-4">
+                                    <h4 className="text-base font-semibold text-textPrimary flex items-center gap-2">
+                                        <Users size={18} className="text-violet-400" />
+                                        Channel Size
+                                    </h4>
+                                    <span className="text-xs text-textSecondary bg-glass px-2 py-1 rounded">
+                                        Viewer count
+                                    </span>
+                                </div>
+
+                                <p className="text-xs text-textSecondary mb-4">
+                                    Only mine on channels within these viewer counts. Set a maximum to spend your watch time on smaller streamers. Leave a field empty for no limit.
+                                </p>
+
+                                <div className="flex gap-3">
+                                    <label className="flex-1 text-xs text-textSecondary">
+                                        At least
+                                        <input
+                                            type="number"
+                                            min={0}
+                                            placeholder="No minimum"
+                                            value={settings.min_viewers ?? ''}
+                                            onChange={(e) => setViewerBound('min_viewers', e.target.value)}
+                                            className="mt-1 w-full px-4 py-2.5 glass-input text-textPrimary text-sm placeholder:text-textSecondary focus:outline-none"
+                                        />
+                                    </label>
+                                    <label className="flex-1 text-xs text-textSecondary">
+                                        At most
+                                        <input
+                                            type="number"
+                                            min={0}
+                                            placeholder="No maximum"
+                                            value={settings.max_viewers ?? ''}
+                                            onChange={(e) => setViewerBound('max_viewers', e.target.value)}
+                                            className="mt-1 w-full px-4 py-2.5 glass-input text-textPrimary text-sm placeholder:text-textSecondary focus:outline-none"
+                                        />
+                                    </label>
+                                </div>
+                                {settings.min_viewers != null && settings.max_viewers != null && settings.min_viewers > settings.max_viewers && (
+                                    <p className="mt-2 text-xs text-amber-400">
+                                        The minimum is above the maximum, so no channel qualifies.
+                                    </p>
+                                )}
+                            </div>
+
+                            {/* Channel Scoring Card */}
+                            <div className="glass-panel p-6">
+                                <div className="flex justify-between items-center mb-4">
+                                    <h4 className="text-base font-semibold text-textPrimary flex items-center gap-2">
+                                        <Scale size={18} className="text-amber-400" />
+                                        Channel Scoring
+                                    </h4>
+                                    <span className="text-xs text-textSecondary bg-glass px-2 py-1 rounded">
+                                        Highest wins
+                                    </span>
+                                </div>
+
+                                <p className="text-xs text-textSecondary mb-4">
+                                    How channels are ranked when mining picks one. Preview a campaign to see how these weights order its live channels.
+                                </p>
+
+                                <ChannelScorePreview
+                                    weights={settings.score_weights}
+                                    onChange={(weights) => onUpdateSettings({ score_weights: weights })}
+                                />
+                            </div>
+
                             {/* Recovery Settings Card */}
                             <div className="glass-panel p-6">
                                 <div className="flex justify-between items-center mb-4">
@@ -91,6 +91,11 @@ export default function DropsStatsTab({
                                 <span className="text-textSecondary">Channel</span>
                                 <span className="text-textPrimary font-medium font-mono">
                                     {dropProgress.current_channel.display_name || dropProgress.current_channel.name}
+                                    {dropProgress.current_channel.language && (
+                                        <span className="ml-2 text-xs uppercase text-textSecondary">
+                                            {dropProgress.current_channel.language}
+                                        </span>
+                                    )}
                                 </span>
                             </div>
 
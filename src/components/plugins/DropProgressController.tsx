@@ -45,6 +45,7 @@ interface ProviderStatusValue {
     game_name?: string | null;
     campaign_id?: string | null;
     channel_login?: string | null;
+    channel_language?: string | null;
     drop_name?: string | null;
     current_minutes?: number | null;
     required_minutes?: number | null;
@@ -184,6 +185,7 @@ export default function DropProgressController() {
                               viewer_count: 0,
                               is_live: true,
                               drops_enabled: true,
+                              language: v.channel_language ?? null,
                           }
                         : null;
                     let dropName = (v.drop_name ?? '').trim();
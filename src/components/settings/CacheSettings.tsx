@@ -1,8 +1,53 @@
 import { useAppStore } from '../../stores/AppStore';
 import { SettingsSection, SettingsRow } from './_primitives';
 import EmotePrefetchSection from './EmotePrefetchSection';
+import ChannelCachesSection from './ChannelCachesSection';
 
 import { Logger } from '../../utils/logger';
+import type { CacheBundleSummary } from '../../types';
+
+const bundleFilters = [{ name: 'StreamNook cache bundle', extensions: ['zip'] }];
+
+const exportBundle = async () => {
+  try {
+    const { save } = await import('@tauri-apps/plugin-dialog');
+    const path = await save({
+      title: 'Export cache bundle',
+      defaultPath: `streamnook-cache-${new Date().toISOString().slice(0, 10)}.zip`,
+      filters: bundleFilters,
+    });
+    if (!path) return; // cancelled
+    const { invoke } = await import('@tauri-apps/api/core');
+    const summary = await invoke<CacheBundleSummary>('export_manifest_bundle', { path });
+    useAppStore
+      .getState()
+      .addToast(`Exported ${summary.entries} cache entries (${summary.files} files).`, 'success');
+  } catch (error) {
+    Logger.error('Failed to export cache bundle:', error);
+    useAppStore.getState().addToast('Export failed: ' + error, 'error');
+  }
+};
+
+const importBundle = async () => {
+  try {
+    const { open } = await import('@tauri-apps/plugin-dialog');
+    const path = await open({ title: 'Import cache bundle', multiple: false, directory: false, filters: bundleFilters });
+    if (!path || typeof path !== 'string') return; // cancelled
+    const { invoke } = await import('@tauri-apps/api/core');
+    const summary = await invoke<CacheBundleSummary>('import_manifest_bundle', { path });
+    useAppStore
+      .getState()
+      .addToast(
+        `Imported ${summary.entries} cache entries` +
+          (summary.skipped ? ` (${summary.skipped} already cached).` : '.'),
+        'success'
+      );
+  } catch (error) {
+    Logger.error('Failed to import cache bundle:', error);
+    useAppStore.getState().addToast('Import failed: ' + error, 'error');
+  }
+};
+
 const CacheSettings = () => {
   const { settings, updateSettings } = useAppStore();
 
@@ -60,7 +105,7 @@ const CacheSettings = () => {
 
         <SettingsRow
           title="Cache Maintenance"
-          description="View cache statistics or delete all cached emotes and badges"
+          description="View cache statistics, repair files left broken by an interrupted write, or delete all cached emotes and badges"
         >
           <div className="flex gap-2">
             <button
@@ -104,6 +149,41 @@ const CacheSettings = () => {
             >
               Open Folder
             </button>
+            <button
+              onClick={async () => {
+                try {
+                  const { invoke } = await import('@tauri-apps/api/core');
+                  const report = (await invoke('verify_universal_cache_integrity', { repair: true })) as {
+                    entries_checked: number;
+                    broken_entries: string[];
+                    orphaned_files: string[];
+                    leftover_temp_files: string[];
+                    manifest_recovered: boolean;
+                  };
+                  const fixes = [
+                    report.broken_entries.length && `${report.broken_entries.length} broken entries`,
+                    report.orphaned_files.length && `${report.orphaned_files.length} stray files`,
+                    report.leftover_temp_files.length && `${report.leftover_temp_files.length} half-written files`,
+                    report.manifest_recovered && 'an unreadable manifest',
+                  ].filter(Boolean);
+                  Logger.debug('[Cache] Integrity report:', report);
+                  useAppStore
+                    .getState()
+                    .addToast(
+                      fixes.length
+                        ? `Cache repaired: ${fixes.join(', ')}`
+                        : `Cache OK (${report.entries_checked} entries checked)`,
+                      fixes.length ? 'success' : 'info'
+                    );
+                } catch (error) {
+                  Logger.error('Failed to verify cache:', error);
+                  useAppStore.getState().addToast('Failed to check cache: ' + error, 'error');
+                }
+              }}
+              className="px-4 py-2 bg-secondary hover:bg-surface-hover text-textPrimary text-sm font-medium rounded transition-all"
+            >
+              Check &amp; Repair
+            </button>
             <button
               onClick={async () => {
                 try {
@@ -124,8 +204,30 @@ const CacheSettings = () => {
             </button>
           </div>
         </SettingsRow>
+
+        <SettingsRow
+          title="Offline Bundle"
+          description="Save every cached emote, badge and cosmetic with its image files to a zip, and load it on another PC (or one behind a firewall) so it starts with a full cache"
+        >
+          <div className="flex gap-2">
+            <button
+              onClick={exportBundle}
+              className="px-4 py-2 bg-secondary hover:bg-surface-hover text-textPrimary text-sm font-medium rounded transition-all"
+            >
+              Export Bundle
+            </button>
+            <button
+              onClick={importBundle}
+              className="px-4 py-2 bg-secondary hover:bg-surface-hover text-textPrimary text-sm font-medium rounded transition-all"
+            >
+              Import Bundle
+            </button>
+          </div>
+        </SettingsRow>
       </SettingsSection>
 
+      <ChannelCachesSection />
+
       <EmotePrefetchSection />
     </div>
   );
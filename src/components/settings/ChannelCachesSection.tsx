@@ -0,0 +1,97 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { Pin, PinOff } from 'lucide-react';
+import { SettingsSection, SettingsRow } from './_primitives';
+import { useAppStore } from '../../stores/AppStore';
+import type { ChannelCache } from '../../types';
+import { Logger } from '../../utils/logger';
+
+function formatSize(bytes: number): string {
+  const kb = bytes / 1024;
+  return kb < 1024 ? `${Math.max(1, Math.round(kb))} KB` : `${(kb / 1024).toFixed(1)} MB`;
+}
+
+function formatAge(secs: number): string {
+  const days = Math.floor((Date.now() / 1000 - secs) / 86400);
+  return days <= 0 ? 'today' : days === 1 ? 'yesterday' : `${days} days ago`;
+}
+
+// Per-channel caches (emote dictionaries, channel badges) and their cleanup;
+// see services::channel_caches.
+const ChannelCachesSection = () => {
+  const { settings, updateSettings, followedStreams } = useAppStore();
+  const [channels, setChannels] = useState<ChannelCache[] | null>(null);
+  const expiryDays = settings.cache?.channel_expiry_days ?? 30;
+  const pinned = settings.cache?.pinned_channel_caches ?? [];
+
+  useEffect(() => {
+    invoke<ChannelCache[]>('get_channel_caches')
+      .then(setChannels)
+      .catch((e) => Logger.warn('[ChannelCaches] list failed:', e));
+  }, []);
+
+  const nameOf = (channelId: string) =>
+    followedStreams.find((s) => s.user_id === channelId)?.user_name ?? `Channel ${channelId}`;
+
+  const togglePin = (channelId: string) => {
+    const next = pinned.includes(channelId) ? pinned.filter((id) => id !== channelId) : [...pinned, channelId];
+    updateSettings({ ...settings, cache: { ...settings.cache, pinned_channel_caches: next } });
+  };
+
+  return (
+    <SettingsSection label="Channel Caches">
+      <SettingsRow
+        title={expiryDays > 0 ? `Forget Unvisited Channels After: ${expiryDays} days` : 'Forget Unvisited Channels: Never'}
+        description="Each channel you open keeps its emote list and badges on disk. Channels you haven't opened for this long have them removed (they're fetched again on your next visit). Pinned channels are always kept."
+      >
+        <input
+          type="range"
+          min="0"
+          max="180"
+          step="5"
+          value={expiryDays}
+          onChange={(e) =>
+            updateSettings({
+              ...settings,
+              cache: { ...settings.cache, channel_expiry_days: parseInt(e.target.value) },
+            })
+          }
+          className="w-full accent-accent cursor-pointer"
+        />
+      </SettingsRow>
+
+      <SettingsRow title="Cached Channels" description="Least recently opened first. Pin a channel to keep its caches forever.">
+        {channels === null ? (
+          <p className="text-xs text-textSecondary">Loading...</p>
+        ) : channels.length === 0 ? (
+          <p className="text-xs text-textSecondary italic">No channel caches yet.</p>
+        ) : (
+          <div className="space-y-1 max-h-64 overflow-y-auto custom-scrollbar">
+            {channels.map((c) => {
+              const isPinned = pinned.includes(c.channel_id);
+              return (
+                <div
+                  key={c.channel_id}
+                  className="flex items-center gap-3 bg-background px-3 py-2 rounded-lg border border-borderLight text-sm"
+                >
+                  <span className="flex-1 truncate text-textPrimary">{nameOf(c.channel_id)}</span>
+                  <span className="text-xs text-textSecondary">{formatAge(c.last_accessed)}</span>
+                  <span className="text-xs text-textSecondary w-16 text-right">{formatSize(c.bytes)}</span>
+                  <button
+                    onClick={() => togglePin(c.channel_id)}
+                    title={isPinned ? 'Unpin (allow cleanup)' : 'Pin (keep forever)'}
+                    className={`p-1 rounded transition-colors ${isPinned ? 'text-accent' : 'text-textSecondary hover:text-textPrimary'}`}
+                  >
+                    {isPinned ? <Pin size={14} /> : <PinOff size={14} />}
+                  </button>
+                </div>
+              );
+            })}
+          </div>
+        )}
+      </SettingsRow>
+    </SettingsSection>
+  );
+};
+
+export default ChannelCachesSection;
@@ -323,6 +323,14 @@ const ChatSettings = ({ hidePlacement = false }: { hidePlacement?: boolean } = {
       chat_logging: { ...logging, ...patch },
     });
 
+  const spamCollapse = settings.chat_spam_collapse ?? {};
+  const spamCollapseEnabled = spamCollapse.enabled ?? false;
+  const setSpamCollapse = (patch: Partial<NonNullable<typeof settings.chat_spam_collapse>>) =>
+    updateSettings({
+      ...settings,
+      chat_spam_collapse: { ...spamCollapse, ...patch },
+    });
+
   // The folder logs land in right now (custom or default), resolved by the
   // backend so the displayed path always matches what the writer uses.
   const [logDir, setLogDir] = useState('');
@@ -459,6 +467,48 @@ const ChatSettings = ({ hidePlacement = false }: { hidePlacement?: boolean } = {
             />
           }
         />
+        <SettingsRow
+          title="Collapse repeated messages"
+          description="When chat floods with the same message or emote wall, show the first few copies and fold the rest into one counting row, like '×37 PogChamp'."
+          control={
+            <Toggle
+              enabled={spamCollapseEnabled}
+              onChange={() => setSpamCollapse({ enabled: !spamCollapseEnabled })}
+            />
+          }
+        />
+        {spamCollapseEnabled && (
+          <>
+            <SettingsRow
+              title={`Copies shown first: ${spamCollapse.threshold ?? 3}`}
+              description="How many copies appear as normal messages before the rest are counted."
+            >
+              <input
+                type="range"
+                min="1"
+                max="10"
+                step="1"
+                value={spamCollapse.threshold ?? 3}
+                onChange={(e) => setSpamCollapse({ threshold: parseInt(e.target.value) })}
+                className="w-full accent-accent cursor-pointer"
+              />
+            </SettingsRow>
+            <SettingsRow
+              title={`Flood ends after: ${spamCollapse.window_secs ?? 10}s`}
+              description="A message stops counting as a flood once nobody has repeated it for this long."
+            >
+              <input
+                type="range"
+                min="5"
+                max="60"
+                step="5"
+                value={spamCollapse.window_secs ?? 10}
+                onChange={(e) => setSpamCollapse({ window_secs: parseInt(e.target.value) })}
+                className="w-full accent-accent cursor-pointer"
+              />
+            </SettingsRow>
+          </>
+        )}
       </SettingsSection>
 
       <SettingsSection label="Chat Logging">
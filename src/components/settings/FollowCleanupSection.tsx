@@ -0,0 +1,228 @@
+import { useEffect, useRef, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Loader2, Search, Undo2, User, UserMinus } from 'lucide-react';
+import { useAppStore } from '../../stores/AppStore';
+import type { InactiveFollow, UnfollowBatchComplete, UnfollowBatchProgress } from '../../types';
+import { Logger } from '../../utils/logger';
+
+const DAY_OPTIONS = [30, 90, 180, 365];
+
+/**
+ * Find followed channels that stopped streaming and unfollow them in one go.
+ * The unfollows run paced in the background (services::follow_batch); for a
+ * short while after they finish, Undo follows everything back.
+ */
+export default function FollowCleanupSection() {
+  const addToast = useAppStore((s) => s.addToast);
+  const [days, setDays] = useState(90);
+  const [follows, setFollows] = useState<InactiveFollow[] | null>(null);
+  const [scanning, setScanning] = useState(false);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [batchId, setBatchId] = useState<number | null>(null);
+  const [progress, setProgress] = useState<UnfollowBatchProgress | null>(null);
+  const [undoBatch, setUndoBatch] = useState<number | null>(null);
+  // Channels the last batch removed from the list, to put back on undo.
+  const removedRef = useRef<InactiveFollow[]>([]);
+  const batchIdRef = useRef<number | null>(null);
+  batchIdRef.current = batchId ?? undoBatch;
+
+  useEffect(() => {
+    const unlistenProgress = listen<UnfollowBatchProgress>('unfollow-batch-progress', (event) => {
+      if (event.payload.batch_id === batchIdRef.current) setProgress(event.payload);
+    });
+    const unlistenComplete = listen<UnfollowBatchComplete>('unfollow-batch-complete', (event) => {
+      const result = event.payload;
+      if (result.batch_id !== batchIdRef.current) return;
+      setBatchId(null);
+      setProgress(null);
+      if (result.undo) {
+        const followed = new Set(result.followed ?? []);
+        const restored = removedRef.current.filter((f) => followed.has(f.channel_id));
+        setFollows((current) => [...restored, ...(current ?? [])]);
+        removedRef.current = [];
+        setUndoBatch(null);
+        addToast(`Followed ${followed.size} channels again`, 'success');
+        return;
+      }
+      const unfollowed = new Set(result.unfollowed ?? []);
+      setFollows((current) => {
+        removedRef.current = (current ?? []).filter((f) => unfollowed.has(f.channel_id));
+        return (current ?? []).filter((f) => !unfollowed.has(f.channel_id));
+      });
+      setSelected(new Set());
+      addToast(
+        result.failed.length > 0
+          ? `Unfollowed ${unfollowed.size} channels (${result.failed.length} failed)`
+          : `Unfollowed ${unfollowed.size} channels`,
+        result.failed.length > 0 ? 'warning' : 'success'
+      );
+      if (unfollowed.size > 0 && result.undo_secs) {
+        setUndoBatch(result.batch_id);
+        setTimeout(
+          () => setUndoBatch((current) => (current === result.batch_id ? null : current)),
+          result.undo_secs * 1000
+        );
+      }
+    });
+    return () => {
+      unlistenProgress.then((unlisten) => unlisten());
+      unlistenComplete.then((unlisten) => unlisten());
+    };
+  }, [addToast]);
+
+  const handleScan = async () => {
+    setScanning(true);
+    try {
+      const result = await invoke<InactiveFollow[]>('get_inactive_follows', { days });
+      setFollows(result);
+      // Nothing ticked until the user picks what goes.
+      setSelected(new Set());
+    } catch (e) {
+      Logger.error('[FollowCleanup] Scan failed:', e);
+      addToast(typeof e === 'string' ? e : 'Could not check your follows', 'error');
+    } finally {
+      setScanning(false);
+    }
+  };
+
+  const handleUnfollow = async () => {
+    try {
+      setUndoBatch(null);
+      setBatchId(await invoke<number>('unfollow_channels', { ids: [...selected] }));
+    } catch (e) {
+      addToast(typeof e === 'string' ? e : 'Could not start unfollowing', 'error');
+    }
+  };
+
+  const handleUndo = async () => {
+    if (undoBatch === null) return;
+    try {
+      await invoke<number>('undo_unfollow_channels', { batchId: undoBatch });
+      setBatchId(undoBatch);
+    } catch (e) {
+      setUndoBatch(null);
+      addToast(typeof e === 'string' ? e : 'Could not undo', 'error');
+    }
+  };
+
+  const toggle = (channelId: string) =>
+    setSelected((current) => {
+      const next = new Set(current);
+      if (next.has(channelId)) next.delete(channelId);
+      else next.add(channelId);
+      return next;
+    });
+
+  const running = batchId !== null;
+
+  return (
+    <div className="glass-panel rounded-xl p-4 space-y-4">
+      <div className="flex items-center justify-between gap-3">
+        <h4 className="text-sm font-semibold text-textPrimary uppercase tracking-wide">Inactive Follows</h4>
+        <div className="flex items-center gap-2">
+          <select
+            value={days}
+            onChange={(e) => setDays(parseInt(e.target.value))}
+            disabled={scanning || running}
+            className="glass-input text-sm px-2 py-1.5"
+          >
+            {DAY_OPTIONS.map((d) => (
+              <option key={d} value={d}>
+                No stream in {d} days
+              </option>
+            ))}
+          </select>
+          <button
+            onClick={handleScan}
+            disabled={scanning || running}
+            className="flex items-center gap-1.5 px-3 py-1.5 text-sm font-medium glass-button disabled:cursor-wait disabled:opacity-70"
+          >
+            {scanning ? <Loader2 size={14} className="animate-spin" /> : <Search size={14} />}
+            Find
+          </button>
+        </div>
+      </div>
+
+      <p className="text-xs text-textMuted">
+        Followed channels that haven't gone live in a while, or never have. Unfollowing runs in the
+        background at a pace Twitch allows, and can be undone for a short time afterwards.
+      </p>
+
+      {follows !== null && follows.length === 0 && (
+        <p className="text-xs text-textSecondary italic">Every channel you follow has streamed in the last {days} days.</p>
+      )}
+
+      {follows !== null && follows.length > 0 && (
+        <div className="space-y-1 max-h-72 overflow-y-auto custom-scrollbar">
+          {follows.map((f) => (
+            <label
+              key={f.channel_id}
+              className="flex items-center gap-3 rounded-lg px-3 py-2 bg-white/[0.03] cursor-pointer"
+            >
+              <input
+                type="checkbox"
+                checked={selected.has(f.channel_id)}
+                onChange={() => toggle(f.channel_id)}
+                disabled={running}
+                className="accent-accent"
+              />
+              {f.profile_image_url ? (
+                <img src={f.profile_image_url} alt="" className="w-7 h-7 rounded-full flex-shrink-0" />
+              ) : (
+                <div className="w-7 h-7 rounded-full bg-white/5 flex items-center justify-center flex-shrink-0">
+                  <User size={14} className="text-textMuted" />
+                </div>
+              )}
+              <span className="flex-1 min-w-0 text-sm text-textPrimary truncate">{f.display_name || f.login}</span>
+              <span className="text-xs text-textSecondary flex-shrink-0">
+                {f.days_inactive == null ? 'Never streamed' : `${f.days_inactive} days ago`}
+              </span>
+            </label>
+          ))}
+        </div>
+      )}
+
+      {(running || undoBatch !== null || (follows?.length ?? 0) > 0) && (
+        <div className="flex items-center justify-end gap-2">
+          {running && progress && (
+            <span className="text-xs text-textSecondary mr-auto">
+              {progress.undo ? 'Following back' : 'Unfollowing'} {progress.done}/{progress.total}
+              {progress.failed > 0 && ` (${progress.failed} failed)`}
+            </span>
+          )}
+          {undoBatch !== null && !running && (
+            <button
+              onClick={handleUndo}
+              className="flex items-center gap-1.5 px-3 py-1.5 text-sm font-medium glass-button"
+            >
+              <Undo2 size={14} />
+              Undo
+            </button>
+          )}
+          {(follows?.length ?? 0) > 0 && (
+            <button
+              onClick={() =>
+                setSelected(
+                  selected.size === follows?.length ? new Set() : new Set(follows?.map((f) => f.channel_id))
+                )
+              }
+              disabled={running}
+              className="px-3 py-1.5 text-sm font-medium glass-button disabled:opacity-50"
+            >
+              {selected.size === follows?.length ? 'Select none' : 'Select all'}
+            </button>
+          )}
+          <button
+            onClick={handleUnfollow}
+            disabled={running || selected.size === 0}
+            className="flex items-center gap-1.5 px-3 py-1.5 text-sm font-medium text-red-400 glass-button disabled:opacity-50"
+          >
+            {running ? <Loader2 size={14} className="animate-spin" /> : <UserMinus size={14} />}
+            Unfollow {selected.size > 0 ? selected.size : ''} selected
+          </button>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -3,7 +3,9 @@ import { Eye, EyeOff, Columns, X, Sparkles, Gauge, Zap } from 'lucide-react';
 import CompactViewSettings from './CompactViewSettings';
 import { SettingsSection, SettingsRow } from './_primitives';
 import { useAppStore } from '../../stores/AppStore';
-import type { MotionMode } from '../../types';
+import { Dropdown } from '../ui/Dropdown';
+import { invoke } from '@tauri-apps/api/core';
+import type { MonitorInfo, MotionMode } from '../../types';
 
 export type SidebarMode = 'expanded' | 'compact' | 'hidden' | 'disabled';
 
@@ -56,7 +58,20 @@ const MOTION_MODE_OPTIONS: { value: MotionMode; label: string; hint: string; Ico
 ];
 
 const InterfaceSettings = () => {
-    const { settings, updateSettings } = useAppStore();
+    const { settings, updateSettings, setAlwaysOnTop, fullscreenOnMonitor } = useAppStore();
+    const [monitors, setMonitors] = useState<MonitorInfo[]>([]);
+    const [fullscreenMonitor, setFullscreenMonitor] = useState(0);
+
+    useEffect(() => {
+        invoke<MonitorInfo[]>('get_monitors')
+            .then((list) => {
+                setMonitors(list);
+                // Default to a display other than the one the window is on.
+                const other = list.find((m) => !m.current);
+                if (other) setFullscreenMonitor(other.index);
+            })
+            .catch(() => { /* no main window to ask about (headless) */ });
+    }, []);
     const [sidebarMode, setSidebarMode] = useState<SidebarMode>('compact');
     const [expandOnHover, setExpandOnHover] = useState(true);
     const [showRecommended, setShowRecommended] = useState(true);
@@ -217,6 +232,44 @@ const InterfaceSettings = () => {
                 />
             </SettingsSection>
 
+            <SettingsSection id="settings-section-window" label="Window">
+                <SettingsRow
+                    title="Always on top"
+                    description="Keep StreamNook above other windows. The mini player (title bar, or a global hotkey) is always on top while it's open."
+                    control={
+                        <Toggle
+                            enabled={!!settings.always_on_top}
+                            onChange={() => void setAlwaysOnTop(!settings.always_on_top)}
+                        />
+                    }
+                />
+                {monitors.length > 1 && (
+                    <SettingsRow
+                        title="Full screen on a display"
+                        description="Move StreamNook to another display in borderless full screen. Press F11 to leave full screen."
+                    >
+                        <div className="flex items-center gap-3">
+                            <Dropdown
+                                value={String(fullscreenMonitor)}
+                                onChange={(v) => setFullscreenMonitor(parseInt(v))}
+                                className="flex-1"
+                                ariaLabel="Display"
+                                options={monitors.map((m) => ({
+                                    value: String(m.index),
+                                    label: `Display ${m.index + 1}${m.name ? ` (${m.name})` : ''}, ${m.width}x${m.height}${m.primary ? ', primary' : ''}`,
+                                }))}
+                            />
+                            <button
+                                onClick={() => void fullscreenOnMonitor(fullscreenMonitor)}
+                                className="glass-button px-4 py-2 rounded-lg text-textPrimary text-sm font-medium"
+                            >
+                                Full screen
+                            </button>
+                        </div>
+                    </SettingsRow>
+                )}
+            </SettingsSection>
+
             <div id="settings-section-compact">
                 <CompactViewSettings />
             </div>
@@ -6,8 +6,13 @@ import {
   ToastPosition,
   DEFAULT_TOAST_POSITION,
   DEFAULT_TOAST_EDGE_OFFSET,
+  type AudioCue,
+  type AudioCueEvent,
+  type AudioCueSettings,
+  type WavInfo,
 } from '../../types';
 import { invoke } from '@tauri-apps/api/core';
+import { open } from '@tauri-apps/plugin-dialog';
 import { Bell } from 'lucide-react';
 import { SettingsSection, SettingsRow } from './_primitives';
 import { grantAccolade } from '../../services/supabaseService';
@@ -18,6 +23,11 @@ import {
 } from '../../utils/notifAchievement';
 
 import { Logger } from '../../utils/logger';
+import { SOUND_LABELS, type SoundId } from '../../utils/notificationSound';
+import { AUDIO_CUE_LABELS, getAudioCue, isBuiltInSound, previewCue } from '../../utils/audioCues';
+
+// Dropdown value standing in for "a WAV file"; picking it opens the file picker.
+const CUSTOM_SOUND = '__custom__';
 
 // The six supported toast anchors, placed on a 3x3 grid that stands in for the
 // screen. The middle row's sides are intentionally inert (vertically-centered
@@ -135,6 +145,46 @@ const NotificationsSettings = () => {
     });
   };
 
+  const audioCues: AudioCueSettings = settings.audio_cues ?? {
+    muted: false,
+    mention: getAudioCue(settings, 'mention'),
+    whisper: getAudioCue(settings, 'whisper'),
+    drop_claimed: getAudioCue(settings, 'drop_claimed'),
+    live: getAudioCue(settings, 'live'),
+  };
+  const doNotDisturb = !!liveNotifications.do_not_disturb || audioCues.muted;
+
+  // Do Not Disturb and the cue mute are one switch; the backend keeps them in
+  // step too (audio_cues::sync_mute).
+  const setDoNotDisturb = (enabled: boolean) => {
+    updateSettings({
+      ...settings,
+      live_notifications: { ...liveNotifications, do_not_disturb: enabled },
+      audio_cues: { ...audioCues, muted: enabled },
+    });
+  };
+
+  const updateAudioCue = (event: AudioCueEvent, updates: Partial<AudioCue>) => {
+    updateSettings({
+      ...settings,
+      audio_cues: { ...audioCues, [event]: { ...audioCues[event], ...updates } },
+    });
+  };
+
+  const pickCueFile = async (event: AudioCueEvent) => {
+    const path = await open({
+      multiple: false,
+      filters: [{ name: 'WAV audio', extensions: ['wav'] }],
+    });
+    if (typeof path !== 'string') return;
+    try {
+      await invoke<WavInfo | null>('check_audio_cue_sound', { sound: path });
+      updateAudioCue(event, { sound: path });
+    } catch (error) {
+      addToast(`Can't use that file: ${error}`, 'error');
+    }
+  };
+
   const handleTestNotification = async () => {
     // Snap to green, then let it fade back to the glass surface (the fade-back
     // lives on the glass-button class transition). Brief hold so it reads as a flash.
@@ -390,6 +440,69 @@ const NotificationsSettings = () => {
               </SettingsRow>
             )}
 
+            <SettingsRow
+              title="Do Not Disturb"
+              description="Silence every notification sound, including audio cues"
+              control={
+                <Toggle
+                  enabled={doNotDisturb}
+                  onChange={() => setDoNotDisturb(!doNotDisturb)}
+                />
+              }
+            />
+
+            {liveNotifications.play_sound && (Object.keys(AUDIO_CUE_LABELS) as AudioCueEvent[]).map((event) => {
+              const cue = getAudioCue(settings, event);
+              const soundValue = !cue.sound || isBuiltInSound(cue.sound) ? cue.sound : CUSTOM_SOUND;
+              return (
+                <SettingsRow
+                  key={event}
+                  title={AUDIO_CUE_LABELS[event]}
+                  description={soundValue === CUSTOM_SOUND ? cue.sound : undefined}
+                  disabled={doNotDisturb}
+                  control={
+                    <Toggle
+                      enabled={cue.enabled}
+                      onChange={() => updateAudioCue(event, { enabled: !cue.enabled })}
+                    />
+                  }
+                >
+                  {cue.enabled && (
+                    <div className="flex items-center gap-3">
+                      <Dropdown
+                        value={soundValue}
+                        onChange={(v) => (v === CUSTOM_SOUND ? pickCueFile(event) : updateAudioCue(event, { sound: v }))}
+                        className="w-48"
+                        ariaLabel={`${AUDIO_CUE_LABELS[event]} sound`}
+                        options={[
+                          { value: '', label: 'Sound Style' },
+                          ...(Object.keys(SOUND_LABELS) as SoundId[]).map((id) => ({ value: id, label: SOUND_LABELS[id] })),
+                          { value: CUSTOM_SOUND, label: 'Custom WAV...' },
+                        ]}
+                      />
+                      <input
+                        type="range"
+                        min="0"
+                        max="100"
+                        step="5"
+                        value={cue.volume}
+                        onChange={(e) => updateAudioCue(event, { volume: parseInt(e.target.value) })}
+                        className="flex-1 accent-accent cursor-pointer"
+                        aria-label={`${AUDIO_CUE_LABELS[event]} volume`}
+                      />
+                      <span className="text-xs text-textSecondary w-9 text-right">{cue.volume}%</span>
+                      <button
+                        onClick={() => previewCue(cue, settings)}
+                        className="glass-button px-3 py-1.5 rounded-lg text-textPrimary text-xs font-medium"
+                      >
+                        Play
+                      </button>
+                    </div>
+                  )}
+                </SettingsRow>
+              );
+            })}
+
             <SettingsRow
               title="Test Notification"
               description="Send a test notification to preview your settings"
@@ -1,12 +1,14 @@
+import { useState } from 'react';
 import { Dropdown } from '../ui/Dropdown';
 import { useAppStore } from '../../stores/AppStore';
 import { SettingsSection, SettingsRow, SegmentedSelect } from './_primitives';
-import { DEFAULT_AUDIO_BOOST, DEFAULT_SONG_ID } from '../../types';
+import { DEFAULT_AUDIO_BOOST, DEFAULT_SONG_ID, type GameModeSettings } from '../../types';
 import { Fader } from '../AudioBoostFaders';
 import { audioBoostFaderDefs, audioBoostResetPatch } from '../../utils/audioBoost';
 import { reportCodecPreference } from '../../utils/codecPreference';
 import { invoke } from '@tauri-apps/api/core';
 import { LL_TARGET_DEFAULT } from '../../utils/latency';
+import RaidEventsSection from './RaidEventsSection';
 
 const Toggle = ({ enabled, onChange }: { enabled: boolean; onChange: () => void }) => (
   <button
@@ -77,6 +79,33 @@ const PlayerSettings = () => {
     });
   };
 
+  // Away detection: reads the OS idle time in the backend (services/idle_monitor.rs).
+  const idle = {
+    enabled: false,
+    threshold_mins: 10,
+    pause_playback: false,
+    discord_idle: true,
+    ...(settings.idle ?? {}),
+  };
+  const setIdle = (patch: Partial<typeof idle>) => {
+    updateSettings({ ...settings, idle: { ...idle, ...patch } });
+  };
+
+  // Game mode (services/game_mode.rs). The watch list is edited as text and
+  // saved on blur, so typing a name doesn't save on every key.
+  const gameMode: GameModeSettings = {
+    enabled: false,
+    executables: [],
+    pause_prefetch: true,
+    mining_playback: 'audio_only',
+    slow_polls: true,
+    ...(settings.game_mode ?? {}),
+  };
+  const setGameMode = (patch: Partial<GameModeSettings>) => {
+    updateSettings({ ...settings, game_mode: { ...gameMode, ...patch } });
+  };
+  const [gameListDraft, setGameListDraft] = useState<string | null>(null);
+
   const setAutoSwitch = (patch: Partial<NonNullable<typeof autoSwitch>>) => {
     updateSettings({
       ...settings,
@@ -544,6 +573,132 @@ const PlayerSettings = () => {
           />
         </SettingsRow>
       </SettingsSection>
+
+      <SettingsSection
+        id="settings-section-idle"
+        label="When You're Away"
+        description="Notice when there's been no keyboard or mouse input for a while. Drops mining is never paused. Windows only."
+      >
+        <SettingsRow
+          title="Detect When I'm Away"
+          control={<Toggle enabled={idle.enabled} onChange={() => setIdle({ enabled: !idle.enabled })} />}
+        />
+        {idle.enabled && (
+          <>
+            <SettingsRow
+              title={`Away After: ${idle.threshold_mins} min`}
+              description="How long without input before you count as away."
+            >
+              <input
+                type="range"
+                min="1"
+                max="60"
+                step="1"
+                value={idle.threshold_mins}
+                onChange={(e) => setIdle({ threshold_mins: parseInt(e.target.value, 10) })}
+                className="w-full accent-accent cursor-pointer"
+              />
+            </SettingsRow>
+            <SettingsRow
+              title="Pause Playback While Away"
+              description="Resumes when you're back, if it was playing."
+              control={
+                <Toggle
+                  enabled={idle.pause_playback}
+                  onChange={() => setIdle({ pause_playback: !idle.pause_playback })}
+                />
+              }
+            />
+            <SettingsRow
+              title="Show Idle on Discord"
+              description="Swap the watching presence for the idle one while you're away."
+              control={
+                <Toggle
+                  enabled={idle.discord_idle}
+                  onChange={() => setIdle({ discord_idle: !idle.discord_idle })}
+                />
+              }
+            />
+          </>
+        )}
+      </SettingsSection>
+
+      <SettingsSection
+        id="settings-section-game-mode"
+        label="While Gaming"
+        description="When one of these games is running, StreamNook steps back to leave bandwidth and CPU for it, and picks up again when the game exits."
+      >
+        <SettingsRow
+          title="Game Mode"
+          control={
+            <Toggle enabled={gameMode.enabled} onChange={() => setGameMode({ enabled: !gameMode.enabled })} />
+          }
+        />
+        {gameMode.enabled && (
+          <>
+            <SettingsRow
+              title="Games"
+              description="One executable per line, for example eldenring.exe. Case and folder don't matter."
+            >
+              <textarea
+                value={gameListDraft ?? gameMode.executables.join('\n')}
+                onChange={(e) => setGameListDraft(e.target.value)}
+                onBlur={() => {
+                  if (gameListDraft === null) return;
+                  setGameMode({
+                    executables: gameListDraft
+                      .split('\n')
+                      .map((line) => line.trim())
+                      .filter(Boolean),
+                  });
+                  setGameListDraft(null);
+                }}
+                placeholder="eldenring.exe"
+                rows={3}
+                className="w-full rounded-md border border-white/10 bg-black/30 px-2.5 py-1.5 text-sm text-textPrimary font-mono"
+              />
+            </SettingsRow>
+            <SettingsRow
+              title="Mined Stream"
+              description="What drops mining plays while a game runs. Needs a drops add-on that supports it."
+            >
+              <Dropdown
+                value={gameMode.mining_playback}
+                onChange={(v) => setGameMode({ mining_playback: v as GameModeSettings['mining_playback'] })}
+                className="w-full"
+                ariaLabel="Mined stream while gaming"
+                options={[
+                  { value: 'audio_only', label: 'Audio only' },
+                  { value: 'none', label: 'Nothing (progress only)' },
+                  { value: 'keep', label: 'Leave as is' },
+                ]}
+              />
+            </SettingsRow>
+            <SettingsRow
+              title="Pause Prefetch"
+              description="Hold emote prefetch and favorite warm-up until the game exits."
+              control={
+                <Toggle
+                  enabled={gameMode.pause_prefetch}
+                  onChange={() => setGameMode({ pause_prefetch: !gameMode.pause_prefetch })}
+                />
+              }
+            />
+            <SettingsRow
+              title="Slow Background Checks"
+              description="Poll for badges, stream info and the like less often, as power saver does."
+              control={
+                <Toggle
+                  enabled={gameMode.slow_polls}
+                  onChange={() => setGameMode({ slow_polls: !gameMode.slow_polls })}
+                />
+              }
+            />
+          </>
+        )}
+      </SettingsSection>
+
+      <RaidEventsSection />
     </div>
   );
 };
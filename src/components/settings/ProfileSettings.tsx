@@ -66,6 +66,7 @@ import { buildBttvProBadge, resolveBttvProUrl } from '../../services/bttvProBadg
 import { invoke } from '@tauri-apps/api/core';
 import { Logger } from '../../utils/logger';
 import LinkedAccountsSection from './LinkedAccountsSection';
+import FollowCleanupSection from './FollowCleanupSection';
 import ProfileOverview from './ProfileOverview';
 
 export interface ChatIdentityCache {
@@ -1918,6 +1919,8 @@ const ProfileSettings = () => {
       )}
 
       <LinkedAccountsSection />
+
+      <FollowCleanupSection />
         </>
       )}
     </div>
@@ -0,0 +1,212 @@
+import { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { Plus, Trash2 } from 'lucide-react';
+import { SettingsSection, SettingsRow, SegmentedSelect } from './_primitives';
+import { useAppStore } from '../../stores/AppStore';
+import type { RaidChannelStats, RaidEvent, RaidEventFollow, RaidEventUpdate } from '../../types';
+import { Logger } from '../../utils/logger';
+
+const FOLLOW_OPTIONS: { value: RaidEventFollow; label: string }[] = [
+  { value: 'off', label: 'Track only' },
+  { value: 'watch', label: 'Playback' },
+  { value: 'mine', label: 'Mining' },
+];
+
+// <input type="datetime-local"> works in local time without a zone.
+function toLocalInput(secs: number): string {
+  const d = new Date(secs * 1000);
+  d.setMinutes(d.getMinutes() - d.getTimezoneOffset());
+  return d.toISOString().slice(0, 16);
+}
+
+function fromLocalInput(value: string): number {
+  return Math.floor(new Date(value).getTime() / 1000);
+}
+
+function formatDuration(secs: number): string {
+  const h = Math.floor(secs / 3600);
+  const m = Math.floor((secs % 3600) / 60);
+  return h > 0 ? `${h}h ${m}m` : `${m}m`;
+}
+
+function makeEvent(): RaidEvent {
+  const now = Math.floor(Date.now() / 1000);
+  return {
+    id:
+      typeof crypto !== 'undefined' && 'randomUUID' in crypto
+        ? crypto.randomUUID()
+        : `raid-${Date.now()}-${Math.random().toString(36).slice(2)}`,
+    name: '',
+    enabled: true,
+    channels: [],
+    starts_at: now,
+    ends_at: now + 12 * 3600,
+    follow: 'watch',
+  };
+}
+
+const RaidEventCard = ({
+  event,
+  live,
+  onChange,
+  onRemove,
+}: {
+  event: RaidEvent;
+  live?: RaidEventUpdate;
+  onChange: (patch: Partial<RaidEvent>) => void;
+  onRemove: () => void;
+}) => {
+  const [channelsDraft, setChannelsDraft] = useState<string | null>(null);
+  const [stats, setStats] = useState<Record<string, RaidChannelStats>>({});
+
+  useEffect(() => {
+    invoke<Record<string, RaidChannelStats>>('get_raid_event_stats', { eventId: event.id })
+      .then(setStats)
+      .catch((e) => Logger.warn('[RaidEvents] stats failed:', e));
+  }, [event.id, live]);
+
+  return (
+    <div className="space-y-2 rounded-lg bg-white/[0.03] px-3 py-3">
+      <div className="flex items-center gap-2">
+        <input
+          value={event.name}
+          onChange={(e) => onChange({ name: e.target.value })}
+          placeholder="Event name"
+          className="flex-1 glass-input text-sm px-2 py-1.5"
+        />
+        <label className="flex items-center gap-1.5 text-xs text-textSecondary">
+          <input
+            type="checkbox"
+            checked={event.enabled}
+            onChange={() => onChange({ enabled: !event.enabled })}
+            className="accent-accent"
+          />
+          On
+        </label>
+        <button
+          onClick={onRemove}
+          aria-label="Remove event"
+          className="p-1.5 text-textMuted hover:text-red-400 hover:bg-red-500/10 rounded-md transition-colors"
+        >
+          <Trash2 size={14} />
+        </button>
+      </div>
+
+      <textarea
+        value={channelsDraft ?? event.channels.join('\n')}
+        onChange={(e) => setChannelsDraft(e.target.value)}
+        onBlur={() => {
+          if (channelsDraft === null) return;
+          onChange({
+            channels: channelsDraft
+              .split(/[\n,]/)
+              .map((line) => line.trim().toLowerCase())
+              .filter(Boolean),
+          });
+          setChannelsDraft(null);
+        }}
+        placeholder="One channel per line, in raid order"
+        rows={3}
+        className="w-full rounded-md border border-white/10 bg-black/30 px-2.5 py-1.5 text-sm text-textPrimary font-mono"
+      />
+
+      <div className="flex flex-wrap items-center gap-2 text-[13px] text-textSecondary">
+        <span>From</span>
+        <input
+          type="datetime-local"
+          value={toLocalInput(event.starts_at)}
+          onChange={(e) => e.target.value && onChange({ starts_at: fromLocalInput(e.target.value) })}
+          className="glass-input text-textPrimary text-sm px-2 py-1"
+        />
+        <span>to</span>
+        <input
+          type="datetime-local"
+          value={toLocalInput(event.ends_at)}
+          onChange={(e) => e.target.value && onChange({ ends_at: fromLocalInput(e.target.value) })}
+          className="glass-input text-textPrimary text-sm px-2 py-1"
+        />
+      </div>
+
+      <div className="flex items-center gap-3">
+        <span className="text-[13px] text-textSecondary">Follow the chain with</span>
+        <SegmentedSelect value={event.follow} options={FOLLOW_OPTIONS} onChange={(follow) => onChange({ follow })} />
+      </div>
+
+      {event.channels.length > 0 && (
+        <div className="space-y-0.5">
+          {event.channels.map((login) => {
+            const s = stats[login];
+            const isLive = live?.live.includes(login) ?? false;
+            return (
+              <div key={login} className="flex items-center gap-2 text-xs">
+                <span className={`w-1.5 h-1.5 rounded-full ${isLive ? 'bg-red-500' : 'bg-white/15'}`} />
+                <span className="flex-1 truncate text-textPrimary">
+                  {login}
+                  {live?.watching === login && <span className="text-accent"> · watching</span>}
+                  {live?.mining === login && <span className="text-accent"> · mining</span>}
+                </span>
+                <span className="text-textSecondary tabular-nums">
+                  {s ? `live ${formatDuration(s.live_secs)} · watched ${formatDuration(s.watched_secs)}` : '—'}
+                  {s && s.mined_secs > 0 && ` · mined ${formatDuration(s.mined_secs)}`}
+                </span>
+              </div>
+            );
+          })}
+        </div>
+      )}
+    </div>
+  );
+};
+
+// Raid trains and marathon events; see services::raid_events.
+const RaidEventsSection = () => {
+  const { settings, updateSettings } = useAppStore();
+  const events = settings.raid_events ?? [];
+  const [updates, setUpdates] = useState<Record<string, RaidEventUpdate>>({});
+
+  useEffect(() => {
+    const unlistenPromise = listen<RaidEventUpdate>('raid-event-update', (event) => {
+      setUpdates((current) => ({ ...current, [event.payload.event_id]: event.payload }));
+    });
+    return () => {
+      unlistenPromise.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  const save = (next: RaidEvent[]) => updateSettings({ ...settings, raid_events: next });
+
+  return (
+    <SettingsSection
+      id="settings-section-raid-events"
+      label="Raid Trains"
+      description="Community events where channels go live in turn. While an event runs, StreamNook tracks which of its channels are live and how long you watched each, and can move playback or drops mining to the next live channel when the current one ends."
+    >
+      {events.map((event) => (
+        <SettingsRow key={event.id} title={event.name || 'Untitled event'}>
+          <RaidEventCard
+            event={event}
+            live={updates[event.id]}
+            onChange={(patch) => save(events.map((e) => (e.id === event.id ? { ...e, ...patch } : e)))}
+            onRemove={() => save(events.filter((e) => e.id !== event.id))}
+          />
+        </SettingsRow>
+      ))}
+      <SettingsRow
+        title="Add Event"
+        description="Mining follows only with a drops add-on that can switch channels."
+        control={
+          <button
+            onClick={() => save([...events, makeEvent()])}
+            className="flex items-center gap-1.5 px-3 py-1.5 text-sm font-medium glass-button"
+          >
+            <Plus size={14} />
+            Add
+          </button>
+        }
+      />
+    </SettingsSection>
+  );
+};
+
+export default RaidEventsSection;
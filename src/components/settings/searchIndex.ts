@@ -253,6 +253,41 @@ export const SETTINGS_INDEX: SettingsIndexEntry[] = [
     title: 'Retries on No Match',
     description: 'How many extra times the song identifier listens again when the first try finds no song. Music recognition, Shazam.'
   },
+  {
+    tab: 'Player',
+    section: "When You're Away",
+    sectionId: 'settings-section-idle',
+    title: "Detect When I'm Away",
+    description: 'Notice when there has been no keyboard or mouse input for a while. Idle, AFK, away detection.'
+  },
+  {
+    tab: 'Player',
+    section: "When You're Away",
+    sectionId: 'settings-section-idle',
+    title: 'Pause Playback While Away',
+    description: 'Pause the stream while you are away and resume it when you are back. Idle, AFK.'
+  },
+  {
+    tab: 'Player',
+    section: "When You're Away",
+    sectionId: 'settings-section-idle',
+    title: 'Show Idle on Discord',
+    description: 'Show the idle Discord Rich Presence while you are away. AFK.'
+  },
+  {
+    tab: 'Player',
+    section: 'While Gaming',
+    sectionId: 'settings-section-game-mode',
+    title: 'Game Mode',
+    description: 'When a listed game is running, pause prefetch, switch the mined stream to audio only and slow background checks. Gaming, bandwidth, performance.'
+  },
+  {
+    tab: 'Player',
+    section: 'Raid Trains',
+    sectionId: 'settings-section-raid-events',
+    title: 'Raid train events',
+    description: 'Define an event with its channels and time window; track who is live and your time per channel, and follow the chain with playback or drops mining. Marathon, community event.'
+  },
 
   // === Theme ===
   {
@@ -334,6 +369,13 @@ export const SETTINGS_INDEX: SettingsIndexEntry[] = [
     title: 'Collapse gift-sub floods',
     description: 'When someone gifts a batch of subs, show one row with the recipients attached instead of a separate row per gift.'
   },
+  {
+    tab: 'Chat',
+    section: 'Chat Events',
+    sectionId: 'settings-section-chat-events',
+    title: 'Collapse repeated messages',
+    description: 'Fold floods of the same message or emote wall into one counting row, after a set number of copies.'
+  },
   {
     tab: 'Chat',
     section: 'Chat Logging',
@@ -1155,7 +1197,19 @@ export const SETTINGS_INDEX: SettingsIndexEntry[] = [
     tab: 'Cache',
     section: 'Cache',
     title: 'Cache Maintenance',
-    description: 'View cache statistics or delete all cached emotes and badges.'
+    description: 'View cache statistics, check and repair broken or half-written cache files, or delete all cached emotes and badges. Integrity, corrupt.'
+  },
+  {
+    tab: 'Cache',
+    section: 'Cache',
+    title: 'Offline Bundle',
+    description: 'Export the cache with its image files to a zip and import it on another PC. Offline, firewall, transfer.'
+  },
+  {
+    tab: 'Cache',
+    section: 'Channel Caches',
+    title: 'Forget Unvisited Channels',
+    description: "Remove the emote lists and badges of channels you haven't opened in a while. Pin channels to keep them forever. Stale, cleanup, disk space."
   },
   {
     tab: 'Cache',
@@ -1269,4 +1323,10 @@ export const SETTINGS_INDEX: SettingsIndexEntry[] = [
     title: 'Settings folder',
     description: 'Open the folder on this PC where StreamNook stores settings.json.'
   },
+  {
+    tab: 'Profile',
+    section: 'Inactive Follows',
+    title: 'Unfollow inactive channels',
+    description: "Find followed channels that haven't streamed in 30 to 365 days and unfollow them in bulk, with undo. Cleanup, prune, dead channels."
+  },
 ];
@@ -12,7 +12,13 @@ export interface PlayerControls {
   /** Current playback position in seconds, or null if unavailable. Used to
    *  anchor a VOD clip at the moment the viewer is watching. */
   getCurrentTime(): number | null;
+  /** Media length in seconds, or null when unknown (live, not loaded yet). */
+  getDuration(): number | null;
+  /** True when paused, or nothing is loaded. */
+  isPaused(): boolean;
   togglePlay(): void;
+  /** True when the player is muted, or nothing is loaded. */
+  isMuted(): boolean;
   toggleMute(): void;
   toggleFullscreen(): void;
   volumeUp(): void;
@@ -6,7 +6,7 @@ import { kickBadgeImage } from '../utils/kickBadges';
 // Message segment types - matches Rust MessageSegment enum
 export type MessageSegment =
   | { type: 'text'; content: string }
-  | { type: 'emote'; content: string; emote_id?: string; emote_url: string; is_zero_width?: boolean }
+  | { type: 'emote'; content: string; emote_id?: string; emote_url: string; is_zero_width?: boolean; animated?: boolean }
   | { type: 'emoji'; content: string; emoji_url: string }
   | { type: 'link'; content: string; url: string }
   // Cheermote segment for animated bits (e.g., Cheer500)
@@ -46,6 +46,19 @@ export interface MessageMetadata {
   bits_amount?: number;
   /** System message for subscriptions/donations */
   system_message?: string;
+  /** Hints for the chat virtualizer - matches Rust RenderHints */
+  render_hints?: RenderHints;
+}
+
+export interface RenderHints {
+  /** Names the logged-in user, or replies to them (Twitch only) */
+  mention: boolean;
+  /** Animated emotes and cheermotes in the message */
+  animated_emotes: number;
+  /** Sent by the broadcaster or a moderator, announcements included */
+  from_moderator: boolean;
+  /** Rough rendered length: short fits on one line of a typical chat column */
+  length: 'short' | 'medium' | 'long';
 }
 
 export interface BackendChatMessage {
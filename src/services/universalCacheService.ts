@@ -1,6 +1,7 @@
 // Universal cache service for badges, emotes, and other assets
 import { invoke } from '@tauri-apps/api/core';
 
+import type { CacheCleanup } from '../types';
 import { Logger } from '../utils/logger';
 export interface UniversalCacheEntry {
   id: string;
@@ -82,16 +83,18 @@ export async function syncUniversalCache(
 }
 
 /**
- * Clean up expired cache entries
+ * Clean up expired cache entries and the caches of channels not opened lately
  */
-export async function cleanupUniversalCache(): Promise<number> {
+export async function cleanupUniversalCache(): Promise<CacheCleanup> {
   try {
-    const count = await invoke<number>('cleanup_universal_cache');
-    Logger.debug(`[UniversalCache] Cleaned up ${count} expired entries`);
-    return count;
+    const cleanup = await invoke<CacheCleanup>('cleanup_universal_cache');
+    Logger.debug(
+      `[UniversalCache] Cleaned up ${cleanup.expired_entries} expired entries and ${cleanup.channel_caches} channel caches`
+    );
+    return cleanup;
   } catch (error) {
     Logger.error('[UniversalCache] Failed to cleanup:', error);
-    return 0;
+    return { expired_entries: 0, channel_caches: 0 };
   }
 }
 
@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 import { listen, emit } from '@tauri-apps/api/event';
-import type { Settings, TwitchUser, TwitchStream, UserInfo, TwitchCategory, HypeTrainData, TwitchVideo, ModLogEvent, DropProgressStatus } from '../types';
+import type { Settings, TwitchUser, TwitchStream, UserInfo, TwitchCategory, HypeTrainData, TwitchVideo, ModLogEvent, DropProgressStatus, PromotedChannel } from '../types';
 import { trackActivity } from '../services/logService';
 import { Logger, setDiagnosticsEnabled } from '../utils/logger';
 // Direct import (not via the keybindings index) to avoid a storecommands cycle.
@@ -302,6 +302,10 @@ interface AppState {
   // theater mode (hides chrome, stays windowed) and player fullscreen (video
   // only). Mirrors the actual OS window state.
   isWindowFullscreen: boolean;
+  // Mini player: the backend shrinks the window into a small always-on-top
+  // corner player (enter_mini_mode) and reports it via mini-mode-changed;
+  // chat hides while it's on, like compact view.
+  isMiniMode: boolean;
   toasts: Toast[];
   isAutoSwitching: boolean;
   // Track when raid redirect occurred to prevent auto-switch from overriding
@@ -361,6 +365,7 @@ interface AppState {
   loadMoreRecommendedStreams: () => Promise<void>;
   startStream: (channel: string, streamInfo?: TwitchStream, skipChatRefresh?: boolean) => Promise<void>;
   startOfflineChat: (channel: string, streamInfo?: TwitchStream) => Promise<void>;
+  promoteMiningToViewing: () => Promise<void>;  // Watch the channel drops mining is on; mining keeps going on the real playback
   playMedia: (type: 'clip' | 'video', url: string, info: MediaInfo) => Promise<void>;
   stopStream: (options?: { preserveBackend?: boolean }) => Promise<void>;
   restartStream: () => Promise<void>;  // Restart current stream (stops and starts again)
@@ -407,6 +412,11 @@ interface AppState {
   clearWhisperTargetUser: () => void;
   toggleTheaterMode: () => void;
   toggleWindowFullscreen: () => Promise<void>;
+  toggleMiniMode: () => Promise<void>;
+  fullscreenOnMonitor: (monitorIndex?: number) => Promise<void>;  // Borderless full screen on a display from get_monitors
+  setMiniMode: (active: boolean) => void;  // Follows the backend's mini-mode-changed event
+  setAlwaysOnTop: (onTop: boolean) => Promise<void>;
+  refreshDiscordPresence: () => Promise<void>;  // Presence for what's playing now (MultiNook, the stream, or idle)
   loginToTwitch: () => Promise<void>;
   logoutFromTwitch: () => Promise<void>;
   /** Make a linked account the main (watch & stream as it), then re-establish identity. */
@@ -460,6 +470,41 @@ let hasShownWelcomeBackToast = false;
 let eventSubListenerCleanup: (() => void)[] = [];
 let eventSubConnectionId = 0;
 
+// Resume-position reports for the playing VOD (see `set_vod_position`), every
+// 15s and once more on stop. `flushVodPosition` holds the last-stop report.
+let vodPositionTimer: ReturnType<typeof setInterval> | null = null;
+let flushVodPosition: (() => void) | null = null;
+
+function startVodPositionReports(vodId: string, info: MediaInfo): void {
+  stopVodPositionReports();
+  const report = () => {
+    const controls = getPlayerControls();
+    const position = controls?.getCurrentTime();
+    if (position == null) return;
+    invoke('set_vod_position', {
+      position: {
+        video_id: vodId,
+        user_id: info.user_id || info.broadcaster_id || '',
+        user_login: (info.user_login || '').toLowerCase(),
+        user_name: info.user_name || info.broadcaster_name || '',
+        title: info.title || '',
+        thumbnail_url: info.thumbnail_url || '',
+        position_secs: Math.floor(position),
+        duration_secs: Math.floor(controls?.getDuration() ?? 0),
+      },
+    }).catch(() => {});
+  };
+  vodPositionTimer = setInterval(report, 15_000);
+  flushVodPosition = report;
+}
+
+function stopVodPositionReports(): void {
+  if (vodPositionTimer) clearInterval(vodPositionTimer);
+  vodPositionTimer = null;
+  flushVodPosition?.();
+  flushVodPosition = null;
+}
+
 // Watch streak batch fetches are HEAVY — Twitch GraphQL with one sub-query
 // per channel (28 sub-queries for a typical followed list), the response is
 // a large JSON. `loadFollowedStreams` is called from 10+ call sites that
@@ -533,6 +578,7 @@ export const useAppStore = create<AppState>((set, get) => ({
   isTheaterMode: false,
   originalChatPlacement: null,
   isWindowFullscreen: false,
+  isMiniMode: false,
   toasts: [],
   isAutoSwitching: false,
   // Track when raid redirect occurred to prevent auto-switch from overriding
@@ -778,8 +824,13 @@ export const useAppStore = create<AppState>((set, get) => ({
 
       Logger.debug(`[AutoSwitch] Stream ${currentUserLogin} confirmed offline`);
 
+      // A raid-train event this channel is part of picks the next channel
+      // itself, ahead of the offline-chat preference and the usual search.
+      const raidNext = await invoke<string | null>('get_raid_event_next', { channelLogin: currentUserLogin })
+        .catch(() => null);
+
       // Check if user prefers to stay in offline chat
-      if (settings.auto_switch?.stay_in_offline_chat) {
+      if (settings.auto_switch?.stay_in_offline_chat && !raidNext) {
         Logger.debug('[AutoSwitch] User prefers to stay in offline chat. Transitioning to offline chat mode...');
         set({ isAutoSwitching: false });
         
@@ -828,6 +879,14 @@ export const useAppStore = create<AppState>((set, get) => ({
       // Clear current stream state
       set({ streamUrl: null, activeQuality: null, availableQualities: [], adSource: null, currentStream: null, currentMediaType: null });
 
+      if (raidNext) {
+        if (settings.auto_switch?.show_notification ?? true) {
+          state.addToast(`${currentUserLogin} went offline. Following the raid train to ${raidNext}...`, 'info');
+        }
+        await state.startStream(raidNext);
+        return;
+      }
+
       // Step 3: Find the next best stream based on mode
       const switchMode = settings.auto_switch?.mode ?? 'same_category';
       let streams: TwitchStream[] = [];
@@ -1089,27 +1148,7 @@ export const useAppStore = create<AppState>((set, get) => ({
       if (newSettings.discord_rpc_enabled) {
         try {
           await invoke('connect_discord');
-          
-          let multiNookModule;
-          try { multiNookModule = await import('./multiNookStore'); } catch { /* ignore */ }
-          const multiNookState = multiNookModule ? multiNookModule.usemultiNookStore.getState() : null;
-          
-          if (multiNookState && multiNookState.isMultiNookActive && multiNookState.slots.length > 0) {
-            multiNookModule?.broadcastMultiNookPresence(multiNookState.slots);
-          } else if (get().currentStream) {
-            const currentStream = get().currentStream!;
-            invoke('update_discord_presence', {
-              details: `Watching ${currentStream.user_name}`,
-              activityState: currentStream.title || 'Live on Twitch',
-              largeImage: '',
-              smallImage: '',
-              startTime: Date.now(),
-              gameName: currentStream.game_name || '',
-              streamUrl: `https://twitch.tv/${currentStream.user_login}`,
-            }).catch(() => {});
-          } else {
-             invoke('set_idle_discord_presence').catch(() => {});
-          }
+          await get().refreshDiscordPresence();
         } catch (e) {
           Logger.warn('Could not connect to Discord:', e);
         }
@@ -1358,6 +1397,7 @@ export const useAppStore = create<AppState>((set, get) => ({
           import('./vodReplayStore')
             .then((m) => m.beginVodReplay(vodId, login))
             .catch((e) => Logger.warn('[playMedia] could not start VOD replay:', e));
+          startVodPositionReports(vodId, info);
         }
       }
 
@@ -1372,6 +1412,8 @@ export const useAppStore = create<AppState>((set, get) => ({
   stopStream: async (options) => {
     const preserveBackend = options?.preserveBackend ?? false;
     trackActivity('Stopped stream');
+    // Save where a VOD was left, while the player is still mounted.
+    stopVodPositionReports();
     // Tear down any VOD chat replay session (no-op if none is active).
     import('./vodReplayStore')
       .then((m) => m.stopVodReplay())
@@ -1744,6 +1786,18 @@ export const useAppStore = create<AppState>((set, get) => ({
         // Non-critical, stream can still work
       }
 
+      // Title / category / viewers / uptime / tags, pushed as `stream-info-update`
+      invoke('subscribe_stream_info', { channel }).catch(() => {});
+
+      // System media session (media keys, volume flyout)
+      invoke('set_media_session', {
+        channel: info.user_name || channel,
+        gameName: info.game_name || '',
+        thumbnailUrl: info.thumbnail_url
+          ? info.thumbnail_url.replace('{width}', '440').replace('{height}', '248')
+          : null,
+      }).catch(() => {});
+
       // Update Discord with game matching (don't await - let it run in background)
       if (get().settings.discord_rpc_enabled) {
         const presenceArgs = {
@@ -2044,6 +2098,15 @@ export const useAppStore = create<AppState>((set, get) => ({
       set({ isLoading: false });
     }
   },
+  promoteMiningToViewing: async () => {
+    try {
+      const promoted = await invoke<PromotedChannel>('promote_mining_to_viewing');
+      await get().startStream(promoted.channel_login);
+    } catch (e) {
+      Logger.warn('[Drops] Could not watch the mined channel:', e);
+      get().addToast(`Couldn't watch the mined channel: ${e}`, 'error');
+    }
+  },
   startOfflineChat: async (channel, providedStreamInfo?) => {
     set({ isLoading: true });
     trackActivity(`Joined offline chat: ${channel}`);
@@ -2352,6 +2415,75 @@ export const useAppStore = create<AppState>((set, get) => ({
     }
   },
 
+  fullscreenOnMonitor: async (monitorIndex?: number) => {
+    try {
+      await invoke('set_fullscreen', { monitorIndex: monitorIndex ?? null });
+      set({ isWindowFullscreen: true });
+      trackActivity('Entered full screen');
+    } catch (err) {
+      Logger.error('[Fullscreen] Failed to go full screen on a display:', err);
+      get().addToast(`Couldn't go full screen there: ${err}`, 'error');
+    }
+  },
+
+  toggleMiniMode: async () => {
+    try {
+      await invoke(get().isMiniMode ? 'exit_mini_mode' : 'enter_mini_mode');
+    } catch (err) {
+      Logger.error('[MiniMode] Failed to toggle mini mode:', err);
+      get().addToast(`Couldn't switch the mini player: ${err}`, 'error');
+    }
+  },
+
+  setMiniMode: (active: boolean) => {
+    const state = get();
+    if (active === state.isMiniMode) return;
+    trackActivity(active ? 'Entered mini player' : 'Left mini player');
+    // Compact view already hid chat and holds the placement to restore; leave
+    // both to it.
+    if (state.isTheaterMode) {
+      set({ isMiniMode: active });
+    } else if (active) {
+      set({ isMiniMode: true, originalChatPlacement: state.chatPlacement, chatPlacement: 'hidden' });
+    } else {
+      set({ isMiniMode: false, chatPlacement: state.originalChatPlacement || 'right' });
+    }
+  },
+
+  setAlwaysOnTop: async (onTop: boolean) => {
+    try {
+      await invoke('set_always_on_top', { onTop });
+      set({ settings: { ...get().settings, always_on_top: onTop } });
+    } catch (err) {
+      Logger.error('[AlwaysOnTop] Failed to pin the window:', err);
+    }
+  },
+
+  refreshDiscordPresence: async () => {
+    if (!get().settings.discord_rpc_enabled) return;
+
+    let multiNookModule;
+    try { multiNookModule = await import('./multiNookStore'); } catch { /* ignore */ }
+    const multiNookState = multiNookModule ? multiNookModule.usemultiNookStore.getState() : null;
+
+    if (multiNookState && multiNookState.isMultiNookActive && multiNookState.slots.length > 0) {
+      multiNookModule?.broadcastMultiNookPresence(multiNookState.slots);
+    } else if (get().currentStream) {
+      const currentStream = get().currentStream!;
+      invoke('update_discord_presence', {
+        details: `Watching ${currentStream.user_name}`,
+        activityState: currentStream.title || 'Live on Twitch',
+        largeImage: '',
+        smallImage: '',
+        startTime: Date.now(),
+        gameName: currentStream.game_name || '',
+        streamUrl: `https://twitch.tv/${currentStream.user_login}`,
+      }).catch(() => {});
+    } else {
+      invoke('set_idle_discord_presence').catch(() => {});
+    }
+  },
+
   loginToTwitch: async () => {
     trackActivity('Started Twitch login');
     try {
@@ -9,7 +9,8 @@
 //     own text and are routed here by inspecting that substring.
 //   • Synthetic events `USER_BADGES:#<channel>:<badges>`,
 //     `{"type":"ROOMSTATE","channel":…}`, `{"type":"CLEARMSG","channel":…}`,
-//     `{"type":"CLEARCHAT","channel":…}` carry the channel explicitly.
+//     `{"type":"CLEARCHAT","channel":…}`, `{"type":"SPAM_COLLAPSE","channel":…}`
+//     carry the channel explicitly.
 //   • Global events `HEARTBEAT`, `IRC_CONNECTED`, `RECONNECTING:n`,
 //     `RECONNECTED`, `RECONNECT_*`, `CONNECTION_WARNING:…` are not channel-
 //     scoped and apply to every active channel slice.
@@ -1398,6 +1399,13 @@ function handleWsMessage(raw: string) {
         handleNotice(parsed);
         return;
       }
+      if (parsed.type === 'SPAM_COLLAPSE' && parsed.id) {
+        // Flush first so the counter row lands after the copies that showed.
+        flushPendingNow();
+        const ch = (parsed.channel as string | undefined)?.toLowerCase();
+        if (ch) withSlice(ch, (slice) => upsertSpamCollapse(slice, parsed));
+        return;
+      }
 
       // Structured ChatMessage (from Rust parser) — route by parsed.channel if
       // present (future server change), else by content.
@@ -2193,6 +2201,42 @@ export function injectSystemMessage(channel: string, message: string, songCard?:
   });
 }
 
+/** Create or update the counter row for a flood of one message
+ *  (services/spam_collapse.rs): "×37 PogChamp". The backend holds the copies
+ *  back and sends the running total under one id, so an update replaces the
+ *  row in place; once the row has scrolled out of the buffer, it's added again. */
+function upsertSpamCollapse(
+  slice: ChannelSlice,
+  c: { id: string; count: number; content: string; segments: any[] },
+): void {
+  const prefix = `×${c.count} `;
+  const row = {
+    id: c.id,
+    user_id: '',
+    username: 'System',
+    display_name: 'Chat',
+    color: '#9147ff',
+    badges: [],
+    content: prefix + c.content,
+    segments: [{ type: 'text', content: prefix }, ...(c.segments ?? [])],
+    is_action: false,
+    is_first_message: false,
+    is_mentioned: false,
+    is_from_shared_chat: false,
+    tags: new Map([
+      ['user-id', 'tw-system'],
+      ['id', c.id],
+    ]),
+  };
+  const idx = slice.messages.findIndex((m) => typeof m !== 'string' && m.id === c.id);
+  if (idx >= 0) {
+    slice.messages[idx] = row;
+    return;
+  }
+  pushMessage(slice, row);
+  slice.seenMessageIds.add(c.id);
+}
+
 /** Inject a no-input channel-points redemption as a chat row. Reuses the native
  *  highlight-message render path (via the `custom-reward-id` tag) so it reads as
  *  a redemption, with the redeemer as the author and the reward name as the body.
@@ -49,11 +49,45 @@ export interface VideoPlayerSettings {
   song_id?: SongIdSettings;
   experimental_low_latency?: boolean;
   ll_target_latency?: number;
+  mute_during_ads?: boolean; // Mute while a detected ad break runs
 }
 
 export interface CacheSettings {
   enabled: boolean;
   expiry_days: number;
+  channel_expiry_days?: number; // Days a channel's own caches outlive its last visit; 0 = forever
+  pinned_channel_caches?: string[]; // Channel ids whose caches are never cleaned up
+}
+
+/** Returned by `get_channel_caches`. */
+export interface ChannelCache {
+  channel_id: string;
+  last_accessed: number; // unix seconds
+  bytes: number;
+  files: number;
+  pinned: boolean;
+}
+
+/** Returned by `cleanup_universal_cache`. */
+export interface CacheCleanup {
+  expired_entries: number;
+  channel_caches: number; // Channels whose caches were removed
+}
+
+export type SetupStep = 'playback' | 'login' | 'mining_defaults';
+
+export interface SetupStepStatus {
+  step: SetupStep;
+  state: 'pending' | 'done' | 'failed' | 'skipped';
+  /** What was found, or why the step failed. */
+  detail?: string | null;
+  updated_at?: string | null;
+}
+
+/** `get_first_run_state`: every wizard step in order with its latest result. */
+export interface FirstRunState {
+  steps: SetupStepStatus[];
+  completed_at?: string | null;
 }
 
 export interface StreamlinkSettings {
@@ -92,6 +126,220 @@ export interface ChatLoggingSettings {
   timestamps?: boolean; // Start each line with the time it was sent (default: true)
 }
 
+// Mirrors ChatSpamCollapseSettings on the Rust side (services/spam_collapse.rs).
+export interface ChatSpamCollapseSettings {
+  enabled?: boolean; // Off by default
+  threshold?: number; // Copies that show before the rest fold into a counter row (default: 3)
+  window_secs?: number; // A flood ends after this many quiet seconds (default: 10)
+}
+
+// Mirrors PlaybackResourceSettings on the Rust side (services/playback_priority.rs).
+export interface PlaybackResourceSettings {
+  priority?: 'idle' | 'below_normal' | 'normal' | 'above_normal'; // Applied while a stream plays (default: normal)
+  gpu?: 'default' | 'power_saving' | 'high_performance'; // Windows; takes effect on the next launch
+}
+
+// Post-processing for finished recordings (a recording plugin reports them).
+// Mirrors RecordingSettings on the Rust side.
+export interface PostProcessOptions {
+  remux?: boolean; // Copy into an MP4 without re-encoding (default: true)
+  trim_ads?: boolean; // Leave out ad segments; HLS playlist recordings only (default: true)
+  thumbnail?: boolean; // Save a JPEG beside the output (default: true)
+  delete_source?: boolean; // Delete the original after a remux (default: false)
+}
+
+export interface RecordingSettings {
+  folder?: string; // Custom folder; empty uses Recordings under the app data dir
+  max_concurrent?: number; // Most recordings at once, 0 for no limit (default: 2)
+  min_free_disk_mb?: number; // Stop recordings below this much free space, 0 to never (default: 2048)
+  postprocess?: boolean; // Post-process recordings as they finish (off by default)
+  postprocess_options?: PostProcessOptions;
+}
+
+export type RuleTrigger =
+  | { type: 'channel_live'; channel_login: string }
+  | { type: 'drop_claimed' }
+  | { type: 'points_balance_above'; channel_login: string; threshold: number }
+  | { type: 'title_contains'; channel_login: string; word: string };
+
+export type RuleAction =
+  | { type: 'start_recording'; channel_login?: string | null } // Needs a plugin that provides recording.start
+  | { type: 'start_mining'; campaign_id?: string | null } // Auto-picks a campaign when omitted
+  | { type: 'send_webhook'; url: string }
+  | { type: 'switch_obs_scene'; scene: string }
+  | { type: 'notify'; message?: string | null };
+
+export interface AutomationRule {
+  id: string;
+  name: string;
+  enabled: boolean;
+  trigger: RuleTrigger;
+  action: RuleAction;
+}
+
+/** What moves along a raid-train event's chain when its live channel goes offline. */
+export type RaidEventFollow = 'off' | 'watch' | 'mine'; // mine needs a plugin that provides drops.switch-channel
+
+/** A raid train or marathon event, tracked by the backend (services::raid_events). */
+export interface RaidEvent {
+  id: string;
+  name: string;
+  enabled: boolean;
+  channels: string[]; // Logins in raid order
+  starts_at: number; // Unix seconds
+  ends_at: number; // Unix seconds
+  follow: RaidEventFollow;
+}
+
+/** Per-channel time tracked during a raid event (`get_raid_event_stats`). */
+export interface RaidChannelStats {
+  live_secs: number;
+  watched_secs: number;
+  mined_secs: number;
+}
+
+/** `raid-event-update`, raised every poll while an event is on. */
+export interface RaidEventUpdate {
+  event_id: string;
+  live: string[];
+  watching: string | null;
+  mining: string | null;
+}
+
+export interface ObsSettings {
+  enabled: boolean;
+  host: string; // Default 127.0.0.1
+  port: number; // Default 4455
+  password?: string | null; // Not included in exported backups
+  brb_scene?: string | null; // Scene to cut to when a raid is started from StreamNook
+  switch_on_raid?: boolean;
+}
+
+export interface ObsStreamStatus {
+  streaming: boolean;
+  reconnecting: boolean;
+  timecode: string;
+  duration_ms: number;
+  skipped_frames: number;
+  total_frames: number;
+  current_scene: string | null;
+}
+
+export interface LocalApiSettings {
+  enabled: boolean;
+  port: number; // Default 47615, bound on 127.0.0.1 only
+}
+
+/** Fetch playback tokens for the first `count` pinned favorites as they go live. */
+export interface FavoriteWarmup {
+  enabled: boolean;
+  count: number;
+}
+
+export interface PinnedChannel {
+  user_id: string;
+  login: string;
+  display_name: string;
+  profile_image_url?: string | null;
+  quality?: string | null; // Falls back to settings.quality
+  pinned_at: number; // Unix seconds
+}
+
+// get_favorites_with_status
+export interface FavoriteWithStatus extends PinnedChannel {
+  start_quality: string; // Pass to start_stream
+  live: boolean;
+  stream: TwitchStream | null;
+}
+
+export type SettingsSync =
+  | { provider: 'off' }
+  | { provider: 'folder'; path: string } // Dropbox / OneDrive / Syncthing folder
+  | { provider: 'gist'; token: string; gist_id?: string | null }; // gist_id set by the first push
+
+// get_settings_sync_status
+export interface SettingsSyncStatus {
+  last_synced_at: string | null;
+  remote_updated_at: string | null; // null before anything was pushed
+  remote_machine: string | null;
+  local_changed: boolean;
+  remote_changed: boolean;
+  conflict: boolean; // push/pull refuse unless called with force: true
+}
+
+// get_local_api_info
+export interface LocalApiInfo {
+  enabled: boolean;
+  running: boolean;
+  base_url: string; // e.g. http://127.0.0.1:47615/v1
+  token: string;
+  token_header: string; // X-StreamNook-Token
+}
+
+// Outbound webhook. body_template is JSON with {{path}} placeholders into
+// { event, fired_at, data }, e.g. {"content": "{{data.streamer_name}} is live"}.
+export interface WebhookEndpoint {
+  id: string;
+  name: string;
+  url: string;
+  events: string[]; // Script-hook event names: drop_claimed, went_live, stream_started
+  body_template?: string | null;
+  enabled: boolean;
+}
+
+// get_webhook_deliveries
+export interface WebhookDelivery {
+  webhook_id: string;
+  webhook_name: string;
+  event: string;
+  started_at: string;
+  finished_at: string;
+  attempts: number;
+  success: boolean;
+  status: number | null; // HTTP status of the last attempt
+  error: string | null;
+}
+
+/** Returned by `create_backup`; also the `manifest.json` inside the zip. */
+export interface BackupManifest {
+  format: number;
+  app_version: string;
+  created_at: string;
+  entries: { name: string; size: number; sha256: string }[];
+  has_tokens: boolean;
+}
+
+/** Returned by `export_manifest_bundle` and `import_manifest_bundle`. */
+export interface CacheBundleSummary {
+  entries: number;
+  files: number;
+  skipped: number; // export: entries whose file is gone; import: already cached here
+}
+
+/** Returned by `restore_backup`. */
+export interface RestoreSummary {
+  app_version: string;
+  created_at: string;
+  restored: string[]; // archive entries written back, e.g. 'local/settings.json'
+  tokens_restored: number;
+  tokens_skipped: boolean; // the backup has tokens but no password was given
+}
+
+export interface ScriptHook {
+  id: string;
+  event: 'drop_claimed' | 'went_live' | 'stream_started';
+  program: string; // File name inside the hooks folder (open_script_hooks_folder)
+  args?: string[];
+  timeout_secs?: number; // Killed after this long (default 10, max 300)
+  enabled?: boolean;
+}
+
+export interface AutomationRuleNotification {
+  rule_id: string;
+  rule_name: string;
+  message: string;
+}
+
 export interface DropsSettings {
   auto_claim_drops: boolean;
   auto_claim_channel_points: boolean;
@@ -112,6 +360,164 @@ export interface DropsSettings {
   prefer_favorites?: boolean; // Collect your live favorited channels instead of the priority list (default: false)
   // Recovery settings
   recovery_settings?: RecoverySettings;
+  points_targets?: PointsTarget[]; // Balance goals; 'channel-points-target-reached' fires when crossed
+  skip_unlinked_campaigns?: boolean; // Auto-selection skips campaigns whose game account isn't linked (default: false)
+  prefer_followed_channels?: boolean; // Mine on followed channels carrying an active campaign first (default: false)
+  preferred_languages?: string[]; // Stream languages (ISO 639-1) mining favors, most wanted first (default: [])
+  min_viewers?: number | null; // Mine only on channels with at least this many viewers (default: null)
+  max_viewers?: number | null; // Mine only on channels with at most this many viewers (default: null)
+  score_weights?: ChannelScoreWeights; // How channels rank for mining (see preview_channel_scores)
+  notify_on_progress_milestones?: boolean; // Fire 'drop-progress-milestone' at the milestones below (default: false)
+  progress_milestone_percents?: number[]; // Percent-complete milestones (default: [50, 90])
+  progress_milestone_minutes_remaining?: number | null; // Minutes-left milestone; null disables (default: 15)
+}
+
+export interface ChannelScoreWeights {
+  priority_bonus: number; // On the priority list
+  acl_bonus: number; // Named on the campaign's channel list
+  viewer_weight: number; // Times log10(viewers + 1); negative favors small channels
+  uptime_penalty: number; // Times the broadcast's 0..1 uptime penalty
+  language_bonus: number; // First preferred language; less for each after
+}
+
+// One row of preview_channel_scores
+export interface ChannelScore {
+  channel_id: string;
+  channel_login: string;
+  display_name: string;
+  viewer_count: number;
+  language: string | null;
+  uptime_mins: number;
+  score: number;
+  breakdown: { priority: number; acl: number; viewers: number; uptime: number; language: number };
+  skipped: string | null; // Why mining passes it over regardless of score
+}
+
+export type ProgressMilestone =
+  | { kind: 'percent'; percent: number }
+  | { kind: 'minutes_remaining'; minutes: number };
+
+export interface DropProgressMilestone {
+  drop_id: string;
+  campaign_id: string;
+  milestone: ProgressMilestone;
+  current_minutes: number;
+  required_minutes: number;
+  minutes_remaining: number;
+}
+
+export interface CampaignSummary {
+  campaign_id: string;
+  campaign_name: string;
+  game_name: string;
+  image_url: string;
+  started_at: string;
+  completed_at: string;
+  elapsed_secs: number;
+  minutes_watched: number;
+  channels: string[];
+  channel_switches: number;
+  rewards: string[];
+}
+
+export interface UnlinkedCampaign {
+  campaign_id: string;
+  campaign_name: string;
+  game_name: string;
+  image_url: string;
+  account_link: string | null;
+  end_at: string;
+}
+
+export interface StreamCampaign {
+  campaign_id: string;
+  campaign_name: string;
+  image_url: string;
+  end_at: string;
+  is_account_connected: boolean;
+}
+
+/** A live drops-enabled stream; empty `campaigns` means none the user can see covers it. */
+export interface DropsEnabledStream {
+  stream: TwitchStream;
+  campaigns: StreamCampaign[];
+}
+
+export interface DropsStreamsPage {
+  streams: DropsEnabledStream[];
+  cursor: string | null;
+}
+
+/** Whether a campaign can be earned right now (`get_campaign_availability`). */
+export interface CampaignAvailability {
+  campaign_id: string;
+  campaign_name: string;
+  game_name: string;
+  live: boolean;
+  top_channel: string | null;
+  top_viewer_count: number | null;
+  /** False when the lookup failed, so `live` is unknown. */
+  checked: boolean;
+  checked_at: string;
+}
+
+/** Watch payloads sent against minutes credited (`get_mining_efficiency`). */
+export interface MiningEfficiency {
+  since: string;
+  payloads_sent: number;
+  minutes_credited: number;
+  /** Minutes credited per payload; null before any payload. */
+  efficiency: number | null;
+  last_payload_at: string | null;
+  channels: { channel_login: string; payloads: number }[];
+  campaigns: { campaign_id: string; payloads_sent: number; minutes_credited: number }[];
+}
+
+/** A starred drop reward, followed across campaigns by benefit id. */
+export interface WishedReward {
+  benefit_id: string;
+  name: string;
+  image_url: string;
+  game_name: string;
+  added_at: string;
+}
+
+export interface WishedRewardCampaign {
+  campaign_id: string;
+  campaign_name: string;
+  drop_name: string;
+  end_at: string;
+  current_minutes: number;
+  required_minutes: number;
+  claimed: boolean;
+}
+
+/** `get_wishlist_status` entry; `campaigns` is empty when nothing grants it now. */
+export interface WishlistStatus {
+  reward: WishedReward;
+  campaigns: WishedRewardCampaign[];
+}
+
+export type BlacklistReason =
+  | 'WatchPayloadFailures'
+  | 'WentOffline'
+  | 'StaleProgress'
+  | 'GameCategoryChanged'
+  | 'Manual';
+
+/** A channel drops mining skips until `expires_at` (`get_blacklisted_streamers`). */
+export interface BlacklistedStreamer {
+  channel_id: string;
+  channel_name: string;
+  reason: BlacklistReason;
+  blacklisted_at: string;
+  expires_at: string;
+}
+
+export interface PointsTarget {
+  channel_id: string;
+  target: number;
+  label?: string | null;
 }
 
 export interface DropChannel {
@@ -122,6 +528,7 @@ export interface DropChannel {
   viewer_count: number;
   is_live: boolean;
   drops_enabled: boolean;
+  language?: string | null; // Broadcast language (ISO 639-1) when the drops add-on reports it
 }
 
 export interface CurrentDropInfo {
@@ -157,6 +564,34 @@ export interface DropProgressStatus {
   last_update: string;
 }
 
+// The mined channel `promote_mining_to_viewing` handed to the player.
+export interface PromotedChannel {
+  channel_id: string;
+  channel_login: string;
+}
+
+// `import_twitch_drops_miner`: what a TwitchDropsMiner folder adds (or, on a dry run, would add).
+export interface TdmImport {
+  priority_games: string[];
+  excluded_games: string[];
+  priority_mode: 'PriorityOnly' | 'EndingSoonest' | 'LowAvailFirst' | null;
+  auth_token_found: boolean;
+  signed_in_as: string | null;
+  warnings: string[];
+}
+
+// `import_from_client`: what Chatterino or Streamlink Twitch GUI adds (or, on a dry run, would add).
+export type ImportSource = 'chatterino' | 'streamlink_twitch_gui';
+
+export interface ClientImport {
+  source: ImportSource;
+  highlight_phrases: HighlightPhrase[];
+  highlight_users: HighlightUser[];
+  // Channels to pin; SLTG records name the Twitch user id, Chatterino the login.
+  channels: { login: string | null; user_id: string | null; quality: string | null }[];
+  skipped: string[];
+}
+
 export interface ChatDesignSettings {
   show_dividers: boolean;
   alternating_backgrounds: boolean;
@@ -192,6 +627,7 @@ export interface ChatDesignSettings {
   // Show an in-chat notice when a channel's 7TV emote set changes live (a mod
   // adds, removes, or renames an emote). Default true.
   seventv_emote_notices?: boolean;
+  optimize_animated_emotes?: boolean; // Pre-convert oversized animated emotes (prepare_animated_emotes)
   // Auto-expand inline preview cards for links from trusted domains (YouTube,
   // Twitch, imgur, etc.). Other links stay plain clickable links. Default true.
   link_previews?: boolean;
@@ -499,6 +935,61 @@ export interface LiveNotificationSettings {
   // Toast placement: anchor + distance from the anchored top/bottom edge (px)
   toast_position?: ToastPosition;
   toast_edge_offset?: number;
+  // Silences notification sounds; the backend keeps it equal to audio_cues.muted
+  do_not_disturb?: boolean;
+}
+
+// A connected display from get_monitors, numbered left to right
+export interface MonitorInfo {
+  index: number;
+  name: string | null;
+  x: number; // Physical pixels on the virtual desktop
+  y: number;
+  width: number;
+  height: number;
+  scale_factor: number;
+  primary: boolean;
+  current: boolean; // The main window is on this display
+}
+
+export type AudioCueEvent = 'mention' | 'whisper' | 'drop_claimed' | 'live';
+
+export interface AudioCue {
+  enabled: boolean;
+  sound: string; // Built-in sound id, a WAV file path, or '' for the Sound Style
+  volume: number; // 0-100
+}
+
+export interface AudioCueSettings {
+  muted: boolean; // Same switch as live_notifications.do_not_disturb
+  mention: AudioCue;
+  whisper: AudioCue;
+  drop_claimed: AudioCue;
+  live: AudioCue;
+}
+
+export interface IdleSettings {
+  enabled: boolean;
+  threshold_mins: number; // No input for this long counts as away
+  pause_playback: boolean;
+  discord_idle: boolean; // Idle Discord presence while away
+}
+
+// Pause prefetch, quiet mined playback and slow polls while a game runs (emits game-mode-changed)
+export interface GameModeSettings {
+  enabled: boolean;
+  executables: string[]; // e.g. "eldenring.exe"; case and folder don't matter
+  pause_prefetch: boolean;
+  mining_playback: 'keep' | 'audio_only' | 'none';
+  slow_polls: boolean;
+}
+
+// Returned by check_audio_cue_sound for a WAV file
+export interface WavInfo {
+  channels: number;
+  sample_rate: number;
+  bits_per_sample: number;
+  duration_ms: number;
 }
 
 export type AutoSwitchMode = 'same_category' | 'followed_streams';
@@ -641,6 +1132,22 @@ export interface MultiNookPreset {
   updatedAt: number;                // Epoch ms, bumped on every edit
 }
 
+/** A co-streamer found by `get_squad` / `squad-detected`. */
+export interface SquadPartner {
+  channel_id: string;
+  login: string;
+  display_name: string;
+  title: string;
+  viewer_count: number;
+}
+
+/** `squad-detected`, and what `get_squad` returns for a channel streaming with others. */
+export interface Squad {
+  channel_login: string;
+  source: 'shared_chat' | 'title'; // title: partners named in the stream title that are live
+  partners: SquadPartner[];
+}
+
 /** Customizable keyboard shortcut overrides. Maps a bindable-command id to its
  *  user-assigned chord strings (e.g. { 'player.mute': ['M', 'Ctrl+M'] }). Absent
  *  ids fall back to the code-defined defaults; an explicit empty array means the
@@ -666,6 +1173,8 @@ export interface Settings {
   streamlink?: StreamlinkSettings;
   drops: DropsSettings;
   favorite_streamers: string[];
+  pinned_channels?: PinnedChannel[]; // Quick-launch favorites; written by pin_channel / reorder_favorites
+  favorite_warmup?: FavoriteWarmup;
   chat_design?: ChatDesignSettings;
   chat_highlights?: ChatHighlightSettings;
   chat_customization?: ChatCustomizationSettings;
@@ -675,12 +1184,24 @@ export interface Settings {
   chat_render?: ChatRenderSettings;
   cosmetics?: CosmeticsSettings;
   live_notifications?: LiveNotificationSettings;
+  audio_cues?: AudioCueSettings; // Sounds for mentions, whispers, claimed drops and go-lives
   last_seen_version?: string;
   auto_switch?: AutoSwitchSettings;
   theme?: string; // Theme ID (e.g., 'winters-glass', 'dracula', 'nord')
   font?: string; // Interface font id (see FONT_OPTIONS in themes). Default 'satoshi'.
   error_reporting_enabled?: boolean; // Local diagnostic log verbosity; nothing is sent off-device (default: true)
+  always_on_top?: boolean; // Main window pinned above other windows (set via set_always_on_top)
+  idle?: IdleSettings; // Away detection from OS idle time (emits user-idle-changed)
+  power_saver?: boolean; // Stretch background polls and skip UI-only events while the window is minimized/hidden (default: false)
+  playback_resources?: PlaybackResourceSettings; // CPU priority and GPU choice while a stream plays
+  game_mode?: GameModeSettings;
+  recommendation_strategy?: 'twitch' | 'personalized' | 'hybrid'; // Order of recommended streams (backend re-ranks; default hybrid)
+  prefetch_concurrency?: number; // Emote/badge/reward image downloads run at once (default 6)
+  chat_friends?: string[]; // Friend logins (set via set_chat_friend); their first message in a chat raises friend-active-in-chat
+  global_hotkeys?: Record<string, string>; // System-wide hotkeys: toggle_mute | toggle_stream | toggle_window | toggle_mining | toggle_mini_mode | toggle_always_on_top -> accelerator (set via set_hotkey)
+  developer_mode?: boolean; // Unlocks developer tools such as the raw Helix/GQL console (default: false)
   setup_complete?: boolean; // Whether the first-time setup wizard has been completed
+  first_run_state?: FirstRunState; // Wizard step results (written by the setup commands only)
   auto_claim_points_watching?: boolean; // Auto-claim the bonus chest on the channel you're actively watching. On by default; when off, a clickable chest appears on the points button. Scoped to the watched channel only (background automation is a separate opt-in plugin).
   compact_view?: CompactViewSettings; // Compact view preset settings
   custom_themes?: CustomTheme[]; // User-created custom themes
@@ -696,6 +1217,16 @@ export interface Settings {
   show_channel_point_redemptions?: boolean; // Show no-input channel-point redemptions as chat rows (default on)
   collapse_gift_subs?: boolean; // Collapse mass gift-sub bombs into one announcement row with recipients (default on)
   chat_logging?: ChatLoggingSettings; // Save chat to plain text files as you watch
+  chat_spam_collapse?: ChatSpamCollapseSettings; // Fold floods of one message into a counter row
+  recording?: RecordingSettings; // What happens to recordings once they finish
+  ad_free_overrides?: Record<string, AdFreeOverride>; // By lowercase login; unlisted channels follow Turbo/sub detection
+  automation_rules?: AutomationRule[]; // "When X then Y" rules run by the backend
+  raid_events?: RaidEvent[]; // Raid trains and marathon events tracked by the backend
+  script_hooks?: ScriptHook[]; // External programs run on app events (must live in the hooks folder)
+  obs?: ObsSettings; // OBS Studio connection (obs-websocket v5)
+  local_api?: LocalApiSettings; // Localhost control API for Stream Deck (docs/LOCAL_API.md)
+  webhooks?: WebhookEndpoint[]; // Outbound webhooks on app events
+  settings_sync?: SettingsSync; // Per machine; never exported or synced itself
   moderation?: ModerationSettings;
   keybindings?: KeybindingOverrides; // Customizable keyboard shortcut overrides (id -> chords)
   // Which action buttons show in the video player's top-right overlay, by id:
@@ -761,6 +1292,55 @@ export interface ReleaseNotes {
   name: string;
   body: string;
   published_at: string;
+  features: string[]; // Bullets of the release's Features section
+  fixes: string[]; // Bullets of its Fixes section
+}
+
+/** One CHANGELOG.md release, as parsed by the backend. */
+export interface ChangelogRelease {
+  version: string;
+  published_at: string; // YYYY-MM-DD, '' when undated
+  body: string;
+  features: string[];
+  fixes: string[];
+}
+
+/** `show-whats-new` payload (and `get_whats_new`): the releases since the version last run, newest first. */
+export interface WhatsNew {
+  from: string;
+  to: string;
+  releases: ChangelogRelease[];
+}
+
+/** `unfollow-batch-progress` (from `unfollow_channels` / `undo_unfollow_channels`). */
+export interface UnfollowBatchProgress {
+  batch_id: number;
+  undo: boolean; // true while following back
+  done: number;
+  total: number;
+  failed: number;
+  current: string; // channel id just handled
+}
+
+/** `unfollow-batch-complete`. */
+export interface UnfollowBatchComplete {
+  batch_id: number;
+  undo: boolean;
+  unfollowed?: string[]; // when undo is false
+  followed?: string[]; // when undo is true
+  failed: { channel_id: string; error: string }[];
+  undo_secs?: number; // how long undo_unfollow_channels accepts this batch
+}
+
+/** A followed channel from `get_inactive_follows`. */
+export interface InactiveFollow {
+  channel_id: string;
+  login: string;
+  display_name: string;
+  profile_image_url?: string | null;
+  followed_at: string;
+  last_streamed?: string | null; // null: never streamed
+  days_inactive?: number | null;
 }
 
 export interface TwitchStream {
@@ -780,6 +1360,15 @@ export interface TwitchStream {
   is_live?: boolean;
   // Free-form stream tags (e.g. "English", "Speedrun"); used by the category tag filter.
   tags?: string[];
+  // Broadcast language (ISO 639-1, e.g. "en") when the source carries it.
+  language?: string | null;
+}
+
+/** Backend-side filter for get_recommended_streams_paginated / get_streams_by_game. */
+export interface StreamFilter {
+  languages?: string[];
+  tags?: string[];
+  exclude_tags?: string[];
 }
 
 export interface TwitchClip {
@@ -821,6 +1410,329 @@ export interface TwitchVideo {
   duration: string;
 }
 
+// get_share_info: "share what I'm watching" payload.
+export interface ShareInfo {
+  channel_login: string;
+  display_name: string;
+  url: string;
+  is_live: boolean;
+  title: string | null;
+  game_name: string | null;
+  message: string;
+}
+
+/** Payload of the `stream-info-update` event (see `subscribe_stream_info`). */
+export interface StreamInfoUpdate {
+  channel_login: string;
+  is_live: boolean;
+  title: string;
+  game_id: string;
+  game_name: string;
+  viewer_count: number | null;
+  started_at: string | null;
+  uptime_secs: number | null;
+  tags: string[];
+}
+
+/** Payload of the `stream-info-changed` event: the watched channel changed its title or category. */
+export interface StreamInfoChange {
+  channel_login: string;
+  title_changed: boolean;
+  game_changed: boolean;
+  old_title: string;
+  title: string;
+  old_game_name: string;
+  game_name: string;
+  game_id: string; // Empty when the source didn't report one
+}
+
+/** Payload of `chat-ready`: the joined channel's emotes and badges are
+ *  loaded (or failed, when false). */
+export interface ChatReady {
+  channel: string;
+  channel_id: string | null;
+  emotes: boolean;
+  badges: boolean;
+  elapsed_ms: number;
+}
+
+/** One result of `autocomplete`. `insert` is what goes in the input box. */
+export interface Completion {
+  kind: 'emote' | 'user';
+  name: string;
+  insert: string;
+  url?: string;
+  provider?: string;
+  score: number; // Frecency; 0 when never sent
+}
+
+/** One result of `prepare_animated_emotes`. `path` is the converted WebP
+ *  on disk, present only for oversized animations. */
+export interface PreparedEmote {
+  id: string;
+  info: {
+    format: 'gif' | 'webp';
+    width: number;
+    height: number;
+    frame_count: number;
+    duration_ms: number;
+  } | null;
+  path: string | null;
+}
+
+/** A chat tab from `open_chat_tab` / `list_chat_tabs`. */
+export interface ChatTab {
+  id: string;
+  channel: string;
+  window: string; // Label of the window showing it
+  opened_at: number; // Unix seconds
+}
+
+/** Payload of `chat-tab-messages`: bridge frames batched per tab, oldest first. */
+export interface ChatTabMessages {
+  tab_id: string;
+  channel: string;
+  messages: string[];
+}
+
+/** Payload of `ad-break-started`. `mute` mirrors `video_player.mute_during_ads`;
+ *  `duration_secs` is null when the playlist didn't announce one. */
+export interface AdBreakStarted {
+  channel_login: string | null;
+  source: 'playlist';
+  break_number: number;
+  duration_secs: number | null;
+  mute: boolean;
+}
+
+export interface AdBreakEnded {
+  channel_login: string | null;
+  source: 'playlist';
+  break_number: number;
+  elapsed_secs: number | null;
+}
+
+/** `run_dependency_check` item; pass `fix_id` to `fix_dependency` when set. */
+export interface DependencyCheck {
+  id: string;
+  name: string;
+  status: 'ok' | 'warning' | 'error' | 'not_needed';
+  version: string | null;
+  detail: string;
+  suggestion: string | null;
+  fix_id: string | null;
+}
+
+export type FeatureFlag = 'channel_points' | 'spade_payloads';
+
+/** `get_feature_flags` entry; `feature-disabled` / `feature-restored` carry
+ *  `{ feature, label, error, retry_at }`. */
+export interface FeatureStatus {
+  feature: FeatureFlag;
+  label: string;
+  enabled: boolean;
+  consecutive_failures: number;
+  last_error: string | null;
+  /** When the next probe is let through; null while enabled. */
+  retry_at: string | null;
+}
+
+/** Payload of `stream-frame-captured`, also what `capture_stream_frame` saved. */
+export interface CapturedFrame {
+  channel: string;
+  path: string;
+}
+
+export interface StreamBookmark {
+  created_at: string;
+  /** Seconds into the broadcast; null when its start wasn't known. */
+  offset_secs: number | null;
+  note: string;
+  game_name: string;
+}
+
+/** One broadcast watched live (see `services/watch_sessions.rs`). */
+export interface WatchSession {
+  id: string;
+  user_id: string;
+  login: string;
+  display_name: string;
+  title: string;
+  game_name: string;
+  stream_started_at: string | null;
+  started_at: string;
+  last_seen_at: string;
+  bookmarks: StreamBookmark[];
+  vod_id: string | null;
+  chapters: SessionChapter[];
+}
+
+/** A stretch of a watch session under one category and title. */
+export interface SessionChapter {
+  started_at: string;
+  game_name: string;
+  title: string;
+}
+
+/** A recording the recording plugin reports running (`get_active_recordings`). */
+export interface ActiveRecording {
+  channel_login: string;
+  path: string; // Empty until the plugin knows it
+}
+
+/** Payload of `recording-guard-stopped`: a recording stopped for the limit
+ *  or for low disk space (`free_mb` is set for the latter). */
+export interface RecordingGuardStopped {
+  channel_login: string;
+  path: string;
+  reason: 'limit' | 'low_disk';
+  free_mb: number | null;
+}
+
+export type IneligibleReasonKind = 'account_not_linked' | 'channel_not_allowed' | 'sub_only_stream' | 'not_offered' | 'not_running';
+
+export interface IneligibleReason {
+  kind: IneligibleReasonKind;
+  message: string;
+  /** Set for `account_not_linked`: the publisher's link page. */
+  account_link?: string | null;
+}
+
+/** `get_overlay_state`, and the local API's `/v1/overlay`. Only `mining` is set while not mining. */
+export interface OverlayState {
+  mining: boolean;
+  paused?: boolean;
+  drop_name?: string | null;
+  game_name?: string | null;
+  channel_login?: string | null;
+  current_minutes?: number | null;
+  required_minutes?: number | null;
+  percent?: number | null;
+  eta_secs?: number | null;
+}
+
+/** `check_campaign_eligibility`, and the `ineligible-campaign` event payload. */
+export interface CampaignEligibility {
+  campaign_id: string;
+  campaign_name: string;
+  game_name: string;
+  channel_login: string | null;
+  eligible: boolean;
+  reasons: IneligibleReason[];
+}
+
+/** How a channel plays regardless of Turbo/sub detection (settings `ad_free_overrides`). */
+export type AdFreeOverride = 'direct' | 'workaround';
+
+/** Result of `get_ad_free_status`. */
+export interface AdFreeStatus {
+  turbo: boolean;
+  subscribed: boolean;
+  override: AdFreeOverride | null;
+  direct: boolean; // Plays straight from Twitch, skipping any playback plugin
+}
+
+/** A recording in the recordings folder (`list_recordings`,
+ *  `get_recording_metadata`). Channel, game, title and duration come from
+ *  the folder's index and are empty for files it doesn't know. */
+export interface RecordingEntry {
+  path: string;
+  file_name: string;
+  size_bytes: number;
+  modified_at: string | null;
+  channel_login: string;
+  game_name: string;
+  title: string;
+  started_at: string | null;
+  duration_secs: number | null;
+  thumbnail: string | null; // Saved by post-processing
+  sidecars: string[]; // Thumbnail and chapter files that belong to it
+}
+
+/** A post-processing job, from `get_postprocess_jobs` and the
+ *  `recording-postprocess-progress` event ({ job }). */
+export interface PostProcessJob {
+  id: number;
+  input: string;
+  options: PostProcessOptions;
+  state: 'queued' | 'running' | 'done' | 'failed';
+  stage: 'trimming_ads' | 'remuxing' | 'thumbnail' | null;
+  processed_secs: number | null;
+  attempts: number;
+  error: string | null;
+  retry_at: string | null; // When a failed attempt is retried
+  ad_segments_removed: number;
+  outputs: string[];
+  queued_at: string;
+  finished_at: string | null;
+}
+
+/** Payload of the `startup-progress` event (see `services/startup.rs`).
+ *  `stage` is `first_paint`, `badges`, `drop_campaigns` or `ready`. */
+export interface StartupProgress {
+  stage: string;
+  status: 'waiting' | 'started' | 'done' | 'failed' | 'timeout';
+  completed: number;
+  total: number;
+  elapsed_ms: number;
+}
+
+/** Result of `get_prefetch_queue_status`. */
+export interface PrefetchQueueStatus {
+  paused: boolean;
+  concurrency: number;
+  throttled_for_playback: boolean;
+  in_flight: number;
+  queued_visible: number;
+  queued_normal: number;
+  queued_background: number;
+  queued_by_group: Record<string, number>;
+  completed: number;
+}
+
+/** One entry of `get_memory_cache_stats`. */
+export interface MemoryCacheStat {
+  name: string;
+  entries: number;
+  capacity: number;
+  evicted: number;
+}
+
+/** A partly watched VOD (see `set_vod_position`). */
+export interface VodPosition {
+  video_id: string;
+  user_id: string;
+  user_login: string;
+  user_name: string;
+  title: string;
+  thumbnail_url: string;
+  position_secs: number;
+  duration_secs: number;
+  updated_at?: string;
+}
+
+/** Result of `get_continue_watching`. */
+export interface ContinueWatching {
+  live: TwitchStream[];
+  vods: VodPosition[];
+}
+
+/** Payload of `twitch-login-progress`, sent while the device code is polled. */
+export interface LoginProgress {
+  seconds_remaining: number;
+  poll_interval: number;
+  slow_down: boolean;
+}
+
+/** Payload of `combined-login-progress`, one per step of `start_combined_login`. */
+export interface CombinedLoginProgress {
+  step: 'main' | 'drops';
+  state: 'awaiting_code' | 'complete' | 'skipped' | 'failed';
+  user_code: string | null;
+  verification_uri: string | null;
+  error: string | null;
+}
+
 export interface TwitchUser {
   access_token: string;
   username: string;
@@ -973,6 +1885,7 @@ export interface DropCampaign {
   allowed_channels: AllowedChannel[];
   is_acl_based: boolean;
   account_link?: string; // URL to connect game account for drops
+  unlinked_warning?: boolean; // Minutes are accruing but the game account isn't connected
 }
 
 export type CampaignStatus = 'Active' | 'Upcoming' | 'Expired';
@@ -995,6 +1908,13 @@ export interface InventoryResponse {
   completed_drops: CompletedDrop[];
 }
 
+// Last-synced inventory from get_inventory_offline; local_images maps remote image URL -> cached file path
+export interface OfflineInventory {
+  inventory: InventoryResponse;
+  synced_at: string;
+  local_images: Record<string, string>;
+}
+
 export interface CompletedDrop {
   id: string;
   name: string;
@@ -1023,7 +1943,17 @@ export interface ChannelPointsClaim {
   channel_name: string;
   points_earned: number;
   claimed_at: string;
-  claim_type: 'Watch' | 'Raid' | 'Prediction' | 'Bonus' | 'Other';
+  claim_type: 'Watch' | 'Raid' | 'Prediction' | 'Bonus' | 'Redemption' | 'Other';
+  balance_after?: number | null;
+}
+
+export interface PointsTrend {
+  channel_id: string;
+  current_balance: number | null;
+  total_earned: number;
+  total_spent: number;
+  net: number;
+  samples: { at: string; delta: number; balance: number | null }[];
 }
 
 export interface ChannelPointsBalance {
@@ -1075,6 +2005,7 @@ export interface UnlockedEmote {
 export interface DropsStatistics {
   total_drops_claimed: number;
   total_channel_points_earned: number;
+  total_minutes_watched: number;
   active_campaigns: number;
   drops_in_progress: number;
   recent_claims: ClaimedDrop[];
@@ -1197,7 +2128,10 @@ export interface HypeTrainData {
   is_golden_kappa?: boolean;
 }
 
-
-
-
-
+/** A category as resolved by `resolve_game`. */
+export interface ResolvedGame {
+  id: string;
+  name: string;
+  box_art_url: string;
+  matched: 'exact' | 'alias' | 'search';
+}
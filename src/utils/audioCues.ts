@@ -0,0 +1,84 @@
+// Audio cues: per-event sounds for mentions, whispers, claimed drops and
+// channels going live (Settings.audio_cues). A cue plays one of the built-in
+// sounds through the shared Web-Audio engine in notificationSound, or a
+// user's WAV through an <audio> element; WAVs are checked by the backend
+// when picked (check_audio_cue_sound). Nothing plays while notification
+// sounds are off or Do Not Disturb is on, which the backend keeps in step
+// with the cue mute.
+
+import { convertFileSrc } from '@tauri-apps/api/core';
+import type { AudioCue, AudioCueEvent, Settings } from '../types';
+import { Logger } from './logger';
+import { playSound, takeSoundSlot, SOUND_LABELS, type SoundId } from './notificationSound';
+
+export const AUDIO_CUE_LABELS: Record<AudioCueEvent, string> = {
+  mention: 'Mentions',
+  whisper: 'Whispers',
+  drop_claimed: 'Drop claimed',
+  live: 'Channel goes live',
+};
+
+// Mirrors AudioCueSettings::default on the backend. Mentions made no sound
+// before cues existed, so they start off.
+const DEFAULT_CUES: Record<AudioCueEvent, AudioCue> = {
+  mention: { enabled: false, sound: '', volume: 100 },
+  whisper: { enabled: true, sound: '', volume: 100 },
+  drop_claimed: { enabled: true, sound: '', volume: 100 },
+  live: { enabled: true, sound: '', volume: 100 },
+};
+
+export function getAudioCue(settings: Settings, event: AudioCueEvent): AudioCue {
+  return settings.audio_cues?.[event] ?? DEFAULT_CUES[event];
+}
+
+export function isNotificationSoundMuted(settings: Settings): boolean {
+  return (
+    !(settings.live_notifications?.play_sound ?? true) ||
+    !!settings.live_notifications?.do_not_disturb ||
+    !!settings.audio_cues?.muted
+  );
+}
+
+export const isBuiltInSound = (sound: string): sound is SoundId => sound in SOUND_LABELS;
+
+// One element per file, rewound on each play, so a busy chat doesn't stack
+// up decoders for the same WAV.
+const wavPlayers = new Map<string, HTMLAudioElement>();
+
+function playWav(path: string, volume: number): void {
+  let audio = wavPlayers.get(path);
+  if (!audio) {
+    audio = new Audio(convertFileSrc(path));
+    wavPlayers.set(path, audio);
+  }
+  audio.volume = volume;
+  audio.currentTime = 0;
+  audio.play().catch((error) => Logger.warn('Could not play audio cue:', error));
+}
+
+// Play a cue as configured, ignoring mute and its enabled flag. Used by the
+// settings preview.
+export function previewCue(cue: AudioCue, settings: Settings): void {
+  const volume = Math.max(0, Math.min(100, cue.volume)) / 100;
+  if (cue.sound && !isBuiltInSound(cue.sound)) {
+    playWav(cue.sound, volume);
+    return;
+  }
+  const fallback = (settings.live_notifications?.sound_type as SoundId | undefined) ?? 'boop';
+  playSound(cue.sound ? (cue.sound as SoundId) : fallback, volume);
+}
+
+export function playCue(event: AudioCueEvent, settings: Settings): void {
+  if (isNotificationSoundMuted(settings)) return;
+  const cue = getAudioCue(settings, event);
+  if (!cue.enabled) return;
+  previewCue(cue, settings);
+}
+
+// Mentions arrive with chat history and in bursts; same backfill guard and
+// cooldown as highlight-phrase sounds.
+export function playCueThrottled(event: AudioCueEvent, settings: Settings, sentAtMs?: number | null): void {
+  if (isNotificationSoundMuted(settings) || !getAudioCue(settings, event).enabled) return;
+  if (!takeSoundSlot(`cue:${event}`, undefined, sentAtMs)) return;
+  previewCue(getAudioCue(settings, event), settings);
+}
@@ -0,0 +1,43 @@
+// Structured errors from Tauri commands. Migrated commands reject with a
+// `CommandError` (commands/error.rs in the backend); the rest still reject with
+// a plain string, which reads here as kind `other` with the string as message.
+
+export type CommandErrorKind =
+  | 'auth_expired'
+  | 'not_a_moderator'
+  | 'forbidden'
+  | 'not_found'
+  | 'offline'
+  | 'disabled'
+  | 'rate_limited'
+  | 'network'
+  | 'other';
+
+export interface CommandError {
+  kind: CommandErrorKind;
+  retryable: boolean;
+  user_message: string;
+  detail: string;
+}
+
+export function parseCommandError(err: unknown): CommandError {
+  if (err && typeof err === 'object' && 'kind' in err && 'user_message' in err) {
+    return err as CommandError;
+  }
+  const message = err instanceof Error ? err.message : String(err);
+  return { kind: 'other', retryable: false, user_message: message, detail: message };
+}
+
+// The text of any command rejection: the backend's wording for a
+// `CommandError`, the message itself otherwise. `String(err)` would read
+// "[object Object]" for the structured ones.
+export function commandErrorText(err: unknown): string {
+  return parseCommandError(err).user_message;
+}
+
+// `fallback` for errors that carry no more than the raw message, the
+// backend's own wording otherwise.
+export function commandErrorMessage(err: unknown, fallback: string): string {
+  const parsed = parseCommandError(err);
+  return parsed.kind === 'other' ? fallback : parsed.user_message;
+}
@@ -12,6 +12,7 @@ import {
 import { parseNukeArgs, executeNuke, executeUndo, isUserModeratorOf } from './nukeEngine';
 import { parseRemindCommand, fireReminderNow, formatReminderList, remindHelpLines } from './reminderEngine';
 import { recognizeNowPlaying, announceSong } from './songId';
+import { commandErrorText } from './commandError';
 
 // Build a TemplateContext from the current AppStore + supplied args. Centralized
 // so plain-text expansions (ChatWidget) and slash-command expansions (this
@@ -370,8 +371,7 @@ export const handleSlashCommand = async (
             await invoke('update_user_chat_color', { targetUserId: currentUser.user_id, color: colorValue });
             addToast(`Chat color changed to ${colorValue}`, 'success');
           } catch (err: unknown) {
-            const errMsg = err instanceof Error ? err.message : String(err);
-            addToast(`Failed to change color: ${errMsg}`, 'error');
+            addToast(`Failed to change color: ${commandErrorText(err)}`, 'error');
           }
           return true;
         }
@@ -676,8 +676,7 @@ export const handleSlashCommand = async (
             : 0;
           emitSystemMessage(`Emotes refreshed (${total} loaded).`);
         } catch (err: unknown) {
-          const errMsg = err instanceof Error ? err.message : String(err);
-          addToast(`/refresh failed: ${errMsg}`, 'error');
+          addToast(`/refresh failed: ${commandErrorText(err)}`, 'error');
         }
         return true;
       }
@@ -785,8 +784,7 @@ export const handleSlashCommand = async (
           );
           addToast(`/nuke: ${matchedMessages} message(s), ${affectedUsers} user(s)`, 'success');
         } catch (err: unknown) {
-          const errMsg = err instanceof Error ? err.message : String(err);
-          addToast(`/nuke failed: ${errMsg}`, 'error');
+          addToast(`/nuke failed: ${commandErrorText(err)}`, 'error');
         }
         return true;
       }
@@ -802,8 +800,7 @@ export const handleSlashCommand = async (
         try {
           await executeUndo(broadcasterLogin);
         } catch (err: unknown) {
-          const errMsg = err instanceof Error ? err.message : String(err);
-          addToast(`/undo failed: ${errMsg}`, 'error');
+          addToast(`/undo failed: ${commandErrorText(err)}`, 'error');
         }
         return true;
       }
@@ -824,8 +821,7 @@ export const handleSlashCommand = async (
           await invoke('ban_user', { broadcasterId, targetUserId, duration: null, reason: reason || null });
           addToast(`Banned user ID ${targetUserId}`, 'success');
         } catch (err: unknown) {
-          const errMsg = err instanceof Error ? err.message : String(err);
-          addToast(`Ban failed: ${errMsg}`, 'error');
+          addToast(`Ban failed: ${commandErrorText(err)}`, 'error');
         }
         return true;
       }
@@ -861,9 +857,8 @@ export const handleSlashCommand = async (
         return false;
     }
   } catch (err: unknown) {
-    const errMsg = err instanceof Error ? err.message : String(err);
     Logger.error(`[Command Handler] Failed to execute /${command}:`, err);
-    addToast(`Command failed: ${errMsg}`, 'error');
+    addToast(`Command failed: ${commandErrorText(err)}`, 'error');
     return true; // We handled it (it failed, but we shouldn't send it to chat directly)
   }
   
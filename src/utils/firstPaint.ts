@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/core';
+
+// Startup warm-ups (drops cache, cosmetics, badge feed) wait for the main
+// window's first painted frame so they don't compete with it. The first call
+// also tells Rust (`notify_first_paint`), which holds its own deferred startup
+// work (badge databases, drop campaigns) behind the same point and reports it
+// through `startup-progress` events.
+
+let painted: Promise<void> | null = null;
+
+/** Resolves once a frame has been presented. Two rAFs: the first callback runs
+ *  before the paint, the second after it. */
+export function afterFirstPaint(): Promise<void> {
+  if (!painted) {
+    painted = new Promise<void>((resolve) => {
+      requestAnimationFrame(() => requestAnimationFrame(() => resolve()));
+    }).then(() => {
+      invoke('notify_first_paint').catch(() => {});
+    });
+  }
+  return painted;
+}
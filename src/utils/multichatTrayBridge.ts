@@ -247,6 +247,40 @@ if (!isPopout && typeof window !== 'undefined') {
         },
       );
 
+      // System-wide hotkeys (Rust `services/global_hotkeys.rs`) and the OS
+      // media session (`services/media_session.rs`). Window and mining
+      // toggles are handled in Rust; the player ones land here. Stopping
+      // remembers the channel so play / toggle can restart it.
+      let lastStoppedChannel: string | null = null;
+      const setStreamPlaying = async (play: boolean | 'toggle') => {
+        const { useAppStore } = await import('../stores/AppStore');
+        const { currentStream, stopStream, startStream } = useAppStore.getState();
+        if (currentStream && play !== true) {
+          lastStoppedChannel = currentStream.user_login;
+          await stopStream();
+        } else if (!currentStream && play !== false && lastStoppedChannel) {
+          await startStream(lastStoppedChannel);
+        }
+      };
+
+      await listen<{ action: string }>('global-hotkey', async (event) => {
+        switch (event.payload.action) {
+          case 'toggle_mute': {
+            const { getPlayerControls } = await import('../keybindings/playerControls');
+            getPlayerControls()?.toggleMute();
+            break;
+          }
+          case 'toggle_stream':
+            await setStreamPlaying('toggle');
+            break;
+        }
+      });
+
+      await listen<{ action: string }>('media-session-control', async (event) => {
+        const { action } = event.payload;
+        await setStreamPlaying(action === 'play' ? true : action === 'toggle' ? 'toggle' : false);
+      });
+
       Logger.debug('[TrayBridge] tray listeners registered');
       // Tell any popout that just recreated this window (via ensureMainAlive) that
       // main's popout->main listeners are now live, so it can safely emit its
@@ -33,16 +33,21 @@ function getSharedAudioContext(): AudioContext | null {
   }
 }
 
-export function playSound(soundId: SoundId | undefined | null): void {
-  if (!soundId) return;
+// `volume` (0-1) scales the sound's own envelope; audio cues pass their
+// per-event volume through here.
+export function playSound(soundId: SoundId | undefined | null, volume = 1): void {
+  if (!soundId || volume <= 0) return;
   const ctx = getSharedAudioContext();
   if (!ctx) return;
 
   try {
     const oscillator = ctx.createOscillator();
     const gainNode = ctx.createGain();
+    const volumeNode = ctx.createGain();
+    volumeNode.gain.value = Math.min(1, volume);
     oscillator.connect(gainNode);
-    gainNode.connect(ctx.destination);
+    gainNode.connect(volumeNode);
+    volumeNode.connect(ctx.destination);
 
     const t = ctx.currentTime;
 
@@ -132,6 +137,23 @@ export interface SoundPlayOptions {
   sentAtMs?: number | null;
 }
 
+// Whether a throttled play for `key` may fire now; records the play when it
+// may. Shared with the audio-cue player's mention cue.
+export function takeSoundSlot(
+  key: string,
+  cooldownMs: number = DEFAULT_COOLDOWN_MS,
+  sentAtMs?: number | null,
+): boolean {
+  if (sentAtMs != null && Number.isFinite(sentAtMs) && Date.now() - sentAtMs > BACKFILL_SKIP_MS) {
+    return false;
+  }
+  const now = Date.now();
+  const last = lastPlayedAt.get(key) ?? 0;
+  if (now - last < cooldownMs) return false;
+  lastPlayedAt.set(key, now);
+  return true;
+}
+
 export function playSoundThrottled({
   key,
   soundId,
@@ -139,12 +161,6 @@ export function playSoundThrottled({
   sentAtMs,
 }: SoundPlayOptions): void {
   if (!soundId) return;
-  if (sentAtMs != null && Number.isFinite(sentAtMs) && Date.now() - sentAtMs > BACKFILL_SKIP_MS) {
-    return;
-  }
-  const now = Date.now();
-  const last = lastPlayedAt.get(key) ?? 0;
-  if (now - last < cooldownMs) return;
-  lastPlayedAt.set(key, now);
+  if (!takeSoundSlot(key, cooldownMs, sentAtMs)) return;
   playSound(soundId);
 }
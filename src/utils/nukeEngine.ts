@@ -14,6 +14,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { useAppStore } from '../stores/AppStore';
 import { useChatConnectionStore, injectSystemMessage } from '../stores/chatConnectionStore';
 import { Logger } from './logger';
+import { commandErrorText } from './commandError';
 import type { BackendChatMessage } from '../services/twitchChat';
 
 export type NukeAction =
@@ -143,11 +144,12 @@ function pickStructured(msg: string | BackendChatMessage): BackendChatMessage |
   return msg;
 }
 
+/** Resolves to the failure's text, or null when the action went through. */
 async function applyActionToUser(
   broadcasterId: string,
   affected: NukeAffected,
   action: NukeAction,
-): Promise<void> {
+): Promise<string | null> {
   try {
     if (action.kind === 'delete') {
       for (const msgId of affected.message_ids) {
@@ -170,7 +172,9 @@ async function applyActionToUser(
     }
   } catch (err) {
     Logger.error(`[nuke] action failed for ${affected.username}:`, err);
+    return commandErrorText(err);
   }
+  return null;
 }
 
 function describeAction(action: NukeAction): string {
@@ -229,7 +233,9 @@ export async function executeNuke(
   // Execute concurrently per user. Tauri handle invoke is async; the Rust side
   // serializes Helix-bound mod actions naturally, so we don't bother throttling
   // ourselves here. If 429s become a problem, batch in groups of N with a tick.
-  await Promise.all(affected.map((a) => applyActionToUser(broadcasterId, a, parsed.action)));
+  const failures = (
+    await Promise.all(affected.map((a) => applyActionToUser(broadcasterId, a, parsed.action)))
+  ).filter((e): e is string => e !== null);
 
   const record: NukeRecord = {
     ranAt: Date.now(),
@@ -264,7 +270,7 @@ export async function executeNuke(
     chKey,
     `/nuke pattern "${parsed.patternSource}" → ${describeAction(parsed.action)} on ${matchedMessages} message(s) from ${affected.length} user(s)${
       parsed.futureSeconds > 0 ? `. Future window: ${parsed.futureSeconds}s.` : '.'
-    }`,
+    }${failures.length ? ` ${failures.length} failed: ${failures[0]}` : ''}`,
   );
 
   return { matchedMessages, affectedUsers: affected.length };
@@ -335,15 +341,17 @@ export async function executeUndo(channel: string): Promise<void> {
   }
 
   let unbanned = 0;
+  let lastError: string | null = null;
   for (const a of record.affected) {
     try {
       await invoke('unban_user', { broadcasterId: record.broadcasterId, targetUserId: a.user_id });
       unbanned++;
     } catch (err) {
       Logger.error(`[undo] failed to unban ${a.username}:`, err);
+      lastError = commandErrorText(err);
     }
   }
-  injectSystemMessage(chKey, `/undo reversed nuke on "${record.parsed.patternSource}" — unbanned ${unbanned}/${record.affected.length} user(s).`);
+  injectSystemMessage(chKey, `/undo reversed nuke on "${record.parsed.patternSource}" — unbanned ${unbanned}/${record.affected.length} user(s).${lastError ? ` Last error: ${lastError}` : ''}`);
 }
 
 /**
@@ -1,4 +1,31 @@
-import { useEffect, useRef } from 'react';
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+
+/** Matches `STRETCH_FACTOR` in src-tauri/src/services/power_saver.rs. */
+const POWER_SAVER_STRETCH = 4;
+
+let powerSaverActive = false;
+let powerSaverListening = false;
+const powerSaverSubscribers = new Set<() => void>();
+
+/**
+ * Calls `onChange` whenever the backend's power saver starts or stops
+ * stretching polls (`power-saver-changed`). One Tauri listener is shared by
+ * every interval. Returns the unsubscribe function.
+ */
+function watchPowerSaver(onChange: () => void): () => void {
+  if (!powerSaverListening) {
+    powerSaverListening = true;
+    void listen<{ active: boolean }>('power-saver-changed', (event) => {
+      powerSaverActive = event.payload.active;
+      powerSaverSubscribers.forEach((fn) => fn());
+    });
+  }
+  powerSaverSubscribers.add(onChange);
+  return () => {
+    powerSaverSubscribers.delete(onChange);
+  };
+}
 
 /**
  * setInterval-style polling that skips ticks while the StreamNook window is
@@ -9,6 +36,10 @@ import { useEffect, useRef } from 'react';
  * Don't use it for things that need to run regardless of visibility (e.g.
  * background heartbeats Twitch needs to see — those should stay in Rust).
  *
+ * While the backend's power saver is active (window out of sight with the
+ * setting on, or a watched game running) the interval stretches by the same
+ * factor the backend applies to its own polls.
+ *
  * `fn` is captured by ref so its identity doesn't need to be stable across
  * renders, which lets callers pass an inline async function without forcing
  * a wrapping useCallback.
@@ -16,6 +47,9 @@ import { useEffect, useRef } from 'react';
 export function useVisibleInterval(fn: () => void | Promise<void>, ms: number) {
   const fnRef = useRef(fn);
   fnRef.current = fn;
+  const [stretched, setStretched] = useState(powerSaverActive);
+
+  useEffect(() => watchPowerSaver(() => setStretched(powerSaverActive)), []);
 
   useEffect(() => {
     let cancelled = false;
@@ -28,7 +62,7 @@ export function useVisibleInterval(fn: () => void | Promise<void>, ms: number) {
       void fnRef.current();
     };
 
-    const id = setInterval(run, ms);
+    const id = setInterval(run, stretched ? ms * POWER_SAVER_STRETCH : ms);
 
     // When the window becomes visible after being hidden, fire immediately
     // so the UI doesn't have to wait up to `ms` for the next tick.
@@ -42,5 +76,5 @@ export function useVisibleInterval(fn: () => void | Promise<void>, ms: number) {
       clearInterval(id);
       document.removeEventListener('visibilitychange', onVisibilityChange);
     };
-  }, [ms]);
+  }, [ms, stretched]);
 }